@@ -1,9 +1,11 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use std::collections::BTreeMap;
+
 use meilisearch_types::batches::BatchId;
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::keys::Key;
+use meilisearch_types::keys::{Key, KeyId};
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
 use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
@@ -17,7 +19,7 @@ mod reader;
 mod writer;
 
 pub use error::Error;
-pub use reader::{DumpReader, UpdateFile};
+pub use reader::{DumpIndexReader, DumpReader, UpdateFile};
 pub use writer::DumpWriter;
 
 const CURRENT_DUMP_VERSION: Version = Version::V6;
@@ -69,6 +71,12 @@ pub struct TaskDump {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_by: Option<TaskId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<TaskId>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_key: Option<KeyId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Details>,
@@ -101,6 +109,15 @@ pub enum KindDump {
         documents_count: u64,
         allow_index_creation: bool,
     },
+    DocumentImportUrl {
+        primary_key: Option<String>,
+        method: IndexDocumentsMethod,
+        url: String,
+        csv_delimiter: Option<u8>,
+        documents_count: u64,
+        allow_index_creation: bool,
+        replace_all_documents: bool,
+    },
     DocumentDeletion {
         documents_ids: Vec<String>,
     },
@@ -127,6 +144,8 @@ pub enum KindDump {
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
+        #[serde(default, with = "time::serde::rfc3339::option")]
+        execute_at: Option<OffsetDateTime>,
     },
     TaskCancelation {
         query: String,
@@ -139,6 +158,27 @@ pub enum KindDump {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        /// The indexes to dump, all of them when `None`.
+        #[serde(default)]
+        index_uids: Option<Vec<String>>,
+        /// Whether the task queue and the batches are left out of the dump.
+        #[serde(default)]
+        skip_tasks: bool,
+    },
+    /// The uploaded archive is not part of the dump.
+    DumpImport {
+        url: Option<String>,
+        #[serde(default)]
+        index_renames: BTreeMap<String, String>,
+    },
+    PointInTimeRestore {
+        index_uids: Option<Vec<String>>,
+        #[serde(with = "time::serde::rfc3339")]
+        restore_at: OffsetDateTime,
+    },
+    IndexRestore {
+        snapshot: Option<String>,
+        dump_uid: Option<String>,
     },
     SnapshotCreation,
     UpgradeDatabase {
@@ -155,6 +195,9 @@ impl From<Task> for TaskDump {
             status: task.status,
             kind: task.kind.into(),
             canceled_by: task.canceled_by,
+            depends_on: task.depends_on,
+            labels: task.labels,
+            quota_key: task.quota_key,
             details: task.details,
             error: task.error,
             enqueued_at: task.enqueued_at,
@@ -179,6 +222,24 @@ impl From<KindWithContent> for KindDump {
                 documents_count,
                 allow_index_creation,
             },
+            KindWithContent::DocumentImportUrl {
+                primary_key,
+                method,
+                url,
+                csv_delimiter,
+                documents_count,
+                allow_index_creation,
+                replace_all_documents,
+                ..
+            } => KindDump::DocumentImportUrl {
+                primary_key,
+                method,
+                url,
+                csv_delimiter,
+                documents_count,
+                allow_index_creation,
+                replace_all_documents,
+            },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
             }
@@ -202,15 +263,26 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 KindDump::IndexUpdate { primary_key }
             }
-            KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
+            KindWithContent::IndexSwap { swaps, execute_at } => {
+                KindDump::IndexSwap { swaps, execute_at }
+            }
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
             }
             KindWithContent::TaskDeletion { query, tasks } => {
                 KindDump::TasksDeletion { query, tasks }
             }
-            KindWithContent::DumpCreation { keys, instance_uid } => {
-                KindDump::DumpCreation { keys, instance_uid }
+            KindWithContent::DumpCreation { keys, instance_uid, index_uids, skip_tasks } => {
+                KindDump::DumpCreation { keys, instance_uid, index_uids, skip_tasks }
+            }
+            KindWithContent::DumpImport { url, index_renames, .. } => {
+                KindDump::DumpImport { url, index_renames }
+            }
+            KindWithContent::PointInTimeRestore { index_uids, restore_at } => {
+                KindDump::PointInTimeRestore { index_uids, restore_at }
+            }
+            KindWithContent::IndexRestore { snapshot, dump_uid, .. } => {
+                KindDump::IndexRestore { snapshot, dump_uid }
             }
             KindWithContent::SnapshotCreation => KindDump::SnapshotCreation,
             KindWithContent::UpgradeDatabase { from: version } => {
@@ -230,7 +302,7 @@ pub(crate) mod test {
     use maplit::{btreemap, btreeset};
     use meilisearch_types::batches::{Batch, BatchEnqueuedAt, BatchStats};
     use meilisearch_types::facet_values_sort::FacetValuesSort;
-    use meilisearch_types::features::{Network, Remote, RuntimeTogglableFeatures};
+    use meilisearch_types::features::{IndexAliases, Network, Remote, RuntimeTogglableFeatures};
     use meilisearch_types::index_uid_pattern::IndexUidPattern;
     use meilisearch_types::keys::{Action, Key};
     use meilisearch_types::milli::update::Setting;
@@ -305,6 +377,14 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            sensitive_matching: Setting::NotSet,
+            reranker: Setting::NotSet,
+            flattening: Setting::NotSet,
+            ingest_function: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            skip_unchanged_documents: Setting::NotSet,
+            attachments: Setting::NotSet,
+            max_document_revisions: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -326,6 +406,8 @@ pub(crate) mod test {
                 index_uids: maplit::btreemap! { "doggo".to_string() => 1 },
                 progress_trace: Default::default(),
                 write_channel_congestion: None,
+                resource_usage: None,
+                embedder_usage: Default::default(),
             },
             enqueued_at: Some(BatchEnqueuedAt {
                 earliest: datetime!(2022-11-11 0:00 UTC),
@@ -351,6 +433,9 @@ pub(crate) mod test {
                         documents_count: 12,
                     },
                     canceled_by: None,
+                    depends_on: None,
+                    labels: Default::default(),
+                    quota_key: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
@@ -375,6 +460,9 @@ pub(crate) mod test {
                         documents_count: 2,
                     },
                     canceled_by: None,
+                    depends_on: None,
+                    labels: Default::default(),
+                    quota_key: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
@@ -397,6 +485,9 @@ pub(crate) mod test {
                     status: Status::Enqueued,
                     kind: KindDump::IndexDeletion,
                     canceled_by: None,
+                    depends_on: None,
+                    labels: Default::default(),
+                    quota_key: None,
                     details: None,
                     error: None,
                     enqueued_at: datetime!(2022-11-15 0:00 UTC),
@@ -417,8 +508,13 @@ pub(crate) mod test {
                 actions: vec![Action::DocumentsAll],
                 indexes: vec![IndexUidPattern::from_str("doggos").unwrap()],
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
+                rate_limit: None,
+                quotas: None,
+                filter: None,
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
+                rotations: 0,
+                previous_key_expires_at: None,
             },
             Key {
                 description: Some(S("The master key for everything and even the doggos")),
@@ -427,8 +523,13 @@ pub(crate) mod test {
                 actions: vec![Action::All],
                 indexes: vec![IndexUidPattern::all()],
                 expires_at: None,
+                rate_limit: None,
+                quotas: None,
+                filter: None,
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
+                rotations: 0,
+                previous_key_expires_at: None,
             },
             Key {
                 description: Some(S("The useless key to for nothing nor the doggos")),
@@ -437,8 +538,13 @@ pub(crate) mod test {
                 actions: vec![],
                 indexes: vec![],
                 expires_at: None,
+                rate_limit: None,
+                quotas: None,
+                filter: None,
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
+                rotations: 0,
+                previous_key_expires_at: None,
             },
         ]
     }
@@ -499,6 +605,10 @@ pub(crate) mod test {
         let network = create_test_network();
         dump.create_network(network).unwrap();
 
+        // ========== index aliases
+        let index_aliases = create_test_index_aliases();
+        dump.create_index_aliases(index_aliases).unwrap();
+
         // create the dump
         let mut file = tempfile::tempfile().unwrap();
         dump.persist_to(&mut file).unwrap();
@@ -518,6 +628,10 @@ pub(crate) mod test {
         }
     }
 
+    fn create_test_index_aliases() -> IndexAliases {
+        btreemap! { S("dogs") => vec![S("doggos"), S("puppies")] }
+    }
+
     #[test]
     fn test_creating_and_read_dump() {
         let mut file = create_test_dump();
@@ -570,5 +684,9 @@ pub(crate) mod test {
         // ==== checking the network
         let expected = create_test_network();
         assert_eq!(&expected, dump.network().unwrap().unwrap());
+
+        // ==== checking the index aliases
+        let expected = create_test_index_aliases();
+        assert_eq!(&expected, dump.index_aliases().unwrap().unwrap());
     }
 }
@@ -124,10 +124,18 @@ impl CompatV5ToV6 {
                         },
                         v5::tasks::TaskContent::Dump { uid: _ } => {
                             // in v6 we compute the dump_uid from the started_at processing time
-                            v6::Kind::DumpCreation { keys: keys.clone(), instance_uid }
+                            v6::Kind::DumpCreation {
+                                keys: keys.clone(),
+                                instance_uid,
+                                index_uids: None,
+                                skip_tasks: false,
+                            }
                         }
                     },
                     canceled_by: None,
+                    depends_on: None,
+                    labels: Default::default(),
+                    quota_key: None,
                     details: task_view.details.map(|details| match details {
                         v5::Details::DocumentAddition { received_documents, indexed_documents } => {
                             v6::Details::DocumentAdditionOrUpdate {
@@ -187,8 +195,13 @@ impl CompatV5ToV6 {
                     })
                     .collect(),
                 expires_at: key.expires_at,
+                rate_limit: None,
+                quotas: None,
+                filter: None,
                 created_at: key.created_at,
                 updated_at: key.updated_at,
+                rotations: 0,
+                previous_key_expires_at: None,
             })
         })))
     }
@@ -397,6 +410,14 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            sensitive_matching: v6::Setting::NotSet,
+            reranker: v6::Setting::NotSet,
+            flattening: v6::Setting::NotSet,
+            ingest_function: v6::Setting::NotSet,
+            document_schema: v6::Setting::NotSet,
+            skip_unchanged_documents: v6::Setting::NotSet,
+            attachments: v6::Setting::NotSet,
+            max_document_revisions: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
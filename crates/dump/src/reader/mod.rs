@@ -129,6 +129,14 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.network(),
         }
     }
+
+    pub fn index_aliases(&self) -> Result<Option<&v6::IndexAliases>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.index_aliases()),
+            // Index aliases did not exist before the v6 dumps.
+            DumpReader::Compat(_compat) => Ok(None),
+        }
+    }
 }
 
 impl From<V6Reader> for DumpReader {
@@ -189,6 +197,14 @@ impl DumpIndexReader {
             DumpIndexReader::Compat(compat) => compat.settings(),
         }
     }
+
+    /// The remote source of the index, the older dumps don't have any.
+    pub fn source(&self) -> Option<&v6::IndexSource> {
+        match self {
+            DumpIndexReader::Current(v6) => v6.source(),
+            DumpIndexReader::Compat(_) => None,
+        }
+    }
 }
 
 impl From<V6IndexReader> for DumpIndexReader {
@@ -23,6 +23,8 @@ pub type Batch = meilisearch_types::batches::Batch;
 pub type Key = meilisearch_types::keys::Key;
 pub type RuntimeTogglableFeatures = meilisearch_types::features::RuntimeTogglableFeatures;
 pub type Network = meilisearch_types::features::Network;
+pub type IndexAliases = meilisearch_types::features::IndexAliases;
+pub type IndexSource = meilisearch_types::sources::IndexSource;
 
 // ===== Other types to clarify the code of the compat module
 // everything related to the tasks
@@ -57,6 +59,7 @@ pub struct V6Reader {
     keys: BufReader<File>,
     features: Option<RuntimeTogglableFeatures>,
     network: Option<Network>,
+    index_aliases: Option<IndexAliases>,
 }
 
 impl V6Reader {
@@ -108,6 +111,15 @@ impl V6Reader {
             None
         };
 
+        let index_aliases = match fs::read(dump.path().join("index-aliases.json")) {
+            Ok(index_aliases_file) => Some(serde_json::from_reader(&*index_aliases_file)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                debug!("`index-aliases.json` not found in dump");
+                None
+            }
+            Err(error) => return Err(error.into()),
+        };
+
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
@@ -116,6 +128,7 @@ impl V6Reader {
             keys: BufReader::new(File::open(dump.path().join("keys.jsonl"))?),
             features,
             network,
+            index_aliases,
             dump,
         })
     }
@@ -199,6 +212,10 @@ impl V6Reader {
     pub fn network(&self) -> Option<&Network> {
         self.network.as_ref()
     }
+
+    pub fn index_aliases(&self) -> Option<&IndexAliases> {
+        self.index_aliases.as_ref()
+    }
 }
 
 pub struct UpdateFile {
@@ -229,16 +246,24 @@ pub struct V6IndexReader {
     metadata: IndexMetadata,
     documents: BufReader<File>,
     settings: BufReader<File>,
+    source: Option<IndexSource>,
 }
 
 impl V6IndexReader {
     pub fn new(_name: String, path: &Path) -> Result<Self> {
         let metadata = File::open(path.join("metadata.json"))?;
+        // Only the indexes refreshed from a remote source have one.
+        let source = match fs::read(path.join("source.json")) {
+            Ok(source) => Some(serde_json::from_slice(&source)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
 
         let ret = V6IndexReader {
             metadata: serde_json::from_reader(metadata)?,
             documents: BufReader::new(File::open(path.join("documents.jsonl"))?),
             settings: BufReader::new(File::open(path.join("settings.json"))?),
+            source,
         };
 
         Ok(ret)
@@ -254,6 +279,10 @@ impl V6IndexReader {
             .map(|line| -> Result<_> { Ok(serde_json::from_str(&line?)?) }))
     }
 
+    pub fn source(&self) -> Option<&IndexSource> {
+        self.source.as_ref()
+    }
+
     pub fn settings(&mut self) -> Result<Settings<Checked>> {
         let mut settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
         patch_embedders(&mut settings);
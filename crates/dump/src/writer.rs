@@ -5,9 +5,10 @@ use std::path::PathBuf;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use meilisearch_types::batches::Batch;
-use meilisearch_types::features::{Network, RuntimeTogglableFeatures};
+use meilisearch_types::features::{IndexAliases, Network, RuntimeTogglableFeatures};
 use meilisearch_types::keys::Key;
 use meilisearch_types::settings::{Checked, Settings};
+use meilisearch_types::sources::IndexSource;
 use serde_json::{Map, Value};
 use tempfile::TempDir;
 use time::OffsetDateTime;
@@ -70,6 +71,13 @@ impl DumpWriter {
         Ok(std::fs::write(self.dir.path().join("network.json"), serde_json::to_string(&network)?)?)
     }
 
+    pub fn create_index_aliases(&self, index_aliases: IndexAliases) -> Result<()> {
+        Ok(std::fs::write(
+            self.dir.path().join("index-aliases.json"),
+            serde_json::to_string(&index_aliases)?,
+        )?)
+    }
+
     pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
         let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
         let mut tar_encoder = tar::Builder::new(gz_encoder);
@@ -190,6 +198,7 @@ impl UpdateFile {
 }
 
 pub struct IndexWriter {
+    path: PathBuf,
     documents: BufWriter<File>,
     settings: File,
 }
@@ -204,7 +213,7 @@ impl IndexWriter {
         let documents = File::create(path.join("documents.jsonl"))?;
         let settings = File::create(path.join("settings.json"))?;
 
-        Ok(IndexWriter { documents: BufWriter::new(documents), settings })
+        Ok(IndexWriter { path, documents: BufWriter::new(documents), settings })
     }
 
     pub fn push_document(&mut self, document: &Map<String, Value>) -> Result<()> {
@@ -218,6 +227,11 @@ impl IndexWriter {
         Ok(())
     }
 
+    /// Writes the remote source the documents of the index are refreshed from.
+    pub fn source(&mut self, source: &IndexSource) -> Result<()> {
+        Ok(std::fs::write(self.path.join("source.json"), serde_json::to_string(source)?)?)
+    }
+
     pub fn settings(mut self, settings: &Settings<Checked>) -> Result<()> {
         self.settings.write_all(&serde_json::to_vec(&settings)?)?;
         Ok(())
@@ -328,6 +342,7 @@ pub(crate) mod test {
         │    │    └---- 1.jsonl
         │    └---- queue.jsonl
         ├---- experimental-features.json
+        ├---- index-aliases.json
         ├---- instance_uid.uuid
         ├---- keys.jsonl
         ├---- metadata.json
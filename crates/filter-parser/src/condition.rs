@@ -12,6 +12,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
+use crate::value::word_exact;
 use crate::{parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,6 +23,7 @@ pub enum Condition<'a> {
     NotEqual(Token<'a>),
     Null,
     Empty,
+    EmptyArray,
     Exists,
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
@@ -39,6 +41,7 @@ impl Condition<'_> {
             Condition::NotEqual(_) => "!=",
             Condition::Null => "IS NULL",
             Condition::Empty => "IS EMPTY",
+            Condition::EmptyArray => "IS EMPTY ARRAY",
             Condition::Exists => "EXISTS",
             Condition::LowerThan(_) => "<",
             Condition::LowerThanOrEqual(_) => "<=",
@@ -99,6 +102,34 @@ pub fn parse_is_not_empty(input: Span) -> IResult<FilterCondition> {
     Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Empty }))))
 }
 
+/// empty_array    = value "IS" WS+ "EMPTY" WS+ "ARRAY"
+pub fn parse_is_empty_array(input: Span) -> IResult<FilterCondition> {
+    let (input, key) = parse_value(input)?;
+
+    let (input, _) =
+        tuple((tag("IS"), multispace1, tag("EMPTY"), multispace1, word_exact("ARRAY")))(input)?;
+    Ok((input, FilterCondition::Condition { fid: key, op: EmptyArray }))
+}
+
+/// empty_array    = value "IS" WS+ "NOT" WS+ "EMPTY" WS+ "ARRAY"
+pub fn parse_is_not_empty_array(input: Span) -> IResult<FilterCondition> {
+    let (input, key) = parse_value(input)?;
+
+    let (input, _) = tuple((
+        tag("IS"),
+        multispace1,
+        tag("NOT"),
+        multispace1,
+        tag("EMPTY"),
+        multispace1,
+        word_exact("ARRAY"),
+    ))(input)?;
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: EmptyArray })),
+    ))
+}
+
 /// exist          = value "EXISTS"
 pub fn parse_exists(input: Span) -> IResult<FilterCondition> {
     let (input, key) = terminated(parse_value, tag("EXISTS"))(input)?;
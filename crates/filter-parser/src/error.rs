@@ -46,6 +46,24 @@ pub fn cut_with_err<'a, O>(
 pub struct Error<'a> {
     context: Span<'a>,
     kind: ErrorKind<'a>,
+    suggestions: Vec<String>,
+}
+
+/// Structured information about the location of an [`Error`] in the filter,
+/// meant to be used to display the error inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorDetails {
+    /// The line of the filter on which the error is located, starting at 1.
+    pub line: u32,
+    /// The column of the first character of the offending token, starting at 1.
+    pub column: usize,
+    /// The offending token, it is empty when a value was expected but nothing was found.
+    pub token: String,
+    /// The line of the filter on which the error is located followed by a
+    /// line of carets pointing at the offending token.
+    pub snippet: String,
+    /// The closest valid operators, attributes or delimiters to use instead of the offending token.
+    pub suggestions: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -59,6 +77,8 @@ pub enum ErrorKind<'a> {
     ReservedGeo(&'a str),
     GeoRadius,
     GeoBoundingBox,
+    GeoContains,
+    GeoIntersects,
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
     InvalidPrimary,
@@ -88,13 +108,56 @@ impl<'a> Error<'a> {
     }
 
     pub fn new_from_kind(context: Span<'a>, kind: ErrorKind<'a>) -> Self {
-        Self { context, kind }
+        Self { context, kind, suggestions: Vec::new() }
     }
 
     pub fn new_from_external(context: Span<'a>, error: impl std::error::Error) -> Self {
         Self::new_from_kind(context, ErrorKind::External(error.to_string()))
     }
 
+    /// Adds suggestions that cannot be inferred by the parser, e.g. the attributes that are
+    /// close to a non-filterable attribute.
+    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+
+    pub fn details(&self) -> ErrorDetails {
+        let line = self.context.location_line();
+        let column = self.context.get_utf8_column();
+        // The offending token is limited to the line where the error starts.
+        let token = self.context.fragment().lines().next().unwrap_or_default().trim_end();
+
+        let source_line = self.context.extra.lines().nth(line as usize - 1).unwrap_or_default();
+        let snippet = format!(
+            "{source_line}\n{}{}",
+            " ".repeat(column - 1),
+            "^".repeat(token.chars().count().max(1))
+        );
+
+        let mut suggestions = match &self.kind {
+            ErrorKind::InvalidPrimary => token
+                .split_whitespace()
+                .skip(1)
+                .flat_map(|word| nearest_candidates(word, OPERATORS.iter().copied()))
+                .take(1)
+                .collect(),
+            ErrorKind::ExpectedEof => vec!["AND".to_string(), "OR".to_string()],
+            ErrorKind::MissingClosingDelimiter(c) => vec![c.to_string()],
+            ErrorKind::InOpeningBracket => vec!["[".to_string()],
+            ErrorKind::InClosingBracket => vec!["]".to_string()],
+            ErrorKind::ReservedKeyword(word) => vec![format!("\"{word}\"")],
+            ErrorKind::ExpectedValue(ExpectedValueKind::ReservedKeyword)
+            | ErrorKind::InExpectedValue(ExpectedValueKind::ReservedKeyword) => {
+                vec![format!("\"{token}\"")]
+            }
+            _ => Vec::new(),
+        };
+        suggestions.extend(self.suggestions.iter().cloned());
+
+        ErrorDetails { line, column, token: token.to_string(), snippet, suggestions }
+    }
+
     pub fn char(self) -> char {
         match self.kind {
             ErrorKind::Char(c) => c,
@@ -109,7 +172,7 @@ impl<'a> ParseError<Span<'a>> for Error<'a> {
             error::ErrorKind::Eof => ErrorKind::ExpectedEof,
             kind => ErrorKind::InternalError(kind),
         };
-        Self { context: input, kind }
+        Self { context: input, kind, suggestions: Vec::new() }
     }
 
     fn append(_input: Span<'a>, _kind: error::ErrorKind, other: Self) -> Self {
@@ -117,10 +180,85 @@ impl<'a> ParseError<Span<'a>> for Error<'a> {
     }
 
     fn from_char(input: Span<'a>, c: char) -> Self {
-        Self { context: input, kind: ErrorKind::Char(c) }
+        Self { context: input, kind: ErrorKind::Char(c), suggestions: Vec::new() }
     }
 }
 
+/// All the operators supported by the filter syntax, in the order they are displayed in errors.
+const OPERATORS: &[&str] = &[
+    "=",
+    "!=",
+    ">=",
+    ">",
+    "<=",
+    "<",
+    "IN",
+    "NOT IN",
+    "TO",
+    "EXISTS",
+    "NOT EXISTS",
+    "IS NULL",
+    "IS NOT NULL",
+    "IS EMPTY",
+    "IS NOT EMPTY",
+    "IS EMPTY ARRAY",
+    "IS NOT EMPTY ARRAY",
+    "CONTAINS",
+    "NOT CONTAINS",
+    "STARTS WITH",
+    "NOT STARTS WITH",
+    "_geoRadius",
+    "_geoBoundingBox",
+    "_geoContains",
+    "_geoIntersects",
+];
+
+/// Returns the candidates that are the closest to `word`, ignoring the case and the whitespaces.
+///
+/// Only the candidates that are within a small edit distance of `word` are returned,
+/// in the order they are given.
+pub fn nearest_candidates<'c>(
+    word: &str,
+    candidates: impl IntoIterator<Item = &'c str>,
+) -> Vec<String> {
+    let normalize = |s: &str| -> Vec<char> {
+        s.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+    };
+    let word = normalize(word);
+    let max_distance = if word.len() <= 4 { 1 } else { 2 };
+
+    let mut best = None;
+    let mut nearest = Vec::new();
+    for candidate in candidates {
+        let distance = levenshtein(&word, &normalize(candidate));
+        if distance > max_distance || distance >= word.len() {
+            continue;
+        }
+        match best {
+            Some(best_distance) if distance > best_distance => (),
+            Some(best_distance) if distance == best_distance => nearest.push(candidate.to_string()),
+            _ => {
+                best = Some(distance);
+                nearest = vec![candidate.to_string()];
+            }
+        }
+    }
+    nearest
+}
+
+fn levenshtein(left: &[char], right: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, l) in left.iter().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, r) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(l != r);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
 impl<'a> Display for Error<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let input = self.context.fragment();
@@ -146,7 +284,7 @@ impl<'a> Display for Error<'a> {
             }
             ErrorKind::InvalidPrimary => {
                 let text = if input.trim().is_empty() { "but instead got nothing.".to_string() } else { format!("at `{}`.", escaped_input) };
-                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` {}", text)?
+                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` {}", text)?
             }
             ErrorKind::InvalidEscapedNumber => {
                 writeln!(f, "Found an invalid escaped sequence number: `{}`.", escaped_input)?
@@ -155,11 +293,17 @@ impl<'a> Display for Error<'a> {
                 writeln!(f, "Found unexpected characters at the end of the filter: `{}`. You probably forgot an `OR` or an `AND` rule.", escaped_input)?
             }
             ErrorKind::GeoRadius => {
-                writeln!(f, "The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.")?
+                writeln!(f, "The `_geoRadius` filter expects three or four arguments: `_geoRadius(latitude, longitude, radius)` or `_geoRadius(latitude, longitude, minRadius, maxRadius)`.")?
             }
             ErrorKind::GeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter expects two pairs of arguments: `_geoBoundingBox([latitude, longitude], [latitude, longitude])`.")?
             }
+            ErrorKind::GeoContains => {
+                writeln!(f, "The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.")?
+            }
+            ErrorKind::GeoIntersects => {
+                writeln!(f, "The `_geoIntersects` filter expects two pairs of arguments: `_geoIntersects([latitude, longitude], [latitude, longitude])`.")?
+            }
             ErrorKind::ReservedGeo(name) => {
                 writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.", name.escape_debug())?
             }
@@ -9,7 +9,9 @@
 //! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+//!                  where `value` can be suffixed with `.length` to compare the number of values of a field
 //! exists         = value "EXISTS"
+//! empty_array    = value "IS" WS+ ("NOT" WS+)? "EMPTY" WS+ "ARRAY"
 //! not_exists     = value "NOT" WS+ "EXISTS"
 //! to             = value value "TO" WS+ value
 //! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
@@ -17,8 +19,10 @@
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
 //! word           = (alphanumeric | _ | - | .)+
-//! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
+//! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ("," float WS*)? ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoContains    = "_geoContains(" WS* float WS* "," WS* float WS* ")"
+//! geoIntersects  = "_geoIntersects([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -48,11 +52,12 @@ use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
-    parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
-    parse_is_null, parse_not_contains, parse_not_exists, parse_not_starts_with, parse_starts_with,
+    parse_contains, parse_exists, parse_is_empty, parse_is_empty_array, parse_is_not_empty,
+    parse_is_not_empty_array, parse_is_not_null, parse_is_null, parse_not_contains,
+    parse_not_exists, parse_not_starts_with, parse_starts_with,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
-pub use error::{Error, ErrorKind};
+pub use error::{nearest_candidates, Error, ErrorDetails, ErrorKind};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{char, multispace0};
@@ -143,8 +148,10 @@ pub enum FilterCondition<'a> {
     In { fid: Token<'a>, els: Vec<Token<'a>> },
     Or(Vec<Self>),
     And(Vec<Self>),
-    GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
+    GeoLowerThan { point: [Token<'a>; 2], min_radius: Option<Token<'a>>, radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoJsonContains { point: [Token<'a>; 2] },
+    GeoJsonIntersects { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
 }
 
 pub enum TraversedElement<'a> {
@@ -162,6 +169,7 @@ impl<'a> FilterCondition<'a> {
                 | Condition::NotEqual(_)
                 | Condition::Null
                 | Condition::Empty
+                | Condition::EmptyArray
                 | Condition::Exists
                 | Condition::LowerThan(_)
                 | Condition::LowerThanOrEqual(_)
@@ -175,6 +183,8 @@ impl<'a> FilterCondition<'a> {
             }
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
+            | FilterCondition::GeoJsonContains { .. }
+            | FilterCondition::GeoJsonIntersects { .. }
             | FilterCondition::In { .. } => None,
         }
     }
@@ -367,7 +377,7 @@ fn parse_not(input: Span, depth: usize) -> IResult<FilterCondition> {
     ))(input)
 }
 
-/// geoRadius      = WS* "_geoRadius(float WS* "," WS* float WS* "," WS* float)
+/// geoRadius      = WS* "_geoRadius(float WS* "," WS* float WS* "," WS* float (WS* "," WS* float)?)
 /// If we parse `_geoRadius` we MUST parse the rest of the expression.
 fn parse_geo_radius(input: Span) -> IResult<FilterCondition> {
     // we want to allow space BEFORE the _geoRadius but not after
@@ -380,13 +390,18 @@ fn parse_geo_radius(input: Span) -> IResult<FilterCondition> {
 
     let (input, args) = parsed?;
 
-    if args.len() != 3 {
-        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoRadius)));
-    }
-
-    let res = FilterCondition::GeoLowerThan {
-        point: [args[0].into(), args[1].into()],
-        radius: args[2].into(),
+    let res = match args.as_slice() {
+        [lat, lng, radius] => FilterCondition::GeoLowerThan {
+            point: [(*lat).into(), (*lng).into()],
+            min_radius: None,
+            radius: (*radius).into(),
+        },
+        [lat, lng, min_radius, max_radius] => FilterCondition::GeoLowerThan {
+            point: [(*lat).into(), (*lng).into()],
+            min_radius: Some((*min_radius).into()),
+            radius: (*max_radius).into(),
+        },
+        _ => return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoRadius))),
     };
     Ok((input, res))
 }
@@ -422,6 +437,57 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoContains      = WS* "_geoContains(float WS* "," WS* float WS* ")"
+/// If we parse `_geoContains` we MUST parse the rest of the expression.
+fn parse_geo_contains(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoContains but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoContains"))),
+        // if we were able to parse `_geoContains` and can't parse the rest of the input we return a failure
+        cut(delimited(char('('), separated_list1(tag(","), ws(recognize_float)), char(')'))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoContains)));
+
+    let (input, args) = parsed?;
+
+    let res = match args.as_slice() {
+        [lat, lng] => FilterCondition::GeoJsonContains { point: [(*lat).into(), (*lng).into()] },
+        _ => return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoContains))),
+    };
+    Ok((input, res))
+}
+
+/// geoIntersects      = WS* "_geoIntersects([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "]")
+/// If we parse `_geoIntersects` we MUST parse the rest of the expression.
+fn parse_geo_intersects(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoIntersects but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoIntersects"))),
+        // if we were able to parse `_geoIntersects` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+
+    let (input, args) = parsed?;
+
+    if args.len() != 2 || args[0].len() != 2 || args[1].len() != 2 {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+    }
+
+    let res = FilterCondition::GeoJsonIntersects {
+        top_right_point: [args[0][0].into(), args[0][1].into()],
+        bottom_left_point: [args[1][0].into(), args[1][1].into()],
+    };
+    Ok((input, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -477,7 +543,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoContains | geoIntersects | condition | exists | not_exists | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -491,15 +557,14 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
                 Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
             }),
         ),
-        parse_geo_radius,
-        parse_geo_bounding_box,
+        alt((parse_geo_radius, parse_geo_bounding_box, parse_geo_contains, parse_geo_intersects)),
         parse_in,
         parse_not_in,
         parse_condition,
         parse_is_null,
         parse_is_not_null,
-        parse_is_empty,
-        parse_is_not_empty,
+        // `IS EMPTY ARRAY` must be tried before `IS EMPTY` which would leave `ARRAY` unparsed
+        alt((parse_is_empty_array, parse_is_not_empty_array, parse_is_empty, parse_is_not_empty)),
         parse_exists,
         parse_not_exists,
         parse_to,
@@ -557,9 +622,12 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                 }
                 write!(f, "]")
             }
-            FilterCondition::GeoLowerThan { point, radius } => {
+            FilterCondition::GeoLowerThan { point, min_radius: None, radius } => {
                 write!(f, "_geoRadius({}, {}, {})", point[0], point[1], radius)
             }
+            FilterCondition::GeoLowerThan { point, min_radius: Some(min_radius), radius } => {
+                write!(f, "_geoRadius({}, {}, {}, {})", point[0], point[1], min_radius, radius)
+            }
             FilterCondition::GeoBoundingBox {
                 top_right_point: top_left_point,
                 bottom_left_point: bottom_right_point,
@@ -573,6 +641,19 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoJsonContains { point } => {
+                write!(f, "_geoContains({}, {})", point[0], point[1])
+            }
+            FilterCondition::GeoJsonIntersects { top_right_point, bottom_left_point } => {
+                write!(
+                    f,
+                    "_geoIntersects([{}, {}], [{}, {}])",
+                    top_right_point[0],
+                    top_right_point[1],
+                    bottom_left_point[0],
+                    bottom_left_point[1]
+                )
+            }
         }
     }
 }
@@ -585,6 +666,7 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::NotEqual(token) => write!(f, "!= {token}"),
             Condition::Null => write!(f, "IS NULL"),
             Condition::Empty => write!(f, "IS EMPTY"),
+            Condition::EmptyArray => write!(f, "IS EMPTY ARRAY"),
             Condition::Exists => write!(f, "EXISTS"),
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
@@ -690,6 +772,17 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT subscribers IS NOT EMPTY"), @"{subscribers} IS EMPTY");
         insta::assert_snapshot!(p("subscribers  IS   NOT   EMPTY"), @"NOT ({subscribers} IS EMPTY)");
 
+        // Test EMPTY ARRAY + NOT EMPTY ARRAY
+        insta::assert_snapshot!(p("subscribers IS EMPTY ARRAY"), @"{subscribers} IS EMPTY ARRAY");
+        insta::assert_snapshot!(p("NOT subscribers IS EMPTY ARRAY"), @"NOT ({subscribers} IS EMPTY ARRAY)");
+        insta::assert_snapshot!(p("subscribers IS NOT EMPTY ARRAY"), @"NOT ({subscribers} IS EMPTY ARRAY)");
+        insta::assert_snapshot!(p("subscribers  IS   NOT   EMPTY   ARRAY"), @"NOT ({subscribers} IS EMPTY ARRAY)");
+        insta::assert_snapshot!(p("subscribers IS EMPTY ARRAY AND subscribers IS EMPTY"), @"AND[{subscribers} IS EMPTY ARRAY, {subscribers} IS EMPTY, ]");
+
+        // Test the length of a field
+        insta::assert_snapshot!(p("tags.length > 2"), @"{tags.length} > {2}");
+        insta::assert_snapshot!(p("tags.length 1 TO 3"), @"{tags.length} {1} TO {3}");
+
         // Test EXISTS + NOT EXITS
         insta::assert_snapshot!(p("subscribers EXISTS"), @"{subscribers} EXISTS");
         insta::assert_snapshot!(p("NOT subscribers EXISTS"), @"NOT ({subscribers} EXISTS)");
@@ -719,12 +812,19 @@ pub mod tests {
         insta::assert_snapshot!(p("_geoRadius(12, 13, 14)"), @"_geoRadius({12}, {13}, {14})");
         insta::assert_snapshot!(p("NOT _geoRadius(12, 13, 14)"), @"NOT (_geoRadius({12}, {13}, {14}))");
         insta::assert_snapshot!(p("_geoRadius(12,13,14)"), @"_geoRadius({12}, {13}, {14})");
+        insta::assert_snapshot!(p("_geoRadius(12, 13, 14, 15)"), @"_geoRadius({12}, {13}, {14}, {15})");
 
         // Test geo bounding box
         insta::assert_snapshot!(p("_geoBoundingBox([12, 13], [14, 15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
         insta::assert_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo contains and intersects
+        insta::assert_snapshot!(p("_geoContains(12, 13)"), @"_geoContains({12}, {13})");
+        insta::assert_snapshot!(p("NOT _geoContains(12,13)"), @"NOT (_geoContains({12}, {13}))");
+        insta::assert_snapshot!(p("_geoIntersects([12, 13], [14, 15])"), @"_geoIntersects([{12}, {13}], [{14}, {15}])");
+        insta::assert_snapshot!(p("_geoIntersects([12,13],[14,15]) AND _geoContains(1, 2)"), @"AND[_geoIntersects([{12}, {13}], [{14}, {15}]), _geoContains({1}, {2}), ]");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -782,7 +882,7 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -792,22 +892,22 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
         insta::assert_snapshot!(p("_geoRadius"), @r###"
-        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.
+        The `_geoRadius` filter expects three or four arguments: `_geoRadius(latitude, longitude, radius)` or `_geoRadius(latitude, longitude, minRadius, maxRadius)`.
         1:11 _geoRadius
         "###);
 
         insta::assert_snapshot!(p("_geoRadius = 12"), @r###"
-        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.
+        The `_geoRadius` filter expects three or four arguments: `_geoRadius(latitude, longitude, radius)` or `_geoRadius(latitude, longitude, minRadius, maxRadius)`.
         1:16 _geoRadius = 12
         "###);
 
@@ -826,6 +926,16 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_snapshot!(p("_geoContains(1.0)"), @r###"
+        The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.
+        18:18 _geoContains(1.0)
+        "###);
+
+        insta::assert_snapshot!(p("_geoIntersects([1.0, 1.0])"), @r###"
+        The `_geoIntersects` filter expects two pairs of arguments: `_geoIntersects([latitude, longitude], [latitude, longitude])`.
+        27:27 _geoIntersects([1.0, 1.0])
+        "###);
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
         `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
@@ -882,12 +992,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -950,39 +1060,96 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
     }
 
+    #[test]
+    fn error_details() {
+        fn d(s: &str) -> ErrorDetails {
+            FilterCondition::parse(s).unwrap_err().details()
+        }
+
+        assert_eq!(
+            d("channel = Ponce = 12"),
+            ErrorDetails {
+                line: 1,
+                column: 17,
+                token: "= 12".to_string(),
+                snippet: "channel = Ponce = 12\n                ^^^^".to_string(),
+                suggestions: vec!["AND".to_string(), "OR".to_string()],
+            }
+        );
+        assert_eq!(
+            d("channel IN [1, 2"),
+            ErrorDetails {
+                line: 1,
+                column: 17,
+                token: "".to_string(),
+                snippet: "channel IN [1, 2\n                ^".to_string(),
+                suggestions: vec!["]".to_string()],
+            }
+        );
+        assert_eq!(
+            d("channel = AND"),
+            ErrorDetails {
+                line: 1,
+                column: 11,
+                token: "AND".to_string(),
+                snippet: "channel = AND\n          ^^^".to_string(),
+                suggestions: vec!["\"AND\"".to_string()],
+            }
+        );
+
+        // The nearest operator is suggested when the operator is misspelled
+        assert_eq!(d("channel CONTAINZ 12").suggestions, vec!["CONTAINS".to_string()]);
+        assert_eq!(d("channel ISNOTNULL").suggestions, vec!["IS NOT NULL".to_string()]);
+        assert_eq!(d("channel = Ponce AND dog pif").suggestions, Vec::<String>::new());
+
+        // Only the line containing the error is displayed
+        let details = d("channel = Ponce\nAND dog = ");
+        assert_eq!(details.line, 2);
+        assert_eq!(details.snippet, "AND dog = \n          ^");
+    }
+
+    #[test]
+    fn nearest() {
+        let attributes = ["title", "genres", "release_date"];
+        assert_eq!(nearest_candidates("titel", attributes), vec!["title".to_string()]);
+        assert_eq!(nearest_candidates("genre", attributes), vec!["genres".to_string()]);
+        assert_eq!(nearest_candidates("ReleaseDate", attributes), vec!["release_date".to_string()]);
+        assert_eq!(nearest_candidates("id", attributes), Vec::<String>::new());
+    }
+
     #[test]
     fn depth() {
         let filter = FilterCondition::parse("account_ids=1 OR account_ids=2 OR account_ids=3 OR account_ids=4 OR account_ids=5 OR account_ids=6").unwrap().unwrap();
@@ -2,10 +2,38 @@
 
 use serde_json::{Map, Value};
 
+/// Controls how the nested objects are turned into dotted keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlattenOptions {
+    /// The maximum number of dots in a flattened key, unlimited when `None`.
+    ///
+    /// The values nested deeper are merged into the key of their ancestor at the maximum depth,
+    /// the same way the values of an array are merged into the key of the array.
+    pub max_depth: Option<usize>,
+    /// Whether the objects in arrays are keyed by their position, e.g. `a.0.b` instead of `a.b`.
+    ///
+    /// A position counts as one level of depth.
+    pub array_positions: bool,
+}
+
+impl FlattenOptions {
+    /// Whether the keys of an object found under a key made of `level` segments are flattened.
+    pub fn flattens_level(&self, level: usize) -> bool {
+        self.max_depth.map_or(true, |max_depth| level <= max_depth)
+    }
+}
+
 pub fn flatten(json: &Map<String, Value>) -> Map<String, Value> {
+    flatten_with_options(json, FlattenOptions::default())
+}
+
+pub fn flatten_with_options(
+    json: &Map<String, Value>,
+    options: FlattenOptions,
+) -> Map<String, Value> {
     let mut obj = Map::new();
     let mut all_entries = vec![];
-    insert_object(&mut obj, None, json, &mut all_entries);
+    insert_object(&mut obj, None, 0, json, &mut all_entries, options);
     for (key, old_val) in all_entries {
         obj.entry(key).or_insert(old_val.clone());
     }
@@ -15,18 +43,27 @@ pub fn flatten(json: &Map<String, Value>) -> Map<String, Value> {
 fn insert_object<'a>(
     base_json: &mut Map<String, Value>,
     base_key: Option<&str>,
+    level: usize,
     object: &'a Map<String, Value>,
     all_entries: &mut Vec<(String, &'a Value)>,
+    options: FlattenOptions,
 ) {
     for (key, value) in object {
-        let new_key = base_key.map_or_else(|| key.clone(), |base_key| format!("{base_key}.{key}"));
+        let (new_key, new_level) = match base_key {
+            None => (key.clone(), 1),
+            Some(base_key) if options.flattens_level(level) => {
+                (format!("{base_key}.{key}"), level + 1)
+            }
+            // too deep, the values are merged into the base key
+            Some(base_key) => (base_key.to_string(), level),
+        };
         all_entries.push((new_key.clone(), value));
         if let Some(array) = value.as_array() {
-            insert_array(base_json, &new_key, array, all_entries);
+            insert_array(base_json, &new_key, new_level, array, all_entries, options);
         } else if let Some(object) = value.as_object() {
-            insert_object(base_json, Some(&new_key), object, all_entries);
+            insert_object(base_json, Some(&new_key), new_level, object, all_entries, options);
         } else {
-            insert_value(base_json, &new_key, value.clone(), false);
+            insert_value(base_json, &new_key, value.clone(), new_level == level);
         }
     }
 }
@@ -34,14 +71,23 @@ fn insert_object<'a>(
 fn insert_array<'a>(
     base_json: &mut Map<String, Value>,
     base_key: &str,
+    level: usize,
     array: &'a Vec<Value>,
     all_entries: &mut Vec<(String, &'a Value)>,
+    options: FlattenOptions,
 ) {
-    for value in array {
+    let array_positions = options.array_positions && options.flattens_level(level);
+    for (position, value) in array.iter().enumerate() {
         if let Some(object) = value.as_object() {
-            insert_object(base_json, Some(base_key), object, all_entries);
+            if array_positions {
+                let key = format!("{base_key}.{position}");
+                all_entries.push((key.clone(), value));
+                insert_object(base_json, Some(&key), level + 1, object, all_entries, options);
+            } else {
+                insert_object(base_json, Some(base_key), level, object, all_entries, options);
+            }
         } else if let Some(sub_array) = value.as_array() {
-            insert_array(base_json, base_key, sub_array, all_entries);
+            insert_array(base_json, base_key, level, sub_array, all_entries, options);
         } else {
             insert_value(base_json, base_key, value.clone(), true);
         }
@@ -364,4 +410,99 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn flatten_with_max_depth() {
+        let mut base: Value = json!({
+          "a": {
+            "b": {
+              "c": "d",
+              "e": { "f": "g" }
+            },
+            "h": "i"
+          },
+          "j": "k"
+        });
+        let json = std::mem::take(base.as_object_mut().unwrap());
+        let options = FlattenOptions { max_depth: Some(1), ..Default::default() };
+        let flat = flatten_with_options(&json, options);
+
+        assert_eq!(
+            &flat,
+            json!({
+                "a": {
+                    "b": {
+                        "c": "d",
+                        "e": { "f": "g" }
+                    },
+                    "h": "i"
+                },
+                "a.b": ["d", "g"],
+                "a.h": "i",
+                "j": "k",
+            })
+            .as_object()
+            .unwrap()
+        );
+
+        let options = FlattenOptions { max_depth: Some(0), ..Default::default() };
+        let flat = flatten_with_options(&json, options);
+
+        assert_eq!(
+            &flat,
+            json!({
+                "a": ["d", "g", "i"],
+                "j": "k",
+            })
+            .as_object()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn flatten_with_array_positions() {
+        let mut base: Value = json!({
+          "a": [
+            42,
+            { "b": "c" },
+            { "b": "d", "e": [{ "f": "g" }] },
+          ]
+        });
+        let json = std::mem::take(base.as_object_mut().unwrap());
+        let options = FlattenOptions { array_positions: true, ..Default::default() };
+        let flat = flatten_with_options(&json, options);
+
+        assert_eq!(
+            &flat,
+            json!({
+                "a": [42],
+                "a.1": { "b": "c" },
+                "a.1.b": "c",
+                "a.2": { "b": "d", "e": [{ "f": "g" }] },
+                "a.2.b": "d",
+                "a.2.e": [{ "f": "g" }],
+                "a.2.e.0": { "f": "g" },
+                "a.2.e.0.f": "g",
+            })
+            .as_object()
+            .unwrap()
+        );
+
+        let options = FlattenOptions { max_depth: Some(2), array_positions: true };
+        let flat = flatten_with_options(&json, options);
+
+        assert_eq!(
+            &flat,
+            json!({
+                "a": [42],
+                "a.1": { "b": "c" },
+                "a.1.b": "c",
+                "a.2": { "b": "d", "e": [{ "f": "g" }] },
+                "a.2.b": "d",
+                "a.2.e": ["g"],
+            })
+            .as_object()
+            .unwrap()
+        );
+    }
 }
@@ -107,7 +107,7 @@ impl<'a> Dump<'a> {
         task: TaskDump,
         content_file: Option<Box<UpdateFile>>,
     ) -> Result<Task> {
-        let task_has_no_docs = matches!(task.kind, KindDump::DocumentImport { documents_count, .. } if documents_count == 0);
+        let task_has_no_docs = matches!(task.kind, KindDump::DocumentImport { documents_count, .. } | KindDump::DocumentImportUrl { documents_count, .. } if documents_count == 0);
 
         let content_uuid = match content_file {
             Some(content_file) if task.status == Status::Enqueued => {
@@ -144,6 +144,9 @@ impl<'a> Dump<'a> {
             finished_at: task.finished_at,
             error: task.error,
             canceled_by: task.canceled_by,
+            depends_on: task.depends_on,
+            labels: task.labels,
+            quota_key: task.quota_key,
             details: task.details,
             status: task.status,
             kind: match task.kind {
@@ -160,6 +163,25 @@ impl<'a> Dump<'a> {
                     documents_count,
                     allow_index_creation,
                 },
+                KindDump::DocumentImportUrl {
+                    primary_key,
+                    method,
+                    url,
+                    csv_delimiter,
+                    documents_count,
+                    allow_index_creation,
+                    replace_all_documents,
+                } => KindWithContent::DocumentImportUrl {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    primary_key,
+                    method,
+                    url,
+                    csv_delimiter,
+                    content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                    documents_count,
+                    allow_index_creation,
+                    replace_all_documents,
+                },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
@@ -200,16 +222,29 @@ impl<'a> Dump<'a> {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
                 },
-                KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
+                KindDump::IndexSwap { swaps, execute_at } => {
+                    KindWithContent::IndexSwap { swaps, execute_at }
+                }
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
                 }
                 KindDump::TasksDeletion { query, tasks } => {
                     KindWithContent::TaskDeletion { query, tasks }
                 }
-                KindDump::DumpCreation { keys, instance_uid } => {
-                    KindWithContent::DumpCreation { keys, instance_uid }
+                KindDump::DumpCreation { keys, instance_uid, index_uids, skip_tasks } => {
+                    KindWithContent::DumpCreation { keys, instance_uid, index_uids, skip_tasks }
+                }
+                KindDump::DumpImport { url, index_renames } => {
+                    KindWithContent::DumpImport { dump_file: None, url, index_renames }
+                }
+                KindDump::PointInTimeRestore { index_uids, restore_at } => {
+                    KindWithContent::PointInTimeRestore { index_uids, restore_at }
                 }
+                KindDump::IndexRestore { snapshot, dump_uid } => KindWithContent::IndexRestore {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    snapshot,
+                    dump_uid,
+                },
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
                 KindDump::UpgradeDatabase { from } => KindWithContent::UpgradeDatabase { from },
             },
@@ -263,6 +298,27 @@ impl<'a> Dump<'a> {
         self.statuses.entry(task.status).or_default().insert(task.uid);
         self.kinds.entry(task.kind.as_kind()).or_default().insert(task.uid);
 
+        // Only the enqueued tasks are still waiting for their dependency.
+        if let (Some(depends_on), Status::Enqueued) = (task.depends_on, task.status) {
+            self.index_scheduler.queue.tasks.update_dependents(
+                &mut self.wtxn,
+                depends_on,
+                |bitmap| {
+                    bitmap.insert(task.uid);
+                },
+            )?;
+        }
+
+        for (key, value) in &task.labels {
+            self.index_scheduler.queue.tasks.update_label(
+                &mut self.wtxn,
+                &format!("{key}={value}"),
+                |bitmap| {
+                    bitmap.insert(task.uid);
+                },
+            )?;
+        }
+
         Ok(task)
     }
 
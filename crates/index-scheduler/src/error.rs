@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use meilisearch_types::batches::BatchId;
+use meilisearch_types::document_formats::DocumentFormatError;
 use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
@@ -68,6 +69,18 @@ pub enum Error {
     SwapIndexNotFound(String),
     #[error("Meilisearch cannot receive write operations because the limit of the task database has been reached. Please delete tasks to continue performing write operations.")]
     NoSpaceLeftInTaskQueue,
+    #[error("Meilisearch cannot receive write operations because the task queue reached its limit of {0} enqueued tasks. Please retry once some of them have been processed.")]
+    TooManyEnqueuedTasks(u64),
+    #[error("Meilisearch cannot receive write operations because more than {0}% of the disk is used. Please retry once some space has been freed.")]
+    DiskWatermarkReached(u8),
+    #[error("Meilisearch cannot receive write operations because it is in read-only mode. Please retry once the `readOnly` experimental feature has been disabled.")]
+    ReadOnlyMode,
+    #[error("Meilisearch cannot receive write operations because it is a read-only replica. Please send them to the primary instance.")]
+    ReadOnlyReplica,
+    #[error("Meilisearch cannot receive write operations because it is draining before being removed. Please send them to another instance.")]
+    Draining,
+    #[error("The API key cannot register this task because it would exceed its `{quota}` quota of {limit}.")]
+    KeyQuotaExceeded { quota: &'static str, limit: u64 },
     #[error(
         "Indexes {} not found.",
         .0.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
@@ -103,6 +116,10 @@ pub enum Error {
         "Task canceledBy `{canceled_by}` is invalid. It should only contains numeric characters separated by `,` character."
     )]
     InvalidTaskCanceledBy { canceled_by: String },
+    #[error("Task `{0}` cannot be depended on because it does not exist.")]
+    InvalidTaskDependsOn(TaskId),
+    #[error("Task `{dependency}` this task depends on did not succeed.")]
+    TaskDependencyFailed { dependency: TaskId },
     #[error(
         "{index_uid} is not a valid index uid. Index uid can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_), and can not be more than 400 bytes."
     )]
@@ -113,12 +130,34 @@ pub enum Error {
     TaskFileNotFound(TaskId),
     #[error("Batch `{0}` not found.")]
     BatchNotFound(BatchId),
-    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `labels`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskDeletionWithEmptyQuery,
-    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `labels`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskCancelationWithEmptyQuery,
     #[error("Aborted task")]
     AbortedTask,
+    #[error("Could not import the documents from `{url}`: {error}.")]
+    DocumentImportUrl { url: String, error: String },
+    #[error("Importing documents from a URL requires launching Meilisearch with the `--experimental-import-url-allowed-hosts` option.")]
+    DocumentImportUrlDisabled,
+    #[error("Could not import the dump: {0}.")]
+    DumpImport(String),
+    #[error("Importing a dump from a URL requires launching Meilisearch with the `--experimental-import-url-allowed-hosts` option.")]
+    DumpImportUrlDisabled,
+    #[error("Could not restore the indexes: {0}.")]
+    PointInTimeRestore(String),
+    #[error("Could not restore the index `{index_uid}`: {error}.")]
+    IndexRestore { index_uid: String, error: String },
+    #[error("Restoring the indexes to a date requires launching Meilisearch with the `--experimental-restore-points` option.")]
+    RestorePointsDisabled,
+    #[error("Streaming the changes of the documents requires launching Meilisearch with the `--experimental-change-stream` option.")]
+    ChangeStreamDisabled,
+    #[error("Shipping and replaying the task log requires launching Meilisearch with the `--experimental-task-log` option.")]
+    TaskLogDisabled,
+    #[error("Could not replay the task log: {0}.")]
+    TaskLogReplay(String),
+    #[error("Searching through index aliases requires launching Meilisearch with the `--experimental-index-aliases` option.")]
+    IndexAliasesDisabled,
 
     #[error(transparent)]
     Dump(#[from] dump::Error),
@@ -139,6 +178,8 @@ pub enum Error {
     Persist(#[from] tempfile::PersistError),
     #[error(transparent)]
     FeatureNotEnabled(#[from] FeatureNotEnabledError),
+    #[error(transparent)]
+    DocumentFormat(#[from] DocumentFormatError),
 
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
@@ -181,6 +222,12 @@ impl Error {
             | Error::SwapDuplicateIndexesFound(_)
             | Error::SwapIndexNotFound(_)
             | Error::NoSpaceLeftInTaskQueue
+            | Error::TooManyEnqueuedTasks(_)
+            | Error::DiskWatermarkReached(_)
+            | Error::ReadOnlyMode
+            | Error::ReadOnlyReplica
+            | Error::Draining
+            | Error::KeyQuotaExceeded { .. }
             | Error::SwapIndexesNotFound(_)
             | Error::CorruptedDump
             | Error::InvalidTaskDate { .. }
@@ -189,6 +236,8 @@ impl Error {
             | Error::InvalidTaskStatuses { .. }
             | Error::InvalidTaskTypes { .. }
             | Error::InvalidTaskCanceledBy { .. }
+            | Error::InvalidTaskDependsOn(_)
+            | Error::TaskDependencyFailed { .. }
             | Error::InvalidIndexUid { .. }
             | Error::TaskNotFound(_)
             | Error::TaskFileNotFound(_)
@@ -196,6 +245,17 @@ impl Error {
             | Error::TaskDeletionWithEmptyQuery
             | Error::TaskCancelationWithEmptyQuery
             | Error::AbortedTask
+            | Error::DocumentImportUrl { .. }
+            | Error::DocumentImportUrlDisabled
+            | Error::DumpImport(_)
+            | Error::DumpImportUrlDisabled
+            | Error::PointInTimeRestore(_)
+            | Error::IndexRestore { .. }
+            | Error::RestorePointsDisabled
+            | Error::ChangeStreamDisabled
+            | Error::TaskLogDisabled
+            | Error::TaskLogReplay(_)
+            | Error::IndexAliasesDisabled
             | Error::Dump(_)
             | Error::Heed(_)
             | Error::Milli { .. }
@@ -204,6 +264,7 @@ impl Error {
             | Error::IoError(_)
             | Error::Persist(_)
             | Error::FeatureNotEnabled(_)
+            | Error::DocumentFormat(_)
             | Error::Anyhow(_) => true,
             Error::CreateBatch(_)
             | Error::CorruptedTaskQueue
@@ -221,7 +282,7 @@ impl Error {
 
     pub fn from_milli(err: milli::Error, index_uid: Option<String>) -> Self {
         match err {
-            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            milli::Error::UserError(milli::UserError::InvalidFilter(..)) => {
                 Self::Milli { error: err, index_uid }
                     .with_custom_error_code(Code::InvalidDocumentFilter)
             }
@@ -251,6 +312,8 @@ impl ErrorCode for Error {
             Error::InvalidTaskStatuses { .. } => Code::InvalidTaskStatuses,
             Error::InvalidTaskTypes { .. } => Code::InvalidTaskTypes,
             Error::InvalidTaskCanceledBy { .. } => Code::InvalidTaskCanceledBy,
+            Error::InvalidTaskDependsOn(_) => Code::InvalidTaskDependsOn,
+            Error::TaskDependencyFailed { .. } => Code::TaskDependencyFailed,
             Error::InvalidIndexUid { .. } => Code::InvalidIndexUid,
             Error::TaskNotFound(_) => Code::TaskNotFound,
             Error::TaskFileNotFound(_) => Code::TaskFileNotFound,
@@ -259,6 +322,12 @@ impl ErrorCode for Error {
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
             // TODO: not sure of the Code to use
             Error::NoSpaceLeftInTaskQueue => Code::NoSpaceLeftOnDevice,
+            Error::TooManyEnqueuedTasks(_) => Code::TooManyEnqueuedTasks,
+            Error::DiskWatermarkReached(_) => Code::DiskWatermarkReached,
+            Error::ReadOnlyMode => Code::ReadOnlyMode,
+            Error::ReadOnlyReplica => Code::ReadOnlyMode,
+            Error::Draining => Code::ReadOnlyMode,
+            Error::KeyQuotaExceeded { .. } => Code::ApiKeyQuotaExceeded,
             Error::Dump(e) => e.error_code(),
             Error::Milli { error, .. } => error.error_code(),
             Error::ProcessBatchPanicked(_) => Code::Internal,
@@ -268,6 +337,18 @@ impl ErrorCode for Error {
             Error::IoError(e) => e.error_code(),
             Error::Persist(e) => e.error_code(),
             Error::FeatureNotEnabled(_) => Code::FeatureNotEnabled,
+            Error::DocumentImportUrl { .. } => Code::DocumentImportUrlFailed,
+            Error::DocumentImportUrlDisabled => Code::FeatureNotEnabled,
+            Error::DumpImport(_) => Code::DumpImportFailed,
+            Error::DumpImportUrlDisabled => Code::FeatureNotEnabled,
+            Error::PointInTimeRestore(_) => Code::PointInTimeRestoreFailed,
+            Error::IndexRestore { .. } => Code::IndexRestoreFailed,
+            Error::RestorePointsDisabled => Code::FeatureNotEnabled,
+            Error::ChangeStreamDisabled => Code::FeatureNotEnabled,
+            Error::TaskLogDisabled => Code::FeatureNotEnabled,
+            Error::TaskLogReplay(_) => Code::TaskLogReplayFailed,
+            Error::DocumentFormat(e) => e.error_code(),
+            Error::IndexAliasesDisabled => Code::FeatureNotEnabled,
 
             // Irrecoverable errors
             Error::Anyhow(_) => Code::Internal,
@@ -1,22 +1,25 @@
 use std::sync::{Arc, RwLock};
 
-use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
+use meilisearch_types::features::{
+    IndexAliases, InstanceTogglableFeatures, Network, RuntimeTogglableFeatures,
+};
 use meilisearch_types::heed::types::{SerdeJson, Str};
 use meilisearch_types::heed::{Database, Env, RwTxn, WithoutTls};
 
 use crate::error::FeatureNotEnabledError;
-use crate::Result;
+use crate::{Error, Result};
 
 /// The number of database used by features
 const NUMBER_OF_DATABASES: u32 = 1;
 /// Database const names for the `FeatureData`.
-mod db_name {
+pub(crate) mod db_name {
     pub const EXPERIMENTAL_FEATURES: &str = "experimental-features";
 }
 
-mod db_keys {
+pub(crate) mod db_keys {
     pub const EXPERIMENTAL_FEATURES: &str = "experimental-features";
     pub const NETWORK: &str = "network";
+    pub const INDEX_ALIASES: &str = "index-aliases";
 }
 
 #[derive(Clone)]
@@ -24,6 +27,7 @@ pub(crate) struct FeatureData {
     persisted: Database<Str, SerdeJson<RuntimeTogglableFeatures>>,
     runtime: Arc<RwLock<RuntimeTogglableFeatures>>,
     network: Arc<RwLock<Network>>,
+    index_aliases: Arc<RwLock<IndexAliases>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -119,6 +123,15 @@ impl RoFeatures {
         }
     }
 
+    /// Returns an error if the instance is in read-only mode.
+    pub fn check_writes_allowed(&self) -> Result<()> {
+        if self.runtime.read_only {
+            Err(Error::ReadOnlyMode)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn check_composite_embedders(&self, disabled_action: &'static str) -> Result<()> {
         if self.runtime.composite_embedders {
             Ok(())
@@ -159,10 +172,15 @@ impl FeatureData {
         let network_db = runtime_features_db.remap_data_type::<SerdeJson<Network>>();
         let network: Network = network_db.get(wtxn, db_keys::NETWORK)?.unwrap_or_default();
 
+        let index_aliases_db = runtime_features_db.remap_data_type::<SerdeJson<IndexAliases>>();
+        let index_aliases: IndexAliases =
+            index_aliases_db.get(wtxn, db_keys::INDEX_ALIASES)?.unwrap_or_default();
+
         Ok(Self {
             persisted: runtime_features_db,
             runtime,
             network: Arc::new(RwLock::new(network)),
+            index_aliases: Arc::new(RwLock::new(index_aliases)),
         })
     }
 
@@ -209,4 +227,21 @@ impl FeatureData {
     pub fn network(&self) -> Network {
         Network::clone(&*self.network.read().unwrap())
     }
+
+    pub fn put_index_aliases(&self, mut wtxn: RwTxn, new_aliases: IndexAliases) -> Result<()> {
+        self.persisted.remap_data_type::<SerdeJson<IndexAliases>>().put(
+            &mut wtxn,
+            db_keys::INDEX_ALIASES,
+            &new_aliases,
+        )?;
+        wtxn.commit()?;
+
+        let mut index_aliases = self.index_aliases.write().unwrap();
+        *index_aliases = new_aliases;
+        Ok(())
+    }
+
+    pub fn index_aliases(&self) -> IndexAliases {
+        IndexAliases::clone(&*self.index_aliases.read().unwrap())
+    }
 }
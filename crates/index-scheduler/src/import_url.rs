@@ -0,0 +1,200 @@
+//! Downloads the documents served at a remote URL into an update file, when the task importing
+//! them is registered, and the dumps imported from a URL. Only the hosts allowed by the options of
+//! the instance can be reached, and the hosts allowed by the `*` wildcard can't resolve to a
+//! loopback or a private address.
+
+use std::io::{self, ErrorKind, Read, Seek};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use meilisearch_types::document_formats::{
+    read_avro, read_csv, read_json, read_ndjson, PayloadType,
+};
+use uuid::Uuid;
+
+use crate::{Error, IndexScheduler, Result};
+
+/// How long establishing the connection and waiting for each read of the response can take.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The hosts the documents and the dumps can be imported from, and how much can be downloaded.
+#[derive(Debug, Clone)]
+pub struct ImportUrlOptions {
+    /// The lowercased hosts, `*` allowing any host resolving to a public address.
+    pub allowed_hosts: Vec<String>,
+    /// The maximum size, in bytes, of the downloaded documents or dump.
+    pub size_limit: u64,
+}
+
+impl IndexScheduler {
+    /// Streams the documents served at the url into a new update file.
+    ///
+    /// ## Return
+    /// The uuid of the update file and the number of documents it contains.
+    pub fn fetch_remote_documents(
+        &self,
+        url: &str,
+        csv_delimiter: Option<u8>,
+    ) -> Result<(Uuid, u64)> {
+        let options = self.import_url.as_ref().ok_or(Error::DocumentImportUrlDisabled)?;
+        let import_error = |error: String| Error::DocumentImportUrl { url: url.to_string(), error };
+
+        let response = agent(options).get(url).call().map_err(|e| import_error(e.to_string()))?;
+
+        // Object storages often serve the files as binary streams, the extension of the file tells us more.
+        let format = match response.content_type() {
+            "application/json" => Some(PayloadType::Json),
+            "application/x-ndjson" => Some(PayloadType::Ndjson),
+            "text/csv" => Some(PayloadType::Csv { delimiter: csv_delimiter.unwrap_or(b',') }),
+            "application/avro" => Some(PayloadType::Avro),
+            _ => None,
+        };
+        let format = format.or_else(|| {
+            let path = url.split(['?', '#']).next().unwrap_or_default();
+            match path.rsplit_once('.').map(|(_, extension)| extension) {
+                Some("json") => Some(PayloadType::Json),
+                Some("ndjson" | "jsonl") => Some(PayloadType::Ndjson),
+                Some("csv") => Some(PayloadType::Csv { delimiter: csv_delimiter.unwrap_or(b',') }),
+                Some("avro") => Some(PayloadType::Avro),
+                _ => None,
+            }
+        });
+        let format = match format {
+            Some(PayloadType::Csv { delimiter }) => PayloadType::Csv { delimiter },
+            Some(_) if csv_delimiter.is_some() => {
+                return Err(import_error(
+                    "the `csvDelimiter` parameter can only be used with CSV documents".to_string(),
+                ))
+            }
+            Some(format) => format,
+            None => {
+                return Err(import_error(format!(
+                    "the content type `{}` is not supported, the documents must be served as `application/json`, `application/x-ndjson`, `text/csv` or `application/avro`",
+                    response.content_type()
+                )))
+            }
+        };
+
+        let mut fetched = tempfile::tempfile()?;
+        let mut reader = response.into_reader().take(options.size_limit.saturating_add(1));
+        let size =
+            std::io::copy(&mut reader, &mut fetched).map_err(|e| import_error(e.to_string()))?;
+        if size > options.size_limit {
+            return Err(import_error(format!(
+                "the documents are larger than the payload size limit of {} bytes",
+                options.size_limit
+            )));
+        }
+        fetched.rewind()?;
+
+        let (uuid, mut update_file) = self.queue.file_store.new_update()?;
+        let documents_count = match format {
+            PayloadType::Json => read_json(&fetched, &mut update_file)?,
+            PayloadType::Csv { delimiter } => read_csv(&fetched, &mut update_file, delimiter)?,
+            PayloadType::Avro => read_avro(&fetched, &mut update_file)?,
+            PayloadType::Ndjson => {
+                let documents_count = read_ndjson(&fetched)?;
+                fetched.rewind()?;
+                std::io::copy(&mut fetched, &mut update_file)?;
+                documents_count
+            }
+        };
+        update_file.persist()?;
+
+        Ok((uuid, documents_count))
+    }
+}
+
+/// An HTTP agent only connecting to the allowed hosts, and giving up on the slow ones.
+pub(crate) fn agent(options: &ImportUrlOptions) -> ureq::Agent {
+    // The redirections are resolved by the same resolver, they can't reach a forbidden host either.
+    let allowed_hosts = Arc::new(options.allowed_hosts.clone());
+    ureq::AgentBuilder::new()
+        .timeout_connect(TIMEOUT)
+        .timeout_read(TIMEOUT)
+        .resolver(move |netloc: &str| resolve(&allowed_hosts, netloc))
+        .build()
+}
+
+/// Resolves the `host:port` to connect to, failing if the host is not allowed.
+///
+/// The hosts that are explicitly allowed can resolve to any address, the other ones are only
+/// connected to through their public addresses.
+fn resolve(allowed_hosts: &[String], netloc: &str) -> io::Result<Vec<SocketAddr>> {
+    // the IPv6 addresses keep their brackets, like in the options
+    let host = netloc.rsplit_once(':').map_or(netloc, |(host, _port)| host).to_lowercase();
+    let listed = allowed_hosts.iter().any(|allowed| *allowed == host);
+    if !listed && !allowed_hosts.iter().any(|allowed| allowed == "*") {
+        return Err(io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("the host `{host}` is not allowed"),
+        ));
+    }
+
+    let addresses = netloc.to_socket_addrs()?;
+    if listed {
+        return Ok(addresses.collect());
+    }
+    let addresses: Vec<_> = addresses.filter(|address| is_public(address.ip())).collect();
+    if addresses.is_empty() {
+        return Err(io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("the host `{host}` doesn't resolve to a public address"),
+        ));
+    }
+    Ok(addresses)
+}
+
+/// Whether the address can be reached from the internet, unlike the loopback, private,
+/// link-local (where the cloud metadata services live) and reserved addresses.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_unspecified()
+                || a == 0
+                // the shared address space of the carrier-grade NATs, 100.64.0.0/10
+                || (a == 100 && b & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    // the unique local addresses, fc00::/7
+                    || first & 0xfe00 == 0xfc00
+                    // the link-local addresses, fe80::/10
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_allowed_hosts_are_resolved() {
+        let listed = vec!["127.0.0.1".to_string()];
+        assert!(resolve(&listed, "127.0.0.1:8080").is_ok());
+        assert!(resolve(&listed, "localhost:8080").is_err());
+
+        // the wildcard never reaches the private addresses
+        let wildcard = vec!["*".to_string()];
+        for netloc in
+            ["127.0.0.1:80", "10.0.0.1:80", "169.254.169.254:80", "[::1]:80", "[fd00::1]:80"]
+        {
+            let error = resolve(&wildcard, netloc).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::PermissionDenied, "{netloc}");
+        }
+        assert!(resolve(&wildcard, "1.1.1.1:443").is_ok());
+    }
+}
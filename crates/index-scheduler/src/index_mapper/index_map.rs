@@ -297,7 +297,7 @@ impl IndexMap {
 
 /// Create or open an index in the specified path.
 /// The path *must* exist or an error will be thrown.
-fn create_or_open_index(
+pub(super) fn create_or_open_index(
     path: &Path,
     date: Option<(OffsetDateTime, OffsetDateTime)>,
     enable_mdb_writemap: bool,
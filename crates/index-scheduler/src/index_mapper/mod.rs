@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{fs, thread};
@@ -8,7 +9,9 @@ use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli;
 use meilisearch_types::milli::database_stats::DatabaseStats;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::usage::EmbedderUsage;
 use meilisearch_types::milli::{FieldDistribution, Index};
+use meilisearch_types::sources::IndexSource;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use tracing::error;
@@ -22,11 +25,12 @@ use crate::{Error, IndexBudget, IndexSchedulerOptions, Result};
 mod index_map;
 
 /// The number of database used by index mapper
-const NUMBER_OF_DATABASES: u32 = 2;
+const NUMBER_OF_DATABASES: u32 = 3;
 /// Database const names for the `IndexMapper`.
-mod db_name {
+pub(crate) mod db_name {
     pub const INDEX_MAPPING: &str = "index-mapping";
     pub const INDEX_STATS: &str = "index-stats";
+    pub const INDEX_SOURCES: &str = "index-sources";
 }
 
 /// Structure managing meilisearch's indexes.
@@ -66,6 +70,10 @@ pub struct IndexMapper {
     /// Using an UUID forces to use the index_mapping table to recover the index behind a name, ensuring
     /// consistency wrt index swapping.
     pub(crate) index_stats: Database<UuidCodec, SerdeJson<IndexStats>>,
+    /// Map an index UUID with the remote source its documents are periodically refreshed from.
+    ///
+    /// Like the stats, the source follows the index when it is swapped.
+    pub(crate) index_sources: Database<UuidCodec, SerdeJson<IndexSource>>,
 
     /// Path to the folder where the LMDB environments of each index are.
     base_path: PathBuf,
@@ -79,7 +87,9 @@ pub struct IndexMapper {
 
     /// A few types of long running batches of tasks that act on a single index set this field
     /// so that a handle to the index is available from other threads (search) in an optimized manner.
-    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
+    ///
+    /// There is one entry per index being updated, as batches of different indexes can be processed at once.
+    currently_updating_index: Arc<RwLock<HashMap<String, Index>>>,
 }
 
 /// Whether the index is available for use or is forbidden to be inserted back in the index map
@@ -133,6 +143,9 @@ pub struct IndexStats {
     /// Date of the last update of the index.
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
+    /// The requests sent to the embedders to index the documents, by embedder name.
+    #[serde(default)]
+    pub embedder_usage: BTreeMap<String, EmbedderUsage>,
 }
 
 impl IndexStats {
@@ -154,6 +167,7 @@ impl IndexStats {
             field_distribution: index.field_distribution(rtxn)?,
             created_at: index.created_at(rtxn)?,
             updated_at: index.updated_at(rtxn)?,
+            embedder_usage: index.embedder_usage(rtxn)?,
         })
     }
 }
@@ -173,6 +187,7 @@ impl IndexMapper {
             index_map: Arc::new(RwLock::new(IndexMap::new(budget.index_count))),
             index_mapping: env.create_database(wtxn, Some(db_name::INDEX_MAPPING))?,
             index_stats: env.create_database(wtxn, Some(db_name::INDEX_STATS))?,
+            index_sources: env.create_database(wtxn, Some(db_name::INDEX_SOURCES))?,
             base_path: options.indexes_path.clone(),
             index_base_map_size: budget.map_size,
             index_growth_amount: options.index_growth_amount,
@@ -241,12 +256,22 @@ impl IndexMapper {
 
         // Not an error if the index had no stats in cache.
         self.index_stats.delete(&mut wtxn, &uuid)?;
+        // A deleted index must not be refreshed anymore.
+        self.index_sources.delete(&mut wtxn, &uuid)?;
 
         // Once we retrieved the UUID of the index we remove it from the mapping table.
         assert!(self.index_mapping.delete(&mut wtxn, name)?);
 
         wtxn.commit()?;
 
+        self.remove_index_data(uuid, name);
+        Ok(())
+    }
+
+    /// Closes the index of the uuid and removes it from the disk in a separate thread.
+    ///
+    /// The index must not be referenced by the mapping anymore.
+    fn remove_index_data(&self, uuid: Uuid, name: &str) {
         let mut tries = 0;
         // Attempts to remove the index from the in-memory index map in a loop.
         //
@@ -280,7 +305,7 @@ impl IndexMapper {
                     reopen.close(&mut self.index_map.write().unwrap());
                     continue;
                 }
-                Err(None) => return Ok(()),
+                Err(None) => return,
             }
         };
 
@@ -308,7 +333,96 @@ impl IndexMapper {
                 index_map.write().unwrap().end_deletion(&uuid);
             })
             .unwrap();
+    }
+
+    /// Opens a copy of an index, e.g. extracted from a snapshot, under a fresh uuid. The LMDB
+    /// file of the copy is moved to the indexes folder.
+    ///
+    /// The copy can't be accessed by its name until it replaces an index with
+    /// [`Self::replace_index`], and must be discarded with [`Self::discard_index_copy`] otherwise.
+    pub fn open_index_copy(&self, data_path: &Path) -> Result<(Uuid, Index)> {
+        let uuid = Uuid::new_v4();
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+        let dst = index_path.join("data.mdb");
+        if fs::rename(data_path, &dst).is_err() {
+            fs::copy(data_path, &dst)?;
+        }
+
+        // the copy may be larger than the indexes are when first opened
+        let map_size = self
+            .index_base_map_size
+            .max(fs::metadata(&dst)?.len() as usize + self.index_growth_amount);
+        let index = index_map::create_or_open_index(
+            &index_path,
+            None,
+            self.enable_mdb_writemap,
+            map_size,
+            false,
+        )
+        .map_err(|e| Error::from_milli(e, Some(uuid.to_string())))?;
+        Ok((uuid, index))
+    }
+
+    /// Creates an empty index under a fresh uuid, to be filled and handled like the copies
+    /// opened with [`Self::open_index_copy`].
+    pub fn create_index_copy(
+        &self,
+        date: Option<(OffsetDateTime, OffsetDateTime)>,
+    ) -> Result<(Uuid, Index)> {
+        let uuid = Uuid::new_v4();
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+        let index = index_map::create_or_open_index(
+            &index_path,
+            date,
+            self.enable_mdb_writemap,
+            self.index_base_map_size,
+            true,
+        )
+        .map_err(|e| Error::from_milli(e, Some(uuid.to_string())))?;
+        Ok((uuid, index))
+    }
+
+    /// Makes the name refer to a copy opened with [`Self::open_index_copy`] and deletes the index
+    /// it referred to. The copy is closed and reopened on demand like any other index.
+    ///
+    /// The remote source of the replaced index, if any, follows the name.
+    pub fn replace_index(
+        &self,
+        mut wtxn: RwTxn,
+        name: &str,
+        uuid: Uuid,
+        copy: Index,
+    ) -> Result<()> {
+        let rtxn = copy.read_txn()?;
+        let stats = IndexStats::new(&copy, &rtxn)
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+        drop(rtxn);
+        copy.prepare_for_closing().wait();
+
+        let previous = self.index_mapping.get(&wtxn, name)?;
+        self.index_mapping.put(&mut wtxn, name, &uuid)?;
+        self.index_stats.put(&mut wtxn, &uuid, &stats)?;
+        if let Some(previous) = previous {
+            self.index_stats.delete(&mut wtxn, &previous)?;
+            if let Some(source) = self.index_sources.get(&wtxn, &previous)? {
+                self.index_sources.delete(&mut wtxn, &previous)?;
+                self.index_sources.put(&mut wtxn, &uuid, &source)?;
+            }
+        }
+        wtxn.commit()?;
+
+        if let Some(previous) = previous {
+            self.remove_index_data(previous, name);
+        }
+        Ok(())
+    }
 
+    /// Closes a copy opened with [`Self::open_index_copy`] and removes it from the disk.
+    pub fn discard_index_copy(&self, uuid: Uuid, copy: Index) -> Result<()> {
+        copy.prepare_for_closing().wait();
+        fs::remove_dir_all(self.base_path.join(uuid.to_string()))?;
         Ok(())
     }
 
@@ -342,12 +456,8 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
-        if let Some((current_name, current_index)) =
-            self.currently_updating_index.read().unwrap().as_ref()
-        {
-            if current_name == name {
-                return Ok(current_index.clone());
-            }
+        if let Some(current_index) = self.currently_updating_index.read().unwrap().get(name) {
+            return Ok(current_index.clone());
         }
 
         let uuid = self
@@ -520,6 +630,58 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Return the remote source of an index, if one was registered.
+    pub fn source_of(&self, rtxn: &RoTxn, index_uid: &str) -> Result<Option<IndexSource>> {
+        let uuid = self
+            .index_mapping
+            .get(rtxn, index_uid)?
+            .ok_or_else(|| Error::IndexNotFound(index_uid.to_string()))?;
+
+        Ok(self.index_sources.get(rtxn, &uuid)?)
+    }
+
+    /// Stores the remote source of an index, replacing the previous one.
+    pub fn store_source_of(
+        &self,
+        wtxn: &mut RwTxn,
+        index_uid: &str,
+        source: &IndexSource,
+    ) -> Result<()> {
+        let uuid = self
+            .index_mapping
+            .get(wtxn, index_uid)?
+            .ok_or_else(|| Error::IndexNotFound(index_uid.to_string()))?;
+
+        self.index_sources.put(wtxn, &uuid, source)?;
+        Ok(())
+    }
+
+    /// Removes the remote source of an index, returns `false` if there was none.
+    pub fn delete_source_of(&self, wtxn: &mut RwTxn, index_uid: &str) -> Result<bool> {
+        let uuid = self
+            .index_mapping
+            .get(wtxn, index_uid)?
+            .ok_or_else(|| Error::IndexNotFound(index_uid.to_string()))?;
+
+        Ok(self.index_sources.delete(wtxn, &uuid)?)
+    }
+
+    /// Return the name and source of all the indexes with a remote source, without opening them.
+    pub fn sources(&self, rtxn: &RoTxn) -> Result<Vec<(String, IndexSource)>> {
+        let mut sources = Vec::new();
+        // There are usually far less sources than indexes.
+        if self.index_sources.is_empty(rtxn)? {
+            return Ok(sources);
+        }
+        for ret in self.index_mapping.iter(rtxn)? {
+            let (name, uuid) = ret?;
+            if let Some(source) = self.index_sources.get(rtxn, &uuid)? {
+                sources.push((name.to_string(), source));
+            }
+        }
+        Ok(sources)
+    }
+
     pub fn index_exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
         Ok(self.index_mapping.get(rtxn, name)?.is_some())
     }
@@ -528,7 +690,11 @@ impl IndexMapper {
         &self.indexer_config
     }
 
-    pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
-        *self.currently_updating_index.write().unwrap() = index;
+    pub fn set_currently_updating_index(&self, name: &str, index: Option<Index>) {
+        let mut currently_updating_index = self.currently_updating_index.write().unwrap();
+        match index {
+            Some(index) => currently_updating_index.insert(name.to_string(), index),
+            None => currently_updating_index.remove(name),
+        };
     }
 }
@@ -20,6 +20,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
     let IndexScheduler {
         cleanup_enabled: _,
+        read_only_replica: _,
         processing_tasks,
         env,
         version,
@@ -28,12 +29,16 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
         index_mapper,
         features: _,
-        webhook_url: _,
-        webhook_authorization_header: _,
+        key_usage: _,
+        webhooks: _,
+        task_events: _,
+        max_enqueued_tasks: _,
+        disk_watermark: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
         run_loop_iteration: _,
         embedders: _,
+        search_embedder_usage: _,
     } = scheduler;
 
     let rtxn = env.read_txn().unwrap();
@@ -54,12 +59,12 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
     snap.push_str(&format!("### Autobatching Enabled = {}\n", scheduler.autobatching_enabled));
     snap.push_str(&format!(
         "### Processing batch {:?}:\n",
-        processing.batch.as_ref().map(|batch| batch.uid)
+        processing.batches.first().map(|entry| entry.batch.uid)
     ));
     snap.push_str(&snapshot_bitmap(&processing.processing));
-    if let Some(ref batch) = processing.batch {
+    for entry in &processing.batches {
         snap.push('\n');
-        snap.push_str(&snapshot_batch(&batch.to_batch()));
+        snap.push_str(&snapshot_batch(&entry.batch.to_batch()));
     }
     snap.push_str("\n----------------------------------------------------------------------\n");
 
@@ -211,6 +216,9 @@ pub fn snapshot_task(task: &Task) -> String {
         finished_at: _,
         error,
         canceled_by,
+        depends_on,
+        labels,
+        quota_key,
         details,
         status,
         kind,
@@ -224,6 +232,15 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(canceled_by) = canceled_by {
         snap.push_str(&format!("canceled_by: {canceled_by}, "));
     }
+    if let Some(depends_on) = depends_on {
+        snap.push_str(&format!("depends_on: {depends_on}, "));
+    }
+    if !labels.is_empty() {
+        snap.push_str(&format!("labels: {labels:?}, "));
+    }
+    if let Some(quota_key) = quota_key {
+        snap.push_str(&format!("quota_key: {quota_key}, "));
+    }
     if let Some(error) = error {
         snap.push_str(&format!("error: {error:?}, "));
     }
@@ -288,7 +305,16 @@ fn snapshot_details(d: &Details) -> String {
         Details::Dump { dump_uid } => {
             format!("{{ dump_uid: {dump_uid:?} }}")
         },
-        Details::IndexSwap { swaps } => {
+        Details::DumpImport { imported_indexes, indexed_documents } => {
+            format!("{{ imported_indexes: {imported_indexes:?}, indexed_documents: {indexed_documents:?} }}")
+        },
+        Details::PointInTimeRestore { restore_at, restored_indexes, replayed_tasks } => {
+            format!("{{ restore_at: {restore_at:?}, restored_indexes: {restored_indexes:?}, replayed_tasks: {replayed_tasks:?} }}")
+        },
+        Details::IndexRestore { snapshot, dump_uid, indexed_documents } => {
+            format!("{{ snapshot: {snapshot:?}, dump_uid: {dump_uid:?}, indexed_documents: {indexed_documents:?} }}")
+        },
+        Details::IndexSwap { swaps, execute_at: _ } => {
             format!("{{ swaps: {swaps:?} }}")
         }
         Details::UpgradeDatabase { from, to } => {
@@ -345,6 +371,7 @@ pub fn snapshot_batch(batch: &Batch) -> String {
     let stats = BatchStats {
         progress_trace: Default::default(),
         write_channel_congestion: None,
+        resource_usage: None,
         ..stats.clone()
     };
     if let Some(finished_at) = finished_at {
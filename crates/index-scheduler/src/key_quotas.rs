@@ -0,0 +1,166 @@
+//! Tracks the resources used by the tasks registered with the API keys having quotas,
+//! and refuses the new tasks that would exceed them.
+
+use std::collections::BTreeSet;
+
+use meilisearch_types::heed::types::SerdeJson;
+use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::keys::{KeyId, KeyQuotas};
+use meilisearch_types::tasks::{Details, KindWithContent, Task};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::index_mapper::IndexMapper;
+use crate::uuid_codec::UuidCodec;
+use crate::{Error, Result};
+
+/// The number of database used by the key quotas
+const NUMBER_OF_DATABASES: u32 = 1;
+/// Database const names for the `KeyUsageStore`.
+mod db_name {
+    pub const KEY_USAGE: &str = "key-usage";
+}
+
+/// The resources used by the succeeded tasks registered with an API key.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyUsage {
+    /// The existing indexes created with the key.
+    pub indexes: BTreeSet<String>,
+    /// The number of documents added with the key.
+    pub documents: u64,
+}
+
+#[derive(Clone)]
+pub(crate) struct KeyUsageStore {
+    usage: Database<UuidCodec, SerdeJson<KeyUsage>>,
+}
+
+impl KeyUsageStore {
+    pub(crate) const fn nb_db() -> u32 {
+        NUMBER_OF_DATABASES
+    }
+
+    pub fn new(env: &Env<WithoutTls>, wtxn: &mut RwTxn) -> Result<Self> {
+        let usage = env.create_database(wtxn, Some(db_name::KEY_USAGE))?;
+        Ok(Self { usage })
+    }
+
+    pub fn get(&self, rtxn: &RoTxn, key: KeyId) -> Result<KeyUsage> {
+        Ok(self.usage.get(rtxn, &key)?.unwrap_or_default())
+    }
+
+    /// Refuses the task if it would make the key exceed one of its quotas.
+    ///
+    /// Only the tasks that already succeeded are accounted, not the ones still enqueued.
+    pub fn check(
+        &self,
+        rtxn: &RoTxn,
+        index_mapper: &IndexMapper,
+        key: KeyId,
+        quotas: &KeyQuotas,
+        kind: &KindWithContent,
+    ) -> Result<()> {
+        let KeyQuotas { max_indexes, max_documents, max_storage_bytes } = quotas;
+        let (index_uid, creates_index, documents) = match kind {
+            KindWithContent::IndexCreation { index_uid, .. } => (index_uid, true, 0),
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid,
+                allow_index_creation,
+                documents_count,
+                ..
+            } => (index_uid, *allow_index_creation, *documents_count),
+            KindWithContent::DocumentImportUrl { index_uid, allow_index_creation, .. } => {
+                (index_uid, *allow_index_creation, 0)
+            }
+            _ => return Ok(()),
+        };
+        let usage = self.get(rtxn, key)?;
+
+        if let Some(limit) = *max_indexes {
+            let creates_index = creates_index && !index_mapper.index_exists(rtxn, index_uid)?;
+            if creates_index && usage.indexes.len() as u64 >= limit {
+                return Err(Error::KeyQuotaExceeded { quota: "maxIndexes", limit });
+            }
+        }
+
+        if let Some(limit) = *max_documents {
+            if usage.documents.saturating_add(documents) > limit {
+                return Err(Error::KeyQuotaExceeded { quota: "maxDocuments", limit });
+            }
+        }
+
+        if let Some(limit) = *max_storage_bytes {
+            let mut storage = 0u64;
+            for index_uid in &usage.indexes {
+                // the indexes deleted in the meantime don't take space anymore
+                if let Ok(stats) = index_mapper.stats_of(rtxn, index_uid) {
+                    storage = storage.saturating_add(stats.database_size);
+                }
+            }
+            if storage >= limit {
+                return Err(Error::KeyQuotaExceeded { quota: "maxStorageBytes", limit });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Accounts the resources used by a task that just succeeded.
+    ///
+    /// The index created by a document addition is only accounted when it was created by the
+    /// batch that started at `batch_started_at`.
+    pub fn record(
+        &self,
+        wtxn: &mut RwTxn,
+        index_mapper: &IndexMapper,
+        task: &Task,
+        batch_started_at: OffsetDateTime,
+    ) -> Result<()> {
+        if let KindWithContent::IndexDeletion { index_uid } = &task.kind {
+            self.forget_index(wtxn, index_uid)?;
+        }
+
+        let Some(key) = task.quota_key else { return Ok(()) };
+        let mut usage = self.get(wtxn, key)?;
+        match &task.kind {
+            KindWithContent::IndexCreation { index_uid, .. } => {
+                usage.indexes.insert(index_uid.clone());
+            }
+            KindWithContent::DocumentAdditionOrUpdate { index_uid, .. }
+            | KindWithContent::DocumentImportUrl { index_uid, .. } => {
+                if let Some(Details::DocumentAdditionOrUpdate {
+                    indexed_documents: Some(indexed_documents),
+                    ..
+                }) = task.details
+                {
+                    usage.documents = usage.documents.saturating_add(indexed_documents);
+                }
+                if !usage.indexes.contains(index_uid) {
+                    let stats = index_mapper.stats_of(wtxn, index_uid);
+                    if stats.is_ok_and(|stats| stats.created_at >= batch_started_at) {
+                        usage.indexes.insert(index_uid.clone());
+                    }
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.usage.put(wtxn, &key, &usage)?;
+        Ok(())
+    }
+
+    /// Stops accounting a deleted index in the usage of the key that created it.
+    fn forget_index(&self, wtxn: &mut RwTxn, index_uid: &str) -> Result<()> {
+        let mut owners = Vec::new();
+        for entry in self.usage.iter(wtxn)? {
+            let (key, mut usage) = entry?;
+            if usage.indexes.remove(index_uid) {
+                owners.push((key, usage));
+            }
+        }
+        for (key, usage) in owners {
+            self.usage.put(wtxn, &key, &usage)?;
+        }
+        Ok(())
+    }
+}
@@ -12,6 +12,9 @@ scheduler waits to be woken up to process new tasks. It wakes up when:
 2. a new task is registered
 3. a batch of tasks has been processed
 
+The documents of the indexes having a remote source are downloaded and enqueued
+by another thread, so that a slow source never holds the processing of the tasks.
+
 It is only within this thread that the scheduler is allowed to process tasks.
 On the other hand, the publicly accessible methods of the scheduler can be
 called asynchronously from any thread. These methods can either query the
@@ -21,13 +24,19 @@ content of the scheduler or enqueue new tasks.
 mod dump;
 pub mod error;
 mod features;
+mod import_url;
 mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
+mod key_quotas;
 mod lru;
 mod processing;
 mod queue;
+mod restore_points;
+mod s3_snapshot;
 mod scheduler;
+mod sources;
+mod task_log;
 #[cfg(test)]
 mod test_utils;
 pub mod upgrade;
@@ -42,6 +51,8 @@ use std::collections::{BTreeMap, HashMap};
 use std::io::{self, BufReader, Read};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
@@ -50,22 +61,31 @@ pub use error::Error;
 pub use features::RoFeatures;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
+pub use import_url::ImportUrlOptions;
+use meilisearch_types::autobatching::AutobatchingLimits;
 use meilisearch_types::batches::Batch;
-use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
+use meilisearch_types::features::{
+    IndexAliases, InstanceTogglableFeatures, Network, RuntimeTogglableFeatures,
+};
 use meilisearch_types::heed::byteorder::BE;
 use meilisearch_types::heed::types::I128;
 use meilisearch_types::heed::{self, Env, RoTxn, WithoutTls};
+use meilisearch_types::keys::{KeyId, KeyQuotas};
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::usage::EmbedderUsage;
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, Index};
-use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{KindWithContent, Task};
+use meilisearch_types::task_view::{TaskProgressView, TaskView};
+use meilisearch_types::tasks::{KindWithContent, Status, Task};
+use meilisearch_types::webhooks::{PayloadTemplate, TaskWebhook};
 use processing::ProcessingTasks;
 pub use queue::Query;
 use queue::Queue;
 use roaring::RoaringBitmap;
+pub use s3_snapshot::S3SnapshotOptions;
 use scheduler::Scheduler;
+pub use task_log::{TaskLogEntry, TaskLogReplay};
 use time::OffsetDateTime;
 use versioning::Versioning;
 
@@ -88,12 +108,27 @@ pub struct IndexSchedulerOptions {
     pub indexes_path: PathBuf,
     /// The path to the folder containing the snapshots.
     pub snapshots_path: PathBuf,
+    /// The object storage the snapshots are uploaded to instead of the snapshots folder.
+    pub snapshot_s3: Option<S3SnapshotOptions>,
+    /// The number of snapshots kept as restore points for the point-in-time restores.
+    /// `None` disables the restore points.
+    pub restore_points: Option<usize>,
+    /// Set to `true` iff the content files of the succeeded document additions are kept until
+    /// their task is deleted, to stream the changes of the documents of the indexes.
+    pub change_stream: bool,
+    /// Set to `true` iff the succeeded tasks that changed the indexes are kept in the task log,
+    /// to be shipped to and replayed on another instance.
+    pub task_log: bool,
+    /// Set to `true` iff the indexes can be searched together through an alias.
+    pub index_aliases: bool,
     /// The path to the folder containing the dumps.
     pub dumps_path: PathBuf,
-    /// The URL on which we must send the tasks statuses
-    pub webhook_url: Option<String>,
-    /// The value we will send into the Authorization HTTP header on the webhook URL
-    pub webhook_authorization_header: Option<String>,
+    /// The hosts the documents can be imported from. `None` disables the imports from a URL.
+    pub import_url: Option<ImportUrlOptions>,
+    /// The webhooks we must send the tasks statuses to, each of them filtering the tasks it receives.
+    pub webhooks: Vec<TaskWebhook>,
+    /// The channel the tasks are sent to every time they are enqueued, start processing or finish.
+    pub task_events: Option<SyncSender<TaskView>>,
     /// The maximum size, in bytes, of the task index.
     pub task_db_size: usize,
     /// The size, in bytes, with which a meilisearch index is opened the first time of each meilisearch index.
@@ -112,6 +147,9 @@ pub struct IndexSchedulerOptions {
     /// Set to `true` iff the index scheduler is allowed to automatically
     /// delete the finished tasks when there are too many tasks.
     pub cleanup_enabled: bool,
+    /// Set to `true` iff the instance is a read-only replica, only registering the tasks
+    /// replicated by its primary with their uid.
+    pub read_only_replica: bool,
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
@@ -121,6 +159,26 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined maximum size (in bytes) of tasks at once.
     pub batched_tasks_size_limit: u64,
+    /// If the autobatcher is allowed to automatically batch tasks it will only batch
+    /// the tasks adding, updating or deleting up to this number of documents at once.
+    pub max_batched_documents: Option<u64>,
+    /// How long the document operations wait for more tasks to be batched with them
+    /// when none of the limits of their autobatch is reached. `None` never waits.
+    pub autobatching_max_wait: Option<Duration>,
+    /// The limits of the autobatches of some indexes, overriding the ones above.
+    pub index_autobatching: BTreeMap<String, AutobatchingLimits>,
+    /// The maximum number of batches targeting different indexes that can be processed at once.
+    pub max_concurrent_batches: usize,
+    /// The maximum number of documents of the small batches processed in a fast lane while
+    /// the regular lanes are busy. `None` disables the fast lane.
+    pub fast_lane_max_documents: Option<u64>,
+    /// The maximum number of documents indexed between two checkpoints of a document operation,
+    /// so that a shutdown doesn't lose the progress of a large batch. `None` disables the checkpoints.
+    pub checkpoint_documents: Option<u64>,
+    /// The number of enqueued tasks from which the new tasks are refused until the queue drains.
+    pub max_enqueued_tasks: Option<u64>,
+    /// The percentage of the disk holding the tasks from which the new tasks are refused.
+    pub disk_watermark: Option<u8>,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
     /// The experimental features enabled for this instance.
@@ -148,6 +206,8 @@ pub struct IndexScheduler {
     pub(crate) index_mapper: IndexMapper,
     /// In charge of fetching and setting the status of experimental features.
     features: features::FeatureData,
+    /// The resources used by the tasks of the API keys having quotas.
+    pub(crate) key_usage: key_quotas::KeyUsageStore,
 
     /// Everything related to the processing of the tasks
     pub scheduler: scheduler::Scheduler,
@@ -155,10 +215,25 @@ pub struct IndexScheduler {
     /// Whether we should automatically cleanup the task queue or not.
     pub(crate) cleanup_enabled: bool,
 
-    /// The webhook url we should send tasks to after processing every batches.
-    pub(crate) webhook_url: Option<String>,
-    /// The Authorization header to send to the webhook URL.
-    pub(crate) webhook_authorization_header: Option<String>,
+    /// Whether the instance is a read-only replica of a primary instance.
+    read_only_replica: bool,
+
+    /// The webhooks we should send the matching tasks to after processing every batches.
+    pub(crate) webhooks: Vec<TaskWebhook>,
+
+    /// The hosts the documents can be imported from, `None` if the imports from a URL are disabled.
+    pub(crate) import_url: Option<ImportUrlOptions>,
+
+    /// Whether the indexes can be searched together through an alias.
+    index_aliases: bool,
+
+    /// The channel the tasks are sent to every time they are enqueued, start processing or finish.
+    pub(crate) task_events: Option<SyncSender<TaskView>>,
+
+    /// The number of enqueued tasks from which the new tasks are refused.
+    max_enqueued_tasks: Option<u64>,
+    /// The percentage of the disk holding the tasks from which the new tasks are refused.
+    disk_watermark: Option<u8>,
 
     /// A map to retrieve the runtime representation of an embedder depending on its configuration.
     ///
@@ -167,6 +242,10 @@ pub struct IndexScheduler {
     /// to the same embeddings for the same input text.
     embedders: Arc<RwLock<HashMap<EmbedderOptions, Arc<Embedder>>>>,
 
+    /// The requests sent to the embedders by the searches since the start of the instance,
+    /// by index and embedder names.
+    search_embedder_usage: Arc<RwLock<HashMap<String, BTreeMap<String, EmbedderUsage>>>>,
+
     // ================= test
     // The next entry is dedicated to the tests.
     /// Provide a way to set a breakpoint in multiple part of the scheduler.
@@ -197,9 +276,15 @@ impl IndexScheduler {
 
             index_mapper: self.index_mapper.clone(),
             cleanup_enabled: self.cleanup_enabled,
-            webhook_url: self.webhook_url.clone(),
-            webhook_authorization_header: self.webhook_authorization_header.clone(),
+            read_only_replica: self.read_only_replica,
+            webhooks: self.webhooks.clone(),
+            import_url: self.import_url.clone(),
+            index_aliases: self.index_aliases,
+            task_events: self.task_events.clone(),
+            max_enqueued_tasks: self.max_enqueued_tasks,
+            disk_watermark: self.disk_watermark,
             embedders: self.embedders.clone(),
+            search_embedder_usage: self.search_embedder_usage.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
             #[cfg(test)]
@@ -207,11 +292,16 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: self.run_loop_iteration.clone(),
             features: self.features.clone(),
+            key_usage: self.key_usage.clone(),
         }
     }
 
     pub(crate) const fn nb_db() -> u32 {
-        Versioning::nb_db() + Queue::nb_db() + IndexMapper::nb_db() + features::FeatureData::nb_db()
+        Versioning::nb_db()
+            + Queue::nb_db()
+            + IndexMapper::nb_db()
+            + features::FeatureData::nb_db()
+            + key_quotas::KeyUsageStore::nb_db()
     }
 
     /// Create an index scheduler and start its run loop.
@@ -265,6 +355,7 @@ impl IndexScheduler {
         let features = features::FeatureData::new(&env, &mut wtxn, options.instance_features)?;
         let queue = Queue::new(&env, &mut wtxn, &options)?;
         let index_mapper = IndexMapper::new(&env, &mut wtxn, &options, budget)?;
+        let key_usage = key_quotas::KeyUsageStore::new(&env, &mut wtxn)?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
@@ -277,9 +368,15 @@ impl IndexScheduler {
             index_mapper,
             env,
             cleanup_enabled: options.cleanup_enabled,
-            webhook_url: options.webhook_url,
-            webhook_authorization_header: options.webhook_authorization_header,
+            read_only_replica: options.read_only_replica,
+            webhooks: options.webhooks,
+            import_url: options.import_url,
+            index_aliases: options.index_aliases,
+            task_events: options.task_events,
+            max_enqueued_tasks: options.max_enqueued_tasks,
+            disk_watermark: options.disk_watermark,
             embedders: Default::default(),
+            search_embedder_usage: Default::default(),
 
             #[cfg(test)]
             test_breakpoint_sdr,
@@ -288,6 +385,7 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: Arc::new(RwLock::new(0)),
             features,
+            key_usage,
         };
 
         this.run();
@@ -392,7 +490,25 @@ impl IndexScheduler {
                     let ret = catch_unwind(AssertUnwindSafe(|| run.tick()));
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
-                        Ok(Ok(TickOutcome::WaitForSignal)) => run.scheduler.wake_up.wait(),
+                        Ok(Ok(TickOutcome::WaitForSignal)) => {
+                            let next_wake_up = run.time_until_next_scheduled_task().and_then(|scheduled| {
+                                let scheduled =
+                                    scheduled.map(|timeout| timeout.max(Duration::from_secs(1)));
+                                // The gathering autobatches must be closed right on time.
+                                let autobatch = run.time_until_next_autobatch()?;
+                                Ok(scheduled.into_iter().chain(autobatch).min())
+                            });
+                            match next_wake_up {
+                                Ok(Some(timeout)) => {
+                                    run.scheduler.wake_up.wait_timeout(timeout);
+                                }
+                                Ok(None) => run.scheduler.wake_up.wait(),
+                                Err(e) => {
+                                    tracing::error!("{e}");
+                                    run.scheduler.wake_up.wait();
+                                }
+                            }
+                        }
                         Ok(Ok(TickOutcome::StopProcessingForever)) => break,
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
@@ -409,6 +525,30 @@ impl IndexScheduler {
                 }
             })
             .unwrap();
+
+        let sources = self.private_clone();
+        std::thread::Builder::new()
+            .name(String::from("index-sources"))
+            .spawn(move || loop {
+                if let Err(e) = sources.refresh_due_index_sources() {
+                    tracing::error!("While refreshing the index sources: {e}");
+                }
+                match sources.time_until_next_source_refresh() {
+                    // Never spin if a source keeps failing to be refreshed.
+                    Ok(Some(timeout)) => {
+                        sources
+                            .scheduler
+                            .sources_wake_up
+                            .wait_timeout(timeout.max(Duration::from_secs(1)));
+                    }
+                    Ok(None) => sources.scheduler.sources_wake_up.wait(),
+                    Err(e) => {
+                        tracing::error!("{e}");
+                        sources.scheduler.sources_wake_up.wait_timeout(Duration::from_secs(60));
+                    }
+                }
+            })
+            .unwrap();
     }
 
     pub fn indexer_config(&self) -> &IndexerConfig {
@@ -540,6 +680,75 @@ impl IndexScheduler {
         Ok(nbr_index_processing_tasks > 0)
     }
 
+    /// Return the progress of the given task, or `None` if it isn't processing.
+    ///
+    /// The progress is the one of the whole batch the task belongs to.
+    pub fn task_progress(&self, task_id: TaskId) -> Option<TaskProgressView> {
+        let processing = self.processing_tasks.read().unwrap();
+        let entry = processing.batch_of_task(task_id)?;
+        let elapsed = OffsetDateTime::now_utc() - entry.batch.started_at;
+        Some(TaskProgressView::new(entry.progress.as_progress_view(), elapsed))
+    }
+
+    /// Stop creating new batches, the processing batches are left to finish.
+    pub fn pause(&self) {
+        self.scheduler.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Start creating new batches again after a call to [`Self::pause`].
+    pub fn resume(&self) {
+        self.scheduler.paused.store(false, Ordering::Relaxed);
+        self.scheduler.wake_up.signal();
+    }
+
+    /// Return true if the scheduler has been paused.
+    pub fn is_paused(&self) -> bool {
+        self.scheduler.paused.load(Ordering::Relaxed)
+    }
+
+    /// Refuse the new tasks and stop creating new batches, so that the instance can be removed
+    /// once the processing batches are finished.
+    ///
+    /// The document operations commit the documents indexed so far at their next checkpoint and
+    /// leave their remaining tasks enqueued. There is no way back, the instance must be restarted.
+    pub fn drain(&self) {
+        self.scheduler.draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Return true if the instance is draining, see [`Self::drain`].
+    pub fn is_draining(&self) -> bool {
+        // the tick checks the flag again under the lock of the processing batches, it must see
+        // the drain if `is_drained` saw it before taking the lock
+        self.scheduler.draining.load(Ordering::SeqCst)
+    }
+
+    /// Return true once the instance is draining, all its batches are finished and their webhooks
+    /// are sent.
+    pub fn is_drained(&self) -> bool {
+        self.is_draining()
+            && self.processing_tasks.read().unwrap().batches.is_empty()
+            && self.scheduler.notifying_batches.load(Ordering::Relaxed) == 0
+    }
+
+    /// Stop creating new batches and wait, at most `timeout`, for the processing batches to finish.
+    ///
+    /// The document operations commit the documents indexed so far at their next checkpoint and
+    /// leave their remaining tasks enqueued, so that they resume from there after the restart.
+    /// Returns `false` if some batches were still processing after the timeout.
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        self.scheduler.shutting_down.store(true, Ordering::Relaxed);
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            if self.processing_tasks.read().unwrap().batches.is_empty() {
+                return true;
+            }
+            if std::time::Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
     /// Return the tasks matching the query from the user's point of view along
     /// with the total number of tasks matching the query, ignoring from and limit.
     ///
@@ -624,6 +833,38 @@ impl IndexScheduler {
         kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+    ) -> Result<Task> {
+        self.register_with_metadata(kind, task_id, None, BTreeMap::new(), dry_run)
+    }
+
+    /// Register a new task in the scheduler that will only be processed once
+    /// the `depends_on` task succeeded, or fail if it doesn't, and that carries
+    /// the given `key=value` labels.
+    ///
+    /// If it fails and data was associated with the task, it tries to delete the associated data.
+    pub fn register_with_metadata(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        depends_on: Option<TaskId>,
+        labels: BTreeMap<String, String>,
+        dry_run: bool,
+    ) -> Result<Task> {
+        self.register_for_key(kind, task_id, depends_on, labels, None, dry_run)
+    }
+
+    /// Register a new task like [`Self::register_with_metadata`], counting it against the
+    /// quotas of the API key that registers it.
+    ///
+    /// The task is refused if it would make the key exceed one of its quotas.
+    pub fn register_for_key(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        depends_on: Option<TaskId>,
+        labels: BTreeMap<String, String>,
+        quotas: Option<(KeyId, &KeyQuotas)>,
+        dry_run: bool,
     ) -> Result<Task> {
         // if the task doesn't delete anything and 50% of the task queue is full, we must refuse to enqueue the incomming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } if !tasks.is_empty())
@@ -632,8 +873,48 @@ impl IndexScheduler {
             return Err(Error::NoSpaceLeftInTaskQueue);
         }
 
+        // the task cancelations stop the writes in progress, and the dumps and snapshots don't
+        // modify anything, they are the only tasks accepted in read-only mode
+        if !matches!(
+            &kind,
+            KindWithContent::TaskCancelation { .. }
+                | KindWithContent::DumpCreation { .. }
+                | KindWithContent::SnapshotCreation
+        ) {
+            self.features().check_writes_allowed()?;
+        }
+
+        if self.is_draining() {
+            return Err(Error::Draining);
+        }
+
+        // a replica only registers the tasks replicated by its primary, which keep their uid
+        if self.read_only_replica
+            && task_id.is_none()
+            && !matches!(
+                &kind,
+                KindWithContent::DumpCreation { .. } | KindWithContent::SnapshotCreation
+            )
+        {
+            return Err(Error::ReadOnlyReplica);
+        }
+
+        // the task cancelations and deletions help the queue to drain, we never refuse them
+        if !matches!(
+            &kind,
+            KindWithContent::TaskCancelation { .. } | KindWithContent::TaskDeletion { .. }
+        ) {
+            self.check_backpressure()?;
+        }
+
         let mut wtxn = self.env.write_txn()?;
-        let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run)?;
+        if let Some((key, quotas)) = quotas {
+            self.key_usage.check(&wtxn, &self.index_mapper, key, quotas, &kind)?;
+        }
+        let quota_key = quotas.map(|(key, _)| key);
+        let task = self
+            .queue
+            .register(&mut wtxn, &kind, task_id, depends_on, labels, quota_key, dry_run)?;
 
         // If the registered task is a task cancelation
         // we inform the processing tasks to stop (if necessary).
@@ -650,11 +931,77 @@ impl IndexScheduler {
             return Err(e.into());
         }
 
+        if !dry_run {
+            self.publish_task_events([&task]);
+        }
+
         // notify the scheduler loop to execute a new tick
         self.scheduler.wake_up.signal();
         Ok(task)
     }
 
+    /// Returns an error if the task queue is too long or the disk too full to accept new tasks.
+    fn check_backpressure(&self) -> Result<()> {
+        if let Some(max_enqueued_tasks) = self.max_enqueued_tasks {
+            if self.enqueued_tasks_count()? >= max_enqueued_tasks {
+                return Err(Error::TooManyEnqueuedTasks(max_enqueued_tasks));
+            }
+        }
+
+        if let Some(disk_watermark) = self.disk_watermark {
+            let usage = utils::disk_usage_percentage(self.env.path());
+            if usage.is_some_and(|usage| usage > disk_watermark) {
+                return Err(Error::DiskWatermarkReached(disk_watermark));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of tasks waiting to be processed or being processed.
+    pub fn enqueued_tasks_count(&self) -> Result<u64> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.queue.tasks.get_status(&rtxn, Status::Enqueued)?.len())
+    }
+
+    /// Returns the dates the indexes can be restored from, oldest first.
+    ///
+    /// The indexes can be restored to any date after the oldest one, by replaying the tasks
+    /// processed since the latest restore point before the date.
+    pub fn restore_points(&self) -> Result<Vec<OffsetDateTime>> {
+        if self.scheduler.restore_points.is_none() {
+            return Err(Error::RestorePointsDisabled);
+        }
+        let restore_points = restore_points::list(&self.scheduler.snapshots_path)?;
+        Ok(restore_points.into_iter().map(|(date, _)| date).collect())
+    }
+
+    /// Returns the tasks that succeeded on the index after the task `since`, oldest first, to
+    /// stream the changes of its documents.
+    ///
+    /// The tasks following the first task of the index that isn't processed yet are left out, so
+    /// that a client resuming from the last returned task doesn't miss any change.
+    pub fn document_change_tasks(
+        &self,
+        index_uid: &str,
+        since: Option<TaskId>,
+    ) -> Result<Vec<Task>> {
+        if !self.scheduler.change_stream {
+            return Err(Error::ChangeStreamDisabled);
+        }
+        let rtxn = self.env.read_txn()?;
+        let mut tasks = self.queue.tasks.index_tasks(&rtxn, index_uid)?;
+        if let Some(since) = since {
+            tasks.remove_range(..=since);
+        }
+        let enqueued = self.queue.tasks.get_status(&rtxn, Status::Enqueued)?;
+        if let Some(first_enqueued) = (&tasks & enqueued).min() {
+            tasks.remove_range(first_enqueued..);
+        }
+        tasks &= self.queue.tasks.get_status(&rtxn, Status::Succeeded)?;
+        self.queue.tasks.get_existing_tasks(&rtxn, tasks)
+    }
+
     /// Register a new task coming from a dump in the scheduler.
     /// By taking a mutable ref we're pretty sure no one will ever import a dump while actix is running.
     pub fn register_dumped_task(&mut self) -> Result<Dump> {
@@ -685,94 +1032,227 @@ impl IndexScheduler {
         Ok(())
     }
 
-    /// Once the tasks changes have been committed we must send all the tasks that were updated to our webhook if there is one.
+    /// Once the tasks changes have been committed we must send all the tasks that were updated to the webhooks they match.
     fn notify_webhook(&self, updated: &RoaringBitmap) -> Result<()> {
-        if let Some(ref url) = self.webhook_url {
-            struct TaskReader<'a, 'b> {
-                rtxn: &'a RoTxn<'a>,
-                index_scheduler: &'a IndexScheduler,
-                tasks: &'b mut roaring::bitmap::Iter<'b>,
-                buffer: Vec<u8>,
-                written: usize,
-            }
+        if self.webhooks.is_empty() {
+            return Ok(());
+        }
 
-            impl<'a, 'b> Read for TaskReader<'a, 'b> {
-                fn read(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
-                    if self.buffer.is_empty() {
-                        match self.tasks.next() {
-                            None => return Ok(0),
-                            Some(task_id) => {
-                                let task = self
-                                    .index_scheduler
-                                    .queue
-                                    .tasks
-                                    .get_task(self.rtxn, task_id)
-                                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
-                                    .ok_or_else(|| {
-                                        io::Error::new(
-                                            io::ErrorKind::Other,
-                                            Error::CorruptedTaskQueue,
-                                        )
-                                    })?;
-
-                                serde_json::to_writer(
-                                    &mut self.buffer,
-                                    &TaskView::from_task(&task),
-                                )?;
-                                self.buffer.push(b'\n');
-                            }
+        struct TaskReader<'a, 'b> {
+            rtxn: &'a RoTxn<'a>,
+            index_scheduler: &'a IndexScheduler,
+            tasks: &'b mut roaring::bitmap::Iter<'b>,
+            buffer: Vec<u8>,
+            written: usize,
+        }
+
+        impl<'a, 'b> Read for TaskReader<'a, 'b> {
+            fn read(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.buffer.is_empty() {
+                    match self.tasks.next() {
+                        None => return Ok(0),
+                        Some(task_id) => {
+                            let task = self
+                                .index_scheduler
+                                .queue
+                                .tasks
+                                .get_task(self.rtxn, task_id)
+                                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+                                .ok_or_else(|| {
+                                    io::Error::new(io::ErrorKind::Other, Error::CorruptedTaskQueue)
+                                })?;
+
+                            serde_json::to_writer(&mut self.buffer, &TaskView::from_task(&task))?;
+                            self.buffer.push(b'\n');
                         }
                     }
+                }
 
-                    let mut to_write = &self.buffer[self.written..];
-                    let wrote = io::copy(&mut to_write, &mut buf)?;
-                    self.written += wrote as usize;
+                let mut to_write = &self.buffer[self.written..];
+                let wrote = io::copy(&mut to_write, &mut buf)?;
+                self.written += wrote as usize;
 
-                    // we wrote everything and must refresh our buffer on the next call
-                    if self.written == self.buffer.len() {
-                        self.written = 0;
-                        self.buffer.clear();
-                    }
+                // we wrote everything and must refresh our buffer on the next call
+                if self.written == self.buffer.len() {
+                    self.written = 0;
+                    self.buffer.clear();
+                }
+
+                Ok(wrote as usize)
+            }
+        }
+
+        let rtxn = self.env.read_txn()?;
 
-                    Ok(wrote as usize)
+        // We retrieve the tasks once and dispatch them to the webhooks they match.
+        let mut matching_tasks = vec![RoaringBitmap::new(); self.webhooks.len()];
+        for task_id in updated {
+            let task =
+                self.queue.tasks.get_task(&rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            for (webhook, matching) in self.webhooks.iter().zip(&mut matching_tasks) {
+                if webhook.matches(&task) {
+                    matching.insert(task_id);
                 }
             }
+        }
 
-            let rtxn = self.env.read_txn()?;
+        for (webhook, matching) in self.webhooks.iter().zip(&matching_tasks) {
+            if matching.is_empty() {
+                continue;
+            }
+
+            match webhook.parse_payload_template() {
+                Ok(None) => (),
+                Ok(Some(template)) => {
+                    self.notify_templated_webhook(&rtxn, webhook, &template, matching)?;
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "While parsing the payload template of the webhook {}: {e}",
+                        webhook.url
+                    );
+                    continue;
+                }
+            }
 
             let task_reader = TaskReader {
                 rtxn: &rtxn,
                 index_scheduler: self,
-                tasks: &mut updated.into_iter(),
+                tasks: &mut matching.into_iter(),
                 buffer: Vec::with_capacity(50), // on average a task is around ~100 bytes
                 written: 0,
             };
 
-            // let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
             let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
-            let request = ureq::post(url)
+            let request = ureq::post(&webhook.url)
                 .timeout(Duration::from_secs(30))
                 .set("Content-Encoding", "gzip")
                 .set("Content-Type", "application/x-ndjson");
-            let request = match &self.webhook_authorization_header {
+            let request = match &webhook.authorization_header {
                 Some(header) => request.set("Authorization", header),
                 None => request,
             };
 
             if let Err(e) = request.send(reader) {
-                tracing::error!("While sending data to the webhook: {e}");
+                tracing::error!("While sending data to the webhook {}: {e}", webhook.url);
             }
         }
 
         Ok(())
     }
 
+    /// Queues one request per task to a webhook, its body being the rendered payload template.
+    ///
+    /// The requests are sent by the `templated-webhooks` thread, and dropped when too many are
+    /// already queued, so that a slow webhook never slows down the scheduler.
+    fn notify_templated_webhook(
+        &self,
+        rtxn: &RoTxn,
+        webhook: &TaskWebhook,
+        template: &PayloadTemplate,
+        tasks: &RoaringBitmap,
+    ) -> Result<()> {
+        let Some(requests) = &self.scheduler.templated_webhook_requests else { return Ok(()) };
+        for task_id in tasks {
+            let task =
+                self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            let payload = match template.render(&TaskView::from_task(&task)) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::error!(
+                        "While rendering the payload of the task {task_id} for the webhook {}: {e}",
+                        webhook.url
+                    );
+                    continue;
+                }
+            };
+
+            let request = TemplatedWebhookRequest {
+                url: webhook.url.clone(),
+                authorization_header: webhook.authorization_header.clone(),
+                payload,
+            };
+            // the instance isn't drained until the queued requests are sent
+            self.scheduler.notifying_batches.fetch_add(1, Ordering::Relaxed);
+            match requests.try_send(request) {
+                Ok(()) => (),
+                Err(TrySendError::Full(request)) => {
+                    self.scheduler.notifying_batches.fetch_sub(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "The templated webhooks queue is full, dropping the notification of task {task_id} to the webhook {}",
+                        request.url
+                    );
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    self.scheduler.notifying_batches.fetch_sub(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the current state of the tasks to the task events channel.
+    ///
+    /// An event is dropped when the channel is full, a slow consumer must never slow down the scheduler.
+    fn publish_task_events<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) {
+        let Some(task_events) = &self.task_events else { return };
+        for task in tasks {
+            match task_events.try_send(TaskView::from_task(task)) {
+                Ok(()) => (),
+                Err(TrySendError::Full(view)) => {
+                    tracing::warn!(
+                        "The task events channel is full, dropping the event of task {}",
+                        view.uid
+                    )
+                }
+                Err(TrySendError::Disconnected(_)) => return,
+            }
+        }
+    }
+
+    /// Once the tasks changes have been committed we must publish the new state of all the tasks that were updated.
+    fn publish_updated_task_events(&self, updated: &RoaringBitmap) -> Result<()> {
+        if self.task_events.is_none() {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn()?;
+        let tasks = self.queue.tasks.get_existing_tasks(&rtxn, updated)?;
+        self.publish_task_events(&tasks);
+        Ok(())
+    }
+
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
         let index_stats = self.index_mapper.stats_of(&rtxn, index_uid)?;
+        let search_embedder_usage =
+            self.search_embedder_usage.read().unwrap().get(index_uid).cloned().unwrap_or_default();
 
-        Ok(IndexStats { is_indexing, inner_stats: index_stats })
+        Ok(IndexStats { is_indexing, inner_stats: index_stats, search_embedder_usage })
+    }
+
+    /// Records the requests sent to an embedder of an index by a search.
+    pub fn record_search_embedder_usage(
+        &self,
+        index_uid: &str,
+        embedder_name: &str,
+        usage: EmbedderUsage,
+    ) {
+        if usage.is_empty() {
+            return;
+        }
+        let mut search_embedder_usage = self.search_embedder_usage.write().unwrap();
+        let index_usage = search_embedder_usage.entry(index_uid.to_string()).or_default();
+        *index_usage.entry(embedder_name.to_string()).or_default() += usage;
+    }
+
+    /// Forgets the search usage of the embedders of a deleted index.
+    pub(crate) fn forget_search_embedder_usage(&self, index_uid: &str) {
+        self.search_embedder_usage.write().unwrap().remove(index_uid);
     }
 
     pub fn features(&self) -> RoFeatures {
@@ -795,6 +1275,27 @@ impl IndexScheduler {
         self.features.network()
     }
 
+    /// Replaces the index aliases, even when they are disabled so a dump can import them.
+    pub fn put_index_aliases(&self, index_aliases: IndexAliases) -> Result<()> {
+        let wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.features.put_index_aliases(wtxn, index_aliases)?;
+        Ok(())
+    }
+
+    /// The indexes searched through each alias, by alias.
+    pub fn index_aliases(&self) -> Result<IndexAliases> {
+        if !self.index_aliases {
+            return Err(Error::IndexAliasesDisabled);
+        }
+        Ok(self.features.index_aliases())
+    }
+
+    /// Returns the indexes searched through the alias, `None` if it is not an alias or if the
+    /// aliases are disabled.
+    pub fn resolve_index_alias(&self, alias: &str) -> Option<Vec<String>> {
+        self.index_aliases().ok()?.remove(alias)
+    }
+
     pub fn embedders(
         &self,
         index_uid: String,
@@ -857,6 +1358,43 @@ pub enum TickOutcome {
     StopProcessingForever,
 }
 
+/// How many requests to the templated webhooks can wait to be sent before being dropped.
+const TEMPLATED_WEBHOOK_QUEUE_SIZE: usize = 1024;
+
+/// A request to a webhook having a payload template.
+pub(crate) struct TemplatedWebhookRequest {
+    url: String,
+    authorization_header: Option<String>,
+    payload: String,
+}
+
+/// Spawns the thread sending the requests to the webhooks having a payload template, one at a time.
+///
+/// `notifying` is decremented once each request is sent, or failed to be.
+pub(crate) fn spawn_templated_webhooks_sender(
+    notifying: Arc<AtomicUsize>,
+) -> SyncSender<TemplatedWebhookRequest> {
+    let (sender, receiver) = sync_channel::<TemplatedWebhookRequest>(TEMPLATED_WEBHOOK_QUEUE_SIZE);
+    std::thread::Builder::new()
+        .name(String::from("templated-webhooks"))
+        .spawn(move || {
+            let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs(10)).build();
+            for TemplatedWebhookRequest { url, authorization_header, payload } in receiver {
+                let request = agent.post(&url).set("Content-Type", "application/json");
+                let request = match &authorization_header {
+                    Some(header) => request.set("Authorization", header),
+                    None => request,
+                };
+                if let Err(e) = request.send_string(&payload) {
+                    tracing::error!("While sending data to the webhook {url}: {e}");
+                }
+                notifying.fetch_sub(1, Ordering::Relaxed);
+            }
+        })
+        .unwrap();
+    sender
+}
+
 /// How many indexes we can afford to have open simultaneously.
 struct IndexBudget {
     /// Map size of an index.
@@ -876,4 +1414,6 @@ pub struct IndexStats {
     pub is_indexing: bool,
     /// Internal stats computed from the index.
     pub inner_stats: index_mapper::IndexStats,
+    /// The requests sent to the embedders by the searches since the start of the instance.
+    pub search_embedder_usage: BTreeMap<String, EmbedderUsage>,
 }
@@ -1,18 +1,29 @@
 use std::sync::Arc;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::milli::progress::{AtomicSubStep, NamedStep, Progress, ProgressView};
 use meilisearch_types::milli::{make_atomic_progress, make_enum_progress};
-use roaring::RoaringBitmap;
+use meilisearch_types::tasks::TaskId;
+use roaring::{MultiOps, RoaringBitmap};
 
 use crate::utils::ProcessingBatch;
 
 #[derive(Clone, Default)]
 pub struct ProcessingTasks {
-    pub batch: Option<Arc<ProcessingBatch>>,
-    /// The list of tasks ids that are currently running.
+    /// The batches that are currently processing, in the order they started.
+    pub batches: Vec<ProcessingEntry>,
+    /// The list of tasks ids that are currently running, across all the processing batches.
     pub processing: Arc<RoaringBitmap>,
-    /// The progress on processing tasks
-    pub progress: Option<Progress>,
+}
+
+/// A batch that is currently processing.
+#[derive(Clone)]
+pub struct ProcessingEntry {
+    pub batch: Arc<ProcessingBatch>,
+    /// The list of tasks ids of this batch.
+    pub processing: RoaringBitmap,
+    /// The progress on the tasks of this batch.
+    pub progress: Progress,
 }
 
 impl ProcessingTasks {
@@ -21,40 +32,66 @@ impl ProcessingTasks {
         ProcessingTasks::default()
     }
 
-    pub fn get_progress_view(&self) -> Option<ProgressView> {
-        Some(self.progress.as_ref()?.as_progress_view())
+    /// Returns the processing batch with the given uid, if any.
+    pub fn get_batch(&self, batch_uid: BatchId) -> Option<&ProcessingEntry> {
+        self.batches.iter().find(|entry| entry.batch.uid == batch_uid)
+    }
+
+    /// Returns the processing batch containing the given task, if any.
+    pub fn batch_of_task(&self, task_id: TaskId) -> Option<&ProcessingEntry> {
+        self.batches.iter().find(|entry| entry.processing.contains(task_id))
+    }
+
+    /// Returns the uids of the processing batches.
+    pub fn batch_uids(&self) -> RoaringBitmap {
+        self.batches.iter().map(|entry| entry.batch.uid).collect()
+    }
+
+    pub fn get_progress_view(&self, batch_uid: BatchId) -> Option<ProgressView> {
+        Some(self.get_batch(batch_uid)?.progress.as_progress_view())
     }
 
-    /// Stores the currently processing tasks, and the date time at which it started.
+    /// Stores the currently processing tasks of a new batch, and the date time at which it started.
     pub fn start_processing(
         &mut self,
         processing_batch: ProcessingBatch,
         processing: RoaringBitmap,
     ) -> Progress {
-        self.batch = Some(Arc::new(processing_batch));
-        self.processing = Arc::new(processing);
         let progress = Progress::default();
         progress.update_progress(BatchProgress::ProcessingTasks);
-        self.progress = Some(progress.clone());
+        self.batches.retain(|entry| entry.batch.uid != processing_batch.uid);
+        self.batches.push(ProcessingEntry {
+            batch: Arc::new(processing_batch),
+            processing,
+            progress: progress.clone(),
+        });
+        self.update_processing();
 
         progress
     }
 
-    /// Set the processing tasks to an empty list
-    pub fn stop_processing(&mut self) -> Self {
-        self.progress = None;
+    /// Removes the given batch from the processing batches and returns it.
+    pub fn stop_processing(&mut self, batch_uid: BatchId) -> Option<ProcessingEntry> {
+        let position = self.batches.iter().position(|entry| entry.batch.uid == batch_uid)?;
+        let entry = self.batches.remove(position);
+        self.update_processing();
+        Some(entry)
+    }
 
-        Self {
-            batch: std::mem::take(&mut self.batch),
-            processing: std::mem::take(&mut self.processing),
-            progress: None,
-        }
+    /// Removes all the processing batches.
+    pub fn clear(&mut self) {
+        self.batches.clear();
+        self.update_processing();
     }
 
     /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
     pub fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
         !self.processing.is_disjoint(canceled_tasks)
     }
+
+    fn update_processing(&mut self) {
+        self.processing = Arc::new(self.batches.iter().map(|entry| &entry.processing).union());
+    }
 }
 
 make_enum_progress! {
@@ -103,6 +140,28 @@ make_enum_progress! {
     }
 }
 
+make_enum_progress! {
+    pub enum DumpImportProgress {
+        RetrievingTheDump,
+        ImportingTheIndexes,
+    }
+}
+
+make_enum_progress! {
+    pub enum PointInTimeRestoreProgress {
+        ExtractingTheRestorePoint,
+        ReplayingTheTasks,
+        ReplacingTheIndexes,
+    }
+}
+
+make_enum_progress! {
+    pub enum IndexRestoreProgress {
+        ExtractingTheIndex,
+        ReplacingTheIndex,
+    }
+}
+
 make_enum_progress! {
     pub enum CreateIndexProgress {
         CreatingTheIndex,
@@ -144,6 +203,13 @@ make_enum_progress! {
     }
 }
 
+make_enum_progress! {
+    pub enum DocumentImportUrlProgress {
+        ClearingDocuments,
+        IndexingDocuments,
+    }
+}
+
 make_enum_progress! {
     pub enum DocumentEditionProgress {
         RetrievingConfig,
@@ -183,8 +249,8 @@ mod test {
     #[test]
     fn one_level() {
         let mut processing = ProcessingTasks::new();
-        processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        let progress = processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -196,8 +262,8 @@ mod test {
           "percentage": 0.0
         }
         "#);
-        processing.progress.as_ref().unwrap().update_progress(BatchProgress::WritingTasksToDisk);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(BatchProgress::WritingTasksToDisk);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -214,10 +280,10 @@ mod test {
     #[test]
     fn task_progress() {
         let mut processing = ProcessingTasks::new();
-        processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
+        let progress = processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
         let (atomic, tasks) = AtomicTaskStep::new(10);
-        processing.progress.as_ref().unwrap().update_progress(tasks);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(tasks);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -235,7 +301,7 @@ mod test {
         }
         "#);
         atomic.fetch_add(6, Ordering::Relaxed);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -252,8 +318,8 @@ mod test {
           "percentage": 30.000002
         }
         "#);
-        processing.progress.as_ref().unwrap().update_progress(BatchProgress::WritingTasksToDisk);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(BatchProgress::WritingTasksToDisk);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -266,9 +332,9 @@ mod test {
         }
         "#);
         let (atomic, tasks) = AtomicTaskStep::new(5);
-        processing.progress.as_ref().unwrap().update_progress(tasks);
+        progress.update_progress(tasks);
         atomic.fetch_add(4, Ordering::Relaxed);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
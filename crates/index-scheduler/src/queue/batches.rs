@@ -279,9 +279,9 @@ impl BatchQueue {
         tasks
             .into_iter()
             .map(|batch_id| {
-                if Some(batch_id) == processing.batch.as_ref().map(|batch| batch.uid) {
-                    let mut batch = processing.batch.as_ref().unwrap().to_batch();
-                    batch.progress = processing.get_progress_view();
+                if let Some(entry) = processing.get_batch(batch_id) {
+                    let mut batch = entry.batch.to_batch();
+                    batch.progress = Some(entry.progress.as_progress_view());
                     Ok(batch)
                 } else {
                     self.get_batch(rtxn, batch_id)
@@ -310,6 +310,10 @@ impl Queue {
             types,
             index_uids,
             canceled_by,
+            labels,
+            error_codes: _,
+            min_duration: _,
+            max_duration: _,
             before_enqueued_at,
             after_enqueued_at,
             before_started_at,
@@ -318,10 +322,9 @@ impl Queue {
             after_finished_at,
         } = query;
 
+        let processing_batches = processing.batch_uids();
         let mut batches = self.batches.all_batch_ids(rtxn)?;
-        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-            batches.insert(batch_id);
-        }
+        batches |= &processing_batches;
 
         if let Some(from) = from {
             let range = if reverse.unwrap_or_default() {
@@ -342,20 +345,14 @@ impl Queue {
             for status in status {
                 match status {
                     // special case for Processing batches
-                    Status::Processing => {
-                        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-                            status_batches.insert(batch_id);
-                        }
-                    }
+                    Status::Processing => status_batches |= &processing_batches,
                     // Enqueued tasks are not stored in batches
                     Status::Enqueued => (),
                     status => status_batches |= &self.batches.get_status(rtxn, *status)?,
                 };
             }
             if !status.contains(&Status::Processing) {
-                if let Some(ref batch) = processing.batch {
-                    batches.remove(batch.uid);
-                }
+                batches -= &processing_batches;
             }
             batches &= status_batches;
         }
@@ -372,6 +369,44 @@ impl Queue {
             batches &= batches_by_task_uids;
         }
 
+        if let Some(labels) = &labels {
+            let mut label_tasks = RoaringBitmap::new();
+            for label in labels {
+                label_tasks |= self.tasks.get_label(rtxn, &label.to_string())?;
+            }
+            let mut label_batches = RoaringBitmap::new();
+            for entry in &processing.batches {
+                if !entry.processing.is_disjoint(&label_tasks) {
+                    label_batches.insert(entry.batch.uid);
+                }
+            }
+            for task in self.tasks.get_existing_tasks(rtxn, label_tasks)? {
+                if let Some(batch_uid) = task.batch_uid {
+                    label_batches.insert(batch_uid);
+                }
+            }
+            batches &= label_batches;
+        }
+
+        // A batch matches if one of its finished tasks failed with the error codes or took the durations
+        if query.filters_task_outcome() {
+            let mut finished_tasks = self.tasks.get_status(rtxn, Status::Failed)?;
+            if query.error_codes.is_none() {
+                finished_tasks |= self.tasks.get_status(rtxn, Status::Succeeded)?;
+                finished_tasks |= self.tasks.get_status(rtxn, Status::Canceled)?;
+            }
+            let mut outcome_batches = RoaringBitmap::new();
+            for task_id in finished_tasks {
+                let task = self.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                if let Some(batch_uid) = task.batch_uid.filter(|uid| batches.contains(*uid)) {
+                    if query.matches_task_outcome(&task) {
+                        outcome_batches.insert(batch_uid);
+                    }
+                }
+            }
+            batches &= outcome_batches;
+        }
+
         // There is no database for this query, we must retrieve the task queried by the client and ensure it's valid
         if let Some(canceled_by) = &canceled_by {
             let mut all_canceled_batches = RoaringBitmap::new();
@@ -400,12 +435,10 @@ impl Queue {
             let mut kind_batches = RoaringBitmap::new();
             for kind in kind {
                 kind_batches |= self.batches.get_kind(rtxn, *kind)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.kinds.contains(kind).then_some(batch.uid))
-                {
-                    kind_batches.insert(uid);
+                for entry in &processing.batches {
+                    if entry.batch.kinds.contains(kind) {
+                        kind_batches.insert(entry.batch.uid);
+                    }
                 }
             }
             batches &= &kind_batches;
@@ -415,12 +448,10 @@ impl Queue {
             let mut index_batches = RoaringBitmap::new();
             for index in index {
                 index_batches |= self.batches.index_batches(rtxn, index)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.indexes.contains(index).then_some(batch.uid))
-                {
-                    index_batches.insert(uid);
+                for entry in &processing.batches {
+                    if entry.batch.indexes.contains(index) {
+                        index_batches.insert(entry.batch.uid);
+                    }
                 }
             }
             batches &= &index_batches;
@@ -432,24 +463,22 @@ impl Queue {
         // Once we have filtered the two subsets, we put them back together and assign it back to `batches`.
         batches = {
             let (mut filtered_non_processing_batches, mut filtered_processing_batches) =
-                (&batches - &*processing.processing, &batches & &*processing.processing);
+                (&batches - &processing_batches, &batches & &processing_batches);
 
             // special case for Processing batches
-            // A closure that clears the filtered_processing_batches if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_batches =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing
-                            .batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_batches.clear();
+                    for entry in &processing.batches {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &entry.batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_batches.remove(entry.batch.uid);
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -535,9 +564,9 @@ impl Queue {
         if query.index_uids.is_some() || !filters.all_indexes_authorized() {
             for kind in enum_iterator::all::<Kind>().filter(|kind| !kind.related_to_one_index()) {
                 batches -= self.tasks.get_kind(rtxn, kind)?;
-                if let Some(batch) = processing.batch.as_ref() {
-                    if batch.kinds.contains(&kind) {
-                        batches.remove(batch.uid);
+                for entry in &processing.batches {
+                    if entry.batch.kinds.contains(&kind) {
+                        batches.remove(entry.batch.uid);
                     }
                 }
             }
@@ -558,12 +587,12 @@ impl Queue {
                     forbidden_indexes |= index_tasks;
                 }
             }
-            if let Some(batch) = processing.batch.as_ref() {
-                for index in &batch.indexes {
+            for entry in &processing.batches {
+                for index in &entry.batch.indexes {
                     if filters.is_index_authorized(index) {
-                        valid_indexes.insert(batch.uid);
+                        valid_indexes.insert(entry.batch.uid);
                     } else {
-                        forbidden_indexes.insert(batch.uid);
+                        forbidden_indexes.insert(entry.batch.uid);
                     }
                 }
             }
@@ -334,10 +334,12 @@ fn query_batches_special_rules() {
     let _task = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "doggo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "whalo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
 
@@ -442,6 +444,7 @@ fn query_batches_canceled_by() {
     let _ = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "doggo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
 
@@ -13,9 +13,11 @@ use std::time::Duration;
 
 use file_store::FileStore;
 use meilisearch_types::batches::BatchId;
+use meilisearch_types::heed::types::SerdeJson;
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::keys::KeyId;
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task, TaskLabel};
 use roaring::RoaringBitmap;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
@@ -24,16 +26,18 @@ use uuid::Uuid;
 pub(crate) use self::batches::BatchQueue;
 pub(crate) use self::tasks::TaskQueue;
 use crate::processing::ProcessingTasks;
+use crate::task_log::TaskLogEntry;
 use crate::utils::{
     check_index_swap_validity, filter_out_references_to_newer_tasks, ProcessingBatch,
 };
 use crate::{Error, IndexSchedulerOptions, Result, TaskId};
 
 /// The number of database used by queue itself
-const NUMBER_OF_DATABASES: u32 = 1;
+const NUMBER_OF_DATABASES: u32 = 2;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const BATCH_TO_TASKS_MAPPING: &str = "batch-to-tasks-mapping";
+    pub const TASK_LOG: &str = "task-log";
 }
 
 /// Defines a subset of tasks to be retrieved from the [`IndexScheduler`].
@@ -69,6 +73,14 @@ pub struct Query {
     /// The [task ids](`meilisearch_types::tasks::Task::uid`) of the [`TaskCancelation`](meilisearch_types::tasks::Task::Kind::TaskCancelation) tasks
     /// that canceled the matched tasks.
     pub canceled_by: Option<Vec<TaskId>>,
+    /// The [labels](`meilisearch_types::tasks::Task::labels`) of the matched tasks, a task matches if it carries any of them.
+    pub labels: Option<Vec<TaskLabel>>,
+    /// The [error codes](`meilisearch_types::error::ResponseError::error_code`) the matched tasks failed with.
+    pub error_codes: Option<Vec<String>>,
+    /// Inclusive lower bound of the duration of the matched tasks, from their `started_at` to their `finished_at`.
+    pub min_duration: Option<time::Duration>,
+    /// Inclusive upper bound of the duration of the matched tasks, from their `started_at` to their `finished_at`.
+    pub max_duration: Option<time::Duration>,
     /// Exclusive upper bound of the matched tasks' [`enqueued_at`](meilisearch_types::tasks::Task::enqueued_at) field.
     pub before_enqueued_at: Option<OffsetDateTime>,
     /// Exclusive lower bound of the matched tasks' [`enqueued_at`](meilisearch_types::tasks::Task::enqueued_at) field.
@@ -99,6 +111,10 @@ impl Query {
                 types: None,
                 index_uids: None,
                 canceled_by: None,
+                labels: None,
+                error_codes: None,
+                min_duration: None,
+                max_duration: None,
                 before_enqueued_at: None,
                 after_enqueued_at: None,
                 before_started_at: None,
@@ -116,6 +132,26 @@ impl Query {
         Self { index_uids: Some(index_vec), ..self }
     }
 
+    /// Return `true` if the query filters the tasks on their error or their duration,
+    /// which are not indexed in any database and must be checked task by task.
+    pub(crate) fn filters_task_outcome(&self) -> bool {
+        self.error_codes.is_some() || self.min_duration.is_some() || self.max_duration.is_some()
+    }
+
+    /// Return `true` if the error and the duration of the finished task match the query.
+    pub(crate) fn matches_task_outcome(&self, task: &Task) -> bool {
+        let error_code = task.error.as_ref().map(|error| error.error_code());
+        let duration =
+            task.started_at.zip(task.finished_at).map(|(started, finished)| finished - started);
+
+        self.error_codes.as_ref().map_or(true, |codes| {
+            error_code.is_some_and(|error_code| codes.iter().any(|code| code == error_code))
+        }) && self.min_duration.map_or(true, |min| duration.is_some_and(|duration| duration >= min))
+            && self
+                .max_duration
+                .map_or(true, |max| duration.is_some_and(|duration| duration <= max))
+    }
+
     // Removes the `from` and `limit` restrictions from the query.
     // Useful to get the total number of tasks matching a filter.
     pub fn without_limits(self) -> Self {
@@ -132,6 +168,9 @@ pub struct Queue {
     /// Matches a batch id with the associated task ids.
     pub(crate) batch_to_tasks_mapping: Database<BEU32, CboRoaringBitmapCodec>,
 
+    /// The succeeded tasks that changed the indexes, by their id, when the task log is enabled.
+    pub(crate) task_log: Database<BEU32, SerdeJson<TaskLogEntry>>,
+
     /// The list of files referenced by the tasks.
     pub(crate) file_store: FileStore,
 
@@ -146,6 +185,7 @@ impl Queue {
             tasks: self.tasks.private_clone(),
             batches: self.batches.private_clone(),
             batch_to_tasks_mapping: self.batch_to_tasks_mapping,
+            task_log: self.task_log,
             file_store: self.file_store.clone(),
             max_number_of_tasks: self.max_number_of_tasks,
         }
@@ -166,6 +206,7 @@ impl Queue {
             file_store: FileStore::new(&options.update_file_path)?,
             batch_to_tasks_mapping: env
                 .create_database(wtxn, Some(db_name::BATCH_TO_TASKS_MAPPING))?,
+            task_log: env.create_database(wtxn, Some(db_name::TASK_LOG))?,
             tasks: TaskQueue::new(env, wtxn)?,
             batches: BatchQueue::new(env, wtxn)?,
             max_number_of_tasks: options.max_number_of_tasks,
@@ -257,6 +298,9 @@ impl Queue {
         wtxn: &mut RwTxn,
         kind: &KindWithContent,
         task_id: Option<TaskId>,
+        depends_on: Option<TaskId>,
+        labels: BTreeMap<String, String>,
+        quota_key: Option<KeyId>,
         dry_run: bool,
     ) -> Result<Task> {
         let next_task_id = self.tasks.next_task_id(wtxn)?;
@@ -267,6 +311,13 @@ impl Queue {
             }
         }
 
+        // A task can only depend on a task that was registered before it.
+        if let Some(depends_on) = depends_on {
+            if self.tasks.get_task(wtxn, depends_on)?.is_none() {
+                return Err(Error::InvalidTaskDependsOn(depends_on));
+            }
+        }
+
         let mut task = Task {
             uid: task_id.unwrap_or(next_task_id),
             // The batch is defined once we starts processing the task
@@ -276,6 +327,9 @@ impl Queue {
             finished_at: None,
             error: None,
             canceled_by: None,
+            depends_on,
+            labels,
+            quota_key,
             details: kind.default_details(),
             status: Status::Enqueued,
             kind: kind.clone(),
@@ -344,6 +398,8 @@ impl Queue {
                 tasks: to_delete,
             },
             None,
+            None,
+            BTreeMap::new(),
             false,
         )?;
 
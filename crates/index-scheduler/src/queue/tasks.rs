@@ -15,7 +15,7 @@ use crate::utils::{
 use crate::{Error, Result, TaskId, BEI128};
 
 /// The number of database used by the task queue
-const NUMBER_OF_DATABASES: u32 = 8;
+const NUMBER_OF_DATABASES: u32 = 10;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const ALL_TASKS: &str = "all-tasks";
@@ -24,6 +24,8 @@ mod db_name {
     pub const KIND: &str = "kind";
     pub const INDEX_TASKS: &str = "index-tasks";
     pub const CANCELED_BY: &str = "canceled_by";
+    pub const DEPENDS_ON: &str = "depends-on";
+    pub const LABELS: &str = "labels";
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
@@ -42,6 +44,10 @@ pub struct TaskQueue {
     pub(crate) index_tasks: Database<Str, RoaringBitmapCodec>,
     /// Store the tasks that were canceled by a task uid
     pub(crate) canceled_by: Database<BEU32, RoaringBitmapCodec>,
+    /// Store the enqueued tasks waiting for a task uid to succeed
+    pub(crate) depends_on: Database<BEU32, RoaringBitmapCodec>,
+    /// Store the tasks carrying a `key=value` label
+    pub(crate) labels: Database<Str, RoaringBitmapCodec>,
     /// Store the task ids of tasks which were enqueued at a specific date
     pub(crate) enqueued_at: Database<BEI128, CboRoaringBitmapCodec>,
     /// Store the task ids of finished tasks which started being processed at a specific date
@@ -58,6 +64,8 @@ impl TaskQueue {
             kind: self.kind,
             index_tasks: self.index_tasks,
             canceled_by: self.canceled_by,
+            depends_on: self.depends_on,
+            labels: self.labels,
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
@@ -75,6 +83,8 @@ impl TaskQueue {
             kind: env.create_database(wtxn, Some(db_name::KIND))?,
             index_tasks: env.create_database(wtxn, Some(db_name::INDEX_TASKS))?,
             canceled_by: env.create_database(wtxn, Some(db_name::CANCELED_BY))?,
+            depends_on: env.create_database(wtxn, Some(db_name::DEPENDS_ON))?,
+            labels: env.create_database(wtxn, Some(db_name::LABELS))?,
             enqueued_at: env.create_database(wtxn, Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(wtxn, Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(wtxn, Some(db_name::FINISHED_AT))?,
@@ -119,6 +129,15 @@ impl TaskQueue {
             })?;
         }
 
+        // A task stops waiting for its dependency as soon as it leaves the enqueued status.
+        if let Some(depends_on) = task.depends_on {
+            if old_task.status == Status::Enqueued && task.status != Status::Enqueued {
+                self.update_dependents(wtxn, depends_on, |bitmap| {
+                    bitmap.remove(task.uid);
+                })?;
+            }
+        }
+
         if old_task.kind.as_kind() != task.kind.as_kind() {
             self.update_kind(wtxn, old_task.kind.as_kind(), |bitmap| {
                 bitmap.remove(task.uid);
@@ -183,6 +202,50 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// Returns the enqueued tasks waiting for the given task to succeed.
+    pub(crate) fn get_dependents(&self, rtxn: &RoTxn, task_id: TaskId) -> Result<RoaringBitmap> {
+        Ok(self.depends_on.get(rtxn, &task_id)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_dependents(
+        &self,
+        wtxn: &mut RwTxn,
+        task_id: TaskId,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_dependents(wtxn, task_id)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.depends_on.delete(wtxn, &task_id)?;
+        } else {
+            self.depends_on.put(wtxn, &task_id, &tasks)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tasks carrying the given `key=value` label.
+    pub(crate) fn get_label(&self, rtxn: &RoTxn, label: &str) -> Result<RoaringBitmap> {
+        Ok(self.labels.get(rtxn, label)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_label(
+        &self,
+        wtxn: &mut RwTxn,
+        label: &str,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_label(wtxn, label)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.labels.delete(wtxn, label)?;
+        } else {
+            self.labels.put(wtxn, label, &tasks)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn get_status(&self, rtxn: &RoTxn, status: Status) -> Result<RoaringBitmap> {
         Ok(self.status.get(rtxn, &status)?.unwrap_or_default())
     }
@@ -267,6 +330,18 @@ impl TaskQueue {
             bitmap.insert(task.uid);
         })?;
 
+        if let Some(depends_on) = task.depends_on {
+            self.update_dependents(wtxn, depends_on, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+
+        for (key, value) in &task.labels {
+            self.update_label(wtxn, &format!("{key}={value}"), |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+
         utils::insert_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
 
         Ok(())
@@ -281,7 +356,7 @@ impl Queue {
         query: &Query,
         processing_tasks: &ProcessingTasks,
     ) -> Result<RoaringBitmap> {
-        let ProcessingTasks { batch: processing_batch, processing: processing_tasks, progress: _ } =
+        let ProcessingTasks { batches: processing_batches, processing: processing_tasks } =
             processing_tasks;
         let Query {
             limit,
@@ -293,6 +368,10 @@ impl Queue {
             types,
             index_uids,
             canceled_by,
+            labels,
+            error_codes: _,
+            min_duration: _,
+            max_duration: _,
             before_enqueued_at,
             after_enqueued_at,
             before_started_at,
@@ -315,8 +394,10 @@ impl Queue {
         if let Some(batch_uids) = batch_uids {
             let mut batch_tasks = RoaringBitmap::new();
             for batch_uid in batch_uids {
-                if processing_batch.as_ref().map_or(false, |batch| batch.uid == *batch_uid) {
-                    batch_tasks |= &**processing_tasks;
+                if let Some(entry) =
+                    processing_batches.iter().find(|entry| entry.batch.uid == *batch_uid)
+                {
+                    batch_tasks |= &entry.processing;
                 } else {
                     batch_tasks |= self.tasks_in_batch(rtxn, *batch_uid)?;
                 }
@@ -363,6 +444,14 @@ impl Queue {
             }
         }
 
+        if let Some(labels) = labels {
+            let mut label_tasks = RoaringBitmap::new();
+            for label in labels {
+                label_tasks |= self.tasks.get_label(rtxn, &label.to_string())?;
+            }
+            tasks &= &label_tasks;
+        }
+
         if let Some(kind) = types {
             let mut kind_tasks = RoaringBitmap::new();
             for kind in kind {
@@ -388,20 +477,19 @@ impl Queue {
                 (&tasks - &**processing_tasks, &tasks & &**processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_tasks of the batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing_batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
+                    for entry in processing_batches {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &entry.batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_tasks -= &entry.processing;
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -444,6 +532,33 @@ impl Queue {
             *before_finished_at,
         )?;
 
+        // Only the finished tasks have an error or a duration, the remaining ones are then checked
+        // one by one, in order, until enough of them match the query.
+        if query.filters_task_outcome() {
+            tasks -= &**processing_tasks;
+            tasks -= self.tasks.get_status(rtxn, Status::Enqueued)?;
+            if query.error_codes.is_some() {
+                tasks &= self.tasks.get_status(rtxn, Status::Failed)?;
+            }
+
+            let ordered: Box<dyn Iterator<Item = TaskId>> = if query.reverse.unwrap_or_default() {
+                Box::new(tasks.iter())
+            } else {
+                Box::new(tasks.iter().rev())
+            };
+            let mut matching = RoaringBitmap::new();
+            for task_id in ordered {
+                if limit.is_some_and(|limit| matching.len() >= limit as u64) {
+                    break;
+                }
+                let task = self.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                if query.matches_task_outcome(&task) {
+                    matching.insert(task_id);
+                }
+            }
+            tasks = matching;
+        }
+
         if let Some(limit) = limit {
             tasks = if query.reverse.unwrap_or_default() {
                 tasks.into_iter().take(*limit as usize).collect()
@@ -512,26 +627,19 @@ impl Queue {
             .tasks
             .get_existing_tasks(rtxn, tasks.take(query.limit.unwrap_or(u32::MAX) as usize))?;
 
-        let ProcessingTasks { batch, processing, progress: _ } = processing_tasks;
-
         let ret = tasks.into_iter();
-        if processing.is_empty() || batch.is_none() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
-            // Safe because we ensured there was a batch in the previous branch
-            let batch = batch.as_ref().unwrap();
             Ok((
-                ret.map(|task| {
-                    if processing.contains(task.uid) {
-                        Task {
-                            status: Status::Processing,
-                            batch_uid: Some(batch.uid),
-                            started_at: Some(batch.started_at),
-                            ..task
-                        }
-                    } else {
-                        task
-                    }
+                ret.map(|task| match processing_tasks.batch_of_task(task.uid) {
+                    Some(entry) => Task {
+                        status: Status::Processing,
+                        batch_uid: Some(entry.batch.uid),
+                        started_at: Some(entry.batch.started_at),
+                        ..task
+                    },
+                    None => task,
                 })
                 .collect(),
                 total,
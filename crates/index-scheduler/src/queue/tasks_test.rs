@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
+use big_s::S;
 use meili_snap::snapshot;
 use meilisearch_auth::AuthFilter;
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::tasks::{IndexSwap, KindWithContent, Status};
+use meilisearch_types::tasks::{IndexSwap, KindWithContent, Status, TaskLabel};
 use time::{Duration, OffsetDateTime};
 
 use crate::insta_snapshot::{snapshot_bitmap, snapshot_index_scheduler};
@@ -305,10 +308,12 @@ fn query_tasks_special_rules() {
     let _task = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "doggo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "whalo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
 
@@ -400,6 +405,7 @@ fn query_tasks_canceled_by() {
     let _ = index_scheduler.register(kind, None, false).unwrap();
     let kind = KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: ("catto".to_owned(), "doggo".to_owned()) }],
+        execute_at: None,
     };
     let _task = index_scheduler.register(kind, None, false).unwrap();
 
@@ -439,3 +445,101 @@ fn query_tasks_canceled_by() {
     // Return only 1 because the user is not authorized to see task 2
     snapshot!(snapshot_bitmap(&tasks), @"[1,]");
 }
+
+#[test]
+fn query_tasks_labels() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let labels = BTreeMap::from([(S("source"), S("catalog-sync")), (S("deploy"), S("2024-10-01"))]);
+    let kind = index_creation_task("doggo", "bone");
+    let _task = index_scheduler.register_with_metadata(kind, None, None, labels, false).unwrap();
+    let labels = BTreeMap::from([(S("source"), S("manual"))]);
+    let kind = index_creation_task("catto", "his_own_vomit");
+    let _task = index_scheduler.register_with_metadata(kind, None, None, labels, false).unwrap();
+    let kind = index_creation_task("whalo", "plankton");
+    let _task = index_scheduler.register(kind, None, false).unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let proc = index_scheduler.processing_tasks.read().unwrap();
+    let label = |label: &str| label.parse::<TaskLabel>().unwrap();
+
+    let query = Query { labels: Some(vec![label("source=catalog-sync")]), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[0,]");
+
+    // a task matches if it carries any of the labels
+    let query = Query {
+        labels: Some(vec![label("source=manual"), label("deploy=2024-10-01")]),
+        ..Query::default()
+    };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[0,1,]");
+
+    let query = Query { labels: Some(vec![label("source=unknown")]), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[]");
+}
+
+#[test]
+fn query_tasks_error_codes_and_durations() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let kind = index_creation_task("doggo", "bone");
+    let _task = index_scheduler.register(kind, None, false).unwrap();
+    handle.advance_one_successful_batch();
+    let kind = index_creation_task("doggo", "bone");
+    let _task = index_scheduler.register(kind, None, false).unwrap();
+    handle.advance_one_failed_batch();
+    let kind = index_creation_task("catto", "his_own_vomit");
+    let _task = index_scheduler.register(kind, None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let proc = index_scheduler.processing_tasks.read().unwrap();
+
+    let query = Query { error_codes: Some(vec![S("index_already_exists")]), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[1,]");
+
+    let query = Query { error_codes: Some(vec![S("invalid_document_fields")]), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[]");
+
+    // the enqueued task has no duration
+    let query = Query { min_duration: Some(Duration::ZERO), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[0,1,]");
+
+    let query = Query { min_duration: Some(Duration::HOUR), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[]");
+
+    // the limit applies to the matching tasks, the newest first
+    let query = Query { max_duration: Some(Duration::HOUR), limit: Some(1), ..Query::default() };
+    let (tasks, _) = index_scheduler
+        .queue
+        .get_task_ids_from_authorized_indexes(&rtxn, &query, &AuthFilter::default(), &proc)
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[1,]");
+}
@@ -396,3 +396,35 @@ fn test_task_queue_is_full() {
         .unwrap();
     handle.advance_one_failed_batch();
 }
+
+#[test]
+fn test_too_many_enqueued_tasks() {
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_enqueued_tasks = Some(2);
+        None
+    });
+
+    index_scheduler.register(index_creation_task("doggo", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("catto", "id"), None, false).unwrap();
+    snapshot!(index_scheduler.enqueued_tasks_count().unwrap(), @"2");
+
+    let result =
+        index_scheduler.register(index_creation_task("whalo", "id"), None, false).unwrap_err();
+    snapshot!(result, @"Meilisearch cannot receive write operations because the task queue reached its limit of 2 enqueued tasks. Please retry once some of them have been processed.");
+    snapshot!(format!("{:?}", result.error_code()), @"TooManyEnqueuedTasks");
+    snapshot!(result.error_code().http(), @"429 Too Many Requests");
+
+    // The task cancelations are accepted to let the queue drain
+    index_scheduler
+        .register(
+            KindWithContent::TaskCancelation { query: S("test"), tasks: RoaringBitmap::new() },
+            None,
+            false,
+        )
+        .unwrap();
+
+    // Once the tasks are processed, new tasks are accepted again
+    handle.advance_n_successful_batches(3);
+    snapshot!(index_scheduler.enqueued_tasks_count().unwrap(), @"0");
+    index_scheduler.register(index_creation_task("whalo", "id"), None, false).unwrap();
+}
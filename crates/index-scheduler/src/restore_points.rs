@@ -0,0 +1,81 @@
+//! The restore points are copies of the local snapshots kept in the `restore-points` directory
+//! of the snapshots directory, named after the date they were taken at. The tasks processed
+//! since one of them can be replayed on top of it to restore the indexes to a later date.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use time::format_description::FormatItem;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::Result;
+
+const RESTORE_POINTS_DIR: &str = "restore-points";
+const EXTENSION: &str = ".snapshot";
+/// Sorts the restore points by date when sorted by name, down to the nanosecond.
+const DATE_FORMAT: &[FormatItem] =
+    format_description!("[year][month][day]-[hour][minute][second][subsecond digits:9]");
+
+/// Returns the path of the restore point taken at the date.
+pub(crate) fn path_of(snapshots_path: &Path, date: OffsetDateTime) -> PathBuf {
+    // the date format only contains numeric components and can't fail
+    let name = date.format(DATE_FORMAT).unwrap();
+    snapshots_path.join(RESTORE_POINTS_DIR).join(format!("{name}{EXTENSION}"))
+}
+
+/// Returns the date and the path of the restore points, oldest first.
+///
+/// The files of the directory that are not restore points are ignored.
+pub(crate) fn list(snapshots_path: &Path) -> Result<Vec<(OffsetDateTime, PathBuf)>> {
+    let entries = match fs::read_dir(snapshots_path.join(RESTORE_POINTS_DIR)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut restore_points = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+        let Some(date) = name.strip_suffix(EXTENSION) else { continue };
+        let Ok(date) = PrimitiveDateTime::parse(date, DATE_FORMAT) else { continue };
+        restore_points.push((date.assume_utc(), path));
+    }
+    restore_points.sort_unstable_by_key(|(date, _)| *date);
+    Ok(restore_points)
+}
+
+/// Stores the snapshot as a restore point taken at the date, and deletes the oldest restore
+/// points beyond the `keep` latest ones.
+///
+/// Returns the date of the previous oldest restore point, if any, and of the new one when the
+/// oldest restore point changed.
+pub(crate) fn store(
+    snapshots_path: &Path,
+    snapshot: &Path,
+    date: OffsetDateTime,
+    keep: usize,
+) -> Result<Option<(Option<OffsetDateTime>, OffsetDateTime)>> {
+    let dst = path_of(snapshots_path, date);
+    fs::create_dir_all(snapshots_path.join(RESTORE_POINTS_DIR))?;
+    // the snapshot is read-only, a hard link doesn't take any space
+    if fs::hard_link(snapshot, &dst).is_err() {
+        fs::copy(snapshot, &dst)?;
+    }
+
+    let restore_points = list(snapshots_path)?;
+    if restore_points.len() == 1 {
+        return Ok(Some((None, date)));
+    }
+    // at least the new restore point is kept
+    let nb_deleted = restore_points.len().saturating_sub(keep.max(1));
+    if nb_deleted == 0 {
+        return Ok(None);
+    }
+    for (_, path) in &restore_points[..nb_deleted] {
+        fs::remove_file(path)?;
+    }
+    Ok(Some((Some(restore_points[0].0), restore_points[nb_deleted].0)))
+}
@@ -0,0 +1,149 @@
+//! Streams the snapshots to an S3-compatible object storage with a multipart upload, so that the
+//! tarball is never written to the local disk.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use meilisearch_types::compression;
+use rusty_s3::actions::CreateMultipartUpload;
+use rusty_s3::{Bucket, Credentials, S3Action, UrlStyle};
+use url::Url;
+
+/// The size of the parts sent to the object storage, the last one excepted.
+///
+/// The object storages accept at most 10 000 parts, which allows snapshots of up to 640GiB.
+const PART_SIZE: usize = 64 * 1024 * 1024;
+/// How long the signed URLs are valid.
+const URL_VALIDITY: Duration = Duration::from_secs(60 * 60);
+
+/// Where to upload the snapshots on an S3-compatible object storage.
+#[derive(Clone)]
+pub struct S3SnapshotOptions {
+    /// The URL of the object storage, e.g. `https://s3.eu-west-3.amazonaws.com`.
+    pub endpoint: Url,
+    pub region: String,
+    pub bucket: String,
+    /// Prepended to the name of the snapshots, e.g. `backups/`.
+    pub prefix: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Whether the bucket is part of the path of the URLs rather than of their domain.
+    pub path_style: bool,
+}
+
+impl fmt::Debug for S3SnapshotOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3SnapshotOptions")
+            .field("endpoint", &self.endpoint.as_str())
+            .field("region", &self.region)
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("path_style", &self.path_style)
+            .finish_non_exhaustive()
+    }
+}
+
+impl S3SnapshotOptions {
+    /// Uploads the content of the directory as a tar.gz archive named `name`.
+    pub(crate) fn upload_tar_gz(&self, src: &Path, name: &str) -> anyhow::Result<()> {
+        let url_style = if self.path_style { UrlStyle::Path } else { UrlStyle::VirtualHost };
+        let bucket = Bucket::new(
+            self.endpoint.clone(),
+            url_style,
+            self.bucket.clone(),
+            self.region.clone(),
+        )?;
+        let credentials = Credentials::new(self.access_key.clone(), self.secret_key.clone());
+        let object = format!("{}{name}", self.prefix);
+
+        let action = bucket.create_multipart_upload(Some(&credentials), &object);
+        let response = ureq::post(action.sign(URL_VALIDITY).as_str()).call()?.into_string()?;
+        let multipart = CreateMultipartUpload::parse_response(response.as_str())?;
+        let upload_id = multipart.upload_id();
+
+        let upload = MultipartUpload {
+            bucket: &bucket,
+            credentials: &credentials,
+            object: &object,
+            upload_id,
+            buffer: Vec::with_capacity(PART_SIZE),
+            etags: Vec::new(),
+        };
+
+        match compression::write_tar_gz(src, upload).and_then(MultipartUpload::complete) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // the parts already uploaded are billed until the upload is aborted
+                let action = bucket.abort_multipart_upload(Some(&credentials), &object, upload_id);
+                if let Err(abort_error) = ureq::delete(action.sign(URL_VALIDITY).as_str()).call() {
+                    tracing::error!("Could not abort the upload of the snapshot: {abort_error}");
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Buffers what is written to it and uploads it part by part.
+struct MultipartUpload<'a> {
+    bucket: &'a Bucket,
+    credentials: &'a Credentials,
+    object: &'a str,
+    upload_id: &'a str,
+    buffer: Vec<u8>,
+    etags: Vec<String>,
+}
+
+impl MultipartUpload<'_> {
+    fn upload_part(&mut self) -> anyhow::Result<()> {
+        let part_number = u16::try_from(self.etags.len() + 1)
+            .context("The snapshot is too large to be uploaded to the object storage")?;
+        let action = self.bucket.upload_part(
+            Some(self.credentials),
+            self.object,
+            part_number,
+            self.upload_id,
+        );
+        let response = ureq::put(action.sign(URL_VALIDITY).as_str()).send_bytes(&self.buffer)?;
+        let etag = response
+            .header("ETag")
+            .context("The object storage didn't return the ETag of an uploaded part")?;
+        self.etags.push(etag.to_string());
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Uploads the last part and assembles the parts into the final object.
+    fn complete(mut self) -> anyhow::Result<()> {
+        if !self.buffer.is_empty() || self.etags.is_empty() {
+            self.upload_part()?;
+        }
+
+        let action = self.bucket.complete_multipart_upload(
+            Some(self.credentials),
+            self.object,
+            self.upload_id,
+            self.etags.iter().map(String::as_str),
+        );
+        ureq::post(action.sign(URL_VALIDITY).as_str()).send_string(&action.body())?;
+        Ok(())
+    }
+}
+
+impl Write for MultipartUpload<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(PART_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == PART_SIZE {
+            self.upload_part().map_err(io::Error::other)?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
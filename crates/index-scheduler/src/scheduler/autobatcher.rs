@@ -5,9 +5,10 @@ tasks affecting a single index into a [batch](crate::batch::Batch).
 The main function of the autobatcher is [`next_autobatch`].
 */
 
-use meilisearch_types::tasks::TaskId;
 use std::ops::ControlFlow::{self, Break, Continue};
 
+use meilisearch_types::tasks::TaskId;
+
 use crate::KindWithContent;
 
 /// Succinctly describes a task's [`Kind`](meilisearch_types::tasks::Kind)
@@ -16,6 +17,7 @@ use crate::KindWithContent;
 /// Only the non-prioritised tasks that can be grouped in a batch have a corresponding [`AutobatchKind`]
 enum AutobatchKind {
     DocumentImport { allow_index_creation: bool, primary_key: Option<String> },
+    DocumentImportUrl { allow_index_creation: bool },
     DocumentEdition,
     DocumentDeletion { by_filter: bool },
     DocumentClear,
@@ -50,6 +52,9 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::DocumentAdditionOrUpdate {
                 allow_index_creation, primary_key, ..
             } => AutobatchKind::DocumentImport { allow_index_creation, primary_key },
+            KindWithContent::DocumentImportUrl { allow_index_creation, .. } => {
+                AutobatchKind::DocumentImportUrl { allow_index_creation }
+            }
             KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
             KindWithContent::DocumentDeletion { .. } => {
                 AutobatchKind::DocumentDeletion { by_filter: false }
@@ -70,6 +75,9 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::DumpImport { .. }
+            | KindWithContent::PointInTimeRestore { .. }
+            | KindWithContent::IndexRestore { .. }
             | KindWithContent::UpgradeDatabase { .. }
             | KindWithContent::SnapshotCreation => {
                 panic!("The autobatcher should never be called with tasks that don't apply to an index.")
@@ -88,6 +96,9 @@ pub enum BatchKind {
         primary_key: Option<String>,
         operation_ids: Vec<TaskId>,
     },
+    DocumentImportUrl {
+        id: TaskId,
+    },
     DocumentEdition {
         id: TaskId,
     },
@@ -177,6 +188,9 @@ impl BatchKind {
                 }),
                 allow_index_creation,
             ),
+            K::DocumentImportUrl { allow_index_creation } => {
+                (Break(BatchKind::DocumentImportUrl { id: task_id }), allow_index_creation)
+            }
             K::DocumentEdition => (Break(BatchKind::DocumentEdition { id: task_id }), false),
             K::DocumentDeletion { by_filter: includes_by_filter } => (
                 Continue(BatchKind::DocumentDeletion {
@@ -202,7 +216,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentEdition) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentImportUrl { .. } | K::DocumentEdition) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -425,6 +439,7 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
+                | BatchKind::DocumentImportUrl { .. }
                 | BatchKind::DocumentEdition { .. },
                 _,
             ) => {
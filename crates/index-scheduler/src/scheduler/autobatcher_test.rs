@@ -43,6 +43,20 @@ fn doc_imp(
     }
 }
 
+fn doc_imp_url(allow_index_creation: bool) -> KindWithContent {
+    KindWithContent::DocumentImportUrl {
+        index_uid: String::from("doggo"),
+        primary_key: None,
+        method: ReplaceDocuments,
+        url: String::from("https://example.com/doggos.ndjson"),
+        csv_delimiter: None,
+        content_file: Uuid::new_v4(),
+        documents_count: 0,
+        allow_index_creation,
+        replace_all_documents: false,
+    }
+}
+
 fn doc_del() -> KindWithContent {
     KindWithContent::DocumentDeletion {
         index_uid: String::from("doggo"),
@@ -85,6 +99,7 @@ fn idx_del() -> KindWithContent {
 fn idx_swap() -> KindWithContent {
     KindWithContent::IndexSwap {
         swaps: vec![IndexSwap { indexes: (String::from("doggo"), String::from("catto")) }],
+        execute_at: None,
     }
 }
 
@@ -393,3 +408,13 @@ fn autobatch_primary_key() {
     debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp(ReplaceDocuments, true, Some("id"))]), @"Some((DocumentOperation { allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
     debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, Some("id")), doc_imp(ReplaceDocuments, true, None)]), @r###"Some((DocumentOperation { allow_index_creation: true, primary_key: Some("id"), operation_ids: [0] }, true))"###);
 }
+
+#[test]
+fn autobatch_document_import_url() {
+    // an import from an url is always processed alone
+    debug_snapshot!(autobatch_from(false, None, [doc_imp_url(true)]), @"Some((DocumentImportUrl { id: 0 }, true))");
+    debug_snapshot!(autobatch_from(false, None, [doc_imp_url(false)]), @"Some((DocumentImportUrl { id: 0 }, false))");
+    debug_snapshot!(autobatch_from(true, None, [doc_imp_url(true), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentImportUrl { id: 0 }, true))");
+    debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp_url(true)]), @"Some((DocumentOperation { allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+    debug_snapshot!(autobatch_from(true, None, [settings(true), doc_imp_url(true)]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+}
@@ -1,13 +1,17 @@
 use std::fmt;
+use std::time::Duration;
 
 use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::index::DocumentOperationCheckpoint;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::{Settings, Unchecked};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task, TaskId};
 use roaring::RoaringBitmap;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::autobatcher::{self, BatchKind};
+use crate::processing::ProcessingTasks;
 use crate::utils::ProcessingBatch;
 use crate::{Error, IndexScheduler, Result};
 
@@ -25,6 +29,9 @@ pub(crate) enum Batch {
     TaskDeletions(Vec<Task>),
     SnapshotCreation(Vec<Task>),
     Dump(Task),
+    DumpImport(Task),
+    PointInTimeRestore(Task),
+    IndexRestore(Task),
     IndexOperation {
         op: IndexOperation,
         must_create_index: bool,
@@ -50,15 +57,53 @@ pub(crate) enum Batch {
     UpgradeDatabase {
         tasks: Vec<Task>,
     },
+    /// The tasks whose dependency failed or was canceled, they are failed without being processed.
+    FailedDependencies {
+        tasks: Vec<Task>,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum DocumentOperation {
     Replace(Uuid),
     Update(Uuid),
+    MergePatch(Uuid),
+    JsonPatch(Uuid),
+    SkipExisting(Uuid),
+    FailOnExisting(Uuid),
     Delete(Vec<String>),
 }
 
+impl DocumentOperation {
+    /// Returns the operation applying the documents of the content file with the given method.
+    pub fn new(method: IndexDocumentsMethod, content_file: Uuid) -> Self {
+        match method {
+            IndexDocumentsMethod::ReplaceDocuments => DocumentOperation::Replace(content_file),
+            IndexDocumentsMethod::UpdateDocuments => DocumentOperation::Update(content_file),
+            IndexDocumentsMethod::MergePatchDocuments => {
+                DocumentOperation::MergePatch(content_file)
+            }
+            IndexDocumentsMethod::JsonPatchDocuments => DocumentOperation::JsonPatch(content_file),
+            IndexDocumentsMethod::SkipExistingDocuments => {
+                DocumentOperation::SkipExisting(content_file)
+            }
+            IndexDocumentsMethod::FailOnExistingDocuments => {
+                DocumentOperation::FailOnExisting(content_file)
+            }
+        }
+    }
+}
+
+/// The documents of the content file of a task processed by one step of a document operation
+/// split at checkpoints, the content file holding its documents one after the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DocumentsWindow {
+    /// The number of documents processed by the previous steps.
+    pub skip: u64,
+    /// The number of documents processed by this step.
+    pub take: u64,
+}
+
 /// A [batch](Batch) that combines multiple tasks operating on an index.
 #[derive(Debug)]
 pub(crate) enum IndexOperation {
@@ -67,6 +112,12 @@ pub(crate) enum IndexOperation {
         primary_key: Option<String>,
         operations: Vec<DocumentOperation>,
         tasks: Vec<Task>,
+        /// Set when the operation only processes a part of the documents of its single task.
+        window: Option<DocumentsWindow>,
+    },
+    DocumentImportUrl {
+        index_uid: String,
+        task: Task,
     },
     DocumentEdition {
         index_uid: String,
@@ -102,6 +153,9 @@ impl Batch {
         match self {
             Batch::TaskCancelation { task, .. }
             | Batch::Dump(task)
+            | Batch::DumpImport(task)
+            | Batch::PointInTimeRestore(task)
+            | Batch::IndexRestore(task)
             | Batch::IndexCreation { task, .. }
             | Batch::IndexUpdate { task, .. } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
@@ -109,6 +163,7 @@ impl Batch {
             Batch::SnapshotCreation(tasks)
             | Batch::TaskDeletions(tasks)
             | Batch::UpgradeDatabase { tasks }
+            | Batch::FailedDependencies { tasks }
             | Batch::IndexDeletion { tasks, .. } => {
                 RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
             }
@@ -119,7 +174,8 @@ impl Batch {
                 | IndexOperation::DocumentClear { tasks, .. } => {
                     RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
                 }
-                IndexOperation::DocumentEdition { task, .. } => {
+                IndexOperation::DocumentImportUrl { task, .. }
+                | IndexOperation::DocumentEdition { task, .. } => {
                     RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
                 }
                 IndexOperation::DocumentClearAndSetting {
@@ -142,7 +198,11 @@ impl Batch {
             | TaskDeletions(_)
             | SnapshotCreation(_)
             | Dump(_)
+            | DumpImport(_)
+            | PointInTimeRestore(_)
+            | IndexRestore(_)
             | UpgradeDatabase { .. }
+            | FailedDependencies { .. }
             | IndexSwap { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
@@ -162,12 +222,16 @@ impl fmt::Display for Batch {
             Batch::TaskDeletions(_) => f.write_str("TaskDeletion")?,
             Batch::SnapshotCreation(_) => f.write_str("SnapshotCreation")?,
             Batch::Dump(_) => f.write_str("Dump")?,
+            Batch::DumpImport(_) => f.write_str("DumpImport")?,
+            Batch::PointInTimeRestore(_) => f.write_str("PointInTimeRestore")?,
+            Batch::IndexRestore(_) => f.write_str("IndexRestore")?,
             Batch::IndexOperation { op, .. } => write!(f, "{op}")?,
             Batch::IndexCreation { .. } => f.write_str("IndexCreation")?,
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
             Batch::UpgradeDatabase { .. } => f.write_str("UpgradeDatabase")?,
+            Batch::FailedDependencies { .. } => f.write_str("FailedDependencies")?,
         };
         match index_uid {
             Some(name) => f.write_fmt(format_args!(" on {name:?} from tasks: {tasks:?}")),
@@ -180,6 +244,7 @@ impl IndexOperation {
     pub fn index_uid(&self) -> &str {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
+            | IndexOperation::DocumentImportUrl { index_uid, .. }
             | IndexOperation::DocumentEdition { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
@@ -187,6 +252,81 @@ impl IndexOperation {
             | IndexOperation::DocumentClearAndSetting { index_uid, .. } => index_uid,
         }
     }
+
+    /// Splits a document operation into consecutive operations of at most `max_documents` documents.
+    ///
+    /// The tasks adding more documents than that are processed on their own in several steps,
+    /// starting after the documents already processed according to the `checkpoint` of the index.
+    /// The other operations aren't split.
+    pub fn split_at_checkpoints(
+        self,
+        max_documents: u64,
+        checkpoint: Option<DocumentOperationCheckpoint>,
+    ) -> Vec<IndexOperation> {
+        let IndexOperation::DocumentOperation { index_uid, primary_key, operations, tasks, .. } =
+            self
+        else {
+            return vec![self];
+        };
+        let max_documents = max_documents.max(1);
+
+        let mut steps: Vec<(Vec<DocumentOperation>, Vec<Task>, Option<DocumentsWindow>)> =
+            Vec::new();
+        let mut documents: u64 = 0;
+        for (operation, task) in operations.into_iter().zip(tasks) {
+            let task_documents = match &task.kind {
+                KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => {
+                    *documents_count
+                }
+                KindWithContent::DocumentDeletion { documents_ids, .. } => {
+                    documents_ids.len() as u64
+                }
+                _ => 0,
+            };
+
+            if matches!(task.kind, KindWithContent::DocumentAdditionOrUpdate { .. }) {
+                let mut skip = checkpoint
+                    .filter(|checkpoint| checkpoint.task_uid == task.uid)
+                    .map(|checkpoint| checkpoint.processed_documents)
+                    .filter(|&processed| processed < task_documents)
+                    .unwrap_or(0);
+                if skip > 0 || task_documents > max_documents {
+                    while skip < task_documents {
+                        let take = (task_documents - skip).min(max_documents);
+                        let window = DocumentsWindow { skip, take };
+                        steps.push((vec![operation.clone()], vec![task.clone()], Some(window)));
+                        skip += take;
+                    }
+                    continue;
+                }
+            }
+
+            match steps.last_mut() {
+                Some((operations, tasks, None))
+                    if documents.saturating_add(task_documents) <= max_documents =>
+                {
+                    operations.push(operation);
+                    tasks.push(task);
+                    documents = documents.saturating_add(task_documents);
+                }
+                _ => {
+                    steps.push((vec![operation], vec![task], None));
+                    documents = task_documents;
+                }
+            }
+        }
+
+        steps
+            .into_iter()
+            .map(|(operations, tasks, window)| IndexOperation::DocumentOperation {
+                index_uid: index_uid.clone(),
+                primary_key: primary_key.clone(),
+                operations,
+                tasks,
+                window,
+            })
+            .collect()
+    }
 }
 
 impl fmt::Display for IndexOperation {
@@ -195,6 +335,9 @@ impl fmt::Display for IndexOperation {
             IndexOperation::DocumentOperation { .. } => {
                 f.write_str("IndexOperation::DocumentOperation")
             }
+            IndexOperation::DocumentImportUrl { .. } => {
+                f.write_str("IndexOperation::DocumentImportUrl")
+            }
             IndexOperation::DocumentEdition { .. } => {
                 f.write_str("IndexOperation::DocumentEdition")
             }
@@ -237,6 +380,23 @@ impl IndexScheduler {
                 },
                 must_create_index,
             })),
+            BatchKind::DocumentImportUrl { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                match &task.kind {
+                    KindWithContent::DocumentImportUrl { index_uid, .. } => {
+                        Ok(Some(Batch::IndexOperation {
+                            op: IndexOperation::DocumentImportUrl {
+                                index_uid: index_uid.clone(),
+                                task,
+                            },
+                            must_create_index,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentEdition { id } => {
                 let mut task =
                     self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -278,15 +438,7 @@ impl IndexScheduler {
                     match task.kind {
                         KindWithContent::DocumentAdditionOrUpdate {
                             content_file, method, ..
-                        } => match method {
-                            IndexDocumentsMethod::ReplaceDocuments => {
-                                operations.push(DocumentOperation::Replace(content_file))
-                            }
-                            IndexDocumentsMethod::UpdateDocuments => {
-                                operations.push(DocumentOperation::Update(content_file))
-                            }
-                            _ => unreachable!("Unknown document merging method"),
-                        },
+                        } => operations.push(DocumentOperation::new(method, content_file)),
                         KindWithContent::DocumentDeletion { ref documents_ids, .. } => {
                             operations.push(DocumentOperation::Delete(documents_ids.clone()));
                         }
@@ -300,6 +452,7 @@ impl IndexScheduler {
                         primary_key,
                         operations,
                         tasks,
+                        window: None,
                     },
                     must_create_index,
                 }))
@@ -422,21 +575,249 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the enqueued index swaps that must be executed at a given date, with their date.
+    fn scheduled_index_swaps(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+    ) -> Result<Vec<(TaskId, OffsetDateTime)>> {
+        let swaps = self.queue.tasks.get_kind(rtxn, Kind::IndexSwap)? & enqueued;
+        Ok(self
+            .queue
+            .tasks
+            .get_existing_tasks(rtxn, &swaps)?
+            .into_iter()
+            .filter_map(|task| match task.kind {
+                KindWithContent::IndexSwap { execute_at: Some(execute_at), .. } => {
+                    Some((task.uid, execute_at))
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Return how long the run loop can sleep before a scheduled index swap must be executed.
+    pub(crate) fn time_until_next_scheduled_task(&self) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let enqueued = self.queue.tasks.get_status(&rtxn, Status::Enqueued)?;
+        let next =
+            self.scheduled_index_swaps(&rtxn, &enqueued)?.into_iter().map(|(_, at)| at).min();
+        let now = OffsetDateTime::now_utc();
+        Ok(next.map(|next| (next - now).try_into().unwrap_or(Duration::ZERO)))
+    }
+
+    /// Return how long the run loop can sleep before an index stops gathering tasks for its next batch.
+    pub(crate) fn time_until_next_autobatch(&self) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let enqueued = self.queue.tasks.get_status(&rtxn, Status::Enqueued)?;
+        let (_, next) = self.tasks_gathering_in_batches(&rtxn, &enqueued)?;
+        let now = OffsetDateTime::now_utc();
+        // the deadlines are in the future, but rounding must never make the run loop spin
+        Ok(next.map(|next| {
+            Duration::try_from(next - now).unwrap_or_default().max(Duration::from_millis(1))
+        }))
+    }
+
+    /// Returns the enqueued tasks of the indexes whose next batch is still gathering tasks,
+    /// and the date the first of these indexes stops waiting.
+    ///
+    /// An index gathers tasks while the batch limits define a maximum wait, its enqueued tasks are
+    /// only small document operations that don't reach any of the limits of a batch, and its
+    /// oldest enqueued task was enqueued less than the maximum wait ago.
+    fn tasks_gathering_in_batches(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+    ) -> Result<(RoaringBitmap, Option<OffsetDateTime>)> {
+        let mut gathering = RoaringBitmap::new();
+        let mut next_deadline: Option<OffsetDateTime> = None;
+        if !self.scheduler.autobatching_enabled {
+            return Ok((gathering, next_deadline));
+        }
+
+        let now = OffsetDateTime::now_utc();
+        for entry in self.queue.tasks.index_tasks.iter(rtxn)? {
+            let (index, index_tasks) = entry?;
+            let limits = self.scheduler.batch_limits(index);
+            let Some(max_wait) = limits.max_wait else { continue };
+            let index_tasks = index_tasks & enqueued;
+            if index_tasks.is_empty() || index_tasks.len() >= limits.max_tasks as u64 {
+                continue;
+            }
+
+            let mut deadline = None;
+            let mut documents: u64 = 0;
+            let mut total_size: u64 = 0;
+            let mut is_full = false;
+            for task_id in &index_tasks {
+                let task =
+                    self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                let deadline = *deadline.get_or_insert(task.enqueued_at + max_wait);
+                if deadline <= now {
+                    is_full = true;
+                    break;
+                }
+                let Some(task_documents) = documents_count(&task.kind) else {
+                    is_full = true;
+                    break;
+                };
+                documents = documents.saturating_add(task_documents);
+                if let Some(uuid) = task.content_uuid() {
+                    let content_size = self.queue.file_store.compute_size(uuid)?;
+                    total_size = total_size.saturating_add(content_size);
+                }
+                if limits.max_documents.is_some_and(|max| documents >= max)
+                    || total_size >= limits.max_total_size
+                {
+                    is_full = true;
+                    break;
+                }
+            }
+
+            if let (false, Some(deadline)) = (is_full, deadline) {
+                gathering |= index_tasks;
+                next_deadline = Some(next_deadline.map_or(deadline, |next| next.min(deadline)));
+            }
+        }
+
+        Ok((gathering, next_deadline))
+    }
+
+    /// Returns the enqueued tasks whose dependency failed, was canceled or doesn't exist anymore,
+    /// and the enqueued tasks that must wait for a dependency to succeed or for the date
+    /// they are scheduled at.
+    fn tasks_with_unmet_dependency(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+    ) -> Result<(RoaringBitmap, RoaringBitmap)> {
+        let succeeded = self.queue.tasks.get_status(rtxn, Status::Succeeded)?;
+        let mut failed_dependents = RoaringBitmap::new();
+        let mut waiting = RoaringBitmap::new();
+
+        for entry in self.queue.tasks.depends_on.iter(rtxn)? {
+            let (dependency, dependents) = entry?;
+            let dependents = dependents & enqueued;
+            if succeeded.contains(dependency) {
+                continue;
+            } else if enqueued.contains(dependency) {
+                waiting |= dependents;
+            } else {
+                failed_dependents |= dependents;
+            }
+        }
+
+        let now = OffsetDateTime::now_utc();
+        for (task_id, execute_at) in self.scheduled_index_swaps(rtxn, enqueued)? {
+            if execute_at > now {
+                waiting.insert(task_id);
+            }
+        }
+
+        let mut blocked = RoaringBitmap::new();
+        for task in self.queue.tasks.get_existing_tasks(rtxn, &waiting)? {
+            for index in task.indexes() {
+                let mut index_tasks = self.queue.tasks.index_tasks(rtxn, index)? & enqueued;
+                index_tasks.remove_range(..task.uid);
+                blocked |= index_tasks;
+            }
+        }
+        waiting |= blocked;
+
+        Ok((failed_dependents, waiting))
+    }
+
+    /// Returns the enqueued tasks that can't be processed alongside the processing batches:
+    /// their own tasks, the tasks of the indexes they target, and the tasks enqueued after
+    /// an index swap involving one of these indexes.
+    fn tasks_blocked_by_processing_batches(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        processing: &ProcessingTasks,
+    ) -> Result<RoaringBitmap> {
+        let mut blocked = enqueued & &*processing.processing;
+        for entry in &processing.batches {
+            for index in &entry.batch.indexes {
+                blocked |= self.queue.tasks.index_tasks(rtxn, index)? & enqueued;
+            }
+        }
+
+        let swaps = self.queue.tasks.get_kind(rtxn, Kind::IndexSwap)? & &blocked;
+        for task in self.queue.tasks.get_existing_tasks(rtxn, &swaps)? {
+            for index in task.indexes() {
+                let mut index_tasks = self.queue.tasks.index_tasks(rtxn, index)? & enqueued;
+                index_tasks.remove_range(..task.uid);
+                blocked |= index_tasks;
+            }
+        }
+
+        Ok(blocked)
+    }
+
+    /// Returns the enqueued tasks that can be processed in the fast lane: for each index, the
+    /// small tasks at the head of its queue, up to `max_documents` documents.
+    ///
+    /// A small task enqueued after a larger one on the same index is never taken, so that the
+    /// order of the tasks of an index is kept.
+    fn fast_lane_tasks(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        max_documents: u64,
+    ) -> Result<RoaringBitmap> {
+        let mut fast_lane = RoaringBitmap::new();
+        for entry in self.queue.tasks.index_tasks.iter(rtxn)? {
+            let (_index, index_tasks) = entry?;
+            let mut documents: u64 = 0;
+            for task_id in index_tasks & enqueued {
+                let task =
+                    self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                let Some(task_documents) = documents_count(&task.kind) else { break };
+                documents = documents.saturating_add(task_documents);
+                if documents > max_documents {
+                    break;
+                }
+                fast_lane.insert(task_id);
+            }
+        }
+
+        Ok(fast_lane)
+    }
+
     /// Create the next batch to be processed;
+    /// 0. We fail the tasks whose dependency didn't succeed and skip the ones still waiting for it
+    ///    or for the date they are scheduled at.
     /// 1. We get the *last* task to cancel.
     /// 2. We get the *next* task to delete.
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
     /// 5. We get the *next* tasks to process for a specific index.
-    #[tracing::instrument(level = "trace", skip(self, rtxn), target = "indexing::scheduler")]
+    ///
+    /// When batches can be processed concurrently, the tasks of the processing batches and of the
+    /// indexes they target are left aside. Once the regular lanes are all busy, only the tasks
+    /// small enough for the fast lane are considered.
+    #[tracing::instrument(
+        level = "trace",
+        skip(self, rtxn, processing),
+        target = "indexing::scheduler"
+    )]
     pub(crate) fn create_next_batch(
         &self,
         rtxn: &RoTxn,
+        processing: &ProcessingTasks,
     ) -> Result<Option<(Batch, ProcessingBatch)>> {
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::InsideCreateBatch)?;
 
-        let batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        let concurrent = self.scheduler.is_concurrent();
+        let mut batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        // The processing batches are not written yet but their uid is taken.
+        if let Some(processing_batch_id) = processing.batch_uids().max() {
+            if concurrent {
+                batch_id = batch_id.max(processing_batch_id + 1);
+            }
+        }
         let mut current_batch = ProcessingBatch::new(batch_id);
 
         let enqueued = &self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
@@ -456,6 +837,36 @@ impl IndexScheduler {
             return Ok(Some((Batch::UpgradeDatabase { tasks }, current_batch)));
         }
 
+        // 0.5. The tasks waiting for a dependency that will never succeed are failed right away,
+        // and the ones whose dependency is still enqueued or whose date isn't reached yet are left
+        // aside with the tasks enqueued after them on the same indexes, so that the order of the
+        // tasks of an index is kept.
+        let (failed_dependents, waiting) = self.tasks_with_unmet_dependency(rtxn, enqueued)?;
+        if !failed_dependents.is_empty() {
+            let mut tasks = self.queue.tasks.get_existing_tasks(rtxn, failed_dependents)?;
+            current_batch.processing(&mut tasks);
+            return Ok(Some((Batch::FailedDependencies { tasks }, current_batch)));
+        }
+        let mut enqueued = enqueued - waiting;
+        // The indexes waiting for more tasks to fill their next batch are left aside as well.
+        enqueued -= self.tasks_gathering_in_batches(rtxn, &enqueued)?.0;
+        if concurrent {
+            enqueued -= self.tasks_blocked_by_processing_batches(rtxn, &enqueued, processing)?;
+        }
+        let enqueued = &enqueued;
+
+        // When all the regular lanes are busy, only the small tasks can be processed in the fast lane.
+        let fast_lane_tasks;
+        let enqueued = match self.scheduler.fast_lane_max_documents {
+            Some(max_documents)
+                if processing.batches.len() >= self.scheduler.max_concurrent_batches =>
+            {
+                fast_lane_tasks = self.fast_lane_tasks(rtxn, enqueued, max_documents)?;
+                &fast_lane_tasks
+            }
+            _ => enqueued,
+        };
+
         // 1. we get the last task to cancel.
         let to_cancel = self.queue.tasks.get_kind(rtxn, Kind::TaskCancelation)? & enqueued;
         if let Some(task_id) = to_cancel.max() {
@@ -490,6 +901,33 @@ impl IndexScheduler {
             return Ok(Some((Batch::Dump(task), current_batch)));
         }
 
+        // 4.5. we import the dumps, one at a time.
+        let to_import = self.queue.tasks.get_kind(rtxn, Kind::DumpImport)? & enqueued;
+        if let Some(to_import) = to_import.min() {
+            let mut task =
+                self.queue.tasks.get_task(rtxn, to_import)?.ok_or(Error::CorruptedTaskQueue)?;
+            current_batch.processing(Some(&mut task));
+            return Ok(Some((Batch::DumpImport(task), current_batch)));
+        }
+
+        // 4.6. we restore the indexes, one restore at a time.
+        let to_restore = self.queue.tasks.get_kind(rtxn, Kind::PointInTimeRestore)? & enqueued;
+        if let Some(to_restore) = to_restore.min() {
+            let mut task =
+                self.queue.tasks.get_task(rtxn, to_restore)?.ok_or(Error::CorruptedTaskQueue)?;
+            current_batch.processing(Some(&mut task));
+            return Ok(Some((Batch::PointInTimeRestore(task), current_batch)));
+        }
+
+        // 4.7. we restore the single indexes from the backups, one at a time.
+        let to_restore = self.queue.tasks.get_kind(rtxn, Kind::IndexRestore)? & enqueued;
+        if let Some(to_restore) = to_restore.min() {
+            let mut task =
+                self.queue.tasks.get_task(rtxn, to_restore)?.ok_or(Error::CorruptedTaskQueue)?;
+            current_batch.processing(Some(&mut task));
+            return Ok(Some((Batch::IndexRestore(task), current_batch)));
+        }
+
         // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
         let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
         let mut task =
@@ -502,7 +940,9 @@ impl IndexScheduler {
         let index_name = if let Some(&index_name) = task.indexes().first() {
             index_name
         } else {
-            assert!(matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty()));
+            assert!(
+                matches!(&task.kind, KindWithContent::IndexSwap { swaps, .. } if swaps.is_empty())
+            );
             current_batch.processing(Some(&mut task));
             return Ok(Some((Batch::IndexSwap { task }, current_batch)));
         };
@@ -519,27 +959,40 @@ impl IndexScheduler {
 
         // If autobatching is disabled we only take one task at a time.
         // Otherwise, we take only a maximum of tasks to create batches.
-        let tasks_limit = if self.scheduler.autobatching_enabled {
-            self.scheduler.max_number_of_batched_tasks
-        } else {
-            1
-        };
+        let limits = self.scheduler.batch_limits(index_name);
+        let tasks_limit = if self.scheduler.autobatching_enabled { limits.max_tasks } else { 1 };
 
         let mut enqueued = Vec::new();
         let mut total_size: u64 = 0;
-        for task_id in index_tasks.into_iter().take(tasks_limit) {
+        let mut total_documents: u64 = 0;
+        // The consecutive settings updates are collapsed when processed, they are thus
+        // batched together regardless of the limit to avoid reindexing once per batch.
+        let mut only_settings = self.scheduler.autobatching_enabled;
+        for task_id in index_tasks {
             let task = self
                 .queue
                 .tasks
                 .get_task(rtxn, task_id)
                 .and_then(|task| task.ok_or(Error::CorruptedTaskQueue))?;
 
+            only_settings &= matches!(task.kind, KindWithContent::SettingsUpdate { .. });
+            if enqueued.len() >= tasks_limit && !only_settings {
+                break;
+            }
+
             if let Some(uuid) = task.content_uuid() {
                 let content_size = self.queue.file_store.compute_size(uuid)?;
                 total_size = total_size.saturating_add(content_size);
             }
 
-            if total_size > self.scheduler.batched_tasks_size_limit && !enqueued.is_empty() {
+            if total_size > limits.max_total_size && !enqueued.is_empty() {
+                break;
+            }
+
+            total_documents =
+                total_documents.saturating_add(documents_count(&task.kind).unwrap_or_default());
+            if limits.max_documents.is_some_and(|max| total_documents > max) && !enqueued.is_empty()
+            {
                 break;
             }
 
@@ -565,3 +1018,17 @@ impl IndexScheduler {
         Ok(None)
     }
 }
+
+/// Returns the number of documents processed by a document addition, update or deletion by ids,
+/// or by an index creation, or `None` for the other tasks, whose number of documents isn't known
+/// in advance.
+///
+/// Only these tasks are small enough for the fast lane, or wait for more tasks to fill their batch.
+fn documents_count(kind: &KindWithContent) -> Option<u64> {
+    match kind {
+        KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => Some(*documents_count),
+        KindWithContent::DocumentDeletion { documents_ids, .. } => Some(documents_ids.len() as u64),
+        KindWithContent::IndexCreation { .. } => Some(0),
+        _ => None,
+    }
+}
@@ -4,9 +4,13 @@ mod autobatcher_test;
 mod create_batch;
 mod process_batch;
 mod process_dump_creation;
+mod process_dump_import;
 mod process_index_operation;
+mod process_index_restore;
+mod process_point_in_time_restore;
 mod process_snapshot_creation;
 mod process_upgrade;
+mod resource_usage;
 #[cfg(test)]
 mod test;
 #[cfg(test)]
@@ -16,21 +20,33 @@ mod test_embedders;
 #[cfg(test)]
 mod test_failure;
 
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::mpsc::SyncSender;
 use std::sync::Arc;
+use std::time::Duration;
 
+use meilisearch_types::autobatching::AutobatchingLimits;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::{Env, WithoutTls};
 use meilisearch_types::milli;
-use meilisearch_types::tasks::Status;
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::tasks::{KindWithContent, Status};
 use rayon::current_num_threads;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
 
+use self::create_batch::{Batch, IndexOperation};
+use self::resource_usage::MemorySampler;
 use crate::processing::{AtomicTaskStep, BatchProgress};
-use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
+use crate::utils::ProcessingBatch;
+use crate::{
+    spawn_templated_webhooks_sender, task_log, Error, IndexScheduler, IndexSchedulerOptions,
+    Result, S3SnapshotOptions, TemplatedWebhookRequest, TickOutcome,
+};
 
 #[derive(Default, Clone, Debug)]
 pub struct MustStopProcessing(Arc<AtomicBool>);
@@ -56,6 +72,28 @@ pub struct Scheduler {
     /// Get a signal when a batch needs to be processed.
     pub(crate) wake_up: Arc<SignalEvent>,
 
+    /// Get a signal when the sources of the indexes changed and the next refresh must be recomputed.
+    pub(crate) sources_wake_up: Arc<SignalEvent>,
+
+    /// A boolean that can be set to true to stop creating new batches.
+    pub(crate) paused: Arc<AtomicBool>,
+
+    /// Set once the instance is shutting down: no new batch is created and the document
+    /// operations stop at their next checkpoint.
+    pub(crate) shutting_down: Arc<AtomicBool>,
+
+    /// Set once the instance is draining before being removed: the new tasks are refused, no new
+    /// batch is created and the document operations stop at their next checkpoint.
+    pub(crate) draining: Arc<AtomicBool>,
+
+    /// The number of processed batches whose webhooks are still being sent, and of the
+    /// requests to the templated webhooks that are still queued.
+    pub(crate) notifying_batches: Arc<AtomicUsize>,
+
+    /// The queue of the requests to the webhooks having a payload template, sent by their own
+    /// thread. `None` if no webhook has a payload template.
+    pub(crate) templated_webhook_requests: Option<SyncSender<TemplatedWebhookRequest>>,
+
     /// Whether auto-batching is enabled or not.
     pub(crate) autobatching_enabled: bool,
 
@@ -65,12 +103,49 @@ pub struct Scheduler {
     /// The maximum size, in bytes, of tasks in a batch.
     pub(crate) batched_tasks_size_limit: u64,
 
+    /// The maximum number of documents added, updated or deleted by the tasks of a batch.
+    pub(crate) max_batched_documents: Option<u64>,
+
+    /// How long the document operations wait for more tasks when their batch isn't full.
+    pub(crate) autobatching_max_wait: Option<Duration>,
+
+    /// The limits of the batches of some indexes, overriding the ones above.
+    pub(crate) index_autobatching: BTreeMap<String, AutobatchingLimits>,
+
+    /// The maximum number of batches targeting different indexes that can be processed at once.
+    pub(crate) max_concurrent_batches: usize,
+
+    /// The maximum number of documents of the batches processed in the fast lane, alongside
+    /// the regular batches. `None` disables the fast lane.
+    pub(crate) fast_lane_max_documents: Option<u64>,
+
+    /// The maximum number of documents indexed between two checkpoints of a document operation,
+    /// at which its progress is committed. `None` indexes the whole batch at once.
+    pub(crate) checkpoint_documents: Option<u64>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
     /// The path used to create the snapshots.
     pub(crate) snapshots_path: PathBuf,
 
+    /// The object storage the snapshots are uploaded to, if any.
+    pub(crate) snapshot_s3: Option<S3SnapshotOptions>,
+
+    /// The number of snapshots kept as restore points, `None` if they are disabled.
+    ///
+    /// The content files of the succeeded document additions are kept as long as a restore
+    /// point was taken before them, so that they can be replayed.
+    pub(crate) restore_points: Option<usize>,
+
+    /// Whether the content files of the succeeded document additions are kept until their task
+    /// is deleted, so that the changes of the documents can be streamed.
+    pub(crate) change_stream: bool,
+
+    /// Whether the succeeded tasks that changed the indexes are kept in the task log, with the
+    /// content files of the document additions, until the log is trimmed.
+    pub(crate) task_log: bool,
+
     /// The path to the folder containing the auth LMDB env.
     pub(crate) auth_env: Env<WithoutTls>,
 
@@ -88,11 +163,27 @@ impl Scheduler {
         Scheduler {
             must_stop_processing: self.must_stop_processing.clone(),
             wake_up: self.wake_up.clone(),
+            sources_wake_up: self.sources_wake_up.clone(),
+            paused: self.paused.clone(),
+            shutting_down: self.shutting_down.clone(),
+            draining: self.draining.clone(),
+            notifying_batches: self.notifying_batches.clone(),
+            templated_webhook_requests: self.templated_webhook_requests.clone(),
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
             batched_tasks_size_limit: self.batched_tasks_size_limit,
+            max_batched_documents: self.max_batched_documents,
+            autobatching_max_wait: self.autobatching_max_wait,
+            index_autobatching: self.index_autobatching.clone(),
+            max_concurrent_batches: self.max_concurrent_batches,
+            fast_lane_max_documents: self.fast_lane_max_documents,
+            checkpoint_documents: self.checkpoint_documents,
             dumps_path: self.dumps_path.clone(),
             snapshots_path: self.snapshots_path.clone(),
+            snapshot_s3: self.snapshot_s3.clone(),
+            restore_points: self.restore_points,
+            change_stream: self.change_stream,
+            task_log: self.task_log,
             auth_env: self.auth_env.clone(),
             version_file_path: self.version_file_path.clone(),
             embedding_cache_cap: self.embedding_cache_cap,
@@ -100,20 +191,94 @@ impl Scheduler {
     }
 
     pub fn new(options: &IndexSchedulerOptions, auth_env: Env<WithoutTls>) -> Scheduler {
+        let notifying_batches = Arc::new(AtomicUsize::new(0));
+        let templated_webhook_requests = options
+            .webhooks
+            .iter()
+            .any(|webhook| webhook.payload_template.is_some())
+            .then(|| spawn_templated_webhooks_sender(notifying_batches.clone()));
+
         Scheduler {
             must_stop_processing: MustStopProcessing::default(),
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
+            sources_wake_up: Arc::new(SignalEvent::auto(false)),
+            paused: Arc::new(AtomicBool::new(false)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            draining: Arc::new(AtomicBool::new(false)),
+            notifying_batches,
+            templated_webhook_requests,
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
             batched_tasks_size_limit: options.batched_tasks_size_limit,
+            max_batched_documents: options.max_batched_documents,
+            autobatching_max_wait: options.autobatching_max_wait,
+            index_autobatching: options.index_autobatching.clone(),
+            max_concurrent_batches: options.max_concurrent_batches.max(1),
+            fast_lane_max_documents: options.fast_lane_max_documents,
+            checkpoint_documents: options.checkpoint_documents,
             dumps_path: options.dumps_path.clone(),
             snapshots_path: options.snapshots_path.clone(),
+            snapshot_s3: options.snapshot_s3.clone(),
+            restore_points: options.restore_points,
+            change_stream: options.change_stream,
+            task_log: options.task_log,
             auth_env,
             version_file_path: options.version_file_path.clone(),
             embedding_cache_cap: options.embedding_cache_cap,
         }
     }
+
+    /// Whether no new batch must be created and the document operations must stop at their next
+    /// checkpoint, because the instance is shutting down or draining.
+    pub(crate) fn is_stopping(&self) -> bool {
+        self.shutting_down.load(Ordering::Relaxed) || self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Whether the content files of the succeeded document additions are kept after their task
+    /// is processed, for the point-in-time restores, the change stream or the task log.
+    pub(crate) fn keeps_content_files(&self) -> bool {
+        self.restore_points.is_some() || self.change_stream || self.task_log
+    }
+
+    /// Whether several batches can be processed at once, in the regular lanes or in the fast lane.
+    pub(crate) fn is_concurrent(&self) -> bool {
+        self.max_concurrent_batches > 1 || self.fast_lane_max_documents.is_some()
+    }
+
+    /// The maximum number of batches that can be processed at once, including the fast lane.
+    pub(crate) fn max_processing_batches(&self) -> usize {
+        self.max_concurrent_batches + self.fast_lane_max_documents.is_some() as usize
+    }
+
+    /// The limits of the batches of the given index, its own ones falling back to the ones of the instance.
+    pub(crate) fn batch_limits(&self, index: &str) -> BatchLimits {
+        let index_limits = self.index_autobatching.get(index);
+        BatchLimits {
+            max_tasks: index_limits
+                .and_then(|limits| limits.max_tasks)
+                .unwrap_or(self.max_number_of_batched_tasks),
+            max_documents: index_limits
+                .and_then(|limits| limits.max_documents)
+                .or(self.max_batched_documents),
+            max_total_size: index_limits
+                .and_then(|limits| limits.max_total_size)
+                .unwrap_or(self.batched_tasks_size_limit),
+            max_wait: index_limits
+                .and_then(|limits| limits.max_wait_ms)
+                .map(Duration::from_millis)
+                .or(self.autobatching_max_wait),
+        }
+    }
+}
+
+/// The limits of the batches of an index.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BatchLimits {
+    pub max_tasks: usize,
+    pub max_documents: Option<u64>,
+    pub max_total_size: u64,
+    pub max_wait: Option<Duration>,
 }
 
 impl IndexScheduler {
@@ -129,6 +294,10 @@ impl IndexScheduler {
     ///      of their processing.
     /// 6. Reset the in-memory list of processed tasks.
     ///
+    /// When more than one batch can be processed at once, the batches targeting a single index
+    /// are processed on their own thread and the tick returns right after spawning it. The other
+    /// batches are only created once all the processing batches are done, and processed in the tick.
+    ///
     /// Returns the number of processed tasks.
     pub(crate) fn tick(&self) -> Result<TickOutcome> {
         #[cfg(test)]
@@ -143,32 +312,135 @@ impl IndexScheduler {
             wtxn.commit()?;
         }
 
+        // The processing batches are left to finish, but no new batch is created while paused,
+        // shutting down or draining.
+        if self.scheduler.paused.load(Ordering::Relaxed) || self.scheduler.is_stopping() {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
+        let concurrent = self.scheduler.is_concurrent();
+        // The processing batches must be retrieved before opening the read transaction so that
+        // a batch that finishes in the meantime is either seen as processing or as written.
+        let processing = self.processing_tasks.read().unwrap().clone();
+        if concurrent && !processing.batches.is_empty() {
+            // The processing batches are stopping or we can't process more of them,
+            // we'll be woken up once one of them is done.
+            if self.scheduler.must_stop_processing.get()
+                || processing.batches.len() >= self.scheduler.max_processing_batches()
+            {
+                return Ok(TickOutcome::WaitForSignal);
+            }
+        }
+
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
-        let (batch, mut processing_batch) =
-            match self.create_next_batch(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))? {
-                Some(batch) => batch,
-                None => return Ok(TickOutcome::WaitForSignal),
-            };
-        let index_uid = batch.index_uid().map(ToOwned::to_owned);
+        let (batch, processing_batch) = match self
+            .create_next_batch(&rtxn, &processing)
+            .map_err(|e| Error::CreateBatch(Box::new(e)))?
+        {
+            Some(batch) => batch,
+            None => return Ok(TickOutcome::WaitForSignal),
+        };
         drop(rtxn);
 
+        // The batches that don't target a single index must be processed alone.
+        let run_in_worker = concurrent && batch.index_uid().is_some();
+        if concurrent && !run_in_worker && !processing.batches.is_empty() {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         // 1. store the starting date with the bitmap of processing tasks.
+        let progress = {
+            let mut processing_tasks = self.processing_tasks.write().unwrap();
+            // `is_drained` reads the processing batches under this lock, the instance must not
+            // be reported as drained while a batch created before the drain is about to start.
+            if self.scheduler.is_stopping() {
+                return Ok(TickOutcome::WaitForSignal);
+            }
+            // We reset the must_stop flag to be sure that we don't stop processing tasks
+            if processing.batches.is_empty() || !concurrent {
+                self.scheduler.must_stop_processing.reset();
+            }
+            if !concurrent {
+                processing_tasks.clear();
+            }
+            // We can clone the processing batch here because we don't want its modification to affect the view of the processing batches
+            processing_tasks.start_processing(processing_batch.clone(), batch.ids())
+        };
+        // We shouldn't crash the tick function if we can't publish the task events.
+        if let Err(e) = self.publish_processing_task_events(&batch, &processing_batch) {
+            tracing::error!("While publishing the task events: {e}");
+        }
+
+        if !run_in_worker {
+            return self.process_and_commit_batch(batch, processing_batch, progress);
+        }
+
+        let batch_uid = processing_batch.uid;
+        let worker = self.private_clone();
+        std::thread::Builder::new().name(format!("batch-worker-{batch_uid}")).spawn(move || {
+            let ret = catch_unwind(AssertUnwindSafe(|| {
+                worker.process_and_commit_batch(batch, processing_batch, progress)
+            }));
+            match ret {
+                Ok(Ok(_)) => (),
+                Ok(Err(e)) => tracing::error!("{e}"),
+                Err(_) => tracing::error!("Internal error: Unexpected panic in a batch worker."),
+            }
+            // The batch may have stopped before being written, we must not keep it as processing forever.
+            worker.processing_tasks.write().unwrap().stop_processing(batch_uid);
+            worker.scheduler.wake_up.signal();
+        })?;
+
+        Ok(TickOutcome::TickAgain(0))
+    }
+
+    /// Publishes the tasks of a batch that just started processing, as they are seen by the routes.
+    fn publish_processing_task_events(
+        &self,
+        batch: &Batch,
+        processing_batch: &ProcessingBatch,
+    ) -> Result<()> {
+        if self.task_events.is_none() {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn()?;
+        let mut tasks = self.queue.tasks.get_existing_tasks(&rtxn, batch.ids())?;
+        for task in &mut tasks {
+            task.status = Status::Processing;
+            task.batch_uid = Some(processing_batch.uid);
+            task.started_at = Some(processing_batch.started_at);
+        }
+        self.publish_task_events(&tasks);
+        Ok(())
+    }
+
+    /// Process a batch that has already been marked as processing and write its outcome.
+    ///
+    /// With a single processing batch at a time, an aborted batch stays marked as processing so that
+    /// the next tick keeps the `started_at` date of its tasks.
+    fn process_and_commit_batch(
+        &self,
+        batch: Batch,
+        mut processing_batch: ProcessingBatch,
+        progress: Progress,
+    ) -> Result<TickOutcome> {
+        let concurrent = self.scheduler.is_concurrent();
+        let batch_uid = processing_batch.uid;
+        let index_uid = batch.index_uid().map(ToOwned::to_owned);
         let mut ids = batch.ids();
         let processed_tasks = ids.len();
-
-        // We reset the must_stop flag to be sure that we don't stop processing tasks
-        self.scheduler.must_stop_processing.reset();
-        let progress = self
-            .processing_tasks
-            .write()
-            .unwrap()
-            // We can clone the processing batch here because we don't want its modification to affect the view of the processing batches
-            .start_processing(processing_batch.clone(), ids.clone());
+        let checkpointed = self.scheduler.checkpoint_documents.is_some()
+            && matches!(
+                &batch,
+                Batch::IndexOperation { op: IndexOperation::DocumentOperation { .. }, .. }
+            );
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::BatchCreated);
 
         // 2. Process the tasks
+        let memory_sampler = MemorySampler::start();
         let res = {
             let cloned_index_scheduler = self.private_clone();
             let processing_batch = &mut processing_batch;
@@ -212,8 +484,12 @@ impl IndexScheduler {
             })
         };
 
+        let peak_memory = memory_sampler.stop();
+
         // Reset the currently updating index to relinquish the index handle
-        self.index_mapper.set_currently_updating_index(None);
+        if let Some(index_uid) = &index_uid {
+            self.index_mapper.set_currently_updating_index(index_uid, None);
+        }
 
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::AcquiringWtxn)?;
@@ -230,6 +506,12 @@ impl IndexScheduler {
                 #[cfg(test)]
                 self.breakpoint(crate::test_utils::Breakpoint::ProcessBatchSucceeded);
 
+                // A document operation stopped at a checkpoint only returns the tasks it processed,
+                // the other ones are left enqueued to be processed by the next batch.
+                if checkpointed {
+                    ids &= RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid));
+                }
+
                 let (task_progress, task_progress_obj) = AtomicTaskStep::new(tasks.len() as u32);
                 progress.update_progress(task_progress_obj);
                 congestion = cong;
@@ -257,6 +539,17 @@ impl IndexScheduler {
                         Some(_) => failure += 1,
                         None => success += 1,
                     }
+                    if task.status == Status::Succeeded {
+                        self.key_usage.record(
+                            &mut wtxn,
+                            &self.index_mapper,
+                            &task,
+                            processing_batch.started_at,
+                        )?;
+                        if self.scheduler.task_log {
+                            task_log::append(&mut wtxn, &self.queue, &task)?;
+                        }
+                    }
 
                     self.queue
                         .tasks
@@ -284,6 +577,10 @@ impl IndexScheduler {
                 // the `started_at` date times and `processings` of the current processing tasks.
                 // This date time is used by the task cancelation to store the right `started_at`
                 // date in the task on disk.
+                // The concurrent batches don't block the others though, their tasks are simply enqueued again.
+                if concurrent {
+                    self.processing_tasks.write().unwrap().stop_processing(batch_uid);
+                }
                 return Ok(TickOutcome::TickAgain(0));
             }
             // If an index said it was full, we need to:
@@ -302,6 +599,9 @@ impl IndexScheduler {
                 wtxn.abort();
 
                 tracing::info!("The max database size was reached. Resizing the index.");
+                if concurrent {
+                    self.processing_tasks.write().unwrap().stop_processing(batch_uid);
+                }
 
                 return Ok(TickOutcome::TickAgain(0));
             }
@@ -359,6 +659,8 @@ impl IndexScheduler {
             congestion_info.insert("blocking_ratio".into(), congestion.congestion_ratio().into());
             congestion_info
         });
+        processing_batch.stats.resource_usage =
+            Some(resource_usage::resource_usage(&processing_batch, peak_memory));
 
         if let Some(congestion) = congestion {
             tracing::debug!(
@@ -380,14 +682,16 @@ impl IndexScheduler {
 
         // We should stop processing AFTER everything is processed and written to disk otherwise, a batch (which only lives in RAM) may appear in the processing task
         // and then become « not found » for some time until the commit everything is written and the final commit is made.
-        self.processing_tasks.write().unwrap().stop_processing();
+        // The instance isn't drained until the webhooks of the batch are sent though.
+        self.scheduler.notifying_batches.fetch_add(1, Ordering::Relaxed);
+        self.processing_tasks.write().unwrap().stop_processing(batch_uid);
 
         // Once the tasks are committed, we should delete all the update files associated ASAP to avoid leaking files in case of a restart
         tracing::debug!("Deleting the update files");
 
         //We take one read transaction **per thread**. Then, every thread is going to pull out new IDs from the roaring bitmap with the help of an atomic shared index into the bitmap
         let idx = AtomicU32::new(0);
-        (0..current_num_threads()).into_par_iter().try_for_each(|_| -> Result<()> {
+        let deleted = (0..current_num_threads()).into_par_iter().try_for_each(|_| -> Result<()> {
             let rtxn = self.read_txn()?;
             while let Some(id) = ids.select(idx.fetch_add(1, Ordering::Relaxed)) {
                 let task = self
@@ -396,6 +700,20 @@ impl IndexScheduler {
                     .get_task(&rtxn, id)
                     .map_err(|e| Error::UnrecoverableError(Box::new(e)))?
                     .ok_or(Error::CorruptedTaskQueue)?;
+                // the documents of the succeeded additions are replayed by the point-in-time
+                // restores, streamed by the change stream and shipped with the task log, which
+                // ships the documents imported from a URL too
+                if task.status == Status::Succeeded
+                    && match task.kind {
+                        KindWithContent::DocumentAdditionOrUpdate { .. } => {
+                            self.scheduler.keeps_content_files()
+                        }
+                        KindWithContent::DocumentImportUrl { .. } => self.scheduler.task_log,
+                        _ => false,
+                    }
+                {
+                    continue;
+                }
                 if let Err(e) = self.queue.delete_persisted_task_data(&task) {
                     tracing::error!(
                         "Failure to delete the content files associated with task {}. Error: {e}",
@@ -404,10 +722,15 @@ impl IndexScheduler {
                 }
             }
             Ok(())
-        })?;
+        });
 
         // We shouldn't crash the tick function if we can't send data to the webhook.
         let _ = self.notify_webhook(&ids);
+        if let Err(e) = self.publish_updated_task_events(&ids) {
+            tracing::error!("While publishing the task events: {e}");
+        }
+        self.scheduler.notifying_batches.fetch_sub(1, Ordering::Relaxed);
+        deleted?;
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::AfterProcessing);
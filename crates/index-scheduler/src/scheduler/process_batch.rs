@@ -20,7 +20,7 @@ use crate::utils::{
     self, remove_n_tasks_datetime_earlier_than, remove_task_datetime, swap_index_uid_in_task,
     ProcessingBatch,
 };
-use crate::{Error, IndexScheduler, Result, TaskId};
+use crate::{task_log, Error, IndexScheduler, Result, TaskId};
 
 impl IndexScheduler {
     /// Apply the operation associated with the given batch.
@@ -123,6 +123,15 @@ impl IndexScheduler {
             Batch::Dump(task) => {
                 self.process_dump_creation(progress, task).map(|tasks| (tasks, None))
             }
+            Batch::DumpImport(task) => {
+                self.process_dump_import(progress, task).map(|tasks| (tasks, None))
+            }
+            Batch::PointInTimeRestore(task) => {
+                self.process_point_in_time_restore(progress, task).map(|tasks| (tasks, None))
+            }
+            Batch::IndexRestore(task) => {
+                self.process_index_restore(progress, task).map(|tasks| (tasks, None))
+            }
             Batch::IndexOperation { op, must_create_index } => {
                 let index_uid = op.index_uid().to_string();
                 let index = if must_create_index {
@@ -135,19 +144,20 @@ impl IndexScheduler {
                 };
 
                 // the index operation can take a long time, so save this handle to make it available to the search for the duration of the tick
-                self.index_mapper
-                    .set_currently_updating_index(Some((index_uid.clone(), index.clone())));
+                self.index_mapper.set_currently_updating_index(&index_uid, Some(index.clone()));
 
-                let mut index_wtxn = index.write_txn()?;
+                let usage_before = index.embedder_usage(&index.read_txn()?)?;
                 let (tasks, congestion) =
-                    self.apply_index_operation(&mut index_wtxn, &index, op, progress)?;
-
-                {
-                    let span = tracing::trace_span!(target: "indexing::scheduler", "commit");
-                    let _entered = span.enter();
-
-                    index_wtxn.commit()?;
-                }
+                    self.apply_index_operation_with_checkpoints(&index, op, progress)?;
+                let usage_after = index.embedder_usage(&index.read_txn()?)?;
+                current_batch.stats.embedder_usage = usage_after
+                    .into_iter()
+                    .map(|(name, after)| {
+                        let before = usage_before.get(&name).copied().unwrap_or_default();
+                        (name, after.saturating_sub(before))
+                    })
+                    .filter(|(_, usage)| !usage.is_empty())
+                    .collect();
 
                 // if the update processed successfully, we're going to store the new
                 // stats of the index. Since the tasks have already been processed and
@@ -261,6 +271,7 @@ impl IndexScheduler {
                     Err(Error::IndexNotFound(_)) if index_has_been_created => (),
                     Err(e) => return Err(e),
                 }
+                self.forget_search_embedder_usage(&index_uid);
 
                 // We set all the tasks details to the default value.
                 for task in &mut tasks {
@@ -279,7 +290,7 @@ impl IndexScheduler {
                 progress.update_progress(SwappingTheIndexes::EnsuringCorrectnessOfTheSwap);
 
                 let mut wtxn = self.env.write_txn()?;
-                let swaps = if let KindWithContent::IndexSwap { swaps } = &task.kind {
+                let swaps = if let KindWithContent::IndexSwap { swaps, .. } = &task.kind {
                     swaps
                 } else {
                     unreachable!()
@@ -351,6 +362,16 @@ impl IndexScheduler {
                     task.error = None;
                 }
 
+                Ok((tasks, None))
+            }
+            Batch::FailedDependencies { mut tasks } => {
+                for task in tasks.iter_mut() {
+                    let dependency = task.depends_on.ok_or(Error::CorruptedTaskQueue)?;
+                    task.status = Status::Failed;
+                    task.error = Some(Error::TaskDependencyFailed { dependency }.into());
+                    task.details = task.details.as_ref().map(|d| d.to_failed());
+                }
+
                 Ok((tasks, None))
             }
         }
@@ -433,12 +454,18 @@ impl IndexScheduler {
         let mut to_delete_tasks = all_task_ids & matched_tasks;
         to_delete_tasks -= &**processing_tasks;
         to_delete_tasks -= &enqueued_tasks;
+        // nor the tasks that enqueued tasks are still waiting for
+        for entry in self.queue.tasks.depends_on.iter(wtxn)? {
+            let (dependency, _) = entry?;
+            to_delete_tasks.remove(dependency);
+        }
 
         // 2. We now have a list of tasks to delete, delete them
         let mut affected_indexes = HashSet::new();
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_labels = HashSet::new();
         // The tasks that have been removed *per batches*.
         let mut affected_batches: HashMap<BatchId, RoaringBitmap> = HashMap::new();
 
@@ -451,10 +478,20 @@ impl IndexScheduler {
             affected_indexes.extend(task.indexes().into_iter().map(|x| x.to_owned()));
             affected_statuses.insert(task.status);
             affected_kinds.insert(task.kind.as_kind());
-            // Note: don't delete the persisted task data since
-            // we can only delete succeeded, failed, and canceled tasks.
-            // In each of those cases, the persisted data is supposed to
-            // have been deleted already.
+            // Note: the persisted task data is supposed to have been deleted
+            // already since we can only delete succeeded, failed, and canceled tasks.
+            // The content files of the succeeded document additions are the exception,
+            // they are kept for the point-in-time restores and the change stream, and by
+            // the task log until it is trimmed, along with the documents imported from a URL.
+            if let Some(content_uuid) = task.content_uuid() {
+                if self.scheduler.keeps_content_files()
+                    && task.status == Status::Succeeded
+                    && self.queue.file_store.get_update_path(content_uuid).exists()
+                    && !task_log::contains(wtxn, &self.queue, task.uid)?
+                {
+                    self.queue.delete_persisted_task_data(&task)?;
+                }
+            }
             utils::remove_task_datetime(
                 wtxn,
                 self.queue.tasks.enqueued_at,
@@ -480,6 +517,7 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            affected_labels.extend(task.labels.iter().map(|(key, value)| format!("{key}={value}")));
             if let Some(batch_uid) = task.batch_uid {
                 affected_batches.entry(batch_uid).or_default().insert(task_id);
             }
@@ -523,6 +561,9 @@ impl IndexScheduler {
                 }
             }
         }
+        for label in affected_labels {
+            self.queue.tasks.update_label(wtxn, &label, |bitmap| *bitmap -= &to_delete_tasks)?;
+        }
         progress.update_progress(TaskDeletionProgress::DeletingBatches);
         let (atomic_progress, batch_progress) = AtomicBatchStep::new(affected_batches.len() as u32);
         progress.update_progress(batch_progress);
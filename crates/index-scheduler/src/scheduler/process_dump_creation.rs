@@ -25,12 +25,18 @@ impl IndexScheduler {
     ) -> Result<Vec<Task>> {
         progress.update_progress(DumpCreationProgress::StartTheDumpCreation);
         let started_at = OffsetDateTime::now_utc();
-        let (keys, instance_uid) =
-            if let KindWithContent::DumpCreation { keys, instance_uid } = &task.kind {
-                (keys, instance_uid)
+        let (keys, instance_uid, index_uids, skip_tasks) =
+            if let KindWithContent::DumpCreation { keys, instance_uid, index_uids, skip_tasks } =
+                &task.kind
+            {
+                (keys, instance_uid, index_uids, *skip_tasks)
             } else {
                 unreachable!();
             };
+        // Whether the index must be dumped, a dump of a subset of the indexes leaves out the
+        // tasks and the batches targeting the other indexes.
+        let is_dumped =
+            |uid: &str| index_uids.as_ref().map_or(true, |uids| uids.iter().any(|u| u == uid));
         let dump = dump::DumpWriter::new(*instance_uid)?;
 
         // 1. dump the keys
@@ -57,8 +63,16 @@ impl IndexScheduler {
             }
 
             let (_, mut t) = ret?;
+            if skip_tasks || !t.indexes().into_iter().all(is_dumped) {
+                atomic.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
             let status = t.status;
-            let content_file = t.content_uuid();
+            // The dumps uploaded to be imported are not made of documents
+            let content_file = match t.kind {
+                KindWithContent::DumpImport { .. } => None,
+                _ => t.content_uuid(),
+            };
 
             // In the case we're dumping ourselves we want to be marked as finished
             // to not loop over ourselves indefinitely.
@@ -119,6 +133,10 @@ impl IndexScheduler {
             }
 
             let (_, mut b) = ret?;
+            if skip_tasks || !b.stats.index_uids.keys().all(|uid| is_dumped(uid)) {
+                atomic_batch_progress.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
             // In the case we're dumping ourselves we want to be marked as finished
             // to not loop over ourselves indefinitely.
             if b.uid == task.uid {
@@ -140,16 +158,18 @@ impl IndexScheduler {
 
         // 4. Dump the indexes
         progress.update_progress(DumpCreationProgress::DumpTheIndexes);
-        let nb_indexes = self.index_mapper.index_mapping.len(&rtxn)? as u32;
-        let mut count = 0;
-        let () = self.index_mapper.try_for_each_index(&rtxn, |uid, index| -> Result<()> {
+        let mut index_names = self.index_mapper.index_names(&rtxn)?;
+        index_names.retain(|uid| is_dumped(uid));
+        let nb_indexes = index_names.len() as u32;
+        for (count, uid) in index_names.iter().enumerate() {
             progress.update_progress(VariableNameStep::<DumpCreationProgress>::new(
                 uid.to_string(),
-                count,
+                count as u32,
                 nb_indexes,
             ));
-            count += 1;
 
+            let index = self.index_mapper.index(&rtxn, uid)?;
+            let source = self.index_mapper.source_of(&rtxn, uid)?;
             let rtxn = index.read_txn()?;
             let metadata = IndexMetadata {
                 uid: uid.to_owned(),
@@ -162,6 +182,9 @@ impl IndexScheduler {
                     .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?,
             };
             let mut index_dumper = dump.create_index(uid, &metadata)?;
+            if let Some(source) = &source {
+                index_dumper.source(source)?;
+            }
 
             let fields_ids_map = index.fields_ids_map(&rtxn)?;
             let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
@@ -242,14 +265,13 @@ impl IndexScheduler {
 
             // 4.2. Dump the settings
             let settings = meilisearch_types::settings::settings(
-                index,
+                &index,
                 &rtxn,
                 meilisearch_types::settings::SecretPolicy::RevealSecrets,
             )
             .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
             index_dumper.settings(&settings)?;
-            Ok(())
-        })?;
+        }
 
         // 5. Dump experimental feature settings
         progress.update_progress(DumpCreationProgress::DumpTheExperimentalFeatures);
@@ -257,6 +279,9 @@ impl IndexScheduler {
         dump.create_experimental_features(features)?;
         let network = self.network();
         dump.create_network(network)?;
+        // The aliases are dumped even when they are disabled so they can be enabled again later.
+        let index_aliases = self.features.index_aliases();
+        dump.create_index_aliases(index_aliases)?;
 
         let dump_uid = started_at.format(format_description!(
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
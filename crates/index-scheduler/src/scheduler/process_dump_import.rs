@@ -0,0 +1,204 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek};
+
+use dump::{DumpIndexReader, DumpReader};
+use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
+use meilisearch_types::milli::progress::{Progress, VariableNameStep};
+use meilisearch_types::milli::update::{
+    IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod,
+};
+use meilisearch_types::milli::{self, Index};
+use meilisearch_types::settings::apply_settings_to_builder;
+use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
+
+use crate::processing::DumpImportProgress;
+use crate::{import_url, Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Imports the indexes of a dump into the running instance.
+    ///
+    /// The keys, the tasks and the experimental features of the dump are ignored. The import
+    /// fails without creating anything if one of the indexes already exists, and the indexes
+    /// already imported are deleted if it fails midway.
+    pub(super) fn process_dump_import(
+        &self,
+        progress: Progress,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(DumpImportProgress::RetrievingTheDump);
+        let KindWithContent::DumpImport { dump_file, url, index_renames } = &task.kind else {
+            unreachable!();
+        };
+
+        let archive = match (dump_file, url) {
+            (Some(uuid), _) => self.queue.file_store.get_update(*uuid)?,
+            (None, Some(url)) => self.fetch_remote_dump(url)?,
+            // The archives uploaded to an instance are not part of its dumps.
+            (None, None) => {
+                return Err(Error::DumpImport("the dump archive is missing".to_string()))
+            }
+        };
+        let dump = DumpReader::open(BufReader::new(archive))
+            .map_err(|e| Error::DumpImport(e.to_string()))?;
+
+        let target_name = |uid: &str| index_renames.get(uid).cloned().unwrap_or(uid.to_string());
+        let indexes = dump.indexes()?.collect::<std::result::Result<Vec<_>, _>>()?;
+        let index_names: Vec<_> =
+            indexes.iter().map(|index| target_name(&index.metadata().uid)).collect();
+        let rtxn = self.env.read_txn()?;
+        for name in &index_names {
+            if self.index_mapper.exists(&rtxn, name)? {
+                return Err(Error::IndexAlreadyExists(name.clone()));
+            }
+        }
+        drop(rtxn);
+
+        progress.update_progress(DumpImportProgress::ImportingTheIndexes);
+        let nb_indexes = index_names.len() as u32;
+        let mut imported = Vec::new();
+        let mut indexed_documents = 0;
+        for (count, (index, name)) in indexes.into_iter().zip(&index_names).enumerate() {
+            progress.update_progress(VariableNameStep::<DumpImportProgress>::new(
+                name.to_string(),
+                count as u32,
+                nb_indexes,
+            ));
+
+            match self.import_dumped_index(index, name, &mut imported) {
+                Ok(documents) => indexed_documents += documents,
+                Err(e) => {
+                    for name in imported {
+                        let wtxn = self.env.write_txn()?;
+                        if let Err(error) = self.index_mapper.delete_index(wtxn, &name) {
+                            tracing::error!(
+                                "Could not delete the partially imported index `{name}`: {error}"
+                            );
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::DumpImport {
+            imported_indexes: Some(imported),
+            indexed_documents: Some(indexed_documents),
+        });
+        Ok(vec![task])
+    }
+
+    /// Creates the index `name` with the settings and the documents of the dumped index and
+    /// returns the number of documents imported. The index is pushed to `created` as soon as it
+    /// exists, so that it can be deleted if the import fails.
+    fn import_dumped_index(
+        &self,
+        mut dumped: DumpIndexReader,
+        name: &str,
+        created: &mut Vec<String>,
+    ) -> Result<u64> {
+        let metadata = dumped.metadata().clone();
+
+        let wtxn = self.env.write_txn()?;
+        // checked in the same transaction as the creation, in case a task created it meanwhile
+        if self.index_mapper.exists(&wtxn, name)? {
+            return Err(Error::IndexAlreadyExists(name.to_string()));
+        }
+        let date = Some((metadata.created_at, metadata.updated_at));
+        let index = self.index_mapper.create_index(wtxn, name, date)?;
+        created.push(name.to_string());
+
+        let documents = self.index_dumped_index(&mut dumped, &index, name)?;
+        self.refresh_index_stats(name)?;
+        Ok(documents)
+    }
+
+    /// Applies the settings and adds the documents of the dumped index to the empty index, and
+    /// returns the number of documents added. `name` is only used in the errors.
+    pub(super) fn index_dumped_index(
+        &self,
+        dumped: &mut DumpIndexReader,
+        index: &Index,
+        name: &str,
+    ) -> Result<u64> {
+        let metadata = dumped.metadata().clone();
+        let must_stop_processing = self.scheduler.must_stop_processing.clone();
+        let indexer_config = self.indexer_config();
+
+        let mut wtxn = index.write_txn()?;
+        let mut builder = milli::update::Settings::new(&mut wtxn, index, indexer_config);
+        if let Some(ref primary_key) = metadata.primary_key {
+            builder.set_primary_key(primary_key.to_string());
+        }
+        let settings = dumped.settings()?;
+        apply_settings_to_builder(&settings, &mut builder);
+        builder
+            .execute(
+                |indexing_step| tracing::debug!(update = ?indexing_step),
+                || must_stop_processing.get(),
+            )
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+
+        // the documents must be converted to the format accepted by the index
+        let file = tempfile::tempfile()?;
+        let mut builder = DocumentsBatchBuilder::new(BufWriter::new(file));
+        for document in dumped.documents()? {
+            if self.scheduler.must_stop_processing.get() {
+                return Err(Error::AbortedTask);
+            }
+            builder.append_json_object(&document?)?;
+        }
+        let file = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+        let reader = DocumentsBatchReader::from_reader(BufReader::new(file))
+            .map_err(|e| Error::from_milli(e.into(), Some(name.to_string())))?;
+
+        let embedder_configs = index
+            .embedding_configs(&wtxn)
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+        let embedders = self.embedders(name.to_string(), embedder_configs)?;
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            index,
+            indexer_config,
+            IndexDocumentsConfig {
+                update_method: IndexDocumentsMethod::ReplaceDocuments,
+                ..Default::default()
+            },
+            |indexing_step| tracing::trace!(update = ?indexing_step),
+            || must_stop_processing.get(),
+        )
+        .map_err(|e| Error::from_milli(e, Some(name.to_string())))?
+        .with_embedders(embedders);
+        let (builder, documents) = builder
+            .add_documents(reader)
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+        let documents =
+            documents.map_err(|e| Error::from_milli(e.into(), Some(name.to_string())))?;
+        builder.execute().map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+        wtxn.commit()?;
+        Ok(documents)
+    }
+
+    /// Downloads the dump served at the URL into a temporary file, from the hosts the documents
+    /// can be imported from.
+    fn fetch_remote_dump(&self, url: &str) -> Result<File> {
+        let options = self.import_url.as_ref().ok_or(Error::DumpImportUrlDisabled)?;
+        let import_error = |error: String| Error::DumpImport(format!("`{url}`: {error}"));
+
+        let response =
+            import_url::agent(options).get(url).call().map_err(|e| import_error(e.to_string()))?;
+
+        let mut fetched = tempfile::tempfile()?;
+        let mut reader = response.into_reader().take(options.size_limit.saturating_add(1));
+        let size =
+            std::io::copy(&mut reader, &mut fetched).map_err(|e| import_error(e.to_string()))?;
+        if size > options.size_limit {
+            return Err(import_error(format!(
+                "the dump is larger than the payload size limit of {} bytes",
+                options.size_limit
+            )));
+        }
+        fetched.rewind()?;
+        Ok(fetched)
+    }
+}
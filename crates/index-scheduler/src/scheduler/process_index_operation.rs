@@ -1,23 +1,138 @@
+use std::fs::File;
+
 use bumpalo::collections::CollectIn;
 use bumpalo::Bump;
 use meilisearch_types::heed::RwTxn;
 use meilisearch_types::milli::documents::PrimaryKey;
+use meilisearch_types::milli::index::DocumentOperationCheckpoint;
 use meilisearch_types::milli::progress::Progress;
 use meilisearch_types::milli::update::new::indexer::{self, UpdateByFunction};
 use meilisearch_types::milli::update::DocumentAdditionResult;
 use meilisearch_types::milli::{self, ChannelCongestion, Filter, ThreadPoolNoAbortBuilder};
-use meilisearch_types::settings::apply_settings_to_builder;
+use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
 use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
 use meilisearch_types::Index;
+use memmap2::{Mmap, MmapOptions};
 use roaring::RoaringBitmap;
+use serde::de::IgnoredAny;
 
-use super::create_batch::{DocumentOperation, IndexOperation};
+use super::create_batch::{DocumentOperation, DocumentsWindow, IndexOperation};
 use crate::processing::{
-    DocumentDeletionProgress, DocumentEditionProgress, DocumentOperationProgress, SettingsProgress,
+    DocumentDeletionProgress, DocumentEditionProgress, DocumentImportUrlProgress,
+    DocumentOperationProgress, SettingsProgress,
 };
 use crate::{Error, IndexScheduler, Result};
 
 impl IndexScheduler {
+    /// Process the index operation on the given index and commit it.
+    ///
+    /// When checkpoints are enabled, a document operation is processed and committed in several
+    /// steps, the tasks adding many documents being split themselves. It stops at its next
+    /// checkpoint if the instance is shutting down or draining, or if one of its steps fails after
+    /// the first one. Only the tasks of the committed steps are returned then, the other ones are
+    /// left enqueued so that the next batch resumes from the checkpoint.
+    ///
+    /// A split task fails at its first failing step, the documents of its previous steps being
+    /// kept indexed.
+    pub(crate) fn apply_index_operation_with_checkpoints(
+        &self,
+        index: &Index,
+        operation: IndexOperation,
+        progress: Progress,
+    ) -> Result<(Vec<Task>, Option<ChannelCongestion>)> {
+        let (operations, checkpoint) = match self.scheduler.checkpoint_documents {
+            Some(max_documents) => {
+                let checkpoint = index.document_operation_checkpoint(&index.read_txn()?)?;
+                (operation.split_at_checkpoints(max_documents, checkpoint), checkpoint)
+            }
+            None => (vec![operation], None),
+        };
+
+        let steps = operations.len();
+        let mut tasks: Vec<Task> = Vec::new();
+        let mut congestion = None;
+        // The task being split and the number of its documents indexed by its previous steps.
+        let mut indexed_documents =
+            checkpoint.map(|checkpoint| (checkpoint.task_uid, checkpoint.indexed_documents));
+        for (step, operation) in operations.into_iter().enumerate() {
+            if step > 0 && self.scheduler.is_stopping() {
+                tracing::info!(
+                    "Stopping the document operation at checkpoint {step}/{steps} to shut down or drain."
+                );
+                break;
+            }
+
+            let window = match &operation {
+                IndexOperation::DocumentOperation { window: Some(window), tasks, .. } => {
+                    tasks.first().map(|task| (*window, task.uid))
+                }
+                _ => None,
+            };
+            // The remaining steps of a failed task are skipped.
+            if let Some((_, uid)) = window {
+                if tasks.last().is_some_and(|task| task.uid == uid) {
+                    continue;
+                }
+            }
+
+            let mut index_wtxn = index.write_txn()?;
+            let outcome = self
+                .apply_index_operation(&mut index_wtxn, index, operation, progress.clone())
+                .and_then(|(mut step_tasks, step_congestion)| {
+                    let mut next_indexed_documents = None;
+                    if let Some((window, uid)) = window {
+                        let task = &mut step_tasks[0];
+                        let previously_indexed = indexed_documents
+                            .filter(|(task_uid, _)| *task_uid == uid)
+                            .map_or(0, |(_, indexed)| indexed);
+                        let indexed = previously_indexed + indexed_documents_of(task);
+                        let processed_documents = window.skip + window.take;
+                        if task.status == Status::Failed
+                            || processed_documents >= documents_count_of(task)
+                        {
+                            index.delete_document_operation_checkpoint(&mut index_wtxn)?;
+                            set_indexed_documents(task, indexed);
+                        } else {
+                            let checkpoint = DocumentOperationCheckpoint {
+                                task_uid: uid,
+                                processed_documents,
+                                indexed_documents: indexed,
+                            };
+                            index
+                                .put_document_operation_checkpoint(&mut index_wtxn, &checkpoint)?;
+                            next_indexed_documents = Some((uid, indexed));
+                            // The task is only returned once all its documents are processed.
+                            step_tasks.clear();
+                        }
+                    }
+
+                    let span = tracing::trace_span!(target: "indexing::scheduler", "commit");
+                    let _entered = span.enter();
+
+                    index_wtxn.commit()?;
+                    Ok((step_tasks, step_congestion, next_indexed_documents))
+                });
+
+            match outcome {
+                Ok((step_tasks, step_congestion, next_indexed_documents)) => {
+                    indexed_documents = next_indexed_documents;
+                    tasks.extend(step_tasks);
+                    congestion = step_congestion.or(congestion);
+                }
+                // The committed steps must not be reported as failed, the next batch retries the other ones.
+                Err(e) if step > 0 => {
+                    tracing::warn!(
+                        "Stopping the document operation at checkpoint {step}/{steps}: {e}"
+                    );
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok((tasks, congestion))
+    }
+
     /// Process the index operation on the given index.
     ///
     /// ## Return
@@ -61,7 +176,13 @@ impl IndexScheduler {
 
                 Ok((tasks, None))
             }
-            IndexOperation::DocumentOperation { index_uid, primary_key, operations, mut tasks } => {
+            IndexOperation::DocumentOperation {
+                index_uid,
+                primary_key,
+                operations,
+                mut tasks,
+                window,
+            } => {
                 progress.update_progress(DocumentOperationProgress::RetrievingConfig);
                 // TODO: at some point, for better efficiency we might want to reuse the bumpalo for successive batches.
                 // this is made difficult by the fact we're doing private clones of the index scheduler and sending it
@@ -70,9 +191,16 @@ impl IndexScheduler {
                 for operation in &operations {
                     match operation {
                         DocumentOperation::Replace(content_uuid)
-                        | DocumentOperation::Update(content_uuid) => {
+                        | DocumentOperation::Update(content_uuid)
+                        | DocumentOperation::MergePatch(content_uuid)
+                        | DocumentOperation::JsonPatch(content_uuid)
+                        | DocumentOperation::SkipExisting(content_uuid)
+                        | DocumentOperation::FailOnExisting(content_uuid) => {
                             let content_file = self.queue.file_store.get_update(*content_uuid)?;
-                            let mmap = unsafe { memmap2::Mmap::map(&content_file)? };
+                            let mmap = match window {
+                                Some(window) => map_documents_window(&content_file, window)?,
+                                None => unsafe { memmap2::Mmap::map(&content_file)? },
+                            };
                             content_files.push(mmap);
                         }
                         _ => (),
@@ -103,6 +231,30 @@ impl IndexScheduler {
                                 .update_documents(mmap)
                                 .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
                         }
+                        DocumentOperation::MergePatch(_content_uuid) => {
+                            let mmap = content_files_iter.next().unwrap();
+                            indexer
+                                .merge_patch_documents(mmap)
+                                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                        }
+                        DocumentOperation::JsonPatch(_content_uuid) => {
+                            let mmap = content_files_iter.next().unwrap();
+                            indexer
+                                .json_patch_documents(mmap)
+                                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                        }
+                        DocumentOperation::SkipExisting(_content_uuid) => {
+                            let mmap = content_files_iter.next().unwrap();
+                            indexer
+                                .skip_existing_documents(mmap)
+                                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                        }
+                        DocumentOperation::FailOnExisting(_content_uuid) => {
+                            let mmap = content_files_iter.next().unwrap();
+                            indexer
+                                .fail_on_existing_documents(mmap)
+                                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                        }
                         DocumentOperation::Delete(document_ids) => {
                             let document_ids: bumpalo::collections::vec::Vec<_> = document_ids
                                 .iter()
@@ -174,6 +326,10 @@ impl IndexScheduler {
                 progress.update_progress(DocumentOperationProgress::Indexing);
                 let mut congestion = None;
                 if tasks.iter().any(|res| res.error.is_none()) {
+                    document_changes
+                        .record_document_revisions(index_wtxn, index, &db_fields_ids_map)
+                        .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+
                     congestion = Some(
                         indexer::index(
                             index_wtxn,
@@ -203,6 +359,43 @@ impl IndexScheduler {
 
                 Ok((tasks, congestion))
             }
+            IndexOperation::DocumentImportUrl { index_uid, task } => {
+                let KindWithContent::DocumentImportUrl {
+                    primary_key,
+                    method,
+                    content_file,
+                    replace_all_documents,
+                    ..
+                } = &task.kind
+                else {
+                    unreachable!()
+                };
+                let (primary_key, method) = (primary_key.clone(), *method);
+                let operations = vec![DocumentOperation::new(method, *content_file)];
+
+                // The documents are only cleared in the same transaction as the indexing,
+                // so the index is never seen empty by the searches.
+                if *replace_all_documents {
+                    progress.update_progress(DocumentImportUrlProgress::ClearingDocuments);
+                    milli::update::ClearDocuments::new(index_wtxn, index)
+                        .execute()
+                        .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                }
+
+                progress.update_progress(DocumentImportUrlProgress::IndexingDocuments);
+                self.apply_index_operation(
+                    index_wtxn,
+                    index,
+                    IndexOperation::DocumentOperation {
+                        index_uid,
+                        primary_key,
+                        operations,
+                        tasks: vec![task],
+                        window: None,
+                    },
+                    progress,
+                )
+            }
             IndexOperation::DocumentEdition { index_uid, mut task } => {
                 progress.update_progress(DocumentEditionProgress::RetrievingConfig);
 
@@ -487,15 +680,23 @@ impl IndexScheduler {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
 
+                // The consecutive settings updates are collapsed into their final state
+                // so that the superseded ones don't trigger any work.
+                let mut merged_settings: Option<Settings<Unchecked>> = None;
                 for (task, (_, settings)) in tasks.iter_mut().zip(settings) {
-                    let checked_settings = settings.clone().check();
+                    match merged_settings.as_mut() {
+                        Some(merged) => merged.merge(&settings),
+                        None => merged_settings = Some(settings.clone()),
+                    }
                     task.details = Some(Details::SettingsUpdate { settings: Box::new(settings) });
-                    apply_settings_to_builder(&checked_settings, &mut builder);
 
                     // We can apply the status right now and if an update fail later
                     // the whole batch will be marked as failed.
                     task.status = Status::Succeeded;
                 }
+                if let Some(merged_settings) = merged_settings {
+                    apply_settings_to_builder(&merged_settings.check(), &mut builder);
+                }
 
                 progress.update_progress(SettingsProgress::ApplyTheSettings);
                 builder
@@ -537,3 +738,44 @@ impl IndexScheduler {
         }
     }
 }
+
+/// Maps the documents of the window in the content file, which holds them one after the other.
+fn map_documents_window(content_file: &File, window: DocumentsWindow) -> Result<Mmap> {
+    let mmap = unsafe { Mmap::map(content_file)? };
+    let mut documents = serde_json::Deserializer::from_slice(&mmap).into_iter::<IgnoredAny>();
+    let mut offset_after = |count: u64| -> Result<usize> {
+        for _ in 0..count {
+            documents.next().transpose().map_err(std::io::Error::from)?;
+        }
+        Ok(documents.byte_offset())
+    };
+    let start = offset_after(window.skip)?;
+    let end = offset_after(window.take)?;
+
+    let mmap =
+        unsafe { MmapOptions::new().offset(start as u64).len(end - start).map(content_file)? };
+    Ok(mmap)
+}
+
+/// The number of documents of the content file of a document addition.
+fn documents_count_of(task: &Task) -> u64 {
+    match &task.kind {
+        KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => *documents_count,
+        _ => 0,
+    }
+}
+
+fn indexed_documents_of(task: &Task) -> u64 {
+    match &task.details {
+        Some(Details::DocumentAdditionOrUpdate { indexed_documents, .. }) => {
+            indexed_documents.unwrap_or_default()
+        }
+        _ => 0,
+    }
+}
+
+fn set_indexed_documents(task: &mut Task, indexed: u64) {
+    if let Some(Details::DocumentAdditionOrUpdate { indexed_documents, .. }) = &mut task.details {
+        *indexed_documents = Some(indexed);
+    }
+}
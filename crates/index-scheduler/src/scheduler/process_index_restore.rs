@@ -0,0 +1,122 @@
+use std::fs::{self, File};
+use std::io::{BufReader, ErrorKind};
+
+use dump::DumpReader;
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::milli::Index;
+use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
+use meilisearch_types::{compression, VERSION_FILE_NAME};
+use uuid::Uuid;
+
+use crate::processing::IndexRestoreProgress;
+use crate::{Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Replaces an index with its copy from a snapshot of the snapshot directory or a dump of the
+    /// dump directory, or creates it if it doesn't exist anymore.
+    ///
+    /// Only the index is extracted from the backup, its other indexes, its tasks and its keys are
+    /// ignored. The current index is only replaced once the copy is ready.
+    pub(super) fn process_index_restore(
+        &self,
+        progress: Progress,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(IndexRestoreProgress::ExtractingTheIndex);
+        let KindWithContent::IndexRestore { index_uid, snapshot, dump_uid } = &task.kind else {
+            unreachable!();
+        };
+
+        let (uuid, copy) = match (snapshot, dump_uid) {
+            (Some(snapshot), _) => self.extract_index_from_snapshot(index_uid, snapshot)?,
+            (None, Some(dump_uid)) => self.extract_index_from_dump(index_uid, dump_uid)?,
+            (None, None) => {
+                return Err(Error::IndexRestore {
+                    index_uid: index_uid.clone(),
+                    error: "the backup to restore it from is missing".to_string(),
+                })
+            }
+        };
+
+        progress.update_progress(IndexRestoreProgress::ReplacingTheIndex);
+        let wtxn = self.env.write_txn()?;
+        self.index_mapper.replace_index(wtxn, index_uid, uuid, copy)?;
+        let rtxn = self.env.read_txn()?;
+        let stats = self.index_mapper.stats_of(&rtxn, index_uid)?;
+        drop(rtxn);
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::IndexRestore {
+            snapshot: snapshot.clone(),
+            dump_uid: dump_uid.clone(),
+            indexed_documents: Some(stats.documents_database_stats.number_of_entries()),
+        });
+        Ok(vec![task])
+    }
+
+    /// Extracts the index from the snapshot and opens it as a copy.
+    fn extract_index_from_snapshot(
+        &self,
+        index_uid: &str,
+        snapshot: &str,
+    ) -> Result<(Uuid, Index)> {
+        let restore_error =
+            |error: String| Error::IndexRestore { index_uid: index_uid.to_string(), error };
+
+        let snapshot_path = self.scheduler.snapshots_path.join(snapshot);
+        if !snapshot_path.is_file() {
+            return Err(restore_error(format!("the snapshot `{snapshot}` doesn't exist")));
+        }
+        let extracted = tempfile::tempdir_in(&self.scheduler.snapshots_path)?;
+        compression::from_tar_gz(&snapshot_path, extracted.path())?;
+        let snapshot_version = fs::read_to_string(extracted.path().join(VERSION_FILE_NAME))?;
+        if snapshot_version != fs::read_to_string(&self.scheduler.version_file_path)? {
+            return Err(restore_error(format!(
+                "the snapshot `{snapshot}` was taken by Meilisearch v{snapshot_version}"
+            )));
+        }
+
+        let (indexes, _) = self.read_restore_point(&extracted.path().join("tasks"), None)?;
+        let Some((_, data_path)) = indexes.into_iter().find(|(name, _)| name == index_uid) else {
+            return Err(restore_error(format!("it doesn't exist in the snapshot `{snapshot}`")));
+        };
+        self.index_mapper.open_index_copy(&data_path)
+    }
+
+    /// Rebuilds the index from the settings and the documents of the dump in a new copy.
+    fn extract_index_from_dump(&self, index_uid: &str, dump_uid: &str) -> Result<(Uuid, Index)> {
+        let restore_error =
+            |error: String| Error::IndexRestore { index_uid: index_uid.to_string(), error };
+
+        let dump_path = self.scheduler.dumps_path.join(format!("{dump_uid}.dump"));
+        let archive = match File::open(dump_path) {
+            Ok(archive) => archive,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(restore_error(format!("the dump `{dump_uid}` doesn't exist")))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let dump =
+            DumpReader::open(BufReader::new(archive)).map_err(|e| restore_error(e.to_string()))?;
+        let dumped = dump
+            .indexes()?
+            .find(|index| index.as_ref().map_or(true, |index| index.metadata().uid == index_uid))
+            .transpose()?;
+        let Some(mut dumped) = dumped else {
+            return Err(restore_error(format!("it doesn't exist in the dump `{dump_uid}`")));
+        };
+
+        let metadata = dumped.metadata().clone();
+        let date = Some((metadata.created_at, metadata.updated_at));
+        let (uuid, copy) = self.index_mapper.create_index_copy(date)?;
+        if let Err(e) = self.index_dumped_index(&mut dumped, &copy, index_uid) {
+            if let Err(error) = self.index_mapper.discard_index_copy(uuid, copy) {
+                tracing::error!(
+                    "Could not delete the restored copy of the index `{index_uid}`: {error}"
+                );
+            }
+            return Err(e);
+        }
+        Ok((uuid, copy))
+    }
+}
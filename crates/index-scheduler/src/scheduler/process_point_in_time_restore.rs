@@ -0,0 +1,312 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use meilisearch_types::heed::types::Str;
+use meilisearch_types::heed::{self, Database, RoTxn};
+use meilisearch_types::milli::progress::{Progress, VariableNameStep};
+use meilisearch_types::milli::update::Settings as MilliSettings;
+use meilisearch_types::milli::Index;
+use meilisearch_types::tasks::{Details, Kind, KindWithContent, Status, Task};
+use meilisearch_types::{compression, VERSION_FILE_NAME};
+use roaring::RoaringBitmap;
+use time::Duration;
+use uuid::Uuid;
+
+use super::create_batch::{DocumentOperation, IndexOperation};
+use crate::index_mapper::db_name::INDEX_MAPPING;
+use crate::processing::PointInTimeRestoreProgress;
+use crate::queue::TaskQueue;
+use crate::utils::keep_ids_within_datetimes;
+use crate::uuid_codec::UuidCodec;
+use crate::{restore_points, Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Restores the indexes to their state at a past date.
+    ///
+    /// The indexes are copied from the latest restore point taken before the date, then the
+    /// tasks that succeeded on them between the restore point and the date are replayed on the
+    /// copies. The current indexes are only replaced once every copy is ready, and the indexes
+    /// that don't exist in the restore point are left untouched.
+    pub(super) fn process_point_in_time_restore(
+        &self,
+        progress: Progress,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(PointInTimeRestoreProgress::ExtractingTheRestorePoint);
+        let KindWithContent::PointInTimeRestore { index_uids, restore_at } = &task.kind else {
+            unreachable!();
+        };
+        let restore_at = *restore_at;
+
+        let restore_points = self.restore_points()?;
+        let Some(restore_point) = restore_points.into_iter().rev().find(|date| *date <= restore_at)
+        else {
+            return Err(Error::PointInTimeRestore(
+                "there is no restore point before the requested date".to_string(),
+            ));
+        };
+
+        let extracted = tempfile::tempdir_in(&self.scheduler.snapshots_path)?;
+        compression::from_tar_gz(
+            restore_points::path_of(&self.scheduler.snapshots_path, restore_point),
+            extracted.path(),
+        )?;
+        let snapshot_version = fs::read_to_string(extracted.path().join(VERSION_FILE_NAME))?;
+        if snapshot_version != fs::read_to_string(&self.scheduler.version_file_path)? {
+            return Err(Error::PointInTimeRestore(format!(
+                "the restore point was taken by Meilisearch v{snapshot_version}"
+            )));
+        }
+
+        let (indexes, first_unapplied) =
+            self.read_restore_point(&extracted.path().join("tasks"), index_uids.as_deref())?;
+
+        // the tasks processed between the restore point and the date, in the order they were
+        let rtxn = self.env.read_txn()?;
+        let mut replayed = self.queue.tasks.get_status(&rtxn, Status::Succeeded)?;
+        keep_ids_within_datetimes(
+            &rtxn,
+            &mut replayed,
+            self.queue.tasks.finished_at,
+            Some(restore_point - Duration::NANOSECOND),
+            Some(restore_at + Duration::NANOSECOND),
+        )?;
+
+        // the restore point doesn't tell which of the deleted tasks it already contains
+        let mut deletions = self.queue.tasks.get_kind(&rtxn, Kind::TaskDeletion)?
+            & self.queue.tasks.get_status(&rtxn, Status::Succeeded)?;
+        keep_ids_within_datetimes(
+            &rtxn,
+            &mut deletions,
+            self.queue.tasks.finished_at,
+            Some(restore_point - Duration::NANOSECOND),
+            None,
+        )?;
+        for deletion in self.queue.tasks.get_existing_tasks(&rtxn, deletions)? {
+            if let KindWithContent::TaskDeletion { tasks, .. } = &deletion.kind {
+                if tasks.max().is_some_and(|max| max >= first_unapplied) {
+                    return Err(Error::PointInTimeRestore(format!(
+                        "the tasks processed since the restore point were deleted by task `{}`",
+                        deletion.uid
+                    )));
+                }
+            }
+        }
+
+        // the indexes restored since the restore point don't follow the task log anymore
+        let restores = self.queue.tasks.get_kind(&rtxn, Kind::PointInTimeRestore)? & &replayed;
+        for restore in self.queue.tasks.get_existing_tasks(&rtxn, restores)? {
+            let restored = restore.indexes();
+            if restored.is_empty()
+                || indexes.iter().any(|(name, _)| restored.contains(&name.as_str()))
+            {
+                return Err(Error::PointInTimeRestore(format!(
+                    "the indexes were already restored by task `{}` since the restore point",
+                    restore.uid
+                )));
+            }
+        }
+
+        progress.update_progress(PointInTimeRestoreProgress::ReplayingTheTasks);
+        let mut copies = Vec::new();
+        let replayed_tasks =
+            match self.restore_copies(&rtxn, indexes, &replayed, &progress, &mut copies) {
+                Ok(replayed_tasks) => replayed_tasks,
+                Err(e) => {
+                    for (name, uuid, copy) in copies {
+                        if let Err(error) = self.index_mapper.discard_index_copy(uuid, copy) {
+                            tracing::error!(
+                                "Could not delete the restored copy of the index `{name}`: {error}"
+                            );
+                        }
+                    }
+                    return Err(e);
+                }
+            };
+        drop(rtxn);
+
+        progress.update_progress(PointInTimeRestoreProgress::ReplacingTheIndexes);
+        let mut restored_indexes = Vec::new();
+        for (name, uuid, copy) in copies {
+            let wtxn = self.env.write_txn()?;
+            self.index_mapper.replace_index(wtxn, &name, uuid, copy)?;
+            restored_indexes.push(name);
+        }
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::PointInTimeRestore {
+            restore_at,
+            restored_indexes: Some(restored_indexes),
+            replayed_tasks: Some(replayed_tasks),
+        });
+        Ok(vec![task])
+    }
+
+    /// Opens a copy of each index of the restore point and replays the tasks on it, and returns
+    /// the number of replayed tasks. The copies are pushed to `copies` as soon as they are opened,
+    /// so that they can be discarded if the restore fails.
+    fn restore_copies(
+        &self,
+        rtxn: &RoTxn,
+        indexes: Vec<(String, PathBuf)>,
+        replayed: &RoaringBitmap,
+        progress: &Progress,
+        copies: &mut Vec<(String, Uuid, Index)>,
+    ) -> Result<u64> {
+        let nb_indexes = indexes.len() as u32;
+        let mut replayed_tasks = 0;
+        for (count, (name, data_path)) in indexes.into_iter().enumerate() {
+            progress.update_progress(VariableNameStep::<PointInTimeRestoreProgress>::new(
+                name.clone(),
+                count as u32,
+                nb_indexes,
+            ));
+
+            let (uuid, copy) = self.index_mapper.open_index_copy(&data_path)?;
+            copies.push((name.clone(), uuid, copy.clone()));
+
+            let tasks = self.queue.tasks.index_tasks(rtxn, &name)? & replayed;
+            replayed_tasks += tasks.len();
+            for task in self.queue.tasks.get_existing_tasks(rtxn, tasks)? {
+                if self.scheduler.must_stop_processing.get() {
+                    return Err(Error::AbortedTask);
+                }
+                self.replay_task(&name, &copy, task)?;
+            }
+        }
+        Ok(replayed_tasks)
+    }
+
+    /// Returns the path of the LMDB file of the indexes to restore in the extracted restore point,
+    /// or any other extracted snapshot, all of them if `index_uids` is `None`, and the uid of the
+    /// first task it doesn't contain.
+    pub(super) fn read_restore_point(
+        &self,
+        snapshot_tasks_path: &Path,
+        index_uids: Option<&[String]>,
+    ) -> Result<(Vec<(String, PathBuf)>, u32)> {
+        let env = unsafe {
+            let env_options = heed::EnvOpenOptions::new();
+            let mut env_options = env_options.read_txn_without_tls();
+            env_options
+                .max_dbs(Self::nb_db())
+                .map_size(self.env.info().map_size)
+                .open(snapshot_tasks_path)
+        }?;
+        let mut wtxn = env.write_txn()?;
+        let queue = TaskQueue::new(&env, &mut wtxn)?;
+        let index_mapping: Database<Str, UuidCodec> =
+            env.create_database(&mut wtxn, Some(INDEX_MAPPING))?;
+        wtxn.commit()?;
+
+        let rtxn = env.read_txn()?;
+        // the tasks still enqueued when the restore point was taken may have been processed since
+        let first_unapplied = match queue.get_status(&rtxn, Status::Enqueued)?.min() {
+            Some(enqueued) => enqueued,
+            None => queue.next_task_id(&rtxn)?,
+        };
+
+        let index_uids = match index_uids {
+            Some(index_uids) => index_uids.to_vec(),
+            None => index_mapping
+                .iter(&rtxn)?
+                .map(|entry| entry.map(|(name, _)| name.to_string()))
+                .collect::<heed::Result<_>>()?,
+        };
+        let indexes_path = snapshot_tasks_path.with_file_name("indexes");
+        let mut indexes = Vec::new();
+        for name in index_uids {
+            let uuid: Uuid = index_mapping.get(&rtxn, &name)?.ok_or_else(|| {
+                Error::PointInTimeRestore(format!(
+                    "the index `{name}` doesn't exist in the restore point"
+                ))
+            })?;
+            indexes.push((name, indexes_path.join(uuid.to_string()).join("data.mdb")));
+        }
+        drop(rtxn);
+        env.prepare_for_closing().wait();
+
+        Ok((indexes, first_unapplied))
+    }
+
+    /// Applies a succeeded task to the restored copy of its index.
+    fn replay_task(&self, index_uid: &str, index: &Index, task: Task) -> Result<()> {
+        let uid = task.uid;
+        let operation = match task.kind.clone() {
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid,
+                primary_key,
+                method,
+                content_file,
+                ..
+            } => {
+                if !self.queue.file_store.get_update_path(content_file).exists() {
+                    return Err(Error::PointInTimeRestore(format!(
+                        "the documents of task `{uid}` are not kept anymore"
+                    )));
+                }
+                IndexOperation::DocumentOperation {
+                    index_uid,
+                    primary_key,
+                    operations: vec![DocumentOperation::new(method, content_file)],
+                    tasks: vec![task],
+                    window: None,
+                }
+            }
+            KindWithContent::DocumentEdition { index_uid, .. } => {
+                IndexOperation::DocumentEdition { index_uid, task }
+            }
+            KindWithContent::DocumentDeletion { index_uid, .. }
+            | KindWithContent::DocumentDeletionByFilter { index_uid, .. } => {
+                IndexOperation::DocumentDeletion { index_uid, tasks: vec![task] }
+            }
+            KindWithContent::DocumentClear { index_uid } => {
+                IndexOperation::DocumentClear { index_uid, tasks: vec![task] }
+            }
+            KindWithContent::SettingsUpdate { index_uid, new_settings, is_deletion, .. } => {
+                IndexOperation::Settings {
+                    index_uid,
+                    settings: vec![(is_deletion, *new_settings)],
+                    tasks: vec![task],
+                }
+            }
+            KindWithContent::IndexUpdate { primary_key, .. } => {
+                if let Some(primary_key) = primary_key {
+                    let mut wtxn = index.write_txn()?;
+                    let mut builder =
+                        MilliSettings::new(&mut wtxn, index, self.index_mapper.indexer_config());
+                    builder.set_primary_key(primary_key);
+                    let must_stop_processing = self.scheduler.must_stop_processing.clone();
+                    builder
+                        .execute(
+                            |indexing_step| tracing::debug!(update = ?indexing_step),
+                            || must_stop_processing.get(),
+                        )
+                        .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+                    wtxn.commit()?;
+                }
+                return Ok(());
+            }
+            // the indexes deleted, swapped or fetched from a remote URL since the restore
+            // point can't be rebuilt from the task log
+            kind => {
+                return Err(Error::PointInTimeRestore(format!(
+                    "task `{uid}` of type `{}` cannot be replayed on `{index_uid}`",
+                    kind.as_kind()
+                )))
+            }
+        };
+
+        let mut wtxn = index.write_txn()?;
+        let (tasks, _) =
+            self.apply_index_operation(&mut wtxn, index, operation, Progress::default())?;
+        if let Some(error) = tasks.into_iter().find_map(|task| task.error) {
+            return Err(Error::PointInTimeRestore(format!(
+                "task `{uid}` failed when replayed: {}",
+                error.message
+            )));
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+}
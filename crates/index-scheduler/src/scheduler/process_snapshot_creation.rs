@@ -4,11 +4,13 @@ use std::sync::atomic::Ordering;
 
 use meilisearch_types::heed::CompactionOption;
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
-use meilisearch_types::tasks::{Status, Task};
+use meilisearch_types::tasks::{Kind, Status, Task};
 use meilisearch_types::{compression, VERSION_FILE_NAME};
+use time::{Duration, OffsetDateTime};
 
 use crate::processing::{AtomicUpdateFileStep, SnapshotCreationProgress};
-use crate::{Error, IndexScheduler, Result};
+use crate::utils::keep_ids_within_datetimes;
+use crate::{restore_points, Error, IndexScheduler, Result};
 
 impl IndexScheduler {
     pub(super) fn process_snapshot(
@@ -17,8 +19,12 @@ impl IndexScheduler {
         mut tasks: Vec<Task>,
     ) -> Result<Vec<Task>> {
         progress.update_progress(SnapshotCreationProgress::StartTheSnapshotCreation);
+        // the tasks finished after this date are not part of the snapshot
+        let snapshot_date = OffsetDateTime::now_utc();
 
-        fs::create_dir_all(&self.scheduler.snapshots_path)?;
+        if self.scheduler.snapshot_s3.is_none() {
+            fs::create_dir_all(&self.scheduler.snapshots_path)?;
+        }
         let temp_snapshot_dir = tempfile::tempdir()?;
 
         // 1. Snapshot the version file.
@@ -100,24 +106,47 @@ impl IndexScheduler {
         base_path.pop();
         let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
 
-        // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
-        let snapshot_path = self.scheduler.snapshots_path.join(format!("{}.snapshot", db_name));
-        let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.scheduler.snapshots_path)?;
-        compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
-        let file = temp_snapshot_file.persist(snapshot_path)?;
-
-        // 5.3 Change the permission to make the snapshot readonly
-        let mut permissions = file.metadata()?.permissions();
-        permissions.set_readonly(true);
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            #[allow(clippy::non_octal_unix_permissions)]
-            //                     rwxrwxrwx
-            permissions.set_mode(0b100100100);
-        }
+        // 5.2 Stream the tarball straight to the object storage, if any
+        if let Some(s3) = &self.scheduler.snapshot_s3 {
+            s3.upload_tar_gz(temp_snapshot_dir.path(), &format!("{}.snapshot", db_name))?;
+        } else {
+            // 5.3 Tarball the content of the snapshot in a tempfile with a .snapshot extension
+            let snapshot_path = self.scheduler.snapshots_path.join(format!("{}.snapshot", db_name));
+            let temp_snapshot_file =
+                tempfile::NamedTempFile::new_in(&self.scheduler.snapshots_path)?;
+            compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
+            let file = temp_snapshot_file.persist(&snapshot_path)?;
+
+            // 5.4 Change the permission to make the snapshot readonly
+            let mut permissions = file.metadata()?.permissions();
+            permissions.set_readonly(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                #[allow(clippy::non_octal_unix_permissions)]
+                //                     rwxrwxrwx
+                permissions.set_mode(0b100100100);
+            }
 
-        file.set_permissions(permissions)?;
+            file.set_permissions(permissions)?;
+
+            // 5.5 Keep the snapshot as a restore point
+            if let Some(keep) = self.scheduler.restore_points {
+                let oldest = restore_points::store(
+                    &self.scheduler.snapshots_path,
+                    &snapshot_path,
+                    snapshot_date,
+                    keep,
+                )?;
+                // the change stream and the task log keep the content files until their task
+                // is deleted or the log is trimmed
+                if let Some((previous_oldest, oldest)) =
+                    oldest.filter(|_| !self.scheduler.change_stream && !self.scheduler.task_log)
+                {
+                    self.delete_unreplayable_content_files(previous_oldest, oldest)?;
+                }
+            }
+        }
 
         for task in &mut tasks {
             task.status = Status::Succeeded;
@@ -125,4 +154,33 @@ impl IndexScheduler {
 
         Ok(tasks)
     }
+
+    /// Deletes the content files of the document additions that succeeded before the oldest
+    /// restore point, they can't be replayed anymore.
+    ///
+    /// Only the tasks finished after the previous oldest restore point can still have one.
+    fn delete_unreplayable_content_files(
+        &self,
+        previous_oldest: Option<OffsetDateTime>,
+        oldest: OffsetDateTime,
+    ) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        let mut tasks = self.queue.tasks.get_kind(&rtxn, Kind::DocumentAdditionOrUpdate)?
+            & self.queue.tasks.get_status(&rtxn, Status::Succeeded)?;
+        keep_ids_within_datetimes(
+            &rtxn,
+            &mut tasks,
+            self.queue.tasks.finished_at,
+            previous_oldest.map(|date| date - Duration::NANOSECOND),
+            Some(oldest),
+        )?;
+        for task in self.queue.tasks.get_existing_tasks(&rtxn, tasks)? {
+            if let Some(content_uuid) = task.content_uuid() {
+                if self.queue.file_store.get_update_path(content_uuid).exists() {
+                    self.queue.delete_persisted_task_data(&task)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,103 @@
+//! Measures the resources used to process a batch, so that the slow operations can be pinpointed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use meilisearch_types::task_view::DetailsView;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::utils::ProcessingBatch;
+
+/// How often the memory of the process is sampled while a batch is processing.
+const MEMORY_SAMPLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Samples the resident memory of the whole process in the background, keeping its peak.
+pub(crate) struct MemorySampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Option<u64>>>,
+}
+
+impl MemorySampler {
+    /// Starts sampling the memory, doesn't do anything if the memory of the
+    /// process can't be read on this platform.
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let Ok(pid) = sysinfo::get_current_pid() else { return Self { stop, handle: None } };
+
+        let must_stop = stop.clone();
+        let handle = std::thread::Builder::new()
+            .name(String::from("memory-sampler"))
+            .spawn(move || {
+                let mut system = System::new();
+                let mut peak = None;
+                loop {
+                    system.refresh_processes_specifics(
+                        ProcessesToUpdate::Some(&[pid]),
+                        true,
+                        ProcessRefreshKind::nothing().with_memory(),
+                    );
+                    if let Some(process) = system.process(pid) {
+                        peak = peak.max(Some(process.memory()));
+                    }
+                    if must_stop.load(Ordering::Relaxed) {
+                        break peak;
+                    }
+                    std::thread::park_timeout(MEMORY_SAMPLING_INTERVAL);
+                }
+            })
+            .ok();
+
+        Self { stop, handle }
+    }
+
+    /// Stops sampling and returns the highest resident memory observed, in bytes.
+    pub fn stop(mut self) -> Option<u64> {
+        let handle = self.handle.take()?;
+        self.stop.store(true, Ordering::Relaxed);
+        handle.thread().unpark();
+        // losing the measure must never fail the batch
+        handle.join().ok().flatten()
+    }
+}
+
+impl Drop for MemorySampler {
+    fn drop(&mut self) {
+        if let Some(handle) = &self.handle {
+            self.stop.store(true, Ordering::Relaxed);
+            handle.thread().unpark();
+        }
+    }
+}
+
+/// Summarizes the resources used by a finished batch.
+///
+/// Must be called once all the tasks have been registered in the batch, so that
+/// its details account for all the documents processed.
+pub(crate) fn resource_usage(
+    batch: &ProcessingBatch,
+    peak_memory: Option<u64>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut usage = serde_json::Map::new();
+    if let Some(peak_memory) = peak_memory {
+        usage.insert("peak_memory_bytes".into(), peak_memory.into());
+    }
+
+    let DetailsView { indexed_documents, deleted_documents, edited_documents, .. } = &batch.details;
+    let documents = [indexed_documents, deleted_documents, edited_documents]
+        .into_iter()
+        .filter_map(|count| count.flatten())
+        .sum::<u64>();
+    let elapsed = batch.finished_at.map(|finished_at| finished_at - batch.started_at);
+    if let Some(elapsed) = elapsed.filter(|elapsed| elapsed.is_positive()) {
+        if documents > 0 {
+            let documents_per_second = documents as f64 / elapsed.as_seconds_f64();
+            // two decimals are precise enough even for the slowest batches
+            let documents_per_second = (documents_per_second * 100.0).round() / 100.0;
+            usage.insert("documents_per_second".into(), documents_per_second.into());
+        }
+    }
+
+    usage
+}
@@ -7,10 +7,10 @@ use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexDocumentsMethod::*;
 use meilisearch_types::milli::{self};
 use meilisearch_types::settings::SettingEmbeddingSettings;
-use meilisearch_types::tasks::{IndexSwap, KindWithContent};
+use meilisearch_types::tasks::{IndexSwap, KindWithContent, Status};
 use roaring::RoaringBitmap;
 
-use crate::insta_snapshot::snapshot_index_scheduler;
+use crate::insta_snapshot::{snapshot_bitmap, snapshot_index_scheduler};
 use crate::test_utils::Breakpoint::*;
 use crate::test_utils::{
     index_creation_task, read_json, replace_document_import_task, sample_documents,
@@ -375,6 +375,7 @@ fn swap_indexes() {
                     IndexSwap { indexes: ("a".to_owned(), "b".to_owned()) },
                     IndexSwap { indexes: ("c".to_owned(), "d".to_owned()) },
                 ],
+                execute_at: None,
             },
             None,
             false,
@@ -385,6 +386,7 @@ fn swap_indexes() {
         .register(
             KindWithContent::IndexSwap {
                 swaps: vec![IndexSwap { indexes: ("a".to_owned(), "c".to_owned()) }],
+                execute_at: None,
             },
             None,
             false,
@@ -398,7 +400,9 @@ fn swap_indexes() {
     handle.advance_one_successful_batch();
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "second_swap_processed");
 
-    index_scheduler.register(KindWithContent::IndexSwap { swaps: vec![] }, None, false).unwrap();
+    index_scheduler
+        .register(KindWithContent::IndexSwap { swaps: vec![], execute_at: None }, None, false)
+        .unwrap();
     handle.advance_one_successful_batch();
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "third_empty_swap_processed");
 }
@@ -431,6 +435,7 @@ fn swap_indexes_errors() {
                     IndexSwap { indexes: ("a".to_owned(), "b".to_owned()) },
                     IndexSwap { indexes: ("b".to_owned(), "a".to_owned()) },
                 ],
+                execute_at: None,
             },
             None,
             false,
@@ -450,6 +455,7 @@ fn swap_indexes_errors() {
                     IndexSwap { indexes: ("c".to_owned(), "e".to_owned()) },
                     IndexSwap { indexes: ("d".to_owned(), "f".to_owned()) },
                 ],
+                execute_at: None,
             },
             None,
             false,
@@ -588,6 +594,30 @@ fn cancel_processing_task() {
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "cancel_processed");
 }
 
+#[test]
+fn progress_of_processing_task() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let (file0, documents_count0) = sample_documents(&index_scheduler, 0, 0);
+    file0.persist().unwrap();
+
+    let _ = index_scheduler
+        .register(replace_document_import_task("catto", None, 0, documents_count0), None, false)
+        .unwrap();
+    assert!(index_scheduler.task_progress(0).is_none());
+
+    handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+    // nothing has been indexed yet, thus the remaining time is unknown
+    let progress = index_scheduler.task_progress(0).unwrap();
+    assert_eq!(progress.percentage, 0.0);
+    assert!(progress.estimated_remaining_time.is_none());
+    // an unknown task has no progress
+    assert!(index_scheduler.task_progress(1).is_none());
+
+    handle.advance_till([ProcessBatchSucceeded, AfterProcessing]);
+    assert!(index_scheduler.task_progress(0).is_none());
+}
+
 #[test]
 fn cancel_mix_of_tasks() {
     let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -631,6 +661,54 @@ fn cancel_mix_of_tasks() {
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "cancel_processed");
 }
 
+#[test]
+fn collapse_consecutive_settings_updates() {
+    use meilisearch_types::settings::{Settings, Unchecked};
+    use milli::update::Setting;
+
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_number_of_batched_tasks = 2;
+        None
+    });
+
+    let updates = [
+        Settings { search_cutoff_ms: Setting::Set(100), ..Default::default() },
+        Settings { distinct_attribute: Setting::Set(S("id")), ..Default::default() },
+        Settings { search_cutoff_ms: Setting::Set(200), ..Default::default() },
+    ];
+    for new_settings in updates {
+        let new_settings: Box<Settings<Unchecked>> = Box::new(new_settings);
+        index_scheduler
+            .register(
+                KindWithContent::SettingsUpdate {
+                    index_uid: S("doggos"),
+                    new_settings,
+                    is_deletion: false,
+                    allow_index_creation: true,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        index_scheduler.assert_internally_consistent();
+    }
+
+    // the settings updates are batched together regardless of the limit of batched tasks
+    handle.advance_one_successful_batch();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    for task_id in 0..3 {
+        let task = index_scheduler.queue.tasks.get_task(&rtxn, task_id).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        assert_eq!(task.batch_uid, Some(0));
+    }
+
+    // only the final state of the settings is applied
+    let index = index_scheduler.index("doggos").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    snapshot!(format!("{:?}", index.search_cutoff(&rtxn).unwrap()), @"Some(200)");
+    snapshot!(format!("{:?}", index.distinct_field(&rtxn).unwrap()), @r###"Some("id")"###);
+}
+
 #[test]
 fn test_settings_update() {
     use meilisearch_types::settings::{Settings, Unchecked};
@@ -732,10 +810,13 @@ fn basic_get_stats() {
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "dumpImport": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
+        "indexRestore": 0,
         "indexSwap": 0,
         "indexUpdate": 0,
+        "pointInTimeRestore": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
         "taskCancelation": 0,
@@ -765,10 +846,13 @@ fn basic_get_stats() {
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "dumpImport": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
+        "indexRestore": 0,
         "indexSwap": 0,
         "indexUpdate": 0,
+        "pointInTimeRestore": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
         "taskCancelation": 0,
@@ -805,10 +889,13 @@ fn basic_get_stats() {
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "dumpImport": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
+        "indexRestore": 0,
         "indexSwap": 0,
         "indexUpdate": 0,
+        "pointInTimeRestore": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
         "taskCancelation": 0,
@@ -846,10 +933,13 @@ fn basic_get_stats() {
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "dumpImport": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
+        "indexRestore": 0,
         "indexSwap": 0,
         "indexUpdate": 0,
+        "pointInTimeRestore": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
         "taskCancelation": 0,
@@ -864,7 +954,12 @@ fn basic_get_stats() {
 fn cancel_processing_dump() {
     let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
-    let dump_creation = KindWithContent::DumpCreation { keys: Vec::new(), instance_uid: None };
+    let dump_creation = KindWithContent::DumpCreation {
+        keys: Vec::new(),
+        instance_uid: None,
+        index_uids: None,
+        skip_tasks: false,
+    };
     let dump_cancellation = KindWithContent::TaskCancelation {
         query: "cancel dump".to_owned(),
         tasks: RoaringBitmap::from_iter([0]),
@@ -929,3 +1024,301 @@ fn create_and_list_index() {
     ]
     "###);
 }
+
+#[test]
+fn task_dependencies() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let err = index_scheduler
+        .register_with_metadata(
+            KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None },
+            None,
+            Some(12),
+            BTreeMap::new(),
+            false,
+        )
+        .unwrap_err();
+    snapshot!(err, @"Task `12` cannot be depended on because it does not exist.");
+
+    // 0: succeeds, 1: waits for it
+    index_scheduler
+        .register(
+            KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+            None,
+            false,
+        )
+        .unwrap();
+    index_scheduler
+        .register_with_metadata(
+            KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None },
+            None,
+            Some(0),
+            BTreeMap::new(),
+            false,
+        )
+        .unwrap();
+    // 2: fails, 3: waits for it and 4 must be processed after 3 since they share an index
+    index_scheduler
+        .register(
+            KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+            None,
+            false,
+        )
+        .unwrap();
+    index_scheduler
+        .register_with_metadata(
+            KindWithContent::DocumentClear { index_uid: S("cattos") },
+            None,
+            Some(2),
+            BTreeMap::new(),
+            false,
+        )
+        .unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("cattos") }, None, false)
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    handle.advance_one_successful_batch();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+    handle.advance_one_failed_batch();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 1).unwrap().unwrap();
+    snapshot!(format!("{} {:?}", task.status, task.batch_uid), @"succeeded Some(1)");
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 3).unwrap().unwrap();
+    snapshot!(task.status, @"failed");
+    snapshot!(task.error.unwrap().message, @"Task `2` this task depends on did not succeed.");
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 4).unwrap().unwrap();
+    snapshot!(task.status, @"enqueued");
+    drop(rtxn);
+
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 4).unwrap().unwrap();
+    snapshot!(task.status, @"succeeded");
+}
+
+#[test]
+fn concurrent_batches_of_independent_indexes() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 3;
+        None
+    });
+
+    // 0, 2 and 3 must wait for the batch of doggos, and 4 must wait for the swap 3
+    index_scheduler.register(index_creation_task("doggos", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("cattos", "id"), None, false).unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("doggos") }, None, false)
+        .unwrap();
+    index_scheduler
+        .register(
+            KindWithContent::IndexSwap {
+                swaps: vec![IndexSwap { indexes: (S("doggos"), S("cattos")) }],
+                execute_at: None,
+            },
+            None,
+            false,
+        )
+        .unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("cattos") }, None, false)
+        .unwrap();
+    index_scheduler.register(index_creation_task("girafos", "id"), None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let next_batch = || {
+        let processing = index_scheduler.processing_tasks.read().unwrap().clone();
+        let (batch, processing_batch) =
+            index_scheduler.create_next_batch(&rtxn, &processing).unwrap()?;
+        let description = format!(
+            "{} {:?} {}",
+            processing_batch.uid,
+            batch.index_uid(),
+            snapshot_bitmap(&batch.ids())
+        );
+        index_scheduler
+            .processing_tasks
+            .write()
+            .unwrap()
+            .start_processing(processing_batch, batch.ids());
+        Some(description)
+    };
+
+    snapshot!(next_batch().unwrap(), @r#"0 Some("doggos") [0,]"#);
+    snapshot!(next_batch().unwrap(), @r#"1 Some("cattos") [1,]"#);
+    snapshot!(next_batch().unwrap(), @r#"2 Some("girafos") [5,]"#);
+    assert!(next_batch().is_none());
+
+    let processing = index_scheduler.processing_tasks.read().unwrap().clone();
+    snapshot!(snapshot_bitmap(&processing.processing), @"[0,1,5,]");
+    snapshot!(snapshot_bitmap(&processing.batch_uids()), @"[0,1,2,]");
+}
+
+#[test]
+fn fast_lane_of_small_tasks() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.fast_lane_max_documents = Some(10);
+        None
+    });
+
+    // 2 is too large for the fast lane and 3 must wait behind it, 4 is too large as well
+    let addition = |index, uuid, documents| {
+        let (_, file) = index_scheduler.queue.create_update_file_with_uuid(uuid).unwrap();
+        file.persist().unwrap();
+        replace_document_import_task(index, Some("id"), uuid, documents)
+    };
+    index_scheduler.register(addition("doggos", 0, 100), None, false).unwrap();
+    index_scheduler.register(addition("cattos", 1, 5), None, false).unwrap();
+    index_scheduler.register(addition("cattos", 2, 100), None, false).unwrap();
+    index_scheduler.register(addition("cattos", 3, 5), None, false).unwrap();
+    index_scheduler.register(addition("girafos", 4, 100), None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let next_batch = || {
+        let processing = index_scheduler.processing_tasks.read().unwrap().clone();
+        let (batch, processing_batch) =
+            index_scheduler.create_next_batch(&rtxn, &processing).unwrap()?;
+        let description = format!("{:?} {}", batch.index_uid(), snapshot_bitmap(&batch.ids()));
+        index_scheduler
+            .processing_tasks
+            .write()
+            .unwrap()
+            .start_processing(processing_batch, batch.ids());
+        Some(description)
+    };
+
+    snapshot!(next_batch().unwrap(), @r#"Some("doggos") [0,]"#);
+    snapshot!(next_batch().unwrap(), @r#"Some("cattos") [1,]"#);
+    assert!(next_batch().is_none());
+}
+
+#[test]
+fn autobatching_limits() {
+    use meilisearch_types::autobatching::AutobatchingLimits;
+
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 3;
+        config.max_batched_documents = Some(150);
+        config.index_autobatching.insert(
+            S("cattos"),
+            AutobatchingLimits { max_wait_ms: Some(60 * 60 * 1000), ..Default::default() },
+        );
+        config.index_autobatching.insert(
+            S("girafos"),
+            AutobatchingLimits { max_documents: Some(10), ..Default::default() },
+        );
+        None
+    });
+
+    // 1 doesn't fit in the batch of 0, cattos waits for more tasks, 4 doesn't fit with 3
+    let addition = |index, uuid, documents| {
+        let (_, file) = index_scheduler.queue.create_update_file_with_uuid(uuid).unwrap();
+        file.persist().unwrap();
+        replace_document_import_task(index, Some("id"), uuid, documents)
+    };
+    index_scheduler.register(addition("doggos", 0, 100), None, false).unwrap();
+    index_scheduler.register(addition("doggos", 1, 100), None, false).unwrap();
+    index_scheduler.register(addition("cattos", 2, 5), None, false).unwrap();
+    index_scheduler.register(addition("girafos", 3, 5), None, false).unwrap();
+    index_scheduler.register(addition("girafos", 4, 6), None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let next_batch = || {
+        let processing = index_scheduler.processing_tasks.read().unwrap().clone();
+        let (batch, processing_batch) =
+            index_scheduler.create_next_batch(&rtxn, &processing).unwrap()?;
+        let description = format!("{:?} {}", batch.index_uid(), snapshot_bitmap(&batch.ids()));
+        index_scheduler
+            .processing_tasks
+            .write()
+            .unwrap()
+            .start_processing(processing_batch, batch.ids());
+        Some(description)
+    };
+
+    snapshot!(next_batch().unwrap(), @r#"Some("doggos") [0,]"#);
+    snapshot!(next_batch().unwrap(), @r#"Some("girafos") [3,]"#);
+    assert!(next_batch().is_none());
+
+    // the run loop is woken up once cattos stops waiting
+    let timeout = index_scheduler.time_until_next_autobatch().unwrap().unwrap();
+    assert!(timeout > std::time::Duration::from_secs(59 * 60));
+    assert!(timeout <= std::time::Duration::from_secs(60 * 60));
+}
+
+#[test]
+fn scheduled_index_swap() {
+    use time::{Duration, OffsetDateTime};
+
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 3;
+        None
+    });
+
+    // 0 and 1 must wait for the date of the swap 0, 3 is due
+    let now = OffsetDateTime::now_utc();
+    let swap = |lhs: &str, rhs: &str, execute_at| KindWithContent::IndexSwap {
+        swaps: vec![IndexSwap { indexes: (S(lhs), S(rhs)) }],
+        execute_at: Some(execute_at),
+    };
+    index_scheduler.register(swap("doggos", "cattos", now + Duration::HOUR), None, false).unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("doggos") }, None, false)
+        .unwrap();
+    index_scheduler.register(index_creation_task("girafos", "id"), None, false).unwrap();
+    index_scheduler.register(swap("pandas", "koalas", now - Duration::HOUR), None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let next_batch = || {
+        let processing = index_scheduler.processing_tasks.read().unwrap().clone();
+        let (batch, processing_batch) =
+            index_scheduler.create_next_batch(&rtxn, &processing).unwrap()?;
+        let description = format!("{:?} {}", batch.index_uid(), snapshot_bitmap(&batch.ids()));
+        index_scheduler
+            .processing_tasks
+            .write()
+            .unwrap()
+            .start_processing(processing_batch, batch.ids());
+        Some(description)
+    };
+
+    snapshot!(next_batch().unwrap(), @r#"Some("girafos") [2,]"#);
+    snapshot!(next_batch().unwrap(), @"None [3,]");
+    assert!(next_batch().is_none());
+
+    // the run loop is woken up once the swap 0 is due
+    let timeout = index_scheduler.time_until_next_scheduled_task().unwrap().unwrap();
+    assert!(timeout > std::time::Duration::from_secs(59 * 60));
+    assert!(timeout <= std::time::Duration::from_secs(60 * 60));
+}
+
+#[test]
+fn publish_task_events() {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(10);
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.task_events = Some(sender.clone());
+        None
+    });
+
+    index_scheduler.register(index_creation_task("doggos", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("doggos", "id"), None, true).unwrap();
+    handle.advance_one_successful_batch();
+
+    let events: Vec<_> = receiver
+        .try_iter()
+        .map(|view| format!("{} {:?} {:?}", view.uid, view.status, view.batch_uid))
+        .collect();
+    snapshot!(format!("{events:#?}"), @r###"
+    [
+        "0 Enqueued None",
+        "0 Processing Some(0)",
+        "0 Succeeded Some(0)",
+    ]
+    "###);
+}
@@ -1164,3 +1164,153 @@ fn test_document_addition_with_set_and_null_primary_key_inference_works() {
         .collect::<Vec<_>>();
     snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
 }
+
+#[test]
+fn document_addition_records_resource_usage() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let content = r#"[
+            { "id": 1, "doggo": "jean bob" },
+            { "id": 2, "catto": "jorts" },
+            { "id": 3, "doggo": "bork" }
+        ]"#;
+
+    let (uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(0).unwrap();
+    let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+    file.persist().unwrap();
+    index_scheduler
+        .register(
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            },
+            None,
+            false,
+        )
+        .unwrap();
+    handle.advance_one_successful_batch();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let batch = index_scheduler.queue.batches.get_batch(&rtxn, 0).unwrap().unwrap();
+    let resource_usage = batch.stats.resource_usage.unwrap();
+    let documents_per_second = resource_usage["documents_per_second"].as_f64().unwrap();
+    assert!(documents_per_second > 0.0, "{resource_usage:?}");
+    if let Some(peak_memory) = resource_usage.get("peak_memory_bytes") {
+        assert!(peak_memory.as_u64().unwrap() > 0, "{resource_usage:?}");
+    }
+}
+
+#[test]
+fn document_addition_stops_at_checkpoint_on_shutdown() {
+    use std::sync::atomic::Ordering;
+
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.checkpoint_documents = Some(1);
+        None
+    });
+
+    for id in 0..3 {
+        let content = format!(r#"{{ "id": {id}, "doggo": "bob" }}"#);
+        let (uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(id).unwrap();
+        let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method: ReplaceDocuments,
+                    content_file: uuid,
+                    documents_count,
+                    allow_index_creation: true,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+    }
+
+    // The instance starts shutting down while the three additions are processing in a single batch
+    handle.advance_till([Start, BatchCreated]);
+    index_scheduler.scheduler.shutting_down.store(true, Ordering::Relaxed);
+    handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
+
+    let statuses = |index_scheduler: &IndexScheduler| {
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..3).unwrap();
+        let index = index_scheduler.index("doggos").unwrap();
+        let index_rtxn = index.read_txn().unwrap();
+        let statuses: Vec<_> = tasks.iter().map(|task| task.status).collect();
+        format!("{statuses:?} {} documents", index.number_of_documents(&index_rtxn).unwrap())
+    };
+    snapshot!(statuses(&index_scheduler), @"[Succeeded, Enqueued, Enqueued] 1 documents");
+
+    // The remaining tasks are processed once the instance is up again
+    index_scheduler.scheduler.shutting_down.store(false, Ordering::Relaxed);
+    handle.advance_one_successful_batch();
+    snapshot!(statuses(&index_scheduler), @"[Succeeded, Succeeded, Succeeded] 3 documents");
+}
+
+#[test]
+fn large_document_addition_resumes_from_its_checkpoint() {
+    use std::sync::atomic::Ordering;
+
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.checkpoint_documents = Some(2);
+        None
+    });
+
+    let content = r#"[
+        { "id": 0, "doggo": "bob" },
+        { "id": 1, "doggo": "kefir" },
+        { "id": 2, "doggo": "intel" },
+        { "id": 3, "doggo": "echo" },
+        { "id": 4, "doggo": "max" }
+    ]"#;
+    let (uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(0).unwrap();
+    let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+    file.persist().unwrap();
+    index_scheduler
+        .register(
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            },
+            None,
+            false,
+        )
+        .unwrap();
+
+    // The instance starts shutting down while the single addition is processing
+    handle.advance_till([Start, BatchCreated]);
+    index_scheduler.scheduler.shutting_down.store(true, Ordering::Relaxed);
+    handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
+
+    let state = |index_scheduler: &IndexScheduler| {
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let task = index_scheduler.queue.tasks.get_task(&rtxn, 0).unwrap().unwrap();
+        let index = index_scheduler.index("doggos").unwrap();
+        let index_rtxn = index.read_txn().unwrap();
+        let checkpoint = index.document_operation_checkpoint(&index_rtxn).unwrap();
+        format!(
+            "{:?} {:?} {} documents {checkpoint:?}",
+            task.status,
+            task.details,
+            index.number_of_documents(&index_rtxn).unwrap()
+        )
+    };
+    snapshot!(state(&index_scheduler), @"Enqueued Some(DocumentAdditionOrUpdate { received_documents: 5, indexed_documents: None }) 2 documents Some(DocumentOperationCheckpoint { task_uid: 0, processed_documents: 2, indexed_documents: 2 })");
+
+    // The addition resumes after its indexed documents once the instance is up again
+    index_scheduler.scheduler.shutting_down.store(false, Ordering::Relaxed);
+    handle.advance_one_successful_batch();
+    snapshot!(state(&index_scheduler), @"Succeeded Some(DocumentAdditionOrUpdate { received_documents: 5, indexed_documents: Some(5) }) 5 documents None");
+}
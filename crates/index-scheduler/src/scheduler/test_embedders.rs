@@ -423,6 +423,7 @@ fn import_vectors_first_and_embedder_later() {
                     max_bytes: Some(
                         400,
                     ),
+                    chunk_bytes: None,
                 },
                 quantized: None,
             },
@@ -620,6 +621,7 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunk_bytes: None,
                     },
                     quantized: None,
                 },
@@ -664,6 +666,7 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunk_bytes: None,
                     },
                     quantized: None,
                 },
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::sources::{IndexSource, RefreshMode};
+use meilisearch_types::tasks::{KindWithContent, Status};
+use time::OffsetDateTime;
+
+use crate::{IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Return the remote source the documents of the index are refreshed from, if any.
+    pub fn index_source(&self, index_uid: &str) -> Result<Option<IndexSource>> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.source_of(&rtxn, index_uid)
+    }
+
+    /// Register the remote source of an index, replacing the previous one.
+    ///
+    /// The documents are downloaded and their first refresh is enqueued right away, the next ones
+    /// every `refresh_interval` seconds. The source is not registered if the download fails.
+    pub fn put_index_source(
+        &self,
+        index_uid: &str,
+        mut source: IndexSource,
+    ) -> Result<IndexSource> {
+        self.features().check_writes_allowed()?;
+        // Error early rather than enqueuing a refresh that would fail.
+        if !self.index_exists(index_uid)? {
+            return Err(crate::Error::IndexNotFound(index_uid.to_string()));
+        }
+        self.refresh_index_source(index_uid, &mut source)?;
+        source.next_refresh_at =
+            next_refresh_at(OffsetDateTime::now_utc(), source.refresh_interval);
+
+        let mut wtxn = self.env.write_txn()?;
+        self.index_mapper.store_source_of(&mut wtxn, index_uid, &source)?;
+        wtxn.commit()?;
+        // The refreshing thread must take the new interval into account.
+        self.scheduler.sources_wake_up.signal();
+
+        Ok(source)
+    }
+
+    /// Register the source of an index imported from a dump as is, without downloading its documents.
+    pub fn import_index_source(&self, index_uid: &str, source: IndexSource) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.index_mapper.store_source_of(&mut wtxn, index_uid, &source)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Stop refreshing the documents of the index, returns `false` if it had no source.
+    pub fn delete_index_source(&self, index_uid: &str) -> Result<bool> {
+        self.features().check_writes_allowed()?;
+        let mut wtxn = self.env.write_txn()?;
+        let deleted = self.index_mapper.delete_source_of(&mut wtxn, index_uid)?;
+        wtxn.commit()?;
+        Ok(deleted)
+    }
+
+    /// Enqueue the refresh of all the sources that are due.
+    ///
+    /// A source whose previous refresh is not finished yet or that fails to be downloaded is
+    /// postponed to its next interval, so a slow source cannot fill the task queue.
+    pub(crate) fn refresh_due_index_sources(&self) -> Result<()> {
+        // The refreshes would be refused, they are enqueued once the writes are accepted again.
+        if self.read_only_replica
+            || self.scheduler.is_stopping()
+            || self.features().check_writes_allowed().is_err()
+        {
+            return Ok(());
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let rtxn = self.env.read_txn()?;
+        let due: Vec<_> = self
+            .index_mapper
+            .sources(&rtxn)?
+            .into_iter()
+            .filter(|(_, source)| source.next_refresh_at <= now)
+            .collect();
+        let processing = self.processing_tasks.read().unwrap().processing.clone();
+
+        let mut pending = Vec::with_capacity(due.len());
+        for (index_uid, source) in due {
+            let unfinished = match source.last_refresh_task_uid {
+                Some(task_id) if processing.contains(task_id) => true,
+                Some(task_id) => self
+                    .queue
+                    .tasks
+                    .get_task(&rtxn, task_id)?
+                    .is_some_and(|task| task.status == Status::Enqueued),
+                None => false,
+            };
+            pending.push((index_uid, source, unfinished));
+        }
+        drop(rtxn);
+
+        for (index_uid, mut source, unfinished) in pending {
+            if unfinished {
+                tracing::warn!(
+                    "Skipping the refresh of the index `{index_uid}`, the previous one is not finished"
+                );
+            } else if let Err(e) = self.refresh_index_source(&index_uid, &mut source) {
+                tracing::error!("While refreshing the source of the index `{index_uid}`: {e}");
+            }
+            source.next_refresh_at =
+                next_refresh_at(OffsetDateTime::now_utc(), source.refresh_interval);
+
+            // The source may have been deleted or replaced while its documents were downloaded.
+            let mut wtxn = self.env.write_txn()?;
+            let current = self.index_mapper.source_of(&wtxn, &index_uid)?;
+            if current.is_some_and(|current| current.url == source.url) {
+                self.index_mapper.store_source_of(&mut wtxn, &index_uid, &source)?;
+            }
+            wtxn.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Return how long the run loop can sleep before a source must be refreshed.
+    pub(crate) fn time_until_next_source_refresh(&self) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let next =
+            self.index_mapper.sources(&rtxn)?.into_iter().map(|(_, s)| s.next_refresh_at).min();
+        let now = OffsetDateTime::now_utc();
+        Ok(next.map(|next| (next - now).try_into().unwrap_or(Duration::ZERO)))
+    }
+
+    /// Download the documents of the source and enqueue the task importing them.
+    fn refresh_index_source(&self, index_uid: &str, source: &mut IndexSource) -> Result<()> {
+        let (content_file, documents_count) =
+            self.fetch_remote_documents(&source.url, source.csv_delimiter)?;
+        let kind = KindWithContent::DocumentImportUrl {
+            index_uid: index_uid.to_string(),
+            primary_key: source.primary_key.clone(),
+            method: IndexDocumentsMethod::ReplaceDocuments,
+            url: source.url.clone(),
+            csv_delimiter: source.csv_delimiter,
+            content_file,
+            documents_count,
+            allow_index_creation: false,
+            replace_all_documents: source.mode == RefreshMode::Full,
+        };
+        let task = match self.register(kind, None, false) {
+            Ok(task) => task,
+            Err(e) => {
+                self.queue.delete_update_file(content_file)?;
+                return Err(e);
+            }
+        };
+        source.last_refresh_task_uid = Some(task.uid);
+
+        Ok(())
+    }
+}
+
+fn next_refresh_at(now: OffsetDateTime, refresh_interval: u64) -> OffsetDateTime {
+    let interval = time::Duration::seconds(refresh_interval.try_into().unwrap_or(i64::MAX));
+    now.checked_add(interval)
+        .unwrap_or(OffsetDateTime::new_utc(time::Date::MAX, time::Time::MIDNIGHT))
+}
@@ -0,0 +1,342 @@
+//! The task log is a compact copy of the succeeded tasks that changed the indexes, kept in the
+//! `task-log` database when Meilisearch is launched with `--experimental-task-log`. Unlike the
+//! tasks of the task queue, its entries and the content files they reference are only deleted
+//! when the log is trimmed.
+//!
+//! The log is shipped as a `.tar.gz` archive containing a `log.ndjson` file, with one entry per
+//! line, and the content files of its document additions and imports from a URL in a `payloads`
+//! directory. Replaying the archive on another instance registers the tasks of its entries there,
+//! with their uid.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Write};
+use std::ops::Bound;
+use std::path::Path;
+
+use meilisearch_types::compression;
+use meilisearch_types::heed::types::DecodeIgnore;
+use meilisearch_types::heed::{RoTxn, RwTxn};
+use meilisearch_types::tasks::{KindWithContent, Status, Task};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::queue::Queue;
+use crate::{Error, IndexScheduler, Result, TaskId};
+
+const LOG_FILE: &str = "log.ndjson";
+const PAYLOADS_DIR: &str = "payloads";
+
+/// A succeeded task of the task log, with what is needed to register it again on another instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLogEntry {
+    pub uid: TaskId,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+    /// The content of the task, the `contentFile` of a document addition or import references
+    /// its payload.
+    pub kind: KindWithContent,
+}
+
+/// What the replay of a task log archive registered.
+#[derive(Debug, Default)]
+pub struct TaskLogReplay {
+    /// The tasks registered on the instance, in the order of the log.
+    pub tasks: Vec<Task>,
+    /// The uids of the entries skipped because the instance already registered their task.
+    pub skipped: Vec<TaskId>,
+    /// The uid of the last entry of the archive, from which the log must be shipped next.
+    pub last_uid: Option<TaskId>,
+}
+
+/// Appends the succeeded task to the task log if it changed the indexes.
+pub(crate) fn append(wtxn: &mut RwTxn, queue: &Queue, task: &Task) -> Result<()> {
+    match task.kind {
+        KindWithContent::DocumentAdditionOrUpdate { .. }
+        | KindWithContent::DocumentImportUrl { .. }
+        | KindWithContent::DocumentDeletion { .. }
+        | KindWithContent::DocumentDeletionByFilter { .. }
+        | KindWithContent::DocumentEdition { .. }
+        | KindWithContent::DocumentClear { .. }
+        | KindWithContent::SettingsUpdate { .. }
+        | KindWithContent::IndexDeletion { .. }
+        | KindWithContent::IndexCreation { .. }
+        | KindWithContent::IndexUpdate { .. }
+        | KindWithContent::IndexSwap { .. }
+        | KindWithContent::DumpImport { .. }
+        | KindWithContent::PointInTimeRestore { .. }
+        | KindWithContent::IndexRestore { .. } => (),
+        KindWithContent::TaskCancelation { .. }
+        | KindWithContent::TaskDeletion { .. }
+        | KindWithContent::DumpCreation { .. }
+        | KindWithContent::SnapshotCreation
+        | KindWithContent::UpgradeDatabase { .. } => return Ok(()),
+    }
+
+    let entry = TaskLogEntry {
+        uid: task.uid,
+        finished_at: task.finished_at.unwrap_or_else(OffsetDateTime::now_utc),
+        kind: task.kind.clone(),
+    };
+    queue.task_log.put(wtxn, &task.uid, &entry)?;
+    Ok(())
+}
+
+/// Returns `true` if the task is still in the task log.
+pub(crate) fn contains(rtxn: &RoTxn, queue: &Queue, task_id: TaskId) -> Result<bool> {
+    Ok(queue.task_log.remap_data_type::<DecodeIgnore>().get(rtxn, &task_id)?.is_some())
+}
+
+impl IndexScheduler {
+    /// Returns the entries of the task log after the task `since`, oldest first, at most `limit`.
+    ///
+    /// The entries following the first task that isn't processed yet are left out, so that the
+    /// log can be shipped again from the last returned entry without missing any task.
+    pub fn task_log_entries(
+        &self,
+        since: Option<TaskId>,
+        limit: Option<u32>,
+    ) -> Result<Vec<TaskLogEntry>> {
+        if !self.scheduler.task_log {
+            return Err(Error::TaskLogDisabled);
+        }
+        let rtxn = self.env.read_txn()?;
+        let first_enqueued = self.queue.tasks.get_status(&rtxn, Status::Enqueued)?.min();
+        let range = (
+            since.map_or(Bound::Unbounded, Bound::Excluded),
+            first_enqueued.map_or(Bound::Unbounded, Bound::Excluded),
+        );
+
+        let mut entries = Vec::new();
+        for entry in self.queue.task_log.range(&rtxn, &range)? {
+            if limit.is_some_and(|limit| entries.len() >= limit as usize) {
+                break;
+            }
+            let (_, entry) = entry?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Writes the entries of the task log and the content files of their document additions and
+    /// imports as a `.tar.gz` archive that can be replayed on another instance.
+    pub fn write_task_log_archive(
+        &self,
+        entries: &[TaskLogEntry],
+        writer: impl Write,
+    ) -> Result<()> {
+        let archive_dir = tempfile::tempdir()?;
+        let payloads_dir = archive_dir.path().join(PAYLOADS_DIR);
+        fs::create_dir_all(&payloads_dir)?;
+
+        let mut log = BufWriter::new(File::create(archive_dir.path().join(LOG_FILE))?);
+        for entry in entries {
+            serde_json::to_writer(&mut log, entry).map_err(io::Error::from)?;
+            log.write_all(b"\n")?;
+            // the replay reports the content files that are not kept anymore
+            if let Some(content_file) = shipped_content_file(&entry.kind) {
+                let src = self.queue.file_store.get_update_path(content_file);
+                match fs::copy(src, payloads_dir.join(content_file.to_string())) {
+                    Ok(_) => (),
+                    Err(e) if e.kind() == ErrorKind::NotFound => (),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        log.flush()?;
+        drop(log);
+
+        compression::write_tar_gz(archive_dir.path(), writer)?;
+        Ok(())
+    }
+
+    /// Deletes the entries of the task log up to the task `until`, or all of them, and returns
+    /// how many were deleted.
+    ///
+    /// The content files of their document additions and imports are deleted too, unless their
+    /// task still exists and the point-in-time restores or the change stream need them.
+    pub fn trim_task_log(&self, until: Option<TaskId>) -> Result<u64> {
+        if !self.scheduler.task_log {
+            return Err(Error::TaskLogDisabled);
+        }
+        let range = (Bound::Unbounded, until.map_or(Bound::Unbounded, Bound::Included));
+        let mut wtxn = self.env.write_txn()?;
+        let mut content_files = Vec::new();
+        for entry in self.queue.task_log.range(&wtxn, &range)? {
+            let (uid, entry) = entry?;
+            match entry.kind {
+                KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => {
+                    let still_needed = (self.scheduler.restore_points.is_some()
+                        || self.scheduler.change_stream)
+                        && self.queue.tasks.get_task(&wtxn, uid)?.is_some();
+                    if !still_needed {
+                        content_files.push(content_file);
+                    }
+                }
+                // only the task log keeps the documents imported from a URL
+                KindWithContent::DocumentImportUrl { content_file, .. } => {
+                    content_files.push(content_file)
+                }
+                _ => (),
+            }
+        }
+        let deleted = self.queue.task_log.delete_range(&mut wtxn, &range)? as u64;
+        wtxn.commit()?;
+
+        for content_file in content_files {
+            if !self.queue.file_store.get_update_path(content_file).exists() {
+                continue;
+            }
+            if let Err(e) = self.queue.delete_update_file(content_file) {
+                tracing::error!(
+                    "Failure to delete the content file {content_file} of the task log. Error: {e}"
+                );
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Registers the tasks of a task log archive, with the uid they had on the instance the log
+    /// was shipped from, and the content files of their document additions and imports.
+    ///
+    /// The archive is checked before registering anything. The entries whose task the instance
+    /// already registered, with the same type and indexes, are skipped and reported, so that an
+    /// archive that was partially replayed or shipped twice can be replayed again. The replay
+    /// fails if the instance registered another task with the uid of an entry, as the instance
+    /// doesn't come from the same log then.
+    pub fn replay_task_log(&self, archive: &Path) -> Result<TaskLogReplay> {
+        let extracted = tempfile::tempdir()?;
+        compression::from_tar_gz(archive, extracted.path())
+            .map_err(|e| Error::TaskLogReplay(format!("the archive is invalid: {e}")))?;
+        let log = match File::open(extracted.path().join(LOG_FILE)) {
+            Ok(log) => log,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(Error::TaskLogReplay(format!(
+                    "the archive doesn't contain a `{LOG_FILE}` file"
+                )))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let entries = serde_json::Deserializer::from_reader(BufReader::new(log))
+            .into_iter::<TaskLogEntry>()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                Error::TaskLogReplay(format!("an entry of `{LOG_FILE}` is invalid: {e}"))
+            })?;
+
+        let payloads_dir = extracted.path().join(PAYLOADS_DIR);
+        let kinds = entries
+            .iter()
+            .map(|entry| replayed_kind(entry, &payloads_dir))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut replay =
+            TaskLogReplay { last_uid: entries.last().map(|entry| entry.uid), ..Default::default() };
+        let rtxn = self.env.read_txn()?;
+        let next_task_id = self.queue.tasks.next_task_id(&rtxn)?;
+        for entry in entries.iter().filter(|entry| entry.uid < next_task_id) {
+            let uid = entry.uid;
+            let registered = self.queue.tasks.get_task(&rtxn, uid)?;
+            match registered {
+                Some(task)
+                    if task.kind.as_kind() == entry.kind.as_kind()
+                        && task.indexes() == entry.kind.indexes() =>
+                {
+                    replay.skipped.push(uid)
+                }
+                _ => {
+                    return Err(Error::TaskLogReplay(format!(
+                        "the instance already registered a task `{uid}` that is not the one of the log, the log can only be replayed on an instance whose tasks all come from it"
+                    )))
+                }
+            }
+        }
+        drop(rtxn);
+
+        for (entry, mut kind) in entries.into_iter().zip(kinds) {
+            if entry.uid < next_task_id {
+                continue;
+            }
+
+            let content_file = match &mut kind {
+                KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+                | KindWithContent::DocumentImportUrl { content_file, .. } => {
+                    let (uuid, mut file) = self.queue.create_update_file(false)?;
+                    io::copy(
+                        &mut File::open(payloads_dir.join(content_file.to_string()))?,
+                        &mut file,
+                    )?;
+                    file.persist()?;
+                    *content_file = uuid;
+                    Some(uuid)
+                }
+                _ => None,
+            };
+
+            match self.register(kind, Some(entry.uid), false) {
+                Ok(task) => replay.tasks.push(task),
+                Err(e) => {
+                    if let Some(uuid) = content_file {
+                        self.queue.delete_update_file(uuid)?;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(replay)
+    }
+}
+
+/// Returns the task to register to replay the entry, or why it can't be replayed.
+fn replayed_kind(entry: &TaskLogEntry, payloads_dir: &Path) -> Result<KindWithContent> {
+    let uid = entry.uid;
+    match &entry.kind {
+        KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+        | KindWithContent::DocumentImportUrl { content_file, .. } => {
+            if !payloads_dir.join(content_file.to_string()).is_file() {
+                return Err(Error::TaskLogReplay(format!(
+                    "the documents of task `{uid}` are missing from the archive"
+                )));
+            }
+            Ok(entry.kind.clone())
+        }
+        // the swaps scheduled at a date were already executed on the instance the log comes from
+        KindWithContent::IndexSwap { swaps, .. } => {
+            Ok(KindWithContent::IndexSwap { swaps: swaps.clone(), execute_at: None })
+        }
+        KindWithContent::DocumentDeletion { .. }
+        | KindWithContent::DocumentDeletionByFilter { .. }
+        | KindWithContent::DocumentEdition { .. }
+        | KindWithContent::DocumentClear { .. }
+        | KindWithContent::SettingsUpdate { .. }
+        | KindWithContent::IndexDeletion { .. }
+        | KindWithContent::IndexCreation { .. }
+        | KindWithContent::IndexUpdate { .. } => Ok(entry.kind.clone()),
+        // the indexes restored from a backup can't be rebuilt from the log, they must be copied
+        // from a snapshot of the instance the log comes from
+        KindWithContent::DumpImport { .. }
+        | KindWithContent::PointInTimeRestore { .. }
+        | KindWithContent::IndexRestore { .. } => Err(Error::TaskLogReplay(format!(
+            "task `{uid}` of type `{}` cannot be replayed, the indexes must be copied from a snapshot instead",
+            entry.kind.as_kind()
+        ))),
+        KindWithContent::TaskCancelation { .. }
+        | KindWithContent::TaskDeletion { .. }
+        | KindWithContent::DumpCreation { .. }
+        | KindWithContent::SnapshotCreation
+        | KindWithContent::UpgradeDatabase { .. } => Err(Error::TaskLogReplay(format!(
+            "task `{uid}` of type `{}` is not part of a task log",
+            entry.kind.as_kind()
+        ))),
+    }
+}
+
+/// Returns the content file shipped with the entry of the task, if any.
+fn shipped_content_file(kind: &KindWithContent) -> Option<Uuid> {
+    match kind {
+        KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+        | KindWithContent::DocumentImportUrl { content_file, .. } => Some(*content_file),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{BufWriter, Write};
 use std::sync::Arc;
 use std::time::Duration;
@@ -96,9 +97,15 @@ impl IndexScheduler {
             update_file_path: tempdir.path().join("file_store"),
             indexes_path: tempdir.path().join("indexes"),
             snapshots_path: tempdir.path().join("snapshots"),
+            snapshot_s3: None,
+            restore_points: None,
+            change_stream: false,
+            task_log: false,
+            index_aliases: false,
             dumps_path: tempdir.path().join("dumps"),
-            webhook_url: None,
-            webhook_authorization_header: None,
+            import_url: None,
+            webhooks: Vec::new(),
+            task_events: None,
             task_db_size: 1000 * 1000 * 10, // 10 MB, we don't use MiB on purpose.
             index_base_map_size: 1000 * 1000, // 1 MB, we don't use MiB on purpose.
             enable_mdb_writemap: false,
@@ -107,9 +114,18 @@ impl IndexScheduler {
             indexer_config: Arc::new(indexer_config),
             autobatching_enabled: true,
             cleanup_enabled: true,
+            read_only_replica: false,
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: usize::MAX,
             batched_tasks_size_limit: u64::MAX,
+            max_batched_documents: None,
+            autobatching_max_wait: None,
+            index_autobatching: BTreeMap::new(),
+            max_concurrent_batches: 1,
+            fast_lane_max_documents: None,
+            checkpoint_documents: None,
+            max_enqueued_tasks: None,
+            disk_watermark: None,
             instance_features: Default::default(),
             auto_upgrade: true, // Don't cost much and will ensure the happy path works
             embedding_cache_cap: 10,
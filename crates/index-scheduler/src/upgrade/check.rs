@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use meilisearch_types::features::RuntimeTogglableFeatures;
+use meilisearch_types::heed::types::{SerdeJson, Str};
+use meilisearch_types::heed::{Database, EnvFlags, EnvOpenOptions};
+use meilisearch_types::milli::update::upgrade::{upgrade_plan, UpgradeCost};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::upgrade_start;
+use crate::features::{db_keys as features_keys, db_name as features_db};
+use crate::index_mapper::{db_name as index_mapper_db, IndexStats};
+use crate::uuid_codec::UuidCodec;
+use crate::IndexScheduler;
+
+/// Number of documents read per second by the steps that go through every document of an index.
+///
+/// Deliberately pessimistic, the actual throughput mostly depends on the disk.
+const DOCUMENTS_PER_SECOND: u64 = 20_000;
+/// Number of embeddings rewritten per second by the steps that go through the vector store.
+const EMBEDDINGS_PER_SECOND: u64 = 5_000;
+
+/// What a dumpless upgrade of a database would imply, as reported by `meilisearch --upgrade-check`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeReport {
+    /// Version of the database, as read from its `VERSION` file.
+    pub from: String,
+    /// Version of the running Meilisearch binary.
+    pub to: String,
+    /// `false` if any incompatibility prevents the dumpless upgrade.
+    pub upgradable: bool,
+    pub incompatibilities: Vec<String>,
+    pub indexes: Vec<IndexUpgradeReport>,
+    /// Sum of the estimated duration of the upgrade of every index.
+    pub estimated_duration_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexUpgradeReport {
+    pub uid: String,
+    pub number_of_documents: u64,
+    pub number_of_embeddings: u64,
+    pub steps: Vec<UpgradeStepReport>,
+    pub estimated_duration_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeStepReport {
+    pub from: String,
+    pub to: String,
+    /// The internal structures of the index rewritten by this step.
+    pub changes: &'static [&'static str],
+    pub cost: UpgradeCost,
+}
+
+/// Inspects the task database at `tasks_path` and reports what upgrading it from `from` to `to`
+/// would imply, without modifying anything.
+///
+/// The database must not be in use by another Meilisearch process.
+pub fn upgrade_check(
+    tasks_path: &Path,
+    task_db_size: usize,
+    from: (u32, u32, u32),
+    to: (u32, u32, u32),
+) -> anyhow::Result<UpgradeReport> {
+    let mut incompatibilities = Vec::new();
+    // The indexes are upgraded one after the other, starting from the version of the
+    // database, unless a previous upgrade was interrupted midway.
+    let steps = match upgrade_start(from, to)
+        .map_err(|e| e.to_string())
+        .and_then(|_| upgrade_plan(from).map_err(|e| e.to_string()))
+    {
+        Ok(_) if from == to => Vec::new(),
+        Ok(steps) => steps,
+        Err(error) => {
+            incompatibilities.push(error);
+            Vec::new()
+        }
+    };
+
+    let mut options = EnvOpenOptions::new().read_txn_without_tls();
+    options.max_dbs(IndexScheduler::nb_db()).map_size(task_db_size);
+    let env = unsafe {
+        options.flags(EnvFlags::READ_ONLY);
+        options.open(tasks_path)
+    }?;
+    let rtxn = env.read_txn()?;
+
+    // The features are read as raw JSON so that the ones that no longer exist are not silently dropped.
+    let features: Option<Database<Str, SerdeJson<Map<String, Value>>>> =
+        env.open_database(&rtxn, Some(features_db::EXPERIMENTAL_FEATURES))?;
+    if let Some(features) = features {
+        let known = serde_json::to_value(RuntimeTogglableFeatures::default())?;
+        let persisted = features.get(&rtxn, features_keys::EXPERIMENTAL_FEATURES)?;
+        for (name, enabled) in persisted.into_iter().flatten() {
+            if enabled == Value::Bool(true) && known.get(&name).is_none() {
+                incompatibilities.push(format!(
+                    "The experimental feature `{name}` is enabled but has been removed in v{}",
+                    version_string(to)
+                ));
+            }
+        }
+    }
+
+    let mut indexes = Vec::new();
+    let index_mapping: Option<Database<Str, UuidCodec>> =
+        env.open_database(&rtxn, Some(index_mapper_db::INDEX_MAPPING))?;
+    let index_stats: Option<Database<UuidCodec, SerdeJson<IndexStats>>> =
+        env.open_database(&rtxn, Some(index_mapper_db::INDEX_STATS))?;
+
+    if let (Some(index_mapping), Some(index_stats)) = (index_mapping, index_stats) {
+        for result in index_mapping.iter(&rtxn)? {
+            let (uid, uuid) = result?;
+            let (number_of_documents, number_of_embeddings) = match index_stats.get(&rtxn, &uuid)? {
+                Some(stats) => {
+                    let number_of_documents = stats
+                        .number_of_documents
+                        .unwrap_or_else(|| stats.documents_database_stats.number_of_entries());
+                    // The stats of the pre-v1.13 versions don't count the embeddings,
+                    // assume there is one per document.
+                    (number_of_documents, stats.number_of_embeddings.unwrap_or(number_of_documents))
+                }
+                None => (0, 0),
+            };
+
+            let estimated_duration_secs: f64 = steps
+                .iter()
+                .map(|step| match step.cost {
+                    UpgradeCost::Constant => 0.0,
+                    UpgradeCost::Documents => {
+                        number_of_documents as f64 / DOCUMENTS_PER_SECOND as f64
+                    }
+                    UpgradeCost::Embeddings => {
+                        number_of_embeddings as f64 / EMBEDDINGS_PER_SECOND as f64
+                    }
+                })
+                .sum();
+
+            indexes.push(IndexUpgradeReport {
+                uid: uid.to_string(),
+                number_of_documents,
+                number_of_embeddings,
+                steps: steps
+                    .iter()
+                    .map(|step| UpgradeStepReport {
+                        from: version_string(step.from),
+                        to: version_string(step.to),
+                        changes: step.changes,
+                        cost: step.cost,
+                    })
+                    .collect(),
+                estimated_duration_secs: estimated_duration_secs.ceil() as u64,
+            });
+        }
+    }
+
+    Ok(UpgradeReport {
+        from: version_string(from),
+        to: version_string(to),
+        upgradable: incompatibilities.is_empty(),
+        incompatibilities,
+        estimated_duration_secs: indexes.iter().map(|index| index.estimated_duration_secs).sum(),
+        indexes,
+    })
+}
+
+fn version_string((major, minor, patch): (u32, u32, u32)) -> String {
+    format!("{major}.{minor}.{patch}")
+}
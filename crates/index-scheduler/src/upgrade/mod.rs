@@ -1,3 +1,5 @@
+mod check;
+
 use anyhow::bail;
 use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
 use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
@@ -5,6 +7,7 @@ use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH}
 use time::OffsetDateTime;
 use tracing::info;
 
+pub use self::check::{upgrade_check, IndexUpgradeReport, UpgradeReport, UpgradeStepReport};
 use crate::queue::TaskQueue;
 use crate::versioning::Versioning;
 
@@ -18,18 +21,15 @@ trait UpgradeIndexScheduler {
     fn target_version(&self) -> (u32, u32, u32);
 }
 
-pub fn upgrade_index_scheduler(
-    env: &Env<WithoutTls>,
-    versioning: &Versioning,
-    from: (u32, u32, u32),
-    to: (u32, u32, u32),
-) -> anyhow::Result<()> {
+const UPGRADE_FUNCTIONS: &[&dyn UpgradeIndexScheduler] = &[&ToCurrentNoOp {}];
+
+/// Returns the index of the first upgrade function to apply, or an error if the
+/// task queue cannot be upgraded from `from` to `to`.
+fn upgrade_start(from: (u32, u32, u32), to: (u32, u32, u32)) -> anyhow::Result<usize> {
     let current_major = to.0;
     let current_minor = to.1;
     let current_patch = to.2;
 
-    let upgrade_functions: &[&dyn UpgradeIndexScheduler] = &[&ToCurrentNoOp {}];
-
     let start = match from {
         (1, 12, _) => 0,
         (1, 13, _) => 0,
@@ -52,9 +52,20 @@ pub fn upgrade_index_scheduler(
         }
     };
 
+    Ok(start)
+}
+
+pub fn upgrade_index_scheduler(
+    env: &Env<WithoutTls>,
+    versioning: &Versioning,
+    from: (u32, u32, u32),
+    to: (u32, u32, u32),
+) -> anyhow::Result<()> {
+    let start = upgrade_start(from, to)?;
+
     info!("Upgrading the task queue");
     let mut local_from = from;
-    for upgrade in upgrade_functions[start..].iter() {
+    for upgrade in UPGRADE_FUNCTIONS[start..].iter() {
         let target = upgrade.target_version();
         info!(
             "Upgrading from v{}.{}.{} to v{}.{}.{}",
@@ -80,6 +91,9 @@ pub fn upgrade_index_scheduler(
             finished_at: None,
             error: None,
             canceled_by: None,
+            depends_on: None,
+            labels: Default::default(),
+            quota_key: None,
             details: Some(Details::UpgradeDatabase { from, to }),
             status: Status::Enqueued,
             kind: KindWithContent::UpgradeDatabase { from },
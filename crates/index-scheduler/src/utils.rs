@@ -2,6 +2,7 @@
 
 use std::collections::{BTreeSet, HashSet};
 use std::ops::Bound;
+use std::path::Path;
 
 use meilisearch_types::batches::{Batch, BatchEnqueuedAt, BatchId, BatchStats};
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
@@ -9,6 +10,7 @@ use meilisearch_types::milli::CboRoaringBitmapCodec;
 use meilisearch_types::task_view::DetailsView;
 use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status};
 use roaring::RoaringBitmap;
+use sysinfo::Disks;
 use time::OffsetDateTime;
 
 use crate::{Error, Result, Task, TaskId, BEI128};
@@ -243,6 +245,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     let mut index_uids = vec![];
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentImportUrl { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
@@ -251,7 +254,11 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
-        K::IndexSwap { swaps } => {
+        K::IndexRestore { index_uid, .. } => index_uids.push(index_uid),
+        K::PointInTimeRestore { index_uids: restored, .. } => {
+            index_uids.extend(restored.iter_mut().flatten())
+        }
+        K::IndexSwap { swaps, .. } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
                     index_uids.push(lhs);
@@ -264,10 +271,11 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
+        | K::DumpImport { .. }
         | K::UpgradeDatabase { .. }
         | K::SnapshotCreation => (),
     };
-    if let Some(Details::IndexSwap { swaps }) = &mut task.details {
+    if let Some(Details::IndexSwap { swaps, .. }) = &mut task.details {
         for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
             if lhs == swap.0 || lhs == swap.1 {
                 index_uids.push(lhs);
@@ -306,8 +314,11 @@ pub(crate) fn filter_out_references_to_newer_tasks(task: &mut Task) {
 }
 
 pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
-    let swaps =
-        if let KindWithContent::IndexSwap { swaps } = &task.kind { swaps } else { return Ok(()) };
+    let swaps = if let KindWithContent::IndexSwap { swaps, .. } = &task.kind {
+        swaps
+    } else {
+        return Ok(());
+    };
     let mut all_indexes = HashSet::new();
     let mut duplicate_indexes = BTreeSet::new();
     for IndexSwap { indexes: (lhs, rhs) } in swaps {
@@ -337,6 +348,24 @@ pub fn clamp_to_page_size(size: usize) -> usize {
     size / page_size::get() * page_size::get()
 }
 
+/// Returns the percentage of the disk holding the provided path that is used,
+/// or `None` if the disk can't be found.
+pub(crate) fn disk_usage_percentage(path: &Path) -> Option<u8> {
+    let path = path.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    // the disk holding the path is the one with the most specific mount point
+    let disk = disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())?;
+    let total = disk.total_space();
+    if total == 0 {
+        return None;
+    }
+    let used = total.saturating_sub(disk.available_space());
+    Some((used * 100 / total) as u8)
+}
+
 #[cfg(test)]
 impl crate::IndexScheduler {
     /// Asserts that the index scheduler's content is internally consistent.
@@ -354,6 +383,9 @@ impl crate::IndexScheduler {
                 finished_at,
                 error: _,
                 canceled_by,
+                depends_on,
+                labels,
+                quota_key: _,
                 details,
                 status,
                 kind,
@@ -433,11 +465,20 @@ impl crate::IndexScheduler {
                     _ => panic!(),
                 }
             }
+            if let Some(depends_on) = depends_on {
+                let dependents = self.queue.tasks.get_dependents(&rtxn, depends_on).unwrap();
+                assert_eq!(dependents.contains(uid), status == Status::Enqueued);
+            }
+            for (key, value) in labels {
+                let labeled = self.queue.tasks.get_label(&rtxn, &format!("{key}={value}")).unwrap();
+                assert!(labeled.contains(uid));
+            }
             if let Some(details) = details {
                 match details {
-                    Details::IndexSwap { swaps: sw1 } => {
-                        if let KindWithContent::IndexSwap { swaps: sw2 } = &kind {
+                    Details::IndexSwap { swaps: sw1, execute_at: at1 } => {
+                        if let KindWithContent::IndexSwap { swaps: sw2, execute_at: at2 } = &kind {
                             assert_eq!(&sw1, sw2);
+                            assert_eq!(&at1, at2);
                         }
                     }
                     Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
@@ -591,6 +632,19 @@ impl crate::IndexScheduler {
                     Details::Dump { dump_uid: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::DumpImport { imported_indexes: _, indexed_documents: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::DumpImport);
+                    }
+                    Details::PointInTimeRestore {
+                        restore_at: _,
+                        restored_indexes: _,
+                        replayed_tasks: _,
+                    } => {
+                        assert_eq!(kind.as_kind(), Kind::PointInTimeRestore);
+                    }
+                    Details::IndexRestore { snapshot: _, dump_uid: _, indexed_documents: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexRestore);
+                    }
                     Details::UpgradeDatabase { from: _, to: _ } => {
                         assert_eq!(kind.as_kind(), Kind::UpgradeDatabase);
                     }
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+
+use crate::error::{AuthControllerError, Result};
+
+/// Prefixes the encrypted values, a JSON document can never start with a null byte.
+const ENCRYPTED_PREFIX: &[u8] = b"\0aes256gcm:";
+const NONCE_SIZE: usize = 12;
+
+/// The key the API keys are encrypted with in the auth store, so that stealing the database
+/// files doesn't give away the API keys.
+#[derive(Clone)]
+pub struct EncryptionKey(Arc<Aes256Gcm>);
+
+impl EncryptionKey {
+    /// Parses a base64-encoded 256-bit key, e.g. generated with `openssl rand -base64 32`.
+    pub fn from_base64(encoded: &str) -> std::result::Result<Self, InvalidEncryptionKey> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|_| InvalidEncryptionKey)?;
+        let cipher = Aes256Gcm::new_from_slice(&bytes).map_err(|_| InvalidEncryptionKey)?;
+        Ok(Self(Arc::new(cipher)))
+    }
+
+    /// Encrypts the value with a fresh nonce.
+    pub(crate) fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext =
+            self.0.encrypt(&nonce, plaintext).map_err(|_| AuthControllerError::Encryption)?;
+
+        let mut bytes = Vec::with_capacity(ENCRYPTED_PREFIX.len() + NONCE_SIZE + ciphertext.len());
+        bytes.extend_from_slice(ENCRYPTED_PREFIX);
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(bytes)
+    }
+
+    /// Decrypts a value produced by [`Self::encrypt`].
+    pub(crate) fn decrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let bytes = bytes.strip_prefix(ENCRYPTED_PREFIX).ok_or(AuthControllerError::Decryption)?;
+        if bytes.len() < NONCE_SIZE {
+            return Err(AuthControllerError::Decryption);
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_SIZE);
+        self.0
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| AuthControllerError::Decryption)
+    }
+}
+
+/// Returns `true` if the value was produced by [`EncryptionKey::encrypt`].
+pub(crate) fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(ENCRYPTED_PREFIX)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The auth encryption key must be a base64-encoded 256-bit key.")]
+pub struct InvalidEncryptionKey;
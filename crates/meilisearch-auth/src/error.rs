@@ -11,6 +11,20 @@ pub enum AuthControllerError {
     ApiKeyNotFound(String),
     #[error("`uid` field value `{0}` is already an existing API key.")]
     ApiKeyAlreadyExists(String),
+    #[error("The API keys are encrypted, the auth encryption key must be provided to read them.")]
+    MissingEncryptionKey,
+    #[error("The API keys could not be decrypted, the auth encryption key is wrong or the auth store is corrupted.")]
+    Decryption,
+    #[error("The API keys could not be encrypted.")]
+    Encryption,
+    #[error(
+        "The master key has been rotated, Meilisearch must be launched with the new master key."
+    )]
+    RotatedMasterKey,
+    #[error("The master key has already been rotated out and can't be used again.")]
+    ReusedMasterKey,
+    #[error("API key `{0}` would get a different value on this instance. Meilisearch must be launched with the master key of the instance the keys were exported from.")]
+    ImportedKeyValueMismatch(String),
     #[error("Internal error: {0}")]
     Internal(Box<dyn Error + Send + Sync + 'static>),
 }
@@ -27,7 +41,13 @@ impl ErrorCode for AuthControllerError {
         match self {
             Self::ApiKeyNotFound(_) => Code::ApiKeyNotFound,
             Self::ApiKeyAlreadyExists(_) => Code::ApiKeyAlreadyExists,
-            Self::Internal(_) => Code::Internal,
+            Self::ReusedMasterKey => Code::InvalidMasterKey,
+            Self::ImportedKeyValueMismatch(_) => Code::InvalidApiKeyImport,
+            Self::MissingEncryptionKey
+            | Self::Decryption
+            | Self::Encryption
+            | Self::RotatedMasterKey
+            | Self::Internal(_) => Code::Internal,
         }
     }
 }
@@ -1,36 +1,57 @@
-mod dump;
+mod encryption;
 pub mod error;
+mod rate_limit;
 mod store;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 
+pub use encryption::{EncryptionKey, InvalidEncryptionKey};
 use error::{AuthControllerError, Result};
 use maplit::hashset;
 use meilisearch_types::heed::{Env, WithoutTls};
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::keys::{
+    Action, CreateApiKey, ExportedApiKey, Key, KeyId, KeyQuotas, PatchApiKey,
+};
 use meilisearch_types::milli::update::Setting;
+use rate_limit::RateLimiter;
 use serde::{Deserialize, Serialize};
 pub use store::open_auth_store_env;
-use store::{generate_key_as_hexa, HeedAuthStore};
-use time::OffsetDateTime;
+use store::{generate_key_as_hexa, sha256_digest, HeedAuthStore};
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct AuthController {
     store: HeedAuthStore,
-    master_key: Option<String>,
+    master_key: Arc<RwLock<Option<String>>>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl AuthController {
-    pub fn new(auth_env: Env<WithoutTls>, master_key: &Option<String>) -> Result<Self> {
-        let store = HeedAuthStore::new(auth_env)?;
+    pub fn new(
+        auth_env: Env<WithoutTls>,
+        master_key: &Option<String>,
+        encryption_key: Option<EncryptionKey>,
+    ) -> Result<Self> {
+        let store = HeedAuthStore::new(auth_env, encryption_key)?;
+
+        if let Some(master_key) = master_key {
+            if store.is_rotated_master_key(master_key)? {
+                return Err(AuthControllerError::RotatedMasterKey);
+            }
+        }
 
         if store.is_empty()? {
             generate_default_keys(&store)?;
         }
 
-        Ok(Self { store, master_key: master_key.clone() })
+        Ok(Self {
+            store,
+            master_key: Arc::new(RwLock::new(master_key.clone())),
+            rate_limiter: Arc::default(),
+        })
     }
 
     /// Return `Ok(())` if the auth controller is able to access one of its database.
@@ -66,10 +87,32 @@ impl AuthController {
             Setting::NotSet => (),
             name => key.name = name.set(),
         };
+        match patch.rate_limit {
+            Setting::NotSet => (),
+            rate_limit => key.rate_limit = rate_limit.set(),
+        };
+        match patch.quotas {
+            Setting::NotSet => (),
+            quotas => key.quotas = quotas.set(),
+        };
+        match patch.filter {
+            Setting::NotSet => (),
+            filter => key.filter = filter.set(),
+        };
         key.updated_at = OffsetDateTime::now_utc();
         self.store.put_api_key(key)
     }
 
+    /// Gives a new value to the key, the previous one being still accepted during the overlap.
+    pub fn rotate_key(&self, uid: Uuid, overlap: Option<Duration>) -> Result<Key> {
+        let mut key = self.get_key(uid)?;
+        let now = OffsetDateTime::now_utc();
+        key.rotations += 1;
+        key.previous_key_expires_at = overlap.map(|overlap| now.saturating_add(overlap));
+        key.updated_at = now;
+        self.store.put_api_key(key)
+    }
+
     pub fn get_key(&self, uid: Uuid) -> Result<Key> {
         self.store
             .get_api_key(uid)?
@@ -77,7 +120,7 @@ impl AuthController {
     }
 
     pub fn get_optional_uid_from_encoded_key(&self, encoded_key: &[u8]) -> Result<Option<Uuid>> {
-        match &self.master_key {
+        match self.get_master_key() {
             Some(master_key) => {
                 self.store.get_uid_from_encoded_key(encoded_key, master_key.as_bytes())
             }
@@ -94,6 +137,7 @@ impl AuthController {
         &self,
         uid: Uuid,
         search_rules: Option<SearchRules>,
+        tenant_token_routes: Option<BTreeSet<TenantTokenRoute>>,
     ) -> Result<AuthFilter> {
         let key = self.get_key(uid)?;
 
@@ -101,29 +145,135 @@ impl AuthController {
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
 
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        let key_quotas = key.quotas.map(|quotas| (uid, quotas));
+
+        Ok(AuthFilter {
+            search_rules,
+            key_authorized_indexes,
+            allow_index_creation,
+            key_quotas,
+            key_filter: key.filter,
+            tenant_token_routes,
+        })
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
         self.store.list_api_keys()
     }
 
+    /// Export all the keys along with the digest of their current value, without the value itself.
+    pub fn export_keys(&self) -> Result<Vec<ExportedApiKey>> {
+        let keys = self.store.list_api_keys()?;
+        Ok(keys
+            .into_iter()
+            .map(|key| {
+                let key_sha256 = self.generate_key(&key).map(|value| sha256_digest(&value));
+                ExportedApiKey { key, key_sha256 }
+            })
+            .collect())
+    }
+
+    /// Import keys exported from another instance, replacing the keys with the same uid.
+    ///
+    /// Nothing is imported if one of the keys would get a different value on this instance,
+    /// which happens when the master keys of the two instances differ.
+    pub fn import_keys(&self, keys: Vec<ExportedApiKey>) -> Result<usize> {
+        for ExportedApiKey { key, key_sha256 } in &keys {
+            let Some(expected) = key_sha256 else { continue };
+            let digest = self.generate_key(key).map(|value| sha256_digest(&value));
+            if digest.as_ref() != Some(expected) {
+                return Err(AuthControllerError::ImportedKeyValueMismatch(key.uid.to_string()));
+            }
+        }
+
+        let count = keys.len();
+        for ExportedApiKey { key, .. } in keys {
+            let uid = key.uid;
+            self.store.put_api_key(key)?;
+            self.rate_limiter.forget(uid);
+        }
+        Ok(count)
+    }
+
     pub fn delete_key(&self, uid: Uuid) -> Result<()> {
         if self.store.delete_api_key(uid)? {
+            self.rate_limiter.forget(uid);
             Ok(())
         } else {
             Err(AuthControllerError::ApiKeyNotFound(uid.to_string()))
         }
     }
 
-    pub fn get_master_key(&self) -> Option<&String> {
-        self.master_key.as_ref()
+    /// Delete the keys that expired more than `retention` ago, returning them.
+    pub fn delete_expired_keys(&self, retention: std::time::Duration) -> Result<Vec<Key>> {
+        let Some(deadline) = Duration::try_from(retention)
+            .ok()
+            .and_then(|retention| OffsetDateTime::now_utc().checked_sub(retention))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut deleted = Vec::new();
+        for key in self.store.list_api_keys()? {
+            if key.expires_at.is_some_and(|expires_at| expires_at <= deadline)
+                && self.store.delete_api_key(key.uid)?
+            {
+                self.rate_limiter.forget(key.uid);
+                deleted.push(key);
+            }
+        }
+        Ok(deleted)
+    }
+
+    pub fn get_master_key(&self) -> Option<String> {
+        self.master_key.read().unwrap().clone()
+    }
+
+    /// Replace the master key, which gives a new value to every API key.
+    ///
+    /// The master key being rotated out is refused from now on, including at the next launch.
+    pub fn rotate_master_key(&self, new_master_key: String) -> Result<()> {
+        if self.store.is_rotated_master_key(&new_master_key)? {
+            return Err(AuthControllerError::ReusedMasterKey);
+        }
+
+        let mut master_key = self.master_key.write().unwrap();
+        if let Some(previous_master_key) = master_key.as_deref() {
+            self.store.put_rotated_master_key(previous_master_key)?;
+        }
+        *master_key = Some(new_master_key);
+        Ok(())
     }
 
-    /// Generate a valid key from a key id using the current master key.
+    /// Generate the current value of a key using the current master key.
     /// Returns None if no master key has been set.
-    pub fn generate_key(&self, uid: Uuid) -> Option<String> {
-        self.master_key.as_ref().map(|master_key| generate_key_as_hexa(uid, master_key.as_bytes()))
+    pub fn generate_key(&self, key: &Key) -> Option<String> {
+        self.get_master_key()
+            .map(|master_key| generate_key_as_hexa(key.uid, key.rotations, master_key.as_bytes()))
+    }
+
+    /// Generate the values of a key that are accepted at the moment, the current one first.
+    /// Returns an empty list if no master key has been set.
+    pub fn generate_accepted_keys(&self, uid: Uuid) -> Result<Vec<String>> {
+        let Some(master_key) = self.get_master_key() else { return Ok(Vec::new()) };
+        let key = self.get_key(uid)?;
+        Ok(key
+            .accepted_rotations(OffsetDateTime::now_utc())
+            .map(|rotations| generate_key_as_hexa(uid, rotations, master_key.as_bytes()))
+            .collect())
+    }
+
+    /// Counts a request made with the key to perform the action.
+    ///
+    /// Returns how long to wait before retrying when the rate limit of the key is exceeded.
+    pub fn register_request(
+        &self,
+        uid: Uuid,
+        action: Action,
+    ) -> Result<Option<std::time::Duration>> {
+        let key = self.get_key(uid)?;
+        let Some(rate_limit) = &key.rate_limit else { return Ok(None) };
+        Ok(self.rate_limiter.register(uid, action.route_group(), rate_limit).err())
     }
 
     /// Check if the provided key is authorized to make a specific action
@@ -169,6 +319,9 @@ pub struct AuthFilter {
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
     allow_index_creation: bool,
+    key_quotas: Option<(KeyId, KeyQuotas)>,
+    key_filter: Option<serde_json::Value>,
+    tenant_token_routes: Option<BTreeSet<TenantTokenRoute>>,
 }
 
 impl Default for AuthFilter {
@@ -177,6 +330,9 @@ impl Default for AuthFilter {
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
             allow_index_creation: true,
+            key_quotas: None,
+            key_filter: None,
+            tenant_token_routes: None,
         }
     }
 }
@@ -187,6 +343,21 @@ impl AuthFilter {
         self.allow_index_creation && self.is_index_authorized(index)
     }
 
+    /// Return the quotas of the API key, if it has some.
+    pub fn key_quotas(&self) -> Option<(KeyId, &KeyQuotas)> {
+        self.key_quotas.as_ref().map(|(key, quotas)| (*key, quotas))
+    }
+
+    /// Return the filter applied to every search and document read made with the API key.
+    pub fn key_filter(&self) -> Option<&serde_json::Value> {
+        self.key_filter.as_ref()
+    }
+
+    /// Return true if the route can be accessed, only the tenant tokens can restrict the routes.
+    pub fn is_route_authorized(&self, route: TenantTokenRoute) -> bool {
+        self.tenant_token_routes.as_ref().map_or(true, |routes| routes.contains(&route))
+    }
+
     #[inline]
     /// Return true if a tenant token was used to generate the search rules.
     pub fn is_tenant_token(&self) -> bool {
@@ -198,6 +369,9 @@ impl AuthFilter {
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
             allow_index_creation: false,
+            key_quotas: None,
+            key_filter: None,
+            tenant_token_routes: None,
         }
     }
 
@@ -263,7 +437,15 @@ impl AuthFilter {
             return None;
         }
         let search_rules = self.search_rules.as_ref().unwrap_or(&self.key_authorized_indexes);
-        search_rules.get_index_search_rules(index)
+        let rules = search_rules.get_index_search_rules(index);
+        match &self.key_filter {
+            Some(key_filter) => {
+                let mut rules = rules.unwrap_or_default();
+                rules.add_filter(key_filter.clone());
+                Some(rules)
+            }
+            None => rules,
+        }
     }
 }
 
@@ -343,9 +525,54 @@ impl IntoIterator for SearchRules {
 /// Contains the rules to apply on the top of the search query for a specific index.
 ///
 /// filter: search filter to apply in addition to query filters.
+/// attributes_to_retrieve: the only attributes of the documents that can be returned.
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct IndexSearchRules {
     pub filter: Option<serde_json::Value>,
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+}
+
+/// The search routes a tenant token can be restricted to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum TenantTokenRoute {
+    /// The search and multi-search routes.
+    Search,
+    FacetSearch,
+    Similar,
+}
+
+impl TenantTokenRoute {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TenantTokenRoute::Search => "search",
+            TenantTokenRoute::FacetSearch => "facetSearch",
+            TenantTokenRoute::Similar => "similar",
+        }
+    }
+}
+
+impl IndexSearchRules {
+    /// Restrict the rules with another filter, both filters must match.
+    fn add_filter(&mut self, filter: serde_json::Value) {
+        use serde_json::Value;
+
+        self.filter = Some(match self.filter.take() {
+            None => filter,
+            Some(rules_filter) => {
+                let rules_filter = match rules_filter {
+                    Value::Array(rules_filter) => rules_filter,
+                    rules_filter => vec![rules_filter],
+                };
+                let filter = match filter {
+                    Value::Array(filter) => filter,
+                    filter => vec![filter],
+                };
+                Value::Array([rules_filter, filter].concat())
+            }
+        });
+    }
 }
 
 fn generate_default_keys(store: &HeedAuthStore) -> Result<()> {
@@ -372,3 +599,64 @@ pub fn generate_master_key() -> String {
     // We're using the URL_SAFE alphabet that will produce keys without =, / or other unusual characters.
     base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buf)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_keys_are_deleted_after_the_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let auth_env = open_auth_store_env(dir.path()).unwrap();
+        let controller =
+            AuthController::new(auth_env, &Some("MASTER_KEY".to_string()), None).unwrap();
+
+        let now = OffsetDateTime::now_utc();
+        let mut keys = controller.list_keys().unwrap();
+        let mut expired = keys.remove(0);
+        expired.expires_at = Some(now - Duration::hours(2));
+        let expired = controller.store.put_api_key(expired).unwrap();
+        let mut within_retention = keys.remove(0);
+        within_retention.expires_at = Some(now - Duration::minutes(5));
+        let within_retention = controller.store.put_api_key(within_retention).unwrap();
+
+        let deleted = controller.delete_expired_keys(std::time::Duration::from_secs(3600)).unwrap();
+        let deleted: Vec<_> = deleted.into_iter().map(|key| key.uid).collect();
+        assert_eq!(deleted, vec![expired.uid]);
+        assert!(controller.get_key(expired.uid).is_err());
+        assert!(controller.get_key(within_retention.uid).is_ok());
+
+        // the keys that never expire are kept
+        let kept = controller.list_keys().unwrap();
+        assert_eq!(kept.len(), keys.len() + 1);
+    }
+
+    #[test]
+    fn encrypted_keys_are_found_by_their_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let auth_env = open_auth_store_env(dir.path()).unwrap();
+        let encryption_key =
+            EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=").unwrap();
+        let controller =
+            AuthController::new(auth_env, &Some("MASTER_KEY".to_string()), Some(encryption_key))
+                .unwrap();
+
+        let key = controller.list_keys().unwrap().remove(0);
+        let value = controller.generate_key(&key).unwrap();
+        assert_eq!(controller.get_uid_from_encoded_key(&value).unwrap(), key.uid);
+
+        // the previous value is accepted during the overlap only
+        controller.rotate_key(key.uid, Some(Duration::hours(1))).unwrap();
+        let rotated = controller.generate_accepted_keys(key.uid).unwrap();
+        assert_eq!(rotated.len(), 2);
+        assert_eq!(rotated[1], value);
+        assert_eq!(controller.get_uid_from_encoded_key(&rotated[0]).unwrap(), key.uid);
+        assert_eq!(controller.get_uid_from_encoded_key(&value).unwrap(), key.uid);
+        let key = controller.rotate_key(key.uid, None).unwrap();
+        assert!(controller.get_uid_from_encoded_key(&value).is_err());
+
+        let value = controller.generate_key(&key).unwrap();
+        controller.delete_key(key.uid).unwrap();
+        assert!(controller.get_uid_from_encoded_key(&value).is_err());
+    }
+}
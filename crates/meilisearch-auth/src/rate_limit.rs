@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use meilisearch_types::keys::{KeyId, RateLimit, RequestsLimit};
+
+/// Counts the requests made with the API keys having a rate limit, in fixed windows.
+///
+/// The counters live in memory only, they start from zero again when Meilisearch restarts.
+#[derive(Default)]
+pub struct RateLimiter {
+    /// The windows of each key, for all its routes (`None`) and for each limited route group.
+    windows: Mutex<HashMap<(KeyId, Option<String>), Window>>,
+}
+
+struct Window {
+    started_at: Instant,
+    requests: u32,
+}
+
+impl Window {
+    /// Returns how long to wait before the window accepts a new request.
+    fn retry_after(&self, now: Instant, limit: RequestsLimit) -> Option<Duration> {
+        let elapsed = now.duration_since(self.started_at);
+        let period = limit.per.duration();
+        (elapsed < period && self.requests >= limit.requests).then(|| period - elapsed)
+    }
+}
+
+impl RateLimiter {
+    /// Counts a request made with the key on a route of the group.
+    ///
+    /// Returns how long to wait before retrying when one of the limits is exceeded,
+    /// in which case the request isn't counted.
+    pub fn register(
+        &self,
+        uid: KeyId,
+        group: &str,
+        rate_limit: &RateLimit,
+    ) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let limits: Vec<_> = rate_limit
+            .limits_of(group)
+            .enumerate()
+            // the first limit is the one of all the routes
+            .map(|(i, limit)| ((uid, (i > 0).then(|| group.to_string())), limit))
+            .collect();
+
+        for (window_key, limit) in &limits {
+            if let Some(retry_after) =
+                windows.get(window_key).and_then(|window| window.retry_after(now, *limit))
+            {
+                return Err(retry_after);
+            }
+        }
+
+        for (window_key, limit) in limits {
+            let window =
+                windows.entry(window_key).or_insert(Window { started_at: now, requests: 0 });
+            if now.duration_since(window.started_at) >= limit.per.duration() {
+                *window = Window { started_at: now, requests: 0 };
+            }
+            window.requests += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the requests made with the key.
+    pub fn forget(&self, uid: KeyId) {
+        self.windows.lock().unwrap().retain(|(window_uid, _), _| *window_uid != uid);
+    }
+}
@@ -9,46 +9,133 @@ use std::str::FromStr;
 use hmac::{Hmac, Mac};
 use meilisearch_types::heed::{BoxedError, WithoutTls};
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::KeyId;
+use meilisearch_types::keys::{accepted_rotations, KeyId};
 use meilisearch_types::milli::heed;
-use meilisearch_types::milli::heed::types::{Bytes, DecodeIgnore, SerdeJson};
+use meilisearch_types::milli::heed::types::{Bytes, DecodeIgnore, SerdeJson, Str, Unit};
 use meilisearch_types::milli::heed::{Database, Env, EnvOpenOptions, RwTxn};
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use thiserror::Error;
 use time::OffsetDateTime;
 use uuid::fmt::Hyphenated;
 use uuid::Uuid;
 
+use super::encryption::{is_encrypted, EncryptionKey};
 use super::error::{AuthControllerError, Result};
 use super::{Action, Key};
 
 const AUTH_STORE_SIZE: usize = 1_073_741_824; //1GiB
 const KEY_DB_NAME: &str = "api-keys";
 const KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME: &str = "keyid-action-index-expiration";
+const ROTATED_MASTER_KEYS_DB_NAME: &str = "rotated-master-keys";
+const KEY_ROTATIONS_DB_NAME: &str = "key-rotations";
 
 #[derive(Clone)]
 pub struct HeedAuthStore {
     env: Env<WithoutTls>,
-    keys: Database<Bytes, SerdeJson<Key>>,
+    /// The JSON-encoded keys, encrypted when an encryption key is provided.
+    keys: Database<Bytes, Bytes>,
     action_keyid_index_expiration: Database<KeyIdActionCodec, SerdeJson<Option<OffsetDateTime>>>,
+    /// The SHA-256 digests of the master keys that have been rotated out.
+    rotated_master_keys: Database<Str, Unit>,
+    /// What the values of the keys are derived from, kept in plain text so that finding the key
+    /// of a value doesn't require decrypting every key.
+    key_rotations: Database<Bytes, SerdeJson<KeyRotations>>,
+    encryption_key: Option<EncryptionKey>,
 }
 
 pub fn open_auth_store_env(path: &Path) -> heed::Result<Env<WithoutTls>> {
     let options = EnvOpenOptions::new();
     let mut options = options.read_txn_without_tls();
     options.map_size(AUTH_STORE_SIZE); // 1GB
-    options.max_dbs(2);
+    options.max_dbs(4);
     unsafe { options.open(path) }
 }
 
 impl HeedAuthStore {
-    pub fn new(env: Env<WithoutTls>) -> Result<Self> {
+    pub fn new(env: Env<WithoutTls>, encryption_key: Option<EncryptionKey>) -> Result<Self> {
         let mut wtxn = env.write_txn()?;
         let keys = env.create_database(&mut wtxn, Some(KEY_DB_NAME))?;
         let action_keyid_index_expiration =
             env.create_database(&mut wtxn, Some(KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME))?;
+        let rotated_master_keys =
+            env.create_database(&mut wtxn, Some(ROTATED_MASTER_KEYS_DB_NAME))?;
+        let key_rotations = env.create_database(&mut wtxn, Some(KEY_ROTATIONS_DB_NAME))?;
+        let store = Self {
+            env,
+            keys,
+            action_keyid_index_expiration,
+            rotated_master_keys,
+            key_rotations,
+            encryption_key,
+        };
+        store.upgrade_stored_keys(&mut wtxn)?;
         wtxn.commit()?;
-        Ok(Self { env, keys, action_keyid_index_expiration })
+        Ok(store)
+    }
+
+    /// Encrypts the keys stored before an encryption key was provided, and indexes the rotations
+    /// of the keys stored before they were indexed.
+    ///
+    /// Every key is decoded on the way, so that a missing or wrong encryption key is reported
+    /// at launch rather than on the first request.
+    fn upgrade_stored_keys(&self, wtxn: &mut RwTxn) -> Result<()> {
+        let mut plaintext_keys = Vec::new();
+        let mut unindexed_keys = Vec::new();
+        for result in self.keys.iter(wtxn)? {
+            let (uid, bytes) = result?;
+            let key = self.decode_key(bytes)?;
+            if self.key_rotations.get(wtxn, uid)?.is_none() {
+                unindexed_keys.push((uid.to_vec(), KeyRotations::of(&key)));
+            }
+            if !is_encrypted(bytes) {
+                plaintext_keys.push((uid.to_vec(), key));
+            }
+        }
+
+        for (uid, rotations) in unindexed_keys {
+            self.key_rotations.put(wtxn, &uid, &rotations)?;
+        }
+
+        if self.encryption_key.is_some() {
+            for (uid, key) in plaintext_keys {
+                self.keys.put(wtxn, &uid, &self.encode_key(&key)?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_key(&self, key: &Key) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(key)?;
+        match &self.encryption_key {
+            Some(encryption_key) => encryption_key.encrypt(&json),
+            None => Ok(json),
+        }
+    }
+
+    fn decode_key(&self, bytes: &[u8]) -> Result<Key> {
+        if is_encrypted(bytes) {
+            let encryption_key =
+                self.encryption_key.as_ref().ok_or(AuthControllerError::MissingEncryptionKey)?;
+            Ok(serde_json::from_slice(&encryption_key.decrypt(bytes)?)?)
+        } else {
+            Ok(serde_json::from_slice(bytes)?)
+        }
+    }
+
+    /// Return `true` if the master key has been rotated out.
+    pub fn is_rotated_master_key(&self, master_key: &str) -> Result<bool> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.rotated_master_keys.get(&rtxn, &sha256_digest(master_key))?.is_some())
+    }
+
+    /// Remember that the master key has been rotated out, so that it can't be used again.
+    pub fn put_rotated_master_key(&self, master_key: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.rotated_master_keys.put(&mut wtxn, &sha256_digest(master_key), &())?;
+        wtxn.commit()?;
+        Ok(())
     }
 
     /// Return `Ok(())` if the auth store is able to access one of its database.
@@ -78,7 +165,8 @@ impl HeedAuthStore {
         let uid = key.uid;
         let mut wtxn = self.env.write_txn()?;
 
-        self.keys.put(&mut wtxn, uid.as_bytes(), &key)?;
+        self.keys.put(&mut wtxn, uid.as_bytes(), &self.encode_key(&key)?)?;
+        self.key_rotations.put(&mut wtxn, uid.as_bytes(), &KeyRotations::of(&key))?;
 
         // delete key from inverted database before refilling it.
         self.delete_key_from_inverted_db(&mut wtxn, &uid)?;
@@ -111,10 +199,10 @@ impl HeedAuthStore {
                     actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
                 }
                 Action::DumpsAll => {
-                    actions.insert(Action::DumpsCreate);
+                    actions.extend([Action::DumpsCreate, Action::DumpsImport]);
                 }
                 Action::SnapshotsAll => {
-                    actions.insert(Action::SnapshotsCreate);
+                    actions.extend([Action::SnapshotsCreate, Action::SnapshotsRestore]);
                 }
                 Action::TasksAll => {
                     actions.extend([Action::TasksGet, Action::TasksDelete, Action::TasksCancel]);
@@ -155,7 +243,7 @@ impl HeedAuthStore {
 
     pub fn get_api_key(&self, uid: Uuid) -> Result<Option<Key>> {
         let rtxn = self.env.read_txn()?;
-        self.keys.get(&rtxn, uid.as_bytes()).map_err(|e| e.into())
+        self.keys.get(&rtxn, uid.as_bytes())?.map(|bytes| self.decode_key(bytes)).transpose()
     }
 
     pub fn get_uid_from_encoded_key(
@@ -164,21 +252,19 @@ impl HeedAuthStore {
         master_key: &[u8],
     ) -> Result<Option<Uuid>> {
         let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
         let uid = self
-            .keys
-            .remap_data_type::<DecodeIgnore>()
+            .key_rotations
             .iter(&rtxn)?
-            .filter_map(|res| match res {
-                Ok((uid, _)) => {
-                    let (uid, _) = try_split_array_at(uid)?;
-                    let uid = Uuid::from_bytes(*uid);
-                    if generate_key_as_hexa(uid, master_key).as_bytes() == encoded_key {
-                        Some(uid)
-                    } else {
-                        None
-                    }
-                }
-                Err(_) => None,
+            .filter_map(|res| {
+                let (uid, key_rotations) = res.ok()?;
+                let uid = Uuid::from_slice(uid).ok()?;
+                key_rotations
+                    .accepted(now)
+                    .any(|rotations| {
+                        generate_key_as_hexa(uid, rotations, master_key).as_bytes() == encoded_key
+                    })
+                    .then_some(uid)
             })
             .next();
 
@@ -188,6 +274,7 @@ impl HeedAuthStore {
     pub fn delete_api_key(&self, uid: Uuid) -> Result<bool> {
         let mut wtxn = self.env.write_txn()?;
         let existing = self.keys.delete(&mut wtxn, uid.as_bytes())?;
+        self.key_rotations.delete(&mut wtxn, uid.as_bytes())?;
         self.delete_key_from_inverted_db(&mut wtxn, &uid)?;
         wtxn.commit()?;
 
@@ -197,6 +284,7 @@ impl HeedAuthStore {
     pub fn delete_all_keys(&self) -> Result<()> {
         let mut wtxn = self.env.write_txn()?;
         self.keys.clear(&mut wtxn)?;
+        self.key_rotations.clear(&mut wtxn)?;
         wtxn.commit()?;
         Ok(())
     }
@@ -205,8 +293,8 @@ impl HeedAuthStore {
         let mut list = Vec::new();
         let rtxn = self.env.read_txn()?;
         for result in self.keys.remap_key_type::<DecodeIgnore>().iter(&rtxn)? {
-            let (_, content) = result?;
-            list.push(content);
+            let (_, bytes) = result?;
+            list.push(self.decode_key(bytes)?);
         }
         list.sort_unstable_by_key(|k| Reverse(k.created_at));
         Ok(list)
@@ -271,6 +359,26 @@ impl HeedAuthStore {
     }
 }
 
+/// The number of rotations of a key and until when its previous value is accepted, which is
+/// all that is needed, with the uid and the master key, to derive the values of the key.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyRotations {
+    rotations: u32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    previous_key_expires_at: Option<OffsetDateTime>,
+}
+
+impl KeyRotations {
+    fn of(key: &Key) -> Self {
+        Self { rotations: key.rotations, previous_key_expires_at: key.previous_key_expires_at }
+    }
+
+    fn accepted(&self, now: OffsetDateTime) -> impl Iterator<Item = u32> {
+        accepted_rotations(self.rotations, self.previous_key_expires_at, now)
+    }
+}
+
 /// Codec allowing to retrieve the expiration date of an action,
 /// optionally on a specific index, for a given key.
 pub struct KeyIdActionCodec;
@@ -319,7 +427,7 @@ pub struct InvalidActionError {
     pub action_byte: u8,
 }
 
-pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
+pub fn generate_key_as_hexa(uid: Uuid, rotations: u32, master_key: &[u8]) -> String {
     // format uid as hyphenated allowing user to generate their own keys.
     let mut uid_buffer = [0; Hyphenated::LENGTH];
     let uid = uid.hyphenated().encode_lower(&mut uid_buffer);
@@ -327,11 +435,20 @@ pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
     // new_from_slice function never fail.
     let mut mac = Hmac::<Sha256>::new_from_slice(master_key).unwrap();
     mac.update(uid.as_bytes());
+    // the keys that were never rotated keep the value derived from their uid only.
+    if rotations > 0 {
+        mac.update(format!(":{rotations}").as_bytes());
+    }
 
     let result = mac.finalize();
     format!("{:x}", result.into_bytes())
 }
 
+/// Returns the hexadecimal SHA-256 digest of a secret, to compare it without storing it.
+pub fn sha256_digest(secret: &str) -> String {
+    format!("{:x}", <Sha256 as sha2::Digest>::digest(secret.as_bytes()))
+}
+
 /// Divides one slice into two at an index, returns `None` if mid is out of bounds.
 pub fn try_split_at<T>(slice: &[T], mid: usize) -> Option<(&[T], &[T])> {
     if mid <= slice.len() {
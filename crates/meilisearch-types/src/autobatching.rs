@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// The limits of the autobatches of an index, overriding the ones of the instance.
+///
+/// A limit left to `None` falls back to the one of the instance.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AutobatchingLimits {
+    /// The maximum number of tasks batched together.
+    #[serde(default)]
+    pub max_tasks: Option<usize>,
+    /// The maximum number of documents added, updated or deleted by the tasks batched together.
+    #[serde(default)]
+    pub max_documents: Option<u64>,
+    /// The maximum total size, in bytes, of the payloads of the tasks batched together.
+    #[serde(default)]
+    pub max_total_size: Option<u64>,
+    /// How long, in milliseconds, the document operations wait for more tasks to be batched
+    /// with them when none of the other limits is reached.
+    #[serde(default)]
+    pub max_wait_ms: Option<u64>,
+}
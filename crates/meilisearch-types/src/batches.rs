@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use milli::progress::ProgressView;
+use milli::vector::usage::EmbedderUsage;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use utoipa::ToSchema;
@@ -64,4 +65,10 @@ pub struct BatchStats {
     pub progress_trace: serde_json::Map<String, serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub write_channel_congestion: Option<serde_json::Map<String, serde_json::Value>>,
+    /// The peak memory of the process and the documents processed per second during the batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<serde_json::Map<String, serde_json::Value>>,
+    /// The requests sent to the embedders to index the documents of the batch, by embedder name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub embedder_usage: BTreeMap<String, EmbedderUsage>,
 }
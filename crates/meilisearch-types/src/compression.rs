@@ -9,13 +9,18 @@ use tar::{Archive, Builder};
 
 pub fn to_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
     let mut f = File::create(dest)?;
-    let gz_encoder = GzEncoder::new(&mut f, Compression::default());
+    write_tar_gz(src, &mut f)?;
+    f.flush()?;
+    Ok(())
+}
+
+/// Writes the content of the directory as a tar.gz archive and returns the writer.
+pub fn write_tar_gz<W: Write>(src: impl AsRef<Path>, writer: W) -> anyhow::Result<W> {
+    let gz_encoder = GzEncoder::new(writer, Compression::default());
     let mut tar_encoder = Builder::new(gz_encoder);
     tar_encoder.append_dir_all(".", src)?;
     let gz_encoder = tar_encoder.into_inner()?;
-    gz_encoder.finish()?;
-    f.flush()?;
-    Ok(())
+    Ok(gz_encoder.finish()?)
 }
 
 pub fn from_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
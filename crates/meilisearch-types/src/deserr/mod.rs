@@ -8,11 +8,11 @@ use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, Value
 
 use crate::error::deserr_codes::*;
 use crate::error::{
-    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
-    ParseOffsetDateTimeError,
+    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidRestoreDateError,
+    InvalidTaskDateError, InvalidTaskDurationError, ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
-use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
+use crate::tasks::{ParseTaskKindError, ParseTaskLabelError, ParseTaskStatusError};
 
 pub mod query_params;
 
@@ -150,6 +150,8 @@ make_missing_field_convenience_builder!(MissingApiKeyActions, missing_api_key_ac
 make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_expires_at);
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
+make_missing_field_convenience_builder!(MissingRestoreAt, missing_restore_at);
+make_missing_field_convenience_builder!(MissingRestoreDumpUid, missing_restore_dump_uid);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
@@ -188,9 +190,12 @@ merge_with_error_impl_take_error_message!(DeserrParseIntError);
 merge_with_error_impl_take_error_message!(DeserrParseBoolError);
 merge_with_error_impl_take_error_message!(uuid::Error);
 merge_with_error_impl_take_error_message!(InvalidTaskDateError);
+merge_with_error_impl_take_error_message!(InvalidTaskDurationError);
 merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
+merge_with_error_impl_take_error_message!(InvalidRestoreDateError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
+merge_with_error_impl_take_error_message!(ParseTaskLabelError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
 merge_with_error_impl_take_error_message!(InvalidMultiSearchWeight);
 merge_with_error_impl_take_error_message!(InvalidNetworkUrl);
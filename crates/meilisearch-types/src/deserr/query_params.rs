@@ -19,7 +19,7 @@ use deserr::{DeserializeError, Deserr, MergeWithError, ValueKind};
 
 use super::{DeserrParseBoolError, DeserrParseIntError};
 use crate::index_uid::IndexUid;
-use crate::tasks::{Kind, Status};
+use crate::tasks::{Kind, Status, TaskLabel};
 
 /// A wrapper type indicating that the inner value should be
 /// deserialised from a query parameter string.
@@ -87,6 +87,7 @@ macro_rules! impl_from_query_param_from_str {
 }
 impl_from_query_param_from_str!(Kind);
 impl_from_query_param_from_str!(Status);
+impl_from_query_param_from_str!(TaskLabel);
 impl_from_query_param_from_str!(IndexUid);
 
 /// Implement `FromQueryParameter` for the given type using its `FromStr`
@@ -1,8 +1,9 @@
 use std::fmt::{self, Debug, Display};
 use std::fs::File;
-use std::io::{self, BufWriter};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom};
 use std::marker::PhantomData;
 
+use apache_avro::schema::{Schema, SchemaKind};
 use bumpalo::Bump;
 use bumparaw_collections::RawMap;
 use memmap2::Mmap;
@@ -24,6 +25,7 @@ pub enum PayloadType {
     Ndjson,
     Json,
     Csv { delimiter: u8 },
+    Avro,
 }
 
 impl fmt::Display for PayloadType {
@@ -32,6 +34,7 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            PayloadType::Avro => f.write_str("avro"),
         }
     }
 }
@@ -40,6 +43,17 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    MalformedAvro(AvroError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AvroError {
+    #[error(transparent)]
+    Avro(#[from] apache_avro::Error),
+    #[error("the documents must be Avro records, but the schema of the payload is a `{0:?}`")]
+    NotARecord(SchemaKind),
+    #[error("records framed with a schema registry header (schema id {0}) are not supported, send an Avro object container file embedding its schema instead")]
+    SchemaRegistryFraming(u32),
 }
 
 impl Display for DocumentFormatError {
@@ -78,6 +92,9 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::MalformedAvro(e) => {
+                write!(f, "The `{}` payload provided is malformed: `{}`.", PayloadType::Avro, e)
+            }
         }
     }
 }
@@ -113,6 +130,12 @@ impl From<(PayloadType, csv::Error)> for DocumentFormatError {
     }
 }
 
+impl From<AvroError> for DocumentFormatError {
+    fn from(error: AvroError) -> Self {
+        Self::MalformedAvro(error)
+    }
+}
+
 impl From<io::Error> for DocumentFormatError {
     fn from(error: io::Error) -> Self {
         Self::Io(error)
@@ -124,6 +147,7 @@ impl ErrorCode for DocumentFormatError {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
             DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::MalformedAvro(_) => Code::MalformedPayload,
         }
     }
 }
@@ -251,6 +275,40 @@ pub fn read_json(input: &File, output: impl io::Write) -> Result<u64> {
     }
 }
 
+/// Reads an Avro object container file and write its records in NDJSON in a file.
+///
+/// The schema of the container must be a record, each record being a document.
+pub fn read_avro(mut input: &File, output: impl io::Write) -> Result<u64> {
+    // Records coming from a Kafka topic are framed with a zero byte followed by the id of
+    // their schema in a registry, whereas object container files start with the `Obj` magic.
+    let mut header = [0; 5];
+    if input.read_exact(&mut header).is_ok() && header[0] == 0 {
+        let schema_id = u32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+        return Err(AvroError::SchemaRegistryFraming(schema_id).into());
+    }
+    input.seek(SeekFrom::Start(0))?;
+
+    let reader = apache_avro::Reader::new(BufReader::new(input)).map_err(AvroError::from)?;
+    if !matches!(reader.writer_schema(), Schema::Record(_)) {
+        return Err(AvroError::NotARecord(SchemaKind::from(reader.writer_schema())).into());
+    }
+
+    let mut out = BufWriter::new(output);
+    let mut count = 0;
+    for value in reader {
+        let value = value.map_err(AvroError::from)?;
+        let object = Value::try_from(value).map_err(AvroError::from)?;
+        to_writer(&mut out, &object)
+            .map_err(|e| DocumentFormatError::from((PayloadType::Avro, e)))?;
+        count += 1;
+    }
+
+    match out.into_inner() {
+        Ok(_) => Ok(count),
+        Err(ie) => Err(DocumentFormatError::Io(ie.into_error())),
+    }
+}
+
 /// Reads NDJSON from file and checks it.
 pub fn read_ndjson(input: &File) -> Result<u64> {
     // We memory map to be able to deserialize into a RawMap that
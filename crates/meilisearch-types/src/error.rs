@@ -28,6 +28,33 @@ pub struct ResponseError {
     /// A link to the documentation about this specific error.
     #[serde(rename = "link")]
     error_link: String,
+    /// Where the error is located in the request, only available for filter errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<ErrorDetails>,
+}
+
+/// The location of an error in a filter and the nearest valid alternatives.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct ErrorDetails {
+    /// The line on which the error is located, starting at 1.
+    pub line: u32,
+    /// The column of the first character of the offending token, starting at 1.
+    pub column: usize,
+    /// The offending token.
+    pub token: String,
+    /// The line on which the error is located with carets pointing at the offending token.
+    pub snippet: String,
+    /// The nearest valid operators, attributes or delimiters.
+    pub suggestions: Vec<String>,
+}
+
+impl From<milli::FilterErrorDetails> for ErrorDetails {
+    fn from(details: milli::FilterErrorDetails) -> Self {
+        let milli::FilterErrorDetails { line, column, token, snippet, suggestions } = details;
+        Self { line, column, token, snippet, suggestions }
+    }
 }
 
 impl ResponseError {
@@ -41,8 +68,19 @@ impl ResponseError {
             error_code: code.name(),
             error_type: code.type_(),
             error_link: code.url(),
+            details: None,
         }
     }
+
+    pub fn with_details(mut self, details: Option<ErrorDetails>) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// The name of the code of the error, e.g. `invalid_document_fields`.
+    pub fn error_code(&self) -> &str {
+        &self.error_code
+    }
 }
 
 impl fmt::Display for ResponseError {
@@ -58,7 +96,7 @@ where
     T: std::error::Error + ErrorCode,
 {
     fn from(other: T) -> Self {
-        Self::from_msg(other.to_string(), other.error_code())
+        Self::from_msg(other.to_string(), other.error_code()).with_details(other.error_details())
     }
 }
 
@@ -68,7 +106,7 @@ impl aweb::error::ResponseError for ResponseError {
         let mut builder = HttpResponseBuilder::new(self.status_code());
         builder.content_type("application/json");
 
-        if self.code == StatusCode::SERVICE_UNAVAILABLE {
+        if matches!(self.code, StatusCode::SERVICE_UNAVAILABLE | StatusCode::TOO_MANY_REQUESTS) {
             builder.insert_header((header::RETRY_AFTER, "10"));
         }
 
@@ -102,6 +140,11 @@ pub trait ErrorCode {
     fn error_type(&self) -> String {
         self.error_code().type_()
     }
+
+    /// returns the location of the error in the request, if any
+    fn error_details(&self) -> Option<ErrorDetails> {
+        None
+    }
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -196,11 +239,18 @@ macro_rules! make_error_codes {
 make_error_codes! {
 ApiKeyAlreadyExists                   , InvalidRequest       , CONFLICT ;
 ApiKeyNotFound                        , InvalidRequest       , NOT_FOUND ;
+ApiKeyQuotaExceeded                   , Auth                 , FORBIDDEN ;
 BadParameter                          , InvalidRequest       , BAD_REQUEST;
 BadRequest                            , InvalidRequest       , BAD_REQUEST;
 DatabaseSizeLimitReached              , Internal             , INTERNAL_SERVER_ERROR;
+DiskWatermarkReached                  , System               , TOO_MANY_REQUESTS ;
+DocumentAlreadyExists                 , InvalidRequest       , CONFLICT ;
 DocumentNotFound                      , InvalidRequest       , NOT_FOUND;
+DocumentRevisionNotFound              , InvalidRequest       , NOT_FOUND;
+DocumentSchemaMismatch                , InvalidRequest       , BAD_REQUEST ;
+DocumentAttachmentExtractionFailed    , InvalidRequest       , BAD_REQUEST ;
 DumpAlreadyProcessing                 , InvalidRequest       , CONFLICT;
+DumpImportFailed                      , InvalidRequest       , BAD_REQUEST ;
 DumpNotFound                          , InvalidRequest       , NOT_FOUND;
 DumpProcessFailed                     , Internal             , INTERNAL_SERVER_ERROR;
 DuplicateIndexFound                   , InvalidRequest       , BAD_REQUEST;
@@ -220,15 +270,22 @@ IndexNotFound                         , InvalidRequest       , NOT_FOUND;
 IndexPrimaryKeyAlreadyExists          , InvalidRequest       , BAD_REQUEST ;
 IndexPrimaryKeyMultipleCandidatesFound, InvalidRequest       , BAD_REQUEST;
 IndexPrimaryKeyNoCandidateFound       , InvalidRequest       , BAD_REQUEST ;
+IndexRestoreFailed                    , InvalidRequest       , BAD_REQUEST ;
+IndexSourceNotFound                   , InvalidRequest       , NOT_FOUND ;
 Internal                              , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidApiKey                         , Auth                 , FORBIDDEN ;
 InvalidApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyDescription              , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyImport                   , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyOverlapSeconds           , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyQuotas                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyFilter                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyRateLimit                , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
@@ -238,19 +295,34 @@ MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentEditionFunction        , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentGeojsonField           , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorDimensions               , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentIds                    , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentImportUrl              , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentJsonPatch              , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentOnConflict             , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentSort                   , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpImportUrl                  , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpIndexRenames               , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpIndexUids                  , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpSkipKeys                   , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpSkipTasks                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchEmbedder                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarEmbedder                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexAliases                   , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexChangesSince              , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexSourceMode                , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexSourceRefreshInterval     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
+InvalidMasterKey                      , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacets              , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacetsByIndex       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacetOrder          , InvalidRequest       , BAD_REQUEST ;
@@ -268,6 +340,10 @@ InvalidNetworkRemotes                 , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkSelf                    , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkSearchApiKey            , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkUrl                     , InvalidRequest       , BAD_REQUEST ;
+InvalidRestoreAt                      , InvalidRequest       , BAD_REQUEST ;
+InvalidRestoreDumpUid                 , InvalidRequest       , BAD_REQUEST ;
+InvalidRestoreIndexUids               , InvalidRequest       , BAD_REQUEST ;
+InvalidRestoreSnapshot                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
@@ -279,11 +355,22 @@ InvalidSimilarRankingScoreThreshold   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchCropStrategy             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHybridFusion             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSearchAccuracy           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchIgnoreTerms              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDebugTimings             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExhaustiveTotalHits      , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExhaustiveFacetCount     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGeoGridPrecision         , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSensitiveMatching        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRerankBudget             , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchExhaustiveFacetCount, InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+InvalidFacetSearchFacetQueryMatching  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarId                      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarFilter                  , InvalidRequest       , BAD_REQUEST ;
@@ -297,11 +384,14 @@ InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarQ                       , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowGraphemeOffsets      , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowMatchedTerms         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
@@ -313,6 +403,14 @@ InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSensitiveMatching      , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsReranker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsFlattening             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsIngestFunction         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDocumentSchema         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSkipUnchangedDocuments , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsAttachments            , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsMaxDocumentRevisions   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
@@ -331,6 +429,7 @@ InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
+InvalidSwapExecuteAt                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterFinishedAt            , InvalidRequest       , BAD_REQUEST ;
@@ -339,8 +438,16 @@ InvalidTaskBeforeEnqueuedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeFinishedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeStartedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskCanceledBy                 , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskDependsOn                  , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskErrorCodes                 , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskFrom                       , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskLabels                     , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskLogLimit                   , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskLogSince                   , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskLogUntil                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskLimit                      , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskMaxDuration                , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskMinDuration                , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskReverse                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
@@ -361,11 +468,15 @@ MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingNetworkUrl                     , InvalidRequest       , BAD_REQUEST ;
 MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
+MissingRestoreAt                      , InvalidRequest       , BAD_REQUEST ;
+MissingRestoreDumpUid                 , InvalidRequest       , BAD_REQUEST ;
 MissingSearchHybrid                   , InvalidRequest       , BAD_REQUEST ;
 MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
+PointInTimeRestoreFailed              , InvalidRequest       , BAD_REQUEST ;
+ReadOnlyMode                          , System               , SERVICE_UNAVAILABLE ;
 RemoteBadResponse                     , System               , BAD_GATEWAY ;
 RemoteBadRequest                      , InvalidRequest       , BAD_REQUEST ;
 RemoteCouldNotSendRequest             , System               , BAD_GATEWAY ;
@@ -373,8 +484,12 @@ RemoteInvalidApiKey                   , Auth                 , FORBIDDEN ;
 RemoteRemoteError                     , System               , BAD_GATEWAY ;
 RemoteTimeout                         , System               , BAD_GATEWAY ;
 TooManySearchRequests                 , System               , SERVICE_UNAVAILABLE ;
+TooManyEnqueuedTasks                  , System               , TOO_MANY_REQUESTS ;
+TooManyApiKeyRequests                 , Auth                 , TOO_MANY_REQUESTS ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
+TaskDependencyFailed                  , InvalidRequest       , BAD_REQUEST ;
 TaskFileNotFound                      , InvalidRequest       , NOT_FOUND ;
+TaskLogReplayFailed                   , InvalidRequest       , BAD_REQUEST ;
 BatchNotFound                         , InvalidRequest       , NOT_FOUND ;
 TooManyOpenFiles                      , System               , UNPROCESSABLE_ENTITY ;
 TooManyVectors                        , InvalidRequest       , BAD_REQUEST ;
@@ -387,6 +502,7 @@ VectorEmbeddingError                  , InvalidRequest       , BAD_REQUEST ;
 NotFoundSimilarId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentEditionContext         , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentEditionFunctionFilter  , InvalidRequest       , BAD_REQUEST ;
+DocumentImportUrlFailed               , InvalidRequest       , BAD_REQUEST ;
 EditDocumentsByFunctionError          , InvalidRequest       , BAD_REQUEST
 }
 
@@ -414,7 +530,7 @@ impl ErrorCode for milli::Error {
                     UserError::NoSpaceLeftOnDevice => Code::NoSpaceLeftOnDevice,
                     UserError::MaxDatabaseSizeReached => Code::DatabaseSizeLimitReached,
                     UserError::AttributeLimitReached => Code::MaxFieldsLimitExceeded,
-                    UserError::InvalidFilter(_) => Code::InvalidSearchFilter,
+                    UserError::InvalidFilter(..) => Code::InvalidSearchFilter,
                     UserError::InvalidFilterExpression(..) => Code::InvalidSearchFilter,
                     UserError::FilterOperatorNotAllowed { .. } => Code::InvalidSearchFilter,
                     UserError::MissingDocumentId { .. } => Code::MissingDocumentId,
@@ -427,9 +543,13 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidOpenAiModel { .. }
                     | UserError::InvalidOpenAiModelDimensions { .. }
                     | UserError::InvalidOpenAiModelDimensionsMax { .. }
+                    | UserError::InvalidCohereModelDimensions { .. }
+                    | UserError::InvalidBedrockModel { .. }
+                    | UserError::InvalidBedrockApiKey { .. }
                     | UserError::InvalidSettingsDimensions { .. }
                     | UserError::InvalidUrl { .. }
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
+                    | UserError::InvalidSettingsDocumentTemplateChunkBytes { .. }
                     | UserError::InvalidPrompt(_)
                     | UserError::InvalidDisableBinaryQuantization { .. }
                     | UserError::InvalidSourceForNested { .. }
@@ -437,11 +557,23 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidSettingsEmbedder { .. } => Code::InvalidSettingsEmbedders,
                     UserError::TooManyEmbedders(_) => Code::InvalidSettingsEmbedders,
                     UserError::InvalidPromptForEmbeddings(..) => Code::InvalidSettingsEmbedders,
+                    UserError::MissingRerankerUrl
+                    | UserError::InvalidRerankerUrl { .. }
+                    | UserError::InvalidRerankerDocumentTemplate(_)
+                    | UserError::InvalidRerankerDocumentTemplateMaxBytes => {
+                        Code::InvalidSettingsReranker
+                    }
                     UserError::NoPrimaryKeyCandidateFound => Code::IndexPrimaryKeyNoCandidateFound,
                     UserError::MultiplePrimaryKeyCandidatesFound { .. } => {
                         Code::IndexPrimaryKeyMultipleCandidatesFound
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
+                    UserError::FlatteningCannotBeChanged => Code::InvalidSettingsFlattening,
+                    UserError::InvalidIngestFunction(_) => Code::InvalidSettingsIngestFunction,
+                    UserError::InvalidDocumentSchema(_) => Code::InvalidSettingsDocumentSchema,
+                    UserError::InvalidAttachmentsExtractionUrl { .. } => {
+                        Code::InvalidSettingsAttachments
+                    }
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
@@ -454,6 +586,7 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidGeoJsonField { .. } => Code::InvalidDocumentGeojsonField,
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
@@ -473,10 +606,23 @@ impl ErrorCode for milli::Error {
                     | UserError::DocumentEditionCompilationError(_) => {
                         Code::EditDocumentsByFunctionError
                     }
+                    UserError::InvalidDocumentJsonPatch { .. } => Code::InvalidDocumentJsonPatch,
+                    UserError::DocumentDoesNotMatchSchema { .. } => Code::DocumentSchemaMismatch,
+                    UserError::InvalidAttachment { .. } => Code::DocumentAttachmentExtractionFailed,
+                    UserError::DocumentAlreadyExists { .. } => Code::DocumentAlreadyExists,
                 }
             }
         }
     }
+
+    fn error_details(&self) -> Option<ErrorDetails> {
+        match self {
+            milli::Error::UserError(milli::UserError::InvalidFilter(_, details)) => {
+                Some(details.clone().into())
+            }
+            _ => None,
+        }
+    }
 }
 
 impl ErrorCode for file_store::Error {
@@ -520,7 +666,7 @@ impl ErrorCode for io::Error {
     }
 }
 
-/// Deserialization when `deserr` cannot parse an API key date.
+/// Deserialization when `deserr` cannot parse a date in the future, like the expiration date of an API key.
 #[derive(Debug)]
 pub struct ParseOffsetDateTimeError(pub String);
 impl fmt::Display for ParseOffsetDateTimeError {
@@ -538,6 +684,24 @@ impl std::fmt::Display for InvalidTaskDateError {
     }
 }
 
+/// Deserialization when `deserr` cannot parse a task duration.
+#[derive(Debug)]
+pub struct InvalidTaskDurationError(pub String);
+impl std::fmt::Display for InvalidTaskDurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is an invalid duration. It should follow the ISO 8601 duration format, e.g. `PT10M`.", self.0)
+    }
+}
+
+/// Deserialization when `deserr` cannot parse the date the indexes are restored to.
+#[derive(Debug)]
+pub struct InvalidRestoreDateError(pub String);
+impl std::fmt::Display for InvalidRestoreDateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is an invalid restore date. It should follow the RFC 3339 date-time format and be in the past.", self.0)
+    }
+}
+
 /// Deserialization error when `deserr` cannot parse a String
 /// into a bool.
 #[derive(Debug)]
@@ -567,6 +731,15 @@ impl fmt::Display for deserr_codes::InvalidSearchSemanticRatio {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchSearchAccuracy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `searchAccuracy` is invalid, expected a float greater than `0.0` and at most `10.0`."
+        )
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidMultiSearchWeight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "the value of `weight` is invalid, expected a positive float (>= 0.0).")
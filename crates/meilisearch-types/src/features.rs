@@ -12,8 +12,12 @@ pub struct RuntimeTogglableFeatures {
     pub network: bool,
     pub get_task_documents_route: bool,
     pub composite_embedders: bool,
+    pub read_only: bool,
 }
 
+/// The indexes searched through each alias, by alias.
+pub type IndexAliases = BTreeMap<String, Vec<String>>;
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct InstanceTogglableFeatures {
     pub metrics: bool,
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::convert::Infallible;
 use std::hash::Hash;
 use std::str::FromStr;
@@ -6,6 +7,7 @@ use deserr::{DeserializeError, Deserr, MergeWithError, ValuePointerRef};
 use enum_iterator::Sequence;
 use milli::update::Setting;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use time::format_description::well_known::Rfc3339;
 use time::macros::{format_description, time};
 use time::{Date, OffsetDateTime, PrimitiveDateTime};
@@ -58,13 +60,35 @@ pub struct CreateApiKey {
     #[schema(value_type = Vec<String>, example = json!(["products"]))]
     pub indexes: Vec<IndexUidPattern>,
     /// Represent the expiration date and time as RFC 3339 format. `null` equals to no expiration time.
-    #[deserr(error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Option<String>) = parse_expiration_date -> ParseOffsetDateTimeError, missing_field_error = DeserrJsonError::missing_api_key_expires_at)]
+    #[deserr(error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Option<String>) = parse_future_date -> ParseOffsetDateTimeError, missing_field_error = DeserrJsonError::missing_api_key_expires_at)]
     pub expires_at: Option<OffsetDateTime>,
+    /// The maximum number of requests the key can make in a period. `null` for no limit.
+    #[schema(value_type = Option<RateLimit>, example = json!({ "requests": 100, "per": "second", "routes": { "documents": { "requests": 10, "per": "minute" } } }))]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimit>)]
+    pub rate_limit: Option<RateLimit>,
+    /// The limits of the resources the tasks registered with the key can use. `null` for no limit.
+    #[schema(value_type = Option<KeyQuotas>, example = json!({ "maxIndexes": 10, "maxDocuments": 100000, "maxStorageBytes": 1073741824 }))]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyQuotas>)]
+    pub quotas: Option<KeyQuotas>,
+    /// A filter expression applied to every search and document read made with the key. `null` for no filter.
+    #[schema(value_type = Option<Value>, example = "tenant = acme")]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyFilter>)]
+    pub filter: Option<Value>,
 }
 
 impl CreateApiKey {
     pub fn to_key(self) -> Key {
-        let CreateApiKey { description, name, uid, actions, indexes, expires_at } = self;
+        let CreateApiKey {
+            description,
+            name,
+            uid,
+            actions,
+            indexes,
+            expires_at,
+            rate_limit,
+            quotas,
+            filter,
+        } = self;
         let now = OffsetDateTime::now_utc();
         Key {
             description,
@@ -73,8 +97,13 @@ impl CreateApiKey {
             actions,
             indexes,
             expires_at,
+            rate_limit,
+            quotas,
+            filter,
             created_at: now,
             updated_at: now,
+            rotations: 0,
+            previous_key_expires_at: None,
         }
     }
 }
@@ -109,6 +138,156 @@ pub struct PatchApiKey {
     #[deserr(default, error = DeserrJsonError<InvalidApiKeyName>)]
     #[schema(value_type = Option<String>, example = "Indexing Products API key")]
     pub name: Setting<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimit>)]
+    #[schema(value_type = Option<RateLimit>, example = json!({ "requests": 100, "per": "second" }))]
+    pub rate_limit: Setting<RateLimit>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyQuotas>)]
+    #[schema(value_type = Option<KeyQuotas>, example = json!({ "maxDocuments": 100000 }))]
+    pub quotas: Setting<KeyQuotas>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyFilter>)]
+    #[schema(value_type = Option<Value>, example = "tenant = acme")]
+    pub filter: Setting<Value>,
+}
+
+/// The limits of the resources the tasks registered with an API key can use.
+///
+/// The usage of a key is tracked by the scheduler as its tasks succeed, and the new tasks
+/// that would exceed one of the quotas are refused. A quota left to `null` is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyQuotas>, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct KeyQuotas {
+    /// The maximum number of indexes created with the key that can exist at the same time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>, example = 10)]
+    pub max_indexes: Option<u64>,
+    /// The maximum number of documents that can be added with the key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>, example = 100000)]
+    pub max_documents: Option<u64>,
+    /// The maximum size on disk of the indexes created with the key, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>, example = 1073741824)]
+    pub max_storage_bytes: Option<u64>,
+}
+
+/// The maximum number of requests an API key can make in a period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyRateLimit>, rename_all = camelCase, deny_unknown_fields, validate = validate_rate_limit -> DeserrJsonError<InvalidApiKeyRateLimit>)]
+#[schema(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// The number of requests accepted in each period, all the routes together.
+    #[schema(example = 100)]
+    pub requests: u32,
+    #[schema(example = "second")]
+    pub per: RateLimitPeriod,
+    /// Stricter limits applied to the routes of a group, e.g. `search` or `documents`.
+    /// The group of a route is the prefix of the action it requires.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[deserr(default)]
+    #[schema(example = json!({ "documents": { "requests": 10, "per": "minute" } }))]
+    pub routes: BTreeMap<String, RequestsLimit>,
+}
+
+impl RateLimit {
+    /// The limits applied to the requests of the route group, the global one first.
+    pub fn limits_of(&self, group: &str) -> impl Iterator<Item = RequestsLimit> + '_ {
+        let global = RequestsLimit { requests: self.requests, per: self.per };
+        std::iter::once(global).chain(self.routes.get(group).copied())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyRateLimit>, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct RequestsLimit {
+    /// The number of requests accepted in each period.
+    pub requests: u32,
+    pub per: RateLimitPeriod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum RateLimitPeriod {
+    Second,
+    Minute,
+}
+
+impl RateLimitPeriod {
+    pub fn duration(self) -> std::time::Duration {
+        match self {
+            RateLimitPeriod::Second => std::time::Duration::from_secs(1),
+            RateLimitPeriod::Minute => std::time::Duration::from_secs(60),
+        }
+    }
+}
+
+fn validate_rate_limit<E: DeserializeError>(
+    rate_limit: RateLimit,
+    location: ValuePointerRef,
+) -> Result<RateLimit, E> {
+    let error = |msg: String| {
+        Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            deserr::ErrorKind::Unexpected { msg },
+            location,
+        )))
+    };
+
+    if rate_limit.requests == 0 {
+        return error("`requests` must be greater than zero.".to_string());
+    }
+    for (group, limit) in &rate_limit.routes {
+        if !Action::route_groups().any(|known| known == group) {
+            let known: Vec<_> = Action::route_groups().collect();
+            return error(format!(
+                "Unknown route group `{group}`: expected one of {}.",
+                known.iter().map(|group| format!("`{group}`")).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        if limit.requests == 0 {
+            return error(format!("`routes.{group}.requests` must be greater than zero."));
+        }
+    }
+    Ok(rate_limit)
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct RotateApiKey {
+    /// For how many seconds the value of the key before the rotation is still accepted. `null` to stop accepting it immediately.
+    #[schema(value_type = Option<u64>, example = 3600)]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyOverlapSeconds>)]
+    pub overlap_seconds: Option<u64>,
+}
+
+/// A key as exported to be imported on another instance, in the format of the dumps.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExportedApiKey {
+    #[serde(flatten)]
+    pub key: Key,
+    /// The SHA-256 digest of the value of the key, so that the importing instance can make sure
+    /// it derives the same value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_sha256: Option<String>,
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct RotateMasterKey {
+    /// The new master key. Every API key gets a new value derived from it.
+    #[schema(example = "a-new-master-key-of-at-least-16-bytes")]
+    #[deserr(error = DeserrJsonError<InvalidMasterKey>)]
+    pub master_key: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -122,13 +301,50 @@ pub struct Key {
     pub indexes: Vec<IndexUidPattern>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub expires_at: Option<OffsetDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quotas: Option<KeyQuotas>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
+    /// The number of times the value of the key was rotated, the value is derived from it.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub rotations: u32,
+    /// Until when the value of the key before its last rotation is still accepted.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub previous_key_expires_at: Option<OffsetDateTime>,
+}
+
+fn is_zero(rotations: &u32) -> bool {
+    *rotations == 0
+}
+
+/// The rotations of a key whose value is still accepted at `now`, the current one first.
+pub fn accepted_rotations(
+    rotations: u32,
+    previous_key_expires_at: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+) -> impl Iterator<Item = u32> {
+    let previous = rotations
+        .checked_sub(1)
+        .filter(|_| previous_key_expires_at.is_some_and(|expires_at| now < expires_at));
+    std::iter::once(rotations).chain(previous)
 }
 
 impl Key {
+    /// The rotations whose value is still accepted at `now`, the current one first.
+    pub fn accepted_rotations(&self, now: OffsetDateTime) -> impl Iterator<Item = u32> {
+        accepted_rotations(self.rotations, self.previous_key_expires_at, now)
+    }
+
     pub fn default_admin() -> Self {
         let now = OffsetDateTime::now_utc();
         let uid = Uuid::new_v4();
@@ -139,8 +355,13 @@ impl Key {
             actions: vec![Action::All],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limit: None,
+            quotas: None,
+            filter: None,
             created_at: now,
             updated_at: now,
+            rotations: 0,
+            previous_key_expires_at: None,
         }
     }
 
@@ -154,13 +375,19 @@ impl Key {
             actions: vec![Action::Search],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limit: None,
+            quotas: None,
+            filter: None,
             created_at: now,
             updated_at: now,
+            rotations: 0,
+            previous_key_expires_at: None,
         }
     }
 }
 
-fn parse_expiration_date(
+/// Parses an optional date that must be in the future.
+pub fn parse_future_date(
     string: Option<String>,
 ) -> std::result::Result<Option<OffsetDateTime>, ParseOffsetDateTimeError> {
     let Some(string) = string else { return Ok(None) };
@@ -308,6 +535,13 @@ pub enum Action {
     #[serde(rename = "network.update")]
     #[deserr(rename = "network.update")]
     NetworkUpdate,
+    // Declared last so that the representation of the other actions doesn't change.
+    #[serde(rename = "dumps.import")]
+    #[deserr(rename = "dumps.import")]
+    DumpsImport,
+    #[serde(rename = "snapshots.restore")]
+    #[deserr(rename = "snapshots.restore")]
+    SnapshotsRestore,
 }
 
 impl Action {
@@ -349,6 +583,8 @@ impl Action {
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
             NETWORK_GET => Some(Self::NetworkGet),
             NETWORK_UPDATE => Some(Self::NetworkUpdate),
+            DUMPS_IMPORT => Some(Self::DumpsImport),
+            SNAPSHOTS_RESTORE => Some(Self::SnapshotsRestore),
             _otherwise => None,
         }
     }
@@ -356,6 +592,43 @@ impl Action {
     pub const fn repr(&self) -> u8 {
         *self as u8
     }
+
+    /// The group of the routes requiring this action, used to rate limit them together.
+    pub const fn route_group(&self) -> &'static str {
+        use Action::*;
+        match self {
+            All => "*",
+            Search => "search",
+            DocumentsAll | DocumentsAdd | DocumentsGet | DocumentsDelete => "documents",
+            IndexesAll | IndexesAdd | IndexesGet | IndexesUpdate | IndexesDelete | IndexesSwap => {
+                "indexes"
+            }
+            TasksAll | TasksCancel | TasksDelete | TasksGet => "tasks",
+            SettingsAll | SettingsGet | SettingsUpdate => "settings",
+            StatsAll | StatsGet => "stats",
+            MetricsAll | MetricsGet => "metrics",
+            DumpsAll | DumpsCreate | DumpsImport => "dumps",
+            SnapshotsAll | SnapshotsCreate | SnapshotsRestore => "snapshots",
+            Version => "version",
+            KeysAdd | KeysGet | KeysUpdate | KeysDelete => "keys",
+            ExperimentalFeaturesGet | ExperimentalFeaturesUpdate => "experimental",
+            NetworkGet | NetworkUpdate => "network",
+        }
+    }
+
+    /// The groups the routes are rate limited by.
+    pub fn route_groups() -> impl Iterator<Item = &'static str> {
+        let mut groups = Vec::new();
+        for group in enum_iterator::all::<Action>()
+            .filter(|action| *action != Action::All)
+            .map(|action| action.route_group())
+        {
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+        groups.into_iter()
+    }
 }
 
 pub mod actions {
@@ -397,4 +670,7 @@ pub mod actions {
 
     pub const NETWORK_GET: u8 = NetworkGet.repr();
     pub const NETWORK_UPDATE: u8 = NetworkUpdate.repr();
+
+    pub const DUMPS_IMPORT: u8 = DumpsImport.repr();
+    pub const SNAPSHOTS_RESTORE: u8 = SnapshotsRestore.repr();
 }
@@ -1,3 +1,4 @@
+pub mod autobatching;
 pub mod batch_view;
 pub mod batches;
 pub mod compression;
@@ -11,10 +12,12 @@ pub mod index_uid_pattern;
 pub mod keys;
 pub mod locales;
 pub mod settings;
+pub mod sources;
 pub mod star_or;
 pub mod task_view;
 pub mod tasks;
 pub mod versioning;
+pub mod webhooks;
 pub use milli::{heed, Index};
 use uuid::Uuid;
 pub use versioning::VERSION_FILE_NAME;
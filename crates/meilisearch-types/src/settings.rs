@@ -8,9 +8,11 @@ use std::str::FromStr;
 
 use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
+use milli::attachments::AttachmentsSettings;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
+use milli::vector::reranker::RerankerSettings;
 use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
@@ -120,6 +122,23 @@ pub struct FacetingSettings {
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct FlatteningSettings {
+    /// The maximum number of dots in the flattened fields. The values nested deeper are merged
+    /// into the field of their ancestor at this depth. Unlimited when `null`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>, example = json!(2))]
+    pub max_depth: Setting<usize>,
+    /// Key the objects in arrays by their position, e.g. `reviews.0.author`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>, example = json!(false))]
+    pub array_positions: Setting<bool>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 #[deserr(rename_all = camelCase, deny_unknown_fields)]
@@ -288,6 +307,52 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Match the query words with their exact case and diacritics by default.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSensitiveMatching>)]
+    #[schema(value_type = Option<bool>, example = json!(false))]
+    pub sensitive_matching: Setting<bool>,
+    /// Rescores the top hits of the searches with a cross-encoder model behind a REST endpoint.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsReranker>)]
+    #[schema(value_type = Option<RerankerSettings>)]
+    pub reranker: Setting<RerankerSettings>,
+    /// How the nested objects of the documents are flattened into dotted fields.
+    /// Can only be changed while the index has no documents.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsFlattening>)]
+    #[schema(value_type = Option<FlatteningSettings>, example = json!({ "maxDepth": 2, "arrayPositions": false }))]
+    pub flattening: Setting<FlatteningSettings>,
+    /// A rhai function transforming the `doc` variable of every document added or updated
+    /// before it is indexed. Requires the `editDocumentsByFunction` experimental feature.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIngestFunction>)]
+    #[schema(value_type = Option<String>, example = json!("doc.brand = doc.brand.to_lower()"))]
+    pub ingest_function: Setting<String>,
+    /// A JSON Schema the documents added or updated must match. A document that doesn't match it
+    /// fails its task before anything is indexed.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentSchema>)]
+    #[schema(value_type = Option<serde_json::Value>, example = json!({ "type": "object", "properties": { "price": { "type": "number" } } }))]
+    pub document_schema: Setting<serde_json::Value>,
+    /// Skip the added or replaced documents that are identical to their stored version,
+    /// instead of indexing them again.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSkipUnchangedDocuments>)]
+    #[schema(value_type = Option<bool>, example = json!(true))]
+    pub skip_unchanged_documents: Setting<bool>,
+    /// The attributes holding binary files, like PDF or DOCX files, whose text is extracted at
+    /// indexing time to be searchable.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsAttachments>)]
+    #[schema(value_type = Option<AttachmentsSettings>, example = json!({ "attributes": ["file"], "extractionUrl": "http://localhost:9998/tika" }))]
+    pub attachments: Setting<AttachmentsSettings>,
+    /// The number of previous versions kept for each document, which can be listed and restored.
+    /// No revision is kept when not set.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsMaxDocumentRevisions>)]
+    #[schema(value_type = Option<u64>, example = json!(10))]
+    pub max_document_revisions: Setting<u64>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -296,6 +361,12 @@ pub struct Settings<T> {
 
 impl<T> Settings<T> {
     pub fn hide_secrets(&mut self) {
+        if let Setting::Set(RerankerSettings { api_key: Setting::Set(api_key), .. }) =
+            &mut self.reranker
+        {
+            Self::hide_secret(api_key);
+        }
+
         let Setting::Set(embedders) = &mut self.embedders else {
             return;
         };
@@ -354,6 +425,14 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            sensitive_matching: Setting::Reset,
+            reranker: Setting::Reset,
+            flattening: Setting::Reset,
+            ingest_function: Setting::Reset,
+            document_schema: Setting::Reset,
+            skip_unchanged_documents: Setting::Reset,
+            attachments: Setting::Reset,
+            max_document_revisions: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -380,6 +459,14 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            sensitive_matching,
+            reranker,
+            flattening,
+            ingest_function,
+            document_schema,
+            skip_unchanged_documents,
+            attachments,
+            max_document_revisions,
             _kind,
         } = self;
 
@@ -404,6 +491,14 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            sensitive_matching,
+            reranker,
+            flattening,
+            ingest_function,
+            document_schema,
+            skip_unchanged_documents,
+            attachments,
+            max_document_revisions,
             _kind: PhantomData,
         }
     }
@@ -454,12 +549,25 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            sensitive_matching: self.sensitive_matching,
+            reranker: self.reranker,
+            flattening: self.flattening,
+            ingest_function: self.ingest_function,
+            document_schema: self.document_schema,
+            skip_unchanged_documents: self.skip_unchanged_documents,
+            attachments: self.attachments,
+            max_document_revisions: self.max_document_revisions,
             _kind: PhantomData,
         }
     }
 
     pub fn validate(self) -> Result<Self, milli::Error> {
-        self.validate_embedding_settings()
+        self.validate_embedding_settings()?.validate_reranker_settings()
+    }
+
+    fn validate_reranker_settings(mut self) -> Result<Self, milli::Error> {
+        self.reranker = milli::update::validate_reranker_settings(self.reranker)?;
+        Ok(self)
     }
 
     fn validate_embedding_settings(mut self) -> Result<Self, milli::Error> {
@@ -530,6 +638,16 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            sensitive_matching: other.sensitive_matching.or(self.sensitive_matching),
+            reranker: other.reranker.clone().or(self.reranker.clone()),
+            flattening: other.flattening.clone().or(self.flattening.clone()),
+            ingest_function: other.ingest_function.clone().or(self.ingest_function.clone()),
+            document_schema: other.document_schema.clone().or(self.document_schema.clone()),
+            skip_unchanged_documents: other
+                .skip_unchanged_documents
+                .or(self.skip_unchanged_documents),
+            attachments: other.attachments.clone().or(self.attachments.clone()),
+            max_document_revisions: other.max_document_revisions.or(self.max_document_revisions),
             _kind: PhantomData,
         }
     }
@@ -568,6 +686,14 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        sensitive_matching,
+        reranker,
+        flattening,
+        ingest_function,
+        document_schema,
+        skip_unchanged_documents,
+        attachments,
+        max_document_revisions,
         _kind,
     } = settings;
 
@@ -772,6 +898,68 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match sensitive_matching {
+        Setting::Set(sensitive_matching) => builder.set_sensitive_matching(*sensitive_matching),
+        Setting::Reset => builder.reset_sensitive_matching(),
+        Setting::NotSet => (),
+    }
+
+    match reranker {
+        Setting::Set(reranker) => builder.set_reranker(reranker.clone()),
+        Setting::Reset => builder.reset_reranker(),
+        Setting::NotSet => (),
+    }
+
+    match flattening {
+        Setting::Set(FlatteningSettings { max_depth, array_positions }) => {
+            match max_depth {
+                Setting::Set(val) => builder.set_flatten_max_depth(*val),
+                Setting::Reset => builder.reset_flatten_max_depth(),
+                Setting::NotSet => (),
+            }
+            match array_positions {
+                Setting::Set(val) => builder.set_flatten_array_positions(*val),
+                Setting::Reset => builder.reset_flatten_array_positions(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_flatten_max_depth();
+            builder.reset_flatten_array_positions();
+        }
+        Setting::NotSet => (),
+    }
+
+    match ingest_function {
+        Setting::Set(code) => builder.set_ingest_function(code.clone()),
+        Setting::Reset => builder.reset_ingest_function(),
+        Setting::NotSet => (),
+    }
+
+    match document_schema {
+        Setting::Set(schema) => builder.set_document_schema(schema.clone()),
+        Setting::Reset => builder.reset_document_schema(),
+        Setting::NotSet => (),
+    }
+
+    match skip_unchanged_documents {
+        Setting::Set(skip) => builder.set_skip_unchanged_documents(*skip),
+        Setting::Reset => builder.reset_skip_unchanged_documents(),
+        Setting::NotSet => (),
+    }
+
+    match attachments {
+        Setting::Set(attachments) => builder.set_attachments(attachments.clone()),
+        Setting::Reset => builder.reset_attachments(),
+        Setting::NotSet => (),
+    }
+
+    match max_document_revisions {
+        Setting::Set(max) => builder.set_max_document_revisions(*max),
+        Setting::Reset => builder.reset_max_document_revisions(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -876,6 +1064,29 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let sensitive_matching = index.sensitive_matching(rtxn)?;
+
+    let reranker = index.reranker(rtxn)?;
+
+    let ingest_function = index.ingest_function(rtxn)?.map(String::from);
+
+    let document_schema = index.document_schema(rtxn)?;
+
+    let skip_unchanged_documents = index.skip_unchanged_documents(rtxn)?;
+
+    let attachments = index.attachments(rtxn)?;
+
+    let max_document_revisions = index.max_document_revisions(rtxn)?;
+
+    let flatten_options = index.flatten_options(rtxn)?;
+    let flattening = FlatteningSettings {
+        max_depth: match flatten_options.max_depth {
+            Some(max_depth) => Setting::Set(max_depth),
+            None => Setting::Reset,
+        },
+        array_positions: Setting::Set(flatten_options.array_positions),
+    };
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -914,6 +1125,29 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        sensitive_matching: Setting::Set(sensitive_matching),
+        reranker: match reranker {
+            Some(reranker) => Setting::Set(reranker.into()),
+            None => Setting::Reset,
+        },
+        flattening: Setting::Set(flattening),
+        ingest_function: match ingest_function {
+            Some(code) => Setting::Set(code),
+            None => Setting::Reset,
+        },
+        document_schema: match document_schema {
+            Some(schema) => Setting::Set(schema),
+            None => Setting::Reset,
+        },
+        skip_unchanged_documents: Setting::Set(skip_unchanged_documents),
+        attachments: match attachments {
+            Some(attachments) => Setting::Set(attachments),
+            None => Setting::Reset,
+        },
+        max_document_revisions: match max_document_revisions {
+            Some(max) => Setting::Set(max),
+            None => Setting::Reset,
+        },
         _kind: PhantomData,
     };
 
@@ -1141,6 +1375,14 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            sensitive_matching: Setting::NotSet,
+            reranker: Setting::NotSet,
+            flattening: Setting::NotSet,
+            ingest_function: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            skip_unchanged_documents: Setting::NotSet,
+            attachments: Setting::NotSet,
+            max_document_revisions: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1172,6 +1414,14 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            sensitive_matching: Setting::NotSet,
+            reranker: Setting::NotSet,
+            flattening: Setting::NotSet,
+            ingest_function: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            skip_unchanged_documents: Setting::NotSet,
+            attachments: Setting::NotSet,
+            max_document_revisions: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -0,0 +1,36 @@
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::InvalidIndexSourceMode;
+use crate::tasks::TaskId;
+
+/// A remote URL serving documents that the scheduler periodically imports in an index.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSource {
+    pub url: String,
+    pub primary_key: Option<String>,
+    pub csv_delimiter: Option<u8>,
+    /// The number of seconds between two refreshes.
+    pub refresh_interval: u64,
+    pub mode: RefreshMode,
+    /// The date from which the scheduler enqueues the next refresh.
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_refresh_at: OffsetDateTime,
+    /// The last task enqueued to refresh the documents of the index.
+    pub last_refresh_task_uid: Option<TaskId>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidIndexSourceMode>, rename_all = camelCase, deny_unknown_fields)]
+pub enum RefreshMode {
+    /// The documents of the index that are not served by the source anymore are deleted.
+    Full,
+    /// The served documents are added or replaced by primary key, the other ones are kept.
+    #[default]
+    Delta,
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use milli::progress::{ProgressStepView, ProgressView};
 use milli::Object;
 use serde::{Deserialize, Serialize};
 use time::{Duration, OffsetDateTime};
@@ -8,7 +11,7 @@ use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
 use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[schema(rename_all = "camelCase")]
 pub struct TaskView {
@@ -27,6 +30,14 @@ pub struct TaskView {
     /// The uid of the task that performed the taskCancelation if the task has been canceled.
     #[schema(value_type = Option<u32>, example = json!(4326))]
     pub canceled_by: Option<TaskId>,
+    /// The uid of the task that must succeed before this task is processed.
+    #[schema(value_type = Option<u32>, example = json!(4325))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<TaskId>,
+    /// The `key=value` labels attached to the task when it was registered.
+    #[schema(value_type = BTreeMap<String, String>, example = json!({ "source": "catalog-sync" }))]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<DetailsView>,
     pub error: Option<ResponseError>,
@@ -46,6 +57,9 @@ pub struct TaskView {
     #[schema(value_type = String, example = json!("2024-08-08_14:12:09.393Z"))]
     #[serde(with = "time::serde::rfc3339::option", default)]
     pub finished_at: Option<OffsetDateTime>,
+    /// The progress of the task, only present while the task is processing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgressView>,
 }
 
 impl TaskView {
@@ -57,16 +71,48 @@ impl TaskView {
             status: task.status,
             kind: task.kind.as_kind(),
             canceled_by: task.canceled_by,
+            depends_on: task.depends_on,
+            labels: task.labels.clone(),
             details: task.details.clone().map(DetailsView::from),
             error: task.error.clone(),
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            progress: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct TaskProgressView {
+    /// The steps currently processed, from the most general to the most precise one.
+    pub steps: Vec<ProgressStepView>,
+    /// The percentage of the batch of the task that has been processed.
+    pub percentage: f32,
+    /// The estimated time before the end of the processing expressed as a `ISO-8601` duration format.
+    #[schema(value_type = Option<String>, example = json!("PT12.5S"))]
+    #[serde(serialize_with = "serialize_duration")]
+    pub estimated_remaining_time: Option<Duration>,
+}
+
+impl TaskProgressView {
+    /// Builds the progress of a task that has been processing for `elapsed`.
+    ///
+    /// The remaining time is extrapolated from the elapsed time and the percentage reached,
+    /// it is unknown until a first step has been made.
+    pub fn new(progress: ProgressView, elapsed: Duration) -> TaskProgressView {
+        let ProgressView { steps, percentage } = progress;
+        let estimated_remaining_time = (percentage > 0.0 && elapsed.is_positive()).then(|| {
+            let remaining = (100.0 - percentage.min(100.0)) as f64 / percentage as f64;
+            elapsed * remaining
+        });
+        TaskProgressView { steps, percentage, estimated_remaining_time }
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[schema(rename_all = "camelCase")]
@@ -101,9 +147,30 @@ pub struct DetailsView {
     /// Original filter query for taskCancelation or taskDeletion tasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_filter: Option<Option<String>>,
-    /// Identifier generated for the dump for dumpCreation task.
+    /// Identifier generated for the dump for dumpCreation task, or of the dump an indexRestore
+    /// task restores the index from.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dump_uid: Option<Option<String>>,
+    /// Name of the indexes created by a dumpImport task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_indexes: Option<Option<Vec<String>>>,
+    /// The date the indexes are restored to by a pointInTimeRestore task.
+    #[schema(value_type = Option<String>, example = json!("2024-08-08T22:00:00Z"))]
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub restore_at: Option<OffsetDateTime>,
+    /// Name of the indexes restored by a pointInTimeRestore task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restored_indexes: Option<Option<Vec<String>>>,
+    /// Number of tasks replayed on top of the restore point by a pointInTimeRestore task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replayed_tasks: Option<Option<u64>>,
+    /// The snapshot an indexRestore task restores the index from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<Option<Object>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -114,6 +181,14 @@ pub struct DetailsView {
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    /// The date before which the indexSwap task isn't processed.
+    #[schema(value_type = Option<String>, example = json!("2024-08-08T22:00:00Z"))]
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub execute_at: Option<OffsetDateTime>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upgrade_from: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -205,6 +280,40 @@ impl DetailsView {
                 // the first one we encounter but that shouldn't be an issue anyway.
                 (Some(Some(left)), Some(Some(_right))) => Some(Some(left.to_string())),
             },
+            imported_indexes: match (self.imported_indexes.clone(), other.imported_indexes.clone())
+            {
+                (None, None) => None,
+                (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
+                (None | Some(None), Some(Some(indexes)))
+                | (Some(Some(indexes)), None | Some(None)) => Some(Some(indexes)),
+                (Some(Some(mut left)), Some(Some(mut right))) => {
+                    left.append(&mut right);
+                    Some(Some(left))
+                }
+            },
+            // We should never be able to batch multiple restores at the same time.
+            restore_at: self.restore_at.or(other.restore_at),
+            restored_indexes: match (self.restored_indexes.clone(), other.restored_indexes.clone())
+            {
+                (None, None) => None,
+                (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
+                (None | Some(None), Some(Some(indexes)))
+                | (Some(Some(indexes)), None | Some(None)) => Some(Some(indexes)),
+                (Some(Some(mut left)), Some(Some(mut right))) => {
+                    left.append(&mut right);
+                    Some(Some(left))
+                }
+            },
+            replayed_tasks: match (self.replayed_tasks, other.replayed_tasks) {
+                (None, None) => None,
+                (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
+                (None | Some(None), Some(Some(task))) | (Some(Some(task)), None | Some(None)) => {
+                    Some(Some(task))
+                }
+                (Some(Some(left)), Some(Some(right))) => Some(Some(left + right)),
+            },
+            // We should never be able to batch multiple index restores at the same time.
+            snapshot: self.snapshot.clone().or(other.snapshot.clone()),
             context: match (&self.context, &other.context) {
                 (None, None) => None,
                 (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
@@ -238,6 +347,8 @@ impl DetailsView {
                     Some(left)
                 }
             },
+            // The swaps are executed together, thus once the latest date is reached
+            execute_at: self.execute_at.max(other.execute_at),
             // We want the earliest version
             upgrade_from: match (self.upgrade_from.clone(), other.upgrade_from.clone()) {
                 (None, None) => None,
@@ -324,8 +435,27 @@ impl From<Details> for DetailsView {
             Details::Dump { dump_uid } => {
                 DetailsView { dump_uid: Some(dump_uid), ..DetailsView::default() }
             }
-            Details::IndexSwap { swaps } => {
-                DetailsView { swaps: Some(swaps), ..Default::default() }
+            Details::DumpImport { imported_indexes, indexed_documents } => DetailsView {
+                imported_indexes: Some(imported_indexes),
+                indexed_documents: Some(indexed_documents),
+                ..DetailsView::default()
+            },
+            Details::PointInTimeRestore { restore_at, restored_indexes, replayed_tasks } => {
+                DetailsView {
+                    restore_at: Some(restore_at),
+                    restored_indexes: Some(restored_indexes),
+                    replayed_tasks: Some(replayed_tasks),
+                    ..DetailsView::default()
+                }
+            }
+            Details::IndexRestore { snapshot, dump_uid, indexed_documents } => DetailsView {
+                snapshot,
+                dump_uid: dump_uid.map(Some),
+                indexed_documents: Some(indexed_documents),
+                ..DetailsView::default()
+            },
+            Details::IndexSwap { swaps, execute_at } => {
+                DetailsView { swaps: Some(swaps), execute_at, ..Default::default() }
             }
             Details::UpgradeDatabase { from, to } => DetailsView {
                 upgrade_from: Some(format!("v{}.{}.{}", from.0, from.1, from.2)),
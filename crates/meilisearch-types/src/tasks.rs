@@ -1,5 +1,5 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
@@ -9,12 +9,12 @@ use milli::Object;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
-use utoipa::ToSchema;
+use utoipa::{PartialSchema, ToSchema};
 use uuid::Uuid;
 
 use crate::batches::BatchId;
 use crate::error::ResponseError;
-use crate::keys::Key;
+use crate::keys::{Key, KeyId};
 use crate::settings::{Settings, Unchecked};
 use crate::{versioning, InstanceUid};
 
@@ -35,6 +35,15 @@ pub struct Task {
 
     pub error: Option<ResponseError>,
     pub canceled_by: Option<TaskId>,
+    /// The task that must succeed before this one can be processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<TaskId>,
+    /// The `key=value` labels attached by the client that registered the task.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// The API key whose quotas the task counts against, see [`crate::keys::KeyQuotas`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_key: Option<KeyId>,
     pub details: Option<Details>,
 
     pub status: Status,
@@ -47,12 +56,15 @@ impl Task {
 
         match &self.kind {
             DumpCreation { .. }
+            | DumpImport { .. }
+            | PointInTimeRestore { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. }
             | UpgradeDatabase { .. }
             | IndexSwap { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentImportUrl { index_uid, .. }
             | DocumentEdition { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
@@ -60,6 +72,7 @@ impl Task {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexRestore { index_uid, .. }
             | IndexDeletion { index_uid } => Some(index_uid),
         }
     }
@@ -72,7 +85,9 @@ impl Task {
     /// Return the content-uuid if there is one
     pub fn content_uuid(&self) -> Option<Uuid> {
         match self.kind {
-            KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DocumentImportUrl { content_file, .. } => Some(content_file),
+            KindWithContent::DumpImport { dump_file, .. } => dump_file,
             KindWithContent::DocumentEdition { .. }
             | KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
@@ -85,12 +100,64 @@ impl Task {
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::PointInTimeRestore { .. }
+            | KindWithContent::IndexRestore { .. }
             | KindWithContent::SnapshotCreation
             | KindWithContent::UpgradeDatabase { .. } => None,
         }
     }
 }
 
+/// A `key=value` label attached to a task, e.g. `source=catalog-sync`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskLabel {
+    pub key: String,
+    pub value: String,
+}
+
+impl Display for TaskLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+impl FromStr for TaskLabel {
+    type Err = ParseTaskLabelError;
+
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        match label.split_once('=') {
+            Some((key, value))
+                if !key.trim().is_empty()
+                    && !value.trim().is_empty()
+                    && !value.contains('=')
+                    && !label.contains(',') =>
+            {
+                Ok(TaskLabel { key: key.trim().to_owned(), value: value.trim().to_owned() })
+            }
+            _ => Err(ParseTaskLabelError(label.to_owned())),
+        }
+    }
+}
+
+impl PartialSchema for TaskLabel {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        String::schema()
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTaskLabelError(pub String);
+impl fmt::Display for ParseTaskLabelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid task label. A label must be formatted as `key=value`, where neither the key nor the value are empty or contain the `=` and `,` characters.",
+            self.0,
+        )
+    }
+}
+impl std::error::Error for ParseTaskLabelError {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum KindWithContent {
@@ -102,6 +169,19 @@ pub enum KindWithContent {
         documents_count: u64,
         allow_index_creation: bool,
     },
+    /// Documents fetched from a remote URL when the task is registered.
+    DocumentImportUrl {
+        index_uid: String,
+        primary_key: Option<String>,
+        method: IndexDocumentsMethod,
+        url: String,
+        csv_delimiter: Option<u8>,
+        content_file: Uuid,
+        documents_count: u64,
+        allow_index_creation: bool,
+        /// Whether the documents of the index that are not served by the URL must be deleted.
+        replace_all_documents: bool,
+    },
     DocumentDeletion {
         index_uid: String,
         documents_ids: Vec<String>,
@@ -138,6 +218,9 @@ pub enum KindWithContent {
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
+        /// The swap isn't processed before this date.
+        #[serde(default, with = "time::serde::rfc3339::option")]
+        execute_at: Option<OffsetDateTime>,
     },
     TaskCancelation {
         query: String,
@@ -150,6 +233,38 @@ pub enum KindWithContent {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        /// The indexes to dump, all of them when `None`.
+        #[serde(default)]
+        index_uids: Option<Vec<String>>,
+        /// Whether the task queue and the batches are left out of the dump.
+        #[serde(default)]
+        skip_tasks: bool,
+    },
+    /// Imports the indexes of a dump into the running instance.
+    DumpImport {
+        /// The update file the dump was uploaded to.
+        dump_file: Option<Uuid>,
+        /// The URL the dump is downloaded from by the task runner, when it wasn't uploaded.
+        url: Option<String>,
+        /// The new names of the imported indexes, by their name in the dump.
+        #[serde(default)]
+        index_renames: BTreeMap<String, String>,
+    },
+    /// Restores indexes to their state at a date, by replaying the tasks processed since the
+    /// latest restore point before it.
+    PointInTimeRestore {
+        /// The indexes to restore, all the indexes of the restore point when `None`.
+        index_uids: Option<Vec<String>>,
+        #[serde(with = "time::serde::rfc3339")]
+        restore_at: OffsetDateTime,
+    },
+    /// Replaces an index, or creates it, with its copy from a snapshot or a dump.
+    IndexRestore {
+        index_uid: String,
+        /// The file of the snapshot directory the index is restored from.
+        snapshot: Option<String>,
+        /// The uid of the dump of the dump directory the index is restored from.
+        dump_uid: Option<String>,
     },
     SnapshotCreation,
     UpgradeDatabase {
@@ -167,6 +282,7 @@ impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
+            KindWithContent::DocumentImportUrl { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
@@ -179,6 +295,9 @@ impl KindWithContent {
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
+            KindWithContent::DumpImport { .. } => Kind::DumpImport,
+            KindWithContent::PointInTimeRestore { .. } => Kind::PointInTimeRestore,
+            KindWithContent::IndexRestore { .. } => Kind::IndexRestore,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
             KindWithContent::UpgradeDatabase { .. } => Kind::UpgradeDatabase,
         }
@@ -189,11 +308,16 @@ impl KindWithContent {
 
         match self {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. }
             | UpgradeDatabase { .. } => vec![],
+            PointInTimeRestore { index_uids, .. } => {
+                index_uids.iter().flatten().map(String::as_str).collect()
+            }
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentImportUrl { index_uid, .. }
             | DocumentEdition { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
@@ -201,8 +325,9 @@ impl KindWithContent {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexRestore { index_uid, .. }
             | IndexDeletion { index_uid } => vec![index_uid],
-            IndexSwap { swaps } => {
+            IndexSwap { swaps, .. } => {
                 let mut indexes = HashSet::<&str>::default();
                 for swap in swaps {
                     indexes.insert(swap.indexes.0.as_str());
@@ -223,6 +348,12 @@ impl KindWithContent {
                     indexed_documents: None,
                 })
             }
+            KindWithContent::DocumentImportUrl { documents_count, .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: *documents_count,
+                    indexed_documents: None,
+                })
+            }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
                 Some(Details::DocumentEdition {
                     deleted_documents: None,
@@ -254,8 +385,8 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
-            KindWithContent::IndexSwap { swaps } => {
-                Some(Details::IndexSwap { swaps: swaps.clone() })
+            KindWithContent::IndexSwap { swaps, execute_at } => {
+                Some(Details::IndexSwap { swaps: swaps.clone(), execute_at: *execute_at })
             }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
@@ -268,6 +399,23 @@ impl KindWithContent {
                 original_filter: query.clone(),
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: None, indexed_documents: None })
+            }
+            KindWithContent::PointInTimeRestore { restore_at, .. } => {
+                Some(Details::PointInTimeRestore {
+                    restore_at: *restore_at,
+                    restored_indexes: None,
+                    replayed_tasks: None,
+                })
+            }
+            KindWithContent::IndexRestore { snapshot, dump_uid, .. } => {
+                Some(Details::IndexRestore {
+                    snapshot: snapshot.clone(),
+                    dump_uid: dump_uid.clone(),
+                    indexed_documents: None,
+                })
+            }
             KindWithContent::SnapshotCreation => None,
             KindWithContent::UpgradeDatabase { from } => Some(Details::UpgradeDatabase {
                 from: (from.0, from.1, from.2),
@@ -288,6 +436,12 @@ impl KindWithContent {
                     indexed_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentImportUrl { documents_count, .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: *documents_count,
+                    indexed_documents: Some(0),
+                })
+            }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
                 Some(Details::DocumentEdition {
                     deleted_documents: Some(0),
@@ -334,6 +488,23 @@ impl KindWithContent {
                 original_filter: query.clone(),
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: None, indexed_documents: Some(0) })
+            }
+            KindWithContent::PointInTimeRestore { restore_at, .. } => {
+                Some(Details::PointInTimeRestore {
+                    restore_at: *restore_at,
+                    restored_indexes: None,
+                    replayed_tasks: Some(0),
+                })
+            }
+            KindWithContent::IndexRestore { snapshot, dump_uid, .. } => {
+                Some(Details::IndexRestore {
+                    snapshot: snapshot.clone(),
+                    dump_uid: dump_uid.clone(),
+                    indexed_documents: Some(0),
+                })
+            }
             KindWithContent::SnapshotCreation => None,
             KindWithContent::UpgradeDatabase { from } => Some(Details::UpgradeDatabase {
                 from: *from,
@@ -356,6 +527,12 @@ impl From<&KindWithContent> for Option<Details> {
                     indexed_documents: None,
                 })
             }
+            KindWithContent::DocumentImportUrl { documents_count, .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: *documents_count,
+                    indexed_documents: None,
+                })
+            }
             KindWithContent::DocumentEdition { .. } => None,
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
@@ -382,6 +559,23 @@ impl From<&KindWithContent> for Option<Details> {
                 original_filter: query.clone(),
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: None, indexed_documents: None })
+            }
+            KindWithContent::PointInTimeRestore { restore_at, .. } => {
+                Some(Details::PointInTimeRestore {
+                    restore_at: *restore_at,
+                    restored_indexes: None,
+                    replayed_tasks: None,
+                })
+            }
+            KindWithContent::IndexRestore { snapshot, dump_uid, .. } => {
+                Some(Details::IndexRestore {
+                    snapshot: snapshot.clone(),
+                    dump_uid: dump_uid.clone(),
+                    indexed_documents: None,
+                })
+            }
             KindWithContent::SnapshotCreation => None,
             KindWithContent::UpgradeDatabase { from } => Some(Details::UpgradeDatabase {
                 from: *from,
@@ -500,6 +694,9 @@ pub enum Kind {
     DumpCreation,
     SnapshotCreation,
     UpgradeDatabase,
+    DumpImport,
+    PointInTimeRestore,
+    IndexRestore,
 }
 
 impl Kind {
@@ -511,11 +708,14 @@ impl Kind {
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexRestore => true,
             Kind::IndexSwap
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
+            | Kind::DumpImport
+            | Kind::PointInTimeRestore
             | Kind::UpgradeDatabase
             | Kind::SnapshotCreation => false,
         }
@@ -537,6 +737,9 @@ impl Display for Kind {
             Kind::DumpCreation => write!(f, "dumpCreation"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
             Kind::UpgradeDatabase => write!(f, "upgradeDatabase"),
+            Kind::DumpImport => write!(f, "dumpImport"),
+            Kind::PointInTimeRestore => write!(f, "pointInTimeRestore"),
+            Kind::IndexRestore => write!(f, "indexRestore"),
         }
     }
 }
@@ -570,6 +773,12 @@ impl FromStr for Kind {
             Ok(Kind::SnapshotCreation)
         } else if kind.eq_ignore_ascii_case("upgradeDatabase") {
             Ok(Kind::UpgradeDatabase)
+        } else if kind.eq_ignore_ascii_case("dumpImport") {
+            Ok(Kind::DumpImport)
+        } else if kind.eq_ignore_ascii_case("pointInTimeRestore") {
+            Ok(Kind::PointInTimeRestore)
+        } else if kind.eq_ignore_ascii_case("indexRestore") {
+            Ok(Kind::IndexRestore)
         } else {
             Err(ParseTaskKindError(kind.to_owned()))
         }
@@ -640,8 +849,25 @@ pub enum Details {
     Dump {
         dump_uid: Option<String>,
     },
+    DumpImport {
+        imported_indexes: Option<Vec<String>>,
+        indexed_documents: Option<u64>,
+    },
+    PointInTimeRestore {
+        #[serde(with = "time::serde::rfc3339")]
+        restore_at: OffsetDateTime,
+        restored_indexes: Option<Vec<String>>,
+        replayed_tasks: Option<u64>,
+    },
+    IndexRestore {
+        snapshot: Option<String>,
+        dump_uid: Option<String>,
+        indexed_documents: Option<u64>,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
+        #[serde(default, with = "time::serde::rfc3339::option")]
+        execute_at: Option<OffsetDateTime>,
     },
     UpgradeDatabase {
         from: (u32, u32, u32),
@@ -664,6 +890,9 @@ impl Details {
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
+            Self::DumpImport { indexed_documents, .. } => *indexed_documents = Some(0),
+            Self::PointInTimeRestore { replayed_tasks, .. } => *replayed_tasks = Some(0),
+            Self::IndexRestore { indexed_documents, .. } => *indexed_documents = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
@@ -726,11 +955,52 @@ pub fn serialize_duration<S: Serializer>(
     }
 }
 
+/// Parses an ISO 8601 duration like the ones written by [`serialize_duration`], also accepting
+/// the hours and minutes, e.g. `PT10M`, `PT1H30M` or `P1DT0.5S`.
+pub fn parse_duration(duration: &str) -> Option<Duration> {
+    let rest = duration.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return None;
+    }
+
+    let mut duration = Duration::ZERO;
+    if !date.is_empty() {
+        let days: u32 = date.strip_suffix('D')?.parse().ok()?;
+        duration += Duration::days(days.into());
+    }
+
+    if let Some(mut time) = time {
+        if time.is_empty() {
+            return None;
+        }
+        for (designator, unit) in [('H', Duration::HOUR), ('M', Duration::MINUTE)] {
+            if let Some((value, rest)) = time.split_once(designator) {
+                let value: u32 = value.parse().ok()?;
+                duration += unit * value;
+                time = rest;
+            }
+        }
+        if !time.is_empty() {
+            let seconds: f64 = time.strip_suffix('S')?.parse().ok()?;
+            if !seconds.is_finite() || seconds < 0.0 {
+                return None;
+            }
+            duration += Duration::seconds_f64(seconds);
+        }
+    }
+
+    Some(duration)
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use super::{Details, Kind};
+    use super::{parse_duration, Details, Kind};
     use crate::heed::types::SerdeJson;
     use crate::heed::{BytesDecode, BytesEncode};
 
@@ -755,4 +1025,18 @@ mod tests {
             assert_eq!(kind, k, "{kind}.to_string() returned {s} which was parsed as {k}");
         }
     }
+
+    #[test]
+    fn parse_iso8601_durations() {
+        use time::Duration;
+
+        assert_eq!(parse_duration("PT10M"), Some(Duration::minutes(10)));
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1DT0.5S"), Some(Duration::DAY + Duration::milliseconds(500)));
+        assert_eq!(parse_duration("P2D"), Some(Duration::days(2)));
+
+        for invalid in ["", "P", "PT", "10M", "PT10", "PT-1S", "PT1M1H", "P1H", "PTinfS"] {
+            assert_eq!(parse_duration(invalid), None, "{invalid} should be invalid");
+        }
+    }
 }
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::task_view::TaskView;
+use crate::tasks::{Kind, Status, Task};
+
+/// A target notified of the finished tasks matching its filters.
+///
+/// A filter left to `None` matches all the tasks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TaskWebhook {
+    pub url: String,
+    /// The value sent in the `Authorization` header of the requests.
+    #[serde(default)]
+    pub authorization_header: Option<String>,
+    /// Only the tasks of one of these types are sent.
+    #[serde(default)]
+    pub types: Option<Vec<Kind>>,
+    /// Only the tasks associated with one of these indexes are sent.
+    #[serde(default)]
+    pub index_uids: Option<Vec<String>>,
+    /// Only the tasks with one of these statuses are sent.
+    #[serde(default)]
+    pub statuses: Option<Vec<Status>>,
+    /// The Liquid template of the body of the requests, rendered for each task with the task in
+    /// the `task` variable, e.g. `{"text": "Task {{ task.uid }} is {{ task.status }}"}`.
+    ///
+    /// A webhook with a template receives one JSON request per task instead of the gzipped
+    /// NDJSON of all the matching tasks.
+    #[serde(default)]
+    pub payload_template: Option<String>,
+}
+
+impl TaskWebhook {
+    /// Creates a webhook notified of all the tasks.
+    pub fn new(url: String, authorization_header: Option<String>) -> Self {
+        Self {
+            url,
+            authorization_header,
+            types: None,
+            index_uids: None,
+            statuses: None,
+            payload_template: None,
+        }
+    }
+
+    /// Returns `true` if the task must be sent to this webhook.
+    pub fn matches(&self, task: &Task) -> bool {
+        let TaskWebhook {
+            url: _,
+            authorization_header: _,
+            types,
+            index_uids,
+            statuses,
+            payload_template: _,
+        } = self;
+
+        types.as_ref().map_or(true, |types| types.contains(&task.kind.as_kind()))
+            && statuses.as_ref().map_or(true, |statuses| statuses.contains(&task.status))
+            && index_uids.as_ref().map_or(true, |index_uids| {
+                task.indexes().into_iter().any(|index| index_uids.iter().any(|uid| uid == index))
+            })
+    }
+
+    /// Parses the payload template of the webhook, if any.
+    pub fn parse_payload_template(&self) -> Result<Option<PayloadTemplate>, liquid::Error> {
+        let Some(template) = &self.payload_template else { return Ok(None) };
+        let template = liquid::ParserBuilder::with_stdlib().build()?.parse(template)?;
+        Ok(Some(PayloadTemplate(template)))
+    }
+}
+
+/// The parsed payload template of a [`TaskWebhook`].
+pub struct PayloadTemplate(liquid::Template);
+
+impl PayloadTemplate {
+    /// Renders the body of the request notifying the webhook of the task.
+    pub fn render(&self, task: &TaskView) -> Result<String, liquid::Error> {
+        let task = liquid::to_object(task)?;
+        self.0.render(&liquid::object!({ "task": task }))
+    }
+}
@@ -58,7 +58,7 @@ fn config_user_id_path(db_path: &Path) -> Option<PathBuf> {
 }
 
 /// Look for the instance-uid in the `data.ms` or in `~/.config/Meilisearch/path-to-db-instance-uid`
-fn find_user_id(db_path: &Path) -> Option<InstanceUid> {
+pub(crate) fn find_user_id(db_path: &Path) -> Option<InstanceUid> {
     fs::read_to_string(db_path.join("instance-uid"))
         .ok()
         .or_else(|| fs::read_to_string(config_user_id_path(db_path)?).ok())
@@ -163,4 +163,15 @@ impl Analytics {
             let _ = segment.sender.try_send(segment_analytics::Message::new(event, request));
         }
     }
+
+    /// Send the aggregated analytics right away instead of waiting for the next tick, returns
+    /// once they are sent.
+    pub async fn flush(&self) {
+        if let Some(ref segment) = self.segment {
+            let (sender, flushed) = tokio::sync::oneshot::channel();
+            if segment.flush_requests.send(sender).await.is_ok() {
+                let _ = flushed.await;
+            }
+        }
+    }
 }
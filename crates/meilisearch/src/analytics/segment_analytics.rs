@@ -21,11 +21,12 @@ use sysinfo::{Disks, System};
 use time::OffsetDateTime;
 use tokio::select;
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
 use super::{config_user_id_path, Aggregate, MEILISEARCH_CONFIG_PATH};
 use crate::option::{
-    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot,
+    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleDump, ScheduleSnapshot,
 };
 use crate::routes::{create_all_stats, Stats};
 use crate::Opt;
@@ -111,6 +112,8 @@ pub struct SegmentAnalytics {
     pub instance_uid: InstanceUid,
     pub user: User,
     pub sender: Sender<Message>,
+    /// Asks to send the aggregated events right away, the sender is notified once they are sent.
+    pub flush_requests: Sender<oneshot::Sender<()>>,
 }
 
 impl SegmentAnalytics {
@@ -159,9 +162,11 @@ impl SegmentAnalytics {
         }
 
         let (sender, inbox) = mpsc::channel(100); // How many analytics can we bufferize
+        let (flush_requests, flush_inbox) = mpsc::channel(10);
 
         let segment = Box::new(Segment {
             inbox,
+            flush_inbox,
             user: user.clone(),
             opt: opt.clone(),
             batcher,
@@ -169,7 +174,7 @@ impl SegmentAnalytics {
         });
         tokio::spawn(segment.run(index_scheduler.clone(), auth_controller.clone()));
 
-        let this = Self { instance_uid, sender, user: user.clone() };
+        let this = Self { instance_uid, sender, flush_requests, user: user.clone() };
 
         Some(Arc::new(this))
     }
@@ -192,14 +197,33 @@ struct Infos {
     experimental_logs_mode: LogMode,
     experimental_dumpless_upgrade: bool,
     experimental_replication_parameters: bool,
+    read_only_replica: bool,
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
     experimental_limit_batched_tasks_total_size: u64,
+    experimental_max_batched_documents: bool,
+    experimental_autobatching_max_wait: bool,
+    experimental_index_autobatching: bool,
+    experimental_max_concurrent_batches: usize,
+    experimental_fast_lane: bool,
+    experimental_batch_checkpoints: bool,
+    experimental_max_enqueued_tasks: bool,
+    experimental_disk_watermark: bool,
     experimental_network: bool,
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
+    experimental_read_only: bool,
     experimental_embedding_cache_entries: usize,
+    experimental_maintenance_window: bool,
+    experimental_restore_points: bool,
+    experimental_change_stream: bool,
+    experimental_task_log: bool,
+    experimental_index_aliases: bool,
+    experimental_import_url: bool,
+    experimental_postgres_cdc: bool,
+    experimental_kafka_ingestion: bool,
+    experimental_task_events: bool,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -208,7 +232,9 @@ struct Infos {
     ignore_dump_if_db_exists: bool,
     import_snapshot: bool,
     schedule_snapshot: Option<u64>,
+    schedule_dump: Option<u64>,
     snapshot_dir: bool,
+    snapshot_s3: bool,
     ignore_missing_snapshot: bool,
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
@@ -226,6 +252,9 @@ struct Infos {
     ssl_require_auth: bool,
     ssl_resumption: bool,
     ssl_tickets: bool,
+    ssl_client_keys_path: bool,
+    auth_encryption_key: bool,
+    expired_keys_retention: Option<u64>,
 }
 
 impl Infos {
@@ -243,16 +272,42 @@ impl Infos {
             experimental_logs_mode,
             experimental_dumpless_upgrade,
             experimental_replication_parameters,
+            read_only_replica,
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_max_batched_documents,
+            experimental_autobatching_max_wait_ms,
+            experimental_index_autobatching,
+            experimental_max_concurrent_batches,
+            experimental_fast_lane_max_documents,
+            experimental_batch_checkpoint_documents,
+            experimental_max_enqueued_tasks,
+            experimental_disk_watermark,
             experimental_embedding_cache_entries,
+            experimental_maintenance_window,
+            experimental_restore_points,
+            experimental_change_stream,
+            experimental_task_log,
+            experimental_index_aliases,
+            experimental_import_url_allowed_hosts,
+            experimental_postgres_cdc_url,
+            experimental_postgres_cdc_slot: _,
+            experimental_postgres_cdc_tables: _,
+            experimental_kafka_brokers,
+            experimental_kafka_group: _,
+            experimental_kafka_topics: _,
+            experimental_task_events_url,
+            experimental_task_events_stream: _,
             http_addr,
             master_key: _,
+            auth_encryption_key,
+            expired_keys_retention,
             env,
             task_webhook_url,
             task_webhook_authorization_header,
+            task_webhooks,
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
@@ -263,18 +318,26 @@ impl Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path,
             import_snapshot,
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             snapshot_dir,
+            snapshot_s3_endpoint: _,
+            snapshot_s3_region: _,
+            snapshot_s3_access_key: _,
+            snapshot_s3_secret_key: _,
+            snapshot_s3_path_style: _,
             schedule_snapshot,
             import_dump,
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dump_dir,
+            schedule_dump,
             log_level,
             indexer_options,
             config_file_path,
+            upgrade_check: _,
             no_analytics: _,
         } = options;
 
@@ -282,6 +345,10 @@ impl Infos {
             ScheduleSnapshot::Disabled => None,
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
+        let schedule_dump = match schedule_dump {
+            ScheduleDump::Disabled => None,
+            ScheduleDump::Enabled(interval) => Some(interval),
+        };
 
         let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
             indexer_options;
@@ -294,6 +361,7 @@ impl Infos {
             network,
             get_task_documents_route,
             composite_embedders,
+            read_only,
         } = features;
 
         // We're going to override every sensible information.
@@ -309,12 +377,23 @@ impl Infos {
             experimental_logs_mode,
             experimental_dumpless_upgrade,
             experimental_replication_parameters,
+            read_only_replica,
             experimental_enable_logs_route: experimental_enable_logs_route | logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_network: network,
             experimental_get_task_documents_route: get_task_documents_route,
             experimental_composite_embedders: composite_embedders,
+            experimental_read_only: read_only,
             experimental_embedding_cache_entries,
+            experimental_maintenance_window: experimental_maintenance_window.is_some(),
+            experimental_restore_points: experimental_restore_points.is_some(),
+            experimental_change_stream,
+            experimental_task_log,
+            experimental_index_aliases,
+            experimental_import_url: experimental_import_url_allowed_hosts.is_some(),
+            experimental_postgres_cdc: experimental_postgres_cdc_url.is_some(),
+            experimental_kafka_ingestion: experimental_kafka_brokers.is_some(),
+            experimental_task_events: experimental_task_events_url.is_some(),
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -323,6 +402,8 @@ impl Infos {
             ignore_dump_if_db_exists,
             import_snapshot: import_snapshot.is_some(),
             schedule_snapshot,
+            schedule_dump,
+            snapshot_s3: snapshot_dir.to_str().is_some_and(|dir| dir.starts_with("s3://")),
             snapshot_dir: snapshot_dir != PathBuf::from("snapshots/"),
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
@@ -330,7 +411,15 @@ impl Infos {
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
-            task_queue_webhook: task_webhook_url.is_some(),
+            experimental_max_batched_documents: experimental_max_batched_documents.is_some(),
+            experimental_autobatching_max_wait: experimental_autobatching_max_wait_ms.is_some(),
+            experimental_index_autobatching: experimental_index_autobatching.is_some(),
+            experimental_max_concurrent_batches,
+            experimental_fast_lane: experimental_fast_lane_max_documents.is_some(),
+            experimental_batch_checkpoints: experimental_batch_checkpoint_documents.is_some(),
+            experimental_max_enqueued_tasks: experimental_max_enqueued_tasks.is_some(),
+            experimental_disk_watermark: experimental_disk_watermark.is_some(),
+            task_queue_webhook: task_webhook_url.is_some() || task_webhooks.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
             max_indexing_memory,
@@ -343,12 +432,16 @@ impl Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path: ssl_client_keys_path.is_some(),
+            auth_encryption_key: auth_encryption_key.is_some(),
+            expired_keys_retention,
         }
     }
 }
 
 pub struct Segment {
     inbox: Receiver<Message>,
+    flush_inbox: Receiver<oneshot::Sender<()>>,
     user: User,
     opt: Opt,
     batcher: AutoBatcher,
@@ -414,6 +507,14 @@ impl Segment {
                 Some(msg) = self.inbox.recv() => {
                     self.handle_msg(msg);
                }
+                Some(flushed) = self.flush_inbox.recv() => {
+                    // the events published before the request are part of the flush
+                    while let Ok(msg) = self.inbox.try_recv() {
+                        self.handle_msg(msg);
+                    }
+                    self.tick(index_scheduler.clone(), auth_controller.clone()).await;
+                    let _ = flushed.send(());
+                }
             }
         }
     }
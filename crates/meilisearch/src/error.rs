@@ -2,7 +2,7 @@ use actix_web as aweb;
 use aweb::error::{JsonPayloadError, QueryPayloadError};
 use byte_unit::{Byte, UnitType};
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
-use meilisearch_types::error::{Code, ErrorCode, ResponseError};
+use meilisearch_types::error::{Code, ErrorCode, ErrorDetails, ResponseError};
 use meilisearch_types::index_uid::{IndexUid, IndexUidFormatError};
 use meilisearch_types::milli;
 use meilisearch_types::milli::OrderBy;
@@ -25,6 +25,8 @@ pub enum MeilisearchHttpError {
     InvalidContentType(String, Vec<String>),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
+    #[error("Revision `{revision}` of the document `{document_id}` not found.")]
+    DocumentRevisionNotFound { document_id: String, revision: u64 },
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {}`.", .0.join(", "), .1)]
@@ -35,6 +37,8 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(usize, &'static str),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
+    #[error("Inside `.queries[{0}]`: Using alternative phrasings in `q` is not allowed in federated queries.\n - Hint: pass each phrasing in its own query, with its weight in `federationOptions.weight`")]
+    AlternativesInFederatedQuery(usize),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
     InconsistentFacetOrder {
         facet: String,
@@ -45,6 +49,10 @@ pub enum MeilisearchHttpError {
     },
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
+    #[error("A dump archive, or a json payload with the `url` of the dump, is missing.")]
+    MissingDumpArchive,
+    #[error("The task log archive to replay is missing.")]
+    MissingTaskLogArchive,
     #[error("Too many search requests running at the same time: {0}. Retry after 10s.")]
     TooManySearchRequests(usize),
     #[error("Internal error: Search limiter is down.")]
@@ -93,8 +101,11 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::AlreadyUsedLogRoute => Code::BadRequest,
             MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => Code::InvalidContentType,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
+            MeilisearchHttpError::MissingDumpArchive => Code::MissingPayload,
+            MeilisearchHttpError::MissingTaskLogArchive => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
+            MeilisearchHttpError::DocumentRevisionNotFound { .. } => Code::DocumentRevisionNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
@@ -118,11 +129,19 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchQueryPagination
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::AlternativesInFederatedQuery(_) => Code::InvalidSearchQ,
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
         }
     }
+
+    fn error_details(&self) -> Option<ErrorDetails> {
+        match self {
+            MeilisearchHttpError::Milli { error, .. } => error.error_details(),
+            _ => None,
+        }
+    }
 }
 
 impl From<MeilisearchHttpError> for aweb::Error {
@@ -0,0 +1,104 @@
+//! Authenticates the requests with the client certificate of their TLS connection, so that
+//! mutual TLS can carry the identity of the client without an `Authorization` header.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// The API keys the client certificates authenticate as, by their SHA-256 fingerprint or their
+/// subject, e.g.
+///
+/// ```json
+/// {
+///   "fingerprints": { "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08": "<key uid>" },
+///   "subjects": { "CN=backend, O=Acme": "<key uid>" }
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientCertificateKeys {
+    #[serde(default)]
+    fingerprints: HashMap<String, Uuid>,
+    #[serde(default)]
+    subjects: HashMap<String, Uuid>,
+}
+
+impl ClientCertificateKeys {
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Could not read the client keys file `{}`: {e}", path.display())
+        })?;
+        let ClientCertificateKeys { fingerprints, subjects } = serde_json::from_str(&content)
+            .map_err(|e| {
+                anyhow::anyhow!("Could not parse the client keys file `{}`: {e}", path.display())
+            })?;
+
+        Ok(Self {
+            fingerprints: fingerprints
+                .into_iter()
+                .map(|(fingerprint, uid)| (normalize_fingerprint(&fingerprint), uid))
+                .collect(),
+            subjects: subjects
+                .into_iter()
+                .map(|(subject, uid)| (normalize_subject(&subject), uid))
+                .collect(),
+        })
+    }
+
+    /// Returns the uid of the API key the DER-encoded certificate authenticates as, if any.
+    ///
+    /// The fingerprint of the certificate takes precedence over its subject.
+    pub fn key_of(&self, certificate: &[u8]) -> Option<Uuid> {
+        let fingerprint: String =
+            Sha256::digest(certificate).iter().map(|byte| format!("{byte:02x}")).collect();
+        if let Some(uid) = self.fingerprints.get(&fingerprint) {
+            return Some(*uid);
+        }
+
+        if self.subjects.is_empty() {
+            return None;
+        }
+        let (_, certificate) = x509_parser::parse_x509_certificate(certificate).ok()?;
+        self.subjects.get(&normalize_subject(&certificate.subject().to_string())).copied()
+    }
+
+    /// Returns the callback storing the API key of the client certificate in the extensions of
+    /// each new TLS connection.
+    pub fn on_connect(
+        self: Arc<Self>,
+    ) -> impl Fn(&dyn Any, &mut Extensions) + Send + Sync + 'static {
+        move |connection, extensions| {
+            let Some(stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else { return };
+            let (_, session) = stream.get_ref();
+            // the first certificate is the one of the client, the others are its issuers
+            let Some(certificate) = session.peer_certificates().and_then(|certs| certs.first())
+            else {
+                return;
+            };
+            if let Some(uid) = self.key_of(certificate) {
+                extensions.insert(ClientCertificateKey(uid));
+            }
+        }
+    }
+}
+
+/// The uid of the API key the client certificate of a connection authenticates as.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCertificateKey(pub Uuid);
+
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint.chars().filter(|c| *c != ':').collect::<String>().to_lowercase()
+}
+
+fn normalize_subject(subject: &str) -> String {
+    subject.split(',').map(str::trim).collect::<Vec<_>>().join(", ")
+}
@@ -6,6 +6,10 @@ pub enum AuthenticationError {
     MissingAuthorizationHeader,
     #[error("The provided API key is invalid.")]
     InvalidToken,
+    #[error("The provided tenant token cannot access the `{0}` route.")]
+    TenantTokenAccessingUnauthorizedRoute(&'static str),
+    #[error("The provided tenant token cannot access the `{0}` attribute.")]
+    TenantTokenAccessingUnauthorizedAttribute(String),
     // Triggered on configuration error.
     #[error("An internal error has occurred. `Irretrievable state`.")]
     IrretrievableState,
@@ -18,6 +22,10 @@ impl ErrorCode for AuthenticationError {
         match self {
             AuthenticationError::MissingAuthorizationHeader => Code::MissingAuthorizationHeader,
             AuthenticationError::InvalidToken => Code::InvalidApiKey,
+            AuthenticationError::TenantTokenAccessingUnauthorizedRoute(_) => Code::InvalidApiKey,
+            AuthenticationError::TenantTokenAccessingUnauthorizedAttribute(_) => {
+                Code::InvalidApiKey
+            }
             AuthenticationError::IrretrievableState => Code::Internal,
             AuthenticationError::MissingMasterKey => Code::MissingMasterKey,
         }
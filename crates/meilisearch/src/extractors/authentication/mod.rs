@@ -1,3 +1,4 @@
+pub mod client_certificate;
 mod error;
 
 use std::marker::PhantomData;
@@ -6,11 +7,13 @@ use std::pin::Pin;
 
 use actix_web::web::Data;
 use actix_web::FromRequest;
+use client_certificate::ClientCertificateKey;
 pub use error::AuthenticationError;
 use futures::future::err;
 use futures::Future;
-use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_auth::{AuthController, AuthFilter, TenantTokenRoute};
 use meilisearch_types::error::{Code, ResponseError};
+use uuid::Uuid;
 
 use self::policies::AuthError;
 
@@ -25,6 +28,15 @@ impl<P, D> GuardedData<P, D> {
         &self.filters
     }
 
+    /// Returns an error if the tenant token used can't access the route.
+    pub fn check_route(&self, route: TenantTokenRoute) -> Result<(), AuthenticationError> {
+        if self.filters.is_route_authorized(route) {
+            Ok(())
+        } else {
+            Err(AuthenticationError::TenantTokenAccessingUnauthorizedRoute(route.name()))
+        }
+    }
+
     async fn auth_bearer(
         auth: Data<AuthController>,
         token: String,
@@ -42,10 +54,32 @@ impl<P, D> GuardedData<P, D> {
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             Err(_) if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
+            Err(e @ AuthError::RateLimitExceeded { .. }) => Err(e.into()),
             Err(e) => Err(ResponseError::from_msg(e.to_string(), Code::InvalidApiKey)),
         }
     }
 
+    /// Authenticates with the API key the client certificate of the connection is mapped to.
+    async fn auth_client_certificate(
+        auth: Data<AuthController>,
+        uid: Uuid,
+        index: Option<String>,
+        data: Option<D>,
+    ) -> Result<Self, ResponseError>
+    where
+        P: Policy + 'static,
+    {
+        let token = match auth.generate_accepted_keys(uid) {
+            Ok(keys) => keys.into_iter().next(),
+            // the key has been deleted since the server started
+            Err(_) => None,
+        };
+        match token {
+            Some(token) => Self::auth_bearer(auth, token, index, data).await,
+            None => Err(AuthenticationError::InvalidToken.into()),
+        }
+    }
+
     async fn auth_token(auth: Data<AuthController>, data: Option<D>) -> Result<Self, ResponseError>
     where
         P: Policy + 'static,
@@ -117,7 +151,15 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                         Box::pin(err(AuthenticationError::MissingAuthorizationHeader.into()))
                     }
                 },
-                None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                None => match req.conn_data::<ClientCertificateKey>() {
+                    Some(ClientCertificateKey(uid)) => Box::pin(Self::auth_client_certificate(
+                        auth,
+                        *uid,
+                        req.match_info().get("index_uid").map(String::from),
+                        req.app_data::<D>().cloned(),
+                    )),
+                    None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                },
             },
             None => Box::pin(err(AuthenticationError::IrretrievableState.into())),
         }
@@ -133,9 +175,11 @@ pub trait Policy {
 }
 
 pub mod policies {
+    use std::collections::BTreeSet;
+
     use actix_web::web::Data;
     use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
-    use meilisearch_auth::{AuthController, AuthFilter, SearchRules};
+    use meilisearch_auth::{AuthController, AuthFilter, SearchRules, TenantTokenRoute};
     use meilisearch_types::error::{Code, ErrorCode};
     // reexport actions in policies in order to be used in routes configuration.
     pub use meilisearch_types::keys::{actions, Action};
@@ -147,7 +191,7 @@ pub mod policies {
 
     enum TenantTokenOutcome {
         NotATenantToken,
-        Valid(Uuid, SearchRules),
+        Valid(Uuid, SearchRules, Option<BTreeSet<TenantTokenRoute>>),
     }
 
     #[derive(thiserror::Error, Debug)]
@@ -172,6 +216,8 @@ pub mod policies {
         CouldNotDecodeTenantToken(jsonwebtoken::errors::Error),
         #[error("Invalid action `{0}`.")]
         InternalInvalidAction(u8),
+        #[error("Too many requests were made with this API key. Retry in {} seconds.", retry_after.as_secs_f64().ceil())]
+        RateLimitExceeded { retry_after: std::time::Duration },
     }
 
     impl From<jsonwebtoken::errors::Error> for AuthError {
@@ -189,6 +235,7 @@ pub mod policies {
         fn error_code(&self) -> Code {
             match self {
                 AuthError::InternalInvalidAction(_) => Code::Internal,
+                AuthError::RateLimitExceeded { .. } => Code::TooManyApiKeyRequests,
                 _ => Code::InvalidApiKey,
             }
         }
@@ -239,10 +286,10 @@ pub mod policies {
                 return Ok(AuthFilter::default());
             }
 
-            let (key_uuid, search_rules) =
+            let (key_uuid, search_rules, routes) =
                 match ActionPolicy::<A>::authenticate_tenant_token(&auth, token) {
-                    Ok(TenantTokenOutcome::Valid(key_uuid, search_rules)) => {
-                        (key_uuid, Some(search_rules))
+                    Ok(TenantTokenOutcome::Valid(key_uuid, search_rules, routes)) => {
+                        (key_uuid, Some(search_rules), routes)
                     }
                     Ok(TenantTokenOutcome::NotATenantToken)
                     | Err(AuthError::InvalidTenantToken) => (
@@ -250,6 +297,7 @@ pub mod policies {
                             .map_err(|_e| AuthError::InvalidApiKey)?
                             .ok_or(AuthError::InvalidApiKey)?,
                         None,
+                        None,
                     ),
                     Err(e) => return Err(e),
                 };
@@ -257,7 +305,7 @@ pub mod policies {
             // check that the indexes are allowed
             let action = Action::from_repr(A).ok_or(AuthError::InternalInvalidAction(A))?;
             let auth_filter = auth
-                .get_key_filters(key_uuid, search_rules)
+                .get_key_filters(key_uuid, search_rules, routes)
                 .map_err(|_e| AuthError::InvalidApiKey)?;
 
             // First check if the index is authorized in the tenant token, this is a public
@@ -287,6 +335,12 @@ pub mod policies {
                 }
             }
             if auth.is_key_authorized(key_uuid, action, index).unwrap_or(false) {
+                if let Some(retry_after) = auth
+                    .register_request(key_uuid, action)
+                    .map_err(|_e| AuthError::InvalidApiKey)?
+                {
+                    return Err(AuthError::RateLimitExceeded { retry_after });
+                }
                 return Ok(auth_filter);
             }
 
@@ -307,17 +361,27 @@ pub mod policies {
             let uid = extract_key_id(token)?;
 
             // Check if tenant token is valid.
-            let key = if let Some(key) = auth.generate_key(uid) {
-                key
-            } else {
-                return Err(AuthError::InvalidTenantToken);
+            let mut keys = auth
+                .generate_accepted_keys(uid)
+                .map_err(|_| AuthError::InvalidTenantToken)?
+                .into_iter();
+            let Some(key) = keys.next() else { return Err(AuthError::InvalidTenantToken) };
+
+            let decode_with = |key: String| {
+                decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(key.as_bytes()),
+                    &tenant_token_validation(),
+                )
+            };
+            let data = match decode_with(key) {
+                Ok(data) => data,
+                // the tokens signed before the last rotation are accepted during its overlap.
+                Err(error) => match keys.next() {
+                    Some(previous_key) => decode_with(previous_key).map_err(|_| error)?,
+                    None => return Err(error.into()),
+                },
             };
-
-            let data = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret(key.as_bytes()),
-                &tenant_token_validation(),
-            )?;
 
             // Check if token is expired.
             if let Some(exp) = data.claims.exp {
@@ -327,7 +391,23 @@ pub mod policies {
                 }
             }
 
-            Ok(TenantTokenOutcome::Valid(uid, data.claims.search_rules))
+            Ok(TenantTokenOutcome::Valid(uid, data.claims.search_rules, data.claims.routes))
+        }
+    }
+
+    /// A policy only granting access to the master key, which must be defined.
+    pub struct MasterPolicy;
+
+    impl Policy for MasterPolicy {
+        fn authenticate(
+            auth: Data<AuthController>,
+            token: &str,
+            _index: Option<&str>,
+        ) -> Result<AuthFilter, AuthError> {
+            match auth.get_master_key() {
+                Some(master_key) if master_key == token => Ok(AuthFilter::default()),
+                _ => Err(AuthError::InvalidApiKey),
+            }
         }
     }
 
@@ -337,5 +417,7 @@ pub mod policies {
         search_rules: SearchRules,
         exp: Option<i64>,
         api_key_uid: Uuid,
+        /// The only search routes the tenant token can access, all of them if missing.
+        routes: Option<BTreeSet<TenantTokenRoute>>,
     }
 }
@@ -0,0 +1,148 @@
+//! Consumes the documents published on Kafka topics as a member of a consumer group.
+//!
+//! The offsets of the messages are only committed once the tasks applying them are finished.
+//! The messages are thus delivered at least once: if Meilisearch stops in between, they are
+//! consumed again on the next start.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+use index_scheduler::IndexScheduler;
+use kafka::consumer::{Consumer, FetchOffset, GroupOffsetStorage};
+use meilisearch_types::Document;
+use serde::Deserialize;
+use tracing::{error, warn};
+
+use super::{register_changes, wait_for_tasks, Change};
+use crate::option::IngestionMapping;
+
+/// How long to wait before polling the topics again when they had no messages.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait before reconnecting to the brokers after an error.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Join the consumer group and consume the messages of the topics in a new thread.
+pub fn start(
+    brokers: &str,
+    group: &str,
+    topics: IngestionMapping,
+    index_scheduler: Arc<IndexScheduler>,
+) -> anyhow::Result<()> {
+    let brokers: Vec<String> = brokers
+        .split(',')
+        .map(str::trim)
+        .filter(|broker| !broker.is_empty())
+        .map(String::from)
+        .collect();
+    // We connect once before starting the thread to report the configuration errors right away.
+    let mut consumer = connect(&brokers, group, &topics)?;
+    let group = group.to_string();
+
+    thread::Builder::new().name(String::from("kafka-ingestion")).spawn(move || loop {
+        match consume_messages(&mut consumer, &topics, &index_scheduler) {
+            Ok(0) => thread::sleep(POLL_INTERVAL),
+            Ok(_) => (),
+            Err(e) => {
+                error!("While consuming the Kafka messages: {e:#}");
+                thread::sleep(RETRY_INTERVAL);
+                // The uncommitted messages are fetched again from the last committed offsets.
+                match connect(&brokers, &group, &topics) {
+                    Ok(new_consumer) => consumer = new_consumer,
+                    Err(e) => error!("While reconnecting to Kafka: {e:#}"),
+                }
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
+fn connect(brokers: &[String], group: &str, topics: &IngestionMapping) -> anyhow::Result<Consumer> {
+    let mut builder = Consumer::from_hosts(brokers.to_vec())
+        .with_group(group.to_string())
+        .with_fallback_offset(FetchOffset::Earliest)
+        .with_offset_storage(Some(GroupOffsetStorage::Kafka));
+    for topic in topics.sources() {
+        builder = builder.with_topic(topic.to_string());
+    }
+    builder.create().context("Could not connect to the Kafka brokers")
+}
+
+/// Register the tasks applying the fetched messages, wait for them to finish and commit the offsets,
+/// returns the number of consumed messages.
+fn consume_messages(
+    consumer: &mut Consumer,
+    topics: &IngestionMapping,
+    index_scheduler: &IndexScheduler,
+) -> anyhow::Result<usize> {
+    let message_sets = consumer.poll()?;
+    if message_sets.is_empty() {
+        return Ok(0);
+    }
+
+    let mut consumed = 0;
+    let mut changes = Vec::new();
+    for message_set in message_sets.iter() {
+        let Some(index_uid) = topics.index_of(message_set.topic()) else { continue };
+        for message in message_set.messages() {
+            consumed += 1;
+            match message_changes(index_uid, message.key, message.value) {
+                Ok(message_changes) => changes.extend(message_changes),
+                Err(e) => warn!(
+                    "Ignoring the message at offset {} of the partition {} of `{}`: {e}",
+                    message.offset,
+                    message_set.partition(),
+                    message_set.topic()
+                ),
+            }
+        }
+    }
+
+    let task_ids = register_changes(index_scheduler, changes)?;
+    wait_for_tasks(index_scheduler, &task_ids)?;
+
+    for message_set in message_sets.iter() {
+        consumer.consume_messageset(message_set)?;
+    }
+    consumer.commit_consumed()?;
+    Ok(consumed)
+}
+
+/// The documents of a message.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Documents {
+    One(Document),
+    Many(Vec<Document>),
+}
+
+/// Translate a message into the changes of the documents of the index.
+///
+/// A message without a value, called a tombstone, deletes the document whose id is the key of the message.
+pub(crate) fn message_changes(
+    index_uid: &str,
+    key: &[u8],
+    value: &[u8],
+) -> anyhow::Result<Vec<Change>> {
+    let index_uid = index_uid.to_string();
+    if value.is_empty() {
+        let document_id = std::str::from_utf8(key)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .context("a tombstone must have a valid UTF-8 key")?;
+        return Ok(vec![Change::Delete { index_uid, document_id: document_id.to_string() }]);
+    }
+
+    let documents = match serde_json::from_slice(value)
+        .context("the value must be a JSON object or an array of objects")?
+    {
+        Documents::One(document) => vec![document],
+        Documents::Many(documents) => documents,
+    };
+    Ok(documents
+        .into_iter()
+        .map(|document| Change::Upsert { index_uid: index_uid.clone(), document })
+        .collect())
+}
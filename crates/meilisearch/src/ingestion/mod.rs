@@ -0,0 +1,200 @@
+//! Ingests the documents produced by external systems without requiring any sync code from the user.
+//!
+//! Every connector runs in its own thread, translates the changes of its system into [`Change`]s,
+//! and registers them as regular tasks with [`register_changes`].
+
+#![cfg_attr(not(any(feature = "postgres-cdc", feature = "kafka-ingestion")), allow(dead_code))]
+
+#[cfg(feature = "kafka-ingestion")]
+mod kafka;
+#[cfg(feature = "postgres-cdc")]
+mod postgres;
+#[cfg(test)]
+mod test;
+
+use std::io::Write;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::tasks::{KindWithContent, Status, TaskId};
+use meilisearch_types::Document;
+
+use crate::option::Opt;
+
+/// A change of a document of an index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The document must be added, or replaced if it exists.
+    Upsert { index_uid: String, document: Document },
+    /// The document with this id must be deleted.
+    Delete { index_uid: String, document_id: String },
+    /// All the documents of the index must be deleted.
+    Clear { index_uid: String },
+}
+
+impl Change {
+    fn index_uid(&self) -> &str {
+        match self {
+            Change::Upsert { index_uid, .. }
+            | Change::Delete { index_uid, .. }
+            | Change::Clear { index_uid } => index_uid,
+        }
+    }
+}
+
+/// The consecutive changes of an index that are registered as a single task.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Operation {
+    Upsert(Vec<Document>),
+    Delete(Vec<String>),
+    Clear,
+}
+
+/// Start the connectors enabled in the options.
+pub fn start(opt: &Opt, index_scheduler: &Arc<IndexScheduler>) -> anyhow::Result<()> {
+    if opt.read_only_replica
+        && (opt.experimental_postgres_cdc_url.is_some() || opt.experimental_kafka_brokers.is_some())
+    {
+        anyhow::bail!("the connectors cannot run on a read-only replica, run them on its primary");
+    }
+
+    if let Some(url) = &opt.experimental_postgres_cdc_url {
+        #[cfg(feature = "postgres-cdc")]
+        {
+            // `clap` ensures the tables are specified along the url.
+            let tables = opt.experimental_postgres_cdc_tables.clone().unwrap_or_default();
+            postgres::start(
+                url,
+                &opt.experimental_postgres_cdc_slot,
+                tables,
+                index_scheduler.clone(),
+            )?;
+        }
+        #[cfg(not(feature = "postgres-cdc"))]
+        {
+            let _ = (url, index_scheduler);
+            anyhow::bail!(
+                "`--experimental-postgres-cdc-url` requires Meilisearch to be compiled with the `postgres-cdc` feature"
+            );
+        }
+    }
+
+    if let Some(brokers) = &opt.experimental_kafka_brokers {
+        #[cfg(feature = "kafka-ingestion")]
+        {
+            // `clap` ensures the topics are specified along the brokers.
+            let topics = opt.experimental_kafka_topics.clone().unwrap_or_default();
+            kafka::start(brokers, &opt.experimental_kafka_group, topics, index_scheduler.clone())?;
+        }
+        #[cfg(not(feature = "kafka-ingestion"))]
+        {
+            let _ = (brokers, index_scheduler);
+            anyhow::bail!(
+                "`--experimental-kafka-brokers` requires Meilisearch to be compiled with the `kafka-ingestion` feature"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Group the changes in as few operations as possible, while preserving the order of the changes of each index.
+pub(crate) fn group_changes(changes: Vec<Change>) -> Vec<(String, Operation)> {
+    let mut operations: Vec<(String, Operation)> = Vec::new();
+    for change in changes {
+        let last =
+            operations.iter_mut().rev().find(|(index_uid, _)| index_uid == change.index_uid());
+        match (last, change) {
+            (Some((_, Operation::Upsert(documents))), Change::Upsert { document, .. }) => {
+                documents.push(document)
+            }
+            (Some((_, Operation::Delete(ids))), Change::Delete { document_id, .. }) => {
+                ids.push(document_id)
+            }
+            // Clearing twice in a row is the same as clearing once.
+            (Some((_, Operation::Clear)), Change::Clear { .. }) => (),
+            (_, Change::Upsert { index_uid, document }) => {
+                operations.push((index_uid, Operation::Upsert(vec![document])))
+            }
+            (_, Change::Delete { index_uid, document_id }) => {
+                operations.push((index_uid, Operation::Delete(vec![document_id])))
+            }
+            (_, Change::Clear { index_uid }) => operations.push((index_uid, Operation::Clear)),
+        }
+    }
+    operations
+}
+
+/// Register the tasks applying the changes, returns their ids in order.
+///
+/// The indexes are created when needed, the primary key of the documents is inferred.
+pub fn register_changes(
+    index_scheduler: &IndexScheduler,
+    changes: Vec<Change>,
+) -> anyhow::Result<Vec<TaskId>> {
+    let mut task_ids = Vec::new();
+    for (index_uid, operation) in group_changes(changes) {
+        let task = match operation {
+            Operation::Upsert(documents) => {
+                let (uuid, mut update_file) = index_scheduler.queue.create_update_file(false)?;
+                for document in &documents {
+                    serde_json::to_writer(&mut update_file, document)?;
+                }
+                update_file.flush()?;
+                update_file.persist()?;
+
+                let kind = KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid,
+                    primary_key: None,
+                    method: IndexDocumentsMethod::ReplaceDocuments,
+                    content_file: uuid,
+                    documents_count: documents.len() as u64,
+                    allow_index_creation: true,
+                };
+                match index_scheduler.register(kind, None, false) {
+                    Ok(task) => task,
+                    Err(e) => {
+                        index_scheduler.queue.delete_update_file(uuid)?;
+                        return Err(e.into());
+                    }
+                }
+            }
+            Operation::Delete(documents_ids) => index_scheduler.register(
+                KindWithContent::DocumentDeletion { index_uid, documents_ids },
+                None,
+                false,
+            )?,
+            Operation::Clear => index_scheduler.register(
+                KindWithContent::DocumentClear { index_uid },
+                None,
+                false,
+            )?,
+        };
+        task_ids.push(task.uid);
+    }
+    Ok(task_ids)
+}
+
+/// Block until all the tasks are finished, whether they succeeded, failed, or were canceled.
+pub fn wait_for_tasks(index_scheduler: &IndexScheduler, task_ids: &[TaskId]) -> anyhow::Result<()> {
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+    let query = Query {
+        uids: Some(task_ids.to_vec()),
+        statuses: Some(vec![Status::Enqueued, Status::Processing]),
+        ..Query::default()
+    };
+    loop {
+        let (_, unfinished) =
+            index_scheduler.get_task_ids_from_authorized_indexes(&query, &AuthFilter::default())?;
+        if unfinished == 0 {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
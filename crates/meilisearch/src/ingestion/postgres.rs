@@ -0,0 +1,184 @@
+//! Consumes the changes of a PostgreSQL database through a logical replication slot using the
+//! `wal2json` output plugin.
+//!
+//! The changes are only peeked from the slot, which is advanced once their tasks are registered.
+//! The changes are thus delivered at least once: if Meilisearch stops in between, they are
+//! consumed again on the next start.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::Document;
+use postgres::{Client, NoTls};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{error, warn};
+
+use super::{register_changes, Change};
+use crate::option::IngestionMapping;
+
+/// The maximum number of changes consumed at once, whole transactions are always consumed.
+const MAX_CHANGES_PER_POLL: i32 = 10_000;
+/// How long to wait before polling the slot again when it had no changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait before reconnecting to the database after an error.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Connect to the database, create the slot if needed, and consume its changes in a new thread.
+pub fn start(
+    url: &str,
+    slot: &str,
+    tables: IngestionMapping,
+    index_scheduler: Arc<IndexScheduler>,
+) -> anyhow::Result<()> {
+    // We connect once before starting the thread to report the configuration errors right away.
+    let mut client = connect(url, slot)?;
+    let (url, slot) = (url.to_string(), slot.to_string());
+
+    thread::Builder::new().name(String::from("postgres-cdc")).spawn(move || loop {
+        match consume_changes(&mut client, &slot, &tables, &index_scheduler) {
+            Ok(0) => thread::sleep(POLL_INTERVAL),
+            Ok(_) => (),
+            Err(e) => {
+                error!("While consuming the PostgreSQL changes: {e:#}");
+                thread::sleep(RETRY_INTERVAL);
+                if client.is_closed() {
+                    match connect(&url, &slot) {
+                        Ok(new_client) => client = new_client,
+                        Err(e) => error!("While reconnecting to PostgreSQL: {e:#}"),
+                    }
+                }
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
+fn connect(url: &str, slot: &str) -> anyhow::Result<Client> {
+    let mut client =
+        Client::connect(url, NoTls).context("Could not connect to the PostgreSQL database")?;
+
+    let exists = client
+        .query_opt("SELECT 1 FROM pg_replication_slots WHERE slot_name = $1", &[&slot])?
+        .is_some();
+    if !exists {
+        client
+            .query_one("SELECT pg_create_logical_replication_slot($1, 'wal2json')", &[&slot])
+            .with_context(|| format!("Could not create the `{slot}` replication slot"))?;
+    }
+
+    Ok(client)
+}
+
+/// Register the tasks applying the pending changes of the slot, returns the number of consumed changes.
+fn consume_changes(
+    client: &mut Client,
+    slot: &str,
+    tables: &IngestionMapping,
+    index_scheduler: &IndexScheduler,
+) -> anyhow::Result<usize> {
+    let rows = client.query(
+        "SELECT lsn::text, data FROM pg_logical_slot_peek_changes($1, NULL, $2, 'format-version', '2', 'include-transaction', 'false')",
+        &[&slot, &MAX_CHANGES_PER_POLL],
+    )?;
+    let Some(last_row) = rows.last() else { return Ok(0) };
+    let last_lsn: String = last_row.get(0);
+
+    let mut changes = Vec::new();
+    for row in &rows {
+        let data: &str = row.get(1);
+        let change: Wal2JsonChange = serde_json::from_str(data)
+            .with_context(|| format!("Could not parse the `{data}` change"))?;
+        changes.extend(change.into_changes(tables));
+    }
+    register_changes(index_scheduler, changes)?;
+
+    client.query_one(
+        "SELECT pg_replication_slot_advance($1, $2::text::pg_lsn)",
+        &[&slot, &last_lsn],
+    )?;
+    Ok(rows.len())
+}
+
+/// A change in the version 2 of the `wal2json` format.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Wal2JsonChange {
+    action: String,
+    #[serde(default)]
+    schema: String,
+    #[serde(default)]
+    table: String,
+    /// The new values of the row, for the insertions and updates.
+    #[serde(default)]
+    columns: Vec<Wal2JsonColumn>,
+    /// The values of the replica identity of the row, for the updates and deletions.
+    #[serde(default)]
+    identity: Vec<Wal2JsonColumn>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Wal2JsonColumn {
+    name: String,
+    value: Value,
+}
+
+impl Wal2JsonChange {
+    /// Translate the change of a row into the changes of the documents, ignores the tables that are not synced.
+    pub(crate) fn into_changes(self, tables: &IngestionMapping) -> Vec<Change> {
+        let Wal2JsonChange { action, schema, table, columns, identity } = self;
+        let Some(index_uid) = tables.index_of(&format!("{schema}.{table}")) else {
+            return Vec::new();
+        };
+        let index_uid = index_uid.to_string();
+        let document = || -> Document {
+            columns.iter().map(|column| (column.name.clone(), column.value.clone())).collect()
+        };
+
+        match action.as_str() {
+            "I" => vec![Change::Upsert { index_uid, document: document() }],
+            "U" => {
+                // The document must be moved when the update changed its primary key.
+                let moved = match identity.as_slice() {
+                    [key] => columns
+                        .iter()
+                        .find(|column| column.name == key.name)
+                        .filter(|column| column.value != key.value)
+                        .and_then(|_| document_id(&key.value)),
+                    _ => None,
+                };
+                let upsert = Change::Upsert { index_uid: index_uid.clone(), document: document() };
+                match moved {
+                    Some(document_id) => vec![Change::Delete { index_uid, document_id }, upsert],
+                    None => vec![upsert],
+                }
+            }
+            "D" => match identity.as_slice() {
+                [key] => match document_id(&key.value) {
+                    Some(document_id) => vec![Change::Delete { index_uid, document_id }],
+                    None => {
+                        warn!("Ignoring the deletion of a row of `{schema}.{table}` identified by `{}`, which is not a valid document id", key.value);
+                        Vec::new()
+                    }
+                },
+                _ => {
+                    warn!("Ignoring the deletion of a row of `{schema}.{table}`, the table must be identified by a single column");
+                    Vec::new()
+                }
+            },
+            "T" => vec![Change::Clear { index_uid }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn document_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
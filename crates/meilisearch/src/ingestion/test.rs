@@ -0,0 +1,106 @@
+use meili_snap::snapshot;
+use serde_json::json;
+
+use super::{group_changes, Change, Operation};
+
+fn upsert(index_uid: &str, id: u32) -> Change {
+    let document = json!({ "id": id }).as_object().unwrap().clone();
+    Change::Upsert { index_uid: index_uid.to_string(), document }
+}
+
+fn delete(index_uid: &str, id: u32) -> Change {
+    Change::Delete { index_uid: index_uid.to_string(), document_id: id.to_string() }
+}
+
+#[test]
+fn group_changes_preserves_the_order_of_each_index() {
+    let changes = vec![
+        upsert("doggos", 1),
+        upsert("catto", 1),
+        upsert("doggos", 2),
+        delete("catto", 1),
+        delete("doggos", 1),
+        upsert("doggos", 3),
+        Change::Clear { index_uid: String::from("catto") },
+        Change::Clear { index_uid: String::from("catto") },
+    ];
+
+    let operations: Vec<_> = group_changes(changes)
+        .into_iter()
+        .map(|(index_uid, operation)| match operation {
+            Operation::Upsert(documents) => format!("{index_uid}: upsert {}", documents.len()),
+            Operation::Delete(ids) => format!("{index_uid}: delete {ids:?}"),
+            Operation::Clear => format!("{index_uid}: clear"),
+        })
+        .collect();
+    snapshot!(format!("{operations:#?}"), @r###"
+    [
+        "doggos: upsert 2",
+        "catto: upsert 1",
+        "catto: delete [\"1\"]",
+        "doggos: delete [\"1\"]",
+        "doggos: upsert 1",
+        "catto: clear",
+    ]
+    "###);
+}
+
+#[cfg(feature = "postgres-cdc")]
+#[test]
+fn wal2json_changes() {
+    use super::postgres::Wal2JsonChange;
+
+    let tables: crate::option::IngestionMapping = "public.doggos=doggos".parse().unwrap();
+    let changes = |data: serde_json::Value| -> Vec<Change> {
+        serde_json::from_value::<Wal2JsonChange>(data).unwrap().into_changes(&tables)
+    };
+
+    let insert = changes(json!({
+        "action": "I", "schema": "public", "table": "doggos",
+        "columns": [{ "name": "id", "type": "integer", "value": 1 }, { "name": "name", "type": "text", "value": "kefir" }],
+    }));
+    snapshot!(format!("{insert:?}"), @r###"[Upsert { index_uid: "doggos", document: {"id": Number(1), "name": String("kefir")} }]"###);
+
+    let moved = changes(json!({
+        "action": "U", "schema": "public", "table": "doggos",
+        "columns": [{ "name": "id", "type": "integer", "value": 2 }, { "name": "name", "type": "text", "value": "kefir" }],
+        "identity": [{ "name": "id", "type": "integer", "value": 1 }],
+    }));
+    snapshot!(format!("{moved:?}"), @r###"[Delete { index_uid: "doggos", document_id: "1" }, Upsert { index_uid: "doggos", document: {"id": Number(2), "name": String("kefir")} }]"###);
+
+    let deletion = changes(json!({
+        "action": "D", "schema": "public", "table": "doggos",
+        "identity": [{ "name": "id", "type": "integer", "value": 2 }],
+    }));
+    snapshot!(format!("{deletion:?}"), @r###"[Delete { index_uid: "doggos", document_id: "2" }]"###);
+
+    let truncate = changes(json!({ "action": "T", "schema": "public", "table": "doggos" }));
+    snapshot!(format!("{truncate:?}"), @r###"[Clear { index_uid: "doggos" }]"###);
+
+    let unsynced = changes(json!({
+        "action": "I", "schema": "public", "table": "cattos",
+        "columns": [{ "name": "id", "type": "integer", "value": 1 }],
+    }));
+    snapshot!(format!("{unsynced:?}"), @"[]");
+}
+
+#[cfg(feature = "kafka-ingestion")]
+#[test]
+fn kafka_messages() {
+    use super::kafka::message_changes;
+
+    let changes = message_changes("doggos", b"1", br#"{ "id": 1, "name": "kefir" }"#).unwrap();
+    snapshot!(format!("{changes:?}"), @r###"[Upsert { index_uid: "doggos", document: {"id": Number(1), "name": String("kefir")} }]"###);
+
+    let changes = message_changes("doggos", b"", br#"[{ "id": 1 }, { "id": 2 }]"#).unwrap();
+    snapshot!(format!("{changes:?}"), @r###"[Upsert { index_uid: "doggos", document: {"id": Number(1)} }, Upsert { index_uid: "doggos", document: {"id": Number(2)} }]"###);
+
+    let tombstone = message_changes("doggos", b"1", b"").unwrap();
+    snapshot!(format!("{tombstone:?}"), @r###"[Delete { index_uid: "doggos", document_id: "1" }]"###);
+
+    let error = message_changes("doggos", b"", b"").unwrap_err();
+    snapshot!(error, @"a tombstone must have a valid UTF-8 key");
+
+    let error = message_changes("doggos", b"1", b"42").unwrap_err();
+    snapshot!(error, @"the value must be a JSON object or an array of objects");
+}
@@ -4,6 +4,7 @@ pub mod error;
 pub mod analytics;
 #[macro_use]
 pub mod extractors;
+pub mod ingestion;
 pub mod metrics;
 pub mod middleware;
 pub mod option;
@@ -12,6 +13,7 @@ mod option_test;
 pub mod routes;
 pub mod search;
 pub mod search_queue;
+pub mod task_events;
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
@@ -32,9 +34,10 @@ use analytics::Analytics;
 use anyhow::bail;
 use error::PayloadError;
 use extractors::payload::PayloadConfig;
+use index_scheduler::upgrade::UpgradeReport;
 use index_scheduler::versioning::Versioning;
 use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
-use meilisearch_auth::{open_auth_store_env, AuthController};
+use meilisearch_auth::{open_auth_store_env, AuthController, EncryptionKey};
 use meilisearch_types::milli::constants::VERSION_MAJOR;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
 use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
@@ -43,11 +46,12 @@ use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{
     create_current_version_file, get_version, VersionFileError, VERSION_MINOR, VERSION_PATCH,
 };
+use meilisearch_types::webhooks::TaskWebhook;
 use meilisearch_types::{compression, heed, milli, VERSION_FILE_NAME};
 pub use option::Opt;
-use option::ScheduleSnapshot;
+use option::{ScheduleDump, ScheduleSnapshot};
 use search_queue::SearchQueue;
-use tracing::{error, info_span};
+use tracing::{error, info, info_span};
 use tracing_subscriber::filter::Targets;
 
 use crate::error::MeilisearchHttpError;
@@ -80,6 +84,9 @@ const DEFAULT_INDEX_COUNT: usize = 4;
 #[cfg(not(windows))]
 const DEFAULT_INDEX_COUNT: usize = 20;
 
+/// The longest time between two checks for the API keys expired for longer than the retention.
+const EXPIRED_KEYS_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Check if a db is empty. It does not provide any information on the
 /// validity of the data in it.
 /// We consider a database as non empty when it's a non empty directory.
@@ -217,18 +224,44 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         update_file_path: opt.db_path.join("update_files"),
         indexes_path: opt.db_path.join("indexes"),
         snapshots_path: opt.snapshot_dir.clone(),
+        snapshot_s3: opt.snapshot_s3_options()?,
+        restore_points: opt.restore_points()?,
+        change_stream: opt.experimental_change_stream,
+        task_log: opt.experimental_task_log,
+        index_aliases: opt.experimental_index_aliases,
         dumps_path: opt.dump_dir.clone(),
-        webhook_url: opt.task_webhook_url.as_ref().map(|url| url.to_string()),
-        webhook_authorization_header: opt.task_webhook_authorization_header.clone(),
+        import_url: opt.import_url_options(),
+        webhooks: opt
+            .task_webhook_url
+            .iter()
+            .map(|url| {
+                TaskWebhook::new(url.to_string(), opt.task_webhook_authorization_header.clone())
+            })
+            .chain(opt.task_webhooks.iter().flat_map(|webhooks| webhooks.0.iter().cloned()))
+            .collect(),
+        task_events: task_events::start(opt)?,
         task_db_size: opt.max_task_db_size.as_u64() as usize,
         index_base_map_size: opt.max_index_size.as_u64() as usize,
         enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
         indexer_config: Arc::new((&opt.indexer_options).try_into()?),
         autobatching_enabled: true,
-        cleanup_enabled: !opt.experimental_replication_parameters,
+        cleanup_enabled: !opt.replication_parameters(),
+        read_only_replica: opt.read_only_replica,
         max_number_of_tasks: 1_000_000,
         max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
         batched_tasks_size_limit: opt.experimental_limit_batched_tasks_total_size,
+        max_batched_documents: opt.experimental_max_batched_documents,
+        autobatching_max_wait: opt.experimental_autobatching_max_wait_ms.map(Duration::from_millis),
+        index_autobatching: opt
+            .experimental_index_autobatching
+            .clone()
+            .map(|index_autobatching| index_autobatching.0)
+            .unwrap_or_default(),
+        max_concurrent_batches: opt.experimental_max_concurrent_batches,
+        fast_lane_max_documents: opt.experimental_fast_lane_max_documents,
+        checkpoint_documents: opt.experimental_batch_checkpoint_documents,
+        max_enqueued_tasks: opt.experimental_max_enqueued_tasks,
+        disk_watermark: opt.experimental_disk_watermark,
         index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().as_u64() as usize,
         index_count: DEFAULT_INDEX_COUNT,
         instance_features: opt.to_instance_features(),
@@ -310,11 +343,17 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
     let auth_controller = Arc::new(auth_controller);
     if let ScheduleSnapshot::Enabled(snapshot_delay) = opt.schedule_snapshot {
         let snapshot_delay = Duration::from_secs(snapshot_delay);
+        let maintenance_window = opt.experimental_maintenance_window;
         let index_scheduler = index_scheduler.clone();
         thread::Builder::new()
             .name(String::from("register-snapshot-tasks"))
             .spawn(move || loop {
                 thread::sleep(snapshot_delay);
+                // A snapshot due outside of the maintenance window is deferred until it opens
+                if let Some(maintenance_window) = maintenance_window {
+                    let now = time::OffsetDateTime::now_utc().time();
+                    thread::sleep(maintenance_window.time_until_open(now));
+                }
                 if let Err(e) =
                     index_scheduler.register(KindWithContent::SnapshotCreation, None, false)
                 {
@@ -324,6 +363,75 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
             .unwrap();
     }
 
+    // We create a loop in a thread that registers dumpCreation tasks
+    if let ScheduleDump::Enabled(dump_delay) = opt.schedule_dump {
+        let dump_delay = Duration::from_secs(dump_delay);
+        let maintenance_window = opt.experimental_maintenance_window;
+        let db_path = opt.db_path.clone();
+        let index_scheduler = index_scheduler.clone();
+        let auth_controller = auth_controller.clone();
+        thread::Builder::new()
+            .name(String::from("register-dump-tasks"))
+            .spawn(move || loop {
+                thread::sleep(dump_delay);
+                // A dump due outside of the maintenance window is deferred until it opens
+                if let Some(maintenance_window) = maintenance_window {
+                    let now = time::OffsetDateTime::now_utc().time();
+                    thread::sleep(maintenance_window.time_until_open(now));
+                }
+                let keys = match auth_controller.list_keys() {
+                    Ok(keys) => keys,
+                    Err(e) => {
+                        error!("Error while registering dump: {}", e);
+                        continue;
+                    }
+                };
+                let task = KindWithContent::DumpCreation {
+                    keys,
+                    instance_uid: analytics::find_user_id(&db_path),
+                    index_uids: None,
+                    skip_tasks: false,
+                };
+                if let Err(e) = index_scheduler.register(task, None, false) {
+                    error!("Error while registering dump: {}", e);
+                }
+            })
+            .unwrap();
+    }
+
+    // We create a loop in a thread that deletes the keys expired for longer than the retention
+    if let Some(retention) = opt.expired_keys_retention {
+        let retention = Duration::from_secs(retention);
+        let read_only_replica = opt.read_only_replica;
+        let index_scheduler = index_scheduler.clone();
+        let auth_controller = auth_controller.clone();
+        thread::Builder::new()
+            .name(String::from("delete-expired-keys"))
+            .spawn(move || loop {
+                thread::sleep(retention.clamp(Duration::from_secs(1), EXPIRED_KEYS_CHECK_INTERVAL));
+                // No key is written in read-only mode, and the keys of a replica come from its primary
+                if read_only_replica || index_scheduler.features().check_writes_allowed().is_err() {
+                    continue;
+                }
+                match auth_controller.delete_expired_keys(retention) {
+                    Ok(keys) => {
+                        for key in keys {
+                            info!(
+                                key_uid = %key.uid,
+                                key_name = ?key.name,
+                                expires_at = ?key.expires_at,
+                                "Deleted an expired API key"
+                            );
+                        }
+                    }
+                    Err(e) => error!("Error while deleting the expired API keys: {}", e),
+                }
+            })
+            .unwrap();
+    }
+
+    ingestion::start(opt, &index_scheduler)?;
+
     Ok((index_scheduler, auth_controller))
 }
 
@@ -336,9 +444,11 @@ fn open_or_create_database_unchecked(
 ) -> anyhow::Result<(IndexScheduler, AuthController)> {
     // we don't want to create anything in the data.ms yet, thus we
     // wrap our two builders in a closure that'll be executed later.
+    let encryption_key =
+        opt.auth_encryption_key.as_deref().map(EncryptionKey::from_base64).transpose()?;
     std::fs::create_dir_all(&index_scheduler_opt.auth_path)?;
     let auth_env = open_auth_store_env(&index_scheduler_opt.auth_path).unwrap();
-    let auth_controller = AuthController::new(auth_env.clone(), &opt.master_key);
+    let auth_controller = AuthController::new(auth_env.clone(), &opt.master_key, encryption_key);
     let index_scheduler_builder = || -> anyhow::Result<_> {
         Ok(IndexScheduler::new(index_scheduler_opt, auth_env, version)?)
     };
@@ -389,6 +499,20 @@ fn check_version(
     Ok((db_major, db_minor, db_patch))
 }
 
+/// Reports what a dumpless upgrade of the database located at `db_path` to the current
+/// Meilisearch binary would imply, without modifying the database.
+pub fn upgrade_check(opt: &Opt, db_path: &Path) -> anyhow::Result<UpgradeReport> {
+    let from = get_version(db_path)?;
+    let to = (VERSION_MAJOR.parse()?, VERSION_MINOR.parse()?, VERSION_PATCH.parse()?);
+
+    index_scheduler::upgrade::upgrade_check(
+        &db_path.join("tasks"),
+        opt.max_task_db_size.as_u64() as usize,
+        from,
+        to,
+    )
+}
+
 /// Persists the version of the current Meilisearch binary to a VERSION file
 pub fn update_version_file_for_dumpless_upgrade(
     opt: &Opt,
@@ -496,13 +620,16 @@ fn import_dump(
         keys.push(key);
     }
 
-    // 3. Import the runtime features and network
+    // 3. Import the runtime features, network and index aliases
     let features = dump_reader.features()?.unwrap_or_default();
     index_scheduler.put_runtime_features(features)?;
 
     let network = dump_reader.network()?.cloned().unwrap_or_default();
     index_scheduler.put_network(network)?;
 
+    let index_aliases = dump_reader.index_aliases()?.cloned().unwrap_or_default();
+    index_scheduler.put_index_aliases(index_aliases)?;
+
     let indexer_config = index_scheduler.indexer_config();
 
     // /!\ The tasks must be imported AFTER importing the indexes or else the scheduler might
@@ -574,6 +701,11 @@ fn import_dump(
         tracing::info!("All documents successfully imported.");
 
         index_scheduler.refresh_index_stats(&uid)?;
+
+        // 4.4 Import the remote source the documents are refreshed from.
+        if let Some(source) = index_reader.source() {
+            index_scheduler.import_index_source(&uid, source.clone())?;
+        }
     }
 
     // 5. Import the queue
@@ -29,6 +29,10 @@ use tracing_subscriber::Layer;
 #[global_allocator]
 static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+/// How long the processing batches can take to reach a checkpoint when shutting down,
+/// below the 30 seconds most orchestrators wait before killing the process.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(25);
+
 fn default_log_route_layer() -> LogRouteType {
     None.with_filter(tracing_subscriber::filter::Targets::new().with_target("", LevelFilter::OFF))
 }
@@ -93,11 +97,22 @@ async fn try_main() -> anyhow::Result<()> {
 
     std::panic::set_hook(Box::new(on_panic));
 
+    if let Some(db_path) = &opt.upgrade_check {
+        let report = meilisearch::upgrade_check(&opt, db_path)?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
     anyhow::ensure!(
         !(cfg!(windows) && opt.experimental_reduce_indexing_memory_usage),
         "The `experimental-reduce-indexing-memory-usage` flag is not supported on Windows"
     );
 
+    anyhow::ensure!(
+        !(opt.read_only_replica && opt.master_key.is_none()),
+        "A read-only replica must be launched with a master key, the primary instance replicates its tasks and keys with it"
+    );
+
     let log_handle = setup(&opt)?;
 
     match (opt.env.as_ref(), &opt.master_key) {
@@ -129,9 +144,24 @@ async fn try_main() -> anyhow::Result<()> {
 
     print_launch_resume(&opt, analytics.clone(), config_read_from);
 
+    let checkpointed_shutdown = opt.experimental_batch_checkpoint_documents.is_some();
+    let shutdown_index_scheduler = index_scheduler.clone();
     tokio::spawn(async move {
-        tokio::signal::ctrl_c().await.unwrap();
-        std::process::exit(130);
+        let exit_code = shutdown_signal(checkpointed_shutdown).await;
+        if checkpointed_shutdown {
+            tracing::info!(
+                "Shutting down, waiting for the processing batches to reach a checkpoint."
+            );
+            let stopped = tokio::task::spawn_blocking(move || {
+                shutdown_index_scheduler.shutdown(SHUTDOWN_TIMEOUT)
+            })
+            .await
+            .unwrap_or(false);
+            if !stopped {
+                tracing::warn!("Some batches were still processing after {SHUTDOWN_TIMEOUT:?}, they will be processed again after the restart.");
+            }
+        }
+        std::process::exit(exit_code);
     });
 
     run_http(index_scheduler, auth_controller, opt, log_handle, Arc::new(analytics)).await?;
@@ -139,6 +169,25 @@ async fn try_main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Wait for a CTRL-C, or a SIGTERM when `terminate` is set, and return the exit code it implies.
+async fn shutdown_signal(terminate: bool) -> i32 {
+    #[cfg(unix)]
+    if terminate {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate()).unwrap();
+        return tokio::select! {
+            _ = tokio::signal::ctrl_c() => 130,
+            _ = sigterm.recv() => 143,
+        };
+    }
+    #[cfg(not(unix))]
+    let _ = terminate;
+
+    tokio::signal::ctrl_c().await.unwrap();
+    130
+}
+
 async fn run_http(
     index_scheduler: Arc<IndexScheduler>,
     auth_controller: Arc<AuthController>,
@@ -178,7 +227,12 @@ async fn run_http(
     .disable_signals()
     .keep_alive(KeepAlive::Os);
 
+    let client_certificate_keys = opt_clone.get_client_certificate_keys()?;
     if let Some(config) = opt_clone.get_ssl_config()? {
+        let http_server = match client_certificate_keys {
+            Some(keys) => http_server.on_connect(Arc::new(keys).on_connect()),
+            None => http_server,
+        };
         http_server.bind_rustls_0_23(opt_clone.http_addr, config)?.run().await?;
     } else {
         http_server.bind(&opt_clone.http_addr)?.run().await?;
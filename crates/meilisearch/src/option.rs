@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::env::VarError;
 use std::ffi::OsStr;
 use std::fmt::Display;
@@ -7,13 +8,18 @@ use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{env, fmt, fs};
 
 use byte_unit::{Byte, ParseError, UnitType};
 use clap::Parser;
+use index_scheduler::{ImportUrlOptions, S3SnapshotOptions};
+use meilisearch_types::autobatching::AutobatchingLimits;
 use meilisearch_types::features::InstanceTogglableFeatures;
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
+use meilisearch_types::webhooks::TaskWebhook;
 use rustls::server::{ServerSessionMemoryCache, WebPkiClientVerifier};
 use rustls::RootCertStore;
 use rustls_pemfile::{certs, rsa_private_keys};
@@ -21,14 +27,19 @@ use serde::{Deserialize, Serialize};
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 use url::Url;
 
+use crate::extractors::authentication::client_certificate::ClientCertificateKeys;
+
 const POSSIBLE_ENV: [&str; 2] = ["development", "production"];
 
 const MEILI_DB_PATH: &str = "MEILI_DB_PATH";
 const MEILI_HTTP_ADDR: &str = "MEILI_HTTP_ADDR";
 const MEILI_MASTER_KEY: &str = "MEILI_MASTER_KEY";
+const MEILI_AUTH_ENCRYPTION_KEY: &str = "MEILI_AUTH_ENCRYPTION_KEY";
+const MEILI_EXPIRED_KEYS_RETENTION: &str = "MEILI_EXPIRED_KEYS_RETENTION";
 const MEILI_ENV: &str = "MEILI_ENV";
 const MEILI_TASK_WEBHOOK_URL: &str = "MEILI_TASK_WEBHOOK_URL";
 const MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER: &str = "MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER";
+const MEILI_TASK_WEBHOOKS: &str = "MEILI_TASK_WEBHOOKS";
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
@@ -38,19 +49,27 @@ const MEILI_SSL_OCSP_PATH: &str = "MEILI_SSL_OCSP_PATH";
 const MEILI_SSL_REQUIRE_AUTH: &str = "MEILI_SSL_REQUIRE_AUTH";
 const MEILI_SSL_RESUMPTION: &str = "MEILI_SSL_RESUMPTION";
 const MEILI_SSL_TICKETS: &str = "MEILI_SSL_TICKETS";
+const MEILI_SSL_CLIENT_KEYS_PATH: &str = "MEILI_SSL_CLIENT_KEYS_PATH";
 const MEILI_IMPORT_SNAPSHOT: &str = "MEILI_IMPORT_SNAPSHOT";
 const MEILI_IGNORE_MISSING_SNAPSHOT: &str = "MEILI_IGNORE_MISSING_SNAPSHOT";
 const MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS: &str = "MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS";
 const MEILI_SNAPSHOT_DIR: &str = "MEILI_SNAPSHOT_DIR";
+const MEILI_SNAPSHOT_S3_ENDPOINT: &str = "MEILI_SNAPSHOT_S3_ENDPOINT";
+const MEILI_SNAPSHOT_S3_REGION: &str = "MEILI_SNAPSHOT_S3_REGION";
+const MEILI_SNAPSHOT_S3_ACCESS_KEY: &str = "MEILI_SNAPSHOT_S3_ACCESS_KEY";
+const MEILI_SNAPSHOT_S3_SECRET_KEY: &str = "MEILI_SNAPSHOT_S3_SECRET_KEY";
+const MEILI_SNAPSHOT_S3_PATH_STYLE: &str = "MEILI_SNAPSHOT_S3_PATH_STYLE";
 const MEILI_SCHEDULE_SNAPSHOT: &str = "MEILI_SCHEDULE_SNAPSHOT";
 const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
+const MEILI_SCHEDULE_DUMP: &str = "MEILI_SCHEDULE_DUMP";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
 const MEILI_EXPERIMENTAL_LOGS_MODE: &str = "MEILI_EXPERIMENTAL_LOGS_MODE";
 const MEILI_EXPERIMENTAL_DUMPLESS_UPGRADE: &str = "MEILI_EXPERIMENTAL_DUMPLESS_UPGRADE";
 const MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS: &str = "MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS";
+const MEILI_READ_ONLY_REPLICA: &str = "MEILI_READ_ONLY_REPLICA";
 const MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE: &str = "MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE";
 const MEILI_EXPERIMENTAL_CONTAINS_FILTER: &str = "MEILI_EXPERIMENTAL_CONTAINS_FILTER";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
@@ -63,17 +82,48 @@ const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
 const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
+const MEILI_EXPERIMENTAL_MAX_BATCHED_DOCUMENTS: &str = "MEILI_EXPERIMENTAL_MAX_BATCHED_DOCUMENTS";
+const MEILI_EXPERIMENTAL_AUTOBATCHING_MAX_WAIT_MS: &str =
+    "MEILI_EXPERIMENTAL_AUTOBATCHING_MAX_WAIT_MS";
+const MEILI_EXPERIMENTAL_INDEX_AUTOBATCHING: &str = "MEILI_EXPERIMENTAL_INDEX_AUTOBATCHING";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
+const MEILI_EXPERIMENTAL_FAST_LANE_MAX_DOCUMENTS: &str =
+    "MEILI_EXPERIMENTAL_FAST_LANE_MAX_DOCUMENTS";
+const MEILI_EXPERIMENTAL_BATCH_CHECKPOINT_DOCUMENTS: &str =
+    "MEILI_EXPERIMENTAL_BATCH_CHECKPOINT_DOCUMENTS";
+const MEILI_EXPERIMENTAL_MAX_ENQUEUED_TASKS: &str = "MEILI_EXPERIMENTAL_MAX_ENQUEUED_TASKS";
+const MEILI_EXPERIMENTAL_DISK_WATERMARK: &str = "MEILI_EXPERIMENTAL_DISK_WATERMARK";
+const MEILI_EXPERIMENTAL_MAINTENANCE_WINDOW: &str = "MEILI_EXPERIMENTAL_MAINTENANCE_WINDOW";
+const MEILI_EXPERIMENTAL_RESTORE_POINTS: &str = "MEILI_EXPERIMENTAL_RESTORE_POINTS";
+const MEILI_EXPERIMENTAL_CHANGE_STREAM: &str = "MEILI_EXPERIMENTAL_CHANGE_STREAM";
+const MEILI_EXPERIMENTAL_TASK_LOG: &str = "MEILI_EXPERIMENTAL_TASK_LOG";
+const MEILI_EXPERIMENTAL_INDEX_ALIASES: &str = "MEILI_EXPERIMENTAL_INDEX_ALIASES";
+const MEILI_EXPERIMENTAL_IMPORT_URL_ALLOWED_HOSTS: &str =
+    "MEILI_EXPERIMENTAL_IMPORT_URL_ALLOWED_HOSTS";
+const MEILI_EXPERIMENTAL_POSTGRES_CDC_URL: &str = "MEILI_EXPERIMENTAL_POSTGRES_CDC_URL";
+const MEILI_EXPERIMENTAL_POSTGRES_CDC_SLOT: &str = "MEILI_EXPERIMENTAL_POSTGRES_CDC_SLOT";
+const MEILI_EXPERIMENTAL_POSTGRES_CDC_TABLES: &str = "MEILI_EXPERIMENTAL_POSTGRES_CDC_TABLES";
+const MEILI_EXPERIMENTAL_KAFKA_BROKERS: &str = "MEILI_EXPERIMENTAL_KAFKA_BROKERS";
+const MEILI_EXPERIMENTAL_KAFKA_GROUP: &str = "MEILI_EXPERIMENTAL_KAFKA_GROUP";
+const MEILI_EXPERIMENTAL_KAFKA_TOPICS: &str = "MEILI_EXPERIMENTAL_KAFKA_TOPICS";
+const MEILI_EXPERIMENTAL_TASK_EVENTS_URL: &str = "MEILI_EXPERIMENTAL_TASK_EVENTS_URL";
+const MEILI_EXPERIMENTAL_TASK_EVENTS_STREAM: &str = "MEILI_EXPERIMENTAL_TASK_EVENTS_STREAM";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
 const DEFAULT_ENV: &str = "development";
 const DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT: &str = "100 MB";
 const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
+const DEFAULT_SNAPSHOT_S3_REGION: &str = "us-east-1";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
+const DEFAULT_DUMP_INTERVAL_SEC_STR: &str = "86400";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
+const DEFAULT_POSTGRES_CDC_SLOT: &str = "meilisearch";
+const DEFAULT_KAFKA_GROUP: &str = "meilisearch";
+const DEFAULT_TASK_EVENTS_STREAM: &str = "meilisearch-tasks";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
@@ -195,6 +245,16 @@ pub struct Opt {
     #[clap(long, env = MEILI_MASTER_KEY)]
     pub master_key: Option<String>,
 
+    /// Encrypts the API keys stored in the database with this base64-encoded 256-bit key, so that
+    /// the database files don't give them away. Once set, it must be provided at every launch.
+    #[clap(long, env = MEILI_AUTH_ENCRYPTION_KEY)]
+    pub auth_encryption_key: Option<String>,
+
+    /// Deletes the API keys that expired more than this number of seconds ago. The expired
+    /// keys are kept by default.
+    #[clap(long, env = MEILI_EXPIRED_KEYS_RETENTION, value_name = "SECONDS")]
+    pub expired_keys_retention: Option<u64>,
+
     /// Configures the instance's environment. Value must be either `production` or `development`.
     #[clap(long, env = MEILI_ENV, default_value_t = default_env(), value_parser = POSSIBLE_ENV)]
     #[serde(default = "default_env")]
@@ -209,6 +269,18 @@ pub struct Opt {
     #[clap(long, env = MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER)]
     pub task_webhook_authorization_header: Option<String>,
 
+    /// Several webhooks called whenever tasks finish, each of them only receiving the tasks matching its filters.
+    ///
+    /// Formatted as a JSON array of objects with a `url`, and optionally an `authorizationHeader`, the
+    /// `types`, `indexUids` and `statuses` filters, and a `payloadTemplate`, e.g.
+    /// `[{ "url": "http://localhost:3000", "statuses": ["failed"], "payloadTemplate": "{\"text\": \"Task {{ task.uid }} failed\"}" }]`.
+    ///
+    /// The `payloadTemplate` is a Liquid template rendered for each task, with the task in the `task` variable.
+    /// These webhooks are called in addition to the `--task-webhook-url` one.
+    #[clap(long, env = MEILI_TASK_WEBHOOKS)]
+    #[serde(default)]
+    pub task_webhooks: Option<TaskWebhooks>,
+
     /// Deactivates Meilisearch's built-in telemetry when provided.
     ///
     /// Meilisearch automatically collects data from all instances that
@@ -270,6 +342,14 @@ pub struct Opt {
     #[clap(long, env = MEILI_SSL_TICKETS)]
     pub ssl_tickets: bool,
 
+    /// Sets the JSON file mapping the client certificates to the uids of the API keys they
+    /// authenticate as, by their SHA-256 `fingerprints` or their `subjects`.
+    ///
+    /// The requests sent with a mapped certificate don't need an `Authorization` header.
+    /// Requires `--ssl-require-auth`.
+    #[clap(long, env = MEILI_SSL_CLIENT_KEYS_PATH, value_parser)]
+    pub ssl_client_keys_path: Option<PathBuf>,
+
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
@@ -300,10 +380,38 @@ pub struct Opt {
     pub ignore_snapshot_if_db_exists: bool,
 
     /// Sets the directory where Meilisearch will store snapshots.
+    ///
+    /// An `s3://bucket/prefix` URL uploads the snapshots to an S3-compatible object storage
+    /// instead, without writing them to the local disk.
     #[clap(long, env = MEILI_SNAPSHOT_DIR, default_value_os_t = default_snapshot_dir())]
     #[serde(default = "default_snapshot_dir")]
     pub snapshot_dir: PathBuf,
 
+    /// Sets the URL of the S3-compatible object storage the snapshots are uploaded to.
+    ///
+    /// Defaults to the AWS S3 endpoint of `--snapshot-s3-region`.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_ENDPOINT)]
+    pub snapshot_s3_endpoint: Option<Url>,
+
+    /// Sets the region of the bucket the snapshots are uploaded to.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_REGION, default_value_t = default_snapshot_s3_region())]
+    #[serde(default = "default_snapshot_s3_region")]
+    pub snapshot_s3_region: String,
+
+    /// Sets the access key used to upload the snapshots to the object storage.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_ACCESS_KEY)]
+    pub snapshot_s3_access_key: Option<String>,
+
+    /// Sets the secret key used to upload the snapshots to the object storage.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_SECRET_KEY)]
+    pub snapshot_s3_secret_key: Option<String>,
+
+    /// Puts the bucket in the path of the object storage URLs rather than in their domain,
+    /// as most self-hosted object storages require.
+    #[serde(default)]
+    #[clap(long, env = MEILI_SNAPSHOT_S3_PATH_STYLE)]
+    pub snapshot_s3_path_style: bool,
+
     /// Activates scheduled snapshots when provided. Snapshots are disabled by default.
     ///
     /// When provided with a value, defines the interval between each snapshot, in seconds.
@@ -338,6 +446,14 @@ pub struct Opt {
     #[serde(default = "default_dump_dir")]
     pub dump_dir: PathBuf,
 
+    /// Activates scheduled dumps when provided. Dumps are disabled by default.
+    ///
+    /// When provided with a value, defines the interval between each dump, in seconds. Unlike the
+    /// snapshots, the dumps can be imported by another version of Meilisearch.
+    #[clap(long, env = MEILI_SCHEDULE_DUMP, num_args(0..=1), value_parser = parse_schedule_dump, default_value_t, default_missing_value = default_dump_interval_sec(), value_name = "DUMP_INTERVAL_SEC")]
+    #[serde(default, deserialize_with = "schedule_dump_deserialize")]
+    pub schedule_dump: ScheduleDump,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports six log levels, listed in order of
@@ -429,6 +545,18 @@ pub struct Opt {
     #[serde(default)]
     pub experimental_replication_parameters: bool,
 
+    /// Runs the instance as a read-only replica of a primary instance, to which the write
+    /// requests must be sent.
+    ///
+    /// - Refuses the tasks and the API key modifications, except the ones the primary replicates
+    ///   with the master key, the tasks with their `TaskId` header, and the dumps and snapshots
+    /// - Requires a master key, shared with the primary
+    /// - Enables the replication parameters, see `--experimental-replication-parameters`
+    /// - Advertises the replica status in the `/health` route, for the load balancers
+    #[clap(long, env = MEILI_READ_ONLY_REPLICA)]
+    #[serde(default)]
+    pub read_only_replica: bool,
+
     /// Experimental RAM reduction during indexing, do not use in production,
     /// see: <https://github.com/meilisearch/product/discussions/652>
     #[clap(long, env = MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE)]
@@ -447,6 +575,74 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks_total_size")]
     pub experimental_limit_batched_tasks_total_size: u64,
 
+    /// Experimentally reduces the maximum number of documents added, updated or deleted by the tasks
+    /// that will be processed at once.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_BATCHED_DOCUMENTS)]
+    #[serde(default)]
+    pub experimental_max_batched_documents: Option<u64>,
+
+    /// Experimentally makes the document additions, updates and deletions wait up to this number of
+    /// milliseconds for more tasks to be batched with them.
+    ///
+    /// A batch stops waiting as soon as it reaches one of its limits, or when another kind of task is
+    /// enqueued on its index. By default, the enqueued tasks are batched right away.
+    #[clap(long, env = MEILI_EXPERIMENTAL_AUTOBATCHING_MAX_WAIT_MS)]
+    #[serde(default)]
+    pub experimental_autobatching_max_wait_ms: Option<u64>,
+
+    /// Experimentally overrides the autobatching limits of some indexes.
+    ///
+    /// The value is a JSON object associating index uids with their limits, e.g.
+    /// `{"products": {"maxTasks": 100, "maxDocuments": 10000, "maxTotalSize": 104857600, "maxWaitMs": 500}}`.
+    /// The limits left out fall back to the ones of the instance.
+    #[clap(long, env = MEILI_EXPERIMENTAL_INDEX_AUTOBATCHING)]
+    #[serde(default)]
+    pub experimental_index_autobatching: Option<IndexAutobatching>,
+
+    /// Experimentally processes up to this number of batches at once, as long as they target different indexes.
+    ///
+    /// With the default of 1, a long indexing job on one index blocks the updates of all the other indexes.
+    /// The task cancelations, task deletions, snapshots, dumps and index swaps are still processed alone.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
+    /// Experimentally processes the small tasks in a fast lane while a large batch runs, so that
+    /// single-document updates don't wait for the end of an import.
+    ///
+    /// The value is the maximum number of documents of a batch processed in the fast lane. Only the
+    /// document additions, updates and deletions by ids, and the index creations, of indexes that are
+    /// not touched by the running batches are processed in the fast lane.
+    #[clap(long, env = MEILI_EXPERIMENTAL_FAST_LANE_MAX_DOCUMENTS)]
+    #[serde(default)]
+    pub experimental_fast_lane_max_documents: Option<u64>,
+
+    /// Experimentally commits the document additions, updates and deletions of a batch every time
+    /// this number of documents is indexed, so that a shutdown doesn't lose the progress of a large import.
+    ///
+    /// On a SIGTERM or a CTRL-C, Meilisearch waits for the processing batches to reach their next
+    /// checkpoint and leaves their remaining tasks enqueued, to be processed after the restart. The
+    /// document additions larger than that are indexed in several steps too, and resume after
+    /// their last indexed document. Such an addition fails at its first failing step, keeping the
+    /// documents of its previous steps.
+    #[clap(long, env = MEILI_EXPERIMENTAL_BATCH_CHECKPOINT_DOCUMENTS)]
+    #[serde(default)]
+    pub experimental_batch_checkpoint_documents: Option<u64>,
+
+    /// Experimentally refuses the new tasks with a `429 Too Many Requests` while this number of tasks is enqueued.
+    ///
+    /// The clients are asked to retry later with a `Retry-After` header instead of piling up tasks that would
+    /// wait for hours. The task cancelations and deletions are always accepted.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_ENQUEUED_TASKS)]
+    #[serde(default)]
+    pub experimental_max_enqueued_tasks: Option<u64>,
+
+    /// Experimentally refuses the new tasks with a `429 Too Many Requests` while more than this percentage
+    /// of the disk holding the database is used.
+    #[clap(long, env = MEILI_EXPERIMENTAL_DISK_WATERMARK, value_parser = clap::value_parser!(u8).range(1..=100))]
+    #[serde(default)]
+    pub experimental_disk_watermark: Option<u8>,
+
     /// Enables experimental caching of search query embeddings. The value represents the maximal number of entries in the cache of each
     /// distinct embedder.
     ///
@@ -455,6 +651,115 @@ pub struct Opt {
     #[serde(default = "default_embedding_cache_entries")]
     pub experimental_embedding_cache_entries: usize,
 
+    /// Experimentally restricts the scheduled snapshots and dumps to a daily window, in UTC, formatted as `HH:MM-HH:MM`.
+    ///
+    /// A snapshot or a dump that is due outside of the window is deferred until the window opens, so that
+    /// maintenance work doesn't collide with peak traffic. The window can wrap around midnight, e.g. `22:00-06:00`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAINTENANCE_WINDOW)]
+    #[serde(default)]
+    pub experimental_maintenance_window: Option<MaintenanceWindow>,
+
+    /// Experimentally keeps this number of local snapshots as restore points, so that the indexes
+    /// can be restored to any date since the oldest one with the `POST /snapshots/restore` route.
+    ///
+    /// The documents of the succeeded additions are kept on disk as long as a restore point was taken
+    /// before them, to be replayed on top of it. Requires the snapshots to be stored on the local disk.
+    #[clap(long, env = MEILI_EXPERIMENTAL_RESTORE_POINTS)]
+    #[serde(default)]
+    pub experimental_restore_points: Option<usize>,
+
+    /// Experimentally keeps the documents of the succeeded additions on disk until their task is deleted,
+    /// so that the changes of the documents of an index can be streamed with the `GET /indexes/{indexUid}/changes` route.
+    #[clap(long, env = MEILI_EXPERIMENTAL_CHANGE_STREAM)]
+    #[serde(default)]
+    pub experimental_change_stream: bool,
+
+    /// Experimentally keeps the succeeded tasks that changed the indexes in a task log, with the documents they sent,
+    /// until it is trimmed. The log can be shipped with the `GET /task-log` route and replayed on another instance
+    /// with the `POST /task-log/replay` route.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TASK_LOG)]
+    #[serde(default)]
+    pub experimental_task_log: bool,
+
+    /// Experimentally allows searching several indexes through an alias, defined with the `/aliases` route,
+    /// with `/indexes/{alias}/search`. The hits of the indexes are merged by ranking score.
+    #[clap(long, env = MEILI_EXPERIMENTAL_INDEX_ALIASES)]
+    #[serde(default)]
+    pub experimental_index_aliases: bool,
+
+    /// Experimentally allows importing documents from a URL, with the `POST /indexes/{indexUid}/documents/import-url`
+    /// route and the sources of the indexes, and importing dumps from a URL. The value is the list of the hosts the documents can be downloaded from,
+    /// separated by commas, e.g. `my-bucket.s3.amazonaws.com,example.com`.
+    ///
+    /// `*` allows any host, as long as it resolves to a public address. The hosts that are explicitly listed
+    /// can resolve to a loopback or a private address. The downloads can't be larger than `--http-payload-size-limit`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_IMPORT_URL_ALLOWED_HOSTS)]
+    #[serde(default)]
+    pub experimental_import_url_allowed_hosts: Option<AllowedHosts>,
+
+    /// Experimentally keeps indexes in sync with a PostgreSQL database by consuming its changes
+    /// through a logical replication slot. The value is the connection string of the database,
+    /// e.g. `host=localhost user=postgres dbname=shop`.
+    ///
+    /// Requires Meilisearch to be compiled with the `postgres-cdc` feature, and the `wal2json`
+    /// output plugin to be installed on the database.
+    #[clap(long, env = MEILI_EXPERIMENTAL_POSTGRES_CDC_URL, requires = "experimental_postgres_cdc_tables")]
+    #[serde(default)]
+    pub experimental_postgres_cdc_url: Option<String>,
+
+    /// The logical replication slot the PostgreSQL changes are consumed from.
+    /// It is created with the `wal2json` output plugin if it doesn't exist.
+    #[clap(long, env = MEILI_EXPERIMENTAL_POSTGRES_CDC_SLOT, default_value_t = default_postgres_cdc_slot())]
+    #[serde(default = "default_postgres_cdc_slot")]
+    pub experimental_postgres_cdc_slot: String,
+
+    /// The PostgreSQL tables whose rows are synced, and the index each one is synced to,
+    /// formatted as `schema.table=index` and separated by commas, e.g. `public.products=products`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_POSTGRES_CDC_TABLES)]
+    #[serde(default)]
+    pub experimental_postgres_cdc_tables: Option<IngestionMapping>,
+
+    /// Experimentally consumes documents from Kafka topics. The value is the list of the
+    /// brokers to bootstrap from, separated by commas, e.g. `localhost:9092`.
+    ///
+    /// Every message is a JSON object, or an array of objects, added or replaced in the index
+    /// of its topic. A message without a value deletes the document whose id is its key.
+    /// The offsets are committed once the tasks of the messages are finished, so the messages
+    /// are consumed at least once.
+    ///
+    /// Requires Meilisearch to be compiled with the `kafka-ingestion` feature.
+    #[clap(long, env = MEILI_EXPERIMENTAL_KAFKA_BROKERS, requires = "experimental_kafka_topics")]
+    #[serde(default)]
+    pub experimental_kafka_brokers: Option<String>,
+
+    /// The consumer group the Kafka offsets are committed for.
+    #[clap(long, env = MEILI_EXPERIMENTAL_KAFKA_GROUP, default_value_t = default_kafka_group())]
+    #[serde(default = "default_kafka_group")]
+    pub experimental_kafka_group: String,
+
+    /// The Kafka topics that are consumed, and the index each one is ingested in,
+    /// formatted as `topic=index` and separated by commas, e.g. `products-events=products`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_KAFKA_TOPICS)]
+    #[serde(default)]
+    pub experimental_kafka_topics: Option<IngestionMapping>,
+
+    /// Experimentally publishes the tasks every time they are enqueued, start processing, or finish,
+    /// to a Redis stream, a NATS subject, or a Kafka topic, e.g. `redis://localhost:6379`,
+    /// `nats://localhost:4222` or `kafka://localhost:9092`.
+    ///
+    /// Unlike the webhooks, the events can be replayed by the consumers. Each event is the task as
+    /// returned by the `/tasks` route, and is retried until the broker acknowledges it.
+    ///
+    /// Publishing to Kafka requires Meilisearch to be compiled with the `kafka-task-events` feature.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TASK_EVENTS_URL)]
+    #[serde(default)]
+    pub experimental_task_events_url: Option<String>,
+
+    /// The Redis stream, NATS subject, or Kafka topic the task events are published to.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TASK_EVENTS_STREAM, default_value_t = default_task_events_stream())]
+    #[serde(default = "default_task_events_stream")]
+    pub experimental_task_events_stream: String,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -463,6 +768,12 @@ pub struct Opt {
     /// Format must be TOML.
     #[clap(long)]
     pub config_file_path: Option<PathBuf>,
+
+    /// Inspects the database located at the specified path, prints a JSON report of what upgrading it
+    /// to this version of Meilisearch would imply, and exits without modifying the database.
+    #[clap(long, value_name = "DATA.MS")]
+    #[serde(skip)]
+    pub upgrade_check: Option<PathBuf>,
 }
 
 impl Opt {
@@ -520,9 +831,12 @@ impl Opt {
             db_path,
             http_addr,
             master_key,
+            auth_encryption_key,
+            expired_keys_retention,
             env,
             task_webhook_url,
             task_webhook_authorization_header,
+            task_webhooks,
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
@@ -533,9 +847,16 @@ impl Opt {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path,
             snapshot_dir,
+            snapshot_s3_endpoint,
+            snapshot_s3_region,
+            snapshot_s3_access_key,
+            snapshot_s3_secret_key,
+            snapshot_s3_path_style,
             schedule_snapshot,
             dump_dir,
+            schedule_dump,
             log_level,
             indexer_options,
             import_snapshot: _,
@@ -545,6 +866,7 @@ impl Opt {
             ignore_missing_dump: _,
             ignore_dump_if_db_exists: _,
             config_file_path: _,
+            upgrade_check: _,
             no_analytics,
             experimental_contains_filter,
             experimental_enable_metrics,
@@ -555,16 +877,48 @@ impl Opt {
             experimental_dumpless_upgrade,
             experimental_enable_logs_route,
             experimental_replication_parameters,
+            read_only_replica,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_max_batched_documents,
+            experimental_autobatching_max_wait_ms,
+            experimental_index_autobatching,
+            experimental_max_concurrent_batches,
+            experimental_fast_lane_max_documents,
+            experimental_batch_checkpoint_documents,
+            experimental_max_enqueued_tasks,
+            experimental_disk_watermark,
             experimental_embedding_cache_entries,
+            experimental_maintenance_window,
+            experimental_restore_points,
+            experimental_change_stream,
+            experimental_task_log,
+            experimental_index_aliases,
+            experimental_import_url_allowed_hosts,
+            experimental_postgres_cdc_url,
+            experimental_postgres_cdc_slot,
+            experimental_postgres_cdc_tables,
+            experimental_kafka_brokers,
+            experimental_kafka_group,
+            experimental_kafka_topics,
+            experimental_task_events_url,
+            experimental_task_events_stream,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
         if let Some(master_key) = master_key {
             export_to_env_if_not_present(MEILI_MASTER_KEY, master_key);
         }
+        if let Some(auth_encryption_key) = auth_encryption_key {
+            export_to_env_if_not_present(MEILI_AUTH_ENCRYPTION_KEY, auth_encryption_key);
+        }
+        if let Some(expired_keys_retention) = expired_keys_retention {
+            export_to_env_if_not_present(
+                MEILI_EXPIRED_KEYS_RETENTION,
+                expired_keys_retention.to_string(),
+            );
+        }
         export_to_env_if_not_present(MEILI_ENV, env);
         if let Some(task_webhook_url) = task_webhook_url {
             export_to_env_if_not_present(MEILI_TASK_WEBHOOK_URL, task_webhook_url.to_string());
@@ -575,6 +929,9 @@ impl Opt {
                 task_webhook_authorization_header,
             );
         }
+        if let Some(task_webhooks) = task_webhooks {
+            export_to_env_if_not_present(MEILI_TASK_WEBHOOKS, task_webhooks.to_string());
+        }
 
         export_to_env_if_not_present(MEILI_NO_ANALYTICS, no_analytics.to_string());
         export_to_env_if_not_present(
@@ -596,12 +953,35 @@ impl Opt {
         export_to_env_if_not_present(MEILI_SSL_REQUIRE_AUTH, ssl_require_auth.to_string());
         export_to_env_if_not_present(MEILI_SSL_RESUMPTION, ssl_resumption.to_string());
         export_to_env_if_not_present(MEILI_SSL_TICKETS, ssl_tickets.to_string());
+        if let Some(ssl_client_keys_path) = ssl_client_keys_path {
+            export_to_env_if_not_present(MEILI_SSL_CLIENT_KEYS_PATH, ssl_client_keys_path);
+        }
         export_to_env_if_not_present(MEILI_SNAPSHOT_DIR, snapshot_dir);
+        if let Some(snapshot_s3_endpoint) = snapshot_s3_endpoint {
+            export_to_env_if_not_present(
+                MEILI_SNAPSHOT_S3_ENDPOINT,
+                snapshot_s3_endpoint.to_string(),
+            );
+        }
+        export_to_env_if_not_present(MEILI_SNAPSHOT_S3_REGION, snapshot_s3_region);
+        if let Some(snapshot_s3_access_key) = snapshot_s3_access_key {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_S3_ACCESS_KEY, snapshot_s3_access_key);
+        }
+        if let Some(snapshot_s3_secret_key) = snapshot_s3_secret_key {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_S3_SECRET_KEY, snapshot_s3_secret_key);
+        }
+        export_to_env_if_not_present(
+            MEILI_SNAPSHOT_S3_PATH_STYLE,
+            snapshot_s3_path_style.to_string(),
+        );
         if let Some(snapshot_interval) = schedule_snapshot_to_env(schedule_snapshot) {
             export_to_env_if_not_present(MEILI_SCHEDULE_SNAPSHOT, snapshot_interval)
         }
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
+        if let ScheduleDump::Enabled(dump_interval) = schedule_dump {
+            export_to_env_if_not_present(MEILI_SCHEDULE_DUMP, dump_interval.to_string())
+        }
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_CONTAINS_FILTER,
@@ -635,6 +1015,7 @@ impl Opt {
             MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS,
             experimental_replication_parameters.to_string(),
         );
+        export_to_env_if_not_present(MEILI_READ_ONLY_REPLICA, read_only_replica.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE,
             experimental_enable_logs_route.to_string(),
@@ -651,10 +1032,113 @@ impl Opt {
             MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE,
             experimental_limit_batched_tasks_total_size.to_string(),
         );
+        if let Some(max_batched_documents) = experimental_max_batched_documents {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_MAX_BATCHED_DOCUMENTS,
+                max_batched_documents.to_string(),
+            );
+        }
+        if let Some(autobatching_max_wait_ms) = experimental_autobatching_max_wait_ms {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_AUTOBATCHING_MAX_WAIT_MS,
+                autobatching_max_wait_ms.to_string(),
+            );
+        }
+        if let Some(index_autobatching) = experimental_index_autobatching {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_INDEX_AUTOBATCHING,
+                index_autobatching.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
+        if let Some(fast_lane_max_documents) = experimental_fast_lane_max_documents {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_FAST_LANE_MAX_DOCUMENTS,
+                fast_lane_max_documents.to_string(),
+            );
+        }
+        if let Some(batch_checkpoint_documents) = experimental_batch_checkpoint_documents {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_BATCH_CHECKPOINT_DOCUMENTS,
+                batch_checkpoint_documents.to_string(),
+            );
+        }
+        if let Some(max_enqueued_tasks) = experimental_max_enqueued_tasks {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_MAX_ENQUEUED_TASKS,
+                max_enqueued_tasks.to_string(),
+            );
+        }
+        if let Some(disk_watermark) = experimental_disk_watermark {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_DISK_WATERMARK,
+                disk_watermark.to_string(),
+            );
+        }
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
         );
+        if let Some(maintenance_window) = experimental_maintenance_window {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_MAINTENANCE_WINDOW,
+                maintenance_window.to_string(),
+            );
+        }
+        if let Some(restore_points) = experimental_restore_points {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_RESTORE_POINTS,
+                restore_points.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_CHANGE_STREAM,
+            experimental_change_stream.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_TASK_LOG,
+            experimental_task_log.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_INDEX_ALIASES,
+            experimental_index_aliases.to_string(),
+        );
+        if let Some(import_url_allowed_hosts) = experimental_import_url_allowed_hosts {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_IMPORT_URL_ALLOWED_HOSTS,
+                import_url_allowed_hosts.to_string(),
+            );
+        }
+        if let Some(postgres_cdc_url) = experimental_postgres_cdc_url {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_POSTGRES_CDC_URL, postgres_cdc_url);
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_POSTGRES_CDC_SLOT,
+            experimental_postgres_cdc_slot,
+        );
+        if let Some(postgres_cdc_tables) = experimental_postgres_cdc_tables {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_POSTGRES_CDC_TABLES,
+                postgres_cdc_tables.to_string(),
+            );
+        }
+        if let Some(kafka_brokers) = experimental_kafka_brokers {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_KAFKA_BROKERS, kafka_brokers);
+        }
+        export_to_env_if_not_present(MEILI_EXPERIMENTAL_KAFKA_GROUP, experimental_kafka_group);
+        if let Some(kafka_topics) = experimental_kafka_topics {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_KAFKA_TOPICS, kafka_topics.to_string());
+        }
+        if let Some(task_events_url) = experimental_task_events_url {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_TASK_EVENTS_URL, task_events_url);
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_TASK_EVENTS_STREAM,
+            experimental_task_events_stream,
+        );
         indexer_options.export_to_env();
     }
 
@@ -702,6 +1186,81 @@ impl Opt {
         }
     }
 
+    /// Loads the API keys the client certificates authenticate as, if a mapping file was given.
+    pub fn get_client_certificate_keys(&self) -> anyhow::Result<Option<ClientCertificateKeys>> {
+        let Some(path) = &self.ssl_client_keys_path else { return Ok(None) };
+        anyhow::ensure!(
+            self.ssl_cert_path.is_some() && self.ssl_auth_path.is_some() && self.ssl_require_auth,
+            "`--ssl-client-keys-path` requires `--ssl-cert-path`, `--ssl-auth-path` and `--ssl-require-auth`"
+        );
+        ClientCertificateKeys::from_path(path).map(Some)
+    }
+
+    /// Returns the object storage the snapshots are uploaded to, if `--snapshot-dir` is an
+    /// `s3://bucket/prefix` URL.
+    pub fn snapshot_s3_options(&self) -> anyhow::Result<Option<S3SnapshotOptions>> {
+        let Some(location) = self.snapshot_dir.to_str().and_then(|dir| dir.strip_prefix("s3://"))
+        else {
+            return Ok(None);
+        };
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        anyhow::ensure!(
+            !bucket.is_empty(),
+            "`--snapshot-dir` must name a bucket: `s3://bucket/prefix`"
+        );
+        let prefix = match prefix.trim_matches('/') {
+            "" => String::new(),
+            prefix => format!("{prefix}/"),
+        };
+
+        let (Some(access_key), Some(secret_key)) =
+            (&self.snapshot_s3_access_key, &self.snapshot_s3_secret_key)
+        else {
+            anyhow::bail!(
+                "Uploading the snapshots to `s3://` requires `--snapshot-s3-access-key` and `--snapshot-s3-secret-key`"
+            );
+        };
+        let endpoint = match &self.snapshot_s3_endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => Url::parse(&format!("https://s3.{}.amazonaws.com", self.snapshot_s3_region))?,
+        };
+
+        Ok(Some(S3SnapshotOptions {
+            endpoint,
+            region: self.snapshot_s3_region.clone(),
+            bucket: bucket.to_string(),
+            prefix,
+            access_key: access_key.clone(),
+            secret_key: secret_key.clone(),
+            path_style: self.snapshot_s3_path_style,
+        }))
+    }
+
+    /// Whether the tasks can be registered with a custom uid or dry-registered, which the replicas
+    /// always allow so that the primary can replicate its tasks to them.
+    pub fn replication_parameters(&self) -> bool {
+        self.experimental_replication_parameters || self.read_only_replica
+    }
+
+    /// Returns the number of snapshots kept as restore points, if they are enabled.
+    pub fn restore_points(&self) -> anyhow::Result<Option<usize>> {
+        if self.experimental_restore_points.is_some()
+            && self.snapshot_dir.to_str().is_some_and(|dir| dir.starts_with("s3://"))
+        {
+            anyhow::bail!(
+                "`--experimental-restore-points` requires the snapshots to be stored on the local disk"
+            );
+        }
+        Ok(self.experimental_restore_points)
+    }
+
+    pub fn import_url_options(&self) -> Option<ImportUrlOptions> {
+        self.experimental_import_url_allowed_hosts.as_ref().map(|hosts| ImportUrlOptions {
+            allowed_hosts: hosts.0.clone(),
+            size_limit: self.http_payload_size_limit.as_u64(),
+        })
+    }
+
     pub(crate) fn to_instance_features(&self) -> InstanceTogglableFeatures {
         InstanceTogglableFeatures {
             metrics: self.experimental_enable_metrics,
@@ -962,6 +1521,10 @@ fn default_limit_batched_tasks_total_size() -> u64 {
     u64::MAX
 }
 
+fn default_max_concurrent_batches() -> usize {
+    1
+}
+
 fn default_embedding_cache_entries() -> usize {
     0
 }
@@ -970,14 +1533,34 @@ fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
 
+fn default_snapshot_s3_region() -> String {
+    DEFAULT_SNAPSHOT_S3_REGION.to_string()
+}
+
 fn default_snapshot_interval_sec() -> &'static str {
     DEFAULT_SNAPSHOT_INTERVAL_SEC_STR
 }
 
+fn default_dump_interval_sec() -> &'static str {
+    DEFAULT_DUMP_INTERVAL_SEC_STR
+}
+
 fn default_dump_dir() -> PathBuf {
     PathBuf::from(DEFAULT_DUMP_DIR)
 }
 
+fn default_postgres_cdc_slot() -> String {
+    DEFAULT_POSTGRES_CDC_SLOT.to_string()
+}
+
+fn default_kafka_group() -> String {
+    DEFAULT_KAFKA_GROUP.to_string()
+}
+
+fn default_task_events_stream() -> String {
+    DEFAULT_TASK_EVENTS_STREAM.to_string()
+}
+
 fn default_experimental_search_queue_size() -> usize {
     1000
 }
@@ -1085,3 +1668,330 @@ where
     }
     deserializer.deserialize_any(BoolOrInt)
 }
+
+/// Indicates if a dump was scheduled, and if yes with which interval.
+#[derive(Debug, Default, Copy, Clone, Deserialize, Serialize)]
+pub enum ScheduleDump {
+    /// Scheduled dumps are disabled.
+    #[default]
+    Disabled,
+    /// Dumps are scheduled at the specified interval, in seconds.
+    Enabled(u64),
+}
+
+impl Display for ScheduleDump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleDump::Disabled => write!(f, ""),
+            ScheduleDump::Enabled(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for ScheduleDump {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "" => ScheduleDump::Disabled,
+            s => ScheduleDump::Enabled(s.parse()?),
+        })
+    }
+}
+
+fn parse_schedule_dump(s: &str) -> Result<ScheduleDump, ParseIntError> {
+    ScheduleDump::from_str(s)
+}
+
+/// Accepts the same values as `schedule_snapshot`, both default to a daily interval.
+fn schedule_dump_deserialize<'de, D>(deserializer: D) -> Result<ScheduleDump, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match schedule_snapshot_deserialize(deserializer)? {
+        ScheduleSnapshot::Disabled => ScheduleDump::Disabled,
+        ScheduleSnapshot::Enabled(interval) => ScheduleDump::Enabled(interval),
+    })
+}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A daily window, in UTC, during which the maintenance work is allowed to start.
+///
+/// The window wraps around midnight when it ends before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct MaintenanceWindow {
+    /// The number of minutes since midnight at which the window opens.
+    start: u16,
+    /// The number of minutes since midnight at which the window closes.
+    end: u16,
+}
+
+impl MaintenanceWindow {
+    /// Whether the window is open at the given time of the day.
+    pub fn contains(&self, time: time::Time) -> bool {
+        let minute = time.hour() as u16 * 60 + time.minute() as u16;
+        if self.start < self.end {
+            self.start <= minute && minute < self.end
+        } else {
+            self.start <= minute || minute < self.end
+        }
+    }
+
+    /// How long to wait from the given time of the day until the window opens,
+    /// or zero if it is already open.
+    pub fn time_until_open(&self, time: time::Time) -> Duration {
+        if self.contains(time) {
+            return Duration::ZERO;
+        }
+        let minute = time.hour() as u16 * 60 + time.minute() as u16;
+        let minutes = (self.start + MINUTES_PER_DAY - minute) % MINUTES_PER_DAY;
+        Duration::from_secs(minutes as u64 * 60 - time.second() as u64)
+    }
+}
+
+impl Display for MaintenanceWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { start, end } = self;
+        write!(f, "{:02}:{:02}-{:02}:{:02}", start / 60, start % 60, end / 60, end % 60)
+    }
+}
+
+impl FromStr for MaintenanceWindow {
+    type Err = MaintenanceWindowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || MaintenanceWindowError(s.to_owned());
+        let parse_time = |time: &str| -> Option<u16> {
+            let (hour, minute) = time.trim().split_once(':')?;
+            let (hour, minute): (u16, u16) = (hour.parse().ok()?, minute.parse().ok()?);
+            (hour < 24 && minute < 60).then_some(hour * 60 + minute)
+        };
+
+        let (start, end) = s.split_once('-').ok_or_else(error)?;
+        let start = parse_time(start).ok_or_else(error)?;
+        let end = parse_time(end).ok_or_else(error)?;
+        if start == end {
+            return Err(error());
+        }
+
+        Ok(MaintenanceWindow { start, end })
+    }
+}
+
+impl TryFrom<String> for MaintenanceWindow {
+    type Error = MaintenanceWindowError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid maintenance window `{0}`. Expected a non-empty daily window in UTC formatted as `HH:MM-HH:MM`, e.g. `22:00-06:00`.")]
+pub struct MaintenanceWindowError(String);
+
+/// Associates the sources of an external system, e.g. the tables of a database, with the index
+/// their documents are ingested in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct IngestionMapping(BTreeMap<String, String>);
+
+impl IngestionMapping {
+    /// Return the index the documents of the source are ingested in, if the source is mapped.
+    pub fn index_of(&self, source: &str) -> Option<&str> {
+        self.0.get(source).map(String::as_str)
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+impl Display for IngestionMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (source, index)) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{source}={index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for IngestionMapping {
+    type Err = IngestionMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mapping = BTreeMap::new();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let error = || IngestionMappingError(pair.to_owned());
+            let (source, index) = pair.split_once('=').ok_or_else(error)?;
+            let (source, index) = (source.trim(), index.trim());
+            if source.is_empty() || IndexUid::from_str(index).is_err() {
+                return Err(error());
+            }
+            if mapping.insert(source.to_string(), index.to_string()).is_some() {
+                return Err(error());
+            }
+        }
+        if mapping.is_empty() {
+            return Err(IngestionMappingError(s.to_owned()));
+        }
+
+        Ok(IngestionMapping(mapping))
+    }
+}
+
+impl TryFrom<String> for IngestionMapping {
+    type Error = IngestionMappingError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid mapping `{0}`. Expected distinct `source=index` pairs separated by commas, where `index` is a valid index uid, e.g. `public.products=products`.")]
+pub struct IngestionMappingError(String);
+
+/// The lowercased hosts the documents can be imported from, `*` allowing any public host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AllowedHosts(pub Vec<String>);
+
+impl Display for AllowedHosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join(","))
+    }
+}
+
+impl FromStr for AllowedHosts {
+    type Err = AllowedHostsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hosts = Vec::new();
+        for host in s.split(',').map(str::trim).filter(|host| !host.is_empty()) {
+            let host = host.to_lowercase();
+            if host != "*" && url::Host::parse(&host).is_err() {
+                return Err(AllowedHostsError(host));
+            }
+            hosts.push(host);
+        }
+        if hosts.is_empty() {
+            return Err(AllowedHostsError(s.to_owned()));
+        }
+        Ok(AllowedHosts(hosts))
+    }
+}
+
+impl TryFrom<String> for AllowedHosts {
+    type Error = AllowedHostsError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid host `{0}`. Expected hosts separated by commas, without scheme nor port, e.g. `my-bucket.s3.amazonaws.com,example.com`, or `*`.")]
+pub struct AllowedHostsError(String);
+
+/// The webhooks, along with their filters, notified whenever tasks finish.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "Vec<TaskWebhook>")]
+pub struct TaskWebhooks(pub Vec<TaskWebhook>);
+
+impl Display for TaskWebhooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let webhooks = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        f.write_str(&webhooks)
+    }
+}
+
+impl FromStr for TaskWebhooks {
+    type Err = TaskWebhooksError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let webhooks: Vec<TaskWebhook> = serde_json::from_str(s)
+            .map_err(|error| TaskWebhooksError::Malformed(error.to_string()))?;
+        webhooks.try_into()
+    }
+}
+
+impl TryFrom<Vec<TaskWebhook>> for TaskWebhooks {
+    type Error = TaskWebhooksError;
+
+    fn try_from(webhooks: Vec<TaskWebhook>) -> Result<Self, Self::Error> {
+        for webhook in &webhooks {
+            if let Err(error) = Url::parse(&webhook.url) {
+                return Err(TaskWebhooksError::InvalidUrl(webhook.url.clone(), error));
+            }
+            if let Err(error) = webhook.parse_payload_template() {
+                let url = webhook.url.clone();
+                return Err(TaskWebhooksError::InvalidPayloadTemplate(url, error.to_string()));
+            }
+        }
+        Ok(TaskWebhooks(webhooks))
+    }
+}
+
+/// The autobatching limits overriding the ones of the instance, by index uid.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "BTreeMap<String, AutobatchingLimits>")]
+pub struct IndexAutobatching(pub BTreeMap<String, AutobatchingLimits>);
+
+impl Display for IndexAutobatching {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limits = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        f.write_str(&limits)
+    }
+}
+
+impl FromStr for IndexAutobatching {
+    type Err = IndexAutobatchingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let limits: BTreeMap<String, AutobatchingLimits> = serde_json::from_str(s)
+            .map_err(|error| IndexAutobatchingError::Malformed(error.to_string()))?;
+        limits.try_into()
+    }
+}
+
+impl TryFrom<BTreeMap<String, AutobatchingLimits>> for IndexAutobatching {
+    type Error = IndexAutobatchingError;
+
+    fn try_from(limits: BTreeMap<String, AutobatchingLimits>) -> Result<Self, Self::Error> {
+        for (index, limits) in &limits {
+            if IndexUid::from_str(index).is_err() {
+                return Err(IndexAutobatchingError::InvalidIndexUid(index.clone()));
+            }
+            if limits.max_tasks == Some(0) {
+                return Err(IndexAutobatchingError::NoTasks(index.clone()));
+            }
+        }
+        Ok(IndexAutobatching(limits))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexAutobatchingError {
+    #[error("Invalid index autobatching: {0}. Expected a JSON object associating index uids with objects of the optional `maxTasks`, `maxDocuments`, `maxTotalSize` and `maxWaitMs` fields.")]
+    Malformed(String),
+    #[error("Invalid index autobatching: `{0}` is not a valid index uid.")]
+    InvalidIndexUid(String),
+    #[error("Invalid index autobatching: the `maxTasks` of `{0}` must be greater than 0.")]
+    NoTasks(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TaskWebhooksError {
+    #[error("Invalid task webhooks: {0}. Expected a JSON array of objects with a `url` and the optional `authorizationHeader`, `types`, `indexUids`, `statuses` and `payloadTemplate` fields.")]
+    Malformed(String),
+    #[error("Invalid task webhook URL `{0}`: {1}.")]
+    InvalidUrl(String, url::ParseError),
+    #[error("Invalid payload template for the task webhook `{0}`: {1}")]
+    InvalidPayloadTemplate(String, String),
+}
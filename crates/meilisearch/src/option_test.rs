@@ -35,3 +35,114 @@ fn test_meilli_config_file_path_invalid() {
         );
     });
 }
+
+#[test]
+fn test_maintenance_window() {
+    use std::time::Duration;
+
+    use time::macros::time;
+
+    use crate::option::MaintenanceWindow;
+
+    let window: MaintenanceWindow = "22:00-06:00".parse().unwrap();
+    assert_eq!(window.to_string(), "22:00-06:00");
+    assert!(window.contains(time!(23:30)));
+    assert!(window.contains(time!(05:59)));
+    assert!(!window.contains(time!(06:00)));
+    assert_eq!(window.time_until_open(time!(02:00)), Duration::ZERO);
+    assert_eq!(window.time_until_open(time!(21:59:30)), Duration::from_secs(30));
+    assert_eq!(window.time_until_open(time!(06:00)), Duration::from_secs(16 * 3600));
+
+    let window: MaintenanceWindow = "1:30-4:00".parse().unwrap();
+    assert_eq!(window.to_string(), "01:30-04:00");
+    assert!(window.contains(time!(01:30)));
+    assert!(!window.contains(time!(04:00)));
+    assert_eq!(window.time_until_open(time!(04:00)), Duration::from_secs(21 * 3600 + 30 * 60));
+
+    for invalid in ["", "22:00", "22:00-24:00", "10:60-11:00", "10:00-10:00", "ten-eleven"] {
+        let error = invalid.parse::<MaintenanceWindow>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Invalid maintenance window `{invalid}`. Expected a non-empty daily window in UTC formatted as `HH:MM-HH:MM`, e.g. `22:00-06:00`.")
+        );
+    }
+}
+
+#[test]
+fn test_schedule_dump() {
+    use crate::option::ScheduleDump;
+
+    let opt = Opt::try_parse_from(["meilisearch"]).unwrap();
+    assert!(matches!(opt.schedule_dump, ScheduleDump::Disabled));
+    let opt = Opt::try_parse_from(["meilisearch", "--schedule-dump"]).unwrap();
+    assert!(matches!(opt.schedule_dump, ScheduleDump::Enabled(86400)));
+    let opt = Opt::try_parse_from(["meilisearch", "--schedule-dump", "3600"]).unwrap();
+    assert!(matches!(opt.schedule_dump, ScheduleDump::Enabled(3600)));
+    assert!(Opt::try_parse_from(["meilisearch", "--schedule-dump", "daily"]).is_err());
+}
+
+#[test]
+fn test_ingestion_mapping() {
+    use crate::option::IngestionMapping;
+
+    let mapping: IngestionMapping =
+        " public.products=products, public.users = users ,".parse().unwrap();
+    assert_eq!(mapping.to_string(), "public.products=products,public.users=users");
+    assert_eq!(mapping.index_of("public.users"), Some("users"));
+    assert_eq!(mapping.index_of("public.orders"), None);
+
+    for invalid in
+        ["", "public.products", "=products", "public.products=", "a=b,a=c", "a=invalid uid"]
+    {
+        assert!(invalid.parse::<IngestionMapping>().is_err(), "{invalid} should be invalid");
+    }
+}
+
+#[test]
+fn test_kafka_options() {
+    let opt = Opt::try_parse_from([
+        "meilisearch",
+        "--experimental-kafka-brokers",
+        "localhost:9092",
+        "--experimental-kafka-topics",
+        "products-events=products",
+    ])
+    .unwrap();
+    assert_eq!(opt.experimental_kafka_group, "meilisearch");
+    assert_eq!(
+        opt.experimental_kafka_topics.unwrap().index_of("products-events"),
+        Some("products")
+    );
+
+    assert!(Opt::try_parse_from(["meilisearch", "--experimental-kafka-brokers", "localhost:9092"])
+        .is_err());
+}
+
+#[test]
+fn test_task_webhooks() {
+    use crate::option::TaskWebhooks;
+
+    let webhooks: TaskWebhooks = r#"[
+        { "url": "http://localhost:3000" },
+        { "url": "http://localhost:3001", "authorizationHeader": "Bearer kefir", "types": ["indexCreation"], "indexUids": ["movies"], "statuses": ["failed"] },
+        { "url": "http://localhost:3002", "payloadTemplate": "{\"text\": \"Task {{ task.uid }} is {{ task.status }}\"}" }
+    ]"#
+    .parse()
+    .unwrap();
+    assert_eq!(webhooks.0.len(), 3);
+    assert_eq!(webhooks.0[0].statuses, None);
+    assert_eq!(webhooks.0[1].index_uids, Some(vec!["movies".to_string()]));
+    assert!(webhooks.0[2].parse_payload_template().unwrap().is_some());
+    assert_eq!(webhooks.to_string().parse::<TaskWebhooks>().unwrap(), webhooks);
+
+    for invalid in [
+        "",
+        "{}",
+        r#"[{ "url": "not a url" }]"#,
+        r#"[{ "url": "http://localhost:3000", "statuses": ["done"] }]"#,
+        r#"[{ "url": "http://localhost:3000", "unknown": true }]"#,
+        r#"[{ "url": "http://localhost:3000", "payloadTemplate": "{{ task.uid" }]"#,
+    ] {
+        assert!(invalid.parse::<TaskWebhooks>().is_err(), "{invalid} should be invalid");
+    }
+}
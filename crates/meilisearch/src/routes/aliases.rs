@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::InvalidIndexAliases;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::features::IndexAliases;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_index_aliases, patch_index_aliases),
+    tags((
+        name = "Index aliases",
+        description = "The `/aliases` route allows you to search several indexes at once, e.g. time-partitioned indexes, through an alias: `/indexes/{alias}/search` merges the hits of the indexes of the alias by ranking score.
+It requires launching Meilisearch with the `--experimental-index-aliases` option.
+
+This route is **synchronous**. This means that no task object will be returned, and any change to the aliases will be made available immediately.",
+    )),
+)]
+pub struct IndexAliasesApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(get_index_aliases))
+            .route(web::patch().to(SeqHandler(patch_index_aliases))),
+    );
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct IndexAliasesView {
+    /// The indexes searched through each alias, by alias.
+    #[schema(value_type = BTreeMap<String, Vec<String>>, example = json!({ "logs": ["logs-2024", "logs-2025"] }))]
+    pub aliases: IndexAliases,
+}
+
+/// Get the index aliases
+///
+/// Get the indexes searched through each alias.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Index aliases",
+    security(("Bearer" = ["indexes.get", "indexes.*", "*"])),
+    responses(
+        (status = OK, description = "The aliases are returned", body = IndexAliasesView, content_type = "application/json", example = json!(
+            { "aliases": { "logs": ["logs-2024", "logs-2025"] } }
+        )),
+        (status = 400, description = "The index aliases are not enabled", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Searching through index aliases requires launching Meilisearch with the `--experimental-index-aliases` option.",
+                "code": "feature_not_enabled",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+            }
+        )),
+    )
+)]
+async fn get_index_aliases(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let mut aliases = index_scheduler.index_aliases()?;
+    aliases.retain(|alias, _| filters.is_index_authorized(alias));
+    debug!(returns = ?aliases, "Get index aliases");
+    Ok(HttpResponse::Ok().json(IndexAliasesView { aliases }))
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct PatchIndexAliases {
+    /// The indexes to search through each alias, `null` to delete the alias.
+    #[schema(value_type = BTreeMap<String, Option<Vec<String>>>, example = json!({ "logs": ["logs-2024", "logs-2025"], "old-logs": null }))]
+    #[deserr(error = DeserrJsonError<InvalidIndexAliases>)]
+    pub aliases: BTreeMap<IndexUid, Option<Vec<IndexUid>>>,
+}
+
+#[derive(Serialize)]
+pub struct PatchIndexAliasesAnalytics {
+    aliases: usize,
+}
+
+impl Aggregate for PatchIndexAliasesAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Index Aliases Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        new
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Update the index aliases
+///
+/// Create, replace or delete aliases, the other aliases are left untouched.
+/// An alias can't have the uid of an index, and the indexes of an alias can't be aliases.
+#[utoipa::path(
+    patch,
+    path = "",
+    tag = "Index aliases",
+    request_body = PatchIndexAliases,
+    security(("Bearer" = ["indexes.update", "indexes.*", "*"])),
+    responses(
+        (status = OK, description = "The aliases are updated and returned", body = IndexAliasesView, content_type = "application/json", example = json!(
+            { "aliases": { "logs": ["logs-2024", "logs-2025"] } }
+        )),
+        (status = 400, description = "An alias is invalid", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "`movies` is the uid of an index, it can't be used as an alias.",
+                "code": "invalid_index_aliases",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#invalid_index_aliases"
+            }
+        )),
+    )
+)]
+async fn patch_index_aliases(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    patch: AwebJson<PatchIndexAliases, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features().check_writes_allowed()?;
+    let mut aliases = index_scheduler.index_aliases()?;
+    let patch = patch.into_inner().aliases;
+    debug!(parameters = ?patch, "Patch index aliases");
+
+    let filters = index_scheduler.filters();
+    for (alias, indexes) in patch {
+        if !filters.is_index_authorized(&alias) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        let Some(mut indexes) = indexes else {
+            aliases.remove(alias.as_str());
+            continue;
+        };
+        if indexes.iter().any(|index_uid| !filters.is_index_authorized(index_uid)) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        if index_scheduler.index_exists(&alias)? {
+            return Err(ResponseError::from_msg(
+                format!("`{alias}` is the uid of an index, it can't be used as an alias."),
+                Code::InvalidIndexAliases,
+            ));
+        }
+        if indexes.is_empty() {
+            return Err(ResponseError::from_msg(
+                format!("The alias `{alias}` must have at least one index."),
+                Code::InvalidIndexAliases,
+            ));
+        }
+        indexes.sort_unstable();
+        indexes.dedup();
+        aliases.insert(alias.into_inner(), indexes.into_iter().map(IndexUid::into_inner).collect());
+    }
+
+    // the aliases are only resolved once, an alias can't stand for the indexes of another one
+    for (alias, indexes) in &aliases {
+        if let Some(index_uid) = indexes.iter().find(|index_uid| aliases.contains_key(*index_uid)) {
+            return Err(ResponseError::from_msg(
+                format!("The alias `{alias}` can't have the alias `{index_uid}` as an index."),
+                Code::InvalidIndexAliases,
+            ));
+        }
+    }
+
+    analytics.publish(PatchIndexAliasesAnalytics { aliases: aliases.len() }, &req);
+
+    index_scheduler.put_index_aliases(aliases.clone())?;
+    debug!(returns = ?aliases, "Patch index aliases");
+    Ok(HttpResponse::Ok().json(IndexAliasesView { aliases }))
+}
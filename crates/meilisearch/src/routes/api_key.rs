@@ -1,17 +1,26 @@
 use std::str;
+use std::str::FromStr;
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
+use index_scheduler::IndexScheduler;
 use meilisearch_auth::error::AuthControllerError;
-use meilisearch_auth::AuthController;
+use meilisearch_auth::{AuthController, MASTER_KEY_MIN_SIZE};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{Code, ResponseError};
-use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::error::{Code, ParseOffsetDateTimeError, ResponseError};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::keys::{
+    CreateApiKey, ExportedApiKey, Key, KeyQuotas, PatchApiKey, RateLimit, RotateApiKey,
+    RotateMasterKey,
+};
+use meilisearch_types::milli::update::Setting;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 use uuid::Uuid;
@@ -20,11 +29,23 @@ use super::{PaginationView, PAGINATION_DEFAULT_LIMIT, PAGINATION_DEFAULT_LIMIT_F
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::Pagination;
+use crate::routes::{is_sent_by_primary, Pagination};
+use crate::search::parse_filter_syntax;
+use crate::Opt;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_api_key, list_api_keys, get_api_key, patch_api_key, delete_api_key),
+    paths(
+        create_api_key,
+        list_api_keys,
+        get_api_key,
+        patch_api_key,
+        delete_api_key,
+        rotate_api_key,
+        rotate_master_key,
+        export_api_keys,
+        import_api_keys
+    ),
     tags((
         name = "Keys",
         description = "Manage API `keys` for a Meilisearch instance. Each key has a given set of permissions.
@@ -41,12 +62,19 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::post().to(SeqHandler(create_api_key)))
             .route(web::get().to(SeqHandler(list_api_keys))),
     )
+    // registered before `/{key}` and `/{key}/rotate` so that they aren't mistaken for keys.
+    .service(web::resource("/export").route(web::get().to(SeqHandler(export_api_keys))))
+    .service(web::resource("/import").route(web::post().to(SeqHandler(import_api_keys))))
+    .service(
+        web::resource("/master-key/rotate").route(web::post().to(SeqHandler(rotate_master_key))),
+    )
     .service(
         web::resource("/{key}")
             .route(web::get().to(SeqHandler(get_api_key)))
             .route(web::patch().to(SeqHandler(patch_api_key)))
             .route(web::delete().to(SeqHandler(delete_api_key))),
-    );
+    )
+    .service(web::resource("/{key}/rotate").route(web::post().to(SeqHandler(rotate_api_key))));
 }
 
 /// Create an API Key
@@ -96,10 +124,16 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 )]
 pub async fn create_api_key(
     auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_CREATE }>, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
     body: AwebJson<CreateApiKey, DeserrJsonError>,
-    _req: HttpRequest,
+    req: HttpRequest,
+    opt: Data<Opt>,
 ) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
     let v = body.into_inner();
+    if let Some(filter) = &v.filter {
+        parse_filter_syntax(filter, Code::InvalidApiKeyFilter)?;
+    }
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
         let key = auth_controller.create_key(v)?;
         Ok(KeyView::from_key(key, &auth_controller))
@@ -264,7 +298,7 @@ pub async fn get_api_key(
 
 /// Update a Key
 ///
-/// Update the name and description of an API key.
+/// Update the name, description and rate limit of an API key.
 /// Updates to keys are partial. This means you should provide only the fields you intend to update, as any fields not present in the payload will remain unchanged.
 #[utoipa::path(
     patch,
@@ -311,11 +345,18 @@ pub async fn get_api_key(
 )]
 pub async fn patch_api_key(
     auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_UPDATE }>, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
+    req: HttpRequest,
+    opt: Data<Opt>,
     body: AwebJson<PatchApiKey, DeserrJsonError>,
     path: web::Path<AuthParam>,
 ) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
     let key = path.into_inner().key;
     let patch_api_key = body.into_inner();
+    if let Setting::Set(filter) = &patch_api_key.filter {
+        parse_filter_syntax(filter, Code::InvalidApiKeyFilter)?;
+    }
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
         let uid =
             Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
@@ -360,8 +401,12 @@ pub async fn patch_api_key(
 )]
 pub async fn delete_api_key(
     auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_DELETE }>, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
+    req: HttpRequest,
+    opt: Data<Opt>,
     path: web::Path<AuthParam>,
 ) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
     let key = path.into_inner().key;
     tokio::task::spawn_blocking(move || {
         let uid =
@@ -374,6 +419,377 @@ pub async fn delete_api_key(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Rotate a Key
+///
+/// Generate a new value for an API key, keeping its uid, name, description, actions, indexes and expiration date.
+/// The previous value is still accepted for `overlapSeconds` seconds, so that the clients can switch to the new value without downtime.
+#[utoipa::path(
+    post,
+    path = "/{uidOrKey}/rotate",
+    tag = "Keys",
+    security(("Bearer" = ["keys.update", "keys.*", "*"])),
+    params(("uidOrKey" = String, Path, format = Password, example = "7b198a7f-52a0-4188-8762-9ad93cd608b2", description = "The `uid` or `key` field of an existing API key", nullable = false)),
+    request_body = RotateApiKey,
+    responses(
+        (status = 200, description = "The key have been rotated", body = KeyView, content_type = "application/json", example = json!(
+            {
+                "uid": "01b4bc42-eb33-4041-b481-254d00cce834",
+                "key": "9f2a0ce1b0b1d3a1a9e4ab1f9a3c2c5e0b9a1d2c3e4f5a6b7c8d9e0f1a2b3c4d",
+                "name": "An API Key",
+                "description": null,
+                "actions": [
+                    "documents.add"
+                ],
+                "indexes": [
+                    "movies"
+                ],
+                "expiresAt": "2022-11-12T10:00:00Z",
+                "createdAt": "2021-11-12T10:00:00Z",
+                "updatedAt": "2021-11-12T11:00:00Z",
+                "previousKeyExpiresAt": "2021-11-12T12:00:00Z"
+            }
+        )),
+        (status = 401, description = "The route has been hit on an unprotected instance", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.",
+                "code": "missing_master_key",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_master_key"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn rotate_api_key(
+    auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_UPDATE }>, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
+    req: HttpRequest,
+    opt: Data<Opt>,
+    body: AwebJson<RotateApiKey, DeserrJsonError>,
+    path: web::Path<AuthParam>,
+) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
+    let key = path.into_inner().key;
+    let RotateApiKey { overlap_seconds } = body.into_inner();
+    let overlap = overlap_seconds
+        .map(|seconds| time::Duration::seconds(seconds.try_into().unwrap_or(i64::MAX)));
+    let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
+        let uid =
+            Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
+        let key = auth_controller.rotate_key(uid, overlap)?;
+
+        Ok(KeyView::from_key(key, &auth_controller))
+    })
+    .await
+    .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::Ok().json(res))
+}
+
+/// Rotate the master key
+///
+/// Replace the master key of the instance. Every API key gets a new value derived from the new master key, and the
+/// tenant tokens signed with the previous values are no longer accepted.
+/// The previous master key is refused from now on: Meilisearch must be relaunched with the new one.
+#[utoipa::path(
+    post,
+    path = "/master-key/rotate",
+    tag = "Keys",
+    security(("Bearer" = [])),
+    request_body = RotateMasterKey,
+    responses(
+        (status = 204, description = "The master key has been rotated"),
+        (status = 400, description = "The new master key is invalid", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The master key must be at least 16 bytes. The provided key is only 8 bytes.",
+                "code": "invalid_master_key",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#invalid_master_key"
+            }
+        )),
+        (status = 401, description = "The route has been hit on an unprotected instance", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.",
+                "code": "missing_master_key",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_master_key"
+            }
+        )),
+    )
+)]
+pub async fn rotate_master_key(
+    auth_controller: GuardedData<MasterPolicy, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
+    req: HttpRequest,
+    opt: Data<Opt>,
+    body: AwebJson<RotateMasterKey, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
+    let RotateMasterKey { master_key } = body.into_inner();
+    if master_key.len() < MASTER_KEY_MIN_SIZE {
+        return Err(ResponseError::from_msg(
+            format!(
+                "The master key must be at least {MASTER_KEY_MIN_SIZE} bytes. The provided key is only {} bytes.",
+                master_key.len()
+            ),
+            Code::InvalidMasterKey,
+        ));
+    }
+    if auth_controller.get_master_key().as_ref() == Some(&master_key) {
+        return Err(ResponseError::from_msg(
+            "The new master key must be different from the current one.".to_string(),
+            Code::InvalidMasterKey,
+        ));
+    }
+
+    tokio::task::spawn_blocking(move || auth_controller.rotate_master_key(master_key))
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Debug, Serialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyImport>, rename_all = camelCase, deny_unknown_fields)]
+pub struct ApiKeysExport {
+    /// The keys, along with the SHA-256 digest of their value.
+    keys: Vec<ExportedApiKeyView>,
+}
+
+/// A key as exported to be imported on another instance.
+#[derive(Debug, Serialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyImport>, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct ExportedApiKeyView {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    name: Option<String>,
+    #[deserr(try_from(&String) = Uuid::from_str -> uuid::Error)]
+    uid: Uuid,
+    actions: Vec<Action>,
+    #[schema(value_type = Vec<String>)]
+    indexes: Vec<IndexUidPattern>,
+    #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
+    #[deserr(try_from(Option<String>) = parse_optional_date -> ParseOffsetDateTimeError)]
+    expires_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimit>)]
+    rate_limit: Option<RateLimit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyQuotas>)]
+    quotas: Option<KeyQuotas>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<Value>)]
+    filter: Option<Value>,
+    #[serde(serialize_with = "time::serde::rfc3339::serialize")]
+    #[deserr(try_from(String) = parse_date -> ParseOffsetDateTimeError)]
+    created_at: OffsetDateTime,
+    #[serde(serialize_with = "time::serde::rfc3339::serialize")]
+    #[deserr(try_from(String) = parse_date -> ParseOffsetDateTimeError)]
+    updated_at: OffsetDateTime,
+    /// The number of times the value of the key was rotated, the value is derived from it.
+    #[deserr(default)]
+    rotations: u32,
+    #[serde(
+        serialize_with = "time::serde::rfc3339::option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[deserr(default, try_from(Option<String>) = parse_optional_date -> ParseOffsetDateTimeError)]
+    previous_key_expires_at: Option<OffsetDateTime>,
+    /// The SHA-256 digest of the value of the key, so that the importing instance can make sure
+    /// it derives the same value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    key_sha256: Option<String>,
+}
+
+impl From<ExportedApiKey> for ExportedApiKeyView {
+    fn from(ExportedApiKey { key, key_sha256 }: ExportedApiKey) -> Self {
+        ExportedApiKeyView {
+            description: key.description,
+            name: key.name,
+            uid: key.uid,
+            actions: key.actions,
+            indexes: key.indexes,
+            expires_at: key.expires_at,
+            rate_limit: key.rate_limit,
+            quotas: key.quotas,
+            filter: key.filter,
+            created_at: key.created_at,
+            updated_at: key.updated_at,
+            rotations: key.rotations,
+            previous_key_expires_at: key.previous_key_expires_at,
+            key_sha256,
+        }
+    }
+}
+
+impl From<ExportedApiKeyView> for ExportedApiKey {
+    fn from(view: ExportedApiKeyView) -> Self {
+        let key = Key {
+            description: view.description,
+            name: view.name,
+            uid: view.uid,
+            actions: view.actions,
+            indexes: view.indexes,
+            expires_at: view.expires_at,
+            rate_limit: view.rate_limit,
+            quotas: view.quotas,
+            filter: view.filter,
+            created_at: view.created_at,
+            updated_at: view.updated_at,
+            rotations: view.rotations,
+            previous_key_expires_at: view.previous_key_expires_at,
+        };
+        ExportedApiKey { key, key_sha256: view.key_sha256 }
+    }
+}
+
+fn parse_date(string: String) -> Result<OffsetDateTime, ParseOffsetDateTimeError> {
+    OffsetDateTime::parse(&string, &Rfc3339).map_err(|_| ParseOffsetDateTimeError(string))
+}
+
+fn parse_optional_date(
+    string: Option<String>,
+) -> Result<Option<OffsetDateTime>, ParseOffsetDateTimeError> {
+    string.map(parse_date).transpose()
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeysImportView {
+    /// The number of keys created or replaced.
+    imported_keys: usize,
+}
+
+/// Export the keys
+///
+/// Export all the API keys so that they can be imported on another instance launched with the same master key,
+/// for example during a blue/green deployment. The values of the keys are not exported, only their SHA-256 digest.
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = "Keys",
+    security(("Bearer" = [])),
+    responses(
+        (status = 200, description = "The keys are returned", body = ApiKeysExport, content_type = "application/json", example = json!(
+            {
+                "keys": [
+                    {
+                        "name": "Default Search API Key",
+                        "description": "Use it to search from the frontend",
+                        "uid": "10a5ef6c-5d07-4b5b-9b37-d1a3e0ac1b4a",
+                        "actions": ["search"],
+                        "indexes": ["*"],
+                        "expiresAt": null,
+                        "createdAt": "2021-08-12T10:00:00Z",
+                        "updatedAt": "2021-08-12T10:00:00Z",
+                        "rotations": 0,
+                        "keySha256": "3f1a0d5b0b0f4f0e8e1c5b2b0c1f7d9a4e2b6c8d0f1a3b5c7d9e1f3a5b7c9d1e"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The route has been hit on an unprotected instance", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.",
+                "code": "missing_master_key",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_master_key"
+            }
+        )),
+    )
+)]
+pub async fn export_api_keys(
+    auth_controller: GuardedData<MasterPolicy, Data<AuthController>>,
+) -> Result<HttpResponse, ResponseError> {
+    let keys = tokio::task::spawn_blocking(move || auth_controller.export_keys())
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    let keys = keys.into_iter().map(ExportedApiKeyView::from).collect();
+    Ok(HttpResponse::Ok().json(ApiKeysExport { keys }))
+}
+
+/// Import keys
+///
+/// Import the keys exported from another instance, replacing the existing keys with the same `uid`.
+/// Nothing is imported if one of the keys would get a different value on this instance, which happens when it
+/// wasn't launched with the master key of the instance the keys were exported from.
+#[utoipa::path(
+    post,
+    path = "/import",
+    tag = "Keys",
+    security(("Bearer" = [])),
+    request_body = ApiKeysExport,
+    responses(
+        (status = 200, description = "The keys have been imported", body = ApiKeysImportView, content_type = "application/json", example = json!(
+            {
+                "importedKeys": 2
+            }
+        )),
+        (status = 400, description = "The keys would get different values", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "API key `10a5ef6c-5d07-4b5b-9b37-d1a3e0ac1b4a` would get a different value on this instance. Meilisearch must be launched with the master key of the instance the keys were exported from.",
+                "code": "invalid_api_key_import",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#invalid_api_key_import"
+            }
+        )),
+        (status = 401, description = "The route has been hit on an unprotected instance", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.",
+                "code": "missing_master_key",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_master_key"
+            }
+        )),
+    )
+)]
+pub async fn import_api_keys(
+    auth_controller: GuardedData<MasterPolicy, Data<AuthController>>,
+    index_scheduler: Data<IndexScheduler>,
+    req: HttpRequest,
+    opt: Data<Opt>,
+    body: AwebJson<ApiKeysExport, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    check_keys_writable(&index_scheduler, &req, &opt)?;
+    let ApiKeysExport { keys } = body.into_inner();
+    let keys = keys.into_iter().map(ExportedApiKey::from).collect();
+    let imported_keys = tokio::task::spawn_blocking(move || auth_controller.import_keys(keys))
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::Ok().json(ApiKeysImportView { imported_keys }))
+}
+
+/// The keys are stored on disk, they can't be modified while the instance is read-only.
+/// A read-only replica only accepts the keys replicated by its primary.
+fn check_keys_writable(
+    index_scheduler: &IndexScheduler,
+    req: &HttpRequest,
+    opt: &Opt,
+) -> Result<(), ResponseError> {
+    index_scheduler.features().check_writes_allowed()?;
+    if opt.read_only_replica && !is_sent_by_primary(req) {
+        return Err(index_scheduler::Error::ReadOnlyReplica.into());
+    }
+    Ok(())
+}
+
 #[derive(Deserialize)]
 pub struct AuthParam {
     key: String,
@@ -397,6 +813,16 @@ pub(super) struct KeyView {
     /// The expiration date of the key. Once this timestamp is exceeded the key is not deleted but cannot be used anymore.
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     expires_at: Option<OffsetDateTime>,
+    /// The maximum number of requests the key can make in a period, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit: Option<RateLimit>,
+    /// The resources the tasks registered with the key can use, if limited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quotas: Option<KeyQuotas>,
+    /// The filter applied to every search and document read made with the key, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Value>)]
+    filter: Option<Value>,
     /// The date of creation of this API Key.
     #[schema(read_only)]
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
@@ -405,11 +831,18 @@ pub(super) struct KeyView {
     #[schema(read_only)]
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     updated_at: OffsetDateTime,
+    /// Until when the value of the key before its last rotation is still accepted.
+    #[schema(read_only)]
+    #[serde(
+        serialize_with = "time::serde::rfc3339::option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    previous_key_expires_at: Option<OffsetDateTime>,
 }
 
 impl KeyView {
     fn from_key(key: Key, auth: &AuthController) -> Self {
-        let generated_key = auth.generate_key(key.uid).unwrap_or_default();
+        let generated_key = auth.generate_key(&key).unwrap_or_default();
 
         KeyView {
             name: key.name,
@@ -419,8 +852,12 @@ impl KeyView {
             actions: key.actions,
             indexes: key.indexes.into_iter().map(|x| x.to_string()).collect(),
             expires_at: key.expires_at,
+            rate_limit: key.rate_limit,
+            quotas: key.quotas,
+            filter: key.filter,
             created_at: key.created_at,
             updated_at: key.updated_at,
+            previous_key_expires_at: key.previous_key_expires_at,
         }
     }
 }
@@ -1,22 +1,40 @@
-use actix_web::web::Data;
-use actix_web::{web, HttpRequest, HttpResponse};
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use actix_web::web::{Bytes, BytesMut, Data};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use futures::StreamExt;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::{
+    InvalidDumpImportUrl, InvalidDumpIndexRenames, InvalidDumpIndexUids, InvalidDumpSkipKeys,
+    InvalidDumpSkipTasks, InvalidIndexUid, InvalidRestoreDumpUid,
+};
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::tasks::KindWithContent;
+use tokio::io::AsyncWriteExt;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use crate::analytics::Analytics;
+use crate::error::{MeilisearchHttpError, PayloadError};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::routes::{get_depends_on, get_task_id, get_task_labels, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_dump),
+    paths(create_dump, import_dump, restore_index),
+    components(schemas(DumpParameters, DumpImportUrl, DumpIndexRestoreParameters)),
     tags((
         name = "Dumps",
         description = "The `dumps` route allows the creation of database dumps.
@@ -26,27 +44,53 @@ During a [dump export](https://www.meilisearch.com/docs/reference/api/dump#creat
 exported—together with their documents and settings—and saved as a single `.dump` file. During a dump import,
 all indexes contained in the indicated `.dump` file are imported along with their associated documents and settings.
 Any existing index with the same uid as an index in the dump file will be overwritten.
-Dump imports are [performed at launch](https://www.meilisearch.com/docs/learn/advanced/dumps#importing-a-dump) using an option.",
+Dump imports are [performed at launch](https://www.meilisearch.com/docs/learn/advanced/dumps#importing-a-dump) using an option,
+or in the running instance with the `/dumps/import` route, which only imports the indexes of the dump.
+A single index can be restored from a dump of the dump directory with the `/dumps/restore-index` route.",
         external_docs(url = "https://www.meilisearch.com/docs/reference/api/dump"),
     )),
 )]
 pub struct DumpApi;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))))
+        .service(web::resource("/import").route(web::post().to(SeqHandler(import_dump))))
+        .service(web::resource("/restore-index").route(web::post().to(SeqHandler(restore_index))));
 }
 
 crate::empty_analytics!(DumpAnalytics, "Dump Created");
+crate::empty_analytics!(DumpImportAnalytics, "Dump Imported");
+crate::empty_analytics!(DumpIndexRestoreAnalytics, "Index Restored From Dump");
+
+/// Restricts what is exported in the dump, everything is exported by default.
+#[derive(Deserr, Debug, Default, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct DumpParameters {
+    /// The indexes to export, along with their tasks. All the indexes are exported when omitted.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["movies"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidDumpIndexUids>)]
+    pub index_uids: Option<Vec<IndexUid>>,
+    /// Leaves the task queue and the batches out of the dump.
+    #[deserr(default, error = DeserrJsonError<InvalidDumpSkipTasks>)]
+    pub skip_tasks: bool,
+    /// Leaves the API keys out of the dump.
+    #[deserr(default, error = DeserrJsonError<InvalidDumpSkipKeys>)]
+    pub skip_keys: bool,
+}
 
 /// Create a dump
 ///
 /// Triggers a dump creation process. Once the process is complete, a dump is created in the
 /// [dump directory](https://www.meilisearch.com/docs/learn/self_hosted/configure_meilisearch_at_launch#dump-directory).
 /// If the dump directory does not exist yet, it will be created.
+///
+/// The optional body restricts the dump to some indexes or leaves the tasks or the keys out of it.
 #[utoipa::path(
     post,
     path = "",
     tag = "Dumps",
+    request_body = DumpParameters,
     security(("Bearer" = ["dumps.create", "dumps.*", "*"])),
     responses(
         (status = 202, description = "Dump is being created", body = SummarizedTaskView, content_type = "application/json", example = json!(
@@ -71,23 +115,309 @@ crate::empty_analytics!(DumpAnalytics, "Dump Created");
 pub async fn create_dump(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
     auth_controller: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<AuthController>>,
+    body: Bytes,
     req: HttpRequest,
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
+    // the body is optional to keep accepting the requests without any
+    let parameters = if body.is_empty() {
+        DumpParameters::default()
+    } else {
+        match serde_json::from_slice(&body).map_err(PayloadError::MalformedPayload)? {
+            serde_json::Value::Null => DumpParameters::default(),
+            value => deserr::deserialize::<_, _, DeserrJsonError>(value)?,
+        }
+    };
+    debug!(parameters = ?parameters, "Create dump");
     analytics.publish(DumpAnalytics::default(), &req);
 
+    let DumpParameters { index_uids, skip_tasks, skip_keys } = parameters;
     let task = KindWithContent::DumpCreation {
-        keys: auth_controller.list_keys()?,
+        keys: if skip_keys { Vec::new() } else { auth_controller.list_keys()? },
         instance_uid: analytics.instance_uid().cloned(),
+        index_uids: index_uids.map(|uids| uids.into_iter().map(IndexUid::into_inner).collect()),
+        skip_tasks,
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Create dump");
     Ok(HttpResponse::Accepted().json(task))
 }
+
+#[derive(Deserr, Debug, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct ImportDumpQuery {
+    /// The new names of the imported indexes, formatted as `nameInTheDump:newName`.
+    #[param(value_type = Option<Vec<String>>, example = json!(["movies:movies_restored"]))]
+    #[deserr(default, error = DeserrQueryParamError<InvalidDumpIndexRenames>)]
+    pub index_renames: Option<CS<String>>,
+}
+
+impl ImportDumpQuery {
+    fn index_renames(self) -> Result<BTreeMap<String, String>, ResponseError> {
+        let mut renames = BTreeMap::new();
+        for rename in self.index_renames.into_iter().flatten() {
+            let invalid = |reason: String| {
+                ResponseError::from_msg(
+                    format!("Invalid index rename `{rename}`: {reason}"),
+                    Code::InvalidDumpIndexRenames,
+                )
+            };
+            let Some((from, to)) = rename.split_once(':') else {
+                return Err(invalid(
+                    "it must be formatted as `nameInTheDump:newName`.".to_string(),
+                ));
+            };
+            let from = IndexUid::try_from(from.to_string()).map_err(|e| invalid(e.to_string()))?;
+            let to = IndexUid::try_from(to.to_string()).map_err(|e| invalid(e.to_string()))?;
+            if renames.insert(from.into_inner(), to.into_inner()).is_some() {
+                return Err(invalid("the index is renamed several times.".to_string()));
+            }
+        }
+        Ok(renames)
+    }
+}
+
+/// Where to download the dump to import from.
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct DumpImportUrl {
+    /// The HTTP(S) URL serving the dump, e.g. an S3 presigned URL.
+    #[deserr(error = DeserrJsonError<InvalidDumpImportUrl>)]
+    #[schema(example = "https://example.com/20250101-120000000.dump")]
+    pub url: String,
+}
+
+/// Import a dump
+///
+/// Enqueues a task importing the indexes of a dump into the running instance, without restarting
+/// it with the `--import-dump` option. The keys, the tasks and the experimental features of the
+/// dump are ignored, and the task fails if one of the imported indexes already exists.
+///
+/// The dump is either sent as the body of the request, which is limited by the
+/// `--http-payload-size-limit` option, or downloaded by the task from the `url` of a JSON body.
+/// Downloading the dump requires launching Meilisearch with the `--experimental-import-url-allowed-hosts`
+/// option, and the dump can only be downloaded from the allowed hosts.
+#[utoipa::path(
+    post,
+    path = "/import",
+    tag = "Dumps",
+    params(ImportDumpQuery),
+    request_body = DumpImportUrl,
+    security(("Bearer" = ["dumps.import", "dumps.*", "*"])),
+    responses(
+        (status = 202, description = "Dump is being imported", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 0,
+                "indexUid": null,
+                "status": "enqueued",
+                "type": "dumpImport",
+                "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn import_dump(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_IMPORT }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<ImportDumpQuery, DeserrQueryParamError>,
+    mut body: Payload,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_renames = params.into_inner().index_renames()?;
+    debug!(index_renames = ?index_renames, "Import dump");
+    analytics.publish(DumpImportAnalytics::default(), &req);
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+
+    let is_json =
+        matches!(req.mime_type(), Ok(Some(mime)) if mime.essence_str() == "application/json");
+    let (dump_file, url) = if is_json {
+        let mut bytes = BytesMut::new();
+        while let Some(chunk) = body.next().await {
+            bytes.extend_from_slice(&chunk?);
+        }
+        let value = serde_json::from_slice(&bytes).map_err(PayloadError::MalformedPayload)?;
+        let DumpImportUrl { url } = deserr::deserialize::<_, _, DeserrJsonError>(value)?;
+        validate_dump_url(&url, &opt)?;
+        (None, Some(url))
+    } else {
+        let (uuid, update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+        let (path, file) = update_file.into_parts();
+        let file = match file {
+            Some(file) => {
+                let (file, temp_path) = file.into_parts();
+                let file = copy_dump_to_file(file, body).await?;
+                Some(tempfile::NamedTempFile::from_parts(file, temp_path))
+            }
+            None => None,
+        };
+        file_store::File::from_parts(path, file).persist()?;
+        (Some(uuid), None)
+    };
+
+    let task = KindWithContent::DumpImport { dump_file, url, index_renames };
+    let scheduler = index_scheduler.clone();
+    let task = match tokio::task::spawn_blocking(move || {
+        scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await?
+    {
+        Ok(task) => task,
+        Err(e) => {
+            if let Some(uuid) = dump_file {
+                index_scheduler.queue.delete_update_file(uuid)?;
+            }
+            return Err(e.into());
+        }
+    };
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Import dump");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// The index to restore and the dump to restore it from.
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct DumpIndexRestoreParameters {
+    /// The index to restore.
+    #[schema(value_type = String, example = "movies")]
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    pub index_uid: IndexUid,
+    /// The uid of a dump of the dump directory, as returned by its `dumpCreation` task.
+    #[schema(example = "20250101-120000000")]
+    #[deserr(error = DeserrJsonError<InvalidRestoreDumpUid>, missing_field_error = DeserrJsonError::missing_restore_dump_uid)]
+    pub dump_uid: String,
+}
+
+/// Restore an index from a dump
+///
+/// Enqueues a task replacing an index with its copy from a dump of the dump directory, or
+/// creating it if it was deleted. The other indexes of the dump are left untouched.
+#[utoipa::path(
+    post,
+    path = "/restore-index",
+    tag = "Dumps",
+    request_body = DumpIndexRestoreParameters,
+    security(("Bearer" = ["dumps.import", "dumps.*", "*"])),
+    responses(
+        (status = 202, description = "The index is being restored", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 0,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexRestore",
+                "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn restore_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_IMPORT }>, Data<IndexScheduler>>,
+    body: AwebJson<DumpIndexRestoreParameters, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let DumpIndexRestoreParameters { index_uid, dump_uid } = body.into_inner();
+    debug!(index_uid = ?index_uid, dump_uid = ?dump_uid, "Restore index from dump");
+    analytics.publish(DumpIndexRestoreAnalytics::default(), &req);
+
+    // only the dumps of the dump directory can be restored from
+    if Path::new(&dump_uid).file_name() != Some(OsStr::new(&dump_uid)) {
+        return Err(ResponseError::from_msg(
+            format!("Invalid dump uid `{dump_uid}`: it must be the uid of a dump of the dump directory."),
+            Code::InvalidRestoreDumpUid,
+        ));
+    }
+
+    let task = KindWithContent::IndexRestore {
+        index_uid: index_uid.into_inner(),
+        snapshot: None,
+        dump_uid: Some(dump_uid),
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
+
+    debug!(returns = ?task, "Restore index from dump");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Ensure the dump can be downloaded from the URL by the task runner.
+fn validate_dump_url(url: &str, opt: &Opt) -> Result<(), ResponseError> {
+    if opt.experimental_import_url_allowed_hosts.is_none() {
+        return Err(index_scheduler::Error::DumpImportUrlDisabled.into());
+    }
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        Ok(parsed) => Err(ResponseError::from_msg(
+            format!(
+                "Invalid url `{url}`: the `{}` scheme is not supported, the url must use `http` or `https`.",
+                parsed.scheme()
+            ),
+            Code::InvalidDumpImportUrl,
+        )),
+        Err(e) => Err(ResponseError::from_msg(
+            format!("Invalid url `{url}`: {e}."),
+            Code::InvalidDumpImportUrl,
+        )),
+    }
+}
+
+async fn copy_dump_to_file(
+    output: std::fs::File,
+    mut body: Payload,
+) -> Result<std::fs::File, MeilisearchHttpError> {
+    let mut file = tokio::io::BufWriter::new(tokio::fs::File::from_std(output));
+    let mut written = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        written += chunk.len();
+        file.write_all(&chunk).await.map_err(|e| PayloadError::ReceivePayload(Box::new(e)))?;
+    }
+    file.flush().await.map_err(|e| PayloadError::ReceivePayload(Box::new(e)))?;
+    if written == 0 {
+        return Err(MeilisearchHttpError::MissingDumpArchive);
+    }
+    Ok(file.into_inner().into_std().await)
+}
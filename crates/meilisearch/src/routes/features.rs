@@ -53,6 +53,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             network: Some(false),
             get_task_documents_route: Some(false),
             composite_embedders: Some(false),
+            read_only: Some(false),
         })),
         (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
@@ -97,6 +98,8 @@ pub struct RuntimeTogglableFeatures {
     pub get_task_documents_route: Option<bool>,
     #[deserr(default)]
     pub composite_embedders: Option<bool>,
+    #[deserr(default)]
+    pub read_only: Option<bool>,
 }
 
 impl From<meilisearch_types::features::RuntimeTogglableFeatures> for RuntimeTogglableFeatures {
@@ -109,6 +112,7 @@ impl From<meilisearch_types::features::RuntimeTogglableFeatures> for RuntimeTogg
             network,
             get_task_documents_route,
             composite_embedders,
+            read_only,
         } = value;
 
         Self {
@@ -119,6 +123,7 @@ impl From<meilisearch_types::features::RuntimeTogglableFeatures> for RuntimeTogg
             network: Some(network),
             get_task_documents_route: Some(get_task_documents_route),
             composite_embedders: Some(composite_embedders),
+            read_only: Some(read_only),
         }
     }
 }
@@ -132,6 +137,7 @@ pub struct PatchExperimentalFeatureAnalytics {
     network: bool,
     get_task_documents_route: bool,
     composite_embedders: bool,
+    read_only: bool,
 }
 
 impl Aggregate for PatchExperimentalFeatureAnalytics {
@@ -148,6 +154,7 @@ impl Aggregate for PatchExperimentalFeatureAnalytics {
             network: new.network,
             get_task_documents_route: new.get_task_documents_route,
             composite_embedders: new.composite_embedders,
+            read_only: new.read_only,
         })
     }
 
@@ -173,6 +180,7 @@ impl Aggregate for PatchExperimentalFeatureAnalytics {
             network: Some(false),
             get_task_documents_route: Some(false),
             composite_embedders: Some(false),
+            read_only: Some(false),
          })),
         (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
@@ -214,6 +222,7 @@ async fn patch_features(
             .0
             .composite_embedders
             .unwrap_or(old_features.composite_embedders),
+        read_only: new_features.0.read_only.unwrap_or(old_features.read_only),
     };
 
     // explicitly destructure for analytics rather than using the `Serialize` implementation, because
@@ -227,6 +236,7 @@ async fn patch_features(
         network,
         get_task_documents_route,
         composite_embedders,
+        read_only,
     } = new_features;
 
     analytics.publish(
@@ -238,6 +248,7 @@ async fn patch_features(
             network,
             get_task_documents_route,
             composite_embedders,
+            read_only,
         },
         &req,
     );
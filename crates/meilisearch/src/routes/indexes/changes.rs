@@ -0,0 +1,318 @@
+use std::io::{BufReader, ErrorKind};
+
+use actix_web::web::{self, Bytes, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, RoFeatures, TaskId};
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::tasks::{KindWithContent, Task};
+use meilisearch_types::{Document, Index};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use super::documents::evaluate_filter;
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::parse_filter;
+
+/// The number of bytes of changes buffered before being sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_index_changes),
+    tags(
+        (
+            name = "Index changes",
+            description = "The `/changes` route streams the changes made to the documents of an index by its succeeded tasks, so that external systems can follow the index instead of exporting all of its documents again.
+It requires launching Meilisearch with the `--experimental-change-stream` option.",
+        ),
+    ),
+)]
+pub struct IndexChangesApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_index_changes))));
+}
+
+crate::empty_analytics!(IndexChangesAnalytics, "Index Changes Streamed");
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct IndexChangesQuery {
+    /// Only stream the changes made by the tasks registered after the task with this uid.
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexChangesSince>)]
+    #[param(required = false, value_type = Option<u32>, example = 147)]
+    pub since: Option<Param<TaskId>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentChangeType {
+    /// The document was added or replaced.
+    Upsert,
+    /// The fields of the document were added or updated, it was added if it didn't exist.
+    Update,
+    /// The document was deleted.
+    Deletion,
+    /// All the documents of the index were deleted.
+    Clear,
+    /// The documents changed by the task are not known, the index must be read again.
+    Resync,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentChangeView {
+    /// The uid of the task that made the change.
+    pub task_uid: TaskId,
+    #[serde(rename = "type")]
+    pub change_type: DocumentChangeType,
+    /// The document sent by the task, only its updated fields for an `update`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub document: Option<Document>,
+    /// The id of the deleted document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+}
+
+impl DocumentChangeView {
+    fn new(task_uid: TaskId, change_type: DocumentChangeType) -> Self {
+        DocumentChangeView { task_uid, change_type, document: None, document_id: None }
+    }
+}
+
+/// Stream the changes of the documents
+///
+/// Stream the changes made to the documents of the index by its succeeded tasks, oldest first, as NDJSON.
+/// Only the tasks processed before the first task of the index that is still enqueued are streamed,
+/// so the `taskUid` of the last change can be given as `since` to resume from it without missing any change.
+///
+/// The documents are only known while the task that sent them exists. When they aren't, or when the task
+/// changed documents it doesn't list, like a deletion by filter or a JSON patch, a `resync` change is sent instead.
+/// The ids of the deleted documents are not sent to the keys restricted by a filter, which get a `resync` change too.
+#[utoipa::path(
+    get,
+    path = "{indexUid}/changes",
+    tag = "Index changes",
+    security(("Bearer" = ["documents.get", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+        IndexChangesQuery,
+    ),
+    responses(
+        (status = 200, description = "The changes are streamed", body = DocumentChangeView, content_type = "application/x-ndjson", example = json!(
+            r#"
+{"taskUid":147,"type":"upsert","document":{"id":85087,"title":"American Ninja 5"}}
+{"taskUid":148,"type":"deletion","documentId":"287947"}
+{"taskUid":151,"type":"resync"}
+"#
+        )),
+        (status = 400, description = "The change stream is not enabled", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Streaming the changes of the documents requires launching Meilisearch with the `--experimental-change-stream` option.",
+                "code": "feature_not_enabled",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+    )
+)]
+pub async fn get_index_changes(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<IndexChangesQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let since = params.into_inner().since.map(|since| since.0);
+    analytics.publish(IndexChangesAnalytics::default(), &req);
+
+    let index = index_scheduler.index(&index_uid)?;
+    let tasks = index_scheduler.document_change_tasks(&index_uid, since)?;
+    debug!(returns = ?tasks.len(), "Stream index changes");
+
+    let key_filter = index_scheduler.filters().key_filter().cloned();
+    let features = index_scheduler.features();
+    let scheduler = index_scheduler.clone();
+    let (sender, receiver) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = send_changes(&scheduler, &index, tasks, key_filter, features, &sender) {
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+    Ok(HttpResponse::Ok().content_type("application/x-ndjson").streaming(stream))
+}
+
+/// Sends the changes made by the tasks to the client, as soon as `CHUNK_SIZE` bytes are ready.
+///
+/// The documents the key can't currently read are left out.
+fn send_changes(
+    index_scheduler: &IndexScheduler,
+    index: &Index,
+    tasks: Vec<Task>,
+    key_filter: Option<Value>,
+    features: RoFeatures,
+    sender: &mpsc::Sender<Result<Bytes, ResponseError>>,
+) -> Result<(), ResponseError> {
+    let rtxn = index.read_txn()?;
+    let readable = match key_filter {
+        Some(filter) => match parse_filter(&filter, Code::InvalidDocumentFilter, features)? {
+            Some(filter) => Some(evaluate_filter(&filter, &rtxn, index)?),
+            None => None,
+        },
+        None => None,
+    };
+    let primary_key = index.primary_key(&rtxn)?.map(String::from);
+
+    let mut chunk = Vec::new();
+    for task in tasks {
+        let task_uid = task.uid;
+        match task.kind {
+            KindWithContent::DocumentAdditionOrUpdate { method, content_file, .. } => {
+                let change_type = match method {
+                    IndexDocumentsMethod::ReplaceDocuments
+                    | IndexDocumentsMethod::FailOnExistingDocuments => DocumentChangeType::Upsert,
+                    IndexDocumentsMethod::UpdateDocuments => DocumentChangeType::Update,
+                    // the payload is not the changed documents, or only some of them were changed
+                    IndexDocumentsMethod::MergePatchDocuments
+                    | IndexDocumentsMethod::JsonPatchDocuments
+                    | IndexDocumentsMethod::SkipExistingDocuments => {
+                        let change = DocumentChangeView::new(task_uid, DocumentChangeType::Resync);
+                        write_change(&mut chunk, &change)?;
+                        continue;
+                    }
+                };
+                let file = match index_scheduler.queue.update_file(content_file) {
+                    Ok(file) => file,
+                    Err(file_store::Error::IoError(e)) if e.kind() == ErrorKind::NotFound => {
+                        let change = DocumentChangeView::new(task_uid, DocumentChangeType::Resync);
+                        write_change(&mut chunk, &change)?;
+                        continue;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                let documents =
+                    serde_json::Deserializer::from_reader(BufReader::new(file)).into_iter();
+                for document in documents {
+                    let document: Document = document
+                        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))?;
+                    if let Some(readable) = &readable {
+                        let external_id = primary_key
+                            .as_deref()
+                            .and_then(|primary_key| document.get(primary_key))
+                            .and_then(|id| match id {
+                                Value::String(id) => Some(id.clone()),
+                                Value::Number(id) => Some(id.to_string()),
+                                _ => None,
+                            });
+                        let docid = match external_id {
+                            Some(id) => index.external_documents_ids().get(&rtxn, &id)?,
+                            None => None,
+                        };
+                        if !docid.is_some_and(|docid| readable.contains(docid)) {
+                            continue;
+                        }
+                    }
+                    let change = DocumentChangeView {
+                        document: Some(document),
+                        ..DocumentChangeView::new(task_uid, change_type)
+                    };
+                    write_change(&mut chunk, &change)?;
+                    if chunk.len() >= CHUNK_SIZE && !send_chunk(&mut chunk, sender) {
+                        return Ok(());
+                    }
+                }
+            }
+            // the deleted documents can't be checked against the filter of the key anymore
+            KindWithContent::DocumentDeletion { .. } if readable.is_some() => {
+                write_change(
+                    &mut chunk,
+                    &DocumentChangeView::new(task_uid, DocumentChangeType::Resync),
+                )?;
+            }
+            KindWithContent::DocumentDeletion { documents_ids, .. } => {
+                for document_id in documents_ids {
+                    let change = DocumentChangeView {
+                        document_id: Some(document_id),
+                        ..DocumentChangeView::new(task_uid, DocumentChangeType::Deletion)
+                    };
+                    write_change(&mut chunk, &change)?;
+                }
+            }
+            KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
+                write_change(
+                    &mut chunk,
+                    &DocumentChangeView::new(task_uid, DocumentChangeType::Clear),
+                )?;
+            }
+            // the documents changed by these tasks are not recorded
+            KindWithContent::DocumentImportUrl { .. }
+            | KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentEdition { .. }
+            | KindWithContent::IndexSwap { .. }
+            | KindWithContent::DumpImport { .. }
+            | KindWithContent::PointInTimeRestore { .. }
+            | KindWithContent::IndexRestore { .. } => {
+                write_change(
+                    &mut chunk,
+                    &DocumentChangeView::new(task_uid, DocumentChangeType::Resync),
+                )?;
+            }
+            KindWithContent::SettingsUpdate { .. }
+            | KindWithContent::IndexCreation { .. }
+            | KindWithContent::IndexUpdate { .. }
+            | KindWithContent::TaskCancelation { .. }
+            | KindWithContent::TaskDeletion { .. }
+            | KindWithContent::DumpCreation { .. }
+            | KindWithContent::SnapshotCreation
+            | KindWithContent::UpgradeDatabase { .. } => (),
+        }
+        if chunk.len() >= CHUNK_SIZE && !send_chunk(&mut chunk, sender) {
+            return Ok(());
+        }
+    }
+    if !chunk.is_empty() {
+        send_chunk(&mut chunk, sender);
+    }
+    Ok(())
+}
+
+fn write_change(chunk: &mut Vec<u8>, change: &DocumentChangeView) -> Result<(), ResponseError> {
+    serde_json::to_writer(&mut *chunk, change)
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))?;
+    chunk.push(b'\n');
+    Ok(())
+}
+
+/// Sends the chunk to the client and empties it, returns `false` if the client is gone.
+fn send_chunk(chunk: &mut Vec<u8>, sender: &mpsc::Sender<Result<Bytes, ResponseError>>) -> bool {
+    sender.blocking_send(Ok(Bytes::from(std::mem::take(chunk)))).is_ok()
+}
@@ -1,6 +1,7 @@
-use std::collections::HashSet;
-use std::io::{ErrorKind, Seek as _};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{ErrorKind, Read as _, Seek as _, Write as _};
 use std::marker::PhantomData;
+use std::str::FromStr as _;
 
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
@@ -12,14 +13,18 @@ use futures::StreamExt;
 use index_scheduler::{IndexScheduler, RoFeatures, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_avro, read_csv, read_json, read_ndjson, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::document_revisions::DocumentRevision;
+use meilisearch_types::milli::update::new::indexer::{DocumentDiagnostics, DocumentsValidation};
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::{AscDesc, DocumentId, Member};
 use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
@@ -42,14 +47,21 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::fix_sort_query_parameters;
 use crate::routes::{
-    get_task_id, is_dry_run, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
+    get_depends_on, get_task_id, get_task_labels, is_dry_run, PaginationView, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
 };
-use crate::search::{parse_filter, ExternalDocumentId, RetrieveVectors};
+use crate::search::{add_search_rules, parse_filter, ExternalDocumentId, RetrieveVectors};
 use crate::{aggregate_methods, Opt};
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "application/avro".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -74,9 +86,16 @@ pub struct DocumentParam {
     document_id: String,
 }
 
+#[derive(Deserialize)]
+pub struct DocumentRevisionParam {
+    index_uid: String,
+    document_id: String,
+    revision: u64,
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    paths(get_document, get_documents, delete_document, replace_documents, update_documents, clear_all_documents, delete_documents_batch, delete_documents_by_filter, edit_documents_by_function, documents_by_query_post),
+    paths(get_document, get_documents, delete_document, replace_documents, update_documents, patch_documents, clear_all_documents, delete_documents_batch, delete_documents_by_filter, edit_documents_by_function, json_patch_documents, import_documents_from_url, documents_by_query_post, get_document_revisions, restore_document_revision, validate_documents),
     tags(
         (
             name = "Documents",
@@ -93,6 +112,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(SeqHandler(get_documents)))
             .route(web::post().to(SeqHandler(replace_documents)))
             .route(web::put().to(SeqHandler(update_documents)))
+            .route(web::patch().to(SeqHandler(patch_documents)))
             .route(web::delete().to(SeqHandler(clear_all_documents))),
     )
     // these routes need to be before the /documents/{document_id} to match properly
@@ -101,11 +121,24 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
     .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_function))))
+    .service(web::resource("/json-patch").route(web::post().to(SeqHandler(json_patch_documents))))
+    .service(
+        web::resource("/import-url").route(web::post().to(SeqHandler(import_documents_from_url))),
+    )
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
+    .service(web::resource("/validate").route(web::post().to(SeqHandler(validate_documents))))
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
             .route(web::delete().to(SeqHandler(delete_document))),
+    )
+    .service(
+        web::resource("/{document_id}/revisions")
+            .route(web::get().to(SeqHandler(get_document_revisions))),
+    )
+    .service(
+        web::resource("/{document_id}/revisions/{revision}/restore")
+            .route(web::post().to(SeqHandler(restore_document_revision))),
     );
 }
 
@@ -135,6 +168,8 @@ pub struct DocumentsFetchAggregator<Method: AggregateMethod> {
     per_document_id: bool,
     // if a filter was used
     per_filter: bool,
+    // if a sort was used
+    per_sort: bool,
 
     #[serde(rename = "vector.retrieve_vectors")]
     retrieve_vectors: bool,
@@ -174,6 +209,7 @@ impl<Method: AggregateMethod> DocumentsFetchAggregator<Method> {
         Self {
             per_document_id: matches!(query, DocumentFetchKind::PerDocumentId { .. }),
             per_filter: matches!(query, DocumentFetchKind::Normal { with_filter, .. } if *with_filter),
+            per_sort: false,
             max_limit: limit,
             max_offset: offset,
             retrieve_vectors,
@@ -193,6 +229,7 @@ impl<Method: AggregateMethod> Aggregate for DocumentsFetchAggregator<Method> {
         Box::new(Self {
             per_document_id: self.per_document_id | new.per_document_id,
             per_filter: self.per_filter | new.per_filter,
+            per_sort: self.per_sort | new.per_sort,
             retrieve_vectors: self.retrieve_vectors | new.retrieve_vectors,
             max_limit: self.max_limit.max(new.max_limit),
             max_offset: self.max_offset.max(new.max_offset),
@@ -276,6 +313,7 @@ pub async fn get_document(
             retrieve_vectors: param_retrieve_vectors.0,
             per_document_id: true,
             per_filter: false,
+            per_sort: false,
             max_limit: 0,
             max_offset: 0,
             max_document_ids: 0,
@@ -285,8 +323,14 @@ pub async fn get_document(
     );
 
     let index = index_scheduler.index(&index_uid)?;
-    let document =
-        retrieve_document(&index, &document_id, attributes_to_retrieve, retrieve_vectors)?;
+    let document = retrieve_document(
+        &index,
+        &document_id,
+        attributes_to_retrieve,
+        retrieve_vectors,
+        index_scheduler.filters().key_filter(),
+        index_scheduler.features(),
+    )?;
     debug!(returns = ?document, "Get document");
     Ok(HttpResponse::Ok().json(document))
 }
@@ -376,14 +420,209 @@ pub async fn delete_document(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentRevisionsView {
+    /// The previous versions of the document, from the most recent to the oldest one.
+    results: Vec<DocumentRevision>,
+}
+
+/// Get the revisions of a document
+///
+/// Get the previous versions of a document, kept when the `maxDocumentRevisions` index setting is set.
+/// A revision is recorded each time the document is replaced, updated or deleted, so the revisions of a deleted document can still be listed.
+#[utoipa::path(
+    get,
+    path = "{indexUid}/documents/{documentId}/revisions",
+    tag = "Documents",
+    security(("Bearer" = ["documents.get", "documents.*", "*"])),
+    params(
+        ("indexUid" = String, Path, example = "movies", description = "Index Unique Identifier", nullable = false),
+        ("documentId" = String, Path, example = "85087", description = "The document identifier", nullable = false),
+    ),
+    responses(
+        (status = 200, description = "The revisions of the document are returned", body = DocumentRevisionsView, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "revision": 2,
+                        "recordedAt": "2024-08-08T17:05:55.791772Z",
+                        "document": { "id": 85087, "title": "American Ninja 5" }
+                    },
+                    {
+                        "revision": 1,
+                        "recordedAt": "2024-08-07T10:12:03.417162Z",
+                        "document": { "id": 85087, "title": "American Ninja" }
+                    }
+                ]
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_document_revisions(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    document_param: web::Path<DocumentParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentParam { index_uid, document_id } = document_param.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    // the revisions of the documents hidden by the filter of the key are hidden too
+    if let Some(key_filter) = index_scheduler.filters().key_filter() {
+        let features = index_scheduler.features();
+        let matches = match index.external_documents_ids().get(&rtxn, &document_id)? {
+            Some(docid) => document_matches_filter(&index, &rtxn, docid, key_filter, features)?,
+            None => false,
+        };
+        if !matches {
+            return Err(MeilisearchHttpError::DocumentNotFound(document_id).into());
+        }
+    }
+    let mut results = index.document_revisions(&rtxn, &document_id)?;
+    results.reverse();
+
+    let revisions = DocumentRevisionsView { results };
+    debug!(returns = ?revisions.results.len(), "Get document revisions");
+    Ok(HttpResponse::Ok().json(revisions))
+}
+
+/// Restore a revision of a document
+///
+/// Enqueue a task replacing the document by one of its previous versions.
+/// The replaced version is itself recorded as a new revision, so a restoration can be undone.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/{documentId}/revisions/{revision}/restore",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid" = String, Path, example = "movies", description = "Index Unique Identifier", nullable = false),
+        ("documentId" = String, Path, example = "85087", description = "The document identifier", nullable = false),
+        ("revision" = u64, Path, example = 2, description = "The revision to restore", nullable = false),
+    ),
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "documentAdditionOrUpdate",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 404, description = "Revision not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Revision `2` of the document `85087` not found.",
+                "code": "document_revision_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#document_revision_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn restore_document_revision(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<DocumentRevisionParam>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentRevisionParam { index_uid, document_id, revision } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    analytics.publish(
+        DocumentsAggregator::<RevisionRestored> {
+            payload_types: HashSet::new(),
+            primary_key: HashSet::new(),
+            index_creation: false,
+            method: PhantomData,
+        },
+        &req,
+    );
+
+    let document = {
+        let index = index_scheduler.index(&index_uid)?;
+        let rtxn = index.read_txn()?;
+        index
+            .document_revisions(&rtxn, &document_id)?
+            .into_iter()
+            .find(|r| r.revision == revision)
+            .map(|r| r.document)
+            .ok_or(MeilisearchHttpError::DocumentRevisionNotFound { document_id, revision })?
+    };
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    serde_json::to_writer(&mut update_file, &document)
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))?;
+    update_file.persist().map_err(MeilisearchHttpError::from)?;
+
+    let task = KindWithContent::DocumentAdditionOrUpdate {
+        method: IndexDocumentsMethod::ReplaceDocuments,
+        content_file: uuid,
+        documents_count: 1,
+        primary_key: None,
+        allow_index_creation: false,
+        index_uid: index_uid.to_string(),
+    };
+
+    let scheduler = index_scheduler.clone();
+    let task = match tokio::task::spawn_blocking(move || {
+        scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await?
+    {
+        Ok(task) => task,
+        Err(e) => {
+            index_scheduler.queue.delete_update_file(uuid)?;
+            return Err(e.into());
+        }
+    };
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Restore document revision");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 #[derive(Debug, Deserr, IntoParams)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 #[into_params(rename_all = "camelCase", parameter_in = Query)]
@@ -406,6 +645,9 @@ pub struct BrowseQueryGet {
     #[param(default, value_type = Option<String>, example = "popularity > 1000")]
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
     filter: Option<String>,
+    #[param(default, value_type = Option<Vec<String>>, example = "price:asc,title:desc")]
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSort>)]
+    sort: Option<String>,
 }
 
 #[derive(Debug, Deserr, ToSchema)]
@@ -430,11 +672,17 @@ pub struct BrowseQuery {
     #[schema(default, value_type = Option<Value>, example = "popularity > 1000")]
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[schema(default, value_type = Option<Vec<String>>, example = json!(["price:asc", "title:desc"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSort>)]
+    sort: Option<Vec<String>>,
 }
 
 /// Get documents with POST
 ///
 /// Get a set of documents.
+///
+/// When `ids` are given, only these documents are returned, in the order of the `ids`.
+/// Use `sort` to order the documents by their sortable attributes instead.
 #[utoipa::path(
     post,
     path = "{indexUid}/documents/fetch",
@@ -495,6 +743,7 @@ pub async fn documents_by_query_post(
     analytics.publish(
         DocumentsFetchAggregator::<DocumentsPOST> {
             per_filter: body.filter.is_some(),
+            per_sort: body.sort.is_some(),
             retrieve_vectors: body.retrieve_vectors,
             max_limit: body.limit,
             max_offset: body.offset,
@@ -511,6 +760,8 @@ pub async fn documents_by_query_post(
 /// Get documents
 ///
 /// Get documents by batches.
+///
+/// Use `sort` to order the documents by their sortable attributes.
 #[utoipa::path(
     get,
     path = "{indexUid}/documents",
@@ -571,7 +822,7 @@ pub async fn get_documents(
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?params, "Get documents GET");
 
-    let BrowseQueryGet { limit, offset, fields, retrieve_vectors, filter, ids } =
+    let BrowseQueryGet { limit, offset, fields, retrieve_vectors, filter, ids, sort } =
         params.into_inner();
 
     let filter = match filter {
@@ -591,11 +842,13 @@ pub async fn get_documents(
         retrieve_vectors: retrieve_vectors.0,
         filter,
         ids,
+        sort: sort.map(|sort| fix_sort_query_parameters(&sort)),
     };
 
     analytics.publish(
         DocumentsFetchAggregator::<DocumentsGET> {
             per_filter: query.filter.is_some(),
+            per_sort: query.sort.is_some(),
             retrieve_vectors: query.retrieve_vectors,
             max_limit: query.limit,
             max_offset: query.offset,
@@ -610,12 +863,16 @@ pub async fn get_documents(
 }
 
 fn documents_by_query(
-    index_scheduler: &IndexScheduler,
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter, ids } = query;
+    let BrowseQuery { offset, limit, fields, retrieve_vectors, mut filter, ids, sort } = query;
+
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules);
+    }
 
     let retrieve_vectors = RetrieveVectors::new(retrieve_vectors);
 
@@ -633,6 +890,27 @@ fn documents_by_query(
         None
     };
 
+    let sort = match sort {
+        Some(sort) => {
+            let mut parsed_sort = Vec::with_capacity(sort.len());
+            for asc_desc in sort {
+                let asc_desc = AscDesc::from_str(&asc_desc).map_err(|error| {
+                    let error = milli::SortError::from(error);
+                    ResponseError::from_msg(error.to_string(), Code::InvalidDocumentSort)
+                })?;
+                if let AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) = asc_desc {
+                    return Err(ResponseError::from_msg(
+                        "The `_geoPoint` sort rule cannot be used to sort documents.".to_string(),
+                        Code::InvalidDocumentSort,
+                    ));
+                }
+                parsed_sort.push(asc_desc);
+            }
+            Some(parsed_sort)
+        }
+        None => None,
+    };
+
     let index = index_scheduler.index(&index_uid)?;
     let (total, documents) = retrieve_documents(
         &index,
@@ -640,6 +918,7 @@ fn documents_by_query(
         limit,
         ids,
         filter,
+        sort,
         fields,
         retrieve_vectors,
         index_scheduler.features(),
@@ -664,6 +943,37 @@ pub struct UpdateDocumentsQuery {
     #[param(value_type = char, default = ",", example = ";")]
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    /// What to do with the documents whose primary key is already used in the index.
+    /// Defaults to `replace` when adding documents and to `merge` when updating them.
+    #[param(value_type = Option<OnConflict>, example = "skip")]
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentOnConflict>)]
+    pub on_conflict: Option<OnConflict>,
+}
+
+/// The policy applied to the documents that already exist in the index.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deserr, ToSchema)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum OnConflict {
+    /// The existing document is replaced by the new one.
+    Replace,
+    /// The new document is merged into the existing one.
+    Merge,
+    /// The new document is ignored.
+    Skip,
+    /// The whole payload is rejected.
+    Fail,
+}
+
+impl OnConflict {
+    fn index_documents_method(self) -> IndexDocumentsMethod {
+        match self {
+            OnConflict::Replace => IndexDocumentsMethod::ReplaceDocuments,
+            OnConflict::Merge => IndexDocumentsMethod::UpdateDocuments,
+            OnConflict::Skip => IndexDocumentsMethod::SkipExistingDocuments,
+            OnConflict::Fail => IndexDocumentsMethod::FailOnExistingDocuments,
+        }
+    }
 }
 
 fn from_char_csv_delimiter(
@@ -682,6 +992,11 @@ fn from_char_csv_delimiter(
 aggregate_methods!(
     Replaced => "Documents Added",
     Updated => "Documents Updated",
+    Patched => "Documents Patched",
+    JsonPatched => "Documents JSON Patched",
+    ImportedFromUrl => "Documents Imported From Url",
+    RevisionRestored => "Document Revision Restored",
+    Validated => "Documents Validated",
 );
 
 #[derive(Serialize)]
@@ -719,6 +1034,8 @@ impl<Method: AggregateMethod> Aggregate for DocumentsAggregator<Method> {
 /// If you send an already existing document (same id) the whole existing document will be overwritten by the new document. Fields previously in the document not present in the new document are removed.
 ///
 /// For a partial update of the document see Add or update documents route.
+///
+/// Use the `onConflict` query parameter to `skip` the documents that already exist, or to `fail` the task when one of them exists.
 /// > info
 /// > If the provided index does not exist, it will be created.
 /// > info
@@ -797,6 +1114,8 @@ pub async fn replace_documents(
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
@@ -804,8 +1123,12 @@ pub async fn replace_documents(
         params.primary_key,
         params.csv_delimiter,
         body,
-        IndexDocumentsMethod::ReplaceDocuments,
+        params
+            .on_conflict
+            .map_or(IndexDocumentsMethod::ReplaceDocuments, OnConflict::index_documents_method),
         uid,
+        depends_on,
+        labels,
         dry_run,
         allow_index_creation,
     )
@@ -820,6 +1143,8 @@ pub async fn replace_documents(
 /// Add a list of documents or update them if they already exist.
 /// If you send an already existing document (same id) the old document will be only partially updated according to the fields of the new document. Thus, any fields not present in the new document are kept and remained unchanged.
 /// To completely overwrite a document, see Add or replace documents route.
+///
+/// Use the `onConflict` query parameter to `skip` the documents that already exist, or to `fail` the task when one of them exists.
 /// > info
 /// > If the provided index does not exist, it will be created.
 /// > info
@@ -898,6 +1223,8 @@ pub async fn update_documents(
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
@@ -905,8 +1232,12 @@ pub async fn update_documents(
         params.primary_key,
         params.csv_delimiter,
         body,
-        IndexDocumentsMethod::UpdateDocuments,
+        params
+            .on_conflict
+            .map_or(IndexDocumentsMethod::UpdateDocuments, OnConflict::index_documents_method),
         uid,
+        depends_on,
+        labels,
         dry_run,
         allow_index_creation,
     )
@@ -916,92 +1247,365 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn document_addition(
-    mime_type: Option<Mime>,
+/// Add or patch documents
+///
+/// Add a list of documents or patch them if they already exist, following the JSON Merge Patch semantics ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)).
+/// If you send an already existing document (same id), the fields of the new document are deeply merged into the old document:
+/// the nested objects are merged instead of being replaced, and the fields set to `null` are removed from the document.
+/// To only update the top-level fields, see Add or update documents route.
+/// > info
+/// > If the provided index does not exist, it will be created.
+#[utoipa::path(
+    patch,
+    path = "{indexUid}/documents",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+        UpdateDocumentsQuery,
+    ),
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": null,
+                "status": "enqueued",
+                "type": "documentAdditionOrUpdate",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn patch_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
-    index_uid: IndexUid,
-    primary_key: Option<String>,
-    csv_delimiter: Option<u8>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
     body: Payload,
-    method: IndexDocumentsMethod,
-    task_id: Option<TaskId>,
-    dry_run: bool,
-    allow_index_creation: bool,
-) -> Result<SummarizedTaskView, MeilisearchHttpError> {
-    let format = match (
-        mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
-        csv_delimiter,
-    ) {
-        (Some(("application", "json")), None) => PayloadType::Json,
-        (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
-        (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
-        (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
-
-        (Some(("application", "json")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/json",
-            )))
-        }
-        (Some(("application", "x-ndjson")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/x-ndjson",
-            )))
-        }
-        (Some((type_, subtype)), _) => {
-            return Err(MeilisearchHttpError::InvalidContentType(
-                format!("{}/{}", type_, subtype),
-                ACCEPTED_CONTENT_TYPE.clone(),
-            ))
-        }
-        (None, _) => {
-            return Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone()))
-        }
-    };
-
-    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
-    let documents_count = match format {
-        PayloadType::Ndjson => {
-            let (path, file) = update_file.into_parts();
-            let file = match file {
-                Some(file) => {
-                    let (file, path) = file.into_parts();
-                    let mut file = copy_body_to_file(file, body, format).await?;
-                    file.rewind().map_err(|e| {
-                        index_scheduler::Error::FileStore(file_store::Error::IoError(e))
-                    })?;
-                    Some(tempfile::NamedTempFile::from_parts(file, path))
-                }
-                None => None,
-            };
-
-            let documents_count = tokio::task::spawn_blocking(move || {
-                let documents_count = file.as_ref().map_or(Ok(0), |ntf| {
-                    read_ndjson(ntf.as_file()).map_err(MeilisearchHttpError::DocumentFormat)
-                })?;
-
-                let update_file = file_store::File::from_parts(path, file);
-                update_file.persist()?;
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-                Ok(documents_count)
-            })
-            .await?;
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Patch documents");
 
-            Ok(documents_count)
-        }
-        PayloadType::Json | PayloadType::Csv { delimiter: _ } => {
-            let temp_file = match tempfile() {
-                Ok(file) => file,
-                Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
-            };
+    if params.on_conflict.is_some() {
+        return Err(ResponseError::from_msg(
+            "`onConflict` cannot be used when patching documents.".to_string(),
+            Code::InvalidDocumentOnConflict,
+        ));
+    }
 
-            let read_file = copy_body_to_file(temp_file, body, format).await?;
-            tokio::task::spawn_blocking(move || {
-                let documents_count = match format {
+    let mut content_types = HashSet::new();
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|s| s.to_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+    content_types.insert(content_type);
+    let mut primary_keys = HashSet::new();
+    if let Some(primary_key) = params.primary_key.clone() {
+        primary_keys.insert(primary_key);
+    }
+    analytics.publish(
+        DocumentsAggregator::<Patched> {
+            payload_types: content_types,
+            primary_key: primary_keys,
+            index_creation: index_scheduler.index_exists(&index_uid).map_or(true, |x| !x),
+            method: PhantomData,
+        },
+        &req,
+    );
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task = document_addition(
+        extract_mime_type(&req)?,
+        index_scheduler,
+        index_uid,
+        params.primary_key,
+        params.csv_delimiter,
+        body,
+        IndexDocumentsMethod::MergePatchDocuments,
+        uid,
+        depends_on,
+        labels,
+        dry_run,
+        allow_index_creation,
+    )
+    .await?;
+    debug!(returns = ?task, "Patch documents");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Deserialize, Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct ValidateDocumentsQuery {
+    /// The primary key of the documents, checked against the one of the index.
+    #[param(example = "id")]
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
+    pub primary_key: Option<String>,
+    /// Customize the csv delimiter when validating CSV documents.
+    #[param(value_type = char, default = ",", example = ";")]
+    #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
+    pub csv_delimiter: Option<u8>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentsValidationView {
+    /// The primary key of the index, or the one that would be inferred from the documents
+    #[schema(example = "id")]
+    primary_key: String,
+    /// The number of documents in the payload
+    documents_count: usize,
+    /// Whether the documents can be indexed without error
+    valid: bool,
+    /// The diagnostics of the documents having at least one error or warning
+    diagnostics: Vec<DocumentDiagnosticsView>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiagnosticsView {
+    /// The position of the document in the payload, starting at zero
+    position: usize,
+    /// The identifier of the document, when it could be extracted
+    document_id: Option<String>,
+    /// The errors that would make the indexing of the documents fail
+    errors: Vec<ResponseError>,
+    /// The values that would be indexed but are unlikely to behave as expected
+    warnings: Vec<String>,
+}
+
+impl From<DocumentsValidation> for DocumentsValidationView {
+    fn from(validation: DocumentsValidation) -> Self {
+        let DocumentsValidation { primary_key, documents_count, diagnostics } = validation;
+        let diagnostics: Vec<_> = diagnostics
+            .into_iter()
+            .map(|DocumentDiagnostics { position, document_id, errors, warnings }| {
+                DocumentDiagnosticsView {
+                    position,
+                    document_id,
+                    errors: errors.into_iter().map(ResponseError::from).collect(),
+                    warnings,
+                }
+            })
+            .collect();
+        let valid = diagnostics.iter().all(|diagnostics| diagnostics.errors.is_empty());
+        DocumentsValidationView { primary_key, documents_count, valid, diagnostics }
+    }
+}
+
+/// Validate documents
+///
+/// Check a list of documents against the settings of the index without adding them.
+/// The primary key is retrieved or inferred, and each document is checked for an invalid identifier,
+/// invalid `_geo` or `_geojson` fields, a violation of the document schema, and a document template of the embedders it can't fill.
+/// A filterable or sortable value whose type differs from the values already in the index is reported as a warning.
+/// No task is enqueued.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/validate",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+        ValidateDocumentsQuery,
+    ),
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "The documents have been validated", body = DocumentsValidationView, content_type = "application/json", example = json!(
+            {
+                "primaryKey": "id",
+                "documentsCount": 2,
+                "valid": false,
+                "diagnostics": [
+                    {
+                        "position": 1,
+                        "documentId": null,
+                        "errors": [
+                            {
+                                "message": "Document doesn't have a `id` attribute: `{\"title\":\"Carol\"}`.",
+                                "code": "missing_document_id",
+                                "type": "invalid_request",
+                                "link": "https://docs.meilisearch.com/errors#missing_document_id"
+                            }
+                        ],
+                        "warnings": []
+                    }
+                ]
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn validate_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<ValidateDocumentsQuery, DeserrQueryParamError>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    debug!(parameters = ?params, "Validate documents");
+    let ValidateDocumentsQuery { primary_key, csv_delimiter } = params.into_inner();
+
+    let mut content_types = HashSet::new();
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|s| s.to_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+    content_types.insert(content_type);
+    analytics.publish(
+        DocumentsAggregator::<Validated> {
+            payload_types: content_types,
+            primary_key: primary_key.iter().cloned().collect(),
+            index_creation: false,
+            method: PhantomData,
+        },
+        &req,
+    );
+
+    let format = payload_type(extract_mime_type(&req)?.as_ref(), csv_delimiter)?;
+    let index = index_scheduler.index(&index_uid)?;
+
+    let temp_file =
+        tempfile().map_err(|e| MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))))?;
+    let mut read_file = copy_body_to_file(temp_file, body, format).await?;
+
+    let validation = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+        let mut payload = Vec::new();
+        match format {
+            PayloadType::Json => {
+                read_json(&read_file, &mut payload)?;
+            }
+            PayloadType::Csv { delimiter } => {
+                read_csv(&read_file, &mut payload, delimiter)?;
+            }
+            PayloadType::Avro => {
+                read_avro(&read_file, &mut payload)?;
+            }
+            PayloadType::Ndjson => {
+                read_ndjson(&read_file)?;
+                read_file
+                    .read_to_end(&mut payload)
+                    .map_err(|e| MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))))?;
+            }
+        }
+
+        let rtxn = index.read_txn()?;
+        milli::update::new::indexer::validate_documents(
+            &rtxn,
+            &index,
+            primary_key.as_deref(),
+            &payload,
+        )
+        .map_err(|e| MeilisearchHttpError::from_milli(e, Some(index_uid.to_string())))
+    })
+    .await??;
+
+    let validation = DocumentsValidationView::from(validation);
+    debug!(returns = ?validation, "Validate documents");
+    Ok(HttpResponse::Ok().json(validation))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn document_addition(
+    mime_type: Option<Mime>,
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    primary_key: Option<String>,
+    csv_delimiter: Option<u8>,
+    body: Payload,
+    method: IndexDocumentsMethod,
+    task_id: Option<TaskId>,
+    depends_on: Option<TaskId>,
+    labels: BTreeMap<String, String>,
+    dry_run: bool,
+    allow_index_creation: bool,
+) -> Result<SummarizedTaskView, MeilisearchHttpError> {
+    let format = payload_type(mime_type.as_ref(), csv_delimiter)?;
+
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    let documents_count = match format {
+        PayloadType::Ndjson => {
+            let (path, file) = update_file.into_parts();
+            let file = match file {
+                Some(file) => {
+                    let (file, path) = file.into_parts();
+                    let mut file = copy_body_to_file(file, body, format).await?;
+                    file.rewind().map_err(|e| {
+                        index_scheduler::Error::FileStore(file_store::Error::IoError(e))
+                    })?;
+                    Some(tempfile::NamedTempFile::from_parts(file, path))
+                }
+                None => None,
+            };
+
+            let documents_count = tokio::task::spawn_blocking(move || {
+                let documents_count = file.as_ref().map_or(Ok(0), |ntf| {
+                    read_ndjson(ntf.as_file()).map_err(MeilisearchHttpError::DocumentFormat)
+                })?;
+
+                let update_file = file_store::File::from_parts(path, file);
+                update_file.persist()?;
+
+                Ok(documents_count)
+            })
+            .await?;
+
+            Ok(documents_count)
+        }
+        PayloadType::Json | PayloadType::Csv { delimiter: _ } | PayloadType::Avro => {
+            let temp_file = match tempfile() {
+                Ok(file) => file,
+                Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+            };
+
+            let read_file = copy_body_to_file(temp_file, body, format).await?;
+            tokio::task::spawn_blocking(move || {
+                let documents_count = match format {
                     PayloadType::Json => read_json(&read_file, &mut update_file)?,
                     PayloadType::Csv { delimiter } => {
                         read_csv(&read_file, &mut update_file, delimiter)?
                     }
+                    PayloadType::Avro => read_avro(&read_file, &mut update_file)?,
                     PayloadType::Ndjson => {
                         unreachable!("We already wrote the user content into the update file")
                     }
@@ -1046,9 +1650,13 @@ async fn document_addition(
         index_uid: index_uid.to_string(),
     };
 
+    let quotas = index_scheduler.filters().key_quotas().map(|(key, quotas)| (key, quotas.clone()));
     let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task, task_id, dry_run))
-        .await?
+    let task = match tokio::task::spawn_blocking(move || {
+        let quotas = quotas.as_ref().map(|(key, quotas)| (*key, quotas));
+        scheduler.register_for_key(task, task_id, depends_on, labels, quotas, dry_run)
+    })
+    .await?
     {
         Ok(task) => task,
         Err(e) => {
@@ -1060,6 +1668,41 @@ async fn document_addition(
     Ok(task.into())
 }
 
+/// Returns the format of a documents payload from its content type.
+fn payload_type(
+    mime_type: Option<&Mime>,
+    csv_delimiter: Option<u8>,
+) -> Result<PayloadType, MeilisearchHttpError> {
+    match (mime_type.map(|m| (m.type_().as_str(), m.subtype().as_str())), csv_delimiter) {
+        (Some(("application", "json")), None) => Ok(PayloadType::Json),
+        (Some(("application", "x-ndjson")), None) => Ok(PayloadType::Ndjson),
+        (Some(("text", "csv")), None) => Ok(PayloadType::Csv { delimiter: b',' }),
+        (Some(("text", "csv")), Some(delimiter)) => Ok(PayloadType::Csv { delimiter }),
+        (Some(("application", "avro")), None) => Ok(PayloadType::Avro),
+
+        (Some(("application", "json")), Some(_)) => {
+            Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/json",
+            )))
+        }
+        (Some(("application", "x-ndjson")), Some(_)) => {
+            Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/x-ndjson",
+            )))
+        }
+        (Some(("application", "avro")), Some(_)) => {
+            Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/avro",
+            )))
+        }
+        (Some((type_, subtype)), _) => Err(MeilisearchHttpError::InvalidContentType(
+            format!("{}/{}", type_, subtype),
+            ACCEPTED_CONTENT_TYPE.clone(),
+        )),
+        (None, _) => Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone())),
+    }
+}
+
 async fn copy_body_to_file(
     output: std::fs::File,
     mut body: Payload,
@@ -1155,10 +1798,13 @@ pub async fn delete_documents_batch(
         KindWithContent::DocumentDeletion { index_uid: index_uid.to_string(), documents_ids: ids };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete documents by batch");
     Ok(HttpResponse::Accepted().json(task))
@@ -1233,10 +1879,13 @@ pub async fn delete_documents_by_filter(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete documents by filter");
     Ok(HttpResponse::Accepted().json(task))
@@ -1376,15 +2025,293 @@ pub async fn edit_documents_by_function(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Edit documents by function");
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentJsonPatch {
+    /// The identifier of the document to patch.
+    #[deserr(error = DeserrJsonError<InvalidDocumentId>)]
+    #[schema(value_type = String)]
+    pub id: Value,
+    /// The JSON Patch operations to apply on the document, in order.
+    #[deserr(error = DeserrJsonError<InvalidDocumentJsonPatch>)]
+    #[schema(value_type = Vec<Object>)]
+    pub operations: Vec<Value>,
+}
+
+/// A line of the update file of a JSON patch task.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPatchLine {
+    document_id: String,
+    operations: Vec<Value>,
+}
+
+/// Patch documents with JSON Patch
+///
+/// Apply [JSON Patch](https://www.rfc-editor.org/rfc/rfc6902) operations (`add`, `remove`, `replace`, `move`, `copy` and `test`) on existing documents.
+/// The operations are applied in order and can target deeply nested fields and array elements, so only the changes need to be sent.
+/// If one of the operations cannot be applied, or if a document does not exist, the task fails and none of its documents are modified.
+/// The primary key of a document cannot be modified.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/json-patch",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+    ),
+    request_body = Vec<DocumentJsonPatch>,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "documentAdditionOrUpdate",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn json_patch_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Vec<DocumentJsonPatch>, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let patches = body.into_inner();
+    debug!(parameters = ?patches, "Patch documents with JSON patches");
+
+    analytics.publish(
+        DocumentsAggregator::<JsonPatched> {
+            payload_types: HashSet::from(["application/json".to_string()]),
+            primary_key: HashSet::new(),
+            index_creation: false,
+            method: PhantomData,
+        },
+        &req,
+    );
+
+    if patches.is_empty() {
+        return Err(MeilisearchHttpError::MissingPayload(PayloadType::Json).into());
+    }
+
+    let documents_count = patches.len() as u64;
+    let mut content = Vec::new();
+    for DocumentJsonPatch { id, operations } in patches {
+        let document_id = milli::documents::validate_document_id_value(id)
+            .map_err(|e| ResponseError::from_msg(e.to_string(), Code::InvalidDocumentId))?;
+        serde_json::to_writer(&mut content, &JsonPatchLine { document_id, operations })
+            .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))?;
+        content.push(b'\n');
+    }
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    update_file
+        .write_all(&content)
+        .map_err(|e| index_scheduler::Error::FileStore(file_store::Error::IoError(e)))?;
+    update_file.persist().map_err(MeilisearchHttpError::from)?;
+
+    let task = KindWithContent::DocumentAdditionOrUpdate {
+        method: IndexDocumentsMethod::JsonPatchDocuments,
+        content_file: uuid,
+        documents_count,
+        primary_key: None,
+        allow_index_creation: false,
+        index_uid: index_uid.to_string(),
+    };
+
+    let scheduler = index_scheduler.clone();
+    let task = match tokio::task::spawn_blocking(move || {
+        scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await?
+    {
+        Ok(task) => task,
+        Err(e) => {
+            index_scheduler.queue.delete_update_file(uuid)?;
+            return Err(e.into());
+        }
+    };
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Patch documents with JSON patches");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentImportUrl {
+    /// The HTTP(S) URL serving the documents, e.g. an S3 presigned URL.
+    /// The format of the documents is deduced from the `Content-Type` of the response or from the extension of the file.
+    #[deserr(error = DeserrJsonError<InvalidDocumentImportUrl>)]
+    #[schema(example = "https://example.com/movies.ndjson")]
+    pub url: String,
+    /// The primary key of the documents.
+    #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
+    #[schema(value_type = Option<String>, example = "id")]
+    pub primary_key: Option<String>,
+    /// Customize the csv delimiter when importing CSV documents.
+    #[deserr(default, try_from(char) = from_char_csv_delimiter_json -> DeserrJsonError<InvalidDocumentCsvDelimiter>, error = DeserrJsonError<InvalidDocumentCsvDelimiter>)]
+    #[schema(value_type = Option<char>, example = ";")]
+    pub csv_delimiter: Option<u8>,
+}
+
+pub(crate) fn from_char_csv_delimiter_json(
+    c: char,
+) -> Result<Option<u8>, DeserrJsonError<InvalidDocumentCsvDelimiter>> {
+    if c.is_ascii() {
+        Ok(Some(c as u8))
+    } else {
+        Err(DeserrJsonError::new(
+            format!("csv delimiter must be an ascii character. Found: `{}`", c),
+            Code::InvalidDocumentCsvDelimiter,
+        ))
+    }
+}
+
+/// Ensure the documents can be fetched from the URL.
+pub(crate) fn validate_import_url(url: &str) -> Result<(), ResponseError> {
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        Ok(parsed) => Err(ResponseError::from_msg(
+            format!(
+                "Invalid url `{url}`: the `{}` scheme is not supported, the url must use `http` or `https`.",
+                parsed.scheme()
+            ),
+            Code::InvalidDocumentImportUrl,
+        )),
+        Err(e) => Err(ResponseError::from_msg(
+            format!("Invalid url `{url}`: {e}."),
+            Code::InvalidDocumentImportUrl,
+        )),
+    }
+}
+
+/// Import documents from a URL
+///
+/// Download the documents served at a remote URL and enqueue a task adding or replacing them in the index.
+/// The documents are downloaded by Meilisearch itself, so large files never go through the client.
+/// The JSON, NDJSON, CSV and Avro formats are supported.
+///
+/// The route requires launching Meilisearch with the `--experimental-import-url-allowed-hosts` option, and the
+/// documents can only be downloaded from the allowed hosts.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/import-url",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+    ),
+    request_body = DocumentImportUrl,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "documentAdditionOrUpdate",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn import_documents_from_url(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<DocumentImportUrl, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let DocumentImportUrl { url, primary_key, csv_delimiter } = body.into_inner();
+    debug!(parameters = ?url, "Import documents from url");
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+
+    analytics.publish(
+        DocumentsAggregator::<ImportedFromUrl> {
+            payload_types: HashSet::new(),
+            primary_key: primary_key.iter().cloned().collect(),
+            index_creation: index_scheduler.index_exists(&index_uid).map_or(true, |x| !x),
+            method: PhantomData,
+        },
+        &req,
+    );
+
+    validate_import_url(&url)?;
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        // The documents are downloaded before the task is registered, so that a slow host
+        // never holds the processing of the other tasks.
+        let (content_file, documents_count) =
+            index_scheduler.fetch_remote_documents(&url, csv_delimiter)?;
+        let task = KindWithContent::DocumentImportUrl {
+            index_uid: index_uid.to_string(),
+            primary_key,
+            method: IndexDocumentsMethod::ReplaceDocuments,
+            url,
+            csv_delimiter,
+            content_file,
+            documents_count,
+            allow_index_creation,
+            replace_all_documents: false,
+        };
+        let quotas = index_scheduler.filters().key_quotas();
+        let task = index_scheduler.register_for_key(task, uid, depends_on, labels, quotas, dry_run);
+        if task.is_err() || dry_run {
+            index_scheduler.queue.delete_update_file(content_file)?;
+        }
+        task
+    })
+    .await??
+    .into();
+
+    debug!(returns = ?task, "Import documents from url");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Delete all documents
 ///
 /// Delete all documents in the specified index.
@@ -1435,10 +2362,13 @@ pub async fn clear_all_documents(
     let task = KindWithContent::DocumentClear { index_uid: index_uid.to_string() };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all documents");
     Ok(HttpResponse::Accepted().json(task))
@@ -1498,6 +2428,7 @@ fn retrieve_documents<S: AsRef<str>>(
     limit: usize,
     ids: Option<Vec<ExternalDocumentId>>,
     filter: Option<Value>,
+    sort: Option<Vec<AscDesc>>,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
@@ -1510,40 +2441,53 @@ fn retrieve_documents<S: AsRef<str>>(
         None
     };
 
-    let mut candidates = if let Some(ids) = ids {
+    // When ids are requested, the documents are returned in the order of the ids.
+    let (requested_docids, mut candidates) = if let Some(ids) = ids {
         let external_document_ids = index.external_documents_ids();
+        let mut requested_docids = Vec::with_capacity(ids.len());
         let mut candidates = RoaringBitmap::new();
         for id in ids.iter() {
             let Some(docid) = external_document_ids.get(&rtxn, id)? else {
                 continue;
             };
-            candidates.insert(docid);
+            if candidates.insert(docid) {
+                requested_docids.push(docid);
+            }
         }
-        candidates
+        (Some(requested_docids), candidates)
     } else {
-        index.documents_ids(&rtxn)?
+        (None, index.documents_ids(&rtxn)?)
     };
 
     if let Some(filter) = filter {
-        candidates &= filter.evaluate(&rtxn, index).map_err(|err| match err {
-            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
-                ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter)
-            }
-            e => e.into(),
-        })?
+        candidates &= evaluate_filter(&filter, &rtxn, index)?;
     }
 
     let (it, number_of_documents) = {
         let number_of_documents = candidates.len();
-        (
-            some_documents(
-                index,
-                &rtxn,
-                candidates.into_iter().skip(offset).take(limit),
-                retrieve_vectors,
-            )?,
-            number_of_documents,
-        )
+        let docids: Vec<_> = match (sort, requested_docids) {
+            (Some(sort), _) => {
+                let limit = offset.saturating_add(limit);
+                milli::documents::recursive_sort(index, &rtxn, &sort, &candidates, limit)
+                    .map_err(|err| match err {
+                        milli::Error::UserError(milli::UserError::InvalidSortableAttribute {
+                            ..
+                        }) => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort),
+                        e => e.into(),
+                    })?
+                    .into_iter()
+                    .skip(offset)
+                    .collect()
+            }
+            (None, Some(docids)) => docids
+                .into_iter()
+                .filter(|docid| candidates.contains(*docid))
+                .skip(offset)
+                .take(limit)
+                .collect(),
+            (None, None) => candidates.into_iter().skip(offset).take(limit).collect(),
+        };
+        (some_documents(index, &rtxn, docids, retrieve_vectors)?, number_of_documents)
     };
 
     let documents: Vec<_> = it
@@ -1568,6 +2512,8 @@ fn retrieve_document<S: AsRef<str>>(
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
+    key_filter: Option<&Value>,
+    features: RoFeatures,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -1576,6 +2522,12 @@ fn retrieve_document<S: AsRef<str>>(
         .get(&txn, doc_id)?
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
+    if let Some(key_filter) = key_filter {
+        if !document_matches_filter(index, &txn, internal_id, key_filter, features)? {
+            return Err(MeilisearchHttpError::DocumentNotFound(doc_id.to_string()).into());
+        }
+    }
+
     let document = some_documents(index, &txn, Some(internal_id), retrieve_vectors)?
         .next()
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))??;
@@ -1593,3 +2545,31 @@ fn retrieve_document<S: AsRef<str>>(
 
     Ok(document)
 }
+
+/// Returns `true` if the document matches the filter.
+fn document_matches_filter(
+    index: &Index,
+    rtxn: &RoTxn,
+    docid: DocumentId,
+    filter: &Value,
+    features: RoFeatures,
+) -> Result<bool, ResponseError> {
+    match parse_filter(filter, Code::InvalidDocumentFilter, features)? {
+        Some(filter) => Ok(evaluate_filter(&filter, rtxn, index)?.contains(docid)),
+        None => Ok(true),
+    }
+}
+
+pub(super) fn evaluate_filter(
+    filter: &milli::Filter,
+    rtxn: &RoTxn,
+    index: &Index,
+) -> Result<RoaringBitmap, ResponseError> {
+    filter.evaluate(rtxn, index).map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(..)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter)
+                .with_details(err.error_details())
+        }
+        e => e.into(),
+    })
+}
@@ -4,6 +4,7 @@ use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::TenantTokenRoute;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
@@ -18,8 +19,9 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_facet_search, FacetSearchResult, HybridQuery, MatchingStrategy,
-    RankingScoreThreshold, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    add_search_rules, check_allowed_attributes, perform_facet_search, CropStrategy,
+    FacetQueryMatching, FacetSearchResult, HybridQuery, MatchingStrategy, RankingScoreThreshold,
+    SearchQ, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEARCH_OFFSET,
 };
@@ -52,6 +54,8 @@ pub struct FacetSearchQuery {
     pub facet_query: Option<String>,
     #[deserr(error = DeserrJsonError<InvalidFacetSearchFacetName>, missing_field_error = DeserrJsonError::missing_facet_search_facet_name)]
     pub facet_name: String,
+    #[deserr(default, error = DeserrJsonError<InvalidFacetSearchFacetQueryMatching>)]
+    pub facet_query_matching: FacetQueryMatching,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
@@ -92,6 +96,7 @@ impl FacetSearchAggregator {
         let FacetSearchQuery {
             facet_query: _,
             facet_name,
+            facet_query_matching,
             vector,
             q,
             filter,
@@ -107,6 +112,7 @@ impl FacetSearchAggregator {
             total_received: 1,
             facet_names: Some(facet_name.clone()).into_iter().collect(),
             additional_search_parameters_provided: q.is_some()
+                || *facet_query_matching != FacetQueryMatching::default()
                 || vector.is_some()
                 || filter.is_some()
                 || *matching_strategy != MatchingStrategy::default()
@@ -247,11 +253,17 @@ pub async fn search(
 
     let facet_query = query.facet_query.clone();
     let facet_name = query.facet_name.clone();
+    let facet_query_matching = query.facet_query_matching;
     let locales = query.locales.clone().map(|l| l.into_iter().map(Into::into).collect());
     let mut search_query = SearchQuery::from(query);
 
     // Tenant token search_rules.
+    index_scheduler.check_route(TenantTokenRoute::FacetSearch)?;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        if let Some(allowed) = &search_rules.attributes_to_retrieve {
+            let used_attributes = search_query.used_attributes();
+            check_allowed_attributes(allowed, used_attributes.iter().chain([&facet_name]))?;
+        }
         add_search_rules(&mut search_query.filter, search_rules);
     }
 
@@ -264,6 +276,7 @@ pub async fn search(
             search_query,
             facet_query,
             facet_name,
+            facet_query_matching,
             search_kind,
             index_scheduler.features(),
             locales,
@@ -289,6 +302,7 @@ impl From<FacetSearchQuery> for SearchQuery {
         let FacetSearchQuery {
             facet_query: _,
             facet_name: _,
+            facet_query_matching: _,
             q,
             vector,
             filter,
@@ -311,7 +325,7 @@ impl From<FacetSearchQuery> for SearchQuery {
         };
 
         SearchQuery {
-            q,
+            q: q.map(SearchQ::Query),
             offset: DEFAULT_SEARCH_OFFSET(),
             limit: DEFAULT_SEARCH_LIMIT(),
             page,
@@ -322,6 +336,8 @@ impl From<FacetSearchQuery> for SearchQuery {
             crop_length: DEFAULT_CROP_LENGTH(),
             attributes_to_highlight: None,
             show_matches_position: false,
+            show_grapheme_offsets: false,
+            show_matched_terms: false,
             show_ranking_score: false,
             show_ranking_score_details: false,
             filter,
@@ -331,12 +347,20 @@ impl From<FacetSearchQuery> for SearchQuery {
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
+            crop_strategy: CropStrategy::default(),
             matching_strategy,
             vector,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            ignore_terms: None,
+            debug_timings: false,
+            exhaustive_total_hits: false,
+            exhaustive_facet_count: false,
+            geo_grid_precision: None,
+            sensitive_matching: None,
+            rerank_budget: None,
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::Infallible;
 
 use actix_web::web::Data;
@@ -11,6 +11,7 @@ use meilisearch_types::deserr::{immutable_field_error, DeserrJsonError, DeserrQu
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::vector::usage::EmbedderUsage;
 use meilisearch_types::milli::{self, FieldDistribution, Index};
 use meilisearch_types::tasks::KindWithContent;
 use serde::Serialize;
@@ -19,7 +20,8 @@ use tracing::debug;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use super::{
-    get_task_id, Pagination, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
+    get_depends_on, get_task_id, get_task_labels, Pagination, PaginationView, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
 };
 use crate::analytics::{Aggregate, Analytics};
 use crate::extractors::authentication::policies::*;
@@ -28,6 +30,7 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod changes;
 pub mod documents;
 pub mod facet_search;
 pub mod search;
@@ -38,14 +41,17 @@ pub mod settings;
 mod settings_analytics;
 pub mod similar;
 mod similar_analytics;
+pub mod source;
 
 #[derive(OpenApi)]
 #[openapi(
     nest(
+        (path = "/", api = changes::IndexChangesApi),
         (path = "/", api = documents::DocumentsApi),
         (path = "/", api = facet_search::FacetSearchApi),
         (path = "/", api = similar::SimilarApi),
         (path = "/", api = settings::SettingsApi),
+        (path = "/", api = source::IndexSourceApi),
     ),
     paths(list_indexes, create_index, get_index, update_index, delete_index, get_index_stats),
     tags(
@@ -73,11 +79,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::scope("/changes").configure(changes::configure))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/similar").configure(similar::configure))
-            .service(web::scope("/settings").configure(settings::configure)),
+            .service(web::scope("/settings").configure(settings::configure))
+            .service(web::scope("/source").configure(source::configure)),
     );
 }
 
@@ -270,10 +278,14 @@ pub async fn create_index(
         let task = KindWithContent::IndexCreation { index_uid: uid.to_string(), primary_key };
         let uid = get_task_id(&req, &opt)?;
         let dry_run = is_dry_run(&req, &opt)?;
-        let task: SummarizedTaskView =
-            tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                .await??
-                .into();
+        let depends_on = get_depends_on(&req)?;
+        let labels = get_task_labels(&req)?;
+        let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+            let quotas = index_scheduler.filters().key_quotas();
+            index_scheduler.register_for_key(task, uid, depends_on, labels, quotas, dry_run)
+        })
+        .await??
+        .into();
         debug!(returns = ?task, "Create index");
 
         Ok(HttpResponse::Accepted().json(task))
@@ -431,10 +443,13 @@ pub async fn update_index(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update index");
     Ok(HttpResponse::Accepted().json(task))
@@ -479,10 +494,13 @@ pub async fn delete_index(
     let task = KindWithContent::IndexDeletion { index_uid: index_uid.into_inner() };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
     debug!(returns = ?task, "Delete index");
 
     Ok(HttpResponse::Accepted().json(task))
@@ -509,10 +527,31 @@ pub struct IndexStats {
     /// Association of every field name with the number of times it occurs in the documents.
     #[schema(value_type = HashMap<String, u64>)]
     pub field_distribution: FieldDistribution,
+    /// The requests sent to the embedders of the index, by embedder name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub embedder_usage: BTreeMap<String, EmbedderUsageView>,
+}
+
+/// The requests sent to an embedder of an `Index`.
+#[derive(Serialize, Debug, Default, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbedderUsageView {
+    /// Requests sent to embed the documents, since the creation of the index.
+    pub indexing: EmbedderUsage,
+    /// Requests sent to embed the search queries, since the start of the instance.
+    pub search: EmbedderUsage,
 }
 
 impl From<index_scheduler::IndexStats> for IndexStats {
     fn from(stats: index_scheduler::IndexStats) -> Self {
+        let mut embedder_usage: BTreeMap<String, EmbedderUsageView> = BTreeMap::new();
+        for (name, usage) in stats.inner_stats.embedder_usage {
+            embedder_usage.entry(name).or_default().indexing = usage;
+        }
+        for (name, usage) in stats.search_embedder_usage {
+            embedder_usage.entry(name).or_default().search = usage;
+        }
+
         IndexStats {
             number_of_documents: stats
                 .inner_stats
@@ -524,6 +563,7 @@ impl From<index_scheduler::IndexStats> for IndexStats {
             number_of_embeddings: stats.inner_stats.number_of_embeddings,
             number_of_embedded_documents: stats.inner_stats.number_of_embedded_documents,
             field_distribution: stats.inner_stats.field_distribution,
+            embedder_usage,
         }
     }
 }
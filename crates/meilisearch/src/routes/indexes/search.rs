@@ -1,11 +1,14 @@
+use std::collections::BTreeMap;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::TenantTokenRoute;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
@@ -17,15 +20,19 @@ use utoipa::{IntoParams, OpenApi};
 use crate::analytics::Analytics;
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
+use crate::routes::multi_search_analytics::MultiSearchAggregator;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, check_allowed_attributes, perform_federated_search, perform_search,
+    record_embedder_usage, AttributeToFormat, CropStrategy, FederatedSearch, FederatedSearchResult,
+    Federation, HybridFusion, HybridQuery, MatchingStrategy, MergeFacets, RankingScoreThreshold,
+    RetrieveVectors, SearchAccuracy, SearchFacet, SearchKind, SearchQ, SearchQuery,
+    SearchQueryWithIndex, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -98,6 +105,12 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     #[param(value_type = bool)]
     show_matches_position: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowGraphemeOffsets>)]
+    #[param(value_type = bool)]
+    show_grapheme_offsets: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchedTerms>)]
+    #[param(value_type = bool)]
+    show_matched_terms: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScore>)]
     #[param(value_type = bool)]
     show_ranking_score: Param<bool>,
@@ -116,6 +129,8 @@ pub struct SearchQueryGet {
     #[deserr(default = DEFAULT_CROP_MARKER(), error = DeserrQueryParamError<InvalidSearchCropMarker>)]
     #[param(default = DEFAULT_CROP_MARKER)]
     crop_marker: String,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchCropStrategy>)]
+    crop_strategy: CropStrategy,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
@@ -126,12 +141,39 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     #[param(value_type = f32)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchHybridFusion>)]
+    #[param(required = false, value_type = Option<HybridFusion>)]
+    pub hybrid_fusion: Option<HybridFusion>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAccuracy>)]
+    #[param(required = false, value_type = Option<f32>)]
+    pub hybrid_search_accuracy: Option<SearchAccuracyGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     #[param(value_type = f32)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
     #[param(value_type = Vec<Locale>, explode = false)]
     pub locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchIgnoreTerms>)]
+    #[param(value_type = Vec<String>, explode = false)]
+    pub ignore_terms: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDebugTimings>)]
+    #[param(value_type = bool)]
+    pub debug_timings: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExhaustiveTotalHits>)]
+    #[param(value_type = bool)]
+    pub exhaustive_total_hits: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExhaustiveFacetCount>)]
+    #[param(value_type = bool)]
+    pub exhaustive_facet_count: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGeoGridPrecision>)]
+    #[param(required = false, value_type = Option<usize>)]
+    pub geo_grid_precision: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSensitiveMatching>)]
+    #[param(required = false, value_type = Option<bool>)]
+    pub sensitive_matching: Option<Param<bool>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerankBudget>)]
+    #[param(required = false, value_type = Option<usize>)]
+    pub rerank_budget: Option<Param<usize>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -168,6 +210,19 @@ impl std::ops::Deref for SemanticRatioGet {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
+#[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchSearchAccuracy)]
+pub struct SearchAccuracyGet(SearchAccuracy);
+
+impl std::convert::TryFrom<String> for SearchAccuracyGet {
+    type Error = InvalidSearchSearchAccuracy;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let f: f32 = s.parse().map_err(|_| InvalidSearchSearchAccuracy)?;
+        Ok(SearchAccuracyGet(SearchAccuracy::try_from(f)?))
+    }
+}
+
 impl TryFrom<SearchQueryGet> for SearchQuery {
     type Error = ResponseError;
 
@@ -180,20 +235,43 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             None => None,
         };
 
+        let fusion = other.hybrid_fusion.unwrap_or_default();
+        let search_accuracy = other.hybrid_search_accuracy.map(|accuracy| accuracy.0);
         let hybrid = match (other.hybrid_embedder, other.hybrid_semantic_ratio) {
-            (None, None) => None,
+            (None, None) => {
+                if other.hybrid_fusion.is_some() {
+                    return Err(ResponseError::from_msg(
+                        "`hybridEmbedder` is mandatory when `hybridFusion` is present".into(),
+                        meilisearch_types::error::Code::InvalidSearchHybridQuery,
+                    ));
+                }
+                if search_accuracy.is_some() {
+                    return Err(ResponseError::from_msg(
+                        "`hybridEmbedder` is mandatory when `hybridSearchAccuracy` is present"
+                            .into(),
+                        meilisearch_types::error::Code::InvalidSearchHybridQuery,
+                    ));
+                }
+                None
+            }
             (None, Some(_)) => {
                 return Err(ResponseError::from_msg(
                     "`hybridEmbedder` is mandatory when `hybridSemanticRatio` is present".into(),
                     meilisearch_types::error::Code::InvalidSearchHybridQuery,
                 ));
             }
-            (Some(embedder), None) => {
-                Some(HybridQuery { semantic_ratio: DEFAULT_SEMANTIC_RATIO(), embedder })
-            }
-            (Some(embedder), Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder })
-            }
+            (Some(embedder), None) => Some(HybridQuery {
+                semantic_ratio: DEFAULT_SEMANTIC_RATIO(),
+                embedder,
+                fusion,
+                search_accuracy,
+            }),
+            (Some(embedder), Some(semantic_ratio)) => Some(HybridQuery {
+                semantic_ratio: *semantic_ratio,
+                embedder,
+                fusion,
+                search_accuracy,
+            }),
         };
 
         if other.vector.is_some() && hybrid.is_none() {
@@ -204,7 +282,7 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
         }
 
         Ok(Self {
-            q: other.q,
+            q: other.q.map(SearchQ::Query),
             vector: other.vector.map(CS::into_inner),
             offset: other.offset.0,
             limit: other.limit.0,
@@ -212,24 +290,38 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             hits_per_page: other.hits_per_page.as_deref().copied(),
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: other.retrieve_vectors.0,
-            attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
+            attributes_to_crop: other
+                .attributes_to_crop
+                .map(|o| o.into_iter().map(AttributeToFormat::Name).collect()),
             crop_length: other.crop_length.0,
-            attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
+            attributes_to_highlight: other
+                .attributes_to_highlight
+                .map(|o| o.into_iter().map(AttributeToFormat::Name).collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             distinct: other.distinct,
             show_matches_position: other.show_matches_position.0,
+            show_grapheme_offsets: other.show_grapheme_offsets.0,
+            show_matched_terms: other.show_matched_terms.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
-            facets: other.facets.map(|o| o.into_iter().collect()),
+            facets: other.facets.map(|o| o.into_iter().map(SearchFacet::Name).collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
+            crop_strategy: other.crop_strategy,
             matching_strategy: other.matching_strategy,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
+            ignore_terms: other.ignore_terms.map(|o| o.into_iter().collect()),
+            debug_timings: other.debug_timings.0,
+            exhaustive_total_hits: other.exhaustive_total_hits.0,
+            exhaustive_facet_count: other.exhaustive_facet_count.0,
+            geo_grid_precision: other.geo_grid_precision.map(|o| o.0),
+            sensitive_matching: other.sensitive_matching.map(|o| o.0),
+            rerank_budget: other.rerank_budget.map(|o| o.0),
         })
     }
 }
@@ -262,6 +354,9 @@ pub fn fix_sort_query_parameters(sort_query: &str) -> Vec<String> {
 /// Search an index with GET
 ///
 /// Search for documents matching a specific query in the given index.
+///
+/// With the `--experimental-index-aliases` option, `indexUid` can be an alias: the indexes of the alias are searched
+/// and their hits are merged by ranking score, like in a federated search.
 #[utoipa::path(
     get,
     path = "/{indexUid}/search",
@@ -329,7 +424,19 @@ pub async fn search_with_url_query(
     let mut query: SearchQuery = params.into_inner().try_into()?;
 
     // Tenant token search_rules.
+    index_scheduler.check_route(TenantTokenRoute::Search)?;
+    if let Some(indexes) = alias_indexes(&index_scheduler, &index_uid)? {
+        let search_result =
+            search_alias(index_scheduler, &search_queue, indexes, query, &req, &analytics).await?;
+        debug!(returns = ?search_result, "Search get");
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+    let mut allowed_attributes = None;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        if let Some(allowed) = &search_rules.attributes_to_retrieve {
+            check_allowed_attributes(allowed, query.used_attributes())?;
+        }
+        allowed_attributes = search_rules.attributes_to_retrieve.clone();
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -340,16 +447,19 @@ pub async fn search_with_url_query(
     let search_kind =
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
+    let embedder_name = search_kind.embedder_name().map(str::to_owned);
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(
-            index_uid.to_string(),
-            &index,
-            query,
-            search_kind,
-            retrieve_vector,
-            index_scheduler.features(),
-        )
+        record_embedder_usage(&index_scheduler, &index_uid, embedder_name.as_deref(), || {
+            perform_search(
+                index_uid.to_string(),
+                &index,
+                query,
+                search_kind,
+                retrieve_vector,
+                index_scheduler.features(),
+            )
+        })
     })
     .await;
     permit.drop().await;
@@ -359,7 +469,10 @@ pub async fn search_with_url_query(
     }
     analytics.publish(aggregate, &req);
 
-    let search_result = search_result?;
+    let mut search_result = search_result?;
+    if let Some(allowed_attributes) = &allowed_attributes {
+        search_result.hits.iter_mut().for_each(|hit| hit.restrict_attributes(allowed_attributes));
+    }
 
     debug!(returns = ?search_result, "Search get");
     Ok(HttpResponse::Ok().json(search_result))
@@ -368,6 +481,9 @@ pub async fn search_with_url_query(
 /// Search with POST
 ///
 /// Search for documents matching a specific query in the given index.
+///
+/// With the `--experimental-index-aliases` option, `indexUid` can be an alias: the indexes of the alias are searched
+/// and their hits are merged by ranking score, like in a federated search.
 #[utoipa::path(
     post,
     path = "/{indexUid}/search",
@@ -435,7 +551,19 @@ pub async fn search_with_post(
     debug!(parameters = ?query, "Search post");
 
     // Tenant token search_rules.
+    index_scheduler.check_route(TenantTokenRoute::Search)?;
+    if let Some(indexes) = alias_indexes(&index_scheduler, &index_uid)? {
+        let search_result =
+            search_alias(index_scheduler, &search_queue, indexes, query, &req, &analytics).await?;
+        debug!(returns = ?search_result, "Search post");
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+    let mut allowed_attributes = None;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        if let Some(allowed) = &search_rules.attributes_to_retrieve {
+            check_allowed_attributes(allowed, query.used_attributes())?;
+        }
+        allowed_attributes = search_rules.attributes_to_retrieve.clone();
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -447,16 +575,19 @@ pub async fn search_with_post(
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
+    let embedder_name = search_kind.embedder_name().map(str::to_owned);
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(
-            index_uid.to_string(),
-            &index,
-            query,
-            search_kind,
-            retrieve_vectors,
-            index_scheduler.features(),
-        )
+        record_embedder_usage(&index_scheduler, &index_uid, embedder_name.as_deref(), || {
+            perform_search(
+                index_uid.to_string(),
+                &index,
+                query,
+                search_kind,
+                retrieve_vectors,
+                index_scheduler.features(),
+            )
+        })
     })
     .await;
     permit.drop().await;
@@ -469,12 +600,125 @@ pub async fn search_with_post(
     }
     analytics.publish(aggregate, &req);
 
-    let search_result = search_result?;
+    let mut search_result = search_result?;
+    if let Some(allowed_attributes) = &allowed_attributes {
+        search_result.hits.iter_mut().for_each(|hit| hit.restrict_attributes(allowed_attributes));
+    }
 
     debug!(returns = ?search_result, "Search post");
     Ok(HttpResponse::Ok().json(search_result))
 }
 
+/// Returns the indexes searched through the alias, `None` if it isn't an alias.
+///
+/// An index shadows the alias with the same uid.
+fn alias_indexes(
+    index_scheduler: &IndexScheduler,
+    index_uid: &IndexUid,
+) -> Result<Option<Vec<IndexUid>>, ResponseError> {
+    if index_scheduler.index_exists(index_uid)? {
+        return Ok(None);
+    }
+    match index_scheduler.resolve_index_alias(index_uid) {
+        Some(indexes) => {
+            Ok(Some(indexes.into_iter().map(IndexUid::try_from).collect::<Result<_, _>>()?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Searches the indexes of an alias with a federated search, their hits being merged by
+/// ranking score.
+async fn search_alias(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: &SearchQueue,
+    indexes: Vec<IndexUid>,
+    query: SearchQuery,
+    req: &HttpRequest,
+    analytics: &Analytics,
+) -> Result<FederatedSearchResult, ResponseError> {
+    let pagination = match (query.page, query.hits_per_page) {
+        (Some(_), _) => Some(("page", Code::InvalidSearchPage)),
+        (None, Some(_)) => Some(("hitsPerPage", Code::InvalidSearchHitsPerPage)),
+        (None, None) => None,
+    };
+    if let Some((parameter, code)) = pagination {
+        return Err(ResponseError::from_msg(
+            format!("Using `{parameter}` is not allowed when searching through an alias, use `offset` and `limit` instead."),
+            code,
+        ));
+    }
+
+    // the pagination and the facets are handled by the federation, over all the indexes
+    let facets: Option<Vec<String>> =
+        query.facets.as_ref().map(|facets| facets.iter().map(|f| f.name().to_string()).collect());
+    let federation = Federation {
+        limit: query.limit,
+        offset: query.offset,
+        facets_by_index: indexes
+            .iter()
+            .map(|index_uid| (index_uid.clone(), facets.clone()))
+            .collect(),
+        merge_facets: facets.is_some().then(MergeFacets::default),
+    };
+    let query = SearchQuery {
+        offset: DEFAULT_SEARCH_OFFSET(),
+        limit: DEFAULT_SEARCH_LIMIT(),
+        facets: None,
+        ..query
+    };
+
+    let filters = index_scheduler.filters();
+    let mut allowed_attributes = BTreeMap::new();
+    let mut queries = Vec::with_capacity(indexes.len());
+    for index_uid in indexes {
+        if !filters.is_index_authorized(&index_uid) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        if !index_scheduler.index_exists(&index_uid)? {
+            return Err(index_scheduler::Error::IndexNotFound(index_uid.into_inner()).into());
+        }
+        let mut query = SearchQueryWithIndex::from_index_query_federation(
+            index_uid.clone(),
+            query.clone(),
+            None,
+        );
+        if let Some(search_rules) = filters.get_index_search_rules(&index_uid) {
+            if let Some(allowed) = &search_rules.attributes_to_retrieve {
+                check_allowed_attributes(allowed, query.used_attributes())?;
+                if let Some(facets) = &facets {
+                    check_allowed_attributes(allowed, facets)?;
+                }
+                allowed_attributes.insert(index_uid.to_string(), allowed.clone());
+            }
+            add_search_rules(&mut query.filter, search_rules);
+        }
+        queries.push(query);
+    }
+
+    let federated_search = FederatedSearch { queries, federation: Some(federation.clone()) };
+    let mut aggregate = MultiSearchAggregator::from_federated_search(&federated_search);
+    let features = index_scheduler.features();
+
+    let permit = search_queue.try_get_search_permit().await?;
+    let mut search_result = perform_federated_search(
+        &index_scheduler,
+        federated_search.queries,
+        federation,
+        features,
+        false,
+    )
+    .await;
+    permit.drop().await;
+    if let Ok(search_result) = &mut search_result {
+        search_result.restrict_attributes(&allowed_attributes);
+        aggregate.succeed();
+    }
+    analytics.publish(aggregate, req);
+
+    search_result
+}
+
 pub fn search_kind(
     query: &SearchQuery,
     index_scheduler: &IndexScheduler,
@@ -482,32 +726,43 @@ pub fn search_kind(
     index: &milli::Index,
 ) -> Result<SearchKind, ResponseError> {
     // handle with care, the order of cases matters, the semantics is subtle
-    match (query.q.as_deref(), &query.hybrid, query.vector.as_deref()) {
+    match (query.q.as_ref().map(SearchQ::as_str), &query.hybrid, query.vector.as_deref()) {
         // empty query, no vector => placeholder search
         (Some(q), _, None) if q.trim().is_empty() => Ok(SearchKind::KeywordOnly),
         // no query, no vector => placeholder search
         (None, _, None) => Ok(SearchKind::KeywordOnly),
         // hybrid.semantic_ratio == 1.0 => vector
-        (_, Some(HybridQuery { semantic_ratio, embedder }), v) if **semantic_ratio == 1.0 => {
+        (_, Some(HybridQuery { semantic_ratio, embedder, fusion: _, search_accuracy: _ }), v)
+            if **semantic_ratio == 1.0 =>
+        {
             SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
         }
         // hybrid.semantic_ratio == 0.0 => keyword
-        (_, Some(HybridQuery { semantic_ratio, embedder: _ }), _) if **semantic_ratio == 0.0 => {
-            Ok(SearchKind::KeywordOnly)
-        }
+        (
+            _,
+            Some(HybridQuery { semantic_ratio, embedder: _, fusion: _, search_accuracy: _ }),
+            _,
+        ) if **semantic_ratio == 0.0 => Ok(SearchKind::KeywordOnly),
         // no query, hybrid, vector => semantic
-        (None, Some(HybridQuery { semantic_ratio: _, embedder }), Some(v)) => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len()))
-        }
+        (
+            None,
+            Some(HybridQuery { semantic_ratio: _, embedder, fusion: _, search_accuracy: _ }),
+            Some(v),
+        ) => SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len())),
         // query, no hybrid, no vector => keyword
         (Some(_), None, None) => Ok(SearchKind::KeywordOnly),
         // query, hybrid, maybe vector => hybrid
-        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => SearchKind::hybrid(
+        (
+            Some(_),
+            Some(HybridQuery { semantic_ratio, embedder, fusion, search_accuracy: _ }),
+            v,
+        ) => SearchKind::hybrid(
             index_scheduler,
             index_uid,
             index,
             embedder,
             **semantic_ratio,
+            *fusion,
             v.map(|v| v.len()),
         ),
 
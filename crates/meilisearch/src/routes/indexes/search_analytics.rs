@@ -8,9 +8,9 @@ use serde_json::{json, Value};
 use crate::aggregate_methods;
 use crate::analytics::{Aggregate, AggregateMethod};
 use crate::search::{
-    SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEMANTIC_RATIO,
+    CropStrategy, HybridFusion, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEMANTIC_RATIO,
 };
 
 aggregate_methods!(
@@ -59,6 +59,10 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     max_vector_size: usize,
     // Whether the semantic ratio passed to a hybrid search equals the default ratio.
     semantic_ratio: bool,
+    // Whether the fusion passed to a hybrid search differs from the default linear blend.
+    hybrid_fusion: bool,
+    // Whether a hybrid search tuned the accuracy of the vector store.
+    hybrid_search_accuracy: bool,
     hybrid: bool,
     retrieve_vectors: bool,
 
@@ -68,10 +72,28 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // List of the unique Locales passed as parameter
     locales: BTreeSet<Locale>,
 
+    // ignore_terms
+    // every time a search is done using ignore_terms
+    ignore_terms_total_number_of_uses: usize,
+
+    // sensitive_matching
+    // every time a search is done with an explicit sensitive_matching
+    sensitive_matching_total_number_of_uses: usize,
+
+    // rerank_budget
+    // every time a search is done with an explicit rerank_budget
+    rerank_budget_total_number_of_uses: usize,
+
+    // debug_timings
+    // every time a search is done using debug_timings
+    debug_timings_total_number_of_uses: usize,
+
     // pagination
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    // every time a search is done using exhaustive_total_hits
+    exhaustive_total_hits: usize,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -80,12 +102,21 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     highlight_post_tag: bool,
     max_attributes_to_crop: usize,
     crop_marker: bool,
+    crop_strategy: bool,
     show_matches_position: bool,
+    show_grapheme_offsets: bool,
+    show_matched_terms: bool,
     crop_length: bool,
 
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+    // every time a search is done using exhaustive_facet_count
+    facets_exhaustive_count: usize,
+
+    // geo grid
+    geo_grid: usize,
+    max_geo_grid_precision: usize,
 
     // scoring
     show_ranking_score: bool,
@@ -111,6 +142,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             crop_length,
             attributes_to_highlight: _,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             show_ranking_score,
             show_ranking_score_details,
             filter,
@@ -120,11 +153,19 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            crop_strategy,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            debug_timings,
+            exhaustive_total_hits,
+            exhaustive_facet_count,
+            geo_grid_precision,
+            sensitive_matching,
+            rerank_budget,
         } = query;
 
         let mut ret = Self::default();
@@ -169,7 +210,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         }
 
         if let Some(ref q) = q {
-            ret.max_terms_number = q.split_whitespace().count();
+            ret.max_terms_number = q.as_str().split_whitespace().count();
         }
 
         if let Some(ref vector) = vector {
@@ -187,6 +228,12 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.max_offset = *offset;
             ret.finite_pagination = 0;
         }
+        ret.exhaustive_total_hits = *exhaustive_total_hits as usize;
+        ret.facets_exhaustive_count = *exhaustive_facet_count as usize;
+        if let Some(precision) = geo_grid_precision {
+            ret.geo_grid = 1;
+            ret.max_geo_grid_precision = *precision;
+        }
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
 
@@ -194,11 +241,30 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.locales = locales.iter().copied().collect();
         }
 
+        if ignore_terms.is_some() {
+            ret.ignore_terms_total_number_of_uses = 1;
+        }
+
+        if sensitive_matching.is_some() {
+            ret.sensitive_matching_total_number_of_uses = 1;
+        }
+
+        if rerank_budget.is_some() {
+            ret.rerank_budget_total_number_of_uses = 1;
+        }
+
+        if *debug_timings {
+            ret.debug_timings_total_number_of_uses = 1;
+        }
+
         ret.highlight_pre_tag = *highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = *highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
         ret.crop_marker = *crop_marker != DEFAULT_CROP_MARKER();
+        ret.crop_strategy = *crop_strategy != CropStrategy::default();
         ret.crop_length = *crop_length != DEFAULT_CROP_LENGTH();
         ret.show_matches_position = *show_matches_position;
+        ret.show_grapheme_offsets = *show_grapheme_offsets;
+        ret.show_matched_terms = *show_matched_terms;
 
         ret.show_ranking_score = *show_ranking_score;
         ret.show_ranking_score_details = *show_ranking_score_details;
@@ -206,6 +272,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
 
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
+            ret.hybrid_fusion = hybrid.fusion != HybridFusion::default();
+            ret.hybrid_search_accuracy = hybrid.search_accuracy.is_some();
             ret.hybrid = true;
         }
 
@@ -221,6 +289,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            geo_grid: _,
+            timings: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -263,24 +333,37 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             max_limit,
             max_offset,
             finite_pagination,
+            exhaustive_total_hits,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
             max_attributes_to_crop,
             crop_marker,
+            crop_strategy,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facets_exhaustive_count,
+            geo_grid,
+            max_geo_grid_precision,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
+            hybrid_fusion,
+            hybrid_search_accuracy,
             hybrid,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
             mut locales,
+            ignore_terms_total_number_of_uses,
+            sensitive_matching_total_number_of_uses,
+            rerank_budget_total_number_of_uses,
+            debug_timings_total_number_of_uses,
             marker: _,
         } = *new;
 
@@ -326,12 +409,16 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
         self.retrieve_vectors |= retrieve_vectors;
         self.semantic_ratio |= semantic_ratio;
+        self.hybrid_fusion |= hybrid_fusion;
+        self.hybrid_search_accuracy |= hybrid_search_accuracy;
         self.hybrid |= hybrid;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
         self.finite_pagination += finite_pagination;
+        self.exhaustive_total_hits =
+            self.exhaustive_total_hits.saturating_add(exhaustive_total_hits);
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -342,13 +429,22 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.highlight_post_tag |= highlight_post_tag;
         self.max_attributes_to_crop = self.max_attributes_to_crop.max(max_attributes_to_crop);
         self.crop_marker |= crop_marker;
+        self.crop_strategy |= crop_strategy;
         self.show_matches_position |= show_matches_position;
+        self.show_grapheme_offsets |= show_grapheme_offsets;
+        self.show_matched_terms |= show_matched_terms;
         self.crop_length |= crop_length;
 
         // facets
         self.facets_sum_of_terms = self.facets_sum_of_terms.saturating_add(facets_sum_of_terms);
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(facets_total_number_of_facets);
+        self.facets_exhaustive_count =
+            self.facets_exhaustive_count.saturating_add(facets_exhaustive_count);
+
+        // geo grid
+        self.geo_grid = self.geo_grid.saturating_add(geo_grid);
+        self.max_geo_grid_precision = self.max_geo_grid_precision.max(max_geo_grid_precision);
 
         // matching strategy
         for (key, value) in matching_strategy.into_iter() {
@@ -364,6 +460,26 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         // locales
         self.locales.append(&mut locales);
 
+        // ignore_terms
+        self.ignore_terms_total_number_of_uses = self
+            .ignore_terms_total_number_of_uses
+            .saturating_add(ignore_terms_total_number_of_uses);
+
+        // sensitive_matching
+        self.sensitive_matching_total_number_of_uses = self
+            .sensitive_matching_total_number_of_uses
+            .saturating_add(sensitive_matching_total_number_of_uses);
+
+        // rerank_budget
+        self.rerank_budget_total_number_of_uses = self
+            .rerank_budget_total_number_of_uses
+            .saturating_add(rerank_budget_total_number_of_uses);
+
+        // debug_timings
+        self.debug_timings_total_number_of_uses = self
+            .debug_timings_total_number_of_uses
+            .saturating_add(debug_timings_total_number_of_uses);
+
         self
     }
 
@@ -389,24 +505,37 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             max_limit,
             max_offset,
             finite_pagination,
+            exhaustive_total_hits,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
             max_attributes_to_crop,
             crop_marker,
+            crop_strategy,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facets_exhaustive_count,
+            geo_grid,
+            max_geo_grid_precision,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
+            hybrid_fusion,
+            hybrid_search_accuracy,
             hybrid,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
             locales,
+            ignore_terms_total_number_of_uses,
+            sensitive_matching_total_number_of_uses,
+            rerank_budget_total_number_of_uses,
+            debug_timings_total_number_of_uses,
             marker: _,
         } = *self;
 
@@ -450,11 +579,14 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "hybrid": {
                 "enabled": hybrid,
                 "semantic_ratio": semantic_ratio,
+                "fusion": hybrid_fusion,
+                "search_accuracy": hybrid_search_accuracy,
             },
             "pagination": {
                "max_limit": max_limit,
                "max_offset": max_offset,
                "most_used_navigation": if finite_pagination > (total_received / 2) { "exhaustive" } else { "estimated" },
+               "exhaustive_total_hits": exhaustive_total_hits,
             },
             "formatting": {
                 "max_attributes_to_retrieve": max_attributes_to_retrieve,
@@ -463,16 +595,36 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
                 "highlight_post_tag": highlight_post_tag,
                 "max_attributes_to_crop": max_attributes_to_crop,
                 "crop_marker": crop_marker,
+                "crop_strategy": crop_strategy,
                 "show_matches_position": show_matches_position,
+                "show_grapheme_offsets": show_grapheme_offsets,
+                "show_matched_terms": show_matched_terms,
                 "crop_length": crop_length,
             },
             "facets": {
                 "avg_facets_number": format!("{:.2}", facets_sum_of_terms as f64 / facets_total_number_of_facets as f64),
+                "exhaustive_facet_count": facets_exhaustive_count,
+            },
+            "geo_grid": {
+                "total": geo_grid,
+                "max_precision": max_geo_grid_precision,
             },
             "matching_strategy": {
                 "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
             "locales": locales,
+            "ignore_terms": {
+                "total_number_of_uses": ignore_terms_total_number_of_uses,
+            },
+            "sensitive_matching": {
+                "total_number_of_uses": sensitive_matching_total_number_of_uses,
+            },
+            "rerank_budget": {
+                "total_number_of_uses": rerank_budget_total_number_of_uses,
+            },
+            "debug_timings": {
+                "total_number_of_uses": debug_timings_total_number_of_uses,
+            },
             "scoring": {
                 "show_ranking_score": show_ranking_score,
                 "show_ranking_score_details": show_ranking_score_details,
@@ -16,7 +16,7 @@ use super::settings_analytics::*;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
-use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::routes::{get_depends_on, get_task_id, get_task_labels, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 /// This macro generates the routes for the settings.
@@ -82,7 +82,7 @@ macro_rules! make_setting_route {
             use $crate::extractors::authentication::GuardedData;
             use $crate::extractors::sequential_extractor::SeqHandler;
             use $crate::Opt;
-            use $crate::routes::{is_dry_run, get_task_id, SummarizedTaskView};
+            use $crate::routes::{get_depends_on, get_task_labels, is_dry_run, get_task_id, SummarizedTaskView};
             #[allow(unused_imports)]
             use super::*;
 
@@ -140,10 +140,13 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let depends_on = get_depends_on(&req)?;
+                let labels = get_task_labels(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Delete settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -226,10 +229,13 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let depends_on = get_depends_on(&req)?;
+                let labels = get_task_labels(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Update settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -508,6 +514,94 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/sensitive-matching",
+        update_verb: put,
+        value_type: bool,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsSensitiveMatching,
+        >,
+        attr: sensitive_matching,
+        camelcase_attr: "sensitiveMatching",
+        analytics: SensitiveMatchingAnalytics
+    },
+    {
+        route: "/reranker",
+        update_verb: put,
+        value_type: meilisearch_types::milli::vector::reranker::RerankerSettings,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsReranker,
+        >,
+        attr: reranker,
+        camelcase_attr: "reranker",
+        analytics: RerankerAnalytics
+    },
+    {
+        route: "/flattening",
+        update_verb: patch,
+        value_type: meilisearch_types::settings::FlatteningSettings,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsFlattening,
+        >,
+        attr: flattening,
+        camelcase_attr: "flattening",
+        analytics: FlatteningAnalytics
+    },
+    {
+        route: "/ingest-function",
+        update_verb: put,
+        value_type: String,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsIngestFunction,
+        >,
+        attr: ingest_function,
+        camelcase_attr: "ingestFunction",
+        analytics: IngestFunctionAnalytics
+    },
+    {
+        route: "/document-schema",
+        update_verb: put,
+        value_type: serde_json::Value,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsDocumentSchema,
+        >,
+        attr: document_schema,
+        camelcase_attr: "documentSchema",
+        analytics: DocumentSchemaAnalytics
+    },
+    {
+        route: "/skip-unchanged-documents",
+        update_verb: put,
+        value_type: bool,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsSkipUnchangedDocuments,
+        >,
+        attr: skip_unchanged_documents,
+        camelcase_attr: "skipUnchangedDocuments",
+        analytics: SkipUnchangedDocumentsAnalytics
+    },
+    {
+        route: "/attachments",
+        update_verb: put,
+        value_type: meilisearch_types::milli::attachments::AttachmentsSettings,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsAttachments,
+        >,
+        attr: attachments,
+        camelcase_attr: "attachments",
+        analytics: AttachmentsAnalytics
+    },
+    {
+        route: "/max-document-revisions",
+        update_verb: put,
+        value_type: u64,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsMaxDocumentRevisions,
+        >,
+        attr: max_document_revisions,
+        camelcase_attr: "maxDocumentRevisions",
+        analytics: MaxDocumentRevisionsAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -597,6 +691,24 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            sensitive_matching: SensitiveMatchingAnalytics::new(
+                new_settings.sensitive_matching.as_ref().set(),
+            ),
+            reranker: RerankerAnalytics::new(new_settings.reranker.as_ref().set()),
+            flattening: FlatteningAnalytics::new(new_settings.flattening.as_ref().set()),
+            ingest_function: IngestFunctionAnalytics::new(
+                new_settings.ingest_function.as_ref().set(),
+            ),
+            document_schema: DocumentSchemaAnalytics::new(
+                new_settings.document_schema.as_ref().set(),
+            ),
+            skip_unchanged_documents: SkipUnchangedDocumentsAnalytics::new(
+                new_settings.skip_unchanged_documents.as_ref().set(),
+            ),
+            attachments: AttachmentsAnalytics::new(new_settings.attachments.as_ref().set()),
+            max_document_revisions: MaxDocumentRevisionsAnalytics::new(
+                new_settings.max_document_revisions.as_ref().set(),
+            ),
         },
         &req,
     );
@@ -611,10 +723,13 @@ pub async fn update_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -705,10 +820,13 @@ pub async fn delete_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -741,5 +859,9 @@ fn validate_settings(
         }
     }
 
+    if let Setting::Set(_) = &settings.ingest_function {
+        features.check_edit_documents_by_function("setting `ingestFunction`")?;
+    }
+
     Ok(settings.validate()?)
 }
@@ -7,11 +7,13 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
+use meilisearch_types::milli::attachments::AttachmentsSettings;
 use meilisearch_types::milli::update::Setting;
+use meilisearch_types::milli::vector::reranker::RerankerSettings;
 use meilisearch_types::milli::FilterableAttributesRule;
 use meilisearch_types::settings::{
-    FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
-    RankingRuleView, SettingEmbeddingSettings, TypoSettings,
+    FacetingSettings, FlatteningSettings, PaginationSettings, PrefixSearchSettings,
+    ProximityPrecisionView, RankingRuleView, SettingEmbeddingSettings, TypoSettings,
 };
 use serde::Serialize;
 
@@ -39,6 +41,14 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub sensitive_matching: SensitiveMatchingAnalytics,
+    pub reranker: RerankerAnalytics,
+    pub flattening: FlatteningAnalytics,
+    pub ingest_function: IngestFunctionAnalytics,
+    pub document_schema: DocumentSchemaAnalytics,
+    pub skip_unchanged_documents: SkipUnchangedDocumentsAnalytics,
+    pub attachments: AttachmentsAnalytics,
+    pub max_document_revisions: MaxDocumentRevisionsAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -165,6 +175,14 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bytes), None) | (None, Some(bytes)) => Some(bytes),
                     (Some(this), Some(other)) => Some(this.max(other)),
                 },
+                document_template_chunking_used: match (
+                    self.embedders.document_template_chunking_used,
+                    new.embedders.document_template_chunking_used,
+                ) {
+                    (None, None) => None,
+                    (Some(used), None) | (None, Some(used)) => Some(used),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
                 binary_quantization_used: match (
                     self.embedders.binary_quantization_used,
                     new.embedders.binary_quantization_used,
@@ -198,6 +216,44 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            sensitive_matching: SensitiveMatchingAnalytics {
+                set: new.sensitive_matching.set | self.sensitive_matching.set,
+                value: new.sensitive_matching.value.or(self.sensitive_matching.value),
+            },
+            reranker: RerankerAnalytics {
+                set: new.reranker.set | self.reranker.set,
+                custom_document_template: new
+                    .reranker
+                    .custom_document_template
+                    .or(self.reranker.custom_document_template),
+            },
+            flattening: FlatteningAnalytics {
+                max_depth: new.flattening.max_depth.or(self.flattening.max_depth),
+                array_positions: new.flattening.array_positions.or(self.flattening.array_positions),
+            },
+            ingest_function: IngestFunctionAnalytics {
+                set: new.ingest_function.set | self.ingest_function.set,
+            },
+            document_schema: DocumentSchemaAnalytics {
+                set: new.document_schema.set | self.document_schema.set,
+            },
+            skip_unchanged_documents: SkipUnchangedDocumentsAnalytics {
+                set: new.skip_unchanged_documents.set | self.skip_unchanged_documents.set,
+                value: new.skip_unchanged_documents.value.or(self.skip_unchanged_documents.value),
+            },
+            attachments: AttachmentsAnalytics {
+                attributes: new.attachments.attributes.or(self.attachments.attributes),
+                extraction_service: new
+                    .attachments
+                    .extraction_service
+                    .or(self.attachments.extraction_service),
+            },
+            max_document_revisions: MaxDocumentRevisionsAnalytics {
+                max_revisions: new
+                    .max_document_revisions
+                    .max_revisions
+                    .or(self.max_document_revisions.max_revisions),
+            },
         })
     }
 
@@ -503,6 +559,8 @@ pub struct EmbeddersAnalytics {
     // max
     pub document_template_max_bytes: Option<usize>,
     // |=
+    pub document_template_chunking_used: Option<bool>,
+    // |=
     pub binary_quantization_used: Option<bool>,
 }
 
@@ -524,6 +582,10 @@ impl EmbeddersAnalytics {
                     EmbedderSource::Ollama => sources.insert("ollama".to_string()),
                     EmbedderSource::Rest => sources.insert("rest".to_string()),
                     EmbedderSource::Composite => sources.insert("composite".to_string()),
+                    EmbedderSource::Cohere => sources.insert("cohere".to_string()),
+                    EmbedderSource::AzureOpenAi => sources.insert("azureOpenAi".to_string()),
+                    EmbedderSource::Bedrock => sources.insert("bedrock".to_string()),
+                    EmbedderSource::Onnx => sources.insert("onnx".to_string()),
                 };
             }
         };
@@ -542,6 +604,11 @@ impl EmbeddersAnalytics {
                     .filter_map(|config| config.document_template_max_bytes.set())
                     .max()
             }),
+            document_template_chunking_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.document_template_chunk_bytes.set().is_some())
+            }),
             binary_quantization_used: setting.as_ref().map(|map| {
                 map.values()
                     .filter_map(|config| config.inner.clone().set())
@@ -674,3 +741,138 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct SensitiveMatchingAnalytics {
+    pub set: bool,
+    pub value: Option<bool>,
+}
+
+impl SensitiveMatchingAnalytics {
+    pub fn new(settings: Option<&bool>) -> Self {
+        Self { set: settings.is_some(), value: settings.copied() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { sensitive_matching: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct RerankerAnalytics {
+    pub set: bool,
+    pub custom_document_template: Option<bool>,
+}
+
+impl RerankerAnalytics {
+    pub fn new(settings: Option<&RerankerSettings>) -> Self {
+        Self {
+            set: settings.is_some(),
+            custom_document_template: settings
+                .map(|settings| settings.document_template.as_ref().set().is_some()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { reranker: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct FlatteningAnalytics {
+    pub max_depth: Option<usize>,
+    pub array_positions: Option<bool>,
+}
+
+impl FlatteningAnalytics {
+    pub fn new(settings: Option<&FlatteningSettings>) -> Self {
+        Self {
+            max_depth: settings.and_then(|settings| settings.max_depth.set()),
+            array_positions: settings.and_then(|settings| settings.array_positions.set()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { flattening: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct IngestFunctionAnalytics {
+    pub set: bool,
+}
+
+impl IngestFunctionAnalytics {
+    pub fn new(ingest_function: Option<&String>) -> Self {
+        Self { set: ingest_function.is_some() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { ingest_function: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct DocumentSchemaAnalytics {
+    pub set: bool,
+}
+
+impl DocumentSchemaAnalytics {
+    pub fn new(document_schema: Option<&serde_json::Value>) -> Self {
+        Self { set: document_schema.is_some() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { document_schema: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct SkipUnchangedDocumentsAnalytics {
+    pub set: bool,
+    pub value: Option<bool>,
+}
+
+impl SkipUnchangedDocumentsAnalytics {
+    pub fn new(settings: Option<&bool>) -> Self {
+        Self { set: settings.is_some(), value: settings.copied() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { skip_unchanged_documents: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct AttachmentsAnalytics {
+    pub attributes: Option<usize>,
+    pub extraction_service: Option<bool>,
+}
+
+impl AttachmentsAnalytics {
+    pub fn new(settings: Option<&AttachmentsSettings>) -> Self {
+        Self {
+            attributes: settings.map(|settings| settings.attributes.len()),
+            extraction_service: settings.map(|settings| settings.extraction_url.is_some()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { attachments: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct MaxDocumentRevisionsAnalytics {
+    pub max_revisions: Option<u64>,
+}
+
+impl MaxDocumentRevisionsAnalytics {
+    pub fn new(setting: Option<&u64>) -> Self {
+        Self { max_revisions: setting.copied() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { max_document_revisions: self, ..Default::default() }
+    }
+}
@@ -2,6 +2,7 @@ use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::TenantTokenRoute;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
@@ -19,8 +20,9 @@ use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::indexes::similar_analytics::{SimilarAggregator, SimilarGET, SimilarPOST};
 use crate::search::{
-    add_search_rules, perform_similar, RankingScoreThresholdSimilar, RetrieveVectors, Route,
-    SearchKind, SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, check_allowed_attributes, perform_similar, RankingScoreThresholdSimilar,
+    RetrieveVectors, Route, SearchKind, SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET,
 };
 
 #[derive(OpenApi)]
@@ -219,7 +221,13 @@ async fn similar(
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
     // Tenant token search_rules.
+    index_scheduler.check_route(TenantTokenRoute::Similar)?;
+    let mut allowed_attributes = None;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        if let Some(allowed) = &search_rules.attributes_to_retrieve {
+            check_allowed_attributes(allowed, query.used_attributes())?;
+        }
+        allowed_attributes = search_rules.attributes_to_retrieve.clone();
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -234,7 +242,7 @@ async fn similar(
         Route::Similar,
     )?;
 
-    tokio::task::spawn_blocking(move || {
+    let mut similar = tokio::task::spawn_blocking(move || {
         perform_similar(
             &index,
             query,
@@ -245,7 +253,11 @@ async fn similar(
             index_scheduler.features(),
         )
     })
-    .await?
+    .await??;
+    if let Some(allowed_attributes) = &allowed_attributes {
+        similar.hits.iter_mut().for_each(|hit| hit.restrict_attributes(allowed_attributes));
+    }
+    Ok(similar)
 }
 
 #[derive(Debug, deserr::Deserr, IntoParams)]
@@ -269,6 +281,8 @@ pub struct SimilarQueryGet {
     retrieve_vectors: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSimilarFilter>)]
     filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarQ>)]
+    q: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSimilarShowRankingScore>)]
     #[param(value_type = bool, default)]
     show_ranking_score: Param<bool>,
@@ -304,6 +318,7 @@ impl From<SimilarQueryGet> for SimilarQuery {
             attributes_to_retrieve,
             retrieve_vectors,
             filter,
+            q,
             show_ranking_score,
             show_ranking_score_details,
             embedder,
@@ -323,6 +338,7 @@ impl From<SimilarQueryGet> for SimilarQuery {
             offset: offset.0,
             limit: limit.0,
             filter,
+            q,
             embedder,
             attributes_to_retrieve: attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: retrieve_vectors.0,
@@ -29,6 +29,10 @@ pub struct SimilarAggregator<Method: AggregateMethod> {
     filter_total_number_of_criteria: usize,
     used_syntax: HashMap<String, usize>,
 
+    // q
+    // every time a request has a q field, this field must be incremented by one
+    q_total_number_of_uses: usize,
+
     // Whether a non-default embedder was specified
     retrieve_vectors: bool,
 
@@ -60,6 +64,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
             show_ranking_score,
             show_ranking_score_details,
             filter,
+            q,
             ranking_score_threshold,
         } = query;
 
@@ -91,6 +96,10 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
+        if q.is_some() {
+            ret.q_total_number_of_uses = 1;
+        }
+
         ret.max_limit = *limit;
         ret.max_offset = *offset;
 
@@ -128,6 +137,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            q_total_number_of_uses,
             max_limit,
             max_offset,
             max_attributes_to_retrieve,
@@ -155,6 +165,10 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             *used_syntax = used_syntax.saturating_add(value);
         }
 
+        // q
+        self.q_total_number_of_uses =
+            self.q_total_number_of_uses.saturating_add(q_total_number_of_uses);
+
         self.retrieve_vectors |= retrieve_vectors;
 
         // pagination
@@ -183,6 +197,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            q_total_number_of_uses,
             max_limit,
             max_offset,
             max_attributes_to_retrieve,
@@ -213,6 +228,9 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
                "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
+            "q": {
+               "total_number_of_uses": q_total_number_of_uses,
+            },
             "vector": {
                 "retrieve_vectors": retrieve_vectors,
             },
@@ -0,0 +1,310 @@
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::sources::{IndexSource, RefreshMode};
+use serde::Serialize;
+use time::OffsetDateTime;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use super::documents::{from_char_csv_delimiter_json, validate_import_url};
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+/// The shortest interval between two refreshes of a source, in seconds.
+const MIN_REFRESH_INTERVAL: u64 = 60;
+/// The longest interval between two refreshes of a source, in seconds.
+const MAX_REFRESH_INTERVAL: u64 = 365 * 24 * 60 * 60;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_index_source, put_index_source, delete_index_source),
+    tags(
+        (
+            name = "Index source",
+            description = "The `/source` route registers a remote URL the documents of an index are periodically refreshed from.
+Every refresh downloads the documents and enqueues a `documentAdditionOrUpdate` task importing them, the first one being enqueued as soon as the source is registered.
+Sources require launching Meilisearch with the `--experimental-import-url-allowed-hosts` option.
+
+This route is **synchronous**. This means that no task object will be returned, and any change to the source will be made available immediately.",
+        ),
+    ),
+)]
+pub struct IndexSourceApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(get_index_source)))
+            .route(web::put().to(SeqHandler(put_index_source)))
+            .route(web::delete().to(SeqHandler(delete_index_source))),
+    );
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexSourceRequest {
+    /// The HTTP(S) URL serving the documents, e.g. an S3 presigned URL.
+    /// The format of the documents is deduced from the `Content-Type` of the response or from the extension of the file.
+    #[deserr(error = DeserrJsonError<InvalidDocumentImportUrl>)]
+    #[schema(example = "https://example.com/movies.ndjson")]
+    pub url: String,
+    /// The primary key of the documents.
+    #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
+    #[schema(value_type = Option<String>, example = "id")]
+    pub primary_key: Option<String>,
+    /// Customize the csv delimiter when importing CSV documents.
+    #[deserr(default, try_from(char) = from_char_csv_delimiter_json -> DeserrJsonError<InvalidDocumentCsvDelimiter>, error = DeserrJsonError<InvalidDocumentCsvDelimiter>)]
+    #[schema(value_type = Option<char>, example = ";")]
+    pub csv_delimiter: Option<u8>,
+    /// The number of seconds between two refreshes, between one minute and one year.
+    #[deserr(error = DeserrJsonError<InvalidIndexSourceRefreshInterval>)]
+    #[schema(example = 86400)]
+    pub refresh_interval: u64,
+    /// `full` deletes the documents that are not served by the source anymore,
+    /// `delta` only adds or replaces the served documents by primary key.
+    #[deserr(default, error = DeserrJsonError<InvalidIndexSourceMode>)]
+    #[schema(value_type = Option<RefreshMode>, example = "delta")]
+    pub mode: RefreshMode,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSourceView {
+    /// The HTTP(S) URL serving the documents.
+    pub url: String,
+    /// The primary key of the documents.
+    pub primary_key: Option<String>,
+    /// The csv delimiter used when importing CSV documents.
+    #[schema(value_type = Option<char>)]
+    pub csv_delimiter: Option<char>,
+    /// The number of seconds between two refreshes.
+    pub refresh_interval: u64,
+    /// Whether the documents that are not served by the source anymore are deleted.
+    pub mode: RefreshMode,
+    /// The date from which the next refresh is enqueued.
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_refresh_at: OffsetDateTime,
+    /// The task enqueued by the last refresh.
+    pub last_refresh_task_uid: Option<u32>,
+}
+
+impl From<IndexSource> for IndexSourceView {
+    fn from(source: IndexSource) -> Self {
+        let IndexSource {
+            url,
+            primary_key,
+            csv_delimiter,
+            refresh_interval,
+            mode,
+            next_refresh_at,
+            last_refresh_task_uid,
+        } = source;
+        IndexSourceView {
+            url,
+            primary_key,
+            csv_delimiter: csv_delimiter.map(char::from),
+            refresh_interval,
+            mode,
+            next_refresh_at,
+            last_refresh_task_uid,
+        }
+    }
+}
+
+/// Get the source of an index
+///
+/// Get the remote source the documents of the index are periodically refreshed from.
+#[utoipa::path(
+    get,
+    path = "{indexUid}/source",
+    tag = "Index source",
+    security(("Bearer" = ["settings.get", "settings.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    responses(
+        (status = 200, description = "The source is returned", body = IndexSourceView, content_type = "application/json", example = json!(
+            {
+                "url": "https://example.com/movies.ndjson",
+                "primaryKey": "id",
+                "csvDelimiter": null,
+                "refreshInterval": 86400,
+                "mode": "delta",
+                "nextRefreshAt": "2024-08-09T17:05:55.791772Z",
+                "lastRefreshTaskUid": 147
+            }
+        )),
+        (status = 404, description = "The index has no source", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` has no source.",
+                "code": "index_source_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_source_not_found"
+            }
+        )),
+    )
+)]
+pub async fn get_index_source(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let source =
+        index_scheduler.index_source(&index_uid)?.ok_or_else(|| source_not_found(&index_uid))?;
+    let source = IndexSourceView::from(source);
+
+    debug!(returns = ?source, "Get index source");
+    Ok(HttpResponse::Ok().json(source))
+}
+
+#[derive(Serialize)]
+pub struct IndexSourceAnalytics {
+    full_refresh: bool,
+    refresh_interval: u64,
+}
+
+impl Aggregate for IndexSourceAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Index Source Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        new
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Set the source of an index
+///
+/// Register the remote URL the documents of the index are periodically refreshed from, replacing the previous one.
+/// The first refresh is enqueued right away.
+#[utoipa::path(
+    put,
+    path = "{indexUid}/source",
+    tag = "Index source",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = IndexSourceRequest,
+    responses(
+        (status = 200, description = "The source is registered", body = IndexSourceView, content_type = "application/json", example = json!(
+            {
+                "url": "https://example.com/movies.ndjson",
+                "primaryKey": "id",
+                "csvDelimiter": null,
+                "refreshInterval": 86400,
+                "mode": "delta",
+                "nextRefreshAt": "2024-08-09T17:05:55.791772Z",
+                "lastRefreshTaskUid": 147
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+    )
+)]
+pub async fn put_index_source(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexSourceRequest, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexSourceRequest { url, primary_key, csv_delimiter, refresh_interval, mode } =
+        body.into_inner();
+    debug!(parameters = ?url, "Put index source");
+
+    validate_import_url(&url)?;
+    if !(MIN_REFRESH_INTERVAL..=MAX_REFRESH_INTERVAL).contains(&refresh_interval) {
+        return Err(ResponseError::from_msg(
+            format!("Invalid value at `.refreshInterval`: the refresh interval must be between {MIN_REFRESH_INTERVAL} and {MAX_REFRESH_INTERVAL} seconds, found `{refresh_interval}`."),
+            Code::InvalidIndexSourceRefreshInterval,
+        ));
+    }
+
+    analytics.publish(
+        IndexSourceAnalytics { full_refresh: mode == RefreshMode::Full, refresh_interval },
+        &req,
+    );
+
+    let source = IndexSource {
+        url,
+        primary_key,
+        csv_delimiter,
+        refresh_interval,
+        mode,
+        next_refresh_at: OffsetDateTime::now_utc(),
+        last_refresh_task_uid: None,
+    };
+    let source: IndexSourceView =
+        tokio::task::spawn_blocking(move || index_scheduler.put_index_source(&index_uid, source))
+            .await??
+            .into();
+
+    debug!(returns = ?source, "Put index source");
+    Ok(HttpResponse::Ok().json(source))
+}
+
+/// Delete the source of an index
+///
+/// Stop refreshing the documents of the index. The documents already imported are kept.
+#[utoipa::path(
+    delete,
+    path = "{indexUid}/source",
+    tag = "Index source",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    responses(
+        (status = 204, description = "The source is deleted"),
+        (status = 404, description = "The index has no source", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` has no source.",
+                "code": "index_source_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_source_not_found"
+            }
+        )),
+    )
+)]
+pub async fn delete_index_source(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let scheduler = index_scheduler.clone();
+    let uid = index_uid.clone();
+    let deleted =
+        tokio::task::spawn_blocking(move || scheduler.delete_index_source(&uid)).await??;
+    if !deleted {
+        return Err(source_not_found(&index_uid));
+    }
+
+    debug!("Delete index source");
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn source_not_found(index_uid: &IndexUid) -> ResponseError {
+    ResponseError::from_msg(
+        format!("Index `{index_uid}` has no source."),
+        Code::IndexSourceNotFound,
+    )
+}
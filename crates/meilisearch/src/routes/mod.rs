@@ -6,19 +6,20 @@ use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
 use meilisearch_types::batch_view::BatchView;
 use meilisearch_types::batches::BatchStats;
-use meilisearch_types::error::{Code, ErrorType, ResponseError};
+use meilisearch_types::error::{Code, ErrorDetails, ErrorType, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::keys::CreateApiKey;
+use meilisearch_types::keys::{CreateApiKey, KeyQuotas, RateLimit, RateLimitPeriod, RequestsLimit};
+use meilisearch_types::milli::vector::usage::EmbedderUsage;
 use meilisearch_types::milli::{
     AttributePatterns, FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
 };
 use meilisearch_types::settings::{
-    Checked, FacetingSettings, MinWordSizeTyposSetting, PaginationSettings, Settings, TypoSettings,
-    Unchecked,
+    Checked, FacetingSettings, FlatteningSettings, MinWordSizeTyposSetting, PaginationSettings,
+    Settings, TypoSettings, Unchecked,
 };
-use meilisearch_types::task_view::{DetailsView, TaskView};
-use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
+use meilisearch_types::task_view::{DetailsView, TaskProgressView, TaskView};
+use meilisearch_types::tasks::{Kind, Status, Task, TaskId, TaskLabel};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use tracing::debug;
@@ -26,16 +27,21 @@ use utoipa::{OpenApi, ToSchema};
 
 use self::api_key::KeyView;
 use self::indexes::documents::BrowseQuery;
-use self::indexes::{IndexCreateRequest, IndexStats, UpdateIndexRequest};
+use self::indexes::{EmbedderUsageView, IndexCreateRequest, IndexStats, UpdateIndexRequest};
 use self::logs::{GetLogs, LogMode, UpdateStderrLogs};
 use self::open_api_utils::OpenApiAuth;
-use self::tasks::AllTasks;
+use self::tasks::{AllTasks, SchedulerState};
+use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
 use crate::milli::progress::{ProgressStepView, ProgressView};
+use crate::routes::aliases::{IndexAliasesView, PatchIndexAliases};
 use crate::routes::batches::AllBatches;
 use crate::routes::features::RuntimeTogglableFeatures;
-use crate::routes::indexes::documents::{DocumentDeletionByFilter, DocumentEditionByFunction};
+use crate::routes::indexes::documents::{
+    DocumentDeletionByFilter, DocumentEditionByFunction, OnConflict,
+};
 use crate::routes::indexes::IndexView;
 use crate::routes::multi_search::SearchResults;
 use crate::routes::network::{Network, Remote};
@@ -50,6 +56,7 @@ use crate::Opt;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 const PAGINATION_DEFAULT_LIMIT_FN: fn() -> usize = || 20;
 
+mod aliases;
 mod api_key;
 pub mod batches;
 mod dump;
@@ -63,6 +70,7 @@ pub mod network;
 mod open_api_utils;
 mod snapshot;
 mod swap_indexes;
+mod task_log;
 pub mod tasks;
 #[cfg(test)]
 mod tasks_test;
@@ -71,6 +79,7 @@ mod tasks_test;
 #[openapi(
     nest(
         (path = "/tasks", api = tasks::TaskApi),
+        (path = "/task-log", api = task_log::TaskLogApi),
         (path = "/batches", api = batches::BatchesApi),
         (path = "/indexes", api = indexes::IndexesApi),
         // We must stop the search path here because the rest must be configured by each route individually
@@ -84,8 +93,9 @@ mod tasks_test;
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
+        (path = "/aliases", api = aliases::IndexAliasesApi),
     ),
-    paths(get_health, get_version, get_stats),
+    paths(get_health, drain, get_version, get_stats),
     tags(
         (name = "Stats", description = "Stats gives extended information and metrics about indexes and the Meilisearch database."),
     ),
@@ -94,14 +104,16 @@ mod tasks_test;
         url = "/",
         description = "Local server",
     )),
-    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
+    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, OnConflict, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, RateLimit, RequestsLimit, RateLimitPeriod, KeyQuotas, UpdateStderrLogs, LogMode, GetLogs, IndexStats, EmbedderUsageView, EmbedderUsage, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, ErrorDetails, AllTasks, SchedulerState, TaskView, TaskProgressView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, FlatteningSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, IndexAliasesView, PatchIndexAliases, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
 )]
 pub struct MeilisearchApi;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/tasks").configure(tasks::configure))
+        .service(web::scope("/task-log").configure(task_log::configure))
         .service(web::scope("/batches").configure(batches::configure))
         .service(web::resource("/health").route(web::get().to(get_health)))
+        .service(web::resource("/drain").route(web::post().to(SeqHandler(drain))))
         .service(web::scope("/logs").configure(logs::configure))
         .service(web::scope("/keys").configure(api_key::configure))
         .service(web::scope("/dumps").configure(dump::configure))
@@ -113,7 +125,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
-        .service(web::scope("/network").configure(network::configure));
+        .service(web::scope("/network").configure(network::configure))
+        .service(web::scope("/aliases").configure(aliases::configure));
 
     #[cfg(feature = "swagger")]
     {
@@ -124,7 +137,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 }
 
 pub fn get_task_id(req: &HttpRequest, opt: &Opt) -> Result<Option<TaskId>, ResponseError> {
-    if !opt.experimental_replication_parameters {
+    if !opt.replication_parameters() {
+        return Ok(None);
+    }
+    // Without its uid, the task is refused by the replica.
+    if opt.read_only_replica && !is_sent_by_primary(req) {
         return Ok(None);
     }
     let task_id = req
@@ -154,8 +171,78 @@ pub fn get_task_id(req: &HttpRequest, opt: &Opt) -> Result<Option<TaskId>, Respo
     Ok(task_id)
 }
 
+/// Whether the request comes from the primary of this read-only replica, which authenticates
+/// with the master key both instances share.
+pub fn is_sent_by_primary(req: &HttpRequest) -> bool {
+    let Some(auth) = req.app_data::<Data<AuthController>>() else { return false };
+    let Some(master_key) = auth.get_master_key() else { return false };
+    req.headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .map_or(false, |token| token == master_key)
+}
+
+/// Returns the uid of the task the registered task must wait for, given by the `DependsOn` header.
+pub fn get_depends_on(req: &HttpRequest) -> Result<Option<TaskId>, ResponseError> {
+    let depends_on = req
+        .headers()
+        .get("DependsOn")
+        .map(|header| {
+            header.to_str().map_err(|e| {
+                ResponseError::from_msg(
+                    format!("DependsOn is not a valid utf-8 string: {e}"),
+                    Code::InvalidTaskDependsOn,
+                )
+            })
+        })
+        .transpose()?
+        .map(|s| {
+            s.parse::<TaskId>().map_err(|e| {
+                ResponseError::from_msg(
+                    format!(
+                        "Could not parse the DependsOn as a {}: {e}",
+                        std::any::type_name::<TaskId>(),
+                    ),
+                    Code::InvalidTaskDependsOn,
+                )
+            })
+        })
+        .transpose()?;
+    Ok(depends_on)
+}
+
+/// Returns the `key=value` labels attached to the registered task, given by the `TaskLabels` header
+/// as a list of labels separated by the `,` character.
+pub fn get_task_labels(req: &HttpRequest) -> Result<BTreeMap<String, String>, ResponseError> {
+    let Some(header) = req.headers().get("TaskLabels") else { return Ok(BTreeMap::new()) };
+    let header = header.to_str().map_err(|e| {
+        ResponseError::from_msg(
+            format!("TaskLabels is not a valid utf-8 string: {e}"),
+            Code::InvalidTaskLabels,
+        )
+    })?;
+
+    let mut labels = BTreeMap::new();
+    for label in header.split(',') {
+        let TaskLabel { key, value } = label.parse().map_err(|e| {
+            ResponseError::from_msg(
+                format!("Invalid TaskLabels header: {e}"),
+                Code::InvalidTaskLabels,
+            )
+        })?;
+        if labels.insert(key.clone(), value).is_some() {
+            return Err(ResponseError::from_msg(
+                format!("Invalid TaskLabels header: the `{key}` label is specified several times."),
+                Code::InvalidTaskLabels,
+            ));
+        }
+    }
+    Ok(labels)
+}
+
 pub fn is_dry_run(req: &HttpRequest, opt: &Opt) -> Result<bool, ResponseError> {
-    if !opt.experimental_replication_parameters {
+    if !opt.replication_parameters() {
         return Ok(false);
     }
     Ok(req
@@ -186,6 +273,13 @@ pub struct SummarizedTaskView {
     /// The type of the task.
     #[serde(rename = "type")]
     kind: Kind,
+    /// The uid of the task that must succeed before this task is processed.
+    #[schema(value_type = Option<u32>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    depends_on: Option<TaskId>,
+    /// The `key=value` labels attached to the task.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
     /// The date on which the task was enqueued.
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     enqueued_at: OffsetDateTime,
@@ -198,6 +292,8 @@ impl From<Task> for SummarizedTaskView {
             index_uid: task.index_uid().map(|s| s.to_string()),
             status: task.status,
             kind: task.kind.as_kind(),
+            depends_on: task.depends_on,
+            labels: task.labels,
             enqueued_at: task.enqueued_at,
         }
     }
@@ -376,6 +472,8 @@ pub struct Stats {
     /// The date of the last update in the RFC 3339 formats. Can be `null` if no update has ever been processed.
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     pub last_update: Option<OffsetDateTime>,
+    /// The number of tasks waiting to be processed or being processed.
+    pub enqueued_tasks: u64,
     /// The stats of every individual index your API key lets you access.
     #[schema(value_type = HashMap<String, indexes::IndexStats>)]
     pub indexes: BTreeMap<String, indexes::IndexStats>,
@@ -395,6 +493,7 @@ pub struct Stats {
                 "databaseSize": 567,
                 "usedDatabaseSize": 456,
                 "lastUpdate": "2019-11-20T09:40:33.711324Z",
+                "enqueuedTasks": 3,
                 "indexes": {
                     "movies": {
                         "numberOfDocuments": 10,
@@ -465,7 +564,15 @@ pub fn create_all_stats(
     database_size += auth_controller.size()?;
     used_database_size += auth_controller.used_size()?;
 
-    let stats = Stats { database_size, used_database_size, last_update: last_task, indexes };
+    let enqueued_tasks = index_scheduler.enqueued_tasks_count()?;
+
+    let stats = Stats {
+        database_size,
+        used_database_size,
+        last_update: last_task,
+        enqueued_tasks,
+        indexes,
+    };
     Ok(stats)
 }
 
@@ -530,6 +637,22 @@ async fn get_version(
 struct HealthResponse {
     /// The status of the instance.
     status: HealthStatus,
+    /// Whether the instance is a read-only replica, only serving the search requests.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    replica: bool,
+}
+
+impl HealthResponse {
+    fn new(index_scheduler: &IndexScheduler, opt: &Opt) -> Self {
+        let status = if index_scheduler.is_drained() {
+            HealthStatus::Drained
+        } else if index_scheduler.is_draining() {
+            HealthStatus::Draining
+        } else {
+            HealthStatus::Available
+        };
+        HealthResponse { status, replica: opt.read_only_replica }
+    }
 }
 
 #[derive(Default, Serialize, ToSchema)]
@@ -537,11 +660,17 @@ struct HealthResponse {
 enum HealthStatus {
     #[default]
     Available,
+    /// The instance refuses the new tasks and finishes its processing batches.
+    Draining,
+    /// The instance can be removed, its batches are finished and their webhooks sent.
+    Drained,
 }
 
 /// Get Health
 ///
 /// The health check endpoint enables you to periodically test the health of your Meilisearch instance.
+/// It also tells if the instance is a read-only replica, to which only the search requests should be sent,
+/// and if it is draining before being removed.
 #[utoipa::path(
     get,
     path = "/health",
@@ -558,10 +687,55 @@ pub async fn get_health(
     index_scheduler: Data<IndexScheduler>,
     auth_controller: Data<AuthController>,
     search_queue: Data<SearchQueue>,
+    opt: Data<Opt>,
 ) -> Result<HttpResponse, ResponseError> {
     search_queue.health().unwrap();
     index_scheduler.health().unwrap();
     auth_controller.health().unwrap();
 
-    Ok(HttpResponse::Ok().json(HealthResponse::default()))
+    let health = HealthResponse::new(&index_scheduler, &opt);
+    Ok(HttpResponse::Ok().json(health))
+}
+
+crate::empty_analytics!(DrainAnalytics, "Instance Drained");
+
+/// Drain the instance
+///
+/// Prepare the instance to be removed. The new tasks are refused and no new batch of tasks is processed,
+/// the document operations being processed stop at their next checkpoint and leave their remaining tasks enqueued.
+/// The analytics are sent right away, and the `/health` route reports the instance as `drained` once its batches are finished and their webhooks sent.
+/// The instance must be restarted to accept tasks again. Only the master key can access this route.
+#[utoipa::path(
+    post,
+    path = "/drain",
+    tag = "Health",
+    security(("Bearer" = [])),
+    responses(
+        (status = 202, description = "The instance is draining", body = HealthResponse, content_type = "application/json", example = json!(
+            {
+                "status": "draining"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn drain(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    req: HttpRequest,
+    opt: Data<Opt>,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.drain();
+    analytics.publish(DrainAnalytics::default(), &req);
+    analytics.flush().await;
+
+    let health = HealthResponse::new(&index_scheduler, &opt);
+    Ok(HttpResponse::Accepted().json(health))
 }
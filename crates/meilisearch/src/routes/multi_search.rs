@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use actix_http::StatusCode;
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::TenantTokenRoute;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::actions;
@@ -18,9 +21,9 @@ use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_federated_search, perform_search, FederatedSearch,
-    FederatedSearchResult, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
-    PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
+    add_search_rules, check_allowed_attributes, perform_federated_search, perform_search,
+    record_embedder_usage, FederatedSearch, FederatedSearchResult, RetrieveVectors,
+    SearchQueryWithIndex, SearchResultWithIndex, PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
 };
 use crate::search_queue::SearchQueue;
 
@@ -161,6 +164,8 @@ pub async fn multi_search_with_post(
     let features = index_scheduler.features();
 
     // regardless of federation, check authorization and apply search rules
+    index_scheduler.check_route(TenantTokenRoute::Search)?;
+    let mut allowed_attributes = BTreeMap::new();
     let auth = 'check_authorization: {
         for (query_index, federated_query) in queries.iter_mut().enumerate() {
             let index_uid = federated_query.index_uid.as_str();
@@ -172,6 +177,13 @@ pub async fn multi_search_with_post(
             // Apply search rules from tenant token
             if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(index_uid)
             {
+                if let Some(allowed) = &search_rules.attributes_to_retrieve {
+                    let used_attributes = federated_query.used_attributes();
+                    if let Err(err) = check_allowed_attributes(allowed, used_attributes) {
+                        break 'check_authorization Err(err).with_index(query_index);
+                    }
+                    allowed_attributes.insert(index_uid.to_string(), allowed.clone());
+                }
                 add_search_rules(&mut federated_query.filter, search_rules);
             }
         }
@@ -188,14 +200,26 @@ pub async fn multi_search_with_post(
 
     let response = match federation {
         Some(federation) => {
+            // the facets of the federation are distributed by index, outside of the queries
+            for (index_uid, facets) in &federation.facets_by_index {
+                if let (Some(allowed), Some(facets)) =
+                    (allowed_attributes.get(index_uid.as_str()), facets)
+                {
+                    check_allowed_attributes(allowed, facets)?;
+                }
+            }
+
             // check remote header
             let is_proxy = req
                 .headers()
                 .get(PROXY_SEARCH_HEADER)
                 .is_some_and(|value| value.as_bytes() == PROXY_SEARCH_HEADER_VALUE.as_bytes());
-            let search_result =
+            let mut search_result =
                 perform_federated_search(&index_scheduler, queries, federation, features, is_proxy)
                     .await;
+            if let Ok(search_result) = &mut search_result {
+                search_result.restrict_attributes(&allowed_attributes);
+            }
             permit.drop().await;
 
             if search_result.is_ok() {
@@ -249,24 +273,35 @@ pub async fn multi_search_with_post(
                     )
                     .with_index(query_index)?;
                     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
+                    let embedder_name = search_kind.embedder_name().map(str::to_owned);
+                    let scheduler = index_scheduler.clone();
 
                     let search_result = tokio::task::spawn_blocking(move || {
-                        perform_search(
-                            index_uid_str.clone(),
-                            &index,
-                            query,
-                            search_kind,
-                            retrieve_vector,
-                            features,
+                        record_embedder_usage(
+                            &scheduler,
+                            &index_uid_str,
+                            embedder_name.as_deref(),
+                            || {
+                                perform_search(
+                                    index_uid_str.clone(),
+                                    &index,
+                                    query,
+                                    search_kind,
+                                    retrieve_vector,
+                                    features,
+                                )
+                            },
                         )
                     })
                     .await
                     .with_index(query_index)?;
 
-                    search_results.push(SearchResultWithIndex {
-                        index_uid: index_uid.into_inner(),
-                        result: search_result.with_index(query_index)?,
-                    });
+                    let mut result = search_result.with_index(query_index)?;
+                    if let Some(allowed) = allowed_attributes.get(index_uid.as_str()) {
+                        result.hits.iter_mut().for_each(|hit| hit.restrict_attributes(allowed));
+                    }
+                    search_results
+                        .push(SearchResultWithIndex { index_uid: index_uid.into_inner(), result });
                 }
                 Ok(search_results)
             }
@@ -54,6 +54,8 @@ impl MultiSearchAggregator {
             show_ranking_score: _,
             show_ranking_score_details: _,
             show_matches_position: _,
+            show_grapheme_offsets: _,
+            show_matched_terms: _,
             filter: _,
             sort: _,
             distinct: _,
@@ -61,11 +63,19 @@ impl MultiSearchAggregator {
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
+            crop_strategy: _,
             matching_strategy: _,
             attributes_to_search_on: _,
             hybrid: _,
             ranking_score_threshold: _,
             locales: _,
+            ignore_terms: _,
+            debug_timings: _,
+            exhaustive_total_hits: _,
+            exhaustive_facet_count: _,
+            geo_grid_precision: _,
+            sensitive_matching: _,
+            rerank_budget: _,
         } in &federated_search.queries
         {
             if let Some(federation_options) = federation_options {
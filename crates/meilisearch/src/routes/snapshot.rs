@@ -1,38 +1,62 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
 use index_scheduler::IndexScheduler;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{
+    InvalidIndexUid, InvalidRestoreAt, InvalidRestoreIndexUids, InvalidRestoreSnapshot,
+};
+use meilisearch_types::error::{Code, InvalidRestoreDateError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::tasks::KindWithContent;
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
 
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::routes::{get_depends_on, get_task_id, get_task_labels, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_snapshot),
+    paths(create_snapshot, restore_snapshot, list_restore_points, restore_index),
+    components(schemas(RestoreParameters, RestorePointsView, IndexRestoreParameters)),
     tags((
         name = "Snapshots",
         description = "The snapshots route allows the creation of database snapshots. Snapshots are .snapshot files that can be used to launch Meilisearch.
 Creating a snapshot is also referred to as exporting it, whereas launching Meilisearch with a snapshot is referred to as importing it.
 During a snapshot export, all indexes of the current instance are exported—together with their documents and settings—and saved as a single .snapshot file.
 During a snapshot import, all indexes contained in the indicated .snapshot file are imported along with their associated documents and settings.
-Snapshot imports are performed at launch using an option.",
+Snapshot imports are performed at launch using an option.
+When launched with `--experimental-restore-points`, the latest snapshots are kept as restore points and the indexes
+can be restored to a past date in the running instance with the `/snapshots/restore` route.
+A single index can be restored from a snapshot of the snapshot directory with the `/snapshots/restore-index` route.",
         external_docs(url = "https://www.meilisearch.com/docs/reference/api/snapshots"),
     )),
 )]
 pub struct SnapshotApi;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_snapshot))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_snapshot))))
+        .service(web::resource("/restore").route(web::post().to(SeqHandler(restore_snapshot))))
+        .service(
+            web::resource("/restore-points").route(web::get().to(SeqHandler(list_restore_points))),
+        )
+        .service(web::resource("/restore-index").route(web::post().to(SeqHandler(restore_index))));
 }
 
 crate::empty_analytics!(SnapshotAnalytics, "Snapshot Created");
+crate::empty_analytics!(SnapshotRestoreAnalytics, "Snapshot Restored");
+crate::empty_analytics!(SnapshotIndexRestoreAnalytics, "Index Restored From Snapshot");
 
 /// Create a snapshot
 ///
@@ -73,11 +97,248 @@ pub async fn create_snapshot(
     let task = KindWithContent::SnapshotCreation;
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Create snapshot");
     Ok(HttpResponse::Accepted().json(task))
 }
+
+/// The date to restore the indexes to.
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct RestoreParameters {
+    /// The RFC 3339 date to restore the indexes to, after the oldest restore point.
+    #[schema(value_type = String, example = "2025-01-01T12:00:00Z")]
+    #[deserr(error = DeserrJsonError<InvalidRestoreAt>, try_from(&String) = parse_restore_date -> InvalidRestoreDateError, missing_field_error = DeserrJsonError::missing_restore_at)]
+    pub restore_at: OffsetDateTime,
+    /// The indexes to restore. All the indexes of the restore point are restored when omitted.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["movies"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidRestoreIndexUids>)]
+    pub index_uids: Option<Vec<IndexUid>>,
+}
+
+fn parse_restore_date(date: &str) -> Result<OffsetDateTime, InvalidRestoreDateError> {
+    match OffsetDateTime::parse(date, &Rfc3339) {
+        Ok(datetime) if datetime <= OffsetDateTime::now_utc() => Ok(datetime),
+        _ => Err(InvalidRestoreDateError(date.to_string())),
+    }
+}
+
+/// Restore the indexes to a date
+///
+/// Enqueues a task restoring the indexes to their state at a past date, from the latest restore
+/// point before the date and the tasks processed since. The indexes created after the restore
+/// point are left untouched. Requires launching Meilisearch with `--experimental-restore-points`.
+#[utoipa::path(
+    post,
+    path = "/restore",
+    tag = "Snapshots",
+    request_body = RestoreParameters,
+    security(("Bearer" = ["snapshots.restore", "snapshots.*", "*"])),
+    responses(
+        (status = 202, description = "The indexes are being restored", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 0,
+                "indexUid": null,
+                "status": "enqueued",
+                "type": "pointInTimeRestore",
+                "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn restore_snapshot(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::SNAPSHOTS_RESTORE }>,
+        Data<IndexScheduler>,
+    >,
+    body: AwebJson<RestoreParameters, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let RestoreParameters { restore_at, index_uids } = body.into_inner();
+    debug!(restore_at = ?restore_at, index_uids = ?index_uids, "Restore snapshot");
+    analytics.publish(SnapshotRestoreAnalytics::default(), &req);
+
+    // refuse the task right away when the restore points are disabled
+    index_scheduler.restore_points()?;
+
+    let task = KindWithContent::PointInTimeRestore {
+        index_uids: index_uids.map(|uids| uids.into_iter().map(IndexUid::into_inner).collect()),
+        restore_at,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
+
+    debug!(returns = ?task, "Restore snapshot");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// The dates the indexes can be restored from.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePointsView {
+    /// The RFC 3339 dates of the restore points, oldest first.
+    #[schema(example = json!(["2025-01-01T00:00:00Z", "2025-01-02T00:00:00Z"]))]
+    pub results: Vec<String>,
+}
+
+/// List the restore points
+///
+/// Lists the dates of the restore points, oldest first. The indexes can be restored to any date
+/// after the oldest one. Requires launching Meilisearch with `--experimental-restore-points`.
+#[utoipa::path(
+    get,
+    path = "/restore-points",
+    tag = "Snapshots",
+    security(("Bearer" = ["snapshots.restore", "snapshots.*", "*"])),
+    responses(
+        (status = 200, description = "The restore points are returned", body = RestorePointsView, content_type = "application/json", example = json!(
+            {
+                "results": ["2025-01-01T00:00:00Z", "2025-01-02T00:00:00Z"]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn list_restore_points(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::SNAPSHOTS_RESTORE }>,
+        Data<IndexScheduler>,
+    >,
+) -> Result<HttpResponse, ResponseError> {
+    let results = index_scheduler
+        .restore_points()?
+        .into_iter()
+        .map(|date| date.format(&Rfc3339).unwrap())
+        .collect();
+
+    let restore_points = RestorePointsView { results };
+    debug!(returns = ?restore_points, "List restore points");
+    Ok(HttpResponse::Ok().json(restore_points))
+}
+
+/// The index to restore and the snapshot to restore it from.
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexRestoreParameters {
+    /// The index to restore.
+    #[schema(value_type = String, example = "movies")]
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    pub index_uid: IndexUid,
+    /// The name of the snapshot file in the snapshot directory. Defaults to the snapshot created
+    /// by the instance.
+    #[schema(example = "data.ms.snapshot")]
+    #[deserr(default, error = DeserrJsonError<InvalidRestoreSnapshot>)]
+    pub snapshot: Option<String>,
+}
+
+/// Restore an index from a snapshot
+///
+/// Enqueues a task replacing an index with its copy from a snapshot of the snapshot directory, or
+/// creating it if it was deleted. The other indexes of the snapshot are left untouched, and the
+/// snapshot must have been created by the same version of Meilisearch.
+#[utoipa::path(
+    post,
+    path = "/restore-index",
+    tag = "Snapshots",
+    request_body = IndexRestoreParameters,
+    security(("Bearer" = ["snapshots.restore", "snapshots.*", "*"])),
+    responses(
+        (status = 202, description = "The index is being restored", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 0,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexRestore",
+                "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn restore_index(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::SNAPSHOTS_RESTORE }>,
+        Data<IndexScheduler>,
+    >,
+    body: AwebJson<IndexRestoreParameters, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let IndexRestoreParameters { index_uid, snapshot } = body.into_inner();
+    debug!(index_uid = ?index_uid, snapshot = ?snapshot, "Restore index from snapshot");
+    analytics.publish(SnapshotIndexRestoreAnalytics::default(), &req);
+
+    let snapshot = match snapshot {
+        // only the files of the snapshot directory can be restored from
+        Some(snapshot) if Path::new(&snapshot).file_name() != Some(OsStr::new(&snapshot)) => {
+            return Err(ResponseError::from_msg(
+                format!("Invalid snapshot `{snapshot}`: it must be the name of a file of the snapshot directory."),
+                Code::InvalidRestoreSnapshot,
+            ))
+        }
+        Some(snapshot) => snapshot,
+        None => {
+            let db_name = opt.db_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
+            format!("{db_name}.snapshot")
+        }
+    };
+
+    let task = KindWithContent::IndexRestore {
+        index_uid: index_uid.into_inner(),
+        snapshot: Some(snapshot),
+        dump_uid: None,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
+
+    debug!(returns = ?task, "Restore index from snapshot");
+    Ok(HttpResponse::Accepted().json(task))
+}
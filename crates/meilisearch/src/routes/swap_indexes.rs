@@ -4,14 +4,16 @@ use deserr::actix_web::AwebJson;
 use deserr::Deserr;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
-use meilisearch_types::error::deserr_codes::InvalidSwapIndexes;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::deserr_codes::{InvalidSwapExecuteAt, InvalidSwapIndexes};
+use meilisearch_types::error::{ParseOffsetDateTimeError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::parse_future_date;
 use meilisearch_types::tasks::{IndexSwap, KindWithContent};
 use serde::Serialize;
+use time::OffsetDateTime;
 use utoipa::{OpenApi, ToSchema};
 
-use super::{get_task_id, is_dry_run, SummarizedTaskView};
+use super::{get_depends_on, get_task_id, get_task_labels, is_dry_run, SummarizedTaskView};
 use crate::analytics::{Aggregate, Analytics};
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
@@ -33,6 +35,11 @@ pub struct SwapIndexesPayload {
     /// Array of the two indexUids to be swapped
     #[deserr(error = DeserrJsonError<InvalidSwapIndexes>, missing_field_error = DeserrJsonError::missing_swap_indexes)]
     indexes: Vec<IndexUid>,
+    /// The `RFC 3339` date before which the swap must not be executed.
+    /// When several swaps of a request have a date, they are all executed at the latest one.
+    #[schema(value_type = Option<String>, example = json!("2024-08-08T22:00:00Z"))]
+    #[deserr(default, error = DeserrJsonError<InvalidSwapExecuteAt>, try_from(Option<String>) = parse_future_date -> ParseOffsetDateTimeError)]
+    execute_at: Option<OffsetDateTime>,
 }
 
 #[derive(Serialize)]
@@ -61,6 +68,7 @@ impl Aggregate for IndexSwappedAnalytics {
 /// Swap the documents, settings, and task history of two or more indexes. You can only swap indexes in pairs. However, a single request can swap as many index pairs as you wish.
 /// Swapping indexes is an atomic transaction: either all indexes are successfully swapped, or none are.
 /// Swapping indexA and indexB will also replace every mention of indexA by indexB and vice-versa in the task history. enqueued tasks are left unmodified.
+/// A swap can be scheduled at a given date with `executeAt`, the tasks of the swapped indexes enqueued after it wait for the swap to be executed.
 #[utoipa::path(
     post,
     path = "",
@@ -99,7 +107,8 @@ pub async fn swap_indexes(
     let filters = index_scheduler.filters();
 
     let mut swaps = vec![];
-    for SwapIndexesPayload { indexes } in params.into_iter() {
+    let mut execute_at = None;
+    for SwapIndexesPayload { indexes, execute_at: swap_execute_at } in params.into_iter() {
         // TODO: switch to deserr
         let (lhs, rhs) = match indexes.as_slice() {
             [lhs, rhs] => (lhs, rhs),
@@ -111,14 +120,18 @@ pub async fn swap_indexes(
             return Err(AuthenticationError::InvalidToken.into());
         }
         swaps.push(IndexSwap { indexes: (lhs.to_string(), rhs.to_string()) });
+        execute_at = execute_at.max(swap_execute_at);
     }
 
-    let task = KindWithContent::IndexSwap { swaps };
+    let task = KindWithContent::IndexSwap { swaps, execute_at };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task, uid, depends_on, labels, dry_run)
+    })
+    .await??
+    .into();
     Ok(HttpResponse::Accepted().json(task))
 }
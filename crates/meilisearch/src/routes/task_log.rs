@@ -0,0 +1,330 @@
+use std::io::{self, ErrorKind, Write};
+
+use actix_web::web::{self, Bytes, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use futures::StreamExt;
+use index_scheduler::{IndexScheduler, TaskLogReplay};
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::tasks::TaskId;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::analytics::Analytics;
+use crate::error::{MeilisearchHttpError, PayloadError};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::payload::Payload;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::SummarizedTaskView;
+
+/// The number of bytes of the archive buffered before being sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(ship_task_log, trim_task_log, replay_task_log),
+    tags((
+        name = "Task log",
+        description = "The `/task-log` routes ship the succeeded tasks that changed the indexes, with the documents they sent, as an archive that can be replayed on another instance.
+It is the building block of the replication of an instance or of its synchronization with another region.
+They require launching Meilisearch with the `--experimental-task-log` option, and only the master key can access them.",
+    )),
+)]
+pub struct TaskLogApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(ship_task_log)))
+            .route(web::delete().to(SeqHandler(trim_task_log))),
+    )
+    .service(web::resource("/replay").route(web::post().to(SeqHandler(replay_task_log))));
+}
+
+crate::empty_analytics!(TaskLogShippedAnalytics, "Task Log Shipped");
+crate::empty_analytics!(TaskLogTrimmedAnalytics, "Task Log Trimmed");
+crate::empty_analytics!(TaskLogReplayedAnalytics, "Task Log Replayed");
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct ShipTaskLogQuery {
+    /// Only ship the tasks registered after the task with this uid.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskLogSince>)]
+    #[param(required = false, value_type = Option<u32>, example = 147)]
+    pub since: Option<Param<TaskId>>,
+    /// The maximum number of tasks to ship, all of them by default.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskLogLimit>)]
+    #[param(required = false, value_type = Option<u32>, example = 1000)]
+    pub limit: Option<Param<u32>>,
+}
+
+/// Ship the task log
+///
+/// Get the succeeded tasks that changed the indexes, oldest first, as a `.tar.gz` archive that can be replayed
+/// on another instance with the `/task-log/replay` route. The archive contains a `log.ndjson` file with one task
+/// per line, and the documents sent by the document additions in a `payloads` directory.
+///
+/// Only the tasks processed before the first task that is still enqueued are shipped, so that the uid of the last
+/// shipped task can be given as `since` to ship the log again from it without missing any task.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Task log",
+    security(("Bearer" = [])),
+    params(ShipTaskLogQuery),
+    responses(
+        (status = 200, description = "The task log is shipped", content_type = "application/gzip"),
+        (status = 400, description = "The task log is not enabled", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Shipping and replaying the task log requires launching Meilisearch with the `--experimental-task-log` option.",
+                "code": "feature_not_enabled",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn ship_task_log(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    params: AwebQueryParameter<ShipTaskLogQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let ShipTaskLogQuery { since, limit } = params.into_inner();
+    analytics.publish(TaskLogShippedAnalytics::default(), &req);
+
+    let entries = index_scheduler.task_log_entries(since.map(|p| p.0), limit.map(|p| p.0))?;
+    debug!(returns = ?entries.len(), "Ship task log");
+
+    let scheduler = index_scheduler.clone();
+    let (sender, receiver) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkSender { chunk: Vec::new(), sender: sender.clone() };
+        match scheduler.write_task_log_archive(&entries, &mut writer) {
+            // the client is gone if the last chunk can't be sent
+            Ok(()) => {
+                let _ = writer.flush();
+            }
+            Err(e) => {
+                let _ = sender.blocking_send(Err(e.into()));
+            }
+        }
+    });
+
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+    Ok(HttpResponse::Ok().content_type("application/gzip").streaming(stream))
+}
+
+/// Sends the bytes written to it to the client, as soon as `CHUNK_SIZE` bytes are ready.
+struct ChunkSender {
+    chunk: Vec<u8>,
+    sender: mpsc::Sender<Result<Bytes, ResponseError>>,
+}
+
+impl Write for ChunkSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.chunk.extend_from_slice(buf);
+        if self.chunk.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.chunk.is_empty() {
+            return Ok(());
+        }
+        self.sender
+            .blocking_send(Ok(Bytes::from(std::mem::take(&mut self.chunk))))
+            .map_err(|_| io::Error::new(ErrorKind::BrokenPipe, "the client is gone"))
+    }
+}
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct TrimTaskLogQuery {
+    /// Delete the tasks up to the task with this uid included, all of them by default.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskLogUntil>)]
+    #[param(required = false, value_type = Option<u32>, example = 147)]
+    pub until: Option<Param<TaskId>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrimmedTaskLogView {
+    /// The number of tasks deleted from the task log.
+    pub deleted_tasks: u64,
+}
+
+/// Trim the task log
+///
+/// Delete the tasks of the task log once they were shipped, with the documents they sent.
+/// The tasks of the task queue are left untouched.
+#[utoipa::path(
+    delete,
+    path = "",
+    tag = "Task log",
+    security(("Bearer" = [])),
+    params(TrimTaskLogQuery),
+    responses(
+        (status = 200, description = "The task log is trimmed", body = TrimmedTaskLogView, content_type = "application/json", example = json!(
+            {
+                "deletedTasks": 12
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn trim_task_log(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    params: AwebQueryParameter<TrimTaskLogQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let until = params.into_inner().until.map(|until| until.0);
+    analytics.publish(TaskLogTrimmedAnalytics::default(), &req);
+
+    let scheduler = index_scheduler.clone();
+    let deleted_tasks =
+        tokio::task::spawn_blocking(move || scheduler.trim_task_log(until)).await??;
+
+    let trimmed = TrimmedTaskLogView { deleted_tasks };
+    debug!(returns = ?trimmed, "Trim task log");
+    Ok(HttpResponse::Ok().json(trimmed))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLogReplayView {
+    /// The tasks registered to replay the task log, in its order.
+    pub tasks: Vec<SummarizedTaskView>,
+    /// The uids of the tasks skipped because the instance already registered them.
+    #[schema(value_type = Vec<u32>)]
+    pub skipped_tasks: Vec<TaskId>,
+    /// The uid of the last task of the archive, to give as `since` when shipping the task log next.
+    #[schema(value_type = Option<u32>)]
+    pub last_task_uid: Option<TaskId>,
+}
+
+impl From<TaskLogReplay> for TaskLogReplayView {
+    fn from(replay: TaskLogReplay) -> Self {
+        TaskLogReplayView {
+            tasks: replay.tasks.into_iter().map(SummarizedTaskView::from).collect(),
+            skipped_tasks: replay.skipped,
+            last_task_uid: replay.last_uid,
+        }
+    }
+}
+
+/// Replay a task log
+///
+/// Enqueue the tasks of an archive shipped by the `/task-log` route of another instance, with the uid they had on it.
+/// The archive is sent as the body of the request, which is limited by the `--http-payload-size-limit` option.
+///
+/// Nothing is enqueued if one of the tasks can't be replayed, like the indexes restored from a backup, or if the
+/// instance registered other tasks than the ones of the log with the same uid. The tasks the instance already
+/// registered are skipped and listed in `skippedTasks`, so that an archive can safely be replayed twice.
+#[utoipa::path(
+    post,
+    path = "/replay",
+    tag = "Task log",
+    security(("Bearer" = [])),
+    responses(
+        (status = 202, description = "The tasks of the task log are enqueued", body = TaskLogReplayView, content_type = "application/json", example = json!(
+            {
+                "tasks": [
+                    {
+                        "taskUid": 147,
+                        "indexUid": "movies",
+                        "status": "enqueued",
+                        "type": "documentAdditionOrUpdate",
+                        "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+                    }
+                ],
+                "skippedTasks": [],
+                "lastTaskUid": 147
+            }
+        )),
+        (status = 400, description = "The task log can't be replayed", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Could not replay the task log: task `148` of type `dumpImport` cannot be replayed, the indexes must be copied from a snapshot instead.",
+                "code": "task_log_replay_failed",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#task_log_replay_failed"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn replay_task_log(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    analytics.publish(TaskLogReplayedAnalytics::default(), &req);
+
+    let archive = copy_archive_to_file(body).await?;
+    let scheduler = index_scheduler.clone();
+    let replay: TaskLogReplayView =
+        tokio::task::spawn_blocking(move || scheduler.replay_task_log(archive.path()))
+            .await??
+            .into();
+
+    debug!(returns = ?replay, "Replay task log");
+    Ok(HttpResponse::Accepted().json(replay))
+}
+
+async fn copy_archive_to_file(
+    mut body: Payload,
+) -> Result<tempfile::NamedTempFile, MeilisearchHttpError> {
+    let receive_error = |e: io::Error| PayloadError::ReceivePayload(Box::new(e));
+    let archive = tempfile::NamedTempFile::new().map_err(receive_error)?;
+    let output = archive.reopen().map_err(receive_error)?;
+    let mut file = tokio::io::BufWriter::new(tokio::fs::File::from_std(output));
+    let mut written = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        written += chunk.len();
+        file.write_all(&chunk).await.map_err(receive_error)?;
+    }
+    file.flush().await.map_err(receive_error)?;
+    if written == 0 {
+        return Err(MeilisearchHttpError::MissingTaskLogArchive);
+    }
+    Ok(archive)
+}
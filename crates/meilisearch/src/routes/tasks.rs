@@ -9,11 +9,11 @@ use meilisearch_types::batches::BatchId;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
+use meilisearch_types::error::{InvalidTaskDateError, InvalidTaskDurationError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
 use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status};
+use meilisearch_types::tasks::{parse_duration, Kind, KindWithContent, Status, TaskLabel};
 use serde::Serialize;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
@@ -22,7 +22,10 @@ use tokio::io::AsyncReadExt;
 use tokio::task;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 
-use super::{get_task_id, is_dry_run, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use super::{
+    get_depends_on, get_task_id, get_task_labels, is_dry_run, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
+};
 use crate::analytics::{Aggregate, AggregateMethod, Analytics};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
@@ -31,7 +34,7 @@ use crate::{aggregate_methods, Opt};
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(get_tasks, delete_tasks, cancel_tasks, get_task),
+    paths(get_tasks, delete_tasks, cancel_tasks, pause_scheduler, resume_scheduler, get_task),
     tags((
         name = "Tasks",
         description = "The tasks route gives information about the progress of the [asynchronous operations](https://docs.meilisearch.com/learn/advanced/asynchronous_operations.html).",
@@ -47,6 +50,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
+    .service(web::resource("/pause").route(web::post().to(SeqHandler(pause_scheduler))))
+    .service(web::resource("/resume").route(web::post().to(SeqHandler(resume_scheduler))))
     .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))))
     .service(
         web::resource("/{task_id}/documents")
@@ -84,6 +89,22 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskCanceledBy>)]
     #[param(required = false, value_type = Option<Vec<u32>>, example = json!([374, "*"]))]
     pub canceled_by: OptionStarOrList<u32>,
+    /// Permits to filter tasks by the `key=value` labels attached to them when they were registered. It's possible to specify several labels by separating them with the `,` character, a task matches if it carries any of them.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskLabels>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["source=catalog-sync", "*"]))]
+    pub labels: OptionStarOrList<TaskLabel>,
+    /// Permits to filter the failed tasks by the code of their error, e.g. `invalid_document_fields`. It's possible to specify several codes by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskErrorCodes>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["invalid_document_fields", "*"]))]
+    pub error_codes: OptionStarOrList<String>,
+    /// Permits to filter tasks based on their duration. Matches the finished tasks that took at least the given duration. Supports the ISO 8601 duration format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskMinDuration>, try_from(OptionStarOr<String>) = deserialize_task_duration -> InvalidTaskDurationError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["PT10M", "*"]))]
+    pub min_duration: OptionStarOr<Duration>,
+    /// Permits to filter tasks based on their duration. Matches the finished tasks that took at most the given duration. Supports the ISO 8601 duration format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskMaxDuration>, try_from(OptionStarOr<String>) = deserialize_task_duration -> InvalidTaskDurationError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["PT1S", "*"]))]
+    pub max_duration: OptionStarOr<Duration>,
     /// Permits to filter tasks by their related type. By default, when `types` query parameter is not set, all task types are returned. It's possible to specify several types by separating them with the `,` character.
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskTypes>)]
     #[param(required = false, value_type = Option<Vec<String>>, example = json!([Kind::DocumentAdditionOrUpdate, "*"]))]
@@ -135,6 +156,10 @@ impl TasksFilterQuery {
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
             canceled_by: self.canceled_by.merge_star_and_none(),
+            labels: self.labels.merge_star_and_none(),
+            error_codes: self.error_codes.merge_star_and_none(),
+            min_duration: self.min_duration.merge_star_and_none(),
+            max_duration: self.max_duration.merge_star_and_none(),
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
             after_enqueued_at: self.after_enqueued_at.merge_star_and_none(),
             before_started_at: self.before_started_at.merge_star_and_none(),
@@ -153,6 +178,7 @@ impl TaskDeletionOrCancelationQuery {
                 uids: OptionStarOrList::None,
                 batch_uids: OptionStarOrList::None,
                 canceled_by: OptionStarOrList::None,
+                labels: OptionStarOrList::None,
                 types: OptionStarOrList::None,
                 statuses: OptionStarOrList::None,
                 index_uids: OptionStarOrList::None,
@@ -183,6 +209,10 @@ pub struct TaskDeletionOrCancelationQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskCanceledBy>)]
     #[param(required = false, value_type = Option<Vec<u32>>, example = json!([374, "*"]))]
     pub canceled_by: OptionStarOrList<u32>,
+    /// Permits to filter tasks by the `key=value` labels attached to them when they were registered. It's possible to specify several labels by separating them with the `,` character, a task matches if it carries any of them.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskLabels>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["source=catalog-sync", "*"]))]
+    pub labels: OptionStarOrList<TaskLabel>,
     /// Permits to filter tasks by their related type. By default, when `types` query parameter is not set, all task types are returned. It's possible to specify several types by separating them with the `,` character.
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskTypes>)]
     #[param(required = false, value_type = Option<Vec<Kind>>, example = json!([Kind::DocumentDeletion, "*"]))]
@@ -234,6 +264,10 @@ impl TaskDeletionOrCancelationQuery {
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
             canceled_by: self.canceled_by.merge_star_and_none(),
+            labels: self.labels.merge_star_and_none(),
+            error_codes: None,
+            min_duration: None,
+            max_duration: None,
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
             after_enqueued_at: self.after_enqueued_at.merge_star_and_none(),
             before_started_at: self.before_started_at.merge_star_and_none(),
@@ -256,6 +290,7 @@ struct TaskFilterAnalytics<Method: AggregateMethod> {
     filtered_by_type: bool,
     filtered_by_status: bool,
     filtered_by_canceled_by: bool,
+    filtered_by_labels: bool,
     filtered_by_before_enqueued_at: bool,
     filtered_by_after_enqueued_at: bool,
     filtered_by_before_started_at: bool,
@@ -279,6 +314,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
             filtered_by_type: self.filtered_by_type | new.filtered_by_type,
             filtered_by_status: self.filtered_by_status | new.filtered_by_status,
             filtered_by_canceled_by: self.filtered_by_canceled_by | new.filtered_by_canceled_by,
+            filtered_by_labels: self.filtered_by_labels | new.filtered_by_labels,
             filtered_by_before_enqueued_at: self.filtered_by_before_enqueued_at
                 | new.filtered_by_before_enqueued_at,
             filtered_by_after_enqueued_at: self.filtered_by_after_enqueued_at
@@ -322,7 +358,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `labels`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -366,6 +402,7 @@ async fn cancel_tasks(
             filtered_by_type: params.types.is_some(),
             filtered_by_status: params.statuses.is_some(),
             filtered_by_canceled_by: params.canceled_by.is_some(),
+            filtered_by_labels: params.labels.is_some(),
             filtered_by_before_enqueued_at: params.before_enqueued_at.is_some(),
             filtered_by_after_enqueued_at: params.after_enqueued_at.is_some(),
             filtered_by_before_started_at: params.before_started_at.is_some(),
@@ -387,9 +424,12 @@ async fn cancel_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task =
-        task::spawn_blocking(move || index_scheduler.register(task_cancelation, uid, dry_run))
-            .await??;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task_cancelation, uid, depends_on, labels, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
@@ -416,7 +456,7 @@ async fn cancel_tasks(
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `labels`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -460,6 +500,7 @@ async fn delete_tasks(
             filtered_by_type: params.types.is_some(),
             filtered_by_status: params.statuses.is_some(),
             filtered_by_canceled_by: params.canceled_by.is_some(),
+            filtered_by_labels: params.labels.is_some(),
             filtered_by_before_enqueued_at: params.before_enqueued_at.is_some(),
             filtered_by_after_enqueued_at: params.after_enqueued_at.is_some(),
             filtered_by_before_started_at: params.before_started_at.is_some(),
@@ -481,13 +522,102 @@ async fn delete_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task = task::spawn_blocking(move || index_scheduler.register(task_deletion, uid, dry_run))
-        .await??;
+    let depends_on = get_depends_on(&req)?;
+    let labels = get_task_labels(&req)?;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_metadata(task_deletion, uid, depends_on, labels, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SchedulerState {
+    /// Whether the scheduler is paused and doesn't start processing new batches.
+    paused: bool,
+}
+
+crate::empty_analytics!(SchedulerPausedAnalytics, "Scheduler Paused");
+
+/// Pause the scheduler
+///
+/// Stop processing new batches of tasks. The batch being processed, if any, is left to finish.
+/// The tasks can still be enqueued while the scheduler is paused.
+/// Only the master key can access this route.
+#[utoipa::path(
+    post,
+    path = "/pause",
+    tag = "Tasks",
+    security(("Bearer" = [])),
+    responses(
+        (status = 200, description = "The scheduler is paused", body = SchedulerState, content_type = "application/json", example = json!(
+            {
+                "paused": true
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn pause_scheduler(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features().check_writes_allowed()?;
+    index_scheduler.pause();
+    analytics.publish(SchedulerPausedAnalytics::default(), &req);
+
+    Ok(HttpResponse::Ok().json(SchedulerState { paused: index_scheduler.is_paused() }))
+}
+
+crate::empty_analytics!(SchedulerResumedAnalytics, "Scheduler Resumed");
+
+/// Resume the scheduler
+///
+/// Start processing the batches of tasks again after the scheduler has been paused.
+/// Only the master key can access this route.
+#[utoipa::path(
+    post,
+    path = "/resume",
+    tag = "Tasks",
+    security(("Bearer" = [])),
+    responses(
+        (status = 200, description = "The scheduler is resumed", body = SchedulerState, content_type = "application/json", example = json!(
+            {
+                "paused": false
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn resume_scheduler(
+    index_scheduler: GuardedData<MasterPolicy, Data<IndexScheduler>>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features().check_writes_allowed()?;
+    index_scheduler.resume();
+    analytics.publish(SchedulerResumedAnalytics::default(), &req);
+
+    Ok(HttpResponse::Ok().json(SchedulerState { paused: index_scheduler.is_paused() }))
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AllTasks {
     /// The list of tasks that matched the filter.
@@ -639,7 +769,10 @@ async fn get_task(
     let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(&query, filters)?;
 
     if let Some(task) = tasks.first() {
-        let task_view = TaskView::from_task(task);
+        let mut task_view = TaskView::from_task(task);
+        if task.status == Status::Processing {
+            task_view.progress = index_scheduler.task_progress(task.uid);
+        }
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
@@ -695,6 +828,9 @@ async fn get_task_documents_file(
 
     if let Some(task) = tasks.first() {
         match task.content_uuid() {
+            Some(_) if matches!(task.kind, KindWithContent::DumpImport { .. }) => {
+                Err(index_scheduler::Error::TaskFileNotFound(task_uid).into())
+            }
             Some(uuid) => {
                 let mut tfile = match index_scheduler.queue.update_file(uuid) {
                     Ok(file) => tokio::fs::File::from_std(file),
@@ -760,3 +896,9 @@ pub fn deserialize_date_before(
 ) -> std::result::Result<OptionStarOr<OffsetDateTime>, InvalidTaskDateError> {
     value.try_map(|x| deserialize_date(&x, DeserializeDateOption::Before))
 }
+
+pub fn deserialize_task_duration(
+    value: OptionStarOr<String>,
+) -> std::result::Result<OptionStarOr<Duration>, InvalidTaskDurationError> {
+    value.try_map(|x| parse_duration(&x).ok_or(InvalidTaskDurationError(x)))
+}
@@ -19,8 +19,9 @@ use tokio::task::JoinHandle;
 
 use super::super::ranking_rules::{self, RankingRules};
 use super::super::{
-    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, HitMaker,
-    HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
+    compute_facet_distribution_stats, prepare_search, record_embedder_usage, AttributesFormat,
+    ComputedFacets, HitMaker, HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQ,
+    SearchQuery, SearchQueryWithIndex,
 };
 use super::proxy::{proxy_search, ProxySearchError, ProxySearchParams};
 use super::types::{
@@ -465,7 +466,7 @@ impl PartitionedQueries {
         }
 
         if let Some(facets) = federated_query.has_facets() {
-            let facets = facets.to_owned();
+            let facets = facets.iter().map(|facet| facet.name().to_owned()).collect();
             return Err(MeilisearchHttpError::FacetsInFederatedQuery(
                 query_index,
                 federated_query.index_uid.into_inner(),
@@ -474,6 +475,10 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.q.as_ref().and_then(SearchQ::alternatives).is_some() {
+            return Err(MeilisearchHttpError::AlternativesInFederatedQuery(query_index).into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -736,7 +741,9 @@ impl SearchByIndex {
                     (SearchKind::SemanticOnly { .. }, _) => {
                         ranking_rules::CanonicalizationKind::Vector
                     }
-                    (_, Some(q)) if !q.is_empty() => ranking_rules::CanonicalizationKind::Keyword,
+                    (_, Some(q)) if !q.as_str().is_empty() => {
+                        ranking_rules::CanonicalizationKind::Keyword
+                    }
                     _ => ranking_rules::CanonicalizationKind::Placeholder,
                 };
 
@@ -800,21 +807,30 @@ impl SearchByIndex {
                     None => TimeBudget::default(),
                 };
 
-                let (mut search, _is_finite_pagination, _max_total_hits, _offset) = prepare_search(
-                    &index,
-                    &rtxn,
-                    &query,
-                    &search_kind,
-                    time_budget,
-                    params.features,
+                let embedder_name = search_kind.embedder_name().map(str::to_owned);
+                let (result, _semantic_hit_count) = record_embedder_usage(
+                    params.index_scheduler,
+                    &index_uid,
+                    embedder_name.as_deref(),
+                    || {
+                        let (mut search, _is_finite_pagination, _max_total_hits, _offset) =
+                            prepare_search(
+                                &index,
+                                &rtxn,
+                                &query,
+                                &search_kind,
+                                time_budget,
+                                params.features,
+                            )?;
+
+                        search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
+                        search.offset(0);
+                        search.limit(params.required_hit_count);
+
+                        super::super::search_from_kind(index_uid.to_string(), search_kind, search)
+                            .map_err(ResponseError::from)
+                    },
                 )?;
-
-                search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
-                search.offset(0);
-                search.limit(params.required_hit_count);
-
-                let (result, _semantic_hit_count) =
-                    super::super::search_from_kind(index_uid.to_string(), search_kind, search)?;
                 let format = AttributesFormat {
                     attributes_to_retrieve: query.attributes_to_retrieve,
                     retrieve_vectors,
@@ -822,9 +838,12 @@ impl SearchByIndex {
                     attributes_to_crop: query.attributes_to_crop,
                     crop_length: query.crop_length,
                     crop_marker: query.crop_marker,
+                    crop_strategy: query.crop_strategy,
                     highlight_pre_tag: query.highlight_pre_tag,
                     highlight_post_tag: query.highlight_post_tag,
                     show_matches_position: query.show_matches_position,
+                    show_grapheme_offsets: query.show_grapheme_offsets,
+                    show_matched_terms: query.show_matched_terms,
                     sort: query.sort,
                     show_ranking_score: query.show_ranking_score,
                     show_ranking_score_details: query.show_ranking_score_details,
@@ -838,6 +857,7 @@ impl SearchByIndex {
                     document_scores,
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
+                    timings: _,
                 } = result;
 
                 candidates |= query_candidates;
@@ -920,6 +940,7 @@ impl SearchByIndex {
             .map(|facets_by_index| {
                 compute_facet_distribution_stats(
                     &facets_by_index,
+                    &Default::default(),
                     &index,
                     &rtxn,
                     candidates,
@@ -985,6 +1006,7 @@ impl SearchByIndex {
             if let Some(facets) = facets {
                 if let Err(mut error) = compute_facet_distribution_stats(
                     &facets,
+                    &Default::default(),
                     &index,
                     &rtxn,
                     Default::default(),
@@ -1,5 +1,5 @@
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::vec::Vec;
 
@@ -139,6 +139,24 @@ pub struct FederatedSearchResult {
     pub used_negative_operator: bool,
 }
 
+impl FederatedSearchResult {
+    /// Only keeps the attributes allowed by the search rules of a tenant token in the hits of
+    /// each index, see [`SearchHit::restrict_attributes`].
+    pub fn restrict_attributes(&mut self, allowed_attributes: &BTreeMap<String, BTreeSet<String>>) {
+        for hit in &mut self.hits {
+            let Some(federation) = hit.document.remove(FEDERATION_HIT) else { continue };
+            let allowed = federation
+                .get(INDEX_UID)
+                .and_then(|index_uid| index_uid.as_str())
+                .and_then(|index_uid| allowed_attributes.get(index_uid));
+            if let Some(allowed) = allowed {
+                hit.restrict_attributes(allowed);
+            }
+            hit.document.insert(FEDERATION_HIT.to_string(), federation);
+        }
+    }
+}
+
 impl fmt::Debug for FederatedSearchResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FederatedSearchResult {
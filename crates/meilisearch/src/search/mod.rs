@@ -1,21 +1,23 @@
 use core::fmt;
-use std::cmp::min;
+use std::cmp::{min, Ordering};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
 use meilisearch_auth::IndexSearchRules;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::error::{Code, ErrorCode, ResponseError};
+use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::constants::RESERVED_GEO_FIELD_NAME;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
@@ -26,8 +28,9 @@ use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    distinct_single_docid, AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index,
+    LocalizedAttributesRule, MatchBounds, MatchedTerm, MatcherBuilder, SortError,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -36,15 +39,19 @@ use serde_json::{json, Value};
 mod mod_test;
 use utoipa::ToSchema;
 
+use self::federated::weighted_scores;
+use self::rerank::RerankStage;
 use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::AuthenticationError;
 
 mod federated;
 pub use federated::{
     perform_federated_search, FederatedSearch, FederatedSearchResult, Federation,
-    FederationOptions, MergeFacets, PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
+    FederationOptions, MergeFacets, Weight, PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
 };
 
 mod ranking_rules;
+mod rerank;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
@@ -55,12 +62,14 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+/// The name of the distance to the `_geoPoint` of the sort added to the hits.
+const GEO_DISTANCE_FIELD_NAME: &str = "_geoDistance";
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
-    pub q: Option<String>,
+    pub q: Option<SearchQ>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
@@ -80,14 +89,20 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
     pub retrieve_vectors: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToCrop>)]
-    pub attributes_to_crop: Option<Vec<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub attributes_to_crop: Option<Vec<AttributeToCrop>>,
     #[deserr(error = DeserrJsonError<InvalidSearchCropLength>, default = DEFAULT_CROP_LENGTH())]
     #[schema(default = DEFAULT_CROP_LENGTH)]
     pub crop_length: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToHighlight>)]
-    pub attributes_to_highlight: Option<HashSet<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub attributes_to_highlight: Option<HashSet<AttributeToHighlight>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>)]
     pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowGraphemeOffsets>)]
+    pub show_grapheme_offsets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchedTerms>)]
+    pub show_matched_terms: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScore>)]
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>)]
@@ -99,7 +114,8 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub facets: Option<Vec<SearchFacet>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -109,6 +125,8 @@ pub struct SearchQuery {
     #[deserr(error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     #[schema(default = DEFAULT_CROP_MARKER)]
     pub crop_marker: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropStrategy>)]
+    pub crop_strategy: CropStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>)]
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
@@ -117,6 +135,20 @@ pub struct SearchQuery {
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchIgnoreTerms>)]
+    pub ignore_terms: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDebugTimings>)]
+    pub debug_timings: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExhaustiveTotalHits>)]
+    pub exhaustive_total_hits: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExhaustiveFacetCount>)]
+    pub exhaustive_facet_count: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoGridPrecision>)]
+    pub geo_grid_precision: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSensitiveMatching>)]
+    pub sensitive_matching: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerankBudget>)]
+    pub rerank_budget: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
@@ -173,6 +205,8 @@ impl fmt::Debug for SearchQuery {
             crop_length,
             attributes_to_highlight,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             show_ranking_score,
             show_ranking_score_details,
             filter,
@@ -182,10 +216,18 @@ impl fmt::Debug for SearchQuery {
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            crop_strategy,
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            debug_timings,
+            exhaustive_total_hits,
+            exhaustive_facet_count,
+            geo_grid_precision,
+            sensitive_matching,
+            rerank_budget,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -198,6 +240,15 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if *exhaustive_total_hits {
+            debug.field("exhaustive_total_hits", &exhaustive_total_hits);
+        }
+        if *exhaustive_facet_count {
+            debug.field("exhaustive_facet_count", &exhaustive_facet_count);
+        }
+        if let Some(geo_grid_precision) = geo_grid_precision {
+            debug.field("geo_grid_precision", &geo_grid_precision);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -241,6 +292,12 @@ impl fmt::Debug for SearchQuery {
         if *show_matches_position {
             debug.field("show_matches_position", show_matches_position);
         }
+        if *show_grapheme_offsets {
+            debug.field("show_grapheme_offsets", show_grapheme_offsets);
+        }
+        if *show_matched_terms {
+            debug.field("show_matched_terms", show_matched_terms);
+        }
         if *show_ranking_score {
             debug.field("show_ranking_score", show_ranking_score);
         }
@@ -263,6 +320,7 @@ impl fmt::Debug for SearchQuery {
         debug.field("highlight_pre_tag", &highlight_pre_tag);
         debug.field("highlight_post_tag", &highlight_post_tag);
         debug.field("crop_marker", &crop_marker);
+        debug.field("crop_strategy", &crop_strategy);
         if let Some(ranking_score_threshold) = ranking_score_threshold {
             debug.field("ranking_score_threshold", &ranking_score_threshold);
         }
@@ -271,6 +329,22 @@ impl fmt::Debug for SearchQuery {
             debug.field("locales", &locales);
         }
 
+        if let Some(ignore_terms) = ignore_terms {
+            debug.field("ignore_terms", &ignore_terms);
+        }
+
+        if let Some(sensitive_matching) = sensitive_matching {
+            debug.field("sensitive_matching", &sensitive_matching);
+        }
+
+        if let Some(rerank_budget) = rerank_budget {
+            debug.field("rerank_budget", &rerank_budget);
+        }
+
+        if *debug_timings {
+            debug.field("debug_timings", &debug_timings);
+        }
+
         debug.finish()
     }
 }
@@ -285,13 +359,54 @@ pub struct HybridQuery {
     pub semantic_ratio: SemanticRatio,
     #[deserr(error = DeserrJsonError<InvalidSearchEmbedder>)]
     pub embedder: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHybridFusion>)]
+    #[serde(default)]
+    pub fusion: HybridFusion,
+    /// Scales the number of candidates inspected by the vector store, trading recall for speed below `1.0`
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAccuracy>)]
+    #[schema(value_type = Option<f32>)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_accuracy: Option<SearchAccuracy>,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum HybridFusion {
+    /// Compare the keyword and semantic scores, weighted by the `semanticRatio`
+    #[default]
+    Linear,
+    /// Reciprocal rank fusion: sum the ranks of the documents in both searches, weighted by the `semanticRatio`
+    ReciprocalRank,
+    /// Min-max normalize the scores of both searches, then sum them weighted by the `semanticRatio`
+    NormalizedSum,
+}
+
+impl From<HybridFusion> for milli::HybridFusion {
+    fn from(other: HybridFusion) -> Self {
+        match other {
+            HybridFusion::Linear => Self::Linear,
+            HybridFusion::ReciprocalRank => Self::ReciprocalRank,
+            HybridFusion::NormalizedSum => Self::NormalizedSum,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
-    SemanticOnly { embedder_name: String, embedder: Arc<Embedder>, quantized: bool },
-    Hybrid { embedder_name: String, embedder: Arc<Embedder>, quantized: bool, semantic_ratio: f32 },
+    SemanticOnly {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantized: bool,
+    },
+    Hybrid {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantized: bool,
+        semantic_ratio: f32,
+        fusion: HybridFusion,
+    },
 }
 
 impl SearchKind {
@@ -319,6 +434,7 @@ impl SearchKind {
         index: &Index,
         embedder_name: &str,
         semantic_ratio: f32,
+        fusion: HybridFusion,
         vector_len: Option<usize>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder, quantized) = Self::embedder(
@@ -329,7 +445,7 @@ impl SearchKind {
             vector_len,
             Route::Search,
         )?;
-        Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio })
+        Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio, fusion })
     }
 
     pub(crate) fn embedder(
@@ -370,6 +486,28 @@ impl SearchKind {
 
         Ok((embedder_name.to_owned(), embedder, quantized))
     }
+
+    pub(crate) fn embedder_name(&self) -> Option<&str> {
+        match self {
+            SearchKind::KeywordOnly => None,
+            SearchKind::SemanticOnly { embedder_name, .. }
+            | SearchKind::Hybrid { embedder_name, .. } => Some(embedder_name),
+        }
+    }
+}
+
+/// Runs `search` and records the requests it sent to the embedder in the stats of the index.
+pub fn record_embedder_usage<T>(
+    index_scheduler: &index_scheduler::IndexScheduler,
+    index_uid: &str,
+    embedder_name: Option<&str>,
+    search: impl FnOnce() -> T,
+) -> T {
+    let (ret, usage) = milli::vector::usage::measure_search_usage(search);
+    if let Some(embedder_name) = embedder_name {
+        index_scheduler.record_search_embedder_usage(index_uid, embedder_name, usage);
+    }
+    ret
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr, Serialize)]
@@ -404,10 +542,145 @@ impl std::ops::Deref for SemanticRatio {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, Serialize)]
+#[deserr(try_from(f32) = TryFrom::try_from -> InvalidSearchSearchAccuracy)]
+pub struct SearchAccuracy(f32);
+
+impl std::convert::TryFrom<f32> for SearchAccuracy {
+    type Error = InvalidSearchSearchAccuracy;
+
+    fn try_from(f: f32) -> Result<Self, Self::Error> {
+        if f > 0.0 && f <= 10.0 {
+            Ok(SearchAccuracy(f))
+        } else {
+            Err(InvalidSearchSearchAccuracy)
+        }
+    }
+}
+
+impl std::ops::Deref for SearchAccuracy {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The `q` search parameter: either a single query or several weighted phrasings of the same query.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum SearchQ {
+    Query(String),
+    /// The candidates of every alternative are merged, ranked by their weighted ranking score.
+    ///
+    /// Federated searches only use the first alternative, the queries already having weights.
+    Alternatives(Vec<QueryAlternative>),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserr, Serialize, ToSchema)]
+#[deserr(error = DeserrJsonError<InvalidSearchQ>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct QueryAlternative {
+    pub q: String,
+    #[deserr(default)]
+    #[schema(value_type = f64, default = 1.0)]
+    pub weight: Weight,
+}
+
+impl SearchQ {
+    /// The query, or its first phrasing when there are alternatives.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SearchQ::Query(q) => q,
+            SearchQ::Alternatives(alternatives) => {
+                alternatives.first().map_or("", |alternative| &alternative.q)
+            }
+        }
+    }
+
+    pub fn alternatives(&self) -> Option<&[QueryAlternative]> {
+        match self {
+            SearchQ::Query(_) => None,
+            SearchQ::Alternatives(alternatives) => Some(alternatives),
+        }
+    }
+}
+
+impl From<String> for SearchQ {
+    fn from(q: String) -> Self {
+        SearchQ::Query(q)
+    }
+}
+
+impl Deserr<DeserrJsonError<InvalidSearchQ>> for SearchQ {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> Result<Self, DeserrJsonError<InvalidSearchQ>> {
+        match value {
+            deserr::Value::String(q) => Ok(SearchQ::Query(q)),
+            deserr::Value::Sequence(_) => {
+                let alternatives =
+                    Vec::<QueryAlternative>::deserialize_from_value(value, location)?;
+                if alternatives.is_empty() {
+                    return Err(deserr::take_cf_content(DeserrJsonError::error::<V>(
+                        None,
+                        deserr::ErrorKind::Unexpected {
+                            msg: "expected at least one alternative phrasing of the query"
+                                .to_string(),
+                        },
+                        location,
+                    )));
+                }
+                Ok(SearchQ::Alternatives(alternatives))
+            }
+            _ => Err(deserr::take_cf_content(DeserrJsonError::error::<V>(
+                None,
+                deserr::ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
 impl SearchQuery {
     pub fn is_finite_pagination(&self) -> bool {
         self.page.or(self.hits_per_page).is_some()
     }
+
+    /// The attributes the query filters, sorts, facets, searches on or deduplicates with.
+    pub fn used_attributes(&self) -> Vec<String> {
+        used_attributes(
+            self.filter.as_ref(),
+            self.sort.as_deref(),
+            self.distinct.as_deref(),
+            self.facets.as_deref(),
+            self.attributes_to_search_on.as_deref(),
+        )
+    }
+
+    /// The offset and limit of the requested hits, depending on the pagination mode.
+    fn offset_and_limit(&self, max_total_hits: usize) -> (usize, usize) {
+        let (offset, limit) = if self.is_finite_pagination() {
+            let limit = self.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
+            let page = self.page.unwrap_or(1);
+
+            // page 0 gives a limit of 0 forcing Meilisearch to return no document.
+            page.checked_sub(1).map_or((0, 0), |p| (limit * p, limit))
+        } else {
+            (self.offset, self.limit)
+        };
+
+        // Make sure that a user can't get more documents than the hard limit,
+        // we align that on the offset too.
+        let offset = min(offset, max_total_hits);
+        let limit = min(limit, max_total_hits.saturating_sub(offset));
+        (offset, limit)
+    }
 }
 
 /// A `SearchQuery` + an index UID and optional FederationOptions.
@@ -422,7 +695,7 @@ pub struct SearchQueryWithIndex {
     #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
     pub index_uid: IndexUid,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
-    pub q: Option<String>,
+    pub q: Option<SearchQ>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
@@ -440,17 +713,23 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
     pub retrieve_vectors: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToCrop>)]
-    pub attributes_to_crop: Option<Vec<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub attributes_to_crop: Option<Vec<AttributeToCrop>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropLength>, default = DEFAULT_CROP_LENGTH())]
     pub crop_length: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToHighlight>)]
-    pub attributes_to_highlight: Option<HashSet<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub attributes_to_highlight: Option<HashSet<AttributeToHighlight>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScore>, default)]
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowGraphemeOffsets>, default)]
+    pub show_grapheme_offsets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchedTerms>, default)]
+    pub show_matched_terms: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
@@ -458,13 +737,16 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub facets: Option<Vec<SearchFacet>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
     pub highlight_post_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     pub crop_marker: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropStrategy>, default)]
+    pub crop_strategy: CropStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
@@ -473,6 +755,20 @@ pub struct SearchQueryWithIndex {
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchIgnoreTerms>, default)]
+    pub ignore_terms: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDebugTimings>, default)]
+    pub debug_timings: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExhaustiveTotalHits>, default)]
+    pub exhaustive_total_hits: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExhaustiveFacetCount>, default)]
+    pub exhaustive_facet_count: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoGridPrecision>, default)]
+    pub geo_grid_precision: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSensitiveMatching>, default)]
+    pub sensitive_matching: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerankBudget>, default)]
+    pub rerank_budget: Option<usize>,
 
     #[deserr(default)]
     pub federation_options: Option<FederationOptions>,
@@ -493,10 +789,21 @@ impl SearchQueryWithIndex {
         }
     }
 
-    pub fn has_facets(&self) -> Option<&[String]> {
+    pub fn has_facets(&self) -> Option<&[SearchFacet]> {
         self.facets.as_deref().filter(|v| !v.is_empty())
     }
 
+    /// The attributes the query filters, sorts, facets, searches on or deduplicates with.
+    pub fn used_attributes(&self) -> Vec<String> {
+        used_attributes(
+            self.filter.as_ref(),
+            self.sort.as_deref(),
+            self.distinct.as_deref(),
+            self.facets.as_deref(),
+            self.attributes_to_search_on.as_deref(),
+        )
+    }
+
     pub fn from_index_query_federation(
         index_uid: IndexUid,
         query: SearchQuery,
@@ -516,6 +823,8 @@ impl SearchQueryWithIndex {
             crop_length,
             attributes_to_highlight,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             show_ranking_score,
             show_ranking_score_details,
             filter,
@@ -525,10 +834,18 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            crop_strategy,
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            debug_timings,
+            exhaustive_total_hits,
+            exhaustive_facet_count,
+            geo_grid_precision,
+            sensitive_matching,
+            rerank_budget,
         } = query;
 
         SearchQueryWithIndex {
@@ -548,6 +865,8 @@ impl SearchQueryWithIndex {
             show_ranking_score,
             show_ranking_score_details,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             filter,
             sort,
             distinct,
@@ -555,10 +874,18 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            crop_strategy,
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            debug_timings,
+            exhaustive_total_hits,
+            exhaustive_facet_count,
+            geo_grid_precision,
+            sensitive_matching,
+            rerank_budget,
             federation_options,
         }
     }
@@ -581,6 +908,8 @@ impl SearchQueryWithIndex {
             show_ranking_score,
             show_ranking_score_details,
             show_matches_position,
+            show_grapheme_offsets,
+            show_matched_terms,
             filter,
             sort,
             distinct,
@@ -588,11 +917,19 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            crop_strategy,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            debug_timings,
+            exhaustive_total_hits,
+            exhaustive_facet_count,
+            geo_grid_precision,
+            sensitive_matching,
+            rerank_budget,
         } = self;
         (
             index_uid,
@@ -611,6 +948,8 @@ impl SearchQueryWithIndex {
                 show_ranking_score,
                 show_ranking_score_details,
                 show_matches_position,
+                show_grapheme_offsets,
+                show_matched_terms,
                 filter,
                 sort,
                 distinct,
@@ -618,11 +957,19 @@ impl SearchQueryWithIndex {
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
+                crop_strategy,
                 matching_strategy,
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
                 locales,
+                ignore_terms,
+                debug_timings,
+                exhaustive_total_hits,
+                exhaustive_facet_count,
+                geo_grid_precision,
+                sensitive_matching,
+                rerank_budget,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -643,6 +990,8 @@ pub struct SimilarQuery {
     pub limit: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSimilarFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarQ>)]
+    pub q: Option<String>,
     #[deserr(error = DeserrJsonError<InvalidSimilarEmbedder>)]
     pub embedder: String,
     #[deserr(default, error = DeserrJsonError<InvalidSimilarAttributesToRetrieve>)]
@@ -658,6 +1007,13 @@ pub struct SimilarQuery {
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
 }
 
+impl SimilarQuery {
+    /// The attributes the query filters on.
+    pub fn used_attributes(&self) -> Vec<String> {
+        used_attributes(self.filter.as_ref(), None, None, None, None)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExternalDocumentId(String);
 
@@ -693,14 +1049,275 @@ impl TryFrom<Value> for ExternalDocumentId {
 #[deserr(rename_all = camelCase)]
 #[serde(rename_all = "camelCase")]
 pub enum MatchingStrategy {
-    /// Remove query words from last to first
+    /// Remove query words from last to first, quoted phrases are never removed
     Last,
     /// All query words are mandatory
     All,
-    /// Remove query words from the most frequent to the least
+    /// Remove query words from the most frequent to the least, quoted phrases are never removed
     Frequency,
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum CropStrategy {
+    /// Crop exactly `cropLength` words around the best matches
+    #[default]
+    Words,
+    /// Extend the crop to the boundaries of the sentences around the best matches
+    Sentence,
+}
+
+/// How the `facetQuery` of a facet search matches the facet values.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum FacetQueryMatching {
+    /// Match the values starting with the query, with typos if the typo tolerance allows them
+    #[default]
+    Fuzzy,
+    /// Match the values starting with the query, without typos
+    Prefix,
+    /// Only match the values equal to the query
+    Exact,
+}
+
+impl From<FacetQueryMatching> for milli::FacetQueryMatching {
+    fn from(other: FacetQueryMatching) -> Self {
+        match other {
+            FacetQueryMatching::Fuzzy => Self::Fuzzy,
+            FacetQueryMatching::Prefix => Self::Prefix,
+            FacetQueryMatching::Exact => Self::Exact,
+        }
+    }
+}
+
+impl From<CropStrategy> for milli::CropStrategy {
+    fn from(other: CropStrategy) -> Self {
+        match other {
+            CropStrategy::Words => Self::Words,
+            CropStrategy::Sentence => Self::Sentence,
+        }
+    }
+}
+
+/// An attribute to format, either by its name or with its own formatting options.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum AttributeToFormat<O> {
+    Name(String),
+    WithOptions(O),
+}
+
+pub type AttributeToHighlight = AttributeToFormat<HighlightOptions>;
+pub type AttributeToCrop = AttributeToFormat<CropOptions>;
+
+impl<O, E> Deserr<E> for AttributeToFormat<O>
+where
+    O: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(name) => Ok(AttributeToFormat::Name(name)),
+            deserr::Value::Map(_) => {
+                O::deserialize_from_value(value, location).map(AttributeToFormat::WithOptions)
+            }
+            _ => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                deserr::ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+/// A facet to compute the distribution of, either by its name or with its own options.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SearchFacet {
+    Name(String),
+    WithOptions(FacetOptions),
+}
+
+impl SearchFacet {
+    pub fn name(&self) -> &str {
+        match self {
+            SearchFacet::Name(name) => name,
+            SearchFacet::WithOptions(FacetOptions { name, .. }) => name,
+        }
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        match self {
+            SearchFacet::Name(_) => None,
+            SearchFacet::WithOptions(FacetOptions { limit, .. }) => *limit,
+        }
+    }
+
+    pub fn sort_by(&self) -> Option<FacetValuesSort> {
+        match self {
+            SearchFacet::Name(_) => None,
+            SearchFacet::WithOptions(FacetOptions { sort_by, .. }) => *sort_by,
+        }
+    }
+
+    pub fn ranges(&self) -> Option<&[FacetRange]> {
+        match self {
+            SearchFacet::Name(_) => None,
+            SearchFacet::WithOptions(FacetOptions { ranges, .. }) => ranges.as_deref(),
+        }
+    }
+
+    pub fn hierarchy(&self) -> Option<&FacetHierarchy> {
+        match self {
+            SearchFacet::Name(_) => None,
+            SearchFacet::WithOptions(FacetOptions { hierarchy, .. }) => hierarchy.as_ref(),
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for SearchFacet
+where
+    FacetOptions: Deserr<E>,
+{
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(name) => Ok(SearchFacet::Name(name)),
+            deserr::Value::Map(_) => {
+                FacetOptions::deserialize_from_value(value, location).map(SearchFacet::WithOptions)
+            }
+            _ => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                deserr::ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+/// The options of a facet, overriding the `faceting` settings of the index for this query.
+#[derive(Debug, Clone, PartialEq, Deserr, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacets>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetOptions {
+    pub name: String,
+    /// Overrides `faceting.maxValuesPerFacet`.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Overrides `faceting.sortFacetValuesBy`.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<FacetValuesSort>,
+    /// Counts the documents by numeric ranges of values instead of by value.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranges: Option<Vec<FacetRange>>,
+    /// Counts the documents by level of a hierarchy of paths instead of by value.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hierarchy: Option<FacetHierarchy>,
+}
+
+/// A range of numeric facet values, `from` is inclusive and `to` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacets>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_range -> DeserrJsonError<InvalidSearchFacets>)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetRange {
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+}
+
+fn validate_facet_range<E: DeserializeError>(
+    range: FacetRange,
+    location: deserr::ValuePointerRef,
+) -> Result<FacetRange, E> {
+    match (range.from, range.to) {
+        (Some(from), Some(to)) if from >= to => {
+            Err(deserr::take_cf_content(E::error::<std::convert::Infallible>(
+                None,
+                deserr::ErrorKind::Unexpected {
+                    msg: format!(
+                        "`from` should be lower than `to`, but found `from: {from}` and `to: {to}`"
+                    ),
+                },
+                location,
+            )))
+        }
+        _ => Ok(range),
+    }
+}
+
+impl From<FacetRange> for milli::FacetRange {
+    fn from(FacetRange { from, to }: FacetRange) -> Self {
+        milli::FacetRange { from, to }
+    }
+}
+
+/// The hierarchy of a facet whose values are paths like `Electronics > Phones > Android`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacets>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetHierarchy {
+    /// The separator between the levels of the paths, e.g. ` > `.
+    pub separator: String,
+    /// The path to return the children of, the root level is returned when missing.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl From<FacetHierarchy> for milli::FacetHierarchy {
+    fn from(FacetHierarchy { separator, path }: FacetHierarchy) -> Self {
+        milli::FacetHierarchy { separator, path }
+    }
+}
+
+/// The highlight tags of an attribute, overriding `highlightPreTag` and `highlightPostTag`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserr, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchAttributesToHighlight>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightOptions {
+    pub attribute: String,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_pre_tag: Option<String>,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_post_tag: Option<String>,
+}
+
+/// The crop options of an attribute, overriding `cropLength` and `cropMarker`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserr, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchAttributesToCrop>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CropOptions {
+    pub attribute: String,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crop_length: Option<usize>,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crop_marker: Option<String>,
+}
+
 impl Default for MatchingStrategy {
     fn default() -> Self {
         Self::Last
@@ -747,12 +1364,43 @@ pub struct SearchHit {
     pub formatted: Document,
     #[serde(default, rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
     pub matches_position: Option<MatchesPosition>,
+    #[serde(default, rename = "_matchedTerms", skip_serializing_if = "Option::is_none")]
+    pub matched_terms: Option<Vec<MatchedTerm>>,
     #[serde(default, rename = "_rankingScore", skip_serializing_if = "Option::is_none")]
     pub ranking_score: Option<f64>,
     #[serde(default, rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl SearchHit {
+    /// Only keeps the attributes allowed by the search rules of a tenant token, `*` allowing all
+    /// of them.
+    ///
+    /// The matched terms can't be related to the attributes they were found in, so they are
+    /// only kept when all the attributes are allowed.
+    pub fn restrict_attributes(&mut self, allowed: &BTreeSet<String>) {
+        if allowed.contains("*") {
+            return;
+        }
+
+        let is_allowed =
+            |attribute: &str| allowed.iter().any(|name| milli::is_faceted_by(attribute, name));
+        let allowed_names = || allowed.iter().map(String::as_str);
+        self.document = permissive_json_pointer::select_values(
+            &self.document,
+            allowed_names().chain([GEO_DISTANCE_FIELD_NAME]),
+        );
+        if !self.formatted.is_empty() {
+            self.formatted =
+                permissive_json_pointer::select_values(&self.formatted, allowed_names());
+        }
+        if let Some(matches_position) = &mut self.matches_position {
+            matches_position.retain(|attribute, _| is_allowed(attribute));
+        }
+        self.matched_terms = None;
+    }
+}
+
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[schema(rename_all = "camelCase")]
@@ -767,10 +1415,15 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_grid: Option<BTreeMap<String, u64>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<SearchTimings>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -787,7 +1440,9 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            geo_grid,
             semantic_hit_count,
+            timings,
             degraded,
             used_negative_operator,
         } = self;
@@ -810,14 +1465,63 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(geo_grid) = geo_grid {
+            debug.field("geo_grid", &format!("[{} cells]", geo_grid.len()));
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(timings) = timings {
+            debug.field("timings", &timings);
+        }
 
         debug.finish()
     }
 }
 
+/// Time spent in each step of a search, in milliseconds.
+///
+/// Only returned when `debugTimings` is set in the search query.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SearchTimings {
+    /// Building the tokenizer and extracting the query terms.
+    pub tokenization_ms: f64,
+    /// Evaluating the filter and resolving the query to find the candidates.
+    pub candidates_ms: f64,
+    /// Time spent in each ranking rule, in the order they are applied.
+    #[schema(value_type = BTreeMap<String, f64>)]
+    pub ranking_rules_ms: IndexMap<String, f64>,
+    /// Ranking the documents by similarity to the query vector.
+    ///
+    /// With a hybrid search, this also includes embedding the query.
+    pub vector_search_ms: f64,
+    /// Retrieving, highlighting and cropping the returned documents.
+    pub formatting_ms: f64,
+}
+
+impl SearchTimings {
+    fn new(timings: milli::SearchTimings, formatting: Duration) -> Self {
+        let milli::SearchTimings { tokenization, candidates, ranking_rules, vector_search } =
+            timings;
+        Self {
+            tokenization_ms: duration_ms(tokenization),
+            candidates_ms: duration_ms(candidates),
+            ranking_rules_ms: ranking_rules
+                .into_iter()
+                .map(|(id, duration)| (id, duration_ms(duration)))
+                .collect(),
+            vector_search_ms: duration_ms(vector_search),
+            formatting_ms: duration_ms(formatting),
+        }
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SimilarResult {
@@ -848,6 +1552,7 @@ pub enum HitsInfo {
     OffsetLimit { limit: usize, offset: usize, estimated_total_hits: usize },
 }
 
+/// The bounds of the values of a facet, the RFC 3339 dates are reported as Unix timestamps.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
 pub struct FacetStats {
     pub min: f64,
@@ -863,6 +1568,51 @@ pub struct FacetSearchResult {
 }
 
 /// Incorporate search rules in search query
+/// The filters, sorts and facets that can't be parsed are ignored, the search reports them.
+fn used_attributes(
+    filter: Option<&Value>,
+    sort: Option<&[String]>,
+    distinct: Option<&str>,
+    facets: Option<&[SearchFacet]>,
+    attributes_to_search_on: Option<&[String]>,
+) -> Vec<String> {
+    let mut attributes = Vec::new();
+    if let Some(Ok(Some(filter))) = filter.map(Filter::from_json) {
+        attributes.extend(filter.attributes().into_iter().map(String::from));
+    }
+    for sort in sort.into_iter().flatten() {
+        match AscDesc::from_str(sort).map(|sort| sort.member().clone()) {
+            Ok(milli::Member::Field(field)) => attributes.push(field),
+            Ok(milli::Member::Geo(_)) => attributes.push(RESERVED_GEO_FIELD_NAME.to_string()),
+            Err(_) => (),
+        }
+    }
+    attributes.extend(distinct.map(String::from));
+    attributes.extend(facets.into_iter().flatten().map(|facet| facet.name().to_string()));
+    attributes.extend(attributes_to_search_on.into_iter().flatten().cloned());
+    attributes
+}
+
+/// Returns an error if one of the attributes can't be retrieved with the search rules of the
+/// tenant token, as filtering, sorting, faceting or searching on it would reveal its values.
+pub fn check_allowed_attributes(
+    allowed: &BTreeSet<String>,
+    attributes: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<(), AuthenticationError> {
+    if allowed.contains("*") {
+        return Ok(());
+    }
+    for attribute in attributes {
+        let attribute = attribute.as_ref();
+        if !allowed.iter().any(|name| milli::is_faceted_by(attribute, name)) {
+            return Err(AuthenticationError::TenantTokenAccessingUnauthorizedAttribute(
+                attribute.to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     *filter = match (filter.take(), rules.filter) {
         (None, rules_filter) => rules_filter,
@@ -903,7 +1653,7 @@ fn prepare_search<'t>(
     match search_kind {
         SearchKind::KeywordOnly => {
             if let Some(q) = &query.q {
-                search.query(q);
+                search.query(q.as_str());
             }
         }
         SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
@@ -916,7 +1666,7 @@ fn prepare_search<'t>(
                     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
 
                     embedder
-                        .embed_search(query.q.as_ref().unwrap(), Some(deadline))
+                        .embed_search(query.q.as_ref().unwrap().as_str(), Some(deadline))
                         .map_err(milli::vector::Error::from)
                         .map_err(milli::Error::from)?
                 }
@@ -924,9 +1674,9 @@ fn prepare_search<'t>(
 
             search.semantic(embedder_name.clone(), embedder.clone(), *quantized, Some(vector));
         }
-        SearchKind::Hybrid { embedder_name, embedder, quantized, semantic_ratio: _ } => {
+        SearchKind::Hybrid { embedder_name, embedder, quantized, semantic_ratio: _, fusion: _ } => {
             if let Some(q) = &query.q {
-                search.query(q);
+                search.query(q.as_str());
             }
             // will be embedded in hybrid search if necessary
             search.semantic(
@@ -951,7 +1701,9 @@ fn prepare_search<'t>(
         .map(|x| x as usize)
         .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
 
-    search.exhaustive_number_hits(is_finite_pagination);
+    search.exhaustive_number_hits(
+        is_finite_pagination || query.exhaustive_total_hits || query.exhaustive_facet_count,
+    );
     search.scoring_strategy(
         if query.show_ranking_score
             || query.show_ranking_score_details
@@ -963,22 +1715,7 @@ fn prepare_search<'t>(
         },
     );
 
-    // compute the offset on the limit depending on the pagination mode.
-    let (offset, limit) = if is_finite_pagination {
-        let limit = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
-        let page = query.page.unwrap_or(1);
-
-        // page 0 gives a limit of 0 forcing Meilisearch to return no document.
-        page.checked_sub(1).map_or((0, 0), |p| (limit * p, limit))
-    } else {
-        (query.offset, query.limit)
-    };
-
-    // Make sure that a user can't get more documents than the hard limit,
-    // we align that on the offset too.
-    let offset = min(offset, max_total_hits);
-    let limit = min(limit, max_total_hits.saturating_sub(offset));
-
+    let (offset, limit) = query.offset_and_limit(max_total_hits);
     search.offset(offset);
     search.limit(limit);
 
@@ -1003,6 +1740,18 @@ fn prepare_search<'t>(
         search.locales(locales.iter().copied().map(Into::into).collect());
     }
 
+    if let Some(ref ignore_terms) = query.ignore_terms {
+        search.ignore_terms(ignore_terms.clone());
+    }
+
+    if let Some(sensitive_matching) = query.sensitive_matching {
+        search.sensitive_matching(sensitive_matching);
+    }
+
+    if let Some(search_accuracy) = query.hybrid.as_ref().and_then(|hybrid| hybrid.search_accuracy) {
+        search.search_accuracy(*search_accuracy);
+    }
+
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
@@ -1017,24 +1766,51 @@ pub fn perform_search(
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
     let time_budget = match index.search_cutoff(&rtxn)? {
+        // A degraded search doesn't know all of its candidates, the facet counts would be approximated.
+        _ if query.exhaustive_facet_count && query.facets.is_some() => TimeBudget::max(),
         Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+    let ((milli_result, semantic_hit_count), is_finite_pagination, max_total_hits, offset) =
+        match query.q.as_ref().and_then(SearchQ::alternatives) {
+            Some(alternatives) => search_alternatives(
+                index_uid,
+                index,
+                &rtxn,
+                &query,
+                alternatives,
+                search_kind,
+                time_budget,
+                features,
+            )?,
+            None => {
+                let (mut search, is_finite_pagination, max_total_hits, offset) =
+                    prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+                let rerank = RerankStage::new(index, &rtxn, &query, max_total_hits)?;
+                if let Some(rerank) = &rerank {
+                    // the page is cut after reranking
+                    search.offset(0);
+                    search.limit(rerank.window);
+                }
+                let (mut result, semantic_hit_count) =
+                    search_from_kind(index_uid, search_kind, search)?;
+                if let Some(rerank) = rerank {
+                    rerank.apply(index, &rtxn, &mut result)?;
+                }
+                ((result, semantic_hit_count), is_finite_pagination, max_total_hits, offset)
+            }
+        };
 
-    let (
-        milli::SearchResult {
-            documents_ids,
-            matching_words,
-            candidates,
-            document_scores,
-            degraded,
-            used_negative_operator,
-        },
-        semantic_hit_count,
-    ) = search_from_kind(index_uid, search_kind, search)?;
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        candidates,
+        document_scores,
+        degraded,
+        used_negative_operator,
+        timings,
+    } = milli_result;
 
     let SearchQuery {
         q,
@@ -1048,6 +1824,8 @@ pub fn perform_search(
         crop_length,
         attributes_to_highlight,
         show_matches_position,
+        show_grapheme_offsets,
+        show_matched_terms,
         show_ranking_score,
         show_ranking_score_details,
         sort,
@@ -1055,8 +1833,13 @@ pub fn perform_search(
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
+        crop_strategy,
         locales,
+        debug_timings,
+        exhaustive_total_hits,
+        geo_grid_precision,
         // already used in prepare_search
+        exhaustive_facet_count: _,
         vector: _,
         hybrid: _,
         offset: _,
@@ -1065,6 +1848,9 @@ pub fn perform_search(
         attributes_to_search_on: _,
         filter: _,
         distinct: _,
+        ignore_terms: _,
+        sensitive_matching: _,
+        rerank_budget: _,
     } = query;
 
     let format = AttributesFormat {
@@ -1074,15 +1860,19 @@ pub fn perform_search(
         attributes_to_crop,
         crop_length,
         crop_marker,
+        crop_strategy,
         highlight_pre_tag,
         highlight_post_tag,
         show_matches_position,
+        show_grapheme_offsets,
+        show_matched_terms,
         sort,
         show_ranking_score,
         show_ranking_score_details,
         locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
     };
 
+    let before_formatting = Instant::now();
     let documents = make_hits(
         index,
         &rtxn,
@@ -1090,8 +1880,11 @@ pub fn perform_search(
         matching_words,
         documents_ids.iter().copied().zip(document_scores.iter()),
     )?;
+    let timings = debug_timings.then(|| SearchTimings::new(timings, before_formatting.elapsed()));
 
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
+    // The exact number of hits is not capped by `maxTotalHits`, only the reachable pages are.
+    let total_hits = if exhaustive_total_hits { candidates.len() as usize } else { number_of_hits };
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
         // If hit_per_page is 0, then pages can't be computed and so we respond 0.
@@ -1099,19 +1892,26 @@ pub fn perform_search(
             .checked_div(hits_per_page)
             .unwrap_or(0);
 
-        HitsInfo::Pagination {
-            hits_per_page,
-            page: page.unwrap_or(1),
-            total_pages,
-            total_hits: number_of_hits,
-        }
+        HitsInfo::Pagination { hits_per_page, page: page.unwrap_or(1), total_pages, total_hits }
     } else {
-        HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
+        HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: total_hits }
     };
 
+    let geo_grid = geo_grid_precision
+        .map(|precision| compute_geo_grid(index, &rtxn, precision, candidates.clone()))
+        .transpose()?;
+
     let (facet_distribution, facet_stats) = facets
         .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
+            let names: Vec<_> = facets.iter().map(SearchFacet::name).collect();
+            compute_facet_distribution_stats(
+                &names,
+                &FacetOverrides::new(&facets),
+                index,
+                &rtxn,
+                candidates,
+                Route::Search,
+            )
         })
         .transpose()?
         .map(|ComputedFacets { distribution, stats }| (distribution, stats))
@@ -1120,17 +1920,193 @@ pub fn perform_search(
     let result = SearchResult {
         hits: documents,
         hits_info,
-        query: q.unwrap_or_default(),
+        query: q.map(|q| q.as_str().to_string()).unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        geo_grid,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        timings,
     };
     Ok(result)
 }
 
+/// Counts the candidates per geohash cell of the requested precision.
+fn compute_geo_grid(
+    index: &Index,
+    rtxn: &RoTxn,
+    precision: usize,
+    candidates: roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, u64>, ResponseError> {
+    if !(1..=milli::MAX_GEO_GRID_PRECISION).contains(&precision) {
+        return Err(ResponseError::from_msg(
+            format!(
+                "Invalid value in parameter `geoGridPrecision`: the precision must be between 1 and {}, but `{precision}` was provided.",
+                milli::MAX_GEO_GRID_PRECISION
+            ),
+            Code::InvalidSearchGeoGridPrecision,
+        ));
+    }
+    if !index.is_geo_enabled(rtxn)? {
+        return Err(ResponseError::from_msg(
+            format!(
+                "Invalid value in parameter `geoGridPrecision`: the `{RESERVED_GEO_FIELD_NAME}` field must be filterable or sortable to compute a geo grid."
+            ),
+            Code::InvalidSearchGeoGridPrecision,
+        ));
+    }
+
+    let mut geo_grid = milli::GeoGrid::new(rtxn, index, precision);
+    geo_grid.candidates(candidates);
+    Ok(geo_grid.execute()?)
+}
+
+/// Searches every alternative phrasing of the query and merges their hits.
+///
+/// The candidates of the alternatives are unioned and the hits are ranked like the hits of a
+/// federated search, by their ranking score multiplied by the weight of their alternative and by
+/// the values of the sort. A document found by several alternatives is ranked by its best
+/// weighted score, and highlighted with the words of the first alternative. The `distinct`
+/// attribute is applied again to the merged hits, which can come from different alternatives.
+#[allow(clippy::too_many_arguments)]
+fn search_alternatives(
+    index_uid: String,
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &SearchQuery,
+    alternatives: &[QueryAlternative],
+    search_kind: SearchKind,
+    time_budget: TimeBudget,
+    features: RoFeatures,
+) -> Result<((milli::SearchResult, Option<u32>), bool, usize, usize), ResponseError> {
+    let is_finite_pagination = query.is_finite_pagination();
+    let mut max_total_hits = DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+    let mut offset = 0;
+    let mut limit = 0;
+    let mut merged: Option<milli::SearchResult> = None;
+    let mut semantic_hit_count = None;
+    let mut hits_by_alternative = Vec::new();
+
+    for (alternative_index, alternative) in alternatives.iter().enumerate() {
+        let alternative_query =
+            SearchQuery { q: Some(SearchQ::Query(alternative.q.clone())), ..query.clone() };
+        let (mut search, _, alternative_max_total_hits, _) = prepare_search(
+            index,
+            rtxn,
+            &alternative_query,
+            &search_kind,
+            time_budget.clone(),
+            features,
+        )?;
+        max_total_hits = alternative_max_total_hits;
+        (offset, limit) = query.offset_and_limit(max_total_hits);
+
+        // every alternative must return enough hits to fill the requested page once merged
+        search.offset(0);
+        search.limit(offset + limit);
+        search.scoring_strategy(ScoringStrategy::Detailed);
+
+        let (result, alternative_semantic_hit_count) =
+            search_from_kind(index_uid.clone(), search_kind.clone(), search)?;
+        semantic_hit_count = semantic_hit_count.max(alternative_semantic_hit_count);
+
+        let milli::SearchResult {
+            matching_words,
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded,
+            used_negative_operator,
+            timings,
+        } = result;
+
+        let weight = *alternative.weight;
+        hits_by_alternative.push(
+            documents_ids
+                .into_iter()
+                .zip(document_scores)
+                .map(move |(docid, scores)| (alternative_index, docid, weight, scores)),
+        );
+
+        match &mut merged {
+            Some(merged) => {
+                merged.candidates |= candidates;
+                merged.degraded |= degraded;
+                merged.used_negative_operator |= used_negative_operator;
+            }
+            None => {
+                merged = Some(milli::SearchResult {
+                    matching_words,
+                    candidates,
+                    documents_ids: Vec::new(),
+                    document_scores: Vec::new(),
+                    degraded,
+                    used_negative_operator,
+                    timings,
+                })
+            }
+        }
+    }
+
+    let hits = itertools::kmerge_by(
+        hits_by_alternative,
+        |(left_index, _, left_weight, left): &(usize, u32, f64, Vec<ScoreDetails>),
+         (right_index, _, right_weight, right): &(usize, u32, f64, Vec<ScoreDetails>)| {
+            match weighted_scores::compare(
+                ScoreDetails::weighted_score_values(left.iter(), *left_weight),
+                ScoreDetails::global_score(left.iter()) * *left_weight,
+                ScoreDetails::weighted_score_values(right.iter(), *right_weight),
+                ScoreDetails::global_score(right.iter()) * *right_weight,
+            ) {
+                // the biggest score goes first
+                Ordering::Greater => true,
+                // on equal scores the hits of the first alternatives come first
+                Ordering::Equal => left_index < right_index,
+                Ordering::Less => false,
+            }
+        },
+    );
+
+    let distinct = match &query.distinct {
+        Some(distinct) => Some(distinct.as_str()),
+        None => index.distinct_field(rtxn)?,
+    };
+    let distinct_fid = match distinct {
+        Some(distinct) => index.fields_ids_map(rtxn)?.id(distinct),
+        None => None,
+    };
+    let mut seen = HashSet::new();
+    let mut excluded = roaring::RoaringBitmap::new();
+    let mut distinct_hits = Vec::new();
+    for (_, docid, _, scores) in hits {
+        if !seen.insert(docid) || excluded.contains(docid) {
+            continue;
+        }
+        if let Some(distinct_fid) = distinct_fid {
+            distinct_single_docid(index, rtxn, distinct_fid, docid, &mut excluded)?;
+        }
+        distinct_hits.push((docid, scores));
+    }
+    let (documents_ids, document_scores) =
+        distinct_hits.into_iter().skip(offset).take(limit).unzip();
+
+    let mut result = merged.unwrap_or_else(|| milli::SearchResult {
+        matching_words: Default::default(),
+        candidates: Default::default(),
+        documents_ids: Vec::new(),
+        document_scores: Vec::new(),
+        degraded: false,
+        used_negative_operator: false,
+        timings: Default::default(),
+    });
+    result.documents_ids = documents_ids;
+    result.document_scores = document_scores;
+
+    Ok(((result, semantic_hit_count), is_finite_pagination, max_total_hits, offset))
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ComputedFacets {
     #[schema(value_type = BTreeMap<String, BTreeMap<String, u64>>)]
@@ -1144,8 +2120,40 @@ pub enum Route {
     Similar,
 }
 
+/// The options of the requested facets overriding the `faceting` settings of the index.
+#[derive(Default)]
+struct FacetOverrides<'a> {
+    max_values: BTreeMap<&'a str, usize>,
+    sort_by: BTreeMap<&'a str, OrderBy>,
+    ranges: BTreeMap<&'a str, Vec<milli::FacetRange>>,
+    hierarchies: BTreeMap<&'a str, milli::FacetHierarchy>,
+}
+
+impl<'a> FacetOverrides<'a> {
+    fn new(facets: &'a [SearchFacet]) -> Self {
+        let mut overrides = Self::default();
+        for facet in facets {
+            if let Some(limit) = facet.limit() {
+                overrides.max_values.insert(facet.name(), limit);
+            }
+            if let Some(sort_by) = facet.sort_by() {
+                overrides.sort_by.insert(facet.name(), sort_by.into());
+            }
+            if let Some(ranges) = facet.ranges() {
+                let ranges = ranges.iter().copied().map(Into::into).collect();
+                overrides.ranges.insert(facet.name(), ranges);
+            }
+            if let Some(hierarchy) = facet.hierarchy() {
+                overrides.hierarchies.insert(facet.name(), hierarchy.clone().into());
+            }
+        }
+        overrides
+    }
+}
+
 fn compute_facet_distribution_stats<S: AsRef<str>>(
     facets: &[S],
+    overrides: &FacetOverrides<'_>,
     index: &Index,
     rtxn: &RoTxn,
     candidates: roaring::RoaringBitmap,
@@ -1153,15 +2161,22 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
 ) -> Result<ComputedFacets, ResponseError> {
     let mut facet_distribution = index.facets_distribution(rtxn);
 
-    let max_values_by_facet = index
+    let max_values_per_facet = index
         .max_values_per_facet(rtxn)
         .map_err(milli::Error::from)?
         .map(|x| x as usize)
         .unwrap_or(DEFAULT_VALUES_PER_FACET);
 
-    facet_distribution.max_values_per_facet(max_values_by_facet);
+    facet_distribution.max_values_per_facet(max_values_per_facet);
+    facet_distribution.max_values_by_facet(overrides.max_values.iter().map(|(k, v)| (k, *v)));
+    facet_distribution.ranges_by_facet(overrides.ranges.iter().map(|(k, v)| (k, v.clone())));
+    facet_distribution
+        .hierarchies_by_facet(overrides.hierarchies.iter().map(|(k, v)| (k, v.clone())));
 
-    let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+    let mut sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+    for (name, order_by) in &overrides.sort_by {
+        sort_facet_values_by.insert(name.to_string(), *order_by);
+    }
 
     // add specific facet if there is no placeholder
     if facets.iter().all(|f| f.as_ref() != "*") {
@@ -1207,8 +2222,8 @@ pub fn search_from_kind(
             let semantic_hit_count = results.document_scores.len() as u32;
             (results, Some(semantic_hit_count))
         }
-        SearchKind::Hybrid { semantic_ratio, .. } => search
-            .execute_hybrid(*semantic_ratio)
+        SearchKind::Hybrid { semantic_ratio, fusion, .. } => search
+            .execute_hybrid(*semantic_ratio, (*fusion).into())
             .map_err(|e| MeilisearchHttpError::from_milli(e, Some(index_uid)))?,
     };
     Ok((milli_result, semantic_hit_count))
@@ -1217,13 +2232,16 @@ pub fn search_from_kind(
 struct AttributesFormat {
     attributes_to_retrieve: Option<BTreeSet<String>>,
     retrieve_vectors: RetrieveVectors,
-    attributes_to_highlight: Option<HashSet<String>>,
-    attributes_to_crop: Option<Vec<String>>,
+    attributes_to_highlight: Option<HashSet<AttributeToHighlight>>,
+    attributes_to_crop: Option<Vec<AttributeToCrop>>,
     crop_length: usize,
     crop_marker: String,
+    crop_strategy: CropStrategy,
     highlight_pre_tag: String,
     highlight_post_tag: String,
     show_matches_position: bool,
+    show_grapheme_offsets: bool,
+    show_matched_terms: bool,
     sort: Option<Vec<String>>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
@@ -1263,10 +2281,14 @@ struct HitMaker<'a> {
     embedding_configs: Vec<milli::index::IndexEmbeddingConfig>,
     formatter_builder: MatcherBuilder<'a>,
     formatted_options: BTreeMap<FieldId, FormatOptions>,
+    format_overrides: BTreeMap<FieldId, FormatOverrides>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     sort: Option<Vec<String>>,
+    retrieve_geo_distance: bool,
     show_matches_position: bool,
+    show_grapheme_offsets: bool,
+    show_matched_terms: bool,
     locales: Option<Vec<Language>>,
 }
 
@@ -1305,6 +2327,7 @@ impl<'a> HitMaker<'a> {
         mut formatter_builder: MatcherBuilder<'a>,
     ) -> milli::Result<Self> {
         formatter_builder.crop_marker(format.crop_marker);
+        formatter_builder.crop_strategy(format.crop_strategy.into());
         formatter_builder.highlight_prefix(format.highlight_pre_tag);
         formatter_builder.highlight_suffix(format.highlight_post_tag);
 
@@ -1364,9 +2387,16 @@ impl<'a> HitMaker<'a> {
             .cloned()
             .collect();
 
+        // `_geoDistance` is returned along with the `_geo` field or when explicitly requested
+        let retrieve_geo_distance = format.attributes_to_retrieve.as_ref().map_or(true, |attrs| {
+            attrs.iter().any(|attr| {
+                attr == "*" || attr == RESERVED_GEO_FIELD_NAME || attr == GEO_DISTANCE_FIELD_NAME
+            })
+        });
+
         let attr_to_highlight = format.attributes_to_highlight.unwrap_or_default();
         let attr_to_crop = format.attributes_to_crop.unwrap_or_default();
-        let formatted_options = compute_formatted_options(
+        let (formatted_options, format_overrides) = compute_formatted_options(
             &attr_to_highlight,
             &attr_to_crop,
             format.crop_length,
@@ -1388,10 +2418,14 @@ impl<'a> HitMaker<'a> {
             embedding_configs,
             formatter_builder,
             formatted_options,
+            format_overrides,
             show_ranking_score: format.show_ranking_score,
             show_ranking_score_details: format.show_ranking_score_details,
             show_matches_position: format.show_matches_position,
+            show_grapheme_offsets: format.show_grapheme_offsets,
+            show_matched_terms: format.show_matched_terms,
             sort: format.sort,
+            retrieve_geo_distance,
             locales: format.locales,
         })
     }
@@ -1450,19 +2484,25 @@ impl<'a> HitMaker<'a> {
         let localized_attributes =
             self.index.localized_attributes_rules(self.rtxn)?.unwrap_or_default();
 
-        let (matches_position, formatted) = format_fields(
+        let (matches_position, matched_terms, formatted) = format_fields(
             &displayed_document,
             &self.fields_ids_map,
             &self.formatter_builder,
             &self.formatted_options,
+            &self.format_overrides,
             self.show_matches_position,
+            self.show_grapheme_offsets,
+            self.show_matched_terms,
             &self.displayed_ids,
             self.locales.as_deref(),
             &localized_attributes,
         )?;
 
-        if let Some(sort) = self.sort.as_ref() {
-            insert_geo_distance(sort, &mut document);
+        if let Some(sort) = self.sort.as_ref().filter(|_| self.retrieve_geo_distance) {
+            // the distance is computed from the displayed `_geo` even if it isn't retrieved
+            if let Some(distance) = geo_distance(sort, &displayed_document) {
+                document.insert(GEO_DISTANCE_FIELD_NAME.to_string(), json!(distance));
+            }
         }
 
         let ranking_score =
@@ -1474,6 +2514,7 @@ impl<'a> HitMaker<'a> {
             document,
             formatted,
             matches_position,
+            matched_terms,
             ranking_score_details,
             ranking_score,
         };
@@ -1515,6 +2556,7 @@ pub fn perform_facet_search(
     search_query: SearchQuery,
     facet_query: Option<String>,
     facet_name: String,
+    facet_query_matching: FacetQueryMatching,
     search_kind: SearchKind,
     features: RoFeatures,
     locales: Option<Vec<Language>>,
@@ -1561,6 +2603,7 @@ pub fn perform_facet_search(
     if let Some(max_facets) = index.max_values_per_facet(&rtxn)? {
         facet_search.max_values(max_facets as usize);
     }
+    facet_search.matching(facet_query_matching.into());
 
     if let Some(locales) = locales {
         facet_search.locales(locales);
@@ -1590,6 +2633,7 @@ pub fn perform_similar(
         offset,
         limit,
         filter: _,
+        q,
         embedder: _,
         attributes_to_retrieve,
         retrieve_vectors: _,
@@ -1629,6 +2673,10 @@ pub fn perform_similar(
         }
     }
 
+    if let Some(ref q) = q {
+        similar.query(q);
+    }
+
     if let Some(ranking_score_threshold) = ranking_score_threshold {
         similar.ranking_score_threshold(ranking_score_threshold.0);
     }
@@ -1640,9 +2688,11 @@ pub fn perform_similar(
         document_scores,
         degraded: _,
         used_negative_operator: _,
+        timings: _,
     } = similar.execute().map_err(|err| match err {
-        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+        milli::Error::UserError(milli::UserError::InvalidFilter(..)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
+                .with_details(err.error_details())
         }
         err => err.into(),
     })?;
@@ -1654,9 +2704,12 @@ pub fn perform_similar(
         attributes_to_crop: None,
         crop_length: DEFAULT_CROP_LENGTH(),
         crop_marker: DEFAULT_CROP_MARKER(),
+        crop_strategy: CropStrategy::default(),
         highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
         highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
         show_matches_position: false,
+        show_grapheme_offsets: false,
+        show_matched_terms: false,
         sort: None,
         show_ranking_score,
         show_ranking_score_details,
@@ -1690,21 +2743,34 @@ pub fn perform_similar(
 }
 
 pub fn insert_geo_distance(sorts: &[String], document: &mut Document) {
+    if let Some(distance) = geo_distance(sorts, document) {
+        document.insert(GEO_DISTANCE_FIELD_NAME.to_string(), json!(distance));
+    }
+}
+
+/// Returns the distance in meters between the `_geoPoint` of the sort and the `_geo` of the document.
+pub fn geo_distance(sorts: &[String], document: &Document) -> Option<usize> {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
             Regex::new(r"_geoPoint\(\s*([[:digit:].\-]+)\s*,\s*([[:digit:].\-]+)\s*\)").unwrap();
     };
-    if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
-        // TODO: TAMO: milli encountered an internal error, what do we want to do?
-        let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
+    let capture_group = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort))?;
+    // TODO: TAMO: milli encountered an internal error, what do we want to do?
+    let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
+    let geo_points = match document.get(RESERVED_GEO_FIELD_NAME) {
+        Some(Value::Array(geo_points)) => geo_points.iter().collect(),
+        Some(geo_point) => vec![geo_point],
+        None => Vec::new(),
+    };
+    // the distance of a document with several geo points is the one of its closest point
+    geo_points
+        .into_iter()
+        .filter_map(|geo_point| {
             extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
-            document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
-        }
-    }
+        })
+        .map(|(lat, lng)| milli::distance_between_two_points(&base, &[lat, lng]))
+        .min_by(f64::total_cmp)
+        .map(|distance| distance.round() as usize)
 }
 
 fn extract_geo_value(value: &Value) -> Option<f64> {
@@ -1715,18 +2781,56 @@ fn extract_geo_value(value: &Value) -> Option<f64> {
     }
 }
 
+/// The highlight tags and crop marker of an attribute, overriding the ones of the query.
+#[derive(Debug, Default, Clone)]
+struct FormatOverrides {
+    highlight_pre_tag: Option<String>,
+    highlight_post_tag: Option<String>,
+    crop_marker: Option<String>,
+}
+
+impl FormatOverrides {
+    /// Merges both overrides, the ones of `self` taking precedence.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            highlight_pre_tag: self.highlight_pre_tag.or(other.highlight_pre_tag),
+            highlight_post_tag: self.highlight_post_tag.or(other.highlight_post_tag),
+            crop_marker: self.crop_marker.or(other.crop_marker),
+        }
+    }
+}
+
+/// Registers the overrides of a field, the ones given for a specific attribute
+/// taking precedence over the ones given for the `*` wildcard.
+fn add_format_overrides(
+    format_overrides: &mut BTreeMap<FieldId, FormatOverrides>,
+    id: FieldId,
+    overrides: &FormatOverrides,
+    is_wildcard: bool,
+) {
+    let entry = format_overrides.entry(id).or_default();
+    let current = std::mem::take(entry);
+    *entry = if is_wildcard {
+        current.merge(overrides.clone())
+    } else {
+        overrides.clone().merge(current)
+    };
+}
+
 fn compute_formatted_options(
-    attr_to_highlight: &HashSet<String>,
-    attr_to_crop: &[String],
+    attr_to_highlight: &HashSet<AttributeToHighlight>,
+    attr_to_crop: &[AttributeToCrop],
     query_crop_length: usize,
     to_retrieve_ids: &BTreeSet<FieldId>,
     fields_ids_map: &FieldsIdsMap,
     displayed_ids: &BTreeSet<FieldId>,
-) -> BTreeMap<FieldId, FormatOptions> {
+) -> (BTreeMap<FieldId, FormatOptions>, BTreeMap<FieldId, FormatOverrides>) {
     let mut formatted_options = BTreeMap::new();
+    let mut format_overrides = BTreeMap::new();
 
     add_highlight_to_formatted_options(
         &mut formatted_options,
+        &mut format_overrides,
         attr_to_highlight,
         fields_ids_map,
         displayed_ids,
@@ -1734,6 +2838,7 @@ fn compute_formatted_options(
 
     add_crop_to_formatted_options(
         &mut formatted_options,
+        &mut format_overrides,
         attr_to_crop,
         query_crop_length,
         fields_ids_map,
@@ -1745,28 +2850,51 @@ fn compute_formatted_options(
         add_non_formatted_ids_to_formatted_options(&mut formatted_options, to_retrieve_ids);
     }
 
-    formatted_options
+    (formatted_options, format_overrides)
 }
 
 fn add_highlight_to_formatted_options(
     formatted_options: &mut BTreeMap<FieldId, FormatOptions>,
-    attr_to_highlight: &HashSet<String>,
+    format_overrides: &mut BTreeMap<FieldId, FormatOverrides>,
+    attr_to_highlight: &HashSet<AttributeToHighlight>,
     fields_ids_map: &FieldsIdsMap,
     displayed_ids: &BTreeSet<FieldId>,
 ) {
     for attr in attr_to_highlight {
         let new_format = FormatOptions { highlight: true, crop: None };
+        let (attr, overrides) = match attr {
+            AttributeToFormat::Name(attr) => (attr.as_str(), None),
+            AttributeToFormat::WithOptions(HighlightOptions {
+                attribute,
+                highlight_pre_tag,
+                highlight_post_tag,
+            }) => {
+                let overrides = FormatOverrides {
+                    highlight_pre_tag: highlight_pre_tag.clone(),
+                    highlight_post_tag: highlight_post_tag.clone(),
+                    crop_marker: None,
+                };
+                (attribute.as_str(), Some(overrides))
+            }
+        };
 
         if attr == "*" {
             for id in displayed_ids {
                 formatted_options.insert(*id, new_format);
+                if let Some(overrides) = &overrides {
+                    add_format_overrides(format_overrides, *id, overrides, true);
+                }
             }
-            break;
+            // the other attributes may still have their own highlight tags
+            continue;
         }
 
         if let Some(id) = fields_ids_map.id(attr) {
             if displayed_ids.contains(&id) {
                 formatted_options.insert(id, new_format);
+                if let Some(overrides) = &overrides {
+                    add_format_overrides(format_overrides, id, overrides, false);
+                }
             }
         }
     }
@@ -1774,19 +2902,35 @@ fn add_highlight_to_formatted_options(
 
 fn add_crop_to_formatted_options(
     formatted_options: &mut BTreeMap<FieldId, FormatOptions>,
-    attr_to_crop: &[String],
+    format_overrides: &mut BTreeMap<FieldId, FormatOverrides>,
+    attr_to_crop: &[AttributeToCrop],
     crop_length: usize,
     fields_ids_map: &FieldsIdsMap,
     displayed_ids: &BTreeSet<FieldId>,
 ) {
     for attr in attr_to_crop {
-        let mut split = attr.rsplitn(2, ':');
-        let (attr_name, attr_len) = match split.next().zip(split.next()) {
-            Some((len, name)) => {
-                let crop_len = len.parse::<usize>().unwrap_or(crop_length);
-                (name, crop_len)
+        let (attr_name, attr_len, overrides) = match attr {
+            AttributeToFormat::Name(attr) => {
+                let mut split = attr.rsplitn(2, ':');
+                match split.next().zip(split.next()) {
+                    Some((len, name)) => {
+                        let crop_len = len.parse::<usize>().unwrap_or(crop_length);
+                        (name, crop_len, None)
+                    }
+                    None => (attr.as_str(), crop_length, None),
+                }
+            }
+            AttributeToFormat::WithOptions(CropOptions {
+                attribute,
+                crop_length: attr_len,
+                crop_marker,
+            }) => {
+                let overrides = crop_marker.clone().map(|crop_marker| FormatOverrides {
+                    crop_marker: Some(crop_marker),
+                    ..Default::default()
+                });
+                (attribute.as_str(), attr_len.unwrap_or(crop_length), overrides)
             }
-            None => (attr.as_str(), crop_length),
         };
 
         if attr_name == "*" {
@@ -1795,6 +2939,9 @@ fn add_crop_to_formatted_options(
                     .entry(*id)
                     .and_modify(|f| f.crop = Some(attr_len))
                     .or_insert(FormatOptions { highlight: false, crop: Some(attr_len) });
+                if let Some(overrides) = &overrides {
+                    add_format_overrides(format_overrides, *id, overrides, true);
+                }
             }
         }
 
@@ -1804,6 +2951,9 @@ fn add_crop_to_formatted_options(
                     .entry(id)
                     .and_modify(|f| f.crop = Some(attr_len))
                     .or_insert(FormatOptions { highlight: false, crop: Some(attr_len) });
+                if let Some(overrides) = &overrides {
+                    add_format_overrides(format_overrides, id, overrides, false);
+                }
             }
         }
     }
@@ -1848,12 +2998,16 @@ fn format_fields(
     field_ids_map: &FieldsIdsMap,
     builder: &MatcherBuilder<'_>,
     formatted_options: &BTreeMap<FieldId, FormatOptions>,
+    format_overrides: &BTreeMap<FieldId, FormatOverrides>,
     compute_matches: bool,
+    grapheme_offsets: bool,
+    compute_matched_terms: bool,
     displayable_ids: &BTreeSet<FieldId>,
     locales: Option<&[Language]>,
     localized_attributes: &[LocalizedAttributesRule],
-) -> milli::Result<(Option<MatchesPosition>, Document)> {
+) -> milli::Result<(Option<MatchesPosition>, Option<Vec<MatchedTerm>>, Document)> {
     let mut matches_position = compute_matches.then(BTreeMap::new);
+    let mut matched_terms = compute_matched_terms.then(Vec::new);
     let mut document = document.clone();
 
     // reduce the formatted option list to the attributes that should be formatted,
@@ -1861,7 +3015,7 @@ fn format_fields(
     let formatting_fields_options: Vec<_> = formatted_options
         .iter()
         .filter(|(_, option)| option.should_format())
-        .map(|(fid, option)| (field_ids_map.name(*fid).unwrap(), option))
+        .map(|(fid, option)| (field_ids_map.name(*fid).unwrap(), option, format_overrides.get(fid)))
         .collect();
 
     // select the attributes to retrieve
@@ -1878,13 +3032,17 @@ fn format_fields(
             // Warn: The time to compute the format list scales with the number of fields to format;
             // cumulated with map_leaf_values that iterates over all the nested fields, it gives a quadratic complexity:
             // d*f where d is the total number of fields to display and f is the total number of fields to format.
-            let format = formatting_fields_options
-                .iter()
-                .filter(|(name, _option)| {
-                    milli::is_faceted_by(name, key) || milli::is_faceted_by(key, name)
-                })
-                .map(|(_, option)| **option)
+            let applying_options = formatting_fields_options.iter().filter(|(name, _, _)| {
+                milli::is_faceted_by(name, key) || milli::is_faceted_by(key, name)
+            });
+            let format = applying_options
+                .clone()
+                .map(|(_, option, _)| **option)
                 .reduce(|acc, option| acc.merge(option));
+            let overrides = applying_options
+                .filter_map(|(_, _, overrides)| overrides.cloned())
+                .reduce(FormatOverrides::merge)
+                .unwrap_or_default();
             let mut infos = Vec::new();
 
             // if no locales has been provided, we try to find the locales in the localized_attributes.
@@ -1899,8 +3057,11 @@ fn format_fields(
                 std::mem::take(value),
                 builder,
                 format,
+                &overrides,
                 &mut infos,
                 compute_matches,
+                grapheme_offsets,
+                matched_terms.as_mut(),
                 array_indices,
                 locales,
             );
@@ -1920,55 +3081,65 @@ fn format_fields(
         .map(|&fid| field_ids_map.name(fid).unwrap());
     let document = permissive_json_pointer::select_values(&document, selectors);
 
-    Ok((matches_position, document))
+    Ok((matches_position, matched_terms, document))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn format_value(
     value: Value,
     builder: &MatcherBuilder<'_>,
     format_options: Option<FormatOptions>,
+    overrides: &FormatOverrides,
     infos: &mut Vec<MatchBounds>,
     compute_matches: bool,
+    grapheme_offsets: bool,
+    matched_terms: Option<&mut Vec<MatchedTerm>>,
     array_indices: &[usize],
     locales: Option<&[Language]>,
 ) -> Value {
-    match value {
-        Value::String(old_string) => {
-            let mut matcher = builder.build(&old_string, locales);
-            if compute_matches {
-                let matches = matcher.matches(array_indices);
-                infos.extend_from_slice(&matches[..]);
-            }
-
-            match format_options {
-                Some(format_options) => {
-                    let value = matcher.format(format_options);
-                    Value::String(value.into_owned())
-                }
-                None => Value::String(old_string),
-            }
-        }
+    let old_string = match value {
+        Value::String(old_string) => old_string,
         // `map_leaf_values` makes sure this is only called for leaf fields
         Value::Array(_) => unreachable!(),
         Value::Object(_) => unreachable!(),
-        Value::Number(number) => {
-            let s = number.to_string();
+        Value::Number(number) => number.to_string(),
+        value => return value,
+    };
 
-            let mut matcher = builder.build(&s, locales);
-            if compute_matches {
-                let matches = matcher.matches(array_indices);
-                infos.extend_from_slice(&matches[..]);
-            }
+    let mut matcher = builder.build(&old_string, locales);
+    if let Some(crop_marker) = &overrides.crop_marker {
+        matcher.crop_marker(crop_marker);
+    }
+    if let Some(highlight_pre_tag) = &overrides.highlight_pre_tag {
+        matcher.highlight_prefix(highlight_pre_tag);
+    }
+    if let Some(highlight_post_tag) = &overrides.highlight_post_tag {
+        matcher.highlight_suffix(highlight_post_tag);
+    }
 
-            match format_options {
-                Some(format_options) => {
-                    let value = matcher.format(format_options);
-                    Value::String(value.into_owned())
-                }
-                None => Value::String(s),
+    if compute_matches {
+        let matches = matcher.matches(array_indices);
+        if grapheme_offsets {
+            infos.extend(matches.into_iter().map(|m| m.with_grapheme_offsets(&old_string)));
+        } else {
+            infos.extend_from_slice(&matches[..]);
+        }
+    }
+
+    if let Some(matched_terms) = matched_terms {
+        for matched_term in matcher.matched_terms() {
+            if !matched_terms.contains(&matched_term) {
+                matched_terms.push(matched_term);
             }
         }
-        value => value,
+    }
+
+    match format_options {
+        Some(format_options) => {
+            let value = matcher.format(format_options);
+            Value::String(value.into_owned())
+        }
+        None => Value::String(old_string),
     }
 }
 
@@ -1977,14 +3148,7 @@ pub(crate) fn parse_filter(
     filter_parsing_error_code: Code,
     features: RoFeatures,
 ) -> Result<Option<Filter>, ResponseError> {
-    let filter = match facets {
-        Value::String(expr) => Filter::from_str(expr).map_err(|e| e.into()),
-        Value::Array(arr) => parse_filter_array(arr).map_err(|e| e.into()),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone()).into()),
-    };
-    let filter = filter.map_err(|err: ResponseError| {
-        ResponseError::from_msg(err.to_string(), filter_parsing_error_code)
-    })?;
+    let filter = parse_filter_syntax(facets, filter_parsing_error_code)?;
 
     if let Some(ref filter) = filter {
         // If the contains operator is used while the contains filter features is not enabled, errors out
@@ -2001,6 +3165,22 @@ pub(crate) fn parse_filter(
     Ok(filter)
 }
 
+/// Only checks the syntax of the filter, without checking the features it requires.
+pub(crate) fn parse_filter_syntax(
+    facets: &Value,
+    filter_parsing_error_code: Code,
+) -> Result<Option<Filter>, ResponseError> {
+    let filter = match facets {
+        Value::String(expr) => Filter::from_str(expr).map_err(|e| e.into()),
+        Value::Array(arr) => parse_filter_array(arr).map_err(|e| e.into()),
+        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone()).into()),
+    };
+    filter.map_err(|err: ResponseError| {
+        ResponseError::from_msg(err.to_string(), filter_parsing_error_code)
+            .with_details(err.details)
+    })
+}
+
 fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
     let mut ands = Vec::new();
     for value in arr {
@@ -112,3 +112,27 @@ fn test_insert_geo_distance_with_coords_as_string() {
     insert_geo_distance(sorters, &mut document);
     assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 }
+
+#[test]
+fn test_insert_geo_distance_with_several_points() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": [
+            { "lat": 0, "lng": 0 },
+            { "lat": 50, "lng": 3 }
+          ]
+        }"#,
+    )
+    .unwrap();
+
+    // the closest point is used to compute the distance
+    let sorters = &["_geoPoint(50,3):desc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    let sorters = &["_geoPoint(0,0):asc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+}
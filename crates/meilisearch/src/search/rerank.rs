@@ -0,0 +1,86 @@
+use std::cmp::{max, min};
+
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli;
+use meilisearch_types::milli::score_details::{Rerank, ScoreDetails};
+use meilisearch_types::milli::vector::reranker::Reranker;
+use milli::Index;
+
+use super::{SearchQ, SearchQuery};
+
+/// The number of top hits rescored by the reranker of the index, unless the query sets a `rerankBudget`.
+const DEFAULT_RERANK_BUDGET: usize = 100;
+
+/// Rescores the top hits of a search with the reranker of the index, before the requested page is cut.
+pub(super) struct RerankStage<'q> {
+    reranker: Reranker,
+    query: &'q str,
+    budget: usize,
+    offset: usize,
+    limit: usize,
+    /// The number of hits to retrieve so that both the reranked hits and the requested page are known.
+    pub window: usize,
+}
+
+impl<'q> RerankStage<'q> {
+    /// Returns `None` when the index has no reranker, there is no query to rerank against,
+    /// or the query disabled reranking with a `rerankBudget` of `0`.
+    pub fn new(
+        index: &Index,
+        rtxn: &RoTxn,
+        query: &'q SearchQuery,
+        max_total_hits: usize,
+    ) -> Result<Option<Self>, ResponseError> {
+        let Some(q) = query.q.as_ref().map(SearchQ::as_str).filter(|q| !q.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let budget = min(query.rerank_budget.unwrap_or(DEFAULT_RERANK_BUDGET), max_total_hits);
+        if budget == 0 {
+            return Ok(None);
+        }
+        let Some(options) = index.reranker(rtxn)? else {
+            return Ok(None);
+        };
+
+        let reranker = Reranker::new(options)?;
+        let (offset, limit) = query.offset_and_limit(max_total_hits);
+        let window = max(budget, offset + limit);
+
+        Ok(Some(Self { reranker, query: q, budget, offset, limit, window }))
+    }
+
+    /// Reorders the first `budget` hits of the result, then keeps the requested page only.
+    ///
+    /// The hits omitted by the reranker keep their retrieval order after the reranked ones.
+    pub fn apply(
+        self,
+        index: &Index,
+        rtxn: &RoTxn,
+        result: &mut milli::SearchResult,
+    ) -> Result<(), ResponseError> {
+        let documents_ids = std::mem::take(&mut result.documents_ids);
+        let document_scores = std::mem::take(&mut result.document_scores);
+
+        let budget = min(self.budget, documents_ids.len());
+        let scored = self.reranker.rerank(index, rtxn, self.query, &documents_ids[..budget])?;
+
+        let mut hits: Vec<_> = documents_ids.into_iter().zip(document_scores).map(Some).collect();
+        let mut reranked = Vec::with_capacity(hits.len());
+        for (position, relevance_score) in scored {
+            if let Some((docid, mut scores)) = hits[position].take() {
+                let relevance_score = relevance_score.clamp(0.0, 1.0);
+                scores.insert(0, ScoreDetails::Rerank(Rerank { relevance_score }));
+                reranked.push((docid, scores));
+            }
+        }
+        reranked.extend(hits.into_iter().flatten());
+
+        let (documents_ids, document_scores) =
+            reranked.into_iter().skip(self.offset).take(self.limit).unzip();
+        result.documents_ids = documents_ids;
+        result.document_scores = document_scores;
+
+        Ok(())
+    }
+}
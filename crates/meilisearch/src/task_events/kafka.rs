@@ -0,0 +1,32 @@
+//! Produces the task events to a Kafka topic, keyed by the uid of the task so that the
+//! events of a task are all stored in the same partition, in order.
+
+use anyhow::Context;
+use kafka::producer::{Producer, Record, RequiredAcks};
+use meilisearch_types::task_view::TaskView;
+
+use super::{Publisher, TIMEOUT};
+
+pub struct Kafka {
+    producer: Producer,
+    topic: String,
+}
+
+impl Kafka {
+    pub fn connect(brokers: &[String], topic: &str) -> anyhow::Result<Self> {
+        let producer = Producer::from_hosts(brokers.to_vec())
+            .with_ack_timeout(TIMEOUT)
+            .with_required_acks(RequiredAcks::All)
+            .create()
+            .context("Could not connect to the Kafka brokers")?;
+        Ok(Kafka { producer, topic: topic.to_string() })
+    }
+}
+
+impl Publisher for Kafka {
+    fn publish(&mut self, task: &TaskView, payload: &[u8]) -> anyhow::Result<()> {
+        let key = task.uid.to_string();
+        self.producer.send(&Record::from_key_value(&self.topic, key.as_bytes(), payload))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Publishes the lifecycle of the tasks to an external queue, so that the consumers can replay
+//! the transitions they missed, which the webhooks can't offer.
+//!
+//! The index scheduler sends a task to a channel every time it is enqueued, starts processing, or
+//! finishes. A dedicated thread publishes them in order, and keeps retrying an event until the broker
+//! acknowledges it. The events are thus delivered at least once while Meilisearch runs.
+
+#[cfg(feature = "kafka-task-events")]
+mod kafka;
+mod nats;
+mod redis;
+#[cfg(test)]
+mod test;
+
+use std::net::TcpStream;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+use meilisearch_types::task_view::TaskView;
+use tracing::error;
+use url::Url;
+
+use crate::option::Opt;
+
+/// The number of events kept while the broker is unreachable, the next ones are dropped.
+const CHANNEL_CAPACITY: usize = 10_000;
+/// How long to wait before reconnecting to the broker after an error.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// How long to wait for the broker to acknowledge an event.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A system the task events are published to.
+trait Publisher {
+    /// Publish the serialized event of the task, returns once the broker acknowledged it.
+    fn publish(&mut self, task: &TaskView, payload: &[u8]) -> anyhow::Result<()>;
+}
+
+/// The broker the task events are published to, deduced from the scheme of its URL.
+#[derive(Debug, PartialEq)]
+enum Broker {
+    Redis(Url),
+    Nats(Url),
+    Kafka(Vec<String>),
+}
+
+impl Broker {
+    fn parse(url: &str) -> anyhow::Result<Self> {
+        let invalid = || {
+            format!("Invalid task events URL `{url}`. Expected a `redis://`, `nats://` or `kafka://` URL.")
+        };
+        let (scheme, hosts) = url.split_once("://").with_context(invalid)?;
+        match scheme {
+            "redis" => Ok(Broker::Redis(Url::parse(url).with_context(invalid)?)),
+            "nats" => Ok(Broker::Nats(Url::parse(url).with_context(invalid)?)),
+            // Kafka is bootstrapped from a list of brokers, which is not a valid URL.
+            "kafka" => {
+                let brokers: Vec<String> = hosts
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|broker| !broker.is_empty())
+                    .map(String::from)
+                    .collect();
+                if brokers.is_empty() {
+                    bail!(invalid());
+                }
+                Ok(Broker::Kafka(brokers))
+            }
+            _ => bail!(invalid()),
+        }
+    }
+
+    fn connect(&self, stream: &str) -> anyhow::Result<Box<dyn Publisher + Send>> {
+        match self {
+            Broker::Redis(url) => Ok(Box::new(redis::Redis::connect(url, stream)?)),
+            Broker::Nats(url) => Ok(Box::new(nats::Nats::connect(url, stream)?)),
+            #[cfg(feature = "kafka-task-events")]
+            Broker::Kafka(brokers) => Ok(Box::new(kafka::Kafka::connect(brokers, stream)?)),
+            #[cfg(not(feature = "kafka-task-events"))]
+            Broker::Kafka(_) => bail!(
+                "A `kafka://` task events URL requires Meilisearch to be compiled with the `kafka-task-events` feature"
+            ),
+        }
+    }
+}
+
+/// Connect to the broker and publish the task events it receives in a new thread.
+///
+/// Returns the channel the index scheduler must send the tasks to, if the task events are enabled.
+pub fn start(opt: &Opt) -> anyhow::Result<Option<SyncSender<TaskView>>> {
+    let Some(url) = &opt.experimental_task_events_url else { return Ok(None) };
+    let broker = Broker::parse(url)?;
+    let stream = opt.experimental_task_events_stream.clone();
+    // We connect once before starting the thread to report the configuration errors right away.
+    let publisher = broker.connect(&stream)?;
+
+    let (sender, receiver) = sync_channel(CHANNEL_CAPACITY);
+    thread::Builder::new()
+        .name(String::from("task-events"))
+        .spawn(move || publish_events(broker, &stream, publisher, receiver))?;
+
+    Ok(Some(sender))
+}
+
+fn publish_events(
+    broker: Broker,
+    stream: &str,
+    mut publisher: Box<dyn Publisher + Send>,
+    receiver: Receiver<TaskView>,
+) {
+    for task in receiver {
+        let payload = match serde_json::to_vec(&task) {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!("While serializing the event of task {}: {e}", task.uid);
+                continue;
+            }
+        };
+
+        // The next events must wait, so that each task is published in the order of its transitions.
+        while let Err(e) = publisher.publish(&task, &payload) {
+            error!("While publishing the event of task {}: {e:#}", task.uid);
+            thread::sleep(RETRY_INTERVAL);
+            match broker.connect(stream) {
+                Ok(new_publisher) => publisher = new_publisher,
+                Err(e) => error!("While reconnecting to the task events broker: {e:#}"),
+            }
+        }
+    }
+}
+
+/// Open a TCP connection to the host of the URL, failing the reads and writes that take too long.
+fn connect_tcp(url: &Url, default_port: u16) -> anyhow::Result<TcpStream> {
+    let addresses = url.socket_addrs(|| Some(default_port))?;
+    let address = addresses.first().with_context(|| format!("Could not resolve `{url}`"))?;
+    let stream = TcpStream::connect_timeout(address, TIMEOUT)
+        .with_context(|| format!("Could not connect to `{address}`"))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    Ok(stream)
+}
@@ -0,0 +1,80 @@
+//! Publishes the task events on a NATS subject. A JetStream stream capturing the subject
+//! makes them replayable.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use anyhow::bail;
+use meilisearch_types::task_view::TaskView;
+use serde_json::json;
+use url::Url;
+
+use super::{connect_tcp, Publisher};
+
+const DEFAULT_PORT: u16 = 4222;
+
+pub struct Nats {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    subject: String,
+}
+
+impl Nats {
+    /// Connect to the NATS server of the URL, authenticating with its user and password if any.
+    pub fn connect(url: &Url, subject: &str) -> anyhow::Result<Self> {
+        let writer = connect_tcp(url, DEFAULT_PORT)?;
+        let mut reader = BufReader::new(writer.try_clone()?);
+
+        // The server introduces itself before anything else.
+        let mut info = String::new();
+        reader.read_line(&mut info)?;
+        if !info.starts_with("INFO") {
+            bail!("Unexpected greeting from the NATS server: {}", info.trim_end());
+        }
+
+        let mut options = json!({ "verbose": false, "pedantic": false, "name": "meilisearch" });
+        if let Some(password) = url.password() {
+            options["user"] = url.username().into();
+            options["pass"] = password.into();
+        }
+
+        let mut nats = Nats { reader, writer, subject: subject.to_string() };
+        nats.writer.write_all(format!("CONNECT {options}\r\n").as_bytes())?;
+        nats.flush()?;
+        Ok(nats)
+    }
+
+    /// Wait for the server to process everything that was sent, which fails if it refused any of it.
+    fn flush(&mut self) -> anyhow::Result<()> {
+        self.writer.write_all(b"PING\r\n")?;
+        loop {
+            let mut line = String::new();
+            self.reader.read_line(&mut line)?;
+            match line.trim_end() {
+                "" => bail!("NATS closed the connection"),
+                "PONG" => return Ok(()),
+                "PING" => self.writer.write_all(b"PONG\r\n")?,
+                error if error.starts_with("-ERR") => {
+                    bail!("NATS replied with an error: {}", error[4..].trim())
+                }
+                // the informative messages and acknowledgements are ignored
+                _ => (),
+            }
+        }
+    }
+}
+
+impl Publisher for Nats {
+    fn publish(&mut self, _task: &TaskView, payload: &[u8]) -> anyhow::Result<()> {
+        self.writer.write_all(&publish_message(&self.subject, payload))?;
+        self.flush()
+    }
+}
+
+/// Encode the `PUB` message sending the payload on the subject.
+pub(super) fn publish_message(subject: &str, payload: &[u8]) -> Vec<u8> {
+    let mut message = format!("PUB {subject} {}\r\n", payload.len()).into_bytes();
+    message.extend_from_slice(payload);
+    message.extend_from_slice(b"\r\n");
+    message
+}
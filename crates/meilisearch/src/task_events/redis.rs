@@ -0,0 +1,90 @@
+//! Appends the task events to a Redis stream, read by the consumers with `XREAD` or a consumer group.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+use anyhow::bail;
+use meilisearch_types::task_view::TaskView;
+use url::Url;
+
+use super::{connect_tcp, Publisher};
+
+const DEFAULT_PORT: u16 = 6379;
+
+pub struct Redis {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    stream: String,
+}
+
+impl Redis {
+    /// Connect to the Redis server of the URL, authenticating with its user and password if any.
+    pub fn connect(url: &Url, stream: &str) -> anyhow::Result<Self> {
+        let writer = connect_tcp(url, DEFAULT_PORT)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        let mut redis = Redis { reader, writer, stream: stream.to_string() };
+
+        if let Some(password) = url.password() {
+            match url.username() {
+                "" => redis.send(command(&[b"AUTH", password.as_bytes()]))?,
+                username => {
+                    redis.send(command(&[b"AUTH", username.as_bytes(), password.as_bytes()]))?
+                }
+            }
+        }
+
+        Ok(redis)
+    }
+
+    /// Send a command and wait for its reply, which must not be an error.
+    fn send(&mut self, command: Vec<u8>) -> anyhow::Result<()> {
+        self.writer.write_all(&command)?;
+
+        let mut reply = String::new();
+        self.reader.read_line(&mut reply)?;
+        let reply = reply.trim_end();
+        match reply.as_bytes().first() {
+            None => bail!("Redis closed the connection"),
+            Some(b'-') => bail!("Redis replied with an error: {}", &reply[1..]),
+            // The content of a bulk string, e.g. the id of the entry, follows its length.
+            Some(b'$') => {
+                let length: i64 = reply[1..].parse()?;
+                if let Ok(length) = u64::try_from(length) {
+                    let mut content = self.reader.by_ref().take(length + 2);
+                    std::io::copy(&mut content, &mut std::io::sink())?;
+                }
+                Ok(())
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+impl Publisher for Redis {
+    fn publish(&mut self, task: &TaskView, payload: &[u8]) -> anyhow::Result<()> {
+        let uid = task.uid.to_string();
+        let status = task.status.to_string();
+        self.send(command(&[
+            b"XADD",
+            self.stream.as_bytes(),
+            b"*",
+            b"uid",
+            uid.as_bytes(),
+            b"status",
+            status.as_bytes(),
+            b"task",
+            payload,
+        ]))
+    }
+}
+
+/// Encode a command in the Redis serialization protocol, as an array of bulk strings.
+pub(super) fn command(args: &[&[u8]]) -> Vec<u8> {
+    let mut command = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        command.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        command.extend_from_slice(arg);
+        command.extend_from_slice(b"\r\n");
+    }
+    command
+}
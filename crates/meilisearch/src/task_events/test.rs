@@ -0,0 +1,28 @@
+use meili_snap::snapshot;
+use url::Url;
+
+use super::{nats, redis, Broker};
+
+#[test]
+fn parse_broker_urls() {
+    let url = Url::parse("redis://:secret@localhost:6379").unwrap();
+    assert_eq!(Broker::parse(url.as_str()).unwrap(), Broker::Redis(url));
+    let url = Url::parse("nats://localhost").unwrap();
+    assert_eq!(Broker::parse(url.as_str()).unwrap(), Broker::Nats(url));
+    let broker = Broker::parse("kafka://kafka-1:9092, kafka-2:9092").unwrap();
+    snapshot!(format!("{broker:?}"), @r###"Kafka(["kafka-1:9092", "kafka-2:9092"])"###);
+
+    for invalid in ["localhost:6379", "amqp://localhost", "kafka://", "nats://[::1"] {
+        let error = Broker::parse(invalid).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid task events URL"), "{invalid}: {error}");
+    }
+}
+
+#[test]
+fn encode_messages() {
+    let command = redis::command(&[b"XADD", b"meilisearch-tasks", b"*", b"task", b"{\"uid\":0}"]);
+    snapshot!(String::from_utf8(command).unwrap().escape_debug(), @r###"*5\r\n$4\r\nXADD\r\n$17\r\nmeilisearch-tasks\r\n$1\r\n*\r\n$4\r\ntask\r\n$9\r\n{\"uid\":0}\r\n"###);
+
+    let message = nats::publish_message("meilisearch-tasks", b"{\"uid\":0}");
+    snapshot!(String::from_utf8(message).unwrap().escape_debug(), @r###"PUB meilisearch-tasks 9\r\n{\"uid\":0}\r\n"###);
+}
@@ -1,6 +1,6 @@
 use std::{thread, time};
 
-use crate::common::{Server, Value};
+use crate::common::{default_settings, Server, Value};
 use crate::json;
 
 #[actix_rt::test]
@@ -421,7 +421,7 @@ async fn error_add_api_key_invalid_parameters_actions() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `network.get`, `network.update`",
+      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `network.get`, `network.update`, `dumps.import`, `snapshots.restore`",
       "code": "invalid_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_api_key_actions"
@@ -1393,7 +1393,7 @@ async fn error_patch_api_key_indexes() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `indexes`: expected one of `description`, `name`",
+      "message": "Immutable field `indexes`: expected one of `description`, `name`, `rateLimit`, `quotas`, `filter`",
       "code": "immutable_api_key_indexes",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_indexes"
@@ -1470,7 +1470,7 @@ async fn error_patch_api_key_actions() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `actions`: expected one of `description`, `name`",
+      "message": "Immutable field `actions`: expected one of `description`, `name`, `rateLimit`, `quotas`, `filter`",
       "code": "immutable_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_actions"
@@ -1539,7 +1539,7 @@ async fn error_patch_api_key_expiration_date() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `expiresAt`: expected one of `description`, `name`",
+      "message": "Immutable field `expiresAt`: expected one of `description`, `name`, `rateLimit`, `quotas`, `filter`",
       "code": "immutable_api_key_expires_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_expires_at"
@@ -1685,6 +1685,466 @@ async fn error_patch_api_key_indexes_invalid_parameters() {
     meili_snap::snapshot!(code, @"400 Bad Request");
 }
 
+#[actix_rt::test]
+async fn rotate_api_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+        "indexes": ["*"],
+        "actions": ["version"],
+        "expiresAt": null
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let first_key = response["key"].as_str().unwrap().to_string();
+
+    // the previous value is still accepted during the overlap
+    let (response, code) = server.rotate_api_key(&uid, json!({ "overlapSeconds": 3600 })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]", ".key" => "[ignored]", ".previousKeyExpiresAt" => "[ignored]" }), @r###"
+    {
+      "name": null,
+      "description": null,
+      "key": "[ignored]",
+      "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+      "actions": [
+        "version"
+      ],
+      "indexes": [
+        "*"
+      ],
+      "expiresAt": null,
+      "createdAt": "[ignored]",
+      "updatedAt": "[ignored]",
+      "previousKeyExpiresAt": "[ignored]"
+    }
+    "###);
+    let second_key = response["key"].as_str().unwrap().to_string();
+    assert_ne!(first_key, second_key);
+
+    let (response, code) = server.get_api_key(&uid).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert_eq!(response["key"].as_str(), Some(second_key.as_str()));
+
+    for key in [&first_key, &second_key] {
+        server.use_api_key(key);
+        let (_response, code) = server.version().await;
+        meili_snap::snapshot!(code, @"200 OK");
+    }
+
+    // the value of the key can be used to rotate it, and the previous value stops being accepted
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.rotate_api_key(&second_key, json!({})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response.get("previousKeyExpiresAt").is_none());
+    let third_key = response["key"].as_str().unwrap().to_string();
+
+    for key in [&first_key, &second_key] {
+        server.use_api_key(key);
+        let (_response, code) = server.version().await;
+        meili_snap::snapshot!(code, @"403 Forbidden");
+    }
+    server.use_api_key(&third_key);
+    let (_response, code) = server.version().await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.rotate_api_key(&uid, json!({ "overlapSeconds": -1 })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_overlap_seconds""###);
+}
+
+#[actix_rt::test]
+async fn rotate_master_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+        "indexes": ["*"],
+        "actions": ["version", "keys.get"],
+        "expiresAt": null
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let first_key = response["key"].as_str().unwrap().to_string();
+
+    // only the master key can rotate the master key
+    server.use_api_key(&first_key);
+    let (_response, code) =
+        server.rotate_master_key(json!({ "masterKey": "NEW_MASTER_KEY_OF_32_BYTES_LONG!" })).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.rotate_master_key(json!({ "masterKey": "TOO_SHORT" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "The master key must be at least 16 bytes. The provided key is only 9 bytes.",
+      "code": "invalid_master_key",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_master_key"
+    }
+    "###);
+
+    let (response, code) =
+        server.rotate_master_key(json!({ "masterKey": "NEW_MASTER_KEY_OF_32_BYTES_LONG!" })).await;
+    meili_snap::snapshot!(code, @"204 No Content");
+    meili_snap::snapshot!(response, @"null");
+
+    // the previous master key and the values derived from it are no longer accepted
+    for key in ["MASTER_KEY", &first_key] {
+        server.use_api_key(key);
+        let (_response, code) = server.version().await;
+        meili_snap::snapshot!(code, @"403 Forbidden");
+    }
+
+    server.use_api_key("NEW_MASTER_KEY_OF_32_BYTES_LONG!");
+    let (response, code) = server.get_api_key(&uid).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let second_key = response["key"].as_str().unwrap().to_string();
+    assert_ne!(first_key, second_key);
+
+    server.use_api_key(&second_key);
+    let (_response, code) = server.version().await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    // a master key that has been rotated out can't come back
+    server.use_api_key("NEW_MASTER_KEY_OF_32_BYTES_LONG!");
+    let (_response, code) =
+        server.rotate_master_key(json!({ "masterKey": "ANOTHER_MASTER_KEY_OF_32_BYTES!!" })).await;
+    meili_snap::snapshot!(code, @"204 No Content");
+    server.use_api_key("ANOTHER_MASTER_KEY_OF_32_BYTES!!");
+    let (response, code) =
+        server.rotate_master_key(json!({ "masterKey": "NEW_MASTER_KEY_OF_32_BYTES_LONG!" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "The master key has already been rotated out and can't be used again.",
+      "code": "invalid_master_key",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_master_key"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn encrypted_api_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut options = default_settings(dir.path());
+    options.auth_encryption_key = Some("not a key".to_string());
+    let error = Server::new_with_options(options).await.map(|_| ()).unwrap_err();
+    meili_snap::snapshot!(error, @"The auth encryption key must be a base64-encoded 256-bit key.");
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut options = default_settings(dir.path());
+    options.auth_encryption_key = Some("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=".to_string());
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "name": "encrypted",
+        "indexes": ["*"],
+        "actions": ["version"],
+        "expiresAt": null
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let (response, code) = server.list_api_keys("").await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["total"], @"3");
+    meili_snap::snapshot!(response["results"][0]["name"], @r###""encrypted""###);
+
+    server.use_api_key(&key);
+    let (_response, code) = server.version().await;
+    meili_snap::snapshot!(code, @"200 OK");
+}
+
+#[actix_rt::test]
+async fn export_and_import_api_keys() {
+    let mut source = Server::new_auth().await;
+    source.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+        "name": "migrated",
+        "indexes": ["*"],
+        "actions": ["version"],
+        "expiresAt": null
+    });
+    let (response, code) = source.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let key = response["key"].as_str().unwrap().to_string();
+    let (response, code) = source.rotate_api_key(&key, json!({})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let key = response["key"].as_str().unwrap().to_string();
+
+    // only the master key can export the keys
+    source.use_api_key(&key);
+    let (_response, code) = source.export_api_keys().await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+
+    source.use_api_key("MASTER_KEY");
+    let (export, code) = source.export_api_keys().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(export["keys"][0], { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]", ".keySha256" => "[ignored]" }), @r###"
+    {
+      "name": "migrated",
+      "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+      "actions": [
+        "version"
+      ],
+      "indexes": [
+        "*"
+      ],
+      "expiresAt": null,
+      "createdAt": "[ignored]",
+      "updatedAt": "[ignored]",
+      "rotations": 1,
+      "keySha256": "[ignored]"
+    }
+    "###);
+    assert!(!export.to_string().contains(&key));
+
+    // an instance launched with another master key would give new values to the keys
+    let dir = tempfile::tempdir().unwrap();
+    let mut options = default_settings(dir.path());
+    options.master_key = Some("ANOTHER_MASTER_KEY".to_string());
+    let mut other = Server::new_with_options(options).await.unwrap();
+    other.use_api_key("ANOTHER_MASTER_KEY");
+    let (response, code) = other.import_api_keys(export.clone()).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_import""###);
+    let (response, _code) = other.list_api_keys("").await;
+    meili_snap::snapshot!(response["total"], @"2");
+
+    // the clients keep using the same value on an instance sharing the master key
+    let mut destination = Server::new_auth().await;
+    destination.use_api_key("MASTER_KEY");
+    let (response, code) = destination.import_api_keys(json!({ "keys": [], "other": 1 })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "Unknown field `other`: expected one of `keys`",
+      "code": "invalid_api_key_import",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key_import"
+    }
+    "###);
+    let (response, code) = destination.import_api_keys(export).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response, @r###"
+    {
+      "importedKeys": 3
+    }
+    "###);
+
+    destination.use_api_key(&key);
+    let (_response, code) = destination.version().await;
+    meili_snap::snapshot!(code, @"200 OK");
+}
+
+#[actix_rt::test]
+async fn rate_limited_api_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["version", "stats.get"],
+        "expiresAt": null,
+        "rateLimit": { "requests": 3, "per": "minute", "routes": { "version": { "requests": 1, "per": "minute" } } }
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!(response["rateLimit"]), @r###"
+    {
+      "requests": 3,
+      "per": "minute",
+      "routes": {
+        "version": {
+          "requests": 1,
+          "per": "minute"
+        }
+      }
+    }
+    "###);
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let key = response["key"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let (_response, code) = server.version().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    // the version routes are exhausted, but not the other ones
+    let (response, code) = server.version().await;
+    meili_snap::snapshot!(code, @"429 Too Many Requests");
+    meili_snap::snapshot!(response["code"], @r###""too_many_api_key_requests""###);
+    let (_response, code) = server.stats().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let (_response, code) = server.stats().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let (response, code) = server.stats().await;
+    meili_snap::snapshot!(code, @"429 Too Many Requests");
+    meili_snap::snapshot!(response["code"], @r###""too_many_api_key_requests""###);
+
+    // the master key is never limited, and removing the limit takes effect immediately
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "rateLimit": null })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response.get("rateLimit").is_none());
+
+    server.use_api_key(&key);
+    let (_response, code) = server.stats().await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    for rate_limit in [
+        json!({ "requests": 0, "per": "second" }),
+        json!({ "requests": 1, "per": "hour" }),
+        json!({ "requests": 1, "per": "second", "routes": { "doggo": { "requests": 1, "per": "second" } } }),
+    ] {
+        let (response, code) = server.patch_api_key(&uid, json!({ "rateLimit": rate_limit })).await;
+        meili_snap::snapshot!(code, @"400 Bad Request");
+        meili_snap::snapshot!(response["code"], @r###""invalid_api_key_rate_limit""###);
+    }
+}
+
+#[actix_rt::test]
+async fn api_key_quotas() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["indexes.create", "documents.add", "tasks.get"],
+        "expiresAt": null,
+        "quotas": { "maxIndexes": 1, "maxDocuments": 2 }
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!(response["quotas"]), @r###"
+    {
+      "maxIndexes": 1,
+      "maxDocuments": 2
+    }
+    "###);
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let key = response["key"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("doggos").create(None).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    meili_snap::snapshot!(task["status"], @r###""succeeded""###);
+
+    // the key already created all the indexes it can
+    let (response, code) = server.index("catto").create(None).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["code"], @r###""api_key_quota_exceeded""###);
+
+    let documents = json!([{ "id": 1 }, { "id": 2 }, { "id": 3 }]);
+    let (response, code) = server.index("doggos").add_documents(documents, None).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["code"], @r###""api_key_quota_exceeded""###);
+
+    let documents = json!([{ "id": 1 }, { "id": 2 }]);
+    let (response, code) = server.index("doggos").add_documents(documents, None).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    meili_snap::snapshot!(task["status"], @r###""succeeded""###);
+
+    let documents = json!([{ "id": 3 }]);
+    let (response, code) = server.index("doggos").add_documents(documents, None).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["code"], @r###""api_key_quota_exceeded""###);
+
+    // the other keys aren't limited by the quotas of this one
+    server.use_api_key("MASTER_KEY");
+    let (_response, code) = server.index("catto").create(None).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let (response, code) =
+        server.patch_api_key(&uid, json!({ "quotas": { "maxIndexes": "doggo" } })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_quotas""###);
+}
+
+#[actix_rt::test]
+async fn filtered_api_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("docs");
+    let (response, _code) = index.update_settings_filterable_attributes(json!(["tenant"])).await;
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    let documents = json!([
+        { "id": 1, "tenant": "acme", "title": "doggo" },
+        { "id": 2, "tenant": "umbrella", "title": "doggo" },
+        { "id": 3, "tenant": "acme", "title": "catto" },
+    ]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+
+    let content = json!({
+        "indexes": ["docs"],
+        "actions": ["search", "documents.get"],
+        "expiresAt": null,
+        "filter": "tenant = acme"
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(response["filter"], @r###""tenant = acme""###);
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let key = response["key"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let index = server.index("docs");
+    // the filter of the key is added to the one of the query
+    let (response, code) = index.search_post(json!({ "q": "doggo", "filter": "id > 0" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "tenant": "acme",
+        "title": "doggo"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.fetch_documents(json!({})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["total"], @"2");
+
+    let (_response, code) = index.get_document(1, None).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let (response, code) = index.get_document(2, None).await;
+    meili_snap::snapshot!(code, @"404 Not Found");
+    meili_snap::snapshot!(response["code"], @r###""document_not_found""###);
+
+    // removing the filter gives back access to all the documents
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "filter": null })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response.get("filter").is_none());
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("docs").fetch_documents(json!({})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["total"], @"3");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "filter": "tenant =" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_filter""###);
+}
+
 #[actix_rt::test]
 async fn error_access_api_key_routes_no_master_key_set() {
     let mut server = Server::new().await;
@@ -19,10 +19,12 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/validate") =>             hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents/0") =>                    hashset!{"documents.get", "documents.*", "*"},
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete-batch") =>         hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete") =>               hashset!{"documents.delete", "documents.*", "*"},
+            ("GET",     "/indexes/products/changes") =>                        hashset!{"documents.get", "documents.*", "*"},
             ("GET",     "/tasks") =>                                           hashset!{"tasks.get", "tasks.*", "*"},
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
@@ -56,7 +58,12 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
+            ("POST",    "/dumps/import") =>                                    hashset!{"dumps.import", "dumps.*", "*"},
+            ("POST",    "/dumps/restore-index") =>                             hashset!{"dumps.import", "dumps.*", "*"},
             ("POST",    "/snapshots") =>                                       hashset!{"snapshots.create", "snapshots.*", "*"},
+            ("POST",    "/snapshots/restore") =>                               hashset!{"snapshots.restore", "snapshots.*", "*"},
+            ("GET",     "/snapshots/restore-points") =>                        hashset!{"snapshots.restore", "snapshots.*", "*"},
+            ("POST",    "/snapshots/restore-index") =>                         hashset!{"snapshots.restore", "snapshots.*", "*"},
             ("GET",     "/version") =>                                         hashset!{"version", "*"},
             ("GET",     "/metrics") =>                                         hashset!{"metrics.get", "metrics.*", "*"},
             ("POST",    "/logs/stream") =>                                     hashset!{"metrics.get", "metrics.*", "*"},
@@ -70,6 +77,8 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PATCH",   "/experimental-features") =>                           hashset!{"experimental.update", "*"},
             ("GET",   "/network") =>                                           hashset!{"network.get", "*"},
             ("PATCH",   "/network") =>                                         hashset!{"network.update", "*"},
+            ("GET",     "/aliases") =>                                         hashset!{"indexes.get", "indexes.*", "*"},
+            ("PATCH",   "/aliases") =>                                         hashset!{"indexes.update", "indexes.*", "*"},
         };
 
         authorizations
@@ -566,3 +566,199 @@ async fn error_access_modified_token() {
     assert_eq!(response, INVALID_RESPONSE.clone());
     assert_eq!(code, 403);
 }
+
+#[actix_rt::test]
+async fn tenant_token_restricted_attributes_and_routes() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let index = server.index("sales");
+    let documents = json!([
+        { "id": 1, "title": "red shoes", "color": "red", "margin": 12 },
+        { "id": 2, "title": "blue shoes", "color": "blue", "margin": 30 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings(json!({"filterableAttributes": ["color"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let content = json!({ "indexes": ["*"], "actions": ["search"], "expiresAt": null });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({ "sales": { "attributesToRetrieve": ["id", "title"] } }),
+        "routes" => json!(["search"]),
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    // the hidden attributes aren't returned even when explicitly requested
+    let (response, code) = index
+        .search_post(json!({
+            "q": "red",
+            "attributesToRetrieve": ["title", "margin"],
+            "attributesToHighlight": ["*"],
+            "showMatchesPosition": true
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "red shoes",
+        "_formatted": {
+          "id": "1",
+          "title": "<em>red</em> shoes"
+        },
+        "_matchesPosition": {
+          "title": [
+            {
+              "start": 0,
+              "length": 3
+            }
+          ]
+        }
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "sales", "q": "blue" }] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["results"][0]["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "title": "blue shoes"
+      }
+    ]
+    "###);
+
+    // the facet search isn't part of the routes of the token
+    let index = server.index("sales");
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "color", "facetQuery": "re" })).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "The provided tenant token cannot access the `facetSearch` route.",
+      "code": "invalid_api_key",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn tenant_token_restricted_attributes_cannot_be_used() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let index = server.index("sales");
+    let documents = json!([
+        { "id": 1, "title": "red shoes", "color": "red", "margin": 12 },
+        { "id": 2, "title": "blue shoes", "color": "blue", "margin": 30 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let settings = json!({
+        "filterableAttributes": ["color", "margin"],
+        "sortableAttributes": ["margin"],
+    });
+    let (task, _code) = index.update_settings(settings).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let content = json!({ "indexes": ["*"], "actions": ["search"], "expiresAt": null });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({ "sales": { "attributesToRetrieve": ["id", "title", "color"] } }),
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    // the allowed attributes can be used
+    let (response, code) =
+        index.search_post(json!({ "filter": "color = red", "facets": ["color"] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "red shoes",
+        "color": "red"
+      }
+    ]
+    "###);
+
+    // the hidden attributes would be revealed by filtering, sorting, faceting or searching on them
+    for query in [
+        json!({ "filter": "color = red AND margin > 20" }),
+        json!({ "filter": [["color = red", "margin > 20"]] }),
+        json!({ "sort": ["margin:asc"] }),
+        json!({ "facets": ["margin"] }),
+        json!({ "facets": ["*"] }),
+        json!({ "distinct": "margin" }),
+        json!({ "q": "12", "attributesToSearchOn": ["margin"] }),
+    ] {
+        let (response, code) = index.search_post(query.clone()).await;
+        assert_eq!(code, 403, "{query}: {response}");
+        assert_eq!(response["code"], json!("invalid_api_key"), "{query}: {response}");
+    }
+    let (response, code) = index.search_get("sort=margin:desc").await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "The provided tenant token cannot access the `margin` attribute.",
+      "code": "invalid_api_key",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key"
+    }
+    "###);
+
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "margin", "facetQuery": "1" })).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["message"], @r###""The provided tenant token cannot access the `margin` attribute.""###);
+    let (response, code) = index
+        .facet_search(json!({ "facetName": "color", "facetQuery": "re", "filter": "margin > 20" }))
+        .await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["message"], @r###""The provided tenant token cannot access the `margin` attribute.""###);
+
+    let (response, code) = server
+        .multi_search(json!({ "queries": [
+            { "indexUid": "sales", "q": "shoes" },
+            { "indexUid": "sales", "filter": "margin > 20" },
+        ] }))
+        .await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["message"], @r###""Inside `.queries[1]`: The provided tenant token cannot access the `margin` attribute.""###);
+
+    let (response, code) = server
+        .multi_search(json!({
+            "federation": {},
+            "queries": [
+                { "indexUid": "sales", "q": "shoes" },
+                { "indexUid": "sales", "sort": ["margin:desc"] },
+            ]
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["message"], @r###""Inside `.queries[1]`: The provided tenant token cannot access the `margin` attribute.""###);
+
+    let (response, code) = server
+        .multi_search(json!({
+            "federation": { "facetsByIndex": { "sales": ["color", "margin"] } },
+            "queries": [{ "indexUid": "sales", "q": "shoes" }]
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["message"], @r###""The provided tenant token cannot access the `margin` attribute.""###);
+}
@@ -225,7 +225,7 @@ async fn list_batch_filter_error() {
     assert_eq!(code, 400, "{}", response);
     meili_snap::snapshot!(meili_snap::json_string!(response), @r#"
     {
-      "message": "Unknown parameter `lol`: expected one of `limit`, `from`, `reverse`, `batchUids`, `uids`, `canceledBy`, `types`, `statuses`, `indexUids`, `afterEnqueuedAt`, `beforeEnqueuedAt`, `afterStartedAt`, `beforeStartedAt`, `afterFinishedAt`, `beforeFinishedAt`",
+      "message": "Unknown parameter `lol`: expected one of `limit`, `from`, `reverse`, `batchUids`, `uids`, `canceledBy`, `labels`, `errorCodes`, `minDuration`, `maxDuration`, `types`, `statuses`, `indexUids`, `afterEnqueuedAt`, `beforeEnqueuedAt`, `afterStartedAt`, `beforeStartedAt`, `afterFinishedAt`, `beforeFinishedAt`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -281,7 +281,8 @@ async fn test_summarized_document_addition_or_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -303,7 +304,8 @@ async fn test_summarized_document_addition_or_update() {
           "test": 1
         },
         "progressTrace": "[progressTrace]",
-        "writeChannelCongestion": "[writeChannelCongestion]"
+        "writeChannelCongestion": "[writeChannelCongestion]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -322,7 +324,8 @@ async fn test_summarized_document_addition_or_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -344,7 +347,8 @@ async fn test_summarized_document_addition_or_update() {
           "test": 1
         },
         "progressTrace": "[progressTrace]",
-        "writeChannelCongestion": "[writeChannelCongestion]"
+        "writeChannelCongestion": "[writeChannelCongestion]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -367,7 +371,8 @@ async fn test_summarized_delete_documents_by_batch() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -388,7 +393,8 @@ async fn test_summarized_delete_documents_by_batch() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -407,7 +413,8 @@ async fn test_summarized_delete_documents_by_batch() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -428,7 +435,8 @@ async fn test_summarized_delete_documents_by_batch() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -453,7 +461,8 @@ async fn test_summarized_delete_documents_by_filter() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -475,7 +484,8 @@ async fn test_summarized_delete_documents_by_filter() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -495,7 +505,8 @@ async fn test_summarized_delete_documents_by_filter() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -517,7 +528,8 @@ async fn test_summarized_delete_documents_by_filter() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -537,7 +549,8 @@ async fn test_summarized_delete_documents_by_filter() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r#"
     {
@@ -559,7 +572,8 @@ async fn test_summarized_delete_documents_by_filter() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -583,7 +597,8 @@ async fn test_summarized_delete_document_by_id() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r#"
     {
@@ -604,7 +619,8 @@ async fn test_summarized_delete_document_by_id() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -623,7 +639,8 @@ async fn test_summarized_delete_document_by_id() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r#"
     {
@@ -644,7 +661,8 @@ async fn test_summarized_delete_document_by_id() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -679,7 +697,8 @@ async fn test_summarized_settings_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -709,7 +728,8 @@ async fn test_summarized_settings_update() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -732,7 +752,8 @@ async fn test_summarized_index_creation() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -750,7 +771,8 @@ async fn test_summarized_index_creation() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -768,7 +790,8 @@ async fn test_summarized_index_creation() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -788,7 +811,8 @@ async fn test_summarized_index_creation() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -921,7 +945,8 @@ async fn test_summarized_index_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -939,7 +964,8 @@ async fn test_summarized_index_update() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -957,7 +983,8 @@ async fn test_summarized_index_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -977,7 +1004,8 @@ async fn test_summarized_index_update() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -998,7 +1026,8 @@ async fn test_summarized_index_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r#"
     {
@@ -1016,7 +1045,8 @@ async fn test_summarized_index_update() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1034,7 +1064,8 @@ async fn test_summarized_index_update() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1054,7 +1085,8 @@ async fn test_summarized_index_update() {
         "indexUids": {
           "test": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1080,7 +1112,8 @@ async fn test_summarized_index_swap() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1105,7 +1138,8 @@ async fn test_summarized_index_swap() {
           "indexSwap": 1
         },
         "indexUids": {},
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1129,7 +1163,8 @@ async fn test_summarized_index_swap() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1147,7 +1182,8 @@ async fn test_summarized_index_swap() {
         "indexUids": {
           "doggos": 1
         },
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1173,7 +1209,8 @@ async fn test_summarized_batch_cancelation() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1193,7 +1230,8 @@ async fn test_summarized_batch_cancelation() {
           "taskCancelation": 1
         },
         "indexUids": {},
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1219,7 +1257,8 @@ async fn test_summarized_batch_deletion() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1239,7 +1278,8 @@ async fn test_summarized_batch_deletion() {
           "taskDeletion": 1
         },
         "indexUids": {},
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -1262,7 +1302,8 @@ async fn test_summarized_dump_creation() {
             ".startedAt" => "[date]",
             ".finishedAt" => "[date]",
             ".stats.progressTrace" => "[progressTrace]",
-            ".stats.writeChannelCongestion" => "[writeChannelCongestion]"
+            ".stats.writeChannelCongestion" => "[writeChannelCongestion]",
+            ".stats.resourceUsage" => "[resourceUsage]"
         },
         @r###"
     {
@@ -1280,7 +1321,8 @@ async fn test_summarized_dump_creation() {
           "dumpCreation": 1
         },
         "indexUids": {},
-        "progressTrace": "[progressTrace]"
+        "progressTrace": "[progressTrace]",
+        "resourceUsage": "[resourceUsage]"
       },
       "duration": "[duration]",
       "startedAt": "[date]",
@@ -84,6 +84,21 @@ impl<'a> Index<'a, Owned> {
         self.service.delete(url).await
     }
 
+    pub async fn get_source(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/source", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn put_source(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/source", urlencode(self.uid.as_ref()));
+        self.service.put_encoded(url, body, self.encoder).await
+    }
+
+    pub async fn delete_source(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/source", urlencode(self.uid.as_ref()));
+        self.service.delete(url).await
+    }
+
     pub async fn add_documents(
         &self,
         documents: Value,
@@ -102,6 +117,11 @@ impl<'a> Index<'a, Owned> {
         self.service.post_str(url, payload, headers).await
     }
 
+    pub async fn add_avro_documents(&self, payload: Vec<u8>) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents", urlencode(self.uid.as_ref()));
+        self.service.post_bytes(url, payload, vec![("content-type", "application/avro")]).await
+    }
+
     pub async fn update_documents(
         &self,
         documents: Value,
@@ -116,6 +136,30 @@ impl<'a> Index<'a, Owned> {
         self.service.put_encoded(url, documents, self.encoder).await
     }
 
+    pub async fn patch_documents(
+        &self,
+        documents: Value,
+        primary_key: Option<&str>,
+    ) -> (Value, StatusCode) {
+        let url = match primary_key {
+            Some(key) => {
+                format!("/indexes/{}/documents?primaryKey={}", urlencode(self.uid.as_ref()), key)
+            }
+            None => format!("/indexes/{}/documents", urlencode(self.uid.as_ref())),
+        };
+        self.service.patch_encoded(url, documents, self.encoder).await
+    }
+
+    pub async fn json_patch_documents(&self, patches: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/json-patch", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, patches, self.encoder).await
+    }
+
+    pub async fn import_documents_from_url(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/import-url", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn raw_update_documents(
         &self,
         payload: &str,
@@ -411,6 +455,47 @@ impl<State> Index<'_, State> {
         self.service.get(url).await
     }
 
+    pub async fn get_document_revisions(&self, id: u64) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/{}/revisions", urlencode(self.uid.as_ref()), id);
+        self.service.get(url).await
+    }
+
+    pub async fn changes(&self, since: Option<u32>) -> (Value, StatusCode) {
+        let url = match since {
+            Some(since) => {
+                format!("/indexes/{}/changes?since={since}", urlencode(self.uid.as_ref()))
+            }
+            None => format!("/indexes/{}/changes", urlencode(self.uid.as_ref())),
+        };
+        self.service.get_ndjson(url).await
+    }
+
+    pub async fn restore_document_revision(&self, id: u64, revision: u64) -> (Value, StatusCode) {
+        let url = format!(
+            "/indexes/{}/documents/{}/revisions/{}/restore",
+            urlencode(self.uid.as_ref()),
+            id,
+            revision
+        );
+        self.service.post(url, json!(null)).await
+    }
+
+    pub async fn validate_documents(
+        &self,
+        documents: Value,
+        primary_key: Option<&str>,
+    ) -> (Value, StatusCode) {
+        let url = match primary_key {
+            Some(key) => format!(
+                "/indexes/{}/documents/validate?primaryKey={}",
+                urlencode(self.uid.as_ref()),
+                key
+            ),
+            None => format!("/indexes/{}/documents/validate", urlencode(self.uid.as_ref())),
+        };
+        self.service.post(url, documents).await
+    }
+
     pub async fn fetch_documents(&self, payload: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents/fetch", urlencode(self.uid.as_ref()));
         self.service.post(url, payload).await
@@ -10,7 +10,7 @@ use actix_web::dev::ServiceResponse;
 use actix_web::http::StatusCode;
 use byte_unit::{Byte, Unit};
 use clap::Parser;
-use meilisearch::option::{IndexerOpts, MaxMemory, MaxThreads, Opt};
+use meilisearch::option::{AllowedHosts, IndexerOpts, MaxMemory, MaxThreads, Opt};
 use meilisearch::setup_meilisearch;
 use once_cell::sync::Lazy;
 use tempfile::TempDir;
@@ -116,6 +116,27 @@ impl Server<Owned> {
         self.service.delete(url).await
     }
 
+    pub async fn rotate_api_key(
+        &self,
+        key: impl AsRef<str>,
+        content: Value,
+    ) -> (Value, StatusCode) {
+        let url = format!("/keys/{}/rotate", key.as_ref());
+        self.service.post(url, content).await
+    }
+
+    pub async fn rotate_master_key(&self, content: Value) -> (Value, StatusCode) {
+        self.service.post("/keys/master-key/rotate", content).await
+    }
+
+    pub async fn export_api_keys(&self) -> (Value, StatusCode) {
+        self.service.get("/keys/export").await
+    }
+
+    pub async fn import_api_keys(&self, content: Value) -> (Value, StatusCode) {
+        self.service.post("/keys/import", content).await
+    }
+
     /// Returns a view to an index. There is no guarantee that the index exists.
     pub fn index(&self, uid: impl AsRef<str>) -> Index<'_> {
         self.index_with_encoder(uid, Encoder::Plain)
@@ -181,6 +202,10 @@ impl Server<Owned> {
         self.service.patch("/network", value).await
     }
 
+    pub async fn set_index_aliases(&self, value: Value) -> (Value, StatusCode) {
+        self.service.patch("/aliases", value).await
+    }
+
     pub async fn get_metrics(&self) -> (Value, StatusCode) {
         self.service.get("/metrics").await
     }
@@ -380,10 +405,39 @@ impl<State> Server<State> {
         self.service.post("/dumps", json!(null)).await
     }
 
+    pub async fn create_dump_with_parameters(&self, parameters: Value) -> (Value, StatusCode) {
+        self.service.post("/dumps", parameters).await
+    }
+
+    pub async fn import_dump(&self, dump: Vec<u8>, query: &str) -> (Value, StatusCode) {
+        let url = format!("/dumps/import?{query}");
+        self.service.post_bytes(url, dump, vec![("content-type", "application/octet-stream")]).await
+    }
+
+    pub async fn import_dump_from_url(&self, value: Value, query: &str) -> (Value, StatusCode) {
+        self.service.post(format!("/dumps/import?{query}"), value).await
+    }
+
     pub async fn create_snapshot(&self) -> (Value, StatusCode) {
         self.service.post("/snapshots", json!(null)).await
     }
 
+    pub async fn restore_snapshot(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/snapshots/restore", value).await
+    }
+
+    pub async fn restore_points(&self) -> (Value, StatusCode) {
+        self.service.get("/snapshots/restore-points").await
+    }
+
+    pub async fn restore_index_from_snapshot(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/snapshots/restore-index", value).await
+    }
+
+    pub async fn restore_index_from_dump(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/dumps/restore-index", value).await
+    }
+
     pub async fn index_swap(&self, value: Value) -> (Value, StatusCode) {
         self.service.post("/swap-indexes", value).await
     }
@@ -441,6 +495,10 @@ impl<State> Server<State> {
     pub async fn get_network(&self) -> (Value, StatusCode) {
         self.service.get("/network").await
     }
+
+    pub async fn get_index_aliases(&self) -> (Value, StatusCode) {
+        self.service.get("/aliases").await
+    }
 }
 
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
@@ -461,6 +519,8 @@ pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
             max_indexing_threads: MaxThreads::from_str("2").unwrap(),
         },
         experimental_enable_metrics: false,
+        // the documents are imported from the mock servers, listening on the loopback
+        experimental_import_url_allowed_hosts: Some(AllowedHosts(vec!["127.0.0.1".to_string()])),
         ..Parser::parse_from(None as Option<&str>)
     }
 }
@@ -57,6 +57,20 @@ impl Service {
         self.request(req).await
     }
 
+    /// Send a test post request from a binary body.
+    pub async fn post_bytes(
+        &self,
+        url: impl AsRef<str>,
+        body: Vec<u8>,
+        headers: Vec<(&str, &str)>,
+    ) -> (Value, StatusCode) {
+        let mut req = test::TestRequest::post().uri(url.as_ref()).set_payload(body);
+        for header in headers {
+            req = req.insert_header(header);
+        }
+        self.request(req).await
+    }
+
     pub async fn get(&self, url: impl AsRef<str>) -> (Value, StatusCode) {
         let req = test::TestRequest::get().uri(url.as_ref());
         self.request(req).await
@@ -163,6 +177,40 @@ impl Service {
         (response, status_code)
     }
 
+    /// Send a test get request to a route answering with NDJSON, its lines are returned as an array.
+    pub async fn get_ndjson(&self, url: impl AsRef<str>) -> (Value, StatusCode) {
+        let app = self.init_web_app().await;
+
+        let mut req = test::TestRequest::get().uri(url.as_ref());
+        if let Some(api_key) = &self.api_key {
+            req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        let status_code = res.status();
+
+        let body = test::read_body(res).await;
+        let lines = serde_json::Deserializer::from_slice(&body)
+            .into_iter()
+            .collect::<Result<Vec<serde_json::Value>, _>>()
+            .unwrap_or_default();
+        (Value(serde_json::Value::Array(lines)), status_code)
+    }
+
+    /// Send a test get request to a route answering with a binary body, which is returned as is.
+    pub async fn get_bytes(&self, url: impl AsRef<str>) -> (Vec<u8>, StatusCode) {
+        let app = self.init_web_app().await;
+
+        let mut req = test::TestRequest::get().uri(url.as_ref());
+        if let Some(api_key) = &self.api_key {
+            req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        let status_code = res.status();
+
+        let body = test::read_body(res).await;
+        (body.to_vec(), status_code)
+    }
+
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
         let bytes = serde_json::to_string(&body).expect("Failed to serialize test data to json");
         let encoded_body = encoder.encode(bytes);
@@ -269,6 +269,35 @@ async fn add_single_document_with_every_encoding() {
     }
 }
 
+#[actix_rt::test]
+async fn error_add_encoded_documents_too_large_once_decoded() {
+    // the payload limit of the test server is 10MiB
+    let documents: Vec<_> = (0..12_000)
+        .map(|id| json!({ "id": id, "content": "Bouvier Bernois ".repeat(64) }))
+        .collect();
+    let documents = serde_json::to_string(&documents).unwrap();
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    for encoder in Encoder::iterator().filter(|encoder| encoder.header().is_some()) {
+        let body: bytes::Bytes = encoder.encode(documents.clone()).into();
+        assert!(body.len() < 10 * 1024 * 1024);
+        let req = test::TestRequest::post()
+            .uri("/indexes/dog/documents")
+            .set_payload(body)
+            .insert_header(("content-type", "application/json"))
+            .insert_header(encoder.header().unwrap())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let status_code = res.status();
+        let body = test::read_body(res).await;
+        let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+        assert_eq!(status_code, 413);
+        assert_eq!(response["code"], "payload_too_large");
+    }
+}
+
 #[actix_rt::test]
 async fn add_csv_document() {
     let server = Server::new().await;
@@ -474,6 +503,89 @@ async fn add_csv_document_with_custom_delimiter() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_avro_document() {
+    use apache_avro::types::{Record, Value as AvroValue};
+    use apache_avro::{Schema, Writer};
+
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let schema = Schema::parse_str(
+        r#"{
+            "type": "record",
+            "name": "pet",
+            "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "name", "type": "string" },
+                { "name": "race", "type": ["null", "string"] }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let mut writer = Writer::new(&schema, Vec::new());
+    for (id, name, race) in [(0, "jean", Some("bernese mountain")), (1, "jorts", None)] {
+        let mut record = Record::new(writer.schema()).unwrap();
+        record.put("id", id as i64);
+        record.put("name", name);
+        match race {
+            Some(race) => record.put("race", AvroValue::Union(1, Box::new(race.into()))),
+            None => record.put("race", AvroValue::Union(0, Box::new(AvroValue::Null))),
+        }
+        writer.append(record).unwrap();
+    }
+    let payload = writer.into_inner().unwrap();
+
+    let (response, code) = index.add_avro_documents(payload).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":2}"###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "name": "jean",
+          "race": "bernese mountain"
+        },
+        {
+          "id": 1,
+          "name": "jorts",
+          "race": null
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_add_malformed_avro_documents() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let (response, code) = index.add_avro_documents(b"Obj\x01 not really".to_vec()).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+
+    // a record coming from a Kafka topic, framed with the id of its schema in a registry
+    let (response, code) = index.add_avro_documents(vec![0, 0, 0, 0, 42, 2, 8]).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `avro` payload provided is malformed: `records framed with a schema registry header (schema id 42) are not supported, send an Avro object container file embedding its schema instead`.",
+      "code": "malformed_payload",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#malformed_payload"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types_error() {
     let server = Server::new().await;
@@ -535,7 +647,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -556,7 +668,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -590,7 +702,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -610,7 +722,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -2987,3 +3099,106 @@ async fn dry_register_file() {
     "###);
     snapshot!(code, @"404 Not Found");
 }
+
+#[actix_rt::test]
+async fn add_documents_on_conflict() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.add_documents(json!([{ "id": 1, "doggo": "kefir", "age": 2 }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    // the existing document is kept while the new one is added
+    let documents = r#"[{ "id": 1, "doggo": "intel" }, { "id": 2, "doggo": "bouvier" }]"#;
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json")],
+            "?onConflict=skip",
+        )
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":1}"###);
+
+    // a single existing document rejects the whole payload
+    let documents = r#"[{ "id": 3, "doggo": "patou" }, { "id": 2, "doggo": "intel" }]"#;
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json")],
+            "?onConflict=fail",
+        )
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Document `2` already exists.",
+      "code": "document_already_exists",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_already_exists"
+    }
+    "###);
+
+    // the existing document is merged even though it is sent with POST
+    let documents = r#"[{ "id": 1, "doggo": "intel" }]"#;
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json")],
+            "?onConflict=merge",
+        )
+        .await;
+    assert_eq!(code, 202, "{response}");
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .get_all_documents(GetAllDocumentsOptions { limit: Some(10), ..Default::default() })
+        .await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "doggo": "intel",
+        "age": 2
+      },
+      {
+        "id": 2,
+        "doggo": "bouvier"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_add_documents_bad_on_conflict() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = r#"[{ "id": 1, "doggo": "kefir" }]"#;
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json")],
+            "?onConflict=ignore",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_on_conflict""###);
+
+    let url = format!("/indexes/{}/documents?onConflict=skip", index.uid);
+    let (response, code) = index.service.patch(url, json!([{ "id": 1, "doggo": "kefir" }])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`onConflict` cannot be used when patching documents.",
+      "code": "invalid_document_on_conflict",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_on_conflict"
+    }
+    "###);
+}
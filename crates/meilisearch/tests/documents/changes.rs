@@ -0,0 +1,165 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn stream_the_document_changes() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let index = server.index("pets");
+    let (response, _code) = index.create(None).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, code) = index.changes(None).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response[0]), @r###"
+    {
+      "message": "Streaming the changes of the documents requires launching Meilisearch with the `--experimental-change-stream` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_change_stream: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("pets");
+
+    let documents = json!([{ "id": 1, "name": "kefir" }, { "id": 2, "name": "intel" }]);
+    let (response, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.delete_document(1).await;
+    let deletion = index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.update_documents(json!([{ "id": 2, "age": 3 }]), None).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.clear_all_documents().await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.changes(None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    [
+      {
+        "taskUid": 0,
+        "type": "upsert",
+        "document": {
+          "id": 1,
+          "name": "kefir"
+        }
+      },
+      {
+        "taskUid": 0,
+        "type": "upsert",
+        "document": {
+          "id": 2,
+          "name": "intel"
+        }
+      },
+      {
+        "taskUid": 1,
+        "type": "deletion",
+        "documentId": "1"
+      },
+      {
+        "taskUid": 2,
+        "type": "update",
+        "document": {
+          "id": 2,
+          "age": 3
+        }
+      },
+      {
+        "taskUid": 3,
+        "type": "clear"
+      }
+    ]
+    "###);
+
+    // resuming from a task only streams the changes made after it
+    let (response, code) = index.changes(Some(deletion.uid() as u32)).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    [
+      {
+        "taskUid": 2,
+        "type": "update",
+        "document": {
+          "id": 2,
+          "age": 3
+        }
+      },
+      {
+        "taskUid": 3,
+        "type": "clear"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn stream_the_document_changes_to_a_filtered_key() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { experimental_change_stream: true, ..default_settings(dir.path()) };
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("docs");
+    let (response, _code) = index.update_settings_filterable_attributes(json!(["tenant"])).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let documents = json!([
+        { "id": 1, "tenant": "acme" },
+        { "id": 2, "tenant": "umbrella" },
+        { "id": 3, "tenant": "acme" },
+    ]);
+    let (response, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.delete_document(2).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.patch_documents(json!([{ "id": 1, "name": null }]), None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let content = json!({
+        "indexes": ["docs"],
+        "actions": ["documents.get"],
+        "expiresAt": null,
+        "filter": "tenant = acme"
+    });
+    let (response, code) = server.add_api_key(content).await;
+    snapshot!(code, @"201 Created");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    // the ids of the deleted documents and the merge patches are not sent, only a `resync`
+    let (response, code) = server.index("docs").changes(None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    [
+      {
+        "taskUid": 1,
+        "type": "upsert",
+        "document": {
+          "id": 1,
+          "tenant": "acme"
+        }
+      },
+      {
+        "taskUid": 1,
+        "type": "upsert",
+        "document": {
+          "id": 3,
+          "tenant": "acme"
+        }
+      },
+      {
+        "taskUid": 2,
+        "type": "resync"
+      },
+      {
+        "taskUid": 3,
+        "type": "resync"
+      }
+    ]
+    "###);
+}
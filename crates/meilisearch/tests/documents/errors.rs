@@ -136,10 +136,17 @@ async fn get_all_documents_bad_filter() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `doggo`.\n1:6 doggo",
+      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `doggo`.\n1:6 doggo",
       "code": "invalid_document_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "doggo",
+        "snippet": "doggo\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 
@@ -150,7 +157,14 @@ async fn get_all_documents_bad_filter() {
       "message": "Attribute `doggo` is not filterable. This index does not have configured filterable attributes.\n1:6 doggo=bernese",
       "code": "invalid_document_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "doggo",
+        "snippet": "doggo=bernese\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 }
@@ -258,7 +272,7 @@ async fn replace_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -270,7 +284,7 @@ async fn replace_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -286,7 +300,7 @@ async fn update_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -298,7 +312,7 @@ async fn update_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -314,7 +328,7 @@ async fn replace_documents_bad_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -330,7 +344,7 @@ async fn update_documents_bad_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/avro`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -525,10 +539,17 @@ async fn delete_document_by_filter() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `hello`.\n1:6 hello",
+      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `hello`.\n1:6 hello",
       "code": "invalid_document_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "hello",
+        "snippet": "hello\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 
@@ -726,10 +747,17 @@ async fn fetch_document_by_filter() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `cool doggo`.\n1:11 cool doggo",
+      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `cool doggo`.\n1:11 cool doggo",
       "code": "invalid_document_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "cool doggo",
+        "snippet": "cool doggo\n^^^^^^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 
@@ -740,7 +768,14 @@ async fn fetch_document_by_filter() {
       "message": "Attribute `doggo` is not filterable. Available filterable attribute patterns are: `color`.\n1:6 doggo = bernese",
       "code": "invalid_document_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "doggo",
+        "snippet": "doggo = bernese\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 }
@@ -568,11 +568,11 @@ async fn get_document_by_ids() {
     {
       "results": [
         {
-          "id": 1,
+          "id": 2,
           "color": "blue"
         },
         {
-          "id": 2,
+          "id": 1,
           "color": "blue"
         }
       ],
@@ -586,7 +586,7 @@ async fn get_document_by_ids() {
 
     let (response, code) =
         index.fetch_documents(json!({ "offset": 1, "limit": 1, "ids": ["0", 0, 3] })).await;
-    let (response2, code2) = index.get_all_documents_raw("?ids=3,0&offset=1&limit=1").await;
+    let (response2, code2) = index.get_all_documents_raw("?ids=0,3&offset=1&limit=1").await;
     snapshot!(code, @"200 OK");
     snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
     {
@@ -1034,3 +1034,101 @@ async fn get_document_with_vectors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_documents_sorted() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) =
+        index.update_settings(json!({ "sortableAttributes": ["price", "name"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "price": 12 },
+                { "id": 1, "name": "intel", "price": 5 },
+                { "id": 2, "name": "bouvier", "price": 12 },
+                { "id": 3, "name": "patou" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.fetch_documents(json!({ "sort": ["price:desc", "name:asc"] })).await;
+    let (response2, code2) = index.get_all_documents_raw("?sort=price:desc,name:asc").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 2,
+          "name": "bouvier",
+          "price": 12
+        },
+        {
+          "id": 0,
+          "name": "kefir",
+          "price": 12
+        },
+        {
+          "id": 1,
+          "name": "intel",
+          "price": 5
+        },
+        {
+          "id": 3,
+          "name": "patou"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 4
+    }
+    "###);
+    assert_eq!(code, code2);
+    assert_eq!(response, response2);
+
+    let (response, code) = index
+        .fetch_documents(json!({ "sort": ["price:asc"], "filter": "price EXISTS", "offset": 1, "limit": 1, "fields": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 0
+        }
+      ],
+      "offset": 1,
+      "limit": 1,
+      "total": 3
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_get_documents_bad_sort() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.update_settings(json!({ "sortableAttributes": ["price"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.fetch_documents(json!({ "sort": ["name:asc"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `name` is not sortable. Available sortable attributes are: `price`.",
+      "code": "invalid_document_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_sort"
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("?sort=_geoPoint(0,0):asc").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_sort""###);
+}
@@ -0,0 +1,172 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common::{default_settings, GetAllDocumentsOptions, Server};
+use crate::json;
+
+async fn create_documents_mock() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/doggos"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "{\"id\":1,\"name\":\"kefir\"}\n{\"id\":2,\"name\":\"intel\"}\n",
+            "application/x-ndjson",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    // object storages usually don't know the type of the files they serve
+    Mock::given(method("GET"))
+        .and(path("/exports/doggos.csv"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("id;name\n3;doggo\n", "application/octet-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/doggos.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw("kefir", "text/plain"))
+        .mount(&mock_server)
+        .await;
+
+    mock_server
+}
+
+#[actix_rt::test]
+async fn import_documents_from_url() {
+    let mock_server = create_documents_mock().await;
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .import_documents_from_url(json!({ "url": format!("{}/doggos", mock_server.uri()) }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response["type"], @r###""documentAdditionOrUpdate""###);
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":2}"###);
+
+    let (response, code) = index
+        .import_documents_from_url(json!({
+            "url": format!("{}/exports/doggos.csv?X-Amz-Signature=kefir", mock_server.uri()),
+            "csvDelimiter": ";",
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":1,"indexedDocuments":1}"###);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "kefir"
+      },
+      {
+        "id": 2,
+        "name": "intel"
+      },
+      {
+        "id": "3",
+        "name": "doggo"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn import_documents_from_url_failures() {
+    let mock_server = create_documents_mock().await;
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    // the documents are downloaded before the task is registered
+    let (response, code) = index
+        .import_documents_from_url(json!({ "url": format!("{}/unknown.json", mock_server.uri()) }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""document_import_url_failed""###);
+
+    let (response, code) = index
+        .import_documents_from_url(json!({ "url": format!("{}/doggos.txt", mock_server.uri()) }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""document_import_url_failed""###);
+
+    // only the allowed hosts can be reached
+    let url = mock_server.uri().replace("127.0.0.1", "localhost");
+    let (response, code) =
+        index.import_documents_from_url(json!({ "url": format!("{url}/doggos") })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""document_import_url_failed""###);
+}
+
+#[actix_rt::test]
+async fn import_documents_from_url_disabled() {
+    let mock_server = create_documents_mock().await;
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { experimental_import_url_allowed_hosts: None, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("doggos");
+
+    let (response, code) = index
+        .import_documents_from_url(json!({ "url": format!("{}/doggos", mock_server.uri()) }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Importing documents from a URL requires launching Meilisearch with the `--experimental-import-url-allowed-hosts` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_import_documents_from_url() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.import_documents_from_url(json!({ "url": "ftp://example.com/doggos.json" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid url `ftp://example.com/doggos.json`: the `ftp` scheme is not supported, the url must use `http` or `https`.",
+      "code": "invalid_document_import_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_import_url"
+    }
+    "###);
+
+    let (response, code) = index.import_documents_from_url(json!({ "url": "doggos.json" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid url `doggos.json`: relative URL without a base.",
+      "code": "invalid_document_import_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_import_url"
+    }
+    "###);
+
+    let (response, code) = index.import_documents_from_url(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `url`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
@@ -1,5 +1,9 @@
 mod add_documents;
+mod changes;
 mod delete_documents;
 mod errors;
 mod get_documents;
+mod import_url;
+mod revisions;
 mod update_documents;
+mod validate_documents;
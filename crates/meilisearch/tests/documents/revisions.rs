@@ -0,0 +1,115 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn list_and_restore_document_revisions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "maxDocumentRevisions": 2 })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    for name in ["kefir", "intel", "bobby", "doggo"] {
+        let documents = json!([{ "id": 1, "name": name }]);
+        let (task, _code) = index.add_documents(documents, Some("id")).await;
+        index.wait_task(task.uid()).await.succeeded();
+    }
+
+    // only the two most recent previous versions are kept
+    let (response, code) = index.get_document_revisions(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].recordedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "revision": 3,
+          "recordedAt": "[date]",
+          "document": {
+            "id": 1,
+            "name": "bobby"
+          }
+        },
+        {
+          "revision": 2,
+          "recordedAt": "[date]",
+          "document": {
+            "id": 1,
+            "name": "intel"
+          }
+        }
+      ]
+    }
+    "###);
+
+    let (task, code) = index.restore_document_revision(1, 2).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "intel"
+    }
+    "###);
+
+    // the restored document replaced a version that is now a revision itself
+    let (response, _code) = index.get_document_revisions(1).await;
+    snapshot!(json_string!(response["results"][0]["document"]), @r###"
+    {
+      "id": 1,
+      "name": "doggo"
+    }
+    "###);
+
+    // a deleted document can be restored
+    let (task, _code) = index.delete_document(1).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, _code) = index.get_document_revisions(1).await;
+    let revision = response["results"][0]["revision"].as_u64().unwrap();
+    let (task, _code) = index.restore_document_revision(1, revision).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "intel"
+    }
+    "###);
+
+    let (response, code) = index.restore_document_revision(1, 1).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Revision `1` of the document `1` not found.",
+      "code": "document_revision_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_revision_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn no_revisions_without_setting() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    for name in ["kefir", "intel"] {
+        let documents = json!([{ "id": 1, "name": name }]);
+        let (task, _code) = index.add_documents(documents, Some("id")).await;
+        index.wait_task(task.uid()).await.succeeded();
+    }
+
+    let (response, code) = index.get_document_revisions(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
@@ -93,6 +93,227 @@ async fn update_document() {
     "###);
 }
 
+#[actix_rt::test]
+async fn patch_document() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "Kefir",
+            "info": { "age": 2, "color": "white", "owner": { "name": "Tamo", "city": "Paris" } },
+            "tags": ["dog"],
+        }
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "info": { "age": 3, "color": null, "owner": { "city": "Lyon" } },
+            "tags": null,
+            "breed": "patou",
+        },
+        {
+            "id": 2,
+            "title": "Intel",
+            "info": { "color": null },
+        }
+    ]);
+    let (response, code) = index.patch_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":2}"###);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "id": 1,
+      "title": "Kefir",
+      "info": {
+        "age": 3,
+        "owner": {
+          "name": "Tamo",
+          "city": "Lyon"
+        }
+      },
+      "breed": "patou"
+    }
+    "###);
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "id": 2,
+      "title": "Intel",
+      "info": {}
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn patch_document_after_update_in_same_batch() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .add_documents(json!([{ "id": 1, "info": { "age": 2, "color": "white" } }]), None)
+        .await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    // the update and the patch can be processed in the same batch, the patch must see the update
+    let (_response, code) =
+        index.update_documents(json!([{ "id": 1, "name": "kefir" }]), None).await;
+    assert_eq!(code, 202);
+    let (response, code) = index
+        .patch_documents(json!([{ "id": 1, "name": null, "info": { "color": "black" } }]), None)
+        .await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "id": 1,
+      "info": {
+        "age": 2,
+        "color": "black"
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn json_patch_document() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "Kefir",
+            "info": { "age": 2, "owner": { "name": "Tamo", "city": "Paris" } },
+            "tags": ["dog", "white"],
+        }
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let patches = json!([
+        {
+            "id": 1,
+            "operations": [
+                { "op": "test", "path": "/title", "value": "Kefir" },
+                { "op": "replace", "path": "/info/owner/city", "value": "Lyon" },
+                { "op": "remove", "path": "/tags/1" },
+                { "op": "add", "path": "/tags/-", "value": "good boy" },
+                { "op": "move", "from": "/info/age", "path": "/age" },
+                { "op": "copy", "from": "/info/owner/name", "path": "/owner" },
+            ]
+        }
+    ]);
+    let (response, code) = index.json_patch_documents(patches).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"], @r###"{"receivedDocuments":1,"indexedDocuments":1}"###);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "id": 1,
+      "title": "Kefir",
+      "info": {
+        "owner": {
+          "name": "Tamo",
+          "city": "Lyon"
+        }
+      },
+      "tags": [
+        "dog",
+        "good boy"
+      ],
+      "age": 2,
+      "owner": "Tamo"
+    }
+    "###);
+
+    // the new fields are searchable
+    let (response, code) = index.search_post(json!({ "q": "good boy" })).await;
+    assert_eq!(code, 200);
+    snapshot!(response["hits"].as_array().unwrap().len(), @"1");
+}
+
+#[actix_rt::test]
+async fn error_json_patch_document() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.add_documents(json!([{ "id": 1, "info": { "age": 2 } }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let patches = json!([{ "id": 2, "operations": [{ "op": "add", "path": "/age", "value": 3 }] }]);
+    let (response, code) = index.json_patch_documents(patches).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Could not apply the JSON patch on the document `2`: the document does not exist.",
+      "code": "invalid_document_json_patch",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_json_patch"
+    }
+    "###);
+
+    let patches =
+        json!([{ "id": 1, "operations": [{ "op": "replace", "path": "/id", "value": 3 }] }]);
+    let (response, code) = index.json_patch_documents(patches).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Could not apply the JSON patch on the document `1`: the primary key cannot be modified.",
+      "code": "invalid_document_json_patch",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_json_patch"
+    }
+    "###);
+
+    // a failing test operation fails the task and leaves the document untouched
+    let patches = json!([{
+        "id": 1,
+        "operations": [
+            { "op": "add", "path": "/name", "value": "kefir" },
+            { "op": "test", "path": "/info/age", "value": 3 },
+        ]
+    }]);
+    let (response, code) = index.json_patch_documents(patches).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""invalid_document_json_patch""###);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "id": 1,
+      "info": {
+        "age": 2
+      }
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn update_document_gzip_encoded() {
     let server = Server::new_shared();
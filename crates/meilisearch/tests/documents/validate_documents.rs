@@ -0,0 +1,108 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn validate_documents_reports_diagnostics() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "price": 10 }]), Some("id")).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) =
+        index.update_settings(json!({ "filterableAttributes": ["price", "_geo"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 2, "price": 12 },
+        { "id": 3, "price": "cheap" },
+        { "name": "no id" },
+        { "id": 4, "_geo": { "lat": "a", "lng": 2 } },
+    ]);
+    let (response, code) = index.validate_documents(documents, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "primaryKey": "id",
+      "documentsCount": 4,
+      "valid": false,
+      "diagnostics": [
+        {
+          "position": 1,
+          "documentId": "3",
+          "errors": [],
+          "warnings": [
+            "The filterable attribute `price` holds strings while the documents of the index only hold numbers for it."
+          ]
+        },
+        {
+          "position": 2,
+          "documentId": null,
+          "errors": [
+            {
+              "message": "Document doesn't have a `id` attribute: `{\"name\":\"no id\"}`.",
+              "code": "missing_document_id",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#missing_document_id"
+            }
+          ],
+          "warnings": []
+        },
+        {
+          "position": 3,
+          "documentId": "4",
+          "errors": [
+            {
+              "message": "Could not parse latitude in the document with the id: `\"4\"`. Was expecting a finite number but instead got `\"a\"`.",
+              "code": "invalid_document_geo_field",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+            }
+          ],
+          "warnings": []
+        }
+      ]
+    }
+    "###);
+
+    // nothing has been enqueued nor indexed
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"1");
+}
+
+#[actix_rt::test]
+async fn validate_documents_infers_primary_key() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.validate_documents(json!([{ "movie_id": 1 }]), None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "primaryKey": "movie_id",
+      "documentsCount": 1,
+      "valid": true,
+      "diagnostics": []
+    }
+    "###);
+
+    let (response, code) =
+        index.validate_documents(json!([{ "movie_id": 1, "title_id": 2 }]), None).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""index_primary_key_multiple_candidates_found""###);
+}
+
+#[actix_rt::test]
+async fn validate_documents_unknown_index() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.validate_documents(json!([{ "id": 1 }]), None).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
@@ -1,7 +1,10 @@
 mod data;
 
 use meili_snap::{json_string, snapshot};
+use meilisearch::option::{AllowedHosts, ScheduleDump};
 use meilisearch::Opt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use self::data::GetDump;
 use crate::common::{default_settings, GetAllDocumentsOptions, Server};
@@ -99,7 +102,18 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -269,7 +283,18 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -438,7 +463,18 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -593,7 +629,18 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -760,7 +807,18 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -926,7 +984,18 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1081,7 +1150,18 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1248,7 +1328,18 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1414,7 +1505,18 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1569,7 +1671,18 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1736,7 +1849,18 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -1902,7 +2026,18 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###
     );
@@ -2133,7 +2268,8 @@ async fn import_dump_v6_containing_experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -2185,7 +2321,18 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###);
 
@@ -2237,6 +2384,7 @@ async fn import_dump_v6_containing_batches_and_enqueued_tasks() {
         ".results[0].duration" => "[date]",
         ".results[0].stats.progressTrace" => "[progressTrace]",
         ".results[0].stats.writeChannelCongestion" => "[writeChannelCongestion]",
+        ".results[0].stats.resourceUsage" => "[resourceUsage]",
     }), name: "batches");
 
     let (indexes, code) = server.list_indexes(None, None).await;
@@ -2256,7 +2404,8 @@ async fn import_dump_v6_containing_batches_and_enqueued_tasks() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -2361,7 +2510,8 @@ async fn generate_and_import_dump_containing_vectors() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -2425,7 +2575,18 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "###);
 
@@ -2489,3 +2650,316 @@ async fn generate_and_import_dump_containing_vectors() {
         })
         .await;
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn generate_and_import_dump_of_some_indexes() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+    let cats = server.index("cats");
+    let (response, _code) = cats.add_documents(json!([{ "id": 0, "catto": "jorts" }]), None).await;
+    cats.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.create_dump_with_parameters(json!({ "indexUids": "pets" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.indexUids`: expected an array, but found a string: `\"pets\"`",
+      "code": "invalid_dump_index_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_index_uids"
+    }
+    "###);
+
+    let (response, code) = server
+        .create_dump_with_parameters(json!({ "indexUids": ["pets"], "skipTasks": true }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    // ========= We made a dump of the pets, now we import it in an empty DB
+    drop(server);
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    opt.import_dump = Some(opt.dump_dir.join(dump_name));
+    opt.db_path = temp.path().join("data.ms");
+    let server = Server::new_with_options(opt).await.unwrap();
+
+    let (indexes, code) = server.list_indexes(None, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(indexes["results"].as_array().unwrap().len(), @"1");
+    snapshot!(indexes["results"][0]["uid"], @r###""pets""###);
+
+    let (tasks, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(tasks["results"].as_array().unwrap().len(), @"0");
+
+    let (documents, code) =
+        server.index("pets").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn generate_and_import_dump_containing_index_aliases() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = Opt { experimental_index_aliases: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+    let (_response, code) =
+        server.set_index_aliases(json!({ "aliases": { "animals": ["pets"] } })).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    // ========= We made a dump, now we import it in an empty DB
+    drop(server);
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    opt.import_dump = Some(opt.dump_dir.join(dump_name));
+    opt.db_path = temp.path().join("data.ms");
+    let server = Server::new_with_options(opt).await.unwrap();
+
+    let (response, code) = server.get_index_aliases().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "aliases": {
+        "animals": [
+          "pets"
+        ]
+      }
+    }
+    "###);
+    let (response, code) = server.index("animals").search_post(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["doggo"], @r###""kefir""###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn import_dump_into_running_instance() {
+    let temp = tempfile::tempdir().unwrap();
+    let opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    let dump = std::fs::read(opt.dump_dir.join(dump_name)).unwrap();
+
+    let (response, code) = server.import_dump(dump.clone(), "indexRenames=pets").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid index rename `pets`: it must be formatted as `nameInTheDump:newName`.",
+      "code": "invalid_dump_index_renames",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_index_renames"
+    }
+    "###);
+
+    // the pets already exist
+    let (response, code) = server.import_dump(dump.clone(), "").await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""index_already_exists""###);
+
+    let (response, code) = server.import_dump(dump, "indexRenames=pets:pets_restored").await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["type"], @r###""dumpImport""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "importedIndexes": [
+        "pets_restored"
+      ],
+      "indexedDocuments": 1
+    }
+    "###);
+
+    let (documents, code) =
+        server.index("pets_restored").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn import_dump_from_url() {
+    let temp = tempfile::tempdir().unwrap();
+    let opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    let dump = std::fs::read(opt.dump_dir.join(dump_name)).unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pets.dump"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(dump, "application/octet-stream"))
+        .mount(&mock_server)
+        .await;
+    let url = format!("{}/pets.dump", mock_server.uri());
+
+    let (response, code) =
+        server.import_dump_from_url(json!({ "url": url }), "indexRenames=pets:pets_restored").await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["details"]["importedIndexes"], @r###"["pets_restored"]"###);
+
+    // the hosts allowed by the wildcard can't resolve to a private address
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        experimental_import_url_allowed_hosts: Some(AllowedHosts(vec!["*".to_string()])),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let (response, code) = server.import_dump_from_url(json!({ "url": url }), "").await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""dump_import_failed""###);
+    let message = response["error"]["message"].as_str().unwrap();
+    assert!(message.contains("doesn't resolve to a public address"), "{message}");
+    let (indexes, _code) = server.list_indexes(None, None).await;
+    snapshot!(indexes["results"], @"[]");
+
+    // the dumps can't be downloaded without allowing some hosts
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { experimental_import_url_allowed_hosts: None, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let (response, code) = server.import_dump_from_url(json!({ "url": url }), "").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Importing a dump from a URL requires launching Meilisearch with the `--experimental-import-url-allowed-hosts` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn scheduled_dump() {
+    let temp = tempfile::tempdir().unwrap();
+    let dump_dir = tempfile::tempdir().unwrap();
+
+    let options = Opt {
+        dump_dir: dump_dir.path().to_owned(),
+        schedule_dump: ScheduleDump::Enabled(2),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let now = std::time::Instant::now();
+    loop {
+        let (value, code) = server.tasks_filter("types=dumpCreation&statuses=succeeded").await;
+        snapshot!(code, @"200 OK");
+        if let Some(task) = value["results"].as_array().and_then(|tasks| tasks.first()) {
+            let dump_uid = task["details"]["dumpUid"].as_str().unwrap();
+            assert!(dump_dir.path().join(format!("{dump_uid}.dump")).exists());
+            break;
+        }
+
+        if now.elapsed() > std::time::Duration::from_secs(30) {
+            panic!("The dump didn't schedule in 30s even though it was supposed to be scheduled every 2s");
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+#[actix_rt::test]
+async fn restore_an_index_from_a_dump() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = server.create_dump().await;
+    let response = server.wait_task(response.uid()).await.succeeded();
+    let dump_uid = response["details"]["dumpUid"].as_str().unwrap().to_string();
+
+    let (response, _code) = pets.add_documents(json!([{ "id": 1, "doggo": "intel" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.restore_index_from_dump(json!({ "indexUid": "pets" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `dumpUid`",
+      "code": "missing_restore_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_restore_dump_uid"
+    }
+    "###);
+
+    let (response, code) =
+        server.restore_index_from_dump(json!({ "indexUid": "pets", "dumpUid": "doggo" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["message"], @r###""Could not restore the index `pets`: the dump `doggo` doesn't exist.""###);
+
+    let (response, code) =
+        server.restore_index_from_dump(json!({ "indexUid": "pets", "dumpUid": dump_uid })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["type"], @r###""indexRestore""###);
+    snapshot!(json_string!(response["details"], { ".dumpUid" => "[dump_uid]" }), @r###"
+    {
+      "dumpUid": "[dump_uid]",
+      "indexedDocuments": 1
+    }
+    "###);
+
+    let (documents, code) = pets.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      }
+    ]
+    "###);
+}
@@ -24,7 +24,8 @@ async fn experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -39,7 +40,8 @@ async fn experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -54,7 +56,8 @@ async fn experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -70,7 +73,8 @@ async fn experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -86,7 +90,8 @@ async fn experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 }
@@ -109,7 +114,8 @@ async fn experimental_feature_metrics() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "readOnly": false
     }
     "###);
 
@@ -156,7 +162,7 @@ async fn errors() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
     {
-      "message": "Unknown field `NotAFeature`: expected one of `metrics`, `logsRoute`, `editDocumentsByFunction`, `containsFilter`, `network`, `getTaskDocumentsRoute`, `compositeEmbedders`",
+      "message": "Unknown field `NotAFeature`: expected one of `metrics`, `logsRoute`, `editDocumentsByFunction`, `containsFilter`, `network`, `getTaskDocumentsRoute`, `compositeEmbedders`, `readOnly`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -189,3 +195,91 @@ async fn errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn read_only_mode() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, _code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.set_features(json!({"readOnly": true})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["readOnly"], @"true");
+
+    let (response, code) = index.add_documents(json!([{ "id": 2, "title": "Shazam" }]), None).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Meilisearch cannot receive write operations because it is in read-only mode. Please retry once the `readOnly` experimental feature has been disabled.",
+      "code": "read_only_mode",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#read_only_mode"
+    }
+    "###);
+
+    let (_response, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+
+    // the search is still available
+    let (response, code) = index.search_post(json!({ "q": "carol" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["estimatedTotalHits"], @"1");
+
+    // and so are the task cancelations
+    let (_response, code) = server.cancel_tasks("statuses=enqueued").await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    let (response, code) = server.set_features(json!({"readOnly": false})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["readOnly"], @"false");
+
+    let (response, code) = index.add_documents(json!([{ "id": 2, "title": "Shazam" }]), None).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await.succeeded();
+}
+
+#[actix_rt::test]
+async fn read_only_mode_rejects_the_keys_sources_and_scheduler_writes() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let (_response, code) = server.set_features(json!({"readOnly": true})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    meili_snap::snapshot!(response["code"], @r###""read_only_mode""###);
+    let (_response, code) = server.patch_api_key(&key, json!({ "name": "search" })).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    let (_response, code) = server.delete_api_key(&key).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    let (_response, code) = server.service.post(format!("/keys/{key}/rotate"), json!({})).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+
+    let (_response, code) = server
+        .index("test")
+        .put_source(json!({ "url": "http://127.0.0.1/doggos.ndjson", "refreshInterval": 3600 }))
+        .await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    let (_response, code) = server.index("test").delete_source().await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+
+    let (_response, code) = server.service.post("/tasks/pause", json!(null)).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+    let (_response, code) = server.service.post("/tasks/resume", json!(null)).await;
+    meili_snap::snapshot!(code, @"503 Service Unavailable");
+
+    // the keys can still be read
+    let (response, code) = server.get_api_key(&key).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["name"], @"null");
+}
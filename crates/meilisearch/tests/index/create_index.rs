@@ -287,3 +287,78 @@ async fn send_task_id() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn create_index_on_read_only_replica() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        read_only_replica: true,
+        master_key: Some("MASTER_KEY".to_string()),
+        ..default_settings(temp.path())
+    };
+    let mut server = Server::new_with_options(options).await.unwrap();
+    server.use_admin_key("MASTER_KEY").await;
+
+    let (response, code) = server.service.get("/health").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "status": "available",
+      "replica": true
+    }
+    "###);
+
+    let index = server.index("catto");
+    let (response, code) = index.create(None).await;
+    snapshot!(code, @"503 Service Unavailable");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Meilisearch cannot receive write operations because it is a read-only replica. Please send them to the primary instance.",
+      "code": "read_only_mode",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#read_only_mode"
+    }
+    "###);
+
+    // the keys can't be modified either
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    snapshot!(code, @"503 Service Unavailable");
+    snapshot!(response["code"], @r###""read_only_mode""###);
+
+    // only the primary, authenticated with the master key, can replicate its tasks
+    let app = server.init_web_app().await;
+    let create_index = |api_key: &str| {
+        let body = serde_json::to_string(&json!({ "uid": "doggo" })).unwrap();
+        test::TestRequest::post()
+            .uri("/indexes")
+            .insert_header(("TaskId", "25"))
+            .insert_header(("Authorization", format!("Bearer {api_key}")))
+            .insert_header(ContentType::json())
+            .set_payload(body)
+            .to_request()
+    };
+    let admin_key = server.service.api_key.clone().unwrap();
+    let res = test::call_service(&app, create_index(&admin_key)).await;
+    snapshot!(res.status(), @"503 Service Unavailable");
+
+    // the tasks replicated by the primary keep their uid
+    let res = test::call_service(&app, create_index("MASTER_KEY")).await;
+    snapshot!(res.status(), @"202 Accepted");
+    let bytes = test::read_body(res).await;
+    let response = serde_json::from_slice::<Value>(&bytes).expect("Expecting valid json");
+    snapshot!(response["taskUid"], @"25");
+    server.wait_task(25).await.succeeded();
+
+    server.use_api_key("MASTER_KEY");
+    let (_response, code) = server
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    snapshot!(code, @"201 Created");
+
+    // the searches are still served
+    let (response, code) = server.index("doggo").search_post(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"0");
+}
@@ -2,5 +2,6 @@ mod create_index;
 mod delete_index;
 mod errors;
 mod get_index;
+mod source;
 mod stats;
 mod update_index;
@@ -0,0 +1,248 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common::{default_settings, GetAllDocumentsOptions, Server};
+use crate::json;
+
+async fn create_source_mock() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/doggos.ndjson"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "{\"id\":1,\"name\":\"kefir\"}\n{\"id\":2,\"name\":\"intel\"}\n",
+            "application/x-ndjson",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    mock_server
+}
+
+#[actix_rt::test]
+async fn index_source_refreshes_documents() {
+    let mock_server = create_source_mock().await;
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 42, "name": "doggo" }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .put_source(json!({
+            "url": format!("{}/doggos.ndjson", mock_server.uri()),
+            "refreshInterval": 3600,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let task_uid = response["lastRefreshTaskUid"].as_u64().unwrap();
+    snapshot!(json_string!(response, { ".url" => "[url]", ".nextRefreshAt" => "[date]", ".lastRefreshTaskUid" => "[uid]" }), @r###"
+    {
+      "url": "[url]",
+      "primaryKey": null,
+      "csvDelimiter": null,
+      "refreshInterval": 3600,
+      "mode": "delta",
+      "nextRefreshAt": "[date]",
+      "lastRefreshTaskUid": "[uid]"
+    }
+    "###);
+
+    let response = index.wait_task(task_uid).await.succeeded();
+    snapshot!(response["type"], @r###""documentAdditionOrUpdate""###);
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":2}"###);
+
+    // the documents that are not served by the source are kept in delta mode
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 42,
+        "name": "doggo"
+      },
+      {
+        "id": 1,
+        "name": "kefir"
+      },
+      {
+        "id": 2,
+        "name": "intel"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.get_source().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["lastRefreshTaskUid"].as_u64().unwrap() == task_uid, @"true");
+
+    let (_response, code) = index.delete_source().await;
+    snapshot!(code, @"204 No Content");
+
+    let (response, code) = index.get_source().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_source_not_found""###);
+}
+
+#[actix_rt::test]
+async fn index_source_full_refresh_deletes_missing_documents() {
+    let mock_server = create_source_mock().await;
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 42, "name": "doggo" }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .put_source(json!({
+            "url": format!("{}/doggos.ndjson", mock_server.uri()),
+            "refreshInterval": 86400,
+            "mode": "full",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["mode"], @r###""full""###);
+    index.wait_task(response["lastRefreshTaskUid"].as_u64().unwrap()).await.succeeded();
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "kefir"
+      },
+      {
+        "id": 2,
+        "name": "intel"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_index_source() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .put_source(json!({ "url": "https://example.com/doggos.ndjson", "refreshInterval": 3600 }))
+        .await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_source().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response, { ".message" => "[message]" }), @r###"
+    {
+      "message": "[message]",
+      "code": "index_source_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_source_not_found"
+    }
+    "###);
+
+    let (response, code) = index.delete_source().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_source_not_found""###);
+
+    let (response, code) = index
+        .put_source(json!({ "url": "ftp://example.com/doggos.json", "refreshInterval": 3600 }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_import_url""###);
+
+    let (response, code) = index
+        .put_source(json!({ "url": "https://example.com/doggos.ndjson", "refreshInterval": 10 }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.refreshInterval`: the refresh interval must be between 60 and 31536000 seconds, found `10`.",
+      "code": "invalid_index_source_refresh_interval",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_source_refresh_interval"
+    }
+    "###);
+
+    let (response, code) = index
+        .put_source(json!({
+            "url": "https://example.com/doggos.ndjson",
+            "refreshInterval": 3600,
+            "mode": "weekly",
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `weekly` at `.mode`: expected one of `full`, `delta`",
+      "code": "invalid_index_source_mode",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_source_mode"
+    }
+    "###);
+
+    let (response, code) =
+        index.put_source(json!({ "url": "https://example.com/doggos.ndjson" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""Missing field `refreshInterval`""###);
+
+    // the source is not registered when its documents can't be downloaded
+    let (response, code) = index
+        .put_source(json!({ "url": "https://example.com/doggos.ndjson", "refreshInterval": 3600 }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""document_import_url_failed""###);
+
+    let (_response, code) = index.get_source().await;
+    snapshot!(code, @"404 Not Found");
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn index_source_is_kept_in_dumps() {
+    let mock_server = create_source_mock().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+    let index = server.index("doggos");
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) = index
+        .put_source(json!({
+            "url": format!("{}/doggos.ndjson", mock_server.uri()),
+            "refreshInterval": 3600,
+            "mode": "full",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    index.wait_task(response["lastRefreshTaskUid"].as_u64().unwrap()).await.succeeded();
+
+    let (response, _code) = server.create_dump().await;
+    let response = server.wait_task(response.uid()).await.succeeded();
+    drop(server);
+
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    opt.import_dump = Some(opt.dump_dir.join(dump_name));
+    opt.db_path = temp.path().join("data.ms");
+    let server = Server::new_with_options(opt).await.unwrap();
+
+    let (response, code) = server.index("doggos").get_source().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".url" => "[url]", ".nextRefreshAt" => "[date]" }), @r###"
+    {
+      "url": "[url]",
+      "primaryKey": null,
+      "csvDelimiter": null,
+      "refreshInterval": 3600,
+      "mode": "full",
+      "nextRefreshAt": "[date]",
+      "lastRefreshTaskUid": 1
+    }
+    "###);
+}
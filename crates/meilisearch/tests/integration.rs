@@ -14,6 +14,7 @@ mod similar;
 mod snapshot;
 mod stats;
 mod swap_indexes;
+mod task_log;
 mod tasks;
 mod upgrade;
 mod vector;
@@ -0,0 +1,154 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn search_through_an_index_alias() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let (response, code) =
+        server.set_index_aliases(json!({ "aliases": { "logs": ["logs-2024"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Searching through index aliases requires launching Meilisearch with the `--experimental-index-aliases` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_index_aliases: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("logs-2024");
+    let documents =
+        json!([{ "id": 1, "message": "disk full" }, { "id": 2, "message": "disk almost full" }]);
+    let (response, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let index = server.index("logs-2025");
+    let (response, _code) =
+        index.add_documents(json!([{ "id": 1, "message": "disk full again" }]), Some("id")).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server
+        .set_index_aliases(
+            json!({ "aliases": { "logs": ["logs-2025", "logs-2024", "logs-2024"] } }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "aliases": {
+        "logs": [
+          "logs-2024",
+          "logs-2025"
+        ]
+      }
+    }
+    "###);
+
+    // the hits of the indexes are merged by ranking score
+    let (response, code) = server.index("logs").search_post(json!({ "q": "disk full" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]", ".hits[]._federation.weightedRankingScore" => "[score]" }), @r###"
+    {
+      "hits": [
+        {
+          "id": 1,
+          "message": "disk full",
+          "_federation": {
+            "indexUid": "logs-2024",
+            "queriesPosition": 0,
+            "weightedRankingScore": "[score]"
+          }
+        },
+        {
+          "id": 1,
+          "message": "disk full again",
+          "_federation": {
+            "indexUid": "logs-2025",
+            "queriesPosition": 1,
+            "weightedRankingScore": "[score]"
+          }
+        },
+        {
+          "id": 2,
+          "message": "disk almost full",
+          "_federation": {
+            "indexUid": "logs-2024",
+            "queriesPosition": 0,
+            "weightedRankingScore": "[score]"
+          }
+        }
+      ],
+      "processingTimeMs": "[time]",
+      "limit": 20,
+      "offset": 0,
+      "estimatedTotalHits": 3
+    }
+    "###);
+
+    // the pagination applies to the merged hits
+    let (response, code) = server
+        .index("logs")
+        .search_post(json!({ "q": "disk full", "offset": 1, "limit": 1 }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["message"]), @r###""disk full again""###);
+    let (response, code) =
+        server.index("logs").search_post(json!({ "q": "disk full", "page": 1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using `page` is not allowed when searching through an alias, use `offset` and `limit` instead.",
+      "code": "invalid_search_page",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_page"
+    }
+    "###);
+
+    // an alias can't be an index nor stand for another alias
+    let (response, code) =
+        server.set_index_aliases(json!({ "aliases": { "logs-2024": ["logs-2025"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`logs-2024` is the uid of an index, it can't be used as an alias.",
+      "code": "invalid_index_aliases",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_aliases"
+    }
+    "###);
+    let (response, code) =
+        server.set_index_aliases(json!({ "aliases": { "all": ["logs"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The alias `all` can't have the alias `logs` as an index.",
+      "code": "invalid_index_aliases",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_aliases"
+    }
+    "###);
+
+    let (response, code) = server.set_index_aliases(json!({ "aliases": { "logs": null } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "aliases": {}
+    }
+    "###);
+    let (response, code) = server.get_index_aliases().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "aliases": {}
+    }
+    "###);
+    let (response, code) = server.index("logs").search_post(json!({ "q": "disk full" })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
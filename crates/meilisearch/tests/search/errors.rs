@@ -41,11 +41,44 @@ async fn search_bad_q() {
     let server = Server::new_shared();
     let index = server.unique_index();
 
+    let (response, code) = index.search_post(json!({"q": 12})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.q`: expected a string or an array, but found a positive integer: `12`",
+      "code": "invalid_search_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+    }
+    "###);
+
     let (response, code) = index.search_post(json!({"q": ["doggo"]})).await;
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type at `.q`: expected a string, but found an array: `[\"doggo\"]`",
+      "message": "Invalid value type at `.q[0]`: expected an object, but found a string: `\"doggo\"`",
+      "code": "invalid_search_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"q": []})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.q`: expected at least one alternative phrasing of the query",
+      "code": "invalid_search_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"q": [{"q": "doggo", "weight": -1.0}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.q[0].weight`: the value of `weight` is invalid, expected a positive float (>= 0.0).",
       "code": "invalid_search_q",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_search_q"
@@ -184,6 +217,164 @@ async fn search_bad_attributes_to_retrieve() {
     // Can't make the `attributes_to_retrieve` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_ignore_terms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"ignoreTerms": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.ignoreTerms`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_ignore_terms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ignore_terms"
+    }
+    "###);
+    // Can't make the `ignore_terms` fail with a get search since it'll accept anything as an array of strings.
+}
+
+#[actix_rt::test]
+async fn search_bad_debug_timings() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"debugTimings": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.debugTimings`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_debug_timings",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_debug_timings"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?debugTimings=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `debugTimings`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_debug_timings",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_debug_timings"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_exhaustive_total_hits() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"exhaustiveTotalHits": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.exhaustiveTotalHits`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_exhaustive_total_hits",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_exhaustive_total_hits"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?exhaustiveTotalHits=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `exhaustiveTotalHits`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_exhaustive_total_hits",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_exhaustive_total_hits"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_exhaustive_facet_count() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"exhaustiveFacetCount": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.exhaustiveFacetCount`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_exhaustive_facet_count",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_exhaustive_facet_count"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?exhaustiveFacetCount=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `exhaustiveFacetCount`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_exhaustive_facet_count",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_exhaustive_facet_count"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_geo_grid_precision() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"geoGridPrecision": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.geoGridPrecision`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_geo_grid_precision",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_grid_precision"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?geoGridPrecision=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `geoGridPrecision`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_geo_grid_precision",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_grid_precision"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_sensitive_matching() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"sensitiveMatching": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.sensitiveMatching`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_sensitive_matching",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sensitive_matching"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?sensitiveMatching=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `sensitiveMatching`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_sensitive_matching",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sensitive_matching"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_retrieve_vectors() {
     let server = Server::new_shared();
@@ -249,6 +440,29 @@ async fn search_bad_attributes_to_crop() {
       "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_crop"
     }
     "###);
+
+    let (response, code) = index.search_post(json!({"attributesToCrop": [12]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.attributesToCrop[0]`: expected a string or an object, but found a positive integer: `12`",
+      "code": "invalid_search_attributes_to_crop",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_crop"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"attributesToCrop": [{"attribute": "title", "doggo": 2}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `.attributesToCrop[0]`: expected one of `attribute`, `cropLength`, `cropMarker`",
+      "code": "invalid_search_attributes_to_crop",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_crop"
+    }
+    "###);
     // Can't make the `attributes_to_crop` fail with a get search since it'll accept anything as an array of strings.
 }
 
@@ -295,6 +509,18 @@ async fn search_bad_attributes_to_highlight() {
       "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_highlight"
     }
     "###);
+
+    let (response, code) =
+        index.search_post(json!({"attributesToHighlight": [{"highlightPreTag": "<b>"}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `attribute` inside `.attributesToHighlight[0]`",
+      "code": "invalid_search_attributes_to_highlight",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_highlight"
+    }
+    "###);
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
@@ -368,6 +594,62 @@ async fn search_bad_show_matches_position() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_show_grapheme_offsets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"showGraphemeOffsets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showGraphemeOffsets`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_show_grapheme_offsets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_grapheme_offsets"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?showGraphemeOffsets=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `showGraphemeOffsets`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_show_grapheme_offsets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_grapheme_offsets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_show_matched_terms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"showMatchedTerms": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showMatchedTerms`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_show_matched_terms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_matched_terms"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?showMatchedTerms=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `showMatchedTerms`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_show_matched_terms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_matched_terms"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_facets() {
     let server = Server::new_shared();
@@ -383,6 +665,78 @@ async fn search_bad_facets() {
       "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
     }
     "###);
+
+    let (response, code) = index.search_post(json!({"facets": [12]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facets[0]`: expected a string or an object, but found a positive integer: `12`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"facets": [{"limit": 10}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `name` inside `.facets[0]`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{"name": "doggo", "limit": -1}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facets[0].limit`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{"name": "doggo", "sortBy": "doggo"}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.facets[0].sortBy`: expected one of `alpha`, `count`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"facets": [{"name": "doggo", "ranges": [{"from": 50, "to": 10}]}]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0].ranges[0]`: `from` should be lower than `to`, but found `from: 50` and `to: 10`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"facets": [{"name": "doggo", "hierarchy": {"path": "bork"}}]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `separator` inside `.facets[0].hierarchy`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
@@ -597,6 +951,34 @@ async fn search_bad_crop_marker() {
     // Can't make the `crop_marker` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_crop_strategy() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"cropStrategy": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.cropStrategy`: expected one of `words`, `sentence`",
+      "code": "invalid_search_crop_strategy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_strategy"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?cropStrategy=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `cropStrategy`: expected one of `words`, `sentence`",
+      "code": "invalid_search_crop_strategy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_strategy"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_matching_strategy() {
     let server = Server::new_shared();
@@ -645,10 +1027,17 @@ async fn filter_invalid_syntax_object() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "title & Glass",
+                "snippet": "title & Glass\n^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -666,10 +1055,17 @@ async fn filter_invalid_syntax_array() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "title & Glass",
+                "snippet": "title & Glass\n^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -690,7 +1086,17 @@ async fn filter_invalid_syntax_string() {
               "message": "Found unexpected characters at the end of the filter: `XOR title = Glass`. You probably forgot an `OR` or an `AND` rule.\n15:32 title = Glass XOR title = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 15,
+                "token": "XOR title = Glass",
+                "snippet": "title = Glass XOR title = Glass\n              ^^^^^^^^^^^^^^^^^",
+                "suggestions": [
+                  "AND",
+                  "OR"
+                ]
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -711,7 +1117,14 @@ async fn filter_invalid_attribute_array() {
               "message": "Index `test`: Attribute `many` is not filterable. Available filterable attribute patterns are: `title`.\n1:5 many = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "many",
+                "snippet": "many = Glass\n^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -732,7 +1145,14 @@ async fn filter_invalid_attribute_string() {
               "message": "Index `test`: Attribute `many` is not filterable. Available filterable attribute patterns are: `title`.\n1:5 many = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "many",
+                "snippet": "many = Glass\n^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -753,7 +1173,14 @@ async fn filter_reserved_geo_attribute_array() {
               "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geo = Glass",
+                "snippet": "_geo = Glass\n^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -774,7 +1201,14 @@ async fn filter_reserved_geo_attribute_string() {
               "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geo = Glass",
+                "snippet": "_geo = Glass\n^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -795,7 +1229,14 @@ async fn filter_reserved_attribute_array() {
               "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geoDistance = Glass",
+                "snippet": "_geoDistance = Glass\n^^^^^^^^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -816,7 +1257,14 @@ async fn filter_reserved_attribute_string() {
               "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geoDistance = Glass",
+                "snippet": "_geoDistance = Glass\n^^^^^^^^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -837,7 +1285,14 @@ async fn filter_reserved_geo_point_array() {
               "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geoPoint = Glass",
+                "snippet": "_geoPoint = Glass\n^^^^^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -858,7 +1313,14 @@ async fn filter_reserved_geo_point_string() {
               "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "_geoPoint = Glass",
+                "snippet": "_geoPoint = Glass\n^^^^^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
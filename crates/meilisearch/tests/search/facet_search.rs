@@ -273,6 +273,69 @@ async fn more_advanced_facet_search() {
     snapshot!(response["facetHits"].as_array().unwrap().len(), @"1");
 }
 
+#[actix_rt::test]
+async fn facet_search_query_matching() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "adventre"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @r###"[{"value":"Adventure","count":2}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "genres", "facetQuery": "adventre", "facetQueryMatching": "prefix"}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @"[]");
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "genres", "facetQuery": "adv", "facetQueryMatching": "prefix"}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @r###"[{"value":"Adventure","count":2}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "genres", "facetQuery": "adv", "facetQueryMatching": "exact"}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @"[]");
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "genres", "facetQuery": "adventure", "facetQueryMatching": "exact"}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @r###"[{"value":"Adventure","count":2}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "genres", "facetQuery": "adv", "facetQueryMatching": "doggo"}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Unknown value `doggo` at `.facetQueryMatching`: expected one of `fuzzy`, `prefix`, `exact`",
+      "code": "invalid_facet_search_facet_query_matching",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_facet_search_facet_query_matching"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn simple_facet_search_with_max_values() {
     let server = Server::new().await;
@@ -723,7 +723,14 @@ async fn test_filterable_attributes_priority() {
               "message": "Index `test`: Attribute `doggos.age` is not filterable. Available filterable attribute patterns are: `doggos.*`.\n1:11 doggos.age > 2",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "doggos.age",
+                "snippet": "doggos.age > 2\n^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
         },
@@ -749,7 +756,14 @@ async fn test_filterable_attributes_priority() {
               "message": "Index `test`: Attribute `doggos` is not filterable. Available filterable attribute patterns are: `doggos.*`.\n1:7 doggos EXISTS",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "doggos",
+                "snippet": "doggos EXISTS\n^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
         },
@@ -618,3 +618,180 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn format_crop_sentence() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "overview": "Natalie risk her future. Split The World is a book written by Emily Henry. I never read it.",
+        },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({ "q": "book", "attributesToCrop": ["overview"], "cropLength": 3 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(response["hits"][0]["_formatted"]["overview"], @r###""…a book written…""###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "q": "book",
+                "attributesToCrop": ["overview"],
+                "cropLength": 3,
+                "cropStrategy": "sentence",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(response["hits"][0]["_formatted"]["overview"], @r###""…Split The World is a book written by Emily Henry.…""###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn format_with_per_attribute_options() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "Split The World",
+            "overview": "Natalie risk her future. Split The World is a book written by Emily Henry. I never read it.",
+        },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "q": "book",
+                "attributesToRetrieve": ["id"],
+                "attributesToHighlight": [
+                    "title",
+                    { "attribute": "overview", "highlightPreTag": "<b>", "highlightPostTag": "</b>" },
+                ],
+                "attributesToCrop": [
+                    "title",
+                    { "attribute": "overview", "cropLength": 3, "cropMarker": "[…]" },
+                ],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"][0]["_formatted"]), @r###"
+                {
+                  "id": "1",
+                  "title": "Split The World",
+                  "overview": "[…]a <b>book</b> written[…]"
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn matches_position_grapheme_offsets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([{ "id": 1, "title": "👩‍👩‍👧 Family café" }]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(json!({ "q": "cafe", "showMatchesPosition": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"][0]["_matchesPosition"]), @r###"
+            {
+              "title": [
+                {
+                  "start": 26,
+                  "length": 5
+                }
+              ]
+            }
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "q": "cafe", "showMatchesPosition": true, "showGraphemeOffsets": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"][0]["_matchesPosition"]), @r###"
+                {
+                  "title": [
+                    {
+                      "start": 26,
+                      "length": 5,
+                      "graphemeStart": 9,
+                      "graphemeLength": 4
+                    }
+                  ]
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn matched_terms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "The quick brown fox" },
+        { "id": 2, "title": "A lazy dog" },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _) = index.update_settings(json!({ "synonyms": { "fast": ["quick"] } })).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(json!({ "q": "fast browm fo" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response["hits"][0].get("_matchedTerms").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "fast browm fo", "showMatchedTerms": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"][0]["_matchedTerms"]), @r###"
+            [
+              {
+                "term": "fast",
+                "word": "quick",
+                "kind": "synonym"
+              },
+              {
+                "term": "browm",
+                "word": "brown",
+                "kind": "typo"
+              },
+              {
+                "term": "fo",
+                "word": "fox",
+                "kind": "prefix"
+              }
+            ]
+            "###);
+        })
+        .await;
+}
@@ -368,3 +368,357 @@ async fn geo_sort_with_words() {
     )
     .await;
 }
+
+#[actix_rt::test]
+async fn geo_multiple_points() {
+    let documents = json!([
+      { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0 }, { "lat": 50, "lng": 50 }] },
+      { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 10, "lng": 10 } },
+      { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": 50, "lng": 0 }, { "lat": 0, "lng": 50 }] },
+    ]);
+    let settings = json!({
+        "filterableAttributes": [RESERVED_GEO_FIELD_NAME],
+        "sortableAttributes": [RESERVED_GEO_FIELD_NAME],
+    });
+
+    // the document 2 has a point at the latitude and another one at the longitude of the box
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({"filter": "_geoBoundingBox([51, 51], [49, 49])"}),
+        |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "_geo": [
+                  {
+                    "lat": 0,
+                    "lng": 0
+                  },
+                  {
+                    "lat": 50,
+                    "lng": 50
+                  }
+                ]
+              }
+            ]
+            "###);
+        },
+    )
+    .await;
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({"filter": "_geoRadius(50, 50, 1000)", "attributesToRetrieve": ["id"]}),
+        |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              }
+            ]
+            "###);
+        },
+    )
+    .await;
+
+    // the documents are sorted by their closest point
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({"sort": ["_geoPoint(0.0, 0.0):desc"]}),
+        |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2,
+                "_geo": [
+                  {
+                    "lat": 50,
+                    "lng": 0
+                  },
+                  {
+                    "lat": 0,
+                    "lng": 50
+                  }
+                ],
+                "_geoDistance": 5559746
+              },
+              {
+                "id": 1,
+                "_geo": {
+                  "lat": 10,
+                  "lng": 10
+                },
+                "_geoDistance": 1568521
+              },
+              {
+                "id": 0,
+                "_geo": [
+                  {
+                    "lat": 0,
+                    "lng": 0
+                  },
+                  {
+                    "lat": 50,
+                    "lng": 50
+                  }
+                ],
+                "_geoDistance": 0
+              }
+            ]
+            "###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn geo_distance_with_attributes_to_retrieve() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = DOCUMENTS.clone();
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": [RESERVED_GEO_FIELD_NAME],
+            "sortableAttributes": [RESERVED_GEO_FIELD_NAME],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 10000)",
+                "sort": ["_geoPoint(45.472735, 9.184019):asc"],
+                "attributesToRetrieve": ["id", "_geoDistance"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geoDistance": 1139
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 10000)",
+                "sort": ["_geoPoint(45.472735, 9.184019):asc"],
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_grid() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = DOCUMENTS.clone();
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": [RESERVED_GEO_FIELD_NAME] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({ "geoGridPrecision": 3, "limit": 0 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["geoGrid"]), @r###"
+            {
+              "9q5": 1,
+              "u0n": 1
+            }
+            "###);
+        })
+        .await;
+
+    // only the candidates of the search are counted
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 10000)",
+                "geoGridPrecision": 1,
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["geoGrid"]), @r###"
+                {
+                  "u": 1
+                }
+                "###);
+            },
+        )
+        .await;
+
+    let (response, code) = index.search_post(json!({ "geoGridPrecision": 13 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `geoGridPrecision`: the precision must be between 1 and 12, but `13` was provided.",
+      "code": "invalid_search_geo_grid_precision",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_grid_precision"
+    }
+    "###);
+
+    let index = server.unique_index();
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) = index.search_post(json!({ "geoGridPrecision": 3 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `geoGridPrecision`: the `_geo` field must be filterable or sortable to compute a geo grid.",
+      "code": "invalid_search_geo_grid_precision",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_grid_precision"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn geojson_contains_and_intersects() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["_geojson"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+      {
+        "id": 1,
+        "_geojson": {
+          "type": "Polygon",
+          "coordinates": [[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]]
+        }
+      },
+      {
+        "id": 2,
+        "_geojson": { "type": "LineString", "coordinates": [[20, 20], [30, 30]] }
+      },
+      { "id": 3, "_geojson": { "type": "Point", "coordinates": [5, 5] } },
+      { "id": 4 },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the filters take `[latitude, longitude]` points while GeoJSON positions are `[longitude, latitude]`
+    index
+        .search(
+            json!({"filter": "_geoContains(2, 3)", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"filter": "_geoContains(5, 5)", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"filter": "_geoIntersects([26, 24], [24, 12])", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"filter": "_geoIntersects([21, 29], [19, 22])", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+
+    let (task, _status_code) = index
+        .add_documents(
+            json!([{ "id": 5, "_geojson": { "type": "LineString", "coordinates": [[0, 0]] } }]),
+            None,
+        )
+        .await;
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `_geojson` field in the document with the id: `5` is not a valid GeoJSON geometry: a line string must have at least two positions.",
+      "code": "invalid_document_geojson_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_geojson_field"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn geojson_filter_not_filterable() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "filter": "_geoContains(1, 2)" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_search_filter""###);
+}
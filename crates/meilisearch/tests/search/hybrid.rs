@@ -368,6 +368,140 @@ async fn invalid_semantic_ratio() {
     "###);
 }
 
+#[actix_rt::test]
+async fn fusion() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    // the keyword search ranks 2, 3, 1 and the semantic search ranks 3, 2, 1
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.4, "embedder": "default", "fusion": "reciprocalRank"}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"},{"id":"3"},{"id":"1"}]"###);
+    snapshot!(response["semanticHitCount"], @"0");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.9, "embedder": "default", "fusion": "reciprocalRank"}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+    snapshot!(response["semanticHitCount"], @"3");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.8, "embedder": "default", "fusion": "normalizedSum"}, "attributesToRetrieve": ["id"], "offset": 1, "limit": 1}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"}]"###);
+    snapshot!(response["semanticHitCount"], @"0");
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(
+                &json!({"q": "Captain", "vector": [1.0, 1.0], "hybridEmbedder": "default", "hybridSemanticRatio": 0.9, "hybridFusion": "reciprocalRank", "attributesToRetrieve": ["id"]}),
+            )
+            .unwrap(),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "fusion": "doggo"}}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Unknown value `doggo` at `.hybrid.fusion`: expected one of `linear`, `reciprocalRank`, `normalizedSum`",
+      "code": "invalid_search_hybrid_fusion",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_fusion"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(&json!({"q": "Captain", "hybridFusion": "reciprocalRank"})).unwrap(),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`hybridEmbedder` is mandatory when `hybridFusion` is present",
+      "code": "invalid_search_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_query"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_accuracy() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    // the index is too small for the accuracy to change the nearest neighbors
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 1.0, "embedder": "default", "searchAccuracy": 0.1}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(
+                &json!({"q": "Captain", "vector": [1.0, 1.0], "hybridEmbedder": "default", "hybridSemanticRatio": 1.0, "hybridSearchAccuracy": 2.5, "attributesToRetrieve": ["id"]}),
+            )
+            .unwrap(),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "searchAccuracy": 0.0}}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.hybrid.searchAccuracy`: the value of `searchAccuracy` is invalid, expected a float greater than `0.0` and at most `10.0`.",
+      "code": "invalid_search_search_accuracy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_accuracy"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(
+                &json!({"q": "Captain", "hybridEmbedder": "default", "hybridSearchAccuracy": 11}),
+            )
+            .unwrap(),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value in parameter `hybridSearchAccuracy`: the value of `searchAccuracy` is invalid, expected a float greater than `0.0` and at most `10.0`.",
+      "code": "invalid_search_search_accuracy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_accuracy"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn single_document() {
     let server = Server::new().await;
@@ -1335,3 +1335,55 @@ async fn german_search() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn filter_on_detected_locales() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["_detectedLocales"],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 1, "title": "Le chat est assis sur le canapé et regarde tranquillement par la fenêtre"},
+      {"id": 2, "title": "The cat is sitting on the couch and quietly looking out of the window"},
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({"filter": "_detectedLocales = fra", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+                snapshot!(code, @"200 OK");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"filter": "_detectedLocales = eng", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+                snapshot!(code, @"200 OK");
+            },
+        )
+        .await;
+}
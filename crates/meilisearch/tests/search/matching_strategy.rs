@@ -73,6 +73,34 @@ async fn simple_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_with_phrase() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    // the phrase must always match, only the loose words can be removed
+    index
+        .search(json!({"q": "\"Captain\" Marvel", "matchingStrategy": "last", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"4"},{"id":"2"},{"id":"6"}]"###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "Captain \"Marvel\"", "matchingStrategy": "last", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"4"},{"id":"5"}]"###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "\"Captain\" Marvel", "matchingStrategy": "frequency", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"4"},{"id":"2"},{"id":"6"}]"###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn search_with_typo() {
     let server = Server::new().await;
@@ -1,6 +1,7 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod aliases;
 mod distinct;
 mod errors;
 mod facet_search;
@@ -13,6 +14,7 @@ mod locales;
 mod matching_strategy;
 mod multi;
 mod pagination;
+mod rerank;
 mod restrict_searchable;
 mod search_queue;
 
@@ -177,6 +179,316 @@ async fn search_with_stop_word() {
           .await;
 }
 
+#[actix_rt::test]
+async fn search_with_ignore_terms() {
+    let index = shared_index_with_documents().await;
+
+    index
+        .search(
+            json!({"q": "best captain marvel movie ", "matchingStrategy": "all", "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "best captain marvel movie ", "matchingStrategy": "all", "ignoreTerms": ["best", "Movie"], "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the last word is ignored even when it is not followed by a space
+    index
+        .search(
+            json!({"q": "best captain marvel movie", "matchingStrategy": "all", "ignoreTerms": ["best", "Movie"], "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_sensitive_matching() {
+    let index = shared_index_with_documents().await;
+
+    index
+        .search(
+            json!({"q": "captain", "sensitiveMatching": true, "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "Captain", "sensitiveMatching": true, "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "glass", "sensitiveMatching": true, "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "Gläss", "sensitiveMatching": true, "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Gläss"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_query_alternatives() {
+    let index = shared_index_with_documents().await;
+
+    index
+        .search(
+            json!({
+                "q": [{"q": "captain"}, {"q": "escape", "weight": 0.5}],
+                "attributesToRetrieve": ["title"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  },
+                  {
+                    "title": "Escape Room"
+                  }
+                ]
+                "###);
+                snapshot!(response["query"], @r###""captain""###);
+                snapshot!(response["estimatedTotalHits"], @"2");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "q": [{"q": "captain", "weight": 0.5}, {"q": "escape"}],
+                "attributesToRetrieve": ["title"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Escape Room"
+                  },
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // a document matched by several alternatives is returned once
+    index
+        .search(
+            json!({
+                "q": [{"q": "captain"}, {"q": "marvel"}],
+                "attributesToRetrieve": ["title"],
+                "limit": 1,
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+                snapshot!(response["estimatedTotalHits"], @"1");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_query_alternatives_sort_and_distinct() {
+    let index = shared_index_with_documents().await;
+
+    // the hits of all the alternatives are sorted together
+    index
+        .search(
+            json!({
+                "q": [{"q": "captain"}, {"q": "escape"}, {"q": "shazam"}],
+                "sort": ["title:desc"],
+                "attributesToRetrieve": ["title"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Shazam!"
+                  },
+                  {
+                    "title": "Escape Room"
+                  },
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["universe"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let documents = json!([
+        { "id": 0, "title": "Captain America", "universe": "marvel" },
+        { "id": 1, "title": "Iron Man", "universe": "marvel" },
+        { "id": 2, "title": "Batman", "universe": "dc" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the hits of different alternatives sharing the distinct value are returned once
+    index
+        .search(
+            json!({
+                "q": [{"q": "captain"}, {"q": "iron"}, {"q": "batman"}],
+                "distinct": "universe",
+                "attributesToRetrieve": ["title"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain America"
+                  },
+                  {
+                    "title": "Batman"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_sensitive_matching_setting() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) = index.update_settings(json!({"sensitiveMatching": true})).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({"q": "captain", "attributesToRetrieve": ["title"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["hits"]), @"[]");
+        })
+        .await;
+
+    index
+        .search(
+            json!({"q": "captain", "sensitiveMatching": false, "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Captain Marvel"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_debug_timings() {
+    let index = shared_index_with_documents().await;
+
+    index
+        .search(json!({"q": "captain"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("timings").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "captain", "debugTimings": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let timings = response["timings"].as_object().unwrap();
+            let keys: Vec<_> = timings.keys().map(String::as_str).collect();
+            snapshot!(format!("{keys:?}"), @r###"["tokenizationMs", "candidatesMs", "rankingRulesMs", "vectorSearchMs", "formattingMs"]"###);
+            for (key, value) in timings {
+                if key != "rankingRulesMs" {
+                    assert!(value.as_f64().unwrap() >= 0.0, "{key}: {value}");
+                }
+            }
+            let ranking_rules: Vec<_> =
+                timings["rankingRulesMs"].as_object().unwrap().keys().map(String::as_str).collect();
+            snapshot!(format!("{ranking_rules:?}"), @r###"["words", "typo", "proximity", "fid", "position", "exact_attribute", "exactness"]"###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn search_with_typo_settings() {
     // related to https://github.com/meilisearch/meilisearch/issues/5240
@@ -762,6 +1074,331 @@ async fn faceting_max_values_per_facet() {
         .await;
 }
 
+#[actix_rt::test]
+async fn faceting_max_values_per_facet_override() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["number", "tens"] })).await;
+
+    let documents: Vec<_> =
+        (0..1_000).map(|id| json!({ "id": id, "number": id, "tens": id / 10 })).collect();
+    let (task, _status_code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "facets": [{ "name": "number", "limit": 500 }, "tens"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let numbers = response["facetDistribution"]["number"].as_object().unwrap();
+                assert_eq!(numbers.len(), 500);
+                let tens = response["facetDistribution"]["tens"].as_object().unwrap();
+                assert_eq!(tens.len(), 100);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": [{ "name": "number", "limit": 10 }, { "name": "tens" }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let numbers = response["facetDistribution"]["number"].as_object().unwrap();
+                assert_eq!(numbers.len(), 10);
+                let tens = response["facetDistribution"]["tens"].as_object().unwrap();
+                assert_eq!(tens.len(), 100);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn faceting_sort_by_override() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color", "size"],
+            "faceting": { "sortFacetValuesBy": { "*": "count" } },
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "color": "blue", "size": "S" },
+        { "id": 1, "color": "red", "size": "S" },
+        { "id": 2, "color": "red", "size": "M" },
+        { "id": 3, "color": "red", "size": "M" },
+        { "id": 4, "color": "blue", "size": "M" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "facets": [{ "name": "color", "sortBy": "alpha" }, "size"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "color": {
+                    "blue": 2,
+                    "red": 3
+                  },
+                  "size": {
+                    "M": 3,
+                    "S": 2
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": ["color", { "name": "size", "sortBy": "alpha" }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "color": {
+                    "red": 3,
+                    "blue": 2
+                  },
+                  "size": {
+                    "M": 3,
+                    "S": 2
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn faceting_ranges() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["price", "color"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "price": 5, "color": "blue" },
+        { "id": 1, "price": 9.99, "color": "red" },
+        { "id": 2, "price": 10, "color": "red" },
+        { "id": 3, "price": 42, "color": "red" },
+        { "id": 4, "price": 120, "color": "blue" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "facets": [
+                    { "name": "price", "ranges": [{ "to": 10 }, { "from": 10, "to": 50 }, { "from": 50 }] },
+                    "color"
+                ]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "color": {
+                    "blue": 2,
+                    "red": 3
+                  },
+                  "price": {
+                    "..10": 2,
+                    "10..50": 2,
+                    "50..": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "color = red",
+                "facets": [{ "name": "price", "ranges": [{ "to": 10 }, { "from": 10 }] }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "price": {
+                    "..10": 1,
+                    "10..": 2
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn faceting_exhaustive_facet_count() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color", "size"],
+            "distinctAttribute": "color",
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "color": "red", "size": "s" },
+        { "id": 1, "color": "red", "size": "m" },
+        { "id": 2, "color": "red", "size": "l" },
+        { "id": 3, "color": "blue", "size": "s" },
+        { "id": 4, "color": "blue", "size": "m" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // only the documents sharing the color of the returned hit are deduplicated
+    index
+        .search(json!({ "limit": 1, "facets": ["size"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "size": {
+                "m": 1,
+                "s": 2
+              }
+            }
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "limit": 1, "facets": ["size"], "exhaustiveFacetCount": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "size": {
+                    "s": 2
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn faceting_hierarchy() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["categories"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "categories": ["Electronics > Phones > Android", "Electronics > Phones > iOS"] },
+        { "id": 1, "categories": "Electronics > Phones > iOS" },
+        { "id": 2, "categories": "Electronics > Laptops" },
+        { "id": 3, "categories": "Books > Science-fiction" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({ "facets": [{ "name": "categories", "hierarchy": { "separator": " > " } }] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "categories": {
+                    "Books": 1,
+                    "Electronics": 3
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": [{
+                    "name": "categories",
+                    "hierarchy": { "separator": " > ", "path": "Electronics > Phones" }
+                }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "categories": {
+                    "Electronics > Phones > Android": 1,
+                    "Electronics > Phones > iOS": 2
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn facet_stats_dates() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["releaseDate", "title"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "title": "Dune", "releaseDate": "2021-09-15T00:00:00Z" },
+        { "id": 1, "title": "Arrival", "releaseDate": "2016-11-11T00:00:00Z" },
+        { "id": 2, "title": "Sicario", "releaseDate": "2015-09-18T00:00:00Z" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({ "facets": ["releaseDate", "title"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(json_string!(response["facetStats"]), @r###"
+            {
+              "releaseDate": {
+                "min": 1442534400.0,
+                "max": 1631664000.0
+              }
+            }
+            "###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn test_score_details() {
     let server = Server::new().await;
@@ -1756,7 +2393,14 @@ async fn test_nested_fields() {
               "message": "Index `test`: Attribute `nested` is not filterable. Available filterable attribute patterns are: `nested.machin`, `nested.object`, `title`.\n1:7 nested = array",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "nested",
+                "snippet": "nested = array\n^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
         },
@@ -1775,7 +2419,14 @@ async fn test_nested_fields() {
               "message": "Index `test`: Attribute `nested` is not filterable. Available filterable attribute patterns are: `nested.machin`, `nested.object`, `title`.\n1:7 nested = \"I lied\"",
               "code": "invalid_search_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "nested",
+                "snippet": "nested = \"I lied\"\n^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
         },
@@ -949,7 +949,14 @@ async fn federation_one_query_error() {
       "message": "Inside `.queries[1]`: Index `nested`: Attribute `title` is not filterable. This index does not have configured filterable attributes.\n1:6 title = toto",
       "code": "invalid_search_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "title",
+        "snippet": "title = toto\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 }
@@ -1045,7 +1052,14 @@ async fn federation_multiple_query_errors() {
       "message": "Inside `.queries[0]`: Index `test`: Attribute `title` is not filterable. This index does not have configured filterable attributes.\n1:6 title = toto",
       "code": "invalid_search_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "title",
+        "snippet": "title = toto\n^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 }
@@ -1110,7 +1124,14 @@ async fn federation_multiple_query_errors_interleaved() {
       "message": "Inside `.queries[1]`: Index `nested`: Attribute `doggos` is not filterable. This index does not have configured filterable attributes.\n1:7 doggos IN [intel, kefir]",
       "code": "invalid_search_filter",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter",
+      "details": {
+        "line": 1,
+        "column": 1,
+        "token": "doggos",
+        "snippet": "doggos IN [intel, kefir]\n^^^^^^",
+        "suggestions": []
+      }
     }
     "###);
 }
@@ -3598,6 +3619,33 @@ async fn federation_federated_contains_facets() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_federated_contains_query_alternatives() {
+    let server = Server::new().await;
+
+    let index = server.index("fruits");
+
+    let documents = FRUITS_DOCUMENTS.clone();
+    let (value, _) = index.add_documents(documents, None).await;
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "fruits", "q": "apple red"},
+        {"indexUid": "fruits", "q": [{"q": "apple red"}, {"q": "gala", "weight": 0.5}]},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    insta::assert_json_snapshot!(response, { ".processingTimeMs" => "[time]" }, @r###"
+    {
+      "message": "Inside `.queries[1]`: Using alternative phrasings in `q` is not allowed in federated queries.\n - Hint: pass each phrasing in its own query, with its weight in `federationOptions.weight`",
+      "code": "invalid_search_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_non_faceted_for_an_index() {
     let server = Server::new().await;
@@ -133,3 +133,38 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn exhaustive_total_hits_is_not_capped_by_max_total_hits() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "Shazam!" },
+        { "id": 2, "title": "Captain Marvel" },
+        { "id": 3, "title": "Escape Room" },
+        { "id": 4, "title": "How to Train Your Dragon: The Hidden World" },
+        { "id": 5, "title": "Glass" }
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _code) =
+        index.update_settings(json!({ "pagination": { "maxTotalHits": 2 } })).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(json!({}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], 2);
+        })
+        .await;
+
+    index
+        .search(json!({ "exhaustiveTotalHits": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+            assert_eq!(response["estimatedTotalHits"], 5);
+        })
+        .await;
+}
@@ -0,0 +1,157 @@
+use meili_snap::{json_string, snapshot};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::common::{Server, Value};
+use crate::json;
+
+/// A reranker preferring the documents that the search ranked last.
+async fn create_reranker_mock() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/rerank"))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = req.body_json().unwrap();
+            let documents = body["documents"].as_array().unwrap();
+            let results: Vec<_> = documents
+                .iter()
+                .enumerate()
+                .map(|(index, _)| json!({ "index": index, "relevance_score": index as f64 / 10.0 }))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "results": results }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let reranker = json!({
+        "url": format!("{}/rerank", mock_server.uri()),
+        "apiKey": "my-super-secret-reranker-key",
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, reranker)
+}
+
+fn hit_ids(response: &Value) -> String {
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].to_string()).collect();
+    format!("[{}]", ids.join(","))
+}
+
+#[actix_rt::test]
+async fn rerank_top_hits() {
+    let (_mock, reranker) = create_reranker_mock().await;
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 0, "name": "kefir" },
+        { "id": 1, "name": "kefir the dog" },
+        { "id": 2, "name": "kefir the good dog" },
+        { "id": 3, "name": "kefir the very good dog" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) =
+        index.update_settings(json!({ "sortableAttributes": ["id"], "reranker": reranker })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({ "q": "kefir", "sort": ["id:asc"], "rerankBudget": 0 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(hit_ids(&response), @"[0,1,2,3]");
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "kefir", "sort": ["id:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(hit_ids(&response), @"[3,2,1,0]");
+        })
+        .await;
+
+    // only the first hits are reranked, the others keep their order
+    index
+        .search(json!({ "q": "kefir", "sort": ["id:asc"], "rerankBudget": 2 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(hit_ids(&response), @"[1,0,2,3]");
+        })
+        .await;
+
+    // the page is cut after reranking
+    index
+        .search(
+            json!({ "q": "kefir", "sort": ["id:asc"], "offset": 1, "limit": 2 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(hit_ids(&response), @"[2,1]");
+            },
+        )
+        .await;
+
+    // placeholder searches have nothing to rerank against
+    index
+        .search(json!({ "sort": ["id:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            snapshot!(hit_ids(&response), @"[0,1,2,3]");
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "q": "kefir", "sort": ["id:asc"], "limit": 1, "showRankingScoreDetails": true, "showRankingScore": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"][0]["_rankingScore"]), @"0.3");
+                snapshot!(json_string!(response["hits"][0]["_rankingScoreDetails"]["rerank"]), @r###"
+                {
+                  "order": 0,
+                  "relevanceScore": 0.3
+                }
+                "###);
+            },
+        )
+        .await;
+
+    let (settings, _code) = index.settings().await;
+    snapshot!(json_string!(settings["reranker"], { ".url" => "[url]" }), @r###"
+    {
+      "url": "[url]",
+      "apiKey": "my-XXXXX...",
+      "documentTemplate": "{{doc.name}}",
+      "documentTemplateMaxBytes": 400
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn invalid_reranker_settings() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "reranker": { "apiKey": "my-api-key" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.reranker.url`: the `url` of the reranker is mandatory",
+      "code": "invalid_settings_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_reranker"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "reranker": { "url": "http://localhost:7700", "documentTemplateMaxBytes": 0 } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.reranker.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero",
+      "code": "invalid_settings_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_reranker"
+    }
+    "###);
+}
@@ -416,3 +416,78 @@ async fn phrase_search_on_title() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_on_attribute_patterns() {
+    let server = Server::new().await;
+    let documents = json!([
+      { "id": 1, "title": "Shazam!", "alt_title": "Captain Marvel", "metadata": { "author": "Bill Parker", "publisher": "Fawcett" } },
+      { "id": 2, "title": "Captain Planet", "alt_title": "The Planeteers", "metadata": { "author": "Ted Turner", "publisher": "Marvel" } },
+      { "id": 3, "title": "Wonder Woman", "alt_title": "Diana", "metadata": { "author": "William Marston", "publisher": "DC" }, "desc": "Not Marvel" }
+    ]);
+    let index = index_with_documents(&server, &documents).await;
+
+    index
+        .search(
+            json!({"q": "Marvel", "attributesToSearchOn": ["*_title"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "Marvel", "attributesToSearchOn": ["metadata.*"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // overlapping patterns select each field only once
+    index
+        .search(
+            json!({"q": "Marvel", "attributesToSearchOn": ["*title", "alt_*", "metadata.*"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(response["hits"].as_array().unwrap().len(), @"2");
+            },
+        )
+        .await;
+
+    let (task, _status_code) =
+        index.update_settings_searchable_attributes(json!(["title", "alt_title"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({"q": "Marvel", "attributesToSearchOn": ["metadata.*"]}),
+            |response, code| {
+                snapshot!(code, @"400 Bad Request");
+                snapshot!(json_string!(response), @r###"
+                {
+                  "message": "Index `test`: Attribute `metadata.*` is not searchable. Available searchable attributes are: `alt_title, title`.",
+                  "code": "invalid_search_attributes_to_search_on",
+                  "type": "invalid_request",
+                  "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+                }
+                "###);
+            },
+        )
+        .await;
+}
@@ -0,0 +1,93 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn extract_attachments_without_service() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) =
+        index.update_settings(json!({ "attachments": { "attributes": ["file"] } })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["attachments"]), @r###"
+    {
+      "attributes": [
+        "file"
+      ]
+    }
+    "###);
+
+    // <html><body><h1>Kefir</h1><p>The best doggo of the world</p></body></html>
+    let documents = json!([
+        {
+            "id": 1,
+            "file": {
+                "contentType": "text/html; charset=utf-8",
+                "data": "PGh0bWw+PGJvZHk+PGgxPktlZmlyPC9oMT48cD5UaGUgYmVzdCBkb2dnbyBvZiB0aGUgd29ybGQ8L3A+PC9ib2R5PjwvaHRtbD4="
+            }
+        },
+        { "id": 2, "file": "already extracted text" },
+    ]);
+    let (task, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "q": "doggo" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "file": "Kefir The best doggo of the world"
+      }
+    ]
+    "###);
+
+    // the binary formats need an extraction service
+    let documents =
+        json!([{ "id": 3, "file": { "contentType": "application/pdf", "data": "JVBERi0xLjQ=" } }]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Could not extract the text of the `file` attachment of the document `3`: the `application/pdf` content type needs an extraction service, set `attachments.extractionUrl`",
+      "code": "document_attachment_extraction_failed",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_attachment_extraction_failed"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn invalid_attachments() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "attachments": { "attributes": ["file"], "url": "http://localhost" } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_attachments""###);
+
+    let (task, code) = index
+        .update_settings(
+            json!({ "attachments": { "attributes": ["file"], "extractionUrl": "tika" } }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""invalid_settings_attachments""###);
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["attachments"], @"null");
+}
@@ -0,0 +1,76 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn document_schema_rejects_invalid_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "documentSchema": {
+                "type": "object",
+                "properties": { "price": { "type": "number" } },
+                "required": ["price"],
+            },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index
+        .add_documents(json!([{ "id": 1, "price": 12.5 }, { "id": 2, "price": 3 }]), Some("id"))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index
+        .add_documents(json!([{ "id": 3, "price": 10 }, { "id": 4, "price": "12" }]), None)
+        .await;
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Document `4` does not match the document schema of the index: `/price`: \"12\" is not of type \"number\"",
+      "code": "document_schema_mismatch",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_schema_mismatch"
+    }
+    "###);
+
+    // the schema applies to the final version of the documents
+    let (task, _code) = index.update_documents(json!([{ "id": 1, "title": "kefir" }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 5 }]), None).await;
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""document_schema_mismatch""###);
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"2");
+
+    let (task, _code) = index.update_settings(json!({ "documentSchema": null })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 5 }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+}
+
+#[actix_rt::test]
+async fn invalid_document_schema() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        index.update_settings(json!({ "documentSchema": { "type": "doggo" } })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""invalid_settings_document_schema""###);
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["documentSchema"], @"null");
+}
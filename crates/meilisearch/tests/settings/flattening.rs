@@ -0,0 +1,107 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::Server;
+use crate::json;
+
+static DOCUMENTS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 1,
+            "address": { "city": "Paris", "country": { "name": "France", "code": "FR" } },
+            "reviews": [{ "author": "kefir" }, { "author": "intel" }],
+        },
+        {
+            "id": 2,
+            "address": { "city": "Brussels", "country": { "name": "Belgium", "code": "BE" } },
+            "reviews": [{ "author": "intel" }],
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn flatten_with_max_depth_and_array_positions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "flattening": { "maxDepth": 1, "arrayPositions": true },
+            "filterableAttributes": ["address.country", "reviews.0", "reviews.1"],
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["flattening"]), @r###"
+    {
+      "maxDepth": 1,
+      "arrayPositions": true
+    }
+    "###);
+
+    // the country name and code are merged into `address.country`
+    index
+        .search(
+            json!({ "filter": "address.country = BE", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the reviews are keyed by their position
+    index
+        .search(
+            json!({ "filter": "reviews.1 = intel", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn flattening_cannot_be_changed_with_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.update_settings(json!({ "flattening": { "maxDepth": 1 } })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The flattening settings cannot be changed once the index contains documents.",
+      "code": "invalid_settings_flattening",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_flattening"
+    }
+    "###);
+
+    // setting the current values is a no-op
+    let (task, _code) =
+        index.update_settings(json!({ "flattening": { "arrayPositions": false } })).await;
+    index.wait_task(task.uid()).await.succeeded();
+}
@@ -166,6 +166,46 @@ test_setting_routes!(
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: sensitive_matching,
+        update_verb: put,
+        default_value: false
+    },
+    {
+        setting: reranker,
+        update_verb: put,
+        default_value: null
+    },
+    {
+        setting: flattening,
+        update_verb: patch,
+        default_value: {"maxDepth": null, "arrayPositions": false}
+    },
+    {
+        setting: ingest_function,
+        update_verb: put,
+        default_value: null
+    },
+    {
+        setting: document_schema,
+        update_verb: put,
+        default_value: null
+    },
+    {
+        setting: skip_unchanged_documents,
+        update_verb: put,
+        default_value: false
+    },
+    {
+        setting: attachments,
+        update_verb: put,
+        default_value: null
+    },
+    {
+        setting: max_document_revisions,
+        update_verb: put,
+        default_value: null
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -199,7 +239,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 28);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -232,6 +272,14 @@ async fn get_settings() {
     assert_eq!(settings["searchCutoffMs"], json!(null));
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
+    assert_eq!(settings["sensitiveMatching"], json!(false));
+    assert_eq!(settings["reranker"], json!(null));
+    assert_eq!(settings["flattening"], json!({ "maxDepth": null, "arrayPositions": false }));
+    assert_eq!(settings["ingestFunction"], json!(null));
+    assert_eq!(settings["documentSchema"], json!(null));
+    assert_eq!(settings["skipUnchangedDocuments"], json!(false));
+    assert_eq!(settings["attachments"], json!(null));
+    assert_eq!(settings["maxDocumentRevisions"], json!(null));
     assert_eq!(settings["embedders"], json!({}));
 }
 
@@ -335,7 +383,18 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "sensitiveMatching": false,
+      "reranker": null,
+      "flattening": {
+        "maxDepth": null,
+        "arrayPositions": false
+      },
+      "ingestFunction": null,
+      "documentSchema": null,
+      "skipUnchangedDocuments": false,
+      "attachments": null,
+      "maxDocumentRevisions": null
     }
     "#);
 
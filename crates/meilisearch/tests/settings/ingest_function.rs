@@ -0,0 +1,126 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn ingest_function_transforms_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = server.set_features(json!({ "editDocumentsByFunction": true })).await;
+    snapshot!(code, @"200 OK");
+
+    let (task, code) = index
+        .update_settings(json!({
+            "ingestFunction": r#"
+                if doc.discarded { doc = () } else {
+                    doc.brand = doc.brand.to_lower();
+                    doc.label = doc.brand + " " + doc.model;
+                }
+            "#,
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "brand": "Kefir", "model": "Hoodie", "discarded": false },
+        { "id": 2, "brand": "INTEL", "model": "Scarf", "discarded": false },
+        { "id": 3, "brand": "Doggo", "model": "Leash", "discarded": true },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "brand": "kefir",
+        "model": "Hoodie",
+        "discarded": false,
+        "label": "kefir Hoodie"
+      },
+      {
+        "id": 2,
+        "brand": "intel",
+        "model": "Scarf",
+        "discarded": false,
+        "label": "intel Scarf"
+      }
+    ]
+    "###);
+
+    // the function also applies to the partial updates, and can remove existing documents
+    let (task, _code) = index
+        .update_documents(
+            json!([{ "id": 1, "brand": "KEFIR" }, { "id": 2, "discarded": true }]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "brand": "kefir",
+        "model": "Hoodie",
+        "discarded": false,
+        "label": "kefir Hoodie"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn ingest_function_cannot_modify_the_primary_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = server.set_features(json!({ "editDocumentsByFunction": true })).await;
+    snapshot!(code, @"200 OK");
+
+    let (task, _code) = index.update_settings(json!({ "ingestFunction": "doc.id += 1" })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = index.add_documents(json!([{ "id": 1 }]), Some("id")).await;
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Document editions cannot modify a document's primary key",
+      "code": "edit_documents_by_function_error",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#edit_documents_by_function_error"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_ingest_function() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "ingestFunction": "doc.id" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "setting `ingestFunction` requires enabling the `edit documents by function` experimental feature. See https://github.com/orgs/meilisearch/discussions/762",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (_, code) = server.set_features(json!({ "editDocumentsByFunction": true })).await;
+    snapshot!(code, @"200 OK");
+
+    let (task, code) = index.update_settings(json!({ "ingestFunction": "doc.id +" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""invalid_settings_ingest_function""###);
+}
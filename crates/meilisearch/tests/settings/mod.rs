@@ -1,7 +1,12 @@
+mod attachments;
 mod distinct;
+mod document_schema;
 mod errors;
+mod flattening;
 mod get_settings;
+mod ingest_function;
 mod prefix_search_settings;
 mod proximity_settings;
+mod skip_unchanged_documents;
 mod tokenizer_customization;
 mod vectors;
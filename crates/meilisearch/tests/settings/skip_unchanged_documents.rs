@@ -0,0 +1,72 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn skip_unchanged_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "skipUnchangedDocuments": true })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "name": "kefir", "tags": ["good", "doggo"] },
+        { "id": 2, "name": "intel", "age": 4 },
+    ]);
+    let (task, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // a full re-sync where only the second document changed and lost a field
+    let documents = json!([
+        { "tags": ["good", "doggo"], "id": 1, "name": "kefir" },
+        { "id": 2, "name": "intel" },
+        { "id": 3, "name": "bobby" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "kefir",
+        "tags": [
+          "good",
+          "doggo"
+        ]
+      },
+      {
+        "id": 2,
+        "name": "intel"
+      },
+      {
+        "id": 3,
+        "name": "bobby"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.search_post(json!({ "q": "doggo" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "kefir",
+        "tags": [
+          "good",
+          "doggo"
+        ]
+      }
+    ]
+    "###);
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["skipUnchangedDocuments"], @"true");
+}
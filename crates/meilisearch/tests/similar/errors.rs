@@ -273,6 +273,37 @@ async fn similar_bad_limit() {
     "###);
 }
 
+#[actix_rt::test]
+async fn similar_bad_q() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        }}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await;
+
+    let (response, code) =
+        index.similar_post(json!({"id": 287947, "q": ["doggo"], "embedder": "manual"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.q`: expected a string, but found an array: `[\"doggo\"]`",
+      "code": "invalid_similar_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_q"
+    }
+    "###);
+    // Can't make the `q` fail with a get search since it'll accept anything as a string.
+}
+
 #[actix_rt::test]
 async fn similar_bad_filter() {
     // Since a filter is deserialized as a json Value it will never fail to deserialize.
@@ -341,10 +372,17 @@ async fn filter_invalid_syntax_object() {
         .similar(json!({"id": 287947, "filter": "title & Glass", "embedder": "manual"}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_similar_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "title & Glass",
+                "snippet": "title & Glass\n^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -379,10 +417,17 @@ async fn filter_invalid_syntax_array() {
         .similar(json!({"id": 287947, "filter": ["title & Glass"], "embedder": "manual"}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS EMPTY ARRAY`, `IS NOT EMPTY ARRAY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, `_geoBoundingBox`, `_geoContains`, or `_geoIntersects` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_similar_filter",
               "type": "invalid_request",
-              "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+              "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+              "details": {
+                "line": 1,
+                "column": 1,
+                "token": "title & Glass",
+                "snippet": "title & Glass\n^^^^^^^^^^^^^",
+                "suggestions": []
+              }
             }
             "###);
             snapshot!(code, @"400 Bad Request");
@@ -417,7 +462,17 @@ async fn filter_invalid_syntax_string() {
         "message": "Found unexpected characters at the end of the filter: `XOR title = Glass`. You probably forgot an `OR` or an `AND` rule.\n15:32 title = Glass XOR title = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+        "details": {
+          "line": 1,
+          "column": 15,
+          "token": "XOR title = Glass",
+          "snippet": "title = Glass XOR title = Glass\n              ^^^^^^^^^^^^^^^^^",
+          "suggestions": [
+            "AND",
+            "OR"
+          ]
+        }
     });
     index
         .similar(
@@ -463,7 +518,14 @@ async fn filter_invalid_attribute_array() {
                   "message": "Attribute `many` is not filterable. Available filterable attribute patterns are: `title`.\n1:5 many = Glass",
                   "code": "invalid_similar_filter",
                   "type": "invalid_request",
-                  "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+                  "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+                  "details": {
+                    "line": 1,
+                    "column": 1,
+                    "token": "many",
+                    "snippet": "many = Glass\n^^^^",
+                    "suggestions": []
+                  }
                 }
                 "###);
             },
@@ -504,7 +566,14 @@ async fn filter_invalid_attribute_string() {
                   "message": "Attribute `many` is not filterable. Available filterable attribute patterns are: `title`.\n1:5 many = Glass",
                   "code": "invalid_similar_filter",
                   "type": "invalid_request",
-                  "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+                  "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+                  "details": {
+                    "line": 1,
+                    "column": 1,
+                    "token": "many",
+                    "snippet": "many = Glass\n^^^^",
+                    "suggestions": []
+                  }
                 }
                 "###);
             },
@@ -539,7 +608,14 @@ async fn filter_reserved_geo_attribute_array() {
         "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+        "details": {
+          "line": 1,
+          "column": 1,
+          "token": "_geo = Glass",
+          "snippet": "_geo = Glass\n^^^^^^^^^^^^",
+          "suggestions": []
+        }
     });
     index
         .similar(
@@ -579,7 +655,14 @@ async fn filter_reserved_geo_attribute_string() {
         "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+        "details": {
+          "line": 1,
+          "column": 1,
+          "token": "_geo = Glass",
+          "snippet": "_geo = Glass\n^^^^^^^^^^^^",
+          "suggestions": []
+        }
     });
     index
         .similar(
@@ -619,7 +702,14 @@ async fn filter_reserved_attribute_array() {
         "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+        "details": {
+          "line": 1,
+          "column": 1,
+          "token": "_geoDistance = Glass",
+          "snippet": "_geoDistance = Glass\n^^^^^^^^^^^^^^^^^^^^",
+          "suggestions": []
+        }
     });
     index
         .similar(
@@ -659,7 +749,14 @@ async fn filter_reserved_attribute_string() {
        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+       "details": {
+         "line": 1,
+         "column": 1,
+         "token": "_geoDistance = Glass",
+         "snippet": "_geoDistance = Glass\n^^^^^^^^^^^^^^^^^^^^",
+         "suggestions": []
+       }
     });
     index
         .similar(
@@ -699,7 +796,14 @@ async fn filter_reserved_geo_point_array() {
         "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+        "details": {
+          "line": 1,
+          "column": 1,
+          "token": "_geoPoint = Glass",
+          "snippet": "_geoPoint = Glass\n^^^^^^^^^^^^^^^^^",
+          "suggestions": []
+        }
     });
     index
         .similar(
@@ -739,7 +843,14 @@ async fn filter_reserved_geo_point_string() {
        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
-        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
+        "link": "https://docs.meilisearch.com/errors#invalid_similar_filter",
+       "details": {
+         "line": 1,
+         "column": 1,
+         "token": "_geoPoint = Glass",
+         "snippet": "_geoPoint = Glass\n^^^^^^^^^^^^^^^^^",
+         "suggestions": []
+       }
     });
     index
         .similar(
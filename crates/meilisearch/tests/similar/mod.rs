@@ -619,6 +619,86 @@ async fn filter() {
         .await;
 }
 
+#[actix_rt::test]
+async fn query() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        }}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await;
+
+    let documents = DOCUMENTS.clone();
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    index
+        .similar(
+            json!({"id": "299537", "q": "-dragon", "attributesToRetrieve": ["title"], "embedder": "manual"}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "Shazam!"
+                  },
+                  {
+                    "title": "Escape Room"
+                  },
+                  {
+                    "title": "All Quiet on the Western Front"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .similar(
+            json!({"id": "299537", "q": "the", "attributesToRetrieve": ["title"], "embedder": "manual"}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "How to Train Your Dragon: The Hidden World"
+                  },
+                  {
+                    "title": "All Quiet on the Western Front"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .similar(
+            json!({"id": "299537", "q": "the -\"western front\"", "attributesToRetrieve": ["title"], "embedder": "manual"}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "How to Train Your Dragon: The Hidden World"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn limit_and_offset() {
     let server = Server::new().await;
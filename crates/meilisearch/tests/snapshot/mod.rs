@@ -203,3 +203,144 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn restore_indexes_to_a_date() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let (response, code) = server.restore_points().await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Restoring the indexes to a date requires launching Meilisearch with the `--experimental-restore-points` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        experimental_restore_points: Some(2),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, _code) = server.create_snapshot().await;
+    server.wait_task(response.uid()).await.succeeded();
+    let (response, code) = server.restore_points().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"1");
+
+    let (response, _code) = pets.add_documents(json!([{ "id": 1, "doggo": "intel" }]), None).await;
+    let response = pets.wait_task(response.uid()).await.succeeded();
+    let restore_at = response["finishedAt"].clone();
+    let (response, _code) = pets.add_documents(json!([{ "id": 2, "doggo": "max" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.restore_snapshot(json!({ "restoreAt": "yesterday" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.restoreAt`: `yesterday` is an invalid restore date. It should follow the RFC 3339 date-time format and be in the past.",
+      "code": "invalid_restore_at",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_restore_at"
+    }
+    "###);
+
+    let (response, code) = server.restore_snapshot(json!({ "restoreAt": restore_at })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["type"], @r###""pointInTimeRestore""###);
+    snapshot!(json_string!(response["details"], { ".restoreAt" => "[date]" }), @r###"
+    {
+      "restoreAt": "[date]",
+      "restoredIndexes": [
+        "pets"
+      ],
+      "replayedTasks": 1
+    }
+    "###);
+
+    let (documents, code) = pets.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      },
+      {
+        "id": 1,
+        "doggo": "intel"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn restore_an_index_from_a_snapshot() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let options =
+        Opt { snapshot_dir: snapshot_dir.path().to_owned(), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let pets = server.index("pets");
+    let (response, _code) = pets.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), None).await;
+    pets.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = server.create_snapshot().await;
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, _code) = pets.delete().await;
+    pets.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server
+        .restore_index_from_snapshot(json!({ "indexUid": "pets", "snapshot": "../db.snapshot" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid snapshot `../db.snapshot`: it must be the name of a file of the snapshot directory.",
+      "code": "invalid_restore_snapshot",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_restore_snapshot"
+    }
+    "###);
+
+    let (response, code) = server.restore_index_from_snapshot(json!({ "indexUid": "catto" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["message"], @r###""Could not restore the index `catto`: it doesn't exist in the snapshot `db.snapshot`.""###);
+
+    let (response, code) = server.restore_index_from_snapshot(json!({ "indexUid": "pets" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["type"], @r###""indexRestore""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "indexedDocuments": 1,
+      "snapshot": "db.snapshot"
+    }
+    "###);
+
+    let (documents, code) = pets.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      }
+    ]
+    "###);
+}
@@ -92,3 +92,21 @@ async fn swap_indexes_bad_indexes() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn swap_indexes_bad_execute_at() {
+    let server = Server::new_shared();
+
+    let (response, code) = server
+        .index_swap(json!([{ "indexes": ["doggo", "crabo"], "executeAt": "2010-11-13T00:00:00Z" }]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `[0].executeAt`: `2010-11-13T00:00:00Z` is not a valid date. It should follow the RFC 3339 format to represents a date or datetime in the future or specified as a null value. e.g. 'YYYY-MM-DD' or 'YYYY-MM-DD HH:MM:SS'.\n",
+      "code": "invalid_swap_execute_at",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_swap_execute_at"
+    }
+    "###);
+}
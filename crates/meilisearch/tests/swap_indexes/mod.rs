@@ -372,3 +372,20 @@ async fn swap_indexes() {
     let (res, _) = d.get_all_documents(GetAllDocumentsOptions::default()).await;
     snapshot!(res["results"], @r###"[{"id":1,"index":"c"}]"###);
 }
+
+#[actix_rt::test]
+async fn scheduled_swap_indexes() {
+    let server = Server::new().await;
+
+    let (task, code) = server
+        .index_swap(json!([{ "indexes": ["a", "b"], "executeAt": "2100-01-01T00:00:00Z" }]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+
+    // the swap stays enqueued until its date
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let (task, code) = server.get_task(task.uid()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(task["status"], @r###""enqueued""###);
+    snapshot!(task["details"]["executeAt"], @r###""2100-01-01T00:00:00Z""###);
+}
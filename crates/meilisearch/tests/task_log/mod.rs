@@ -0,0 +1,132 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn ship_and_replay_the_task_log() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let (response, code) = server.service.get("/task-log").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Shipping and replaying the task log requires launching Meilisearch with the `--experimental-task-log` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_task_log: true, ..default_settings(temp.path()) };
+    let primary = Server::new_with_options(options).await.unwrap();
+    let index = primary.index("pets");
+    let documents = json!([{ "id": 1, "name": "kefir" }, { "id": 2, "name": "intel" }]);
+    let (response, _code) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) =
+        index.update_settings(json!({ "filterableAttributes": ["name"] })).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.delete_document(1).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (archive, code) = primary.service.get_bytes("/task-log").await;
+    snapshot!(code, @"200 OK");
+
+    let temp = tempfile::tempdir().unwrap();
+    let replica = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let headers = vec![("content-type", "application/gzip")];
+    let (response, code) =
+        replica.service.post_bytes("/task-log/replay", archive.clone(), headers.clone()).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".tasks[].enqueuedAt" => "[date]" }), @r###"
+    {
+      "tasks": [
+        {
+          "taskUid": 0,
+          "indexUid": "pets",
+          "status": "enqueued",
+          "type": "documentAdditionOrUpdate",
+          "enqueuedAt": "[date]"
+        },
+        {
+          "taskUid": 1,
+          "indexUid": "pets",
+          "status": "enqueued",
+          "type": "settingsUpdate",
+          "enqueuedAt": "[date]"
+        },
+        {
+          "taskUid": 2,
+          "indexUid": "pets",
+          "status": "enqueued",
+          "type": "documentDeletion",
+          "enqueuedAt": "[date]"
+        }
+      ],
+      "skippedTasks": [],
+      "lastTaskUid": 2
+    }
+    "###);
+    let index = replica.index("pets");
+    index.wait_task(2).await.succeeded();
+    let (response, code) = index.get_document(2, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 2,
+      "name": "intel"
+    }
+    "###);
+    let (_response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"404 Not Found");
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["filterableAttributes"]), @r###"
+    [
+      "name"
+    ]
+    "###);
+
+    // the tasks already replayed are skipped
+    let (response, code) =
+        replica.service.post_bytes("/task-log/replay", archive.clone(), headers.clone()).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response), @r###"
+    {
+      "tasks": [],
+      "skippedTasks": [
+        0,
+        1,
+        2
+      ],
+      "lastTaskUid": 2
+    }
+    "###);
+
+    // the log can't be replayed on an instance that registered other tasks
+    let temp = tempfile::tempdir().unwrap();
+    let other = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let (response, _code) = other.index("dogs").create(None).await;
+    other.wait_task(response.uid()).await.succeeded();
+    let (response, code) = other.service.post_bytes("/task-log/replay", archive, headers).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Could not replay the task log: the instance already registered a task `0` that is not the one of the log, the log can only be replayed on an instance whose tasks all come from it.",
+      "code": "task_log_replay_failed",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#task_log_replay_failed"
+    }
+    "###);
+
+    // once shipped, the task log can be trimmed
+    let (response, code) = primary.service.delete("/task-log?until=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "deletedTasks": 3
+    }
+    "###);
+}
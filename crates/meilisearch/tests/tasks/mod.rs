@@ -1,12 +1,14 @@
 mod errors;
 mod webhook;
 
+use actix_web::test;
 use meili_snap::insta::assert_json_snapshot;
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -1063,3 +1065,227 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_web::test]
+async fn task_depends_on() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.add_documents(json!([{ "id": 1, "doggo": "kefir" }]), None).await;
+    assert_eq!(code, 202);
+    let dependency = task.uid().to_string();
+    let (task, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 2, "doggo": "intel" }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", &dependency)],
+            "",
+        )
+        .await;
+    assert_eq!(code, 202);
+    assert_eq!(task["dependsOn"].to_string(), dependency);
+    let task = index.wait_task(task.uid()).await.succeeded();
+    assert_eq!(task["dependsOn"].to_string(), dependency);
+
+    // a task whose dependency failed is failed without being processed
+    let (task, code) = index.add_documents(json!([{ "doggo": "bobby" }]), Some("id")).await;
+    assert_eq!(code, 202);
+    let dependency = task.uid().to_string();
+    let (task, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 3, "doggo": "bouvier" }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", &dependency)],
+            "",
+        )
+        .await;
+    assert_eq!(code, 202);
+    let task = index.wait_task(task.uid()).await.failed();
+    assert_eq!(task["error"]["code"], json!("task_dependency_failed"));
+    let (response, _code) = index.get_all_documents_raw("?fields=id").await;
+    snapshot!(response["results"], @r###"[{"id":1},{"id":2}]"###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 4 }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", "doggo")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_depends_on""###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 4 }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", "4294967295")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""Task `4294967295` cannot be depended on because it does not exist.""###);
+}
+
+#[actix_web::test]
+async fn pause_and_resume_scheduler() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    // only the master key can pause the scheduler
+    let (response, code) = server.service.post("/tasks/pause", json!(null)).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.service.post("/tasks/pause", json!(null)).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"{"paused":true}"###);
+
+    // the tasks are still enqueued, but not processed
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "doggo": "kefir" }]), None).await;
+    assert_eq!(code, 202);
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let (response, _code) = server.get_task(task.uid()).await;
+    snapshot!(response["status"], @r###""enqueued""###);
+
+    let (response, code) = server.service.post("/tasks/resume", json!(null)).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"{"paused":false}"###);
+    index.wait_task(task.uid()).await.succeeded();
+}
+
+#[actix_web::test]
+async fn drain_the_instance() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    // only the master key can drain the instance
+    let (response, code) = server.service.post("/drain", json!(null)).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    server.use_api_key("MASTER_KEY");
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "doggo": "kefir" }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await.succeeded();
+
+    // nothing is processing, the instance is drained right away
+    let (response, code) = server.service.post("/drain", json!(null)).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response, @r###"{"status":"drained"}"###);
+
+    let (response, code) = index.add_documents(json!([{ "id": 2, "doggo": "intel" }]), None).await;
+    snapshot!(code, @"503 Service Unavailable");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Meilisearch cannot receive write operations because it is draining before being removed. Please send them to another instance.",
+      "code": "read_only_mode",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#read_only_mode"
+    }
+    "###);
+
+    let (response, code) = server.service.get("/health").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"{"status":"drained"}"###);
+}
+
+#[actix_rt::test]
+async fn refuse_tasks_when_queue_is_full() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut server = Server::new_with_options(Opt {
+        master_key: Some("MASTER_KEY".to_string()),
+        experimental_max_enqueued_tasks: Some(1),
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap();
+    server.use_api_key("MASTER_KEY");
+
+    // the scheduler is paused so the first task stays enqueued
+    let (_response, code) = server.service.post("/tasks/pause", json!(null)).await;
+    snapshot!(code, @"200 OK");
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (stats, _code) = server.stats().await;
+    snapshot!(stats["enqueuedTasks"], @"1");
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::post()
+        .uri("/indexes/test/documents")
+        .set_payload(r#"[{ "id": 2 }]"#)
+        .insert_header(("content-type", "application/json"))
+        .insert_header(("Authorization", "Bearer MASTER_KEY"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"429 Too Many Requests");
+    snapshot!(res.headers().get("retry-after").unwrap().to_str().unwrap(), @"10");
+    let body = test::read_body(res).await;
+    let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Meilisearch cannot receive write operations because the task queue reached its limit of 1 enqueued tasks. Please retry once some of them have been processed.",
+      "code": "too_many_enqueued_tasks",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#too_many_enqueued_tasks"
+    }
+    "###);
+
+    // the task cancelations are still accepted
+    let (response, code) = server.cancel_tasks("uids=0").await;
+    snapshot!(code, @"200 OK");
+    let cancelation = response.uid();
+
+    let (_response, code) = server.service.post("/tasks/resume", json!(null)).await;
+    snapshot!(code, @"200 OK");
+    server.wait_task(cancelation).await.succeeded();
+    server.wait_task(task.uid()).await;
+
+    // once the queue drained the new tasks are accepted again
+    let (_task, code) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    snapshot!(code, @"202 Accepted");
+}
+
+#[actix_web::test]
+async fn task_labels() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    // the server is shared, the labels must be unique to this test
+    let labels = format!("source={},deploy=2024-10-01", index.uid);
+
+    let (task, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1, "doggo": "kefir" }]"#,
+            vec![("Content-Type", "application/json"), ("TaskLabels", &labels)],
+            "",
+        )
+        .await;
+    assert_eq!(code, 202);
+    snapshot!(task["labels"]["deploy"], @r###""2024-10-01""###);
+    let labeled = index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.add_documents(json!([{ "id": 2, "doggo": "intel" }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = server.tasks_filter(&format!("labels=source={}", index.uid)).await;
+    assert_eq!(code, 200);
+    let uids: Vec<_> = response["results"].as_array().unwrap().iter().map(|t| &t["uid"]).collect();
+    assert_eq!(uids, vec![&labeled["uid"]]);
+    assert_eq!(response["results"][0]["labels"]["source"], json!(index.uid));
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 3 }]"#,
+            vec![("Content-Type", "application/json"), ("TaskLabels", "source")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_labels""###);
+
+    let (response, code) = server.tasks_filter("labels=source=a=b").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_labels""###);
+}
@@ -9,6 +9,7 @@ use actix_web::dev::{ServiceFactory, ServiceResponse};
 use actix_web::web::{Bytes, Data};
 use actix_web::{post, App, HttpRequest, HttpResponse, HttpServer};
 use meili_snap::snapshot;
+use meilisearch::option::TaskWebhooks;
 use meilisearch::Opt;
 use tokio::sync::mpsc;
 use url::Url;
@@ -127,3 +128,36 @@ async fn test_basic_webhook() {
 
     server_handle.abort();
 }
+
+#[actix_web::test]
+async fn test_filtered_webhooks() {
+    let WebhookHandle { server_handle, url, mut receiver } = create_webhook_server().await;
+
+    let db_path = tempfile::tempdir().unwrap();
+    let task_webhooks: TaskWebhooks =
+        format!(r#"[{{ "url": "{url}", "indexUids": ["kefir"], "statuses": ["succeeded"] }}]"#)
+            .parse()
+            .unwrap();
+    let server = Server::new_with_options(Opt {
+        task_webhooks: Some(task_webhooks),
+        ..default_settings(db_path.path())
+    })
+    .await
+    .unwrap();
+
+    let (task, _status) = server.index("tamo").add_documents(json!({ "id": 1 }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _status) = server.index("kefir").add_documents(json!({ "id": 1 }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    // only the task of kefir is sent
+    let payload = receiver.recv().await.unwrap();
+    let payload = String::from_utf8(payload).unwrap();
+    let tasks: Vec<serde_json::Value> =
+        payload.lines().map(|json| serde_json::from_str(json).unwrap()).collect();
+    snapshot!(tasks.len(), @"1");
+    snapshot!(tasks[0]["indexUid"], @r#""kefir""#);
+    snapshot!(tasks[0]["uid"], @"1");
+
+    server_handle.abort();
+}
@@ -335,3 +335,55 @@ async fn binary_quantize_clear_documents() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn binary_quantized_search_is_rescored_with_full_precision_vectors() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    // Both vectors have the same binary quantized representation
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [0.1, 1.0, 1.0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [1.0, 0.1, 0.1] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "binaryQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.1, 0.1],
+            "hybrid": { "semanticRatio": 1.0, "embedder": "manual" },
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":1},{"id":0}]"###);
+}
@@ -1144,6 +1144,99 @@ async fn add_vector_and_user_provided() {
     "###);
 }
 
+#[actix_rt::test]
+async fn chunked_document_template() {
+    let (_mock, mut setting) = create_mock().await;
+    setting["documentTemplateChunkBytes"] = json!(6);
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "kefir intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "name": "kefir",
+          "_vectors": {
+            "rest": {
+              "embeddings": [
+                [
+                  0.0,
+                  0.0,
+                  0.0
+                ]
+              ],
+              "regenerate": true
+            }
+          }
+        },
+        {
+          "id": 1,
+          "name": "kefir intel",
+          "_vectors": {
+            "rest": {
+              "embeddings": [
+                [
+                  0.0,
+                  0.0,
+                  0.0
+                ],
+                [
+                  1.0,
+                  1.0,
+                  1.0
+                ]
+              ],
+              "regenerate": true
+            }
+          }
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": { "documentTemplateChunkBytes": 0 },
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.rest.documentTemplateChunkBytes`: `documentTemplateChunkBytes` cannot be zero",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn server_returns_bad_request() {
     let (mock, _setting) = create_mock_multiple().await;
@@ -2111,3 +2204,87 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn embedder_usage() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            let embedding = if text == "intel" { [1.0, 1.0, 1.0] } else { [0.0, 0.0, 0.0] };
+            ResponseTemplate::new(200).set_body_json(json!({
+                "data": embedding,
+                "usage": { "total_tokens": text.len() },
+            }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "dimensions": 3,
+                  "request": "{{text}}",
+                  "response": {
+                    "data": "{{embedding}}"
+                  },
+                  "documentTemplate": "{{doc.name}}",
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "echo", "_vectors": { "rest": [1, 1, 1] }},
+      {"id": 2, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await.succeeded();
+
+    let (batch, _code) = index.get_batch(task["batchUid"].as_u64().unwrap() as u32).await;
+    snapshot!(json_string!(batch["stats"]["embedderUsage"]), @r###"
+    {
+      "rest": {
+        "requests": 2,
+        "texts": 2,
+        "tokens": 10
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"q": "intel", "hybrid": {"semanticRatio": 1.0, "embedder": "rest"}}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["id"], @"2");
+
+    let (stats, code) = index.stats().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(stats["embedderUsage"]), @r###"
+    {
+      "rest": {
+        "indexing": {
+          "requests": 2,
+          "texts": 2,
+          "tokens": 10
+        },
+        "search": {
+          "requests": 1,
+          "texts": 1,
+          "tokens": 5
+        }
+      }
+    }
+    "###);
+}
@@ -17,7 +17,7 @@ async fn field_unavailable_for_source() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.manual`: Field `documentTemplate` unavailable for source `userProvided`.\n  - note: `documentTemplate` is available for sources: `openAi`, `huggingFace`, `ollama`, `rest`\n  - note: available fields for source `userProvided`: `source`, `dimensions`, `distribution`, `binaryQuantized`",
+      "message": "`.embedders.manual`: Field `documentTemplate` unavailable for source `userProvided`.\n  - note: `documentTemplate` is available for sources: `openAi`, `huggingFace`, `ollama`, `rest`, `cohere`, `azureOpenAi`, `bedrock`, `onnx`\n  - note: available fields for source `userProvided`: `source`, `dimensions`, `distribution`, `binaryQuantized`",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -32,7 +32,7 @@ async fn field_unavailable_for_source() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.default`: Field `revision` unavailable for source `openAi`.\n  - note: `revision` is available for sources: `huggingFace`\n  - note: available fields for source `openAi`: `source`, `model`, `apiKey`, `dimensions`, `documentTemplate`, `documentTemplateMaxBytes`, `url`, `distribution`, `binaryQuantized`",
+      "message": "`.embedders.default`: Field `revision` unavailable for source `openAi`.\n  - note: `revision` is available for sources: `huggingFace`, `onnx`\n  - note: available fields for source `openAi`: `source`, `model`, `apiKey`, `dimensions`, `documentTemplate`, `documentTemplateMaxBytes`, `documentTemplateChunkBytes`, `url`, `distribution`, `binaryQuantized`",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -1066,3 +1066,132 @@ async fn composite_checks() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn cohere_dimensions_checks() {
+    let server = super::get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "cohere": {"source": "cohere", "apiKey": "toto", "dimensions": 768}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.cohere.dimensions`: Model `embed-english-v3.0` does not support dimensions 768. Supported dimensions: 1024",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "cohere": {"source": "cohere", "apiKey": "toto", "model": "embed-v4.0", "dimensions": 768}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.cohere.dimensions`: Model `embed-v4.0` does not support dimensions 768. Supported dimensions: 256, 512, 1024, 1536",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn azure_openai_checks() {
+    let server = super::get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "azure": {"source": "azureOpenAi", "url": "https://my-resource.openai.azure.com"}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.azure`: Missing field `model` (note: this field is mandatory for source `azureOpenAi`)",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "azure": {"source": "azureOpenAi", "model": "my-deployment"}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.azure`: Missing field `url` (note: this field is mandatory for source `azureOpenAi`)",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn bedrock_checks() {
+    let server = super::get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "bedrock": {"source": "bedrock", "model": "amazon.nova-pro-v1:0", "apiKey": "AKID:SECRET"}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.bedrock.model`: Invalid model `amazon.nova-pro-v1:0` for Bedrock. Supported models: [\"amazon.titan-embed-text-*\", \"cohere.embed-*\"]",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "bedrock": {"source": "bedrock", "model": "amazon.titan-embed-text-v2:0", "apiKey": "AKID"}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.bedrock.apiKey`: Invalid API key for Bedrock. Expected `<accessKeyId>:<secretAccessKey>` or `<accessKeyId>:<secretAccessKey>:<sessionToken>`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn onnx_checks() {
+    let server = super::get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": { "onnx": {"source": "onnx", "dimensions": 384}},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: Field `dimensions` unavailable for source `onnx`.\n  - note: `dimensions` is available for sources: `openAi`, `ollama`, `userProvided`, `rest`, `cohere`, `azureOpenAi`, `bedrock`\n  - note: available fields for source `onnx`: `source`, `model`, `revision`, `pooling`, `documentTemplate`, `documentTemplateMaxBytes`, `documentTemplateChunkBytes`, `distribution`, `binaryQuantized`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
@@ -1,13 +1,13 @@
 use std::fs::{read_dir, read_to_string, remove_file, File};
 use std::io::{BufWriter, Write as _};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::{bail, Context};
 use clap::{Parser, Subcommand, ValueEnum};
 use dump::{DumpWriter, IndexMetadata};
 use file_store::FileStore;
-use meilisearch_auth::{open_auth_store_env, AuthController};
+use meilisearch_auth::{open_auth_store_env, AuthController, EncryptionKey};
 use meilisearch_types::batches::Batch;
 use meilisearch_types::heed::types::{Bytes, SerdeJson, Str};
 use meilisearch_types::heed::{
@@ -17,7 +17,7 @@ use meilisearch_types::milli::constants::RESERVED_VECTORS_FIELD_NAME;
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
 use meilisearch_types::milli::{obkv_to_json, BEU32};
-use meilisearch_types::tasks::{Status, Task};
+use meilisearch_types::tasks::{KindWithContent, Status, Task};
 use meilisearch_types::versioning::{get_version, parse_version};
 use meilisearch_types::Index;
 use serde_json::Value::Object;
@@ -70,6 +70,10 @@ enum Command {
         /// the processed ones is not particularly interesting.
         #[arg(long)]
         skip_enqueued_tasks: bool,
+
+        /// The key the API keys have been encrypted with, if Meilisearch was launched with one.
+        #[arg(long)]
+        auth_encryption_key: Option<String>,
     },
 
     /// Exports the documents of an index in NDJSON format from a Meilisearch index to stdout.
@@ -137,6 +141,30 @@ enum Command {
         #[arg(long, value_delimiter = ',')]
         index_part: Vec<IndexPart>,
     },
+
+    /// Inspects the content of the database without starting Meilisearch.
+    ///
+    /// Opening an index writes to it, so this command must only be executed on the database of
+    /// a crashed or stopped instance, for example to investigate a support case.
+    Inspect {
+        #[command(subcommand)]
+        command: InspectCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum InspectCommand {
+    /// Lists the indexes along with their primary key and number of documents.
+    ListIndexes,
+
+    /// Prints the settings of an index in JSON format to stdout.
+    Settings { index_name: String },
+
+    /// Prints the number of documents in an index.
+    CountDocuments { index_name: String },
+
+    /// Prints a document by its external id in JSON format to stdout.
+    GetDocument { index_name: String, document_id: String },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -152,8 +180,14 @@ fn main() -> anyhow::Result<()> {
 
     match command {
         Command::ClearTaskQueue => clear_task_queue(db_path),
-        Command::ExportADump { dump_dir, skip_enqueued_tasks } => {
-            export_a_dump(db_path, dump_dir, skip_enqueued_tasks, detected_version)
+        Command::ExportADump { dump_dir, skip_enqueued_tasks, auth_encryption_key } => {
+            export_a_dump(
+                db_path,
+                dump_dir,
+                skip_enqueued_tasks,
+                auth_encryption_key,
+                detected_version,
+            )
         }
         Command::ExportDocuments { index_name, ignore_vectors, offset } => {
             export_documents(db_path, index_name, ignore_vectors, offset)
@@ -166,6 +200,7 @@ fn main() -> anyhow::Result<()> {
         Command::HairDryer { index_name, index_part } => {
             hair_dryer(db_path, &index_name, &index_part)
         }
+        Command::Inspect { command } => inspect(db_path, command),
     }
 }
 
@@ -184,6 +219,8 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     let kind = try_opening_poly_database(&env, &wtxn, "kind")?;
     let index_tasks = try_opening_poly_database(&env, &wtxn, "index-tasks")?;
     let canceled_by = try_opening_poly_database(&env, &wtxn, "canceled_by")?;
+    let depends_on = try_opening_poly_database(&env, &wtxn, "depends-on")?;
+    let labels = try_opening_poly_database(&env, &wtxn, "labels")?;
     let enqueued_at = try_opening_poly_database(&env, &wtxn, "enqueued-at")?;
     let started_at = try_opening_poly_database(&env, &wtxn, "started-at")?;
     let finished_at = try_opening_poly_database(&env, &wtxn, "finished-at")?;
@@ -193,6 +230,8 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     try_clearing_poly_database(&mut wtxn, kind, "kind")?;
     try_clearing_poly_database(&mut wtxn, index_tasks, "index-tasks")?;
     try_clearing_poly_database(&mut wtxn, canceled_by, "canceled_by")?;
+    try_clearing_poly_database(&mut wtxn, depends_on, "depends-on")?;
+    try_clearing_poly_database(&mut wtxn, labels, "labels")?;
     try_clearing_poly_database(&mut wtxn, enqueued_at, "enqueued-at")?;
     try_clearing_poly_database(&mut wtxn, started_at, "started-at")?;
     try_clearing_poly_database(&mut wtxn, finished_at, "finished-at")?;
@@ -259,6 +298,7 @@ fn export_a_dump(
     db_path: PathBuf,
     dump_dir: PathBuf,
     skip_enqueued_tasks: bool,
+    auth_encryption_key: Option<String>,
     detected_version: (u32, u32, u32),
 ) -> Result<(), anyhow::Error> {
     let started_at = OffsetDateTime::now_utc();
@@ -295,7 +335,12 @@ fn export_a_dump(
     let auth_path = db_path.join("auth");
     std::fs::create_dir_all(&auth_path).context("While creating the auth directory")?;
     let auth_env = open_auth_store_env(&auth_path).context("While opening the auth store")?;
-    let auth_store = AuthController::new(auth_env, &None)
+    let encryption_key = auth_encryption_key
+        .as_deref()
+        .map(EncryptionKey::from_base64)
+        .transpose()
+        .context("While parsing the auth encryption key")?;
+    let auth_store = AuthController::new(auth_env, &None, encryption_key)
         .with_context(|| format!("While opening the auth store at {}", db_path.display()))?;
     let mut dump_keys = dump.create_keys()?;
     let mut count = 0;
@@ -323,7 +368,11 @@ fn export_a_dump(
     for ret in all_tasks.iter(&rtxn)? {
         let (_, t) = ret?;
         let status = t.status;
-        let content_file = t.content_uuid();
+        // The dumps uploaded to be imported are not made of documents
+        let content_file = match t.kind {
+            KindWithContent::DumpImport { .. } => None,
+            _ => t.content_uuid(),
+        };
 
         if status == Status::Enqueued && skip_enqueued_tasks {
             continue;
@@ -688,3 +737,120 @@ fn hair_dryer(
 
     Ok(())
 }
+
+fn inspect(db_path: PathBuf, command: InspectCommand) -> anyhow::Result<()> {
+    match command {
+        InspectCommand::ListIndexes => inspect_list_indexes(db_path),
+        InspectCommand::Settings { index_name } => inspect_settings(db_path, &index_name),
+        InspectCommand::CountDocuments { index_name } => {
+            inspect_count_documents(db_path, &index_name)
+        }
+        InspectCommand::GetDocument { index_name, document_id } => {
+            inspect_get_document(db_path, &index_name, &document_id)
+        }
+    }
+}
+
+/// Opens the index named `index_name` by looking it up in the index mapping of the scheduler.
+fn open_index_by_name(db_path: &Path, index_name: &str) -> anyhow::Result<Index> {
+    let index_scheduler_path = db_path.join("tasks");
+    let env = unsafe {
+        EnvOpenOptions::new().read_txn_without_tls().max_dbs(100).open(&index_scheduler_path)
+    }
+    .with_context(|| format!("While trying to open {:?}", index_scheduler_path.display()))?;
+
+    let rtxn = env.read_txn()?;
+    let index_mapping: Database<Str, UuidCodec> =
+        try_opening_database(&env, &rtxn, "index-mapping")?;
+
+    let Some(uuid) = index_mapping.get(&rtxn, index_name)? else {
+        bail!("Target index {index_name} not found!")
+    };
+
+    let index_path = db_path.join("indexes").join(uuid.to_string());
+    Index::new(EnvOpenOptions::new().read_txn_without_tls(), &index_path, false).with_context(
+        || format!("While trying to open the index at path {:?}", index_path.display()),
+    )
+}
+
+fn inspect_list_indexes(db_path: PathBuf) -> anyhow::Result<()> {
+    let index_scheduler_path = db_path.join("tasks");
+    let env = unsafe {
+        EnvOpenOptions::new().read_txn_without_tls().max_dbs(100).open(&index_scheduler_path)
+    }
+    .with_context(|| format!("While trying to open {:?}", index_scheduler_path.display()))?;
+
+    let rtxn = env.read_txn()?;
+    let index_mapping: Database<Str, UuidCodec> =
+        try_opening_database(&env, &rtxn, "index-mapping")?;
+
+    let mut count = 0;
+    for result in index_mapping.iter(&rtxn)? {
+        let (uid, uuid) = result?;
+        let index_path = db_path.join("indexes").join(uuid.to_string());
+        let index = Index::new(EnvOpenOptions::new().read_txn_without_tls(), &index_path, false)
+            .with_context(|| {
+                format!("While trying to open the index at path {:?}", index_path.display())
+            })?;
+
+        let rtxn = index.read_txn()?;
+        let primary_key = index.primary_key(&rtxn)?.unwrap_or("<none>");
+        let number_of_documents = index.number_of_documents(&rtxn)?;
+        println!("{uid}\t{uuid}\tprimary key: {primary_key}\tdocuments: {number_of_documents}");
+        count += 1;
+    }
+
+    eprintln!("Found {count} indexes");
+
+    Ok(())
+}
+
+fn inspect_settings(db_path: PathBuf, index_name: &str) -> anyhow::Result<()> {
+    let index = open_index_by_name(&db_path, index_name)?;
+    let rtxn = index.read_txn()?;
+    let settings = meilisearch_types::settings::settings(
+        &index,
+        &rtxn,
+        meilisearch_types::settings::SecretPolicy::HideSecrets,
+    )?;
+
+    let mut stdout = BufWriter::new(std::io::stdout());
+    serde_json::to_writer_pretty(&mut stdout, &settings)?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+fn inspect_count_documents(db_path: PathBuf, index_name: &str) -> anyhow::Result<()> {
+    let index = open_index_by_name(&db_path, index_name)?;
+    let rtxn = index.read_txn()?;
+    println!("{}", index.number_of_documents(&rtxn)?);
+
+    Ok(())
+}
+
+fn inspect_get_document(
+    db_path: PathBuf,
+    index_name: &str,
+    document_id: &str,
+) -> anyhow::Result<()> {
+    let index = open_index_by_name(&db_path, index_name)?;
+    let rtxn = index.read_txn()?;
+
+    let Some(internal_id) = index.external_documents_ids().get(&rtxn, document_id)? else {
+        bail!("Document {document_id} not found in index {index_name}!")
+    };
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+    let document = index.document(&rtxn, internal_id)?;
+    let document = obkv_to_json(&all_fields, &fields_ids_map, document)?;
+
+    let mut stdout = BufWriter::new(std::io::stdout());
+    serde_json::to_writer_pretty(&mut stdout, &document)?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use base64::Engine as _;
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use utoipa::ToSchema;
+
+use crate::{Result, UserError};
+
+/// The extraction service must answer within this delay, as the indexing waits for it.
+const EXTRACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The attributes holding binary files whose text is extracted at indexing time.
+///
+/// An attachment is an object `{"contentType": "application/pdf", "data": "<base64>"}` that is
+/// replaced by its extracted text, which makes it searchable like any other string attribute.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct AttachmentsSettings {
+    /// The top-level attributes holding the attachments.
+    #[deserr(default)]
+    pub attributes: Vec<String>,
+    /// URL of the extraction service, receiving the file in the body of a `PUT` request with its
+    /// content type and replying with the extracted text, like the `/tika` endpoint of Apache Tika.
+    ///
+    /// # Defaults
+    ///
+    /// - Without a service, only the `text/plain` and `text/html` attachments can be extracted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub extraction_url: Option<String>,
+}
+
+/// The attachment of a document, before its text is extracted.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct Attachment {
+    content_type: String,
+    data: String,
+}
+
+/// Replaces the attachments of the documents by their text.
+pub struct AttachmentExtractor {
+    attributes: Vec<String>,
+    extraction_url: Option<String>,
+    client: ureq::Agent,
+}
+
+impl AttachmentExtractor {
+    pub fn new(settings: AttachmentsSettings) -> Result<Self> {
+        let AttachmentsSettings { attributes, extraction_url } = settings;
+        if let Some(url) = &extraction_url {
+            url::Url::parse(url).map_err(|inner_error| {
+                UserError::InvalidAttachmentsExtractionUrl { inner_error, url: url.clone() }
+            })?;
+        }
+        let client = ureq::AgentBuilder::new().timeout(EXTRACTION_TIMEOUT).build();
+        Ok(Self { attributes, extraction_url, client })
+    }
+
+    /// Replaces the attachments of the document by their text.
+    ///
+    /// The attributes that are not attachment objects, like the text extracted from a previous
+    /// version of the document, are left as is.
+    pub fn extract(&self, external_doc: &str, document: &mut Map<String, Value>) -> Result<()> {
+        for attribute in &self.attributes {
+            let Some(value @ Value::Object(_)) = document.get_mut(attribute) else { continue };
+            let attachment: Attachment =
+                serde_json::from_value(value.take()).map_err(|e| UserError::InvalidAttachment {
+                    document_id: external_doc.to_string(),
+                    attribute: attribute.clone(),
+                    error: e.to_string(),
+                })?;
+            *value = Value::String(self.extract_text(external_doc, attribute, attachment)?);
+        }
+        Ok(())
+    }
+
+    fn extract_text(
+        &self,
+        external_doc: &str,
+        attribute: &str,
+        attachment: Attachment,
+    ) -> Result<String> {
+        let error = |error: String| UserError::InvalidAttachment {
+            document_id: external_doc.to_string(),
+            attribute: attribute.to_string(),
+            error,
+        };
+        let Attachment { content_type, data } = attachment;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| error(format!("`data` is not valid base64: {e}")))?;
+
+        // The content type may carry parameters, like `text/html; charset=utf-8`.
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match (self.extraction_url.as_deref(), mime.as_str()) {
+            (Some(url), _) => {
+                let response = self
+                    .client
+                    .put(url)
+                    .set("Content-Type", &content_type)
+                    .set("Accept", "text/plain")
+                    .send_bytes(&bytes)
+                    .map_err(|e| error(format!("the extraction service failed: {e}")))?;
+                response
+                    .into_string()
+                    .map_err(|e| error(format!("could not read the extracted text: {e}")))
+            }
+            (None, "text/plain") => String::from_utf8(bytes)
+                .map_err(|e| error(format!("the text is not valid UTF-8: {e}"))),
+            (None, "text/html") => String::from_utf8(bytes)
+                .map(|html| html_to_text(&html))
+                .map_err(|e| error(format!("the HTML is not valid UTF-8: {e}"))),
+            (None, _) => Err(error(format!(
+                "the `{content_type}` content type needs an extraction service, set `attachments.extractionUrl`"
+            ))),
+        }
+        .map_err(Into::into)
+    }
+}
+
+/// Keeps the text of an HTML document, without its tags, scripts and styles.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        let tag = rest[..tag_end].to_ascii_lowercase();
+        rest = &rest[tag_end..];
+
+        // The content of the scripts and styles is not text.
+        for element in ["script", "style"] {
+            if tag.starts_with(&format!("<{element}")) {
+                let closing = format!("</{element}");
+                let end = rest.to_ascii_lowercase().find(&closing).unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+        }
+        text.push(' ');
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::html_to_text;
+
+    #[test]
+    fn html_text() {
+        let html = r#"<html><head><style>p { color: red; }</style><script>let a = "<b>";</script></head>
+            <body><h1>Hello&nbsp;world</h1><p>Fish &amp; chips</p></body></html>"#;
+        insta::assert_snapshot!(html_to_text(html), @"Hello world Fish & chips");
+    }
+}
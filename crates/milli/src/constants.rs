@@ -4,3 +4,5 @@ pub static VERSION_PATCH: &str = env!("CARGO_PKG_VERSION_PATCH");
 
 pub const RESERVED_VECTORS_FIELD_NAME: &str = "_vectors";
 pub const RESERVED_GEO_FIELD_NAME: &str = "_geo";
+pub const RESERVED_DETECTED_LOCALES_FIELD_NAME: &str = "_detectedLocales";
+pub const RESERVED_GEOJSON_FIELD_NAME: &str = "_geojson";
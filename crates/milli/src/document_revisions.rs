@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+use crate::Object;
+
+/// A previous version of a document, kept when the index has a `maxDocumentRevisions` setting.
+///
+/// The revisions of a document are stored from the oldest to the most recent one, and only the
+/// last `maxDocumentRevisions` ones are kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentRevision {
+    /// The number of the revision, increasing with each version of the document.
+    pub revision: u64,
+    /// When this version was replaced by a newer one or deleted.
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String, example = "2024-08-08T17:05:55.791772Z")]
+    pub recorded_at: OffsetDateTime,
+    /// The content of the document at this revision.
+    #[schema(value_type = Object)]
+    pub document: Object,
+}
+
+/// Appends the document to the revisions, dropping the oldest ones beyond `max_revisions`.
+///
+/// Nothing is appended when the document is equal to the last revision, so that the documents
+/// sent again without changes don't fill the history.
+pub(crate) fn push_revision(
+    revisions: &mut Vec<DocumentRevision>,
+    document: Object,
+    max_revisions: u64,
+) {
+    let last = revisions.last();
+    if last.is_some_and(|last| last.document == document) {
+        return;
+    }
+
+    let revision = last.map_or(1, |last| last.revision + 1);
+    revisions.push(DocumentRevision { revision, recorded_at: OffsetDateTime::now_utc(), document });
+    truncate_revisions(revisions, max_revisions);
+}
+
+/// Keeps only the last `max_revisions` revisions.
+pub(crate) fn truncate_revisions(revisions: &mut Vec<DocumentRevision>, max_revisions: u64) {
+    let excess = revisions.len().saturating_sub(max_revisions as usize);
+    revisions.drain(..excess);
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn document(value: serde_json::Value) -> Object {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn push_and_truncate() {
+        let mut revisions = Vec::new();
+        push_revision(&mut revisions, document(json!({ "id": 1, "name": "kefir" })), 2);
+        push_revision(&mut revisions, document(json!({ "id": 1, "name": "kefir" })), 2);
+        push_revision(&mut revisions, document(json!({ "id": 1, "name": "intel" })), 2);
+        push_revision(&mut revisions, document(json!({ "id": 1, "name": "bobby" })), 2);
+
+        let summary: Vec<_> =
+            revisions.iter().map(|r| (r.revision, r.document["name"].clone())).collect();
+        assert_eq!(summary, vec![(2, json!("intel")), (3, json!("bobby"))]);
+
+        truncate_revisions(&mut revisions, 1);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].revision, 3);
+    }
+}
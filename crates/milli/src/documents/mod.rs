@@ -3,6 +3,7 @@ mod enriched;
 mod primary_key;
 mod reader;
 mod serde_impl;
+mod sort;
 
 use std::fmt::Debug;
 use std::io;
@@ -18,6 +19,7 @@ pub use primary_key::{
 };
 pub use reader::{DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchReader};
 use serde::{Deserialize, Serialize};
+pub use sort::recursive_sort;
 
 use crate::error::{FieldIdMapMissingEntry, InternalError};
 use crate::{FieldId, Object, Result};
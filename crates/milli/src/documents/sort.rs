@@ -0,0 +1,109 @@
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+
+use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::heed_codec::facet::FacetGroupKeyCodec;
+use crate::heed_codec::BytesRefCodec;
+use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
+use crate::{is_faceted, AscDesc, DocumentId, FieldId, Index, Member, Result, UserError};
+
+/// Returns the first `limit` candidates ordered by the given sort criteria.
+///
+/// Like the sort ranking rule, the numbers come before the strings, and the documents
+/// without any value for a criterion are ranked after the others for this criterion.
+/// The documents that no criterion can tell apart are returned in the internal docid order.
+pub fn recursive_sort<'t>(
+    index: &Index,
+    rtxn: &'t RoTxn<'t>,
+    sort: &[AscDesc],
+    candidates: &RoaringBitmap,
+    limit: usize,
+) -> Result<Vec<DocumentId>> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let sortable_fields = index.sortable_fields(rtxn)?;
+
+    let mut criteria = Vec::with_capacity(sort.len());
+    for asc_desc in sort {
+        let (member, ascending) = match asc_desc {
+            AscDesc::Asc(member) => (member, true),
+            AscDesc::Desc(member) => (member, false),
+        };
+        let field = match member {
+            Member::Field(field) => field.as_str(),
+            Member::Geo(_) => RESERVED_GEO_FIELD_NAME,
+        };
+        if matches!(member, Member::Geo(_)) || !is_faceted(field, &sortable_fields) {
+            let (valid_fields, hidden_fields) =
+                index.remove_hidden_fields(rtxn, sortable_fields)?;
+            return Err(UserError::InvalidSortableAttribute {
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+        criteria.push((fields_ids_map.id(field), ascending));
+    }
+
+    let mut output = Vec::new();
+    sort_candidates(index, rtxn, &criteria, candidates.clone(), limit, &mut output)?;
+    Ok(output)
+}
+
+fn sort_candidates<'t>(
+    index: &Index,
+    rtxn: &'t RoTxn<'t>,
+    criteria: &[(Option<FieldId>, bool)],
+    candidates: RoaringBitmap,
+    limit: usize,
+    output: &mut Vec<DocumentId>,
+) -> Result<()> {
+    let remaining_limit = limit.saturating_sub(output.len());
+    let Some((&(field_id, ascending), criteria)) = criteria.split_first() else {
+        output.extend(candidates.iter().take(remaining_limit));
+        return Ok(());
+    };
+    if candidates.len() <= 1 {
+        output.extend(candidates.iter().take(remaining_limit));
+        return Ok(());
+    }
+
+    let mut remaining = candidates;
+    if let Some(field_id) = field_id {
+        let number_db =
+            index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let string_db =
+            index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+
+        let buckets: Box<dyn Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>> + 't> =
+            if ascending {
+                let number_iter =
+                    ascending_facet_sort(rtxn, number_db, field_id, remaining.clone())?;
+                let string_iter =
+                    ascending_facet_sort(rtxn, string_db, field_id, remaining.clone())?;
+                Box::new(number_iter.chain(string_iter))
+            } else {
+                let number_iter =
+                    descending_facet_sort(rtxn, number_db, field_id, remaining.clone())?;
+                let string_iter =
+                    descending_facet_sort(rtxn, string_db, field_id, remaining.clone())?;
+                Box::new(number_iter.chain(string_iter))
+            };
+
+        for bucket in buckets {
+            let (docids, _value) = bucket?;
+            // A document with both numbers and strings is ranked by its numbers.
+            let docids = docids & &remaining;
+            if docids.is_empty() {
+                continue;
+            }
+            remaining -= &docids;
+            sort_candidates(index, rtxn, criteria, docids, limit, output)?;
+            if output.len() >= limit {
+                return Ok(());
+            }
+        }
+    }
+
+    sort_candidates(index, rtxn, criteria, remaining, limit, output)
+}
@@ -15,7 +15,7 @@ use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::documents::{self, DocumentsBatchCursorError};
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
-use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
+use crate::{CriterionError, DocumentId, FieldId, FilterErrorDetails, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
     [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
@@ -76,6 +76,8 @@ pub enum InternalError {
     InvalidMatchingWords,
     #[error("Cannot upgrade to the following version: v{0}.{1}.{2}.")]
     CannotUpgradeToVersion(u32, u32, u32),
+    #[error("{0} are only supported by the new indexer")]
+    UnsupportedByLegacyIndexer(&'static str),
     #[error(transparent)]
     ArroyError(#[from] arroy::Error),
     #[error(transparent)]
@@ -114,6 +116,8 @@ pub enum UserError {
     CriterionError(#[from] CriterionError),
     #[error("Maximum number of documents reached.")]
     DocumentLimitReached,
+    #[error("Document `{document_id}` already exists.")]
+    DocumentAlreadyExists { document_id: String },
     #[error(
         "Document identifier `{}` is invalid. \
 A document identifier can be of type integer or string, \
@@ -152,6 +156,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry: {error}.")]
+    InvalidGeoJsonField { document_id: String, error: String },
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
     #[error("The `_vectors` field in the document with id: `{document_id}` is not an object. Was expecting an object with a key for each embedder with manually provided vectors, but instead got `{value}`")]
@@ -159,7 +165,7 @@ and can not be more than 511 bytes.", .document_id.to_string()
     #[error("Bad embedder configuration in the document with id: `{document_id}`. {error}")]
     InvalidVectorsEmbedderConf { document_id: String, error: String },
     #[error("{0}")]
-    InvalidFilter(String),
+    InvalidFilter(String, FilterErrorDetails),
     #[error("Invalid type for filter subexpression: expected: {}, found: {}.", .0.join(", "), .1)]
     InvalidFilterExpression(&'static [&'static str], Value),
     #[error("Filter operator `{operator}` is not allowed for the attribute `{field}`.\n  - Note: allowed operators: {}.\n  - Note: field `{field}` matched rule #{rule_index} in `filterableAttributes`\n  - Hint: enable {} in rule #{rule_index} by modifying the features.filter object\n  - Hint: prepend another rule matching `{field}` with appropriate filter features before rule #{rule_index}",
@@ -263,6 +269,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     NoSpaceLeftOnDevice,
     #[error("Index already has a primary key: `{0}`.")]
     PrimaryKeyCannotBeChanged(String),
+    #[error("The flattening settings cannot be changed once the index contains documents.")]
+    FlatteningCannotBeChanged,
     #[error(transparent)]
     SerdeJson(serde_json::Error),
     #[error(transparent)]
@@ -332,6 +340,10 @@ and can not be more than 511 bytes.", .document_id.to_string()
     },
     #[error("`.embedders.{embedder_name}.model`: Invalid model `{model}` for OpenAI. Supported models: {:?}", crate::vector::openai::EmbeddingModel::supported_models())]
     InvalidOpenAiModel { embedder_name: String, model: String },
+    #[error("`.embedders.{embedder_name}.model`: Invalid model `{model}` for Bedrock. Supported models: {:?}", crate::vector::bedrock::ModelFamily::supported_models())]
+    InvalidBedrockModel { embedder_name: String, model: String },
+    #[error("`.embedders.{embedder_name}.apiKey`: Invalid API key for Bedrock. Expected `<accessKeyId>:<secretAccessKey>` or `<accessKeyId>:<secretAccessKey>:<sessionToken>`")]
+    InvalidBedrockApiKey { embedder_name: String },
     #[error("`.embedders.{embedder_name}`: Missing field `{field}` (note: this field is mandatory for source `{source_}`)")]
     MissingFieldForSource {
         field: &'static str,
@@ -345,6 +357,13 @@ and can not be more than 511 bytes.", .document_id.to_string()
         dimensions: usize,
         expected_dimensions: usize,
     },
+    #[error("`.embedders.{embedder_name}.dimensions`: Model `{model}` does not support dimensions {dimensions}. Supported dimensions: {}", supported_dimensions.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "))]
+    InvalidCohereModelDimensions {
+        embedder_name: String,
+        model: String,
+        dimensions: usize,
+        supported_dimensions: &'static [usize],
+    },
     #[error("`.embedders.{embedder_name}.dimensions`: Model `{model}` does not support overriding its dimensions to a value higher than {max_dimensions}. Found {dimensions}")]
     InvalidOpenAiModelDimensionsMax {
         embedder_name: String,
@@ -369,8 +388,18 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidDisableBinaryQuantization { embedder_name: String },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.documentTemplateChunkBytes`: `documentTemplateChunkBytes` cannot be zero")]
+    InvalidSettingsDocumentTemplateChunkBytes { embedder_name: String },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
     InvalidUrl { embedder_name: String, inner_error: url::ParseError, url: String },
+    #[error("`.reranker.url`: the `url` of the reranker is mandatory")]
+    MissingRerankerUrl,
+    #[error("`.reranker.url`: could not parse `{url}`: {inner_error}")]
+    InvalidRerankerUrl { inner_error: url::ParseError, url: String },
+    #[error("`.reranker.documentTemplate`: Invalid template: {0}.")]
+    InvalidRerankerDocumentTemplate(crate::prompt::error::NewPromptError),
+    #[error("`.reranker.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
+    InvalidRerankerDocumentTemplateMaxBytes,
     #[error("Document editions cannot modify a document's primary key")]
     DocumentEditionCannotModifyPrimaryKey,
     #[error("Document editions must keep documents as objects")]
@@ -379,8 +408,20 @@ and can not be more than 511 bytes.", .document_id.to_string()
     DocumentEditionRuntimeError(Box<EvalAltResult>),
     #[error("Document edition runtime error encountered while compiling the function: {0}")]
     DocumentEditionCompilationError(rhai::ParseError),
+    #[error("The ingest function does not compile: {0}")]
+    InvalidIngestFunction(rhai::ParseError),
+    #[error("The document schema is not a valid JSON Schema: {0}")]
+    InvalidDocumentSchema(String),
+    #[error("Document `{document_id}` does not match the document schema of the index: {error}")]
+    DocumentDoesNotMatchSchema { document_id: String, error: String },
+    #[error("`.attachments.extractionUrl`: could not parse `{url}`: {inner_error}")]
+    InvalidAttachmentsExtractionUrl { inner_error: url::ParseError, url: String },
+    #[error("Could not extract the text of the `{attribute}` attachment of the document `{document_id}`: {error}")]
+    InvalidAttachment { document_id: String, attribute: String, error: String },
     #[error("{0}")]
     DocumentEmbeddingError(String),
+    #[error("Could not apply the JSON patch on the document `{document_id}`: {error}.")]
+    InvalidDocumentJsonPatch { document_id: String, error: String },
 }
 
 impl From<crate::vector::Error> for Error {
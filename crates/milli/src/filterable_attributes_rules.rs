@@ -5,7 +5,9 @@ use utoipa::ToSchema;
 
 use crate::{
     attribute_patterns::{match_distinct_field, match_field_legacy, PatternMatch},
-    constants::RESERVED_GEO_FIELD_NAME,
+    constants::{
+        RESERVED_DETECTED_LOCALES_FIELD_NAME, RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME,
+    },
     AttributePatterns,
 };
 
@@ -35,6 +37,20 @@ impl FilterableAttributesRule {
         matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEO_FIELD_NAME)
     }
 
+    /// Check if the rule is the GeoJSON field.
+    ///
+    /// prefer using `index.is_geojson_enabled` to check if the GeoJSON geometries are indexed.
+    pub fn has_geojson(&self) -> bool {
+        matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEOJSON_FIELD_NAME)
+    }
+
+    /// Check if the rule is the detected locales field.
+    ///
+    /// prefer using `index.is_detected_locales_enabled` to check if the detected locales are stored.
+    pub fn has_detected_locales(&self) -> bool {
+        matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_DETECTED_LOCALES_FIELD_NAME)
+    }
+
     /// Get the features of the rule.
     pub fn features(&self) -> FilterableAttributesFeatures {
         match self {
@@ -0,0 +1,337 @@
+//! The GeoJSON geometries of the `_geojson` field of the documents.
+//!
+//! The geometries are indexed by their bounding box, stored as the `_geojson.minLat`,
+//! `_geojson.maxLat`, `_geojson.minLng` and `_geojson.maxLng` facets, and checked exactly
+//! against the filters once the candidates are selected with their bounding box.
+//!
+//! The computations are done on the plane of the longitudes and latitudes, the geometries
+//! crossing the antimeridian are thus not supported.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+pub const GEOJSON_MIN_LAT: &str = "_geojson.minLat";
+pub const GEOJSON_MAX_LAT: &str = "_geojson.maxLat";
+pub const GEOJSON_MIN_LNG: &str = "_geojson.minLng";
+pub const GEOJSON_MAX_LNG: &str = "_geojson.maxLng";
+
+/// A `[longitude, latitude]` position, the optional altitude is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "Vec<f64>")]
+pub struct Position {
+    pub lng: f64,
+    pub lat: f64,
+}
+
+impl TryFrom<Vec<f64>> for Position {
+    type Error = String;
+
+    fn try_from(coordinates: Vec<f64>) -> Result<Self, Self::Error> {
+        match coordinates.as_slice() {
+            [lng, lat, ..] if !(-180.0..=180.0).contains(lng) => {
+                Err(format!("the longitude of `[{lng}, {lat}]` must be between -180 and 180"))
+            }
+            [lng, lat, ..] if !(-90.0..=90.0).contains(lat) => {
+                Err(format!("the latitude of `[{lng}, {lat}]` must be between -90 and 90"))
+            }
+            [lng, lat] | [lng, lat, _] => Ok(Position { lng: *lng, lat: *lat }),
+            _ => Err(format!(
+                "a position must have two or three coordinates but `{coordinates:?}` was found"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: Position },
+    MultiPoint { coordinates: Vec<Position> },
+    LineString { coordinates: Vec<Position> },
+    MultiLineString { coordinates: Vec<Vec<Position>> },
+    Polygon { coordinates: Vec<Vec<Position>> },
+    MultiPolygon { coordinates: Vec<Vec<Vec<Position>>> },
+    GeometryCollection { geometries: Vec<Geometry> },
+}
+
+/// A rectangle on the plane of the longitudes and latitudes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl BoundingBox {
+    fn of(positions: impl IntoIterator<Item = Position>) -> Option<Self> {
+        positions.into_iter().fold(None, |bbox, p| {
+            Some(match bbox {
+                None => BoundingBox { min: p, max: p },
+                Some(BoundingBox { min, max }) => BoundingBox {
+                    min: Position { lng: min.lng.min(p.lng), lat: min.lat.min(p.lat) },
+                    max: Position { lng: max.lng.max(p.lng), lat: max.lat.max(p.lat) },
+                },
+            })
+        })
+    }
+
+    pub fn contains(&self, p: Position) -> bool {
+        (self.min.lng..=self.max.lng).contains(&p.lng)
+            && (self.min.lat..=self.max.lat).contains(&p.lat)
+    }
+
+    fn corners(&self) -> [Position; 4] {
+        let BoundingBox { min, max } = *self;
+        [min, Position { lng: max.lng, lat: min.lat }, max, Position { lng: min.lng, lat: max.lat }]
+    }
+
+    fn intersects_segment(&self, a: Position, b: Position) -> bool {
+        if self.contains(a) || self.contains(b) {
+            return true;
+        }
+        let [c0, c1, c2, c3] = self.corners();
+        [(c0, c1), (c1, c2), (c2, c3), (c3, c0)]
+            .into_iter()
+            .any(|(c, d)| segments_intersect(a, b, c, d))
+    }
+}
+
+impl Geometry {
+    /// Parses and validates the geometry of a `_geojson` field.
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        let geometry: Geometry = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        geometry.validate()?;
+        Ok(geometry)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Geometry::Point { .. } | Geometry::MultiPoint { .. } => Ok(()),
+            Geometry::LineString { coordinates } => validate_line(coordinates),
+            Geometry::MultiLineString { coordinates } => {
+                coordinates.iter().try_for_each(|line| validate_line(line))
+            }
+            Geometry::Polygon { coordinates } => validate_polygon(coordinates),
+            Geometry::MultiPolygon { coordinates } => {
+                coordinates.iter().try_for_each(|polygon| validate_polygon(polygon))
+            }
+            Geometry::GeometryCollection { geometries } => {
+                geometries.iter().try_for_each(Geometry::validate)
+            }
+        }
+    }
+
+    /// The smallest rectangle containing the geometry, `None` if the geometry is empty.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        match self {
+            Geometry::Point { coordinates } => BoundingBox::of([*coordinates]),
+            Geometry::MultiPoint { coordinates } | Geometry::LineString { coordinates } => {
+                BoundingBox::of(coordinates.iter().copied())
+            }
+            Geometry::MultiLineString { coordinates } | Geometry::Polygon { coordinates } => {
+                BoundingBox::of(coordinates.iter().flatten().copied())
+            }
+            Geometry::MultiPolygon { coordinates } => {
+                BoundingBox::of(coordinates.iter().flatten().flatten().copied())
+            }
+            Geometry::GeometryCollection { geometries } => BoundingBox::of(
+                geometries
+                    .iter()
+                    .filter_map(Geometry::bounding_box)
+                    .flat_map(|bbox| [bbox.min, bbox.max]),
+            ),
+        }
+    }
+
+    /// Whether the point is in the geometry, including on its boundary.
+    pub fn contains(&self, p: Position) -> bool {
+        match self {
+            Geometry::Point { coordinates } => *coordinates == p,
+            Geometry::MultiPoint { coordinates } => coordinates.contains(&p),
+            Geometry::LineString { coordinates } => line_contains(coordinates, p),
+            Geometry::MultiLineString { coordinates } => {
+                coordinates.iter().any(|line| line_contains(line, p))
+            }
+            Geometry::Polygon { coordinates } => polygon_contains(coordinates, p),
+            Geometry::MultiPolygon { coordinates } => {
+                coordinates.iter().any(|polygon| polygon_contains(polygon, p))
+            }
+            Geometry::GeometryCollection { geometries } => {
+                geometries.iter().any(|geometry| geometry.contains(p))
+            }
+        }
+    }
+
+    /// Whether the geometry and the rectangle have at least one point in common.
+    pub fn intersects(&self, bbox: &BoundingBox) -> bool {
+        match self {
+            Geometry::Point { coordinates } => bbox.contains(*coordinates),
+            Geometry::MultiPoint { coordinates } => coordinates.iter().any(|p| bbox.contains(*p)),
+            Geometry::LineString { coordinates } => line_intersects(coordinates, bbox),
+            Geometry::MultiLineString { coordinates } => {
+                coordinates.iter().any(|line| line_intersects(line, bbox))
+            }
+            Geometry::Polygon { coordinates } => polygon_intersects(coordinates, bbox),
+            Geometry::MultiPolygon { coordinates } => {
+                coordinates.iter().any(|polygon| polygon_intersects(polygon, bbox))
+            }
+            Geometry::GeometryCollection { geometries } => {
+                geometries.iter().any(|geometry| geometry.intersects(bbox))
+            }
+        }
+    }
+}
+
+fn validate_line(line: &[Position]) -> Result<(), String> {
+    if line.len() < 2 {
+        return Err(String::from("a line string must have at least two positions"));
+    }
+    Ok(())
+}
+
+fn validate_polygon(rings: &[Vec<Position>]) -> Result<(), String> {
+    if rings.is_empty() {
+        return Err(String::from("a polygon must have at least one ring"));
+    }
+    for ring in rings {
+        if ring.len() < 4 {
+            return Err(String::from("a polygon ring must have at least four positions"));
+        }
+        if ring.first() != ring.last() {
+            return Err(String::from(
+                "a polygon ring must be closed, its first and last positions must be equal",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The sign of the cross product of `ab` and `ac`, telling on which side of `ab` the point `c` is.
+fn orientation(a: Position, b: Position, c: Position) -> f64 {
+    (b.lng - a.lng) * (c.lat - a.lat) - (b.lat - a.lat) * (c.lng - a.lng)
+}
+
+/// Whether `p` is on the segment `ab`.
+fn segment_contains(a: Position, b: Position, p: Position) -> bool {
+    orientation(a, b, p) == 0.0 && BoundingBox::of([a, b]).is_some_and(|bbox| bbox.contains(p))
+}
+
+fn segments_intersect(a: Position, b: Position, c: Position, d: Position) -> bool {
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+    if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+        return true;
+    }
+    segment_contains(a, b, c)
+        || segment_contains(a, b, d)
+        || segment_contains(c, d, a)
+        || segment_contains(c, d, b)
+}
+
+fn segments(line: &[Position]) -> impl Iterator<Item = (Position, Position)> + '_ {
+    line.windows(2).map(|w| (w[0], w[1]))
+}
+
+fn line_contains(line: &[Position], p: Position) -> bool {
+    segments(line).any(|(a, b)| segment_contains(a, b, p))
+}
+
+fn line_intersects(line: &[Position], bbox: &BoundingBox) -> bool {
+    segments(line).any(|(a, b)| bbox.intersects_segment(a, b))
+}
+
+/// Whether the point is strictly inside the closed ring, with the even-odd rule.
+fn ring_contains(ring: &[Position], p: Position) -> bool {
+    let mut inside = false;
+    for (a, b) in segments(ring) {
+        if (a.lat > p.lat) != (b.lat > p.lat)
+            && p.lng < (b.lng - a.lng) * (p.lat - a.lat) / (b.lat - a.lat) + a.lng
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn polygon_contains(rings: &[Vec<Position>], p: Position) -> bool {
+    let Some((exterior, holes)) = rings.split_first() else { return false };
+    // The boundaries, including the ones of the holes, belong to the polygon.
+    if rings.iter().any(|ring| line_contains(ring, p)) {
+        return true;
+    }
+    ring_contains(exterior, p) && !holes.iter().any(|hole| ring_contains(hole, p))
+}
+
+fn polygon_intersects(rings: &[Vec<Position>], bbox: &BoundingBox) -> bool {
+    // Either a boundary of the polygon crosses the rectangle or is inside it,
+    // or the rectangle is entirely inside the polygon.
+    rings.iter().any(|ring| line_intersects(ring, bbox)) || polygon_contains(rings, bbox.min)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn position(lng: f64, lat: f64) -> Position {
+        Position { lng, lat }
+    }
+
+    fn bbox(min: (f64, f64), max: (f64, f64)) -> BoundingBox {
+        BoundingBox { min: position(min.0, min.1), max: position(max.0, max.1) }
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let polygon = Geometry::from_value(json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]],
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(polygon.bounding_box(), Some(bbox((0.0, 0.0), (10.0, 10.0))));
+        assert!(polygon.contains(position(2.0, 2.0)));
+        assert!(polygon.contains(position(10.0, 5.0)));
+        assert!(polygon.contains(position(4.0, 5.0)));
+        assert!(!polygon.contains(position(5.0, 5.0)));
+        assert!(!polygon.contains(position(11.0, 5.0)));
+
+        // crossing the boundary
+        assert!(polygon.intersects(&bbox((9.0, 9.0), (12.0, 12.0))));
+        // inside the polygon
+        assert!(polygon.intersects(&bbox((1.0, 1.0), (2.0, 2.0))));
+        // around the polygon
+        assert!(polygon.intersects(&bbox((-1.0, -1.0), (12.0, 12.0))));
+        // inside the hole
+        assert!(!polygon.intersects(&bbox((4.5, 4.5), (5.5, 5.5))));
+        // outside the polygon
+        assert!(!polygon.intersects(&bbox((11.0, 11.0), (12.0, 12.0))));
+    }
+
+    #[test]
+    fn line_string() {
+        let line = Geometry::from_value(json!({
+            "type": "LineString",
+            "coordinates": [[0, 0], [10, 10]]
+        }))
+        .unwrap();
+
+        assert!(line.contains(position(5.0, 5.0)));
+        assert!(!line.contains(position(5.0, 6.0)));
+        assert!(line.intersects(&bbox((4.0, 0.0), (6.0, 100.0))));
+        assert!(!line.intersects(&bbox((6.0, 0.0), (10.0, 4.0))));
+    }
+
+    #[test]
+    fn invalid_geometries() {
+        let error = |value| Geometry::from_value(value).unwrap_err();
+
+        insta::assert_snapshot!(error(json!({ "type": "Point", "coordinates": [200, 0] })), @"the longitude of `[200, 0]` must be between -180 and 180");
+        insta::assert_snapshot!(error(json!({ "type": "LineString", "coordinates": [[0, 0]] })), @"a line string must have at least two positions");
+        insta::assert_snapshot!(error(json!({ "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]] })), @"a polygon ring must be closed, its first and last positions must be equal");
+        insta::assert_snapshot!(error(json!({ "type": "Circle", "coordinates": [0, 0] })), @"unknown variant `Circle`, expected one of `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, `MultiPolygon`, `GeometryCollection`");
+    }
+}
@@ -3,33 +3,41 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
 
+use charabia::Language;
 use heed::{types::*, WithoutTls};
 use heed::{CompactionOption, Database, RoTxn, RwTxn, Unspecified};
 use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{self, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::attachments::AttachmentsSettings;
+use crate::constants::{
+    self, RESERVED_DETECTED_LOCALES_FIELD_NAME, RESERVED_GEO_FIELD_NAME,
+    RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::database_stats::DatabaseStats;
+use crate::document_revisions::{push_revision, truncate_revisions, DocumentRevision};
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::fields_ids_map::FieldsIdsMap;
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
-    FieldIdCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::version::VersionCodec;
 use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
+use crate::vector::reranker::RerankerOptions;
+use crate::vector::usage::EmbedderUsage;
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    FieldidsWeightsMap, FilterableAttributesRule, FlattenOptions, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search,
+    U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -76,7 +84,18 @@ pub mod main_key {
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
+    pub const SENSITIVE_MATCHING: &str = "sensitive_matching";
+    pub const RERANKER: &str = "reranker";
+    pub const FLATTEN_MAX_DEPTH: &str = "flatten_max_depth";
+    pub const FLATTEN_ARRAY_POSITIONS: &str = "flatten_array_positions";
+    pub const INGEST_FUNCTION: &str = "ingest_function";
+    pub const DOCUMENT_SCHEMA: &str = "document_schema";
+    pub const ATTACHMENTS: &str = "attachments";
+    pub const SKIP_UNCHANGED_DOCUMENTS: &str = "skip_unchanged_documents";
+    pub const MAX_DOCUMENT_REVISIONS: &str = "max_document_revisions";
+    pub const EMBEDDER_USAGE: &str = "embedder_usage";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
+    pub const DOCUMENT_OPERATION_CHECKPOINT: &str = "document_operation_checkpoint";
 }
 
 pub mod db_name {
@@ -104,6 +123,9 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
+    pub const EMBEDDING_CACHE: &str = "embedding-cache";
+    pub const FULL_PRECISION_VECTORS: &str = "full-precision-vectors";
+    pub const DOCUMENT_REVISIONS: &str = "document-revisions";
     pub const DOCUMENTS: &str = "documents";
 }
 
@@ -170,6 +192,13 @@ pub struct Index {
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
     pub vector_arroy: arroy::Database<Unspecified>,
+    /// Maps a hash of the options of an embedder and of a rendered document to its embedding.
+    pub embedding_cache: Database<Bytes, Bytes>,
+    /// Maps an embedder id and a document id to the full-precision vectors of the binary quantized embedders.
+    pub full_precision_vectors: Database<Bytes, Bytes>,
+
+    /// Maps the external document id to the previous versions of the document.
+    pub(crate) document_revisions: Database<Str, SerdeJson<Vec<DocumentRevision>>>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
@@ -185,7 +214,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(28);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -227,6 +256,10 @@ impl Index {
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
+        let embedding_cache = env.create_database(&mut wtxn, Some(EMBEDDING_CACHE))?;
+        let full_precision_vectors =
+            env.create_database(&mut wtxn, Some(FULL_PRECISION_VECTORS))?;
+        let document_revisions = env.create_database(&mut wtxn, Some(DOCUMENT_REVISIONS))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
 
@@ -255,6 +288,9 @@ impl Index {
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id,
+            embedding_cache,
+            full_precision_vectors,
+            document_revisions,
             documents,
         };
         if this.get_version(&wtxn)?.is_none() && creation {
@@ -978,6 +1014,43 @@ impl Index {
         Ok(geo_filter)
     }
 
+    /// Returns true if the GeoJSON geometries of the `_geojson` fields are indexed for filtering.
+    pub fn is_geojson_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let geojson =
+            self.filterable_attributes_rules(rtxn)?.iter().any(|field| field.has_geojson());
+        Ok(geojson)
+    }
+
+    /// Returns true if the detected locales of the documents are stored in `_detectedLocales`.
+    pub fn is_detected_locales_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let detected_locales = self
+            .filterable_attributes_rules(rtxn)?
+            .iter()
+            .any(|field| field.has_detected_locales());
+        Ok(detected_locales)
+    }
+
+    /// Returns the locales detected in at least one document of the index.
+    ///
+    /// Returns an empty list if the detected locales are not stored.
+    pub fn detected_locales(&self, rtxn: &RoTxn<'_>) -> Result<Vec<Language>> {
+        if !self.is_detected_locales_enabled(rtxn)? {
+            return Ok(Vec::new());
+        }
+        let Some(field_id) = self.fields_ids_map(rtxn)?.id(RESERVED_DETECTED_LOCALES_FIELD_NAME)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let prefix = FacetGroupKey { field_id, level: 0, left_bound: "" };
+        let mut locales = Vec::new();
+        for result in self.facet_id_string_docids.prefix_iter(rtxn, &prefix)? {
+            let (key, _) = result?;
+            locales.extend(Language::from_code(key.left_bound));
+        }
+        Ok(locales)
+    }
+
     pub fn asc_desc_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let asc_desc_fields = self
             .criteria(rtxn)?
@@ -1654,6 +1727,318 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::FACET_SEARCH)
     }
 
+    /// Whether the searches match the query words with their case and diacritics by default.
+    pub fn sensitive_matching(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        self.main
+            .remap_types::<Str, SerdeBincode<bool>>()
+            .get(txn, main_key::SENSITIVE_MATCHING)
+            .map(|v| v.unwrap_or(false))
+    }
+
+    pub(crate) fn put_sensitive_matching(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<bool>>().put(
+            txn,
+            main_key::SENSITIVE_MATCHING,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_sensitive_matching(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SENSITIVE_MATCHING)
+    }
+
+    /// How the nested objects of the documents are turned into dotted fields.
+    pub fn flatten_options(&self, txn: &RoTxn<'_>) -> heed::Result<FlattenOptions> {
+        Ok(FlattenOptions {
+            max_depth: self.flatten_max_depth(txn)?.map(|depth| depth as usize),
+            array_positions: self.flatten_array_positions(txn)?,
+        })
+    }
+
+    pub fn flatten_max_depth(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
+        self.main.remap_types::<Str, BEU64>().get(txn, main_key::FLATTEN_MAX_DEPTH)
+    }
+
+    pub(crate) fn put_flatten_max_depth(&self, txn: &mut RwTxn<'_>, val: u64) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(txn, main_key::FLATTEN_MAX_DEPTH, &val)
+    }
+
+    pub(crate) fn delete_flatten_max_depth(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FLATTEN_MAX_DEPTH)
+    }
+
+    pub fn flatten_array_positions(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        self.main
+            .remap_types::<Str, SerdeBincode<bool>>()
+            .get(txn, main_key::FLATTEN_ARRAY_POSITIONS)
+            .map(|v| v.unwrap_or(false))
+    }
+
+    pub(crate) fn put_flatten_array_positions(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<bool>>().put(
+            txn,
+            main_key::FLATTEN_ARRAY_POSITIONS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_flatten_array_positions(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FLATTEN_ARRAY_POSITIONS)
+    }
+
+    /// The rhai function transforming the documents before they are indexed, if any.
+    pub fn ingest_function<'t>(&self, txn: &'t RoTxn<'_>) -> heed::Result<Option<&'t str>> {
+        self.main.remap_types::<Str, Str>().get(txn, main_key::INGEST_FUNCTION)
+    }
+
+    pub(crate) fn put_ingest_function(&self, txn: &mut RwTxn<'_>, code: &str) -> heed::Result<()> {
+        self.main.remap_types::<Str, Str>().put(txn, main_key::INGEST_FUNCTION, code)
+    }
+
+    pub(crate) fn delete_ingest_function(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::INGEST_FUNCTION)
+    }
+
+    /// The JSON Schema the documents must match to be indexed, if any.
+    pub fn document_schema(&self, txn: &RoTxn<'_>) -> heed::Result<Option<serde_json::Value>> {
+        self.main
+            .remap_types::<Str, SerdeJson<serde_json::Value>>()
+            .get(txn, main_key::DOCUMENT_SCHEMA)
+    }
+
+    pub(crate) fn put_document_schema(
+        &self,
+        txn: &mut RwTxn<'_>,
+        schema: &serde_json::Value,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<serde_json::Value>>().put(
+            txn,
+            main_key::DOCUMENT_SCHEMA,
+            schema,
+        )
+    }
+
+    pub(crate) fn delete_document_schema(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::DOCUMENT_SCHEMA)
+    }
+
+    /// The attributes holding the attachments whose text is extracted, if any.
+    pub fn attachments(&self, txn: &RoTxn<'_>) -> heed::Result<Option<AttachmentsSettings>> {
+        self.main
+            .remap_types::<Str, SerdeJson<AttachmentsSettings>>()
+            .get(txn, main_key::ATTACHMENTS)
+    }
+
+    pub(crate) fn put_attachments(
+        &self,
+        txn: &mut RwTxn<'_>,
+        attachments: &AttachmentsSettings,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<AttachmentsSettings>>().put(
+            txn,
+            main_key::ATTACHMENTS,
+            attachments,
+        )
+    }
+
+    pub(crate) fn delete_attachments(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::ATTACHMENTS)
+    }
+
+    /// Whether the documents whose content didn't change are skipped instead of being indexed again.
+    pub fn skip_unchanged_documents(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        self.main
+            .remap_types::<Str, SerdeBincode<bool>>()
+            .get(txn, main_key::SKIP_UNCHANGED_DOCUMENTS)
+            .map(|v| v.unwrap_or(false))
+    }
+
+    pub(crate) fn put_skip_unchanged_documents(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<bool>>().put(
+            txn,
+            main_key::SKIP_UNCHANGED_DOCUMENTS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_skip_unchanged_documents(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SKIP_UNCHANGED_DOCUMENTS)
+    }
+
+    /// The number of previous versions kept for each document, no revision is kept when `None`.
+    pub fn max_document_revisions(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
+        self.main.remap_types::<Str, BEU64>().get(txn, main_key::MAX_DOCUMENT_REVISIONS)
+    }
+
+    pub(crate) fn put_max_document_revisions(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: u64,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(txn, main_key::MAX_DOCUMENT_REVISIONS, &val)
+    }
+
+    pub(crate) fn delete_max_document_revisions(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::MAX_DOCUMENT_REVISIONS)
+    }
+
+    /// Returns the previous versions of the document, from the oldest to the most recent one.
+    pub fn document_revisions(
+        &self,
+        rtxn: &RoTxn<'_>,
+        external_id: &str,
+    ) -> heed::Result<Vec<DocumentRevision>> {
+        Ok(self.document_revisions.get(rtxn, external_id)?.unwrap_or_default())
+    }
+
+    /// Records the current version of the document, stored under the given internal id, as its
+    /// most recent revision.
+    ///
+    /// Does nothing if the index doesn't keep the revisions of its documents.
+    pub fn record_document_revision(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        external_id: &str,
+        docid: DocumentId,
+        fields_ids_map: &FieldsIdsMap,
+    ) -> Result<()> {
+        let max_revisions = match self.max_document_revisions(wtxn)? {
+            Some(max_revisions) if max_revisions > 0 => max_revisions,
+            _ => return Ok(()),
+        };
+        let Some(document) = self.documents.get(wtxn, &docid)? else { return Ok(()) };
+        let document = crate::all_obkv_to_json(document, fields_ids_map)?;
+
+        let mut revisions = self.document_revisions(wtxn, external_id)?;
+        push_revision(&mut revisions, document, max_revisions);
+        self.document_revisions.put(wtxn, external_id, &revisions)?;
+        Ok(())
+    }
+
+    /// Drops the revisions beyond `max_revisions` for every document.
+    pub(crate) fn truncate_document_revisions(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        max_revisions: u64,
+    ) -> heed::Result<()> {
+        if max_revisions == 0 {
+            return self.clear_document_revisions(wtxn);
+        }
+
+        let mut truncated = Vec::new();
+        for result in self.document_revisions.iter(wtxn)? {
+            let (external_id, mut revisions) = result?;
+            if revisions.len() as u64 > max_revisions {
+                truncate_revisions(&mut revisions, max_revisions);
+                truncated.push((external_id.to_string(), revisions));
+            }
+        }
+        for (external_id, revisions) in truncated {
+            self.document_revisions.put(wtxn, &external_id, &revisions)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the revisions of every document.
+    pub(crate) fn clear_document_revisions(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<()> {
+        self.document_revisions.clear(wtxn)
+    }
+
+    /// The reranker rescoring the top hits of the searches, if any.
+    pub fn reranker(&self, txn: &RoTxn<'_>) -> Result<Option<RerankerOptions>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<RerankerOptions>>()
+            .get(txn, main_key::RERANKER)?)
+    }
+
+    pub(crate) fn put_reranker(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &RerankerOptions,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<RerankerOptions>>().put(txn, main_key::RERANKER, val)
+    }
+
+    pub(crate) fn delete_reranker(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::RERANKER)
+    }
+
+    /// The requests sent to the embedding providers while indexing the documents, by embedder name.
+    ///
+    /// The usage of the embedders is kept after they are removed from the settings.
+    pub fn embedder_usage(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, EmbedderUsage>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, EmbedderUsage>>>()
+            .get(rtxn, main_key::EMBEDDER_USAGE)?
+            .unwrap_or_default())
+    }
+
+    /// The progress of the task whose documents are being indexed in several steps, committed
+    /// along with the documents of each step.
+    pub fn document_operation_checkpoint(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<DocumentOperationCheckpoint>> {
+        self.main
+            .remap_types::<Str, SerdeJson<DocumentOperationCheckpoint>>()
+            .get(rtxn, main_key::DOCUMENT_OPERATION_CHECKPOINT)
+    }
+
+    pub fn put_document_operation_checkpoint(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        checkpoint: &DocumentOperationCheckpoint,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<DocumentOperationCheckpoint>>().put(
+            wtxn,
+            main_key::DOCUMENT_OPERATION_CHECKPOINT,
+            checkpoint,
+        )
+    }
+
+    pub fn delete_document_operation_checkpoint(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DOCUMENT_OPERATION_CHECKPOINT)
+    }
+
+    pub(crate) fn add_embedder_usage(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        usage: &BTreeMap<String, EmbedderUsage>,
+    ) -> heed::Result<()> {
+        if usage.is_empty() {
+            return Ok(());
+        }
+        let mut total = self.embedder_usage(wtxn)?;
+        for (name, usage) in usage {
+            *total.entry(name.clone()).or_default() += *usage;
+        }
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, EmbedderUsage>>>().put(
+            wtxn,
+            main_key::EMBEDDER_USAGE,
+            &total,
+        )
+    }
+
     pub fn localized_attributes_rules(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1731,8 +2116,12 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                self.full_precision_vectors,
+                embedder_id,
+                config.config.quantized(),
+            );
             let embeddings = reader.item_vectors(rtxn, docid)?;
             res.insert(config.name.to_owned(), embeddings);
         }
@@ -1749,8 +2138,12 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                self.full_precision_vectors,
+                embedder_id,
+                config.config.quantized(),
+            );
             reader.aggregate_stats(rtxn, &mut stats)?;
         }
         Ok(stats)
@@ -1764,6 +2157,18 @@ pub struct IndexEmbeddingConfig {
     pub user_provided: RoaringBitmap,
 }
 
+/// How far the indexing of the documents of a task went, when they are indexed in several steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentOperationCheckpoint {
+    /// The uid of the task whose documents are being indexed.
+    pub task_uid: u32,
+    /// The number of documents of the task that were processed by the committed steps.
+    pub processed_documents: u64,
+    /// The number of documents that were indexed by the committed steps.
+    pub indexed_documents: u64,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PrefixSettings {
     pub prefix_count_threshold: usize,
@@ -1873,6 +2278,18 @@ pub(crate) mod tests {
                 IndexDocumentsMethod::UpdateDocuments => {
                     indexer.update_documents(&documents).unwrap()
                 }
+                IndexDocumentsMethod::MergePatchDocuments => {
+                    indexer.merge_patch_documents(&documents).unwrap()
+                }
+                IndexDocumentsMethod::JsonPatchDocuments => {
+                    indexer.json_patch_documents(&documents).unwrap()
+                }
+                IndexDocumentsMethod::SkipExistingDocuments => {
+                    indexer.skip_existing_documents(&documents).unwrap()
+                }
+                IndexDocumentsMethod::FailOnExistingDocuments => {
+                    indexer.fail_on_existing_documents(&documents).unwrap()
+                }
             }
 
             let indexer_alloc = Bump::new();
@@ -2911,6 +3328,7 @@ pub(crate) mod tests {
             mut documents_ids,
             degraded: _,
             used_negative_operator: _,
+            timings: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
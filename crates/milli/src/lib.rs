@@ -9,14 +9,17 @@ pub static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 pub mod documents;
 
 mod asc_desc;
+pub mod attachments;
 mod attribute_patterns;
 mod criterion;
 pub mod database_stats;
+pub mod document_revisions;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 mod filterable_attributes_rules;
+pub mod geojson;
 pub mod heed_codec;
 pub mod index;
 mod localized_attributes_rules;
@@ -41,21 +44,26 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::hash::BuildHasherDefault;
 
+pub use arroy;
+pub use charabia as tokenizer;
 use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
-pub use filter_parser::{Condition, FilterCondition, Span, Token};
+pub use filter_parser::{
+    Condition, ErrorDetails as FilterErrorDetails, FilterCondition, Span, Token,
+};
+pub use flatten_serde_json::FlattenOptions;
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
+pub use heed;
+pub use rhai;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    distinct_single_docid, execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy,
+    SearchContext, SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
-pub use {charabia as tokenizer, heed, rhai};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::attribute_patterns::AttributePatterns;
-pub use self::attribute_patterns::PatternMatch;
+pub use self::attribute_patterns::{AttributePatterns, PatternMatch};
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
@@ -75,16 +83,18 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
-pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::search::facet::{
+    FacetQueryMatching, FacetValueHit, GeoGrid, SearchForFacetValues, MAX_GEO_GRID_PRECISION,
+};
+pub use self::search::hybrid::HybridFusion;
 pub use self::search::similar::Similar;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    CropStrategy, FacetDistribution, FacetHierarchy, FacetRange, Filter, FormatOptions,
+    MatchBounds, MatchKind, MatchedTerm, MatcherBuilder, MatchingWords, OrderBy, Search,
+    SearchResult, SearchTimings, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 pub use self::update::ChannelCongestion;
 
-pub use arroy;
-
 pub type Result<T> = std::result::Result<T, error::Error>;
 
 pub type Attribute = u32;
@@ -339,6 +349,43 @@ pub fn lat_lng_to_xyz(coord: &[f64; 2]) -> [f64; 3] {
     [x, y, z]
 }
 
+/// Returns the geohash of the cell containing a point expressed in terms of latitude and
+/// longitude, the precision is the number of characters of the geohash.
+pub fn lat_lng_to_geohash(coord: &[f64; 2], precision: usize) -> String {
+    const ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+    let [lat, lng] = *coord;
+    let mut lat_range = (-90.0, 90.0);
+    let mut lng_range = (-180.0, 180.0);
+    let mut geohash = String::with_capacity(precision);
+    // The bits alternate between the longitude and the latitude, starting with the longitude.
+    let mut is_lng = true;
+    let mut bits = 0;
+    let mut index = 0;
+
+    while geohash.len() < precision {
+        let (range, value) = if is_lng { (&mut lng_range, lng) } else { (&mut lat_range, lat) };
+        let middle = (range.0 + range.1) / 2.0;
+        index <<= 1;
+        if value >= middle {
+            index |= 1;
+            range.0 = middle;
+        } else {
+            range.1 = middle;
+        }
+        is_lng = !is_lng;
+        bits += 1;
+
+        if bits == 5 {
+            geohash.push(ALPHABET[index] as char);
+            bits = 0;
+            index = 0;
+        }
+    }
+
+    geohash
+}
+
 /// Returns `true` if the field match one of the faceted fields.
 /// See the function [`is_faceted_by`] below to see what “matching” means.
 pub fn is_faceted(field: &str, faceted_fields: impl IntoIterator<Item = impl AsRef<str>>) -> bool {
@@ -385,6 +432,14 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn geohash_of_points() {
+        assert_eq!(lat_lng_to_geohash(&[57.64911, 10.40744], 11), "u4pruydqqvj");
+        assert_eq!(lat_lng_to_geohash(&[48.8583701, 2.2922926], 5), "u09tu");
+        assert_eq!(lat_lng_to_geohash(&[-33.8567844, 151.2152967], 3), "r3g");
+        assert_eq!(lat_lng_to_geohash(&[0.0, 0.0], 1), "s");
+    }
+
     #[test]
     fn json_to_string_object() {
         let value = json!({
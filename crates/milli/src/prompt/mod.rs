@@ -24,17 +24,24 @@ pub struct Prompt {
     template: liquid::Template,
     template_text: String,
     max_bytes: Option<NonZeroUsize>,
+    chunk_bytes: Option<NonZeroUsize>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PromptData {
     pub template: String,
     pub max_bytes: Option<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_bytes: Option<NonZeroUsize>,
 }
 
 impl From<Prompt> for PromptData {
     fn from(value: Prompt) -> Self {
-        Self { template: value.template_text, max_bytes: value.max_bytes }
+        Self {
+            template: value.template_text,
+            max_bytes: value.max_bytes,
+            chunk_bytes: value.chunk_bytes,
+        }
     }
 }
 
@@ -42,7 +49,7 @@ impl TryFrom<PromptData> for Prompt {
     type Error = NewPromptError;
 
     fn try_from(value: PromptData) -> Result<Self, Self::Error> {
-        Prompt::new(value.template, value.max_bytes)
+        Ok(Prompt::new(value.template, value.max_bytes)?.with_chunk_bytes(value.chunk_bytes))
     }
 }
 
@@ -53,6 +60,7 @@ impl Clone for Prompt {
             template: new_template(&template_text).unwrap(),
             template_text,
             max_bytes: self.max_bytes,
+            chunk_bytes: self.chunk_bytes,
         }
     }
 }
@@ -83,13 +91,18 @@ impl Default for Prompt {
             template: default_template(),
             template_text: default_template_text().into(),
             max_bytes: Some(default_max_bytes()),
+            chunk_bytes: None,
         }
     }
 }
 
 impl Default for PromptData {
     fn default() -> Self {
-        Self { template: default_template_text().into(), max_bytes: Some(default_max_bytes()) }
+        Self {
+            template: default_template_text().into(),
+            max_bytes: Some(default_max_bytes()),
+            chunk_bytes: None,
+        }
     }
 }
 
@@ -103,6 +116,7 @@ impl Prompt {
                 .map_err(NewPromptError::cannot_parse_template)?,
             template_text: template,
             max_bytes,
+            chunk_bytes: None,
         };
 
         // render template with special object that's OK with `doc.*` and `fields.*`
@@ -113,6 +127,18 @@ impl Prompt {
         Ok(this)
     }
 
+    /// Splits the rendered texts in chunks of at most `chunk_bytes` bytes, that are embedded separately.
+    pub fn with_chunk_bytes(self, chunk_bytes: Option<NonZeroUsize>) -> Self {
+        Self { chunk_bytes, ..self }
+    }
+
+    /// The chunks of a rendered text, each of them producing an embedding for the document.
+    ///
+    /// When no chunk size is specified, the whole rendered text is the only chunk.
+    pub fn chunks<'t>(&self, rendered: &'t str) -> impl Iterator<Item = &'t str> {
+        split_in_chunks(rendered, self.chunk_bytes.map_or(usize::MAX, NonZeroUsize::get))
+    }
+
     pub fn render_document<
         'a,       // lifetime of the borrow of the document
         'doc: 'a, // lifetime of the allocator, will live for an entire chunk of documents
@@ -171,12 +197,48 @@ fn truncate(s: &mut String, max_bytes: usize) {
     }
 }
 
+/// Splits `text` in chunks of at most `chunk_bytes` bytes, preferably at a whitespace so that words are kept whole.
+fn split_in_chunks(text: &str, chunk_bytes: usize) -> impl Iterator<Item = &str> {
+    let mut remaining = Some(text);
+    std::iter::from_fn(move || {
+        let text = remaining.take()?;
+        if text.len() <= chunk_bytes {
+            return Some(text);
+        }
+
+        let mut end = chunk_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if !text[end..].starts_with(char::is_whitespace) {
+            if let Some((index, _)) =
+                text[..end].char_indices().rev().find(|(_, c)| c.is_whitespace())
+            {
+                if index > 0 {
+                    end = index;
+                }
+            }
+        }
+        // always make progress, even when the first character is larger than a chunk
+        if end == 0 {
+            end = text.chars().next().map_or(text.len(), char::len_utf8);
+        }
+
+        let (chunk, rest) = text.split_at(end);
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            remaining = Some(rest);
+        }
+        Some(chunk)
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::Prompt;
     use crate::error::FaultSource;
     use crate::prompt::error::{NewPromptError, NewPromptErrorKind};
-    use crate::prompt::truncate;
+    use crate::prompt::{split_in_chunks, truncate};
 
     #[test]
     fn default_template() {
@@ -276,4 +338,23 @@ mod test {
         truncate(&mut s, 2);
         assert_eq!(s, "");
     }
+
+    #[test]
+    fn template_chunks() {
+        let chunks = |s, chunk_bytes| split_in_chunks(s, chunk_bytes).collect::<Vec<_>>();
+
+        assert_eq!(chunks("", 4), vec![""]);
+        assert_eq!(chunks("kefir", 42), vec!["kefir"]);
+        assert_eq!(chunks("kefir the dog", 10), vec!["kefir the", "dog"]);
+        assert_eq!(chunks("kefir the dog  ", 10), vec!["kefir the", "dog  "]);
+        assert_eq!(chunks("kefir the dog", 9), vec!["kefir the", "dog"]);
+        assert_eq!(chunks("kefirthedog", 5), vec!["kefir", "thedo", "g"]);
+        assert_eq!(chunks("インテル ザー", 13), vec!["インテル", "ザー"]);
+        assert_eq!(chunks("インテル", 2), vec!["イ", "ン", "テ", "ル"]);
+
+        let prompt = Prompt::new("{{doc.title}}".into(), None).unwrap();
+        assert_eq!(prompt.chunks("kefir the dog").collect::<Vec<_>>(), vec!["kefir the dog"]);
+        let prompt = prompt.with_chunk_bytes(std::num::NonZeroUsize::new(6));
+        assert_eq!(prompt.chunks("kefir the dog").collect::<Vec<_>>(), vec!["kefir", "the", "dog"]);
+    }
 }
@@ -17,6 +17,8 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    /// Relevance of the document to the query according to the reranker of the index
+    Rerank(Rerank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -62,6 +64,7 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Rerank(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -134,6 +137,7 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Rerank(rerank) => RankOrValue::Score(rerank.relevance_score),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -291,6 +295,14 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Rerank(rerank) => {
+                    let details = serde_json::json!({
+                        "order": order,
+                        "relevanceScore": rerank.relevance_score,
+                    });
+                    details_map.insert("rerank".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -545,6 +557,12 @@ pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Rerank {
+    /// Clamped between `0.0` and `1.0`, like the other scores.
+    pub relevance_score: f64,
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
@@ -1,6 +1,7 @@
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -13,12 +14,15 @@ use crate::attribute_patterns::match_field_legacy;
 use crate::facet::FacetType;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
+    OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{facet_max_date, facet_max_value, facet_min_date, facet_min_value};
 use crate::{Error, FieldId, FilterableAttributesRule, Index, PatternMatch, Result, UserError};
 
 /// The default number of values by facets that will
@@ -48,10 +52,42 @@ impl Display for OrderBy {
     }
 }
 
+/// A range of numeric facet values, the lower bound is inclusive and the upper bound exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+impl Display for FacetRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(from) = self.from {
+            write!(f, "{from}")?;
+        }
+        f.write_str("..")?;
+        if let Some(to) = self.to {
+            write!(f, "{to}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The hierarchy of a facet whose string values are paths, e.g. `Electronics > Phones > Android`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetHierarchy {
+    /// The separator of the levels of the path, e.g. ` > `.
+    pub separator: String,
+    /// The path of the level to return the children of, the root level when `None`.
+    pub path: Option<String>,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
+    max_values_by_facet: HashMap<String, usize>,
+    ranges_by_facet: HashMap<String, Vec<FacetRange>>,
+    hierarchies_by_facet: HashMap<String, FacetHierarchy>,
     default_order_by: OrderBy,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
@@ -63,6 +99,9 @@ impl<'a> FacetDistribution<'a> {
             facets: None,
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
+            max_values_by_facet: HashMap::new(),
+            ranges_by_facet: HashMap::new(),
+            hierarchies_by_facet: HashMap::new(),
             default_order_by: OrderBy::default(),
             rtxn,
             index,
@@ -87,6 +126,42 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Overrides the maximum number of values to fetch for specific facets.
+    pub fn max_values_by_facet<I: IntoIterator<Item = (A, usize)>, A: AsRef<str>>(
+        &mut self,
+        max_values_by_facet: I,
+    ) -> &mut Self {
+        self.max_values_by_facet = max_values_by_facet
+            .into_iter()
+            .map(|(name, max)| (name.as_ref().to_string(), max))
+            .collect();
+        self
+    }
+
+    /// Counts the values of specific facets by ranges instead of returning each value.
+    pub fn ranges_by_facet<I: IntoIterator<Item = (A, Vec<FacetRange>)>, A: AsRef<str>>(
+        &mut self,
+        ranges_by_facet: I,
+    ) -> &mut Self {
+        self.ranges_by_facet = ranges_by_facet
+            .into_iter()
+            .map(|(name, ranges)| (name.as_ref().to_string(), ranges))
+            .collect();
+        self
+    }
+
+    /// Counts the values of specific facets by level of their hierarchy instead of by value.
+    pub fn hierarchies_by_facet<I: IntoIterator<Item = (A, FacetHierarchy)>, A: AsRef<str>>(
+        &mut self,
+        hierarchies_by_facet: I,
+    ) -> &mut Self {
+        self.hierarchies_by_facet = hierarchies_by_facet
+            .into_iter()
+            .map(|(name, hierarchy)| (name.as_ref().to_string(), hierarchy))
+            .collect();
+        self
+    }
+
     pub fn default_order_by(&mut self, order_by: OrderBy) -> &mut Self {
         self.default_order_by = order_by;
         self
@@ -105,6 +180,7 @@ impl<'a> FacetDistribution<'a> {
         facet_type: FacetType,
         candidates: &RoaringBitmap,
         distribution: &mut IndexMap<String, u64>,
+        max_values: usize,
     ) -> heed::Result<()> {
         match facet_type {
             FacetType::Number => {
@@ -129,7 +205,7 @@ impl<'a> FacetDistribution<'a> {
                 distribution.extend(
                     lexicographic_distribution
                         .into_iter()
-                        .take(self.max_values_per_facet.saturating_sub(distribution.len())),
+                        .take(max_values.saturating_sub(distribution.len())),
                 );
             }
             FacetType::String => {
@@ -159,7 +235,7 @@ impl<'a> FacetDistribution<'a> {
 
                 let iter = normalized_distribution
                     .into_iter()
-                    .take(self.max_values_per_facet.saturating_sub(distribution.len()))
+                    .take(max_values.saturating_sub(distribution.len()))
                     .map(|(_normalized, (original, count))| (original.to_string(), count));
                 distribution.extend(iter);
             }
@@ -176,6 +252,7 @@ impl<'a> FacetDistribution<'a> {
         candidates: &RoaringBitmap,
         order_by: OrderBy,
         distribution: &mut IndexMap<String, u64>,
+        max_values: usize,
     ) -> heed::Result<()> {
         let search_function = match order_by {
             OrderBy::Lexicographic => lexicographically_iterate_over_facet_distribution,
@@ -190,7 +267,7 @@ impl<'a> FacetDistribution<'a> {
             |facet_key, nbr_docids, _| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
                 distribution.insert(facet_key.to_string(), nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == max_values {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -205,6 +282,7 @@ impl<'a> FacetDistribution<'a> {
         candidates: &RoaringBitmap,
         order_by: OrderBy,
         distribution: &mut IndexMap<String, u64>,
+        max_values: usize,
     ) -> heed::Result<()> {
         let search_function = match order_by {
             OrderBy::Lexicographic => lexicographically_iterate_over_facet_distribution,
@@ -235,7 +313,7 @@ impl<'a> FacetDistribution<'a> {
                 };
 
                 distribution.insert(original_string, nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == max_values {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -248,6 +326,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         order_by: OrderBy,
+        max_values: usize,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
@@ -256,8 +335,20 @@ impl<'a> FacetDistribution<'a> {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    Number,
+                    cnd,
+                    &mut distribution,
+                    max_values,
+                )?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    String,
+                    cnd,
+                    &mut distribution,
+                    max_values,
+                )?;
             }
             _ => {
                 let universe;
@@ -274,12 +365,14 @@ impl<'a> FacetDistribution<'a> {
                     candidates,
                     order_by,
                     &mut distribution,
+                    max_values,
                 )?;
                 self.facet_strings_distribution_from_facet_levels(
                     field_id,
                     candidates,
                     order_by,
                     &mut distribution,
+                    max_values,
                 )?;
             }
         };
@@ -287,6 +380,107 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    fn facet_ranges(
+        &self,
+        field_id: FieldId,
+        ranges: &[FacetRange],
+    ) -> Result<IndexMap<String, u64>> {
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut distribution = IndexMap::new();
+        for range in ranges {
+            let left = range.from.map_or(Bound::Unbounded, Bound::Included);
+            let right = range.to.map_or(Bound::Unbounded, Bound::Excluded);
+            let mut docids = RoaringBitmap::new();
+            // an empty range must not be explored.
+            if !matches!((range.from, range.to), (Some(from), Some(to)) if from >= to) {
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    self.rtxn,
+                    self.index.facet_id_f64_docids,
+                    field_id,
+                    &left,
+                    &right,
+                    Some(candidates),
+                    &mut docids,
+                )?;
+            }
+            distribution.insert(range.to_string(), docids.len());
+        }
+
+        Ok(distribution)
+    }
+
+    /// Counts the documents by children of the path of the hierarchy,
+    /// each child is returned with its full path so that it can be used to drill down.
+    fn facet_hierarchy(
+        &self,
+        field_id: FieldId,
+        hierarchy: &FacetHierarchy,
+        order_by: OrderBy,
+        max_values: usize,
+    ) -> Result<IndexMap<String, u64>> {
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let FacetHierarchy { separator, path } = hierarchy;
+        let depth = path.as_ref().map_or(0, |path| path.split(separator.as_str()).count());
+        let normalized_path = path.as_deref().map(crate::normalize_facet);
+        let prefix = normalized_path.as_deref().unwrap_or_default();
+
+        // the children are sorted by their normalized path to be returned alphabetically.
+        let mut children: BTreeMap<String, (String, RoaringBitmap)> = BTreeMap::new();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: prefix };
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &base)? {
+            let (FacetGroupKey { left_bound, .. }, group_value) = result?;
+            let docids = group_value.bitmap & candidates;
+            let Some(any_docid) = docids.min() else { continue };
+
+            let key: (FieldId, _, &str) = (field_id, any_docid, left_bound);
+            let original = match self.index.field_id_docid_facet_strings.get(self.rtxn, &key)? {
+                Some(original) => original,
+                None => left_bound,
+            };
+
+            let levels: Vec<_> = original.split(separator.as_str()).collect();
+            if levels.len() <= depth {
+                continue;
+            }
+            // the prefix iteration may also return the siblings starting like the path.
+            let parent = levels[..depth].join(separator.as_str());
+            if normalized_path.as_ref().is_some_and(|path| crate::normalize_facet(&parent) != *path)
+            {
+                continue;
+            }
+
+            let child = levels[..=depth].join(separator.as_str());
+            let (_, child_docids) = children
+                .entry(crate::normalize_facet(&child))
+                .or_insert_with(|| (child, RoaringBitmap::new()));
+            *child_docids |= docids;
+        }
+
+        let mut distribution: Vec<_> =
+            children.into_values().map(|(child, docids)| (child, docids.len())).collect();
+        if order_by == OrderBy::Count {
+            distribution.sort_by_key(|(_, count)| Reverse(*count));
+        }
+
+        Ok(distribution.into_iter().take(max_values).collect())
+    }
+
     pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
         let candidates = if let Some(candidates) = self.candidates.clone() {
             candidates
@@ -301,24 +495,21 @@ impl<'a> FacetDistribution<'a> {
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if self.select_field(name, &filterable_attributes_rules) {
-                let min_value = if let Some(min_value) = crate::search::facet::facet_min_value(
-                    self.index,
-                    self.rtxn,
-                    fid,
-                    candidates.clone(),
-                )? {
-                    min_value
-                } else {
+                let min_value =
+                    match facet_min_value(self.index, self.rtxn, fid, candidates.clone())? {
+                        Some(min_value) => Some(min_value),
+                        // the field doesn't contain any number, its dates are used as Unix timestamps.
+                        None => facet_min_date(self.index, self.rtxn, fid, candidates.clone())?,
+                    };
+                let Some(min_value) = min_value else {
                     continue;
                 };
-                let max_value = if let Some(max_value) = crate::search::facet::facet_max_value(
-                    self.index,
-                    self.rtxn,
-                    fid,
-                    candidates.clone(),
-                )? {
-                    max_value
-                } else {
+                let max_value =
+                    match facet_max_value(self.index, self.rtxn, fid, candidates.clone())? {
+                        Some(max_value) => Some(max_value),
+                        None => facet_max_date(self.index, self.rtxn, fid, candidates.clone())?,
+                    };
+                let Some(max_value) = max_value else {
                     continue;
                 };
 
@@ -342,7 +533,19 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let max_values = self
+                    .max_values_by_facet
+                    .get(name)
+                    .copied()
+                    .unwrap_or(self.max_values_per_facet);
+                let values =
+                    match (self.ranges_by_facet.get(name), self.hierarchies_by_facet.get(name)) {
+                        (Some(ranges), _) => self.facet_ranges(fid, ranges)?,
+                        (None, Some(hierarchy)) => {
+                            self.facet_hierarchy(fid, hierarchy, order_by, max_values)?
+                        }
+                        (None, None) => self.facet_values(fid, order_by, max_values)?,
+                    };
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -423,6 +626,9 @@ impl fmt::Debug for FacetDistribution<'_> {
             facets,
             candidates,
             max_values_per_facet,
+            max_values_by_facet,
+            ranges_by_facet,
+            hierarchies_by_facet,
             default_order_by,
             rtxn: _,
             index: _,
@@ -432,6 +638,9 @@ impl fmt::Debug for FacetDistribution<'_> {
             .field("facets", facets)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
+            .field("max_values_by_facet", max_values_by_facet)
+            .field("ranges_by_facet", ranges_by_facet)
+            .field("hierarchies_by_facet", hierarchies_by_facet)
             .field("default_order_by", default_order_by)
             .finish()
     }
@@ -445,7 +654,10 @@ mod tests {
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetDistribution, FacetHierarchy, FacetRange, FilterableAttributesRule,
+        OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -523,6 +735,40 @@ mod tests {
         milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2}}"###);
     }
 
+    #[test]
+    fn max_values_by_facet() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("colour")),
+                    FilterableAttributesRule::Field(S("size")),
+                ])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "colour": "Blue", "size": "S" },
+            { "id": 1, "colour": "Green", "size": "M" },
+            { "id": 2, "colour": "RED", "size": "L" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([("colour", OrderBy::default()), ("size", OrderBy::default())])
+            .candidates([0, 1, 2].iter().copied().collect())
+            .max_values_per_facet(1)
+            .max_values_by_facet(iter::once(("colour", 3)))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 1, "Green": 1, "RED": 1}, "size": {"L": 1}}"###);
+    }
+
     #[test]
     fn many_candidates_few_facet_values() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -671,6 +917,59 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_0_5_000", @"825f23a4090d05756f46176987b7d992");
     }
 
+    #[test]
+    fn facet_ranges() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("price"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..100 {
+            let document = serde_json::json!({
+                "id": i,
+                "price": i,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let ranges = vec![
+            FacetRange { from: None, to: Some(10.) },
+            FacetRange { from: Some(10.), to: Some(50.) },
+            FacetRange { from: Some(50.), to: None },
+            FacetRange { from: Some(50.), to: Some(10.) },
+        ];
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges_by_facet(iter::once(("price", ranges.clone())))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"..10": 10, "10..50": 40, "50..": 50, "50..10": 0}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges_by_facet(iter::once(("price", ranges)))
+            .candidates((0..20).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"..10": 10, "10..50": 10, "50..": 0, "50..10": 0}}"###);
+    }
+
     #[test]
     fn facet_stats() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -724,6 +1023,101 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_hierarchy() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings
+                    .set_filterable_fields(vec![FilterableAttributesRule::Field(S("categories"))])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "categories": ["Electronics > Phones > Android", "Electronics > Phones > iOS"] },
+            { "id": 1, "categories": "Electronics > Phones > iOS" },
+            { "id": 2, "categories": "Electronics > Laptops" },
+            { "id": 3, "categories": "Electronics" },
+            { "id": 4, "categories": "Electronics Outlet > Cables" },
+            { "id": 5, "categories": "Books > Science-fiction" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let hierarchy =
+            |path: Option<&str>| FacetHierarchy { separator: S(" > "), path: path.map(S) };
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("categories", OrderBy::default())))
+            .hierarchies_by_facet(iter::once(("categories", hierarchy(None))))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Books": 1, "Electronics": 4, "Electronics Outlet": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("categories", OrderBy::Count)))
+            .hierarchies_by_facet(iter::once(("categories", hierarchy(Some("Electronics")))))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Electronics > Phones": 2, "Electronics > Laptops": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("categories", OrderBy::default())))
+            .hierarchies_by_facet(iter::once((
+                "categories",
+                hierarchy(Some("electronics > phones")),
+            )))
+            .candidates([1, 2].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Electronics > Phones > iOS": 1}}"###);
+    }
+
+    #[test]
+    fn facet_stats_dates() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("date"))])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "date": "2024-01-01T12:00:00Z" },
+            { "id": 1, "date": "2024-01-02T12:00:00Z" },
+            { "id": 2, "date": "2024-01-03T12:00:00Z" },
+            { "id": 3, "date": "2024-01-04T12:00:00Z" },
+            { "id": 4, "date": "2024-01-05T12:00:00Z" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("date", OrderBy::default())))
+            .candidates((0..5).collect())
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"date": (1704110400.0, 1704456000.0)}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("date", OrderBy::default())))
+            .candidates((1..3).collect())
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"date": (1704196800.0, 1704283200.0)}"###);
+    }
+
     #[test]
     fn facet_stats_array() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
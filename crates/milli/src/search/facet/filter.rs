@@ -1,8 +1,10 @@
 use std::collections::BTreeSet;
 use std::fmt::{Debug, Display};
-use std::ops::Bound::{self, Excluded, Included};
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+use std::ops::RangeBounds;
 
 use either::Either;
+use filter_parser::nearest_candidates;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
 use heed::types::LazyDecode;
 use memchr::memmem::Finder;
@@ -10,9 +12,13 @@ use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
 
 use super::facet_range_search;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, UserError};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::geojson::{
+    BoundingBox, Geometry, Position, GEOJSON_MAX_LAT, GEOJSON_MAX_LNG, GEOJSON_MIN_LAT,
+    GEOJSON_MIN_LNG,
+};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
 };
@@ -26,6 +32,9 @@ use crate::{
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
 
+/// Suffix turning a condition on a field into a condition on its number of values, e.g. `tags.length > 2`.
+const LENGTH_SUFFIX: &str = ".length";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Filter<'a> {
     condition: FilterCondition<'a>,
@@ -36,6 +45,7 @@ pub enum BadGeoError {
     Lat(f64),
     Lng(f64),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    MinRadiusIsAboveMaxRadius(f64, f64),
 }
 
 impl std::error::Error for BadGeoError {}
@@ -46,6 +56,9 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::MinRadiusIsAboveMaxRadius(min, max) => {
+                write!(f, "The minimum radius `{min}` is above the maximum radius `{max}`.")
+            }
             Self::Lat(lat) => write!(
                 f,
                 "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ",
@@ -107,7 +120,8 @@ impl<'a> Display for FilterError<'a> {
 
 impl<'a> From<FPError<'a>> for Error {
     fn from(error: FPError<'a>) -> Self {
-        Self::UserError(UserError::InvalidFilter(error.to_string()))
+        let details = error.details();
+        Self::UserError(UserError::InvalidFilter(error.to_string(), details))
     }
 }
 
@@ -214,7 +228,7 @@ impl<'a> Filter<'a> {
         let condition = match FilterCondition::parse(expression) {
             Ok(Some(fc)) => Ok(fc),
             Ok(None) => return Ok(None),
-            Err(e) => Err(Error::UserError(UserError::InvalidFilter(e.to_string()))),
+            Err(e) => Err(Error::from(e)),
         }?;
 
         if let Some(token) = condition.token_at_depth(MAX_FILTER_DEPTH) {
@@ -227,6 +241,32 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns the attributes the filter is applied on, `_geo` and `_geojson` for the geo filters.
+    pub fn attributes(&self) -> Vec<&str> {
+        fn inner<'a>(condition: &'a FilterCondition<'a>, attributes: &mut Vec<&'a str>) {
+            match condition {
+                FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                    attributes.push(fid.value())
+                }
+                FilterCondition::Not(condition) => inner(condition, attributes),
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    conditions.iter().for_each(|condition| inner(condition, attributes))
+                }
+                FilterCondition::GeoLowerThan { .. } | FilterCondition::GeoBoundingBox { .. } => {
+                    attributes.push(RESERVED_GEO_FIELD_NAME)
+                }
+                FilterCondition::GeoJsonContains { .. }
+                | FilterCondition::GeoJsonIntersects { .. } => {
+                    attributes.push(RESERVED_GEOJSON_FIELD_NAME)
+                }
+            }
+        }
+
+        let mut attributes = Vec::new();
+        inner(&self.condition, &mut attributes);
+        attributes
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -234,22 +274,31 @@ impl<'a> Filter<'a> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
         let fields_ids_map = index.fields_ids_map(rtxn)?;
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
+        let is_filterable = |attribute: &str| {
+            matching_features(attribute, &filterable_attributes_rules)
+                .map_or(false, |(_, features)| features.is_filterable())
+        };
         for fid in self.condition.fids(MAX_FILTER_DEPTH) {
             let attribute = fid.value();
-            if matching_features(attribute, &filterable_attributes_rules)
-                .map_or(false, |(_, features)| features.is_filterable())
-            {
+            if is_filterable(attribute) || length_target(attribute).map_or(false, is_filterable) {
                 continue;
             }
 
             // If the field is not filterable, return an error
-            return Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+            let filterable_patterns =
+                filtered_matching_patterns(&filterable_attributes_rules, &|features| {
+                    features.is_filterable()
+                });
+            let suggestions = nearest_candidates(
                 attribute,
-                filterable_patterns: filtered_matching_patterns(
-                    &filterable_attributes_rules,
-                    &|features| features.is_filterable(),
-                ),
-            }))?;
+                filterable_patterns.iter().copied().filter(|pattern| !pattern.contains('*')),
+            );
+            return Err(fid
+                .as_external_error(FilterError::AttributeNotFilterable {
+                    attribute,
+                    filterable_patterns,
+                })
+                .with_suggestions(suggestions))?;
         }
 
         self.inner_evaluate(rtxn, index, &fields_ids_map, &filterable_attributes_rules, None)
@@ -284,7 +333,7 @@ impl<'a> Filter<'a> {
                     rtxn, index, field_id, operator, features, rule_index,
                 ));
             }
-            Condition::Empty if !features.is_filterable_empty() => {
+            Condition::Empty | Condition::EmptyArray if !features.is_filterable_empty() => {
                 return Err(generate_filter_error(
                     rtxn, index, field_id, operator, features, rule_index,
                 ));
@@ -325,6 +374,9 @@ impl<'a> Filter<'a> {
                 let is_empty = index.empty_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(is_empty);
             }
+            Condition::EmptyArray => {
+                return Self::evaluate_empty_array(rtxn, index, field_id, universe);
+            }
             Condition::Exists => {
                 let exist = index.exists_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(exist);
@@ -427,6 +479,151 @@ impl<'a> Filter<'a> {
         Ok(output)
     }
 
+    /// Returns the documents where the field is an empty array.
+    ///
+    /// The empty strings and objects are also stored as empty, so the documents are read
+    /// to only keep the empty arrays.
+    fn evaluate_empty_array(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        universe: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(field) = fields_ids_map.name(field_id) else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        let mut candidates = index.empty_faceted_documents_ids(rtxn, field_id)?;
+        if let Some(universe) = universe {
+            candidates &= universe;
+        }
+
+        let mut output = RoaringBitmap::new();
+        for result in index.iter_documents(rtxn, candidates)? {
+            let (docid, document) = result?;
+            for (fid, value) in document.iter() {
+                let Some(name) = fields_ids_map.name(fid) else { continue };
+                let path = if name == field {
+                    ""
+                } else {
+                    match field.strip_prefix(name).and_then(|rest| rest.strip_prefix('.')) {
+                        Some(path) => path,
+                        None => continue,
+                    }
+                };
+                let value: Value =
+                    serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                if matches!(select_value(&value, path), Some(Value::Array(array)) if array.is_empty())
+                {
+                    output.insert(docid);
+                    break;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Evaluates the operator against the number of values of the field in each document.
+    ///
+    /// Every value of the field is counted, duplicates included, and a field without any value,
+    /// like an empty array, has a length of zero.
+    fn evaluate_length(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        universe: Option<&RoaringBitmap>,
+        operator: &Condition<'a>,
+        features: &FilterableAttributesFeatures,
+        rule_index: usize,
+    ) -> Result<RoaringBitmap> {
+        let range = match operator {
+            Condition::GreaterThan(_)
+            | Condition::GreaterThanOrEqual(_)
+            | Condition::LowerThan(_)
+            | Condition::LowerThanOrEqual(_)
+            | Condition::Between { .. }
+                if !features.is_filterable_comparison() =>
+            {
+                return Err(generate_filter_error(
+                    rtxn, index, field_id, operator, features, rule_index,
+                ));
+            }
+            Condition::Equal(_) | Condition::NotEqual(_) if !features.is_filterable_equality() => {
+                return Err(generate_filter_error(
+                    rtxn, index, field_id, operator, features, rule_index,
+                ));
+            }
+            Condition::GreaterThan(val) => (Excluded(val.parse_finite_float()?), Unbounded),
+            Condition::GreaterThanOrEqual(val) => (Included(val.parse_finite_float()?), Unbounded),
+            Condition::LowerThan(val) => (Unbounded, Excluded(val.parse_finite_float()?)),
+            Condition::LowerThanOrEqual(val) => (Unbounded, Included(val.parse_finite_float()?)),
+            Condition::Between { from, to } => {
+                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            }
+            Condition::Equal(val) => {
+                let length = val.parse_finite_float()?;
+                (Included(length), Included(length))
+            }
+            Condition::NotEqual(val) => {
+                let operator = Condition::Equal(val.clone());
+                let docids = Self::evaluate_length(
+                    rtxn, index, field_id, None, &operator, features, rule_index,
+                )?;
+                let all_ids = index.documents_ids(rtxn)?;
+                return Ok(all_ids - docids);
+            }
+            // a length is only compared, like for an attribute that doesn't exist
+            Condition::Null
+            | Condition::Empty
+            | Condition::EmptyArray
+            | Condition::Exists
+            | Condition::Contains { .. }
+            | Condition::StartsWith { .. } => return Ok(RoaringBitmap::new()),
+        };
+
+        let mut candidates = index.exists_faceted_documents_ids(rtxn, field_id)?;
+        if let Some(universe) = universe {
+            candidates &= universe;
+        }
+
+        // The facet databases only keep the distinct normalized values of a document, the
+        // values are counted in the stored documents instead, duplicates included.
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let flatten_options = index.flatten_options(rtxn)?;
+        let Some(name) = fields_ids_map.name(field_id) else { return Ok(RoaringBitmap::new()) };
+
+        let mut output = RoaringBitmap::new();
+        for result in index.iter_documents(rtxn, candidates)? {
+            let (docid, document) = result?;
+            // only the top-level fields containing the field are deserialized, then flattened
+            // like at indexing time so that the values nested in arrays of objects are found
+            let mut object = serde_json::Map::new();
+            for (fid, value) in document.iter() {
+                let Some(top_level) = fields_ids_map.name(fid) else { continue };
+                if crate::is_faceted_by(name, top_level) {
+                    let value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    object.insert(top_level.to_string(), value);
+                }
+            }
+            let length = match flatten_serde_json::flatten_with_options(&object, flatten_options)
+                .get(name)
+            {
+                Some(Value::Array(values)) => values.len(),
+                Some(Value::Null) | None => 0,
+                Some(Value::String(value)) if value.is_empty() => 0,
+                Some(Value::Object(object)) if object.is_empty() => 0,
+                Some(_) => 1,
+            };
+            if range.contains(&(length as f64)) {
+                output.insert(docid);
+            }
+        }
+
+        Ok(output)
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -504,7 +701,22 @@ impl<'a> Filter<'a> {
             }
             FilterCondition::Condition { fid, op } => {
                 let Some(field_id) = field_ids_map.id(fid.value()) else {
-                    return Ok(RoaringBitmap::new());
+                    // `field.length` is the number of values of `field`, unless `field` has a `length` subfield
+                    let Some(field) = length_target(fid.value()) else {
+                        return Ok(RoaringBitmap::new());
+                    };
+                    let Some(field_id) = field_ids_map.id(field) else {
+                        return Ok(RoaringBitmap::new());
+                    };
+                    let Some((rule_index, features)) =
+                        matching_features(field, filterable_attribute_rules)
+                    else {
+                        return Ok(RoaringBitmap::new());
+                    };
+
+                    return Self::evaluate_length(
+                        rtxn, index, field_id, universe, op, &features, rule_index,
+                    );
                 };
                 let Some((rule_index, features)) =
                     matching_features(fid.value(), filterable_attribute_rules)
@@ -560,7 +772,7 @@ impl<'a> Filter<'a> {
                     Ok(RoaringBitmap::new())
                 }
             }
-            FilterCondition::GeoLowerThan { point, radius } => {
+            FilterCondition::GeoLowerThan { point, min_radius, radius } => {
                 if index.is_geo_filtering_enabled(rtxn)? {
                     let base_point: [f64; 2] =
                         [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
@@ -570,7 +782,19 @@ impl<'a> Filter<'a> {
                     if !(-180.0..=180.0).contains(&base_point[1]) {
                         return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
                     }
-                    let radius = radius.parse_finite_float()?;
+                    let max_radius = radius.parse_finite_float()?;
+                    let min_radius = match min_radius {
+                        Some(min_radius) => {
+                            let min = min_radius.parse_finite_float()?;
+                            if min > max_radius {
+                                return Err(min_radius.as_external_error(
+                                    BadGeoError::MinRadiusIsAboveMaxRadius(min, max_radius),
+                                ))?;
+                            }
+                            Some(min)
+                        }
+                        None => None,
+                    };
                     let rtree = match index.geo_rtree(rtxn)? {
                         Some(rtree) => rtree,
                         None => return Ok(RoaringBitmap::new()),
@@ -578,13 +802,17 @@ impl<'a> Filter<'a> {
 
                     let xyz_base_point = lat_lng_to_xyz(&base_point);
 
+                    // A document with several points matches as soon as one of them is in the range.
                     let result = rtree
                         .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
+                        .map(|point| {
+                            (point.data.0, distance_between_two_points(&base_point, &point.data.1))
+                        })
+                        .take_while(|(_, distance)| *distance <= max_radius + f64::EPSILON)
+                        .filter(|(_, distance)| {
+                            min_radius.map_or(true, |min| *distance >= min - f64::EPSILON)
                         })
-                        .map(|point| point.data.0)
+                        .map(|(docid, _)| docid)
                         .collect();
 
                     Ok(result)
@@ -726,7 +954,26 @@ impl<'a> Filter<'a> {
                         )?
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    let selected = selected_lat & selected_lng;
+
+                    // The latitude and longitude of a document with several geo points may
+                    // come from different points, these documents must be checked point by point.
+                    match index.geo_rtree(rtxn)? {
+                        Some(rtree)
+                            if rtree.size() as u64
+                                > index.geo_faceted_documents_ids(rtxn)?.len() =>
+                        {
+                            Ok(rtree
+                                .iter()
+                                .filter(|point| selected.contains(point.data.0))
+                                .filter(|point| {
+                                    is_in_bounding_box(&point.data.1, &top_right, &bottom_left)
+                                })
+                                .map(|point| point.data.0)
+                                .collect())
+                        }
+                        _ => Ok(selected),
+                    }
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -739,8 +986,177 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoJsonContains { point } => {
+                if index.is_geojson_enabled(rtxn)? {
+                    let point = parse_position(point)?;
+                    let area = BoundingBox { min: point, max: point };
+                    Self::evaluate_geojson(
+                        rtxn,
+                        index,
+                        field_ids_map,
+                        universe,
+                        &area,
+                        |geometry| geometry.contains(point),
+                    )
+                } else {
+                    Err(point[0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEOJSON_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+            FilterCondition::GeoJsonIntersects { top_right_point, bottom_left_point } => {
+                if index.is_geojson_enabled(rtxn)? {
+                    let top_right = parse_position(top_right_point)?;
+                    let bottom_left = parse_position(bottom_left_point)?;
+                    if top_right.lat < bottom_left.lat {
+                        return Err(bottom_left_point[1].as_external_error(
+                            BadGeoError::BoundingBoxTopIsBelowBottom(
+                                top_right.lat,
+                                bottom_left.lat,
+                            ),
+                        ))?;
+                    }
+
+                    // A rectangle wrapping around the earth is split in two on the antimeridian.
+                    let areas = if top_right.lng < bottom_left.lng {
+                        vec![
+                            BoundingBox {
+                                min: bottom_left,
+                                max: Position { lng: 180.0, lat: top_right.lat },
+                            },
+                            BoundingBox {
+                                min: Position { lng: -180.0, lat: bottom_left.lat },
+                                max: top_right,
+                            },
+                        ]
+                    } else {
+                        vec![BoundingBox { min: bottom_left, max: top_right }]
+                    };
+
+                    areas
+                        .iter()
+                        .map(|area| {
+                            Self::evaluate_geojson(
+                                rtxn,
+                                index,
+                                field_ids_map,
+                                universe,
+                                area,
+                                |geometry| geometry.intersects(area),
+                            )
+                        })
+                        .union()
+                } else {
+                    Err(top_right_point[0].as_external_error(
+                        FilterError::AttributeNotFilterable {
+                            attribute: RESERVED_GEOJSON_FIELD_NAME,
+                            filterable_patterns: filtered_matching_patterns(
+                                filterable_attribute_rules,
+                                &|features| features.is_filterable(),
+                            ),
+                        },
+                    ))?
+                }
+            }
+        }
+    }
+
+    /// Returns the documents whose `_geojson` geometry matches, among the ones whose bounding box
+    /// intersects the area.
+    fn evaluate_geojson(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_ids_map: &FieldsIdsMap,
+        universe: Option<&RoaringBitmap>,
+        area: &BoundingBox,
+        matches: impl Fn(&Geometry) -> bool,
+    ) -> Result<RoaringBitmap> {
+        let fields = [GEOJSON_MIN_LAT, GEOJSON_MAX_LAT, GEOJSON_MIN_LNG, GEOJSON_MAX_LNG];
+        let (Some(geojson_fid), [Some(min_lat), Some(max_lat), Some(min_lng), Some(max_lng)]) = (
+            field_ids_map.id(RESERVED_GEOJSON_FIELD_NAME),
+            fields.map(|name| field_ids_map.id(name)),
+        ) else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        // The bounding boxes of the candidates intersect the area.
+        let mut candidates = universe.cloned();
+        for (field_id, left, right) in [
+            (min_lat, Unbounded, Included(area.max.lat)),
+            (max_lat, Included(area.min.lat), Unbounded),
+            (min_lng, Unbounded, Included(area.max.lng)),
+            (max_lng, Included(area.min.lng), Unbounded),
+        ] {
+            let mut selected = RoaringBitmap::new();
+            Self::explore_facet_number_levels(
+                rtxn,
+                index.facet_id_f64_docids,
+                field_id,
+                left,
+                right,
+                candidates.as_ref(),
+                &mut selected,
+            )?;
+            candidates = Some(selected);
+        }
+
+        let mut result = RoaringBitmap::new();
+        for docid in candidates.unwrap_or_default() {
+            let Some(geojson) = index.document(rtxn, docid)?.get(geojson_fid) else { continue };
+            let value = serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?;
+            // The geometries were validated when the document was indexed.
+            if Geometry::from_value(value).is_ok_and(|geometry| matches(&geometry)) {
+                result.insert(docid);
+            }
         }
+        Ok(result)
+    }
+}
+
+/// Parses a `[lat, lng]` point of a filter.
+fn parse_position(point: &[Token; 2]) -> Result<Position> {
+    let lat = point[0].parse_finite_float()?;
+    let lng = point[1].parse_finite_float()?;
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(point[0].as_external_error(BadGeoError::Lat(lat)))?;
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(point[1].as_external_error(BadGeoError::Lng(lng)))?;
+    }
+    Ok(Position { lng, lat })
+}
+
+/// Returns whether the `[lat, lng]` point is in the bounding box, which may wrap around the earth.
+fn is_in_bounding_box(point: &[f64; 2], top_right: &[f64; 2], bottom_left: &[f64; 2]) -> bool {
+    let [lat, lng] = *point;
+    let in_lat = (bottom_left[0]..=top_right[0]).contains(&lat);
+    let in_lng = if top_right[1] < bottom_left[1] {
+        lng >= bottom_left[1] || lng <= top_right[1]
+    } else {
+        (bottom_left[1]..=top_right[1]).contains(&lng)
+    };
+    in_lat && in_lng
+}
+
+/// Returns the field whose number of values is targeted by `attribute`, if it ends with `.length`.
+fn length_target(attribute: &str) -> Option<&str> {
+    attribute.strip_suffix(LENGTH_SUFFIX).filter(|field| !field.is_empty())
+}
+
+/// Returns the value at the dot-separated `path` of a JSON value, `path` being empty for the value itself.
+fn select_value<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    if path.is_empty() {
+        return Some(value);
     }
+    let Value::Object(object) = value else { return None };
+    object.iter().find_map(|(key, value)| match path.strip_prefix(key.as_str())? {
+        "" => Some(value),
+        rest => select_value(value, rest.strip_prefix('.')?),
+    })
 }
 
 fn generate_filter_error(
@@ -870,6 +1286,16 @@ mod tests {
         assert_eq!(condition, expected);
     }
 
+    #[test]
+    fn attributes() {
+        let filter = Filter::from_str(
+            "channel = mv AND NOT (views > 10 OR tags IN [a, b]) AND _geoRadius(12, 13, 14)",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(filter.attributes(), vec!["channel", "views", "tags", "_geo"]);
+    }
+
     #[test]
     fn not_filterable() {
         let index = TempIndex::new();
@@ -951,6 +1377,27 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn not_filterable_suggestions() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field("title".to_string()),
+                    FilterableAttributesRule::Field("genres".to_string()),
+                ]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("genre = horror").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        let crate::Error::UserError(crate::UserError::InvalidFilter(_, details)) = &error else {
+            panic!("expected an invalid filter error, got {error:?}");
+        };
+        snapshot!(format!("{details:?}"), @r###"ErrorDetails { line: 1, column: 1, token: "genre", snippet: "genre = horror\n^^^^^", suggestions: ["genres"] }"###);
+    }
+
     #[test]
     fn escaped_quote_in_filter_value_2380() {
         let index = TempIndex::new();
@@ -1047,6 +1494,21 @@ mod tests {
         search.filter(Filter::from_str("_geoRadius(45.4777599, 9.1967508, 0)").unwrap().unwrap());
         let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
         assert_eq!(documents_ids, vec![0]);
+
+        // the two restaurants are about 2.5km away from each other
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(
+            Filter::from_str("_geoRadius(45.4777599, 9.1967508, 1000, 5000)").unwrap().unwrap(),
+        );
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![1]);
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(
+            Filter::from_str("_geoRadius(45.4777599, 9.1967508, 0, 5000)").unwrap().unwrap(),
+        );
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![0, 1]);
     }
 
     #[test]
@@ -1100,6 +1562,15 @@ mod tests {
         assert!(error.to_string().contains(
             "Bad longitude `180.000001`. Longitude must be contained between -180 and 180 degrees."
         ));
+
+        // georadius have a minimum radius above its maximum radius
+        let filter = Filter::from_str("_geoRadius(-10, 150, 20, 10)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().contains("The minimum radius `20` is above the maximum radius `10`."),
+            "{}",
+            error.to_string(),
+        );
     }
 
     #[test]
@@ -1251,7 +1722,7 @@ mod tests {
         let filter = Filter::from_str("price < inf").unwrap().unwrap();
         assert!(matches!(
             filter.evaluate(&rtxn, &index),
-            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(_)))
+            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(..)))
         ));
 
         let filter = Filter::from_str("price = NaN").unwrap().unwrap();
@@ -1260,7 +1731,7 @@ mod tests {
         let filter = Filter::from_str("price < NaN").unwrap().unwrap();
         assert!(matches!(
             filter.evaluate(&rtxn, &index),
-            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(_)))
+            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(..)))
         ));
 
         let filter = Filter::from_str("price = infinity").unwrap().unwrap();
@@ -1269,7 +1740,7 @@ mod tests {
         let filter = Filter::from_str("price < infinity").unwrap().unwrap();
         assert!(matches!(
             filter.evaluate(&rtxn, &index),
-            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(_)))
+            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(..)))
         ));
     }
 
@@ -1357,4 +1828,59 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_array_length() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field("tags".to_string()),
+                    FilterableAttributesRule::Field("nested".to_string()),
+                ]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "tags": [] },
+                { "id": 1, "tags": ["blue"] },
+                { "id": 2, "tags": ["blue", "red", 3] },
+                { "id": 3, "tags": ["blue", "BLUE"] },
+                { "id": 4, "tags": "" },
+                { "id": 5, "tags": {} },
+                { "id": 6 },
+                { "id": 7, "nested": { "tags": [] } },
+                { "id": 8, "nested": { "tags": ["blue"], "length": 4 } },
+                { "id": 9, "tags": ["red", "red", "red"] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        snapshot!(format!("{:?}", evaluate("tags IS EMPTY")), @"RoaringBitmap<[0, 4, 5]>");
+        snapshot!(format!("{:?}", evaluate("tags IS EMPTY ARRAY")), @"RoaringBitmap<[0]>");
+        snapshot!(format!("{:?}", evaluate("tags IS NOT EMPTY ARRAY")), @"RoaringBitmap<[1, 2, 3, 4, 5, 6, 7, 8, 9]>");
+        snapshot!(format!("{:?}", evaluate("nested.tags IS EMPTY ARRAY")), @"RoaringBitmap<[7]>");
+
+        snapshot!(format!("{:?}", evaluate("tags.length = 0")), @"RoaringBitmap<[0, 4, 5]>");
+        snapshot!(format!("{:?}", evaluate("tags.length = 1")), @"RoaringBitmap<[1]>");
+        snapshot!(format!("{:?}", evaluate("tags.length > 1")), @"RoaringBitmap<[2, 3, 9]>");
+        snapshot!(format!("{:?}", evaluate("tags.length 1 TO 3")), @"RoaringBitmap<[1, 2, 3, 9]>");
+        snapshot!(format!("{:?}", evaluate("tags.length != 1")), @"RoaringBitmap<[0, 2, 3, 4, 5, 6, 7, 8, 9]>");
+
+        // the duplicates and the case variants are counted, even if they share a facet value
+        snapshot!(format!("{:?}", evaluate("tags.length = 2")), @"RoaringBitmap<[3]>");
+        snapshot!(format!("{:?}", evaluate("tags.length = 3")), @"RoaringBitmap<[2, 9]>");
+        snapshot!(format!("{:?}", evaluate("nested.tags.length = 1")), @"RoaringBitmap<[8]>");
+
+        // an actual `length` field takes precedence
+        snapshot!(format!("{:?}", evaluate("nested.length = 4")), @"RoaringBitmap<[8]>");
+    }
 }
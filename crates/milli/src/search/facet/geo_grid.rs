@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use roaring::RoaringBitmap;
+
+use crate::{lat_lng_to_geohash, Index, Result};
+
+/// The maximum precision of a geohash cell, a cell of this precision is a few centimeters wide.
+pub const MAX_GEO_GRID_PRECISION: usize = 12;
+
+/// Counts the documents per geohash cell of a given precision.
+///
+/// A document with several geo points in the same cell is only counted once in this cell.
+pub struct GeoGrid<'a> {
+    precision: usize,
+    candidates: Option<RoaringBitmap>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> GeoGrid<'a> {
+    pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index, precision: usize) -> GeoGrid<'a> {
+        GeoGrid { precision, candidates: None, rtxn, index }
+    }
+
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    pub fn execute(&self) -> Result<BTreeMap<String, u64>> {
+        let Some(rtree) = self.index.geo_rtree(self.rtxn)? else {
+            return Ok(BTreeMap::new());
+        };
+
+        let mut cells: BTreeMap<String, RoaringBitmap> = BTreeMap::new();
+        for point in rtree.iter() {
+            let (docid, coords) = point.data;
+            if self.candidates.as_ref().is_some_and(|candidates| !candidates.contains(docid)) {
+                continue;
+            }
+            cells.entry(lat_lng_to_geohash(&coords, self.precision)).or_default().insert(docid);
+        }
+
+        Ok(cells.into_iter().map(|(cell, docids)| (cell, docids.len())).collect())
+    }
+}
+
+impl fmt::Debug for GeoGrid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let GeoGrid { precision, candidates, rtxn: _, index: _ } = self;
+
+        f.debug_struct("GeoGrid")
+            .field("precision", precision)
+            .field("candidates", &candidates.as_ref().map(|c| c.len()))
+            .finish()
+    }
+}
@@ -3,12 +3,17 @@ pub use facet_sort_descending::descending_facet_sort;
 use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetHierarchy, FacetRange, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::filter::{BadGeoError, Filter};
-pub use self::search::{FacetValueHit, SearchForFacetValues};
+pub use self::geo_grid::{GeoGrid, MAX_GEO_GRID_PRECISION};
+pub use self::search::{FacetQueryMatching, FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
-use crate::heed_codec::BytesRefCodec;
+use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::{Index, Result};
 
 mod facet_distribution;
@@ -17,6 +22,7 @@ mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
 mod filter;
+mod geo_grid;
 mod search;
 
 fn facet_extreme_value<'t>(
@@ -53,6 +59,48 @@ pub fn facet_max_value<'t>(
     facet_extreme_value(it)
 }
 
+/// Returns the given facet string value as a Unix timestamp if it is an RFC 3339 date.
+fn facet_extreme_date<'t>(
+    mut extreme_it: impl Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>> + 't,
+) -> Result<Option<f64>> {
+    let extreme_value =
+        if let Some(extreme_value) = extreme_it.next() { extreme_value } else { return Ok(None) };
+    let (_, extreme_value) = extreme_value?;
+    let extreme_value = StrRefCodec::bytes_decode(extreme_value).map_err(heed::Error::Decoding)?;
+    // facet strings are normalized to lowercase, the `T` and `Z` of the date must be restored.
+    let date = OffsetDateTime::parse(&extreme_value.to_ascii_uppercase(), &Rfc3339).ok();
+    Ok(date.map(|date| date.unix_timestamp() as f64))
+}
+
+/// Returns the earliest date of the facet string values of the candidates.
+///
+/// The string values are compared lexicographically which only orders the
+/// RFC 3339 dates chronologically when they share the same offset.
+pub fn facet_min_date<'t>(
+    index: &'t Index,
+    rtxn: &'t heed::RoTxn<'t>,
+    field_id: u16,
+    candidates: RoaringBitmap,
+) -> Result<Option<f64>> {
+    let db = index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+    let it = ascending_facet_sort(rtxn, db, field_id, candidates)?;
+    facet_extreme_date(it)
+}
+
+/// Returns the latest date of the facet string values of the candidates.
+///
+/// See [`facet_min_date`] for the limitations.
+pub fn facet_max_date<'t>(
+    index: &'t Index,
+    rtxn: &'t heed::RoTxn<'t>,
+    field_id: u16,
+    candidates: RoaringBitmap,
+) -> Result<Option<f64>> {
+    let db = index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+    let it = descending_facet_sort(rtxn, db, field_id, candidates)?;
+    facet_extreme_date(it)
+}
+
 /// Get the first facet value in the facet database
 pub(crate) fn get_first_facet_value<'t, BoundCodec, DC>(
     txn: &'t RoTxn<'t>,
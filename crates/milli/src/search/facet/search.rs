@@ -18,6 +18,18 @@ use crate::{DocumentId, FieldId, OrderBy, Result, Search};
 /// The maximum number of values per facet returned by the facet search route.
 const DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET: usize = 100;
 
+/// How the facet query should match the facet values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FacetQueryMatching {
+    /// Matches the values starting with the query, with typos if the index allows them.
+    #[default]
+    Fuzzy,
+    /// Matches the values starting with the query, without typos.
+    Prefix,
+    /// Only matches the values equal to the query.
+    Exact,
+}
+
 pub struct SearchForFacetValues<'a> {
     query: Option<String>,
     facet: String,
@@ -25,6 +37,7 @@ pub struct SearchForFacetValues<'a> {
     max_values: usize,
     is_hybrid: bool,
     locales: Option<Vec<Language>>,
+    matching: FacetQueryMatching,
 }
 
 impl<'a> SearchForFacetValues<'a> {
@@ -40,6 +53,7 @@ impl<'a> SearchForFacetValues<'a> {
             max_values: DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET,
             is_hybrid,
             locales: None,
+            matching: FacetQueryMatching::default(),
         }
     }
 
@@ -58,6 +72,11 @@ impl<'a> SearchForFacetValues<'a> {
         self
     }
 
+    pub fn matching(&mut self, matching: FacetQueryMatching) -> &mut Self {
+        self.matching = matching;
+        self
+    }
+
     fn one_original_value_of(
         &self,
         field_id: FieldId,
@@ -133,7 +152,20 @@ impl<'a> SearchForFacetValues<'a> {
                 let field_authorizes_typos =
                     !self.search_query.index.exact_attributes_ids(rtxn)?.contains(&fid);
 
-                if authorize_typos && field_authorizes_typos {
+                if self.matching == FacetQueryMatching::Exact {
+                    if fst.contains(query) {
+                        self.fetch_original_facets_using_normalized(
+                            fid,
+                            query,
+                            query,
+                            &search_candidates,
+                            &mut results,
+                        )?;
+                    }
+                } else if self.matching == FacetQueryMatching::Fuzzy
+                    && authorize_typos
+                    && field_authorizes_typos
+                {
                     let exact_words_fst = self.search_query.index.exact_words(rtxn)?;
                     if exact_words_fst.map_or(false, |fst| fst.contains(query)) {
                         if fst.contains(query) {
@@ -1,11 +1,14 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::time::Instant;
 
 use itertools::Itertools;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::SemanticSearch;
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::{MatchingWords, Result, Search, SearchResult, SearchTimings};
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
@@ -13,10 +16,28 @@ struct ScoreWithRatioResult {
     document_scores: Vec<(u32, ScoreWithRatio)>,
     degraded: bool,
     used_negative_operator: bool,
+    timings: SearchTimings,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
 
+/// The constant dampening the weight of the top ranks in a reciprocal rank fusion.
+const RRF_K: f64 = 60.0;
+
+/// How the keyword and semantic results of a hybrid search are merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HybridFusion {
+    /// Compare the scores of both searches, weighted by the semantic ratio.
+    #[default]
+    Linear,
+    /// Sum `ratio / (60 + rank)` for each search returning the document.
+    ///
+    /// Only the ranks matter, so the scales of the keyword and semantic scores are irrelevant.
+    ReciprocalRank,
+    /// Min-max normalize the scores of each search, then sum them weighted by the semantic ratio.
+    NormalizedSum,
+}
+
 #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
 fn compare_scores(
     &(ref left_scores, left_ratio): &ScoreWithRatio,
@@ -82,6 +103,7 @@ impl ScoreWithRatioResult {
             document_scores,
             degraded: results.degraded,
             used_negative_operator: results.used_negative_operator,
+            timings: results.timings,
         }
     }
 
@@ -136,6 +158,9 @@ impl ScoreWithRatioResult {
             document_scores.push(main_score);
         }
 
+        let mut timings = keyword_results.timings;
+        timings.merge(vector_results.timings);
+
         (
             SearchResult {
                 matching_words: keyword_results.matching_words,
@@ -145,15 +170,140 @@ impl ScoreWithRatioResult {
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
+                timings,
             },
             semantic_hit_count,
         )
     }
+
+    /// Merges the results by summing a fused score per document, see [`HybridFusion`].
+    ///
+    /// A document keeps the score details of the search that contributed the most to its fused score,
+    /// and counts as a semantic hit when that search is the semantic one.
+    #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
+    fn fuse(
+        vector_results: Self,
+        keyword_results: Self,
+        fusion: HybridFusion,
+        from: usize,
+        length: usize,
+    ) -> (SearchResult, u32) {
+        struct FusedHit {
+            docid: u32,
+            score: f64,
+            best_contribution: f64,
+            scores: Vec<ScoreDetails>,
+            semantic: bool,
+        }
+
+        let mut hits: Vec<FusedHit> = Vec::with_capacity(
+            vector_results.document_scores.len() + keyword_results.document_scores.len(),
+        );
+        let mut positions = HashMap::new();
+
+        for (document_scores, semantic) in
+            [(vector_results.document_scores, true), (keyword_results.document_scores, false)]
+        {
+            let contributions = fused_contributions(&document_scores, fusion);
+            for ((docid, (scores, _ratio)), contribution) in
+                document_scores.into_iter().zip(contributions)
+            {
+                match positions.entry(docid) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(hits.len());
+                        hits.push(FusedHit {
+                            docid,
+                            score: contribution,
+                            best_contribution: contribution,
+                            scores,
+                            semantic,
+                        });
+                    }
+                    Entry::Occupied(entry) => {
+                        let hit = &mut hits[*entry.get()];
+                        hit.score += contribution;
+                        if contribution > hit.best_contribution {
+                            hit.best_contribution = contribution;
+                            hit.scores = scores;
+                            hit.semantic = semantic;
+                        }
+                    }
+                }
+            }
+        }
+
+        // stable sort, the ties keep the semantic results first
+        hits.sort_by(|left, right| right.score.total_cmp(&left.score));
+
+        let mut semantic_hit_count = 0;
+        let mut documents_ids = Vec::with_capacity(length);
+        let mut document_scores = Vec::with_capacity(length);
+        for FusedHit { docid, scores, semantic, .. } in hits.into_iter().skip(from).take(length) {
+            if semantic {
+                semantic_hit_count += 1;
+            }
+            documents_ids.push(docid);
+            document_scores.push(scores);
+        }
+
+        let mut timings = keyword_results.timings;
+        timings.merge(vector_results.timings);
+
+        (
+            SearchResult {
+                matching_words: keyword_results.matching_words,
+                candidates: vector_results.candidates | keyword_results.candidates,
+                documents_ids,
+                document_scores,
+                degraded: vector_results.degraded | keyword_results.degraded,
+                used_negative_operator: vector_results.used_negative_operator
+                    | keyword_results.used_negative_operator,
+                timings,
+            },
+            semantic_hit_count,
+        )
+    }
+}
+
+/// The part of the fused score given by each document of a result list, in the order of the list.
+fn fused_contributions(
+    document_scores: &[(u32, ScoreWithRatio)],
+    fusion: HybridFusion,
+) -> Vec<f64> {
+    match fusion {
+        HybridFusion::Linear => unreachable!("linear fusion compares the scores instead"),
+        HybridFusion::ReciprocalRank => document_scores
+            .iter()
+            .enumerate()
+            .map(|(rank, (_, (_, ratio)))| *ratio as f64 / (RRF_K + (rank + 1) as f64))
+            .collect(),
+        HybridFusion::NormalizedSum => {
+            let global_scores: Vec<f64> = document_scores
+                .iter()
+                .map(|(_, (scores, _))| ScoreDetails::global_score(scores.iter()))
+                .collect();
+            let min = global_scores.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = global_scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            global_scores
+                .into_iter()
+                .zip(document_scores)
+                .map(|(score, (_, (_, ratio)))| {
+                    let normalized =
+                        if max - min <= f64::EPSILON { 1.0 } else { (score - min) / (max - min) };
+                    normalized * *ratio as f64
+                })
+                .collect()
+        }
+    }
 }
 
 impl<'a> Search<'a> {
     #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
-    pub fn execute_hybrid(&self, semantic_ratio: f32) -> Result<(SearchResult, Option<u32>)> {
+    pub fn execute_hybrid(
+        &self,
+        semantic_ratio: f32,
+        fusion: HybridFusion,
+    ) -> Result<(SearchResult, Option<u32>)> {
         // TODO: find classier way to achieve that than to reset vector and query params
         // create separate keyword and semantic searches
         let mut search = Search {
@@ -175,13 +325,19 @@ impl<'a> Search<'a> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            ignore_terms: self.ignore_terms.clone(),
+            sensitive_matching: self.sensitive_matching,
+            search_accuracy: self.search_accuracy,
         };
 
         let semantic = search.semantic.take();
         let keyword_results = search.execute()?;
 
         // completely skip semantic search if the results of the keyword search are good enough
-        if self.results_good_enough(&keyword_results, semantic_ratio) {
+        // to win a linear blend, the other fusions rank the semantic hits regardless of the keyword scores
+        if fusion == HybridFusion::Linear
+            && self.results_good_enough(&keyword_results, semantic_ratio)
+        {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         }
 
@@ -194,6 +350,7 @@ impl<'a> Search<'a> {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
 
+        let before_embedding = Instant::now();
         let vector_query = match vector {
             Some(vector_query) => vector_query,
             None => {
@@ -217,17 +374,33 @@ impl<'a> Search<'a> {
             }
         };
 
+        let embedding_duration = before_embedding.elapsed();
+
         search.semantic =
             Some(SemanticSearch { vector: Some(vector_query), embedder_name, embedder, quantized });
 
         // TODO: would be better to have two distinct functions at this point
-        let vector_results = search.execute()?;
+        let mut vector_results = search.execute()?;
+        vector_results.timings.vector_search += embedding_duration;
 
         let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
         let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
 
-        let (merge_results, semantic_hit_count) =
-            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit);
+        let (merge_results, semantic_hit_count) = match fusion {
+            HybridFusion::Linear => ScoreWithRatioResult::merge(
+                vector_results,
+                keyword_results,
+                self.offset,
+                self.limit,
+            ),
+            fusion => ScoreWithRatioResult::fuse(
+                vector_results,
+                keyword_results,
+                fusion,
+                self.offset,
+                self.limit,
+            ),
+        };
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
     }
@@ -263,6 +436,7 @@ fn return_keyword_results(
         mut document_scores,
         degraded,
         used_negative_operator,
+        timings,
     }: SearchResult,
 ) -> (SearchResult, Option<u32>) {
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
@@ -289,6 +463,7 @@ fn return_keyword_results(
             document_scores,
             degraded,
             used_negative_operator,
+            timings,
         },
         Some(0),
     )
@@ -1,13 +1,18 @@
 use std::fmt;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
-pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
+pub use self::facet::{
+    FacetDistribution, FacetHierarchy, FacetRange, Filter, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
+pub use self::new::matches::{
+    CropStrategy, FormatOptions, MatchBounds, MatchKind, MatchedTerm, MatcherBuilder, MatchingWords,
+};
 use self::new::{execute_vector_search, PartialSearchResult};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
@@ -56,6 +61,9 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    ignore_terms: Option<Vec<String>>,
+    sensitive_matching: Option<bool>,
+    search_accuracy: Option<f32>,
 }
 
 impl<'a> Search<'a> {
@@ -77,6 +85,9 @@ impl<'a> Search<'a> {
             index,
             semantic: None,
             locales: None,
+            ignore_terms: None,
+            sensitive_matching: None,
+            search_accuracy: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
         }
@@ -171,6 +182,27 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Terms that are treated as stop words for this search only,
+    /// on top of the stop words defined in the index settings.
+    pub fn ignore_terms(&mut self, ignore_terms: Vec<String>) -> &mut Search<'a> {
+        self.ignore_terms = Some(ignore_terms);
+        self
+    }
+
+    /// Only match the query words with their exact case and diacritics,
+    /// overriding the `sensitiveMatching` setting of the index.
+    pub fn sensitive_matching(&mut self, sensitive_matching: bool) -> &mut Search<'a> {
+        self.sensitive_matching = Some(sensitive_matching);
+        self
+    }
+
+    /// Scales the number of candidates inspected by the vector store during a semantic search,
+    /// `1.0` being the default. Lower values are faster but may miss some of the nearest neighbors.
+    pub fn search_accuracy(&mut self, search_accuracy: f32) -> &mut Search<'a> {
+        self.search_accuracy = Some(search_accuracy);
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -216,7 +248,14 @@ impl<'a> Search<'a> {
             }
         }
 
+        let sensitive_matching = match self.sensitive_matching {
+            Some(sensitive_matching) => sensitive_matching,
+            None => self.index.sensitive_matching(self.rtxn)?,
+        };
+
+        let before_filter = Instant::now();
         let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        ctx.timings.candidates += before_filter.elapsed();
         let PartialSearchResult {
             located_query_terms,
             candidates,
@@ -241,6 +280,7 @@ impl<'a> Search<'a> {
                     *quantized,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
+                    self.search_accuracy,
                 )?
             }
             _ => execute_search(
@@ -261,9 +301,13 @@ impl<'a> Search<'a> {
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
                 self.locales.as_ref(),
+                self.ignore_terms.as_deref(),
+                sensitive_matching,
             )?,
         };
 
+        let timings = std::mem::take(&mut ctx.timings);
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
@@ -277,6 +321,7 @@ impl<'a> Search<'a> {
             documents_ids,
             degraded,
             used_negative_operator,
+            timings,
         })
     }
 }
@@ -302,6 +347,9 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            ignore_terms,
+            sensitive_matching,
+            search_accuracy,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -323,6 +371,9 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("ignore_terms", ignore_terms)
+            .field("sensitive_matching", sensitive_matching)
+            .field("search_accuracy", search_accuracy)
             .finish()
     }
 }
@@ -335,15 +386,49 @@ pub struct SearchResult {
     pub document_scores: Vec<Vec<ScoreDetails>>,
     pub degraded: bool,
     pub used_negative_operator: bool,
+    pub timings: SearchTimings,
+}
+
+/// The time spent in the different steps of a search.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SearchTimings {
+    /// Building the tokenizer and extracting the query terms.
+    pub tokenization: Duration,
+    /// Evaluating the filter and resolving the query to find the candidates.
+    pub candidates: Duration,
+    /// The time spent in each ranking rule, in the order they are applied.
+    pub ranking_rules: Vec<(String, Duration)>,
+    /// Embedding the query and ranking the documents by similarity.
+    pub vector_search: Duration,
+}
+
+impl SearchTimings {
+    pub(crate) fn add_ranking_rule(&mut self, id: String, duration: Duration) {
+        match self.ranking_rules.iter_mut().find(|(rule, _)| *rule == id) {
+            Some((_, total)) => *total += duration,
+            None => self.ranking_rules.push((id, duration)),
+        }
+    }
+
+    /// Adds the timings of another search, e.g. the semantic part of a hybrid search.
+    pub fn merge(&mut self, other: SearchTimings) {
+        let SearchTimings { tokenization, candidates, ranking_rules, vector_search } = other;
+        self.tokenization += tokenization;
+        self.candidates += candidates;
+        for (id, duration) in ranking_rules {
+            self.add_ranking_rule(id, duration);
+        }
+        self.vector_search += vector_search;
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TermsMatchingStrategy {
-    // remove last word first
+    // remove last word first, phrases are never removed
     Last,
     // all words are mandatory
     All,
-    // remove more frequent word first
+    // remove more frequent word first, phrases are never removed
     Frequency,
 }
 
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
@@ -99,10 +101,30 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     }
 
     let ranking_rules_len = ranking_rules.len();
+    let mut ranking_rule_durations = vec![Duration::ZERO; ranking_rules_len];
+
+    /// Run the given expression and add the time it took to the ranking rule at the given index.
+    macro_rules! timed {
+        ($ranking_rule_index:expr, $e:expr) => {{
+            let before = Instant::now();
+            let result = $e;
+            ranking_rule_durations[$ranking_rule_index] += before.elapsed();
+            result
+        }};
+    }
+
+    /// Report the time spent in each ranking rule to the search context.
+    macro_rules! report_durations {
+        () => {
+            for (ranking_rule, duration) in ranking_rules.iter().zip(&ranking_rule_durations) {
+                ctx.timings.add_ranking_rule(ranking_rule.id(), *duration);
+            }
+        };
+    }
 
     logger.start_iteration_ranking_rule(0, ranking_rules[0].as_ref(), query, universe);
 
-    ranking_rules[0].start_iteration(ctx, logger, universe, query)?;
+    timed!(0, ranking_rules[0].start_iteration(ctx, logger, universe, query))?;
 
     let mut ranking_rule_scores: Vec<ScoreDetails> = vec![];
 
@@ -128,7 +150,10 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &ranking_rule_universes[cur_ranking_rule_index],
             );
             ranking_rule_universes[cur_ranking_rule_index].clear();
-            ranking_rules[cur_ranking_rule_index].end_iteration(ctx, logger);
+            timed!(
+                cur_ranking_rule_index,
+                ranking_rules[cur_ranking_rule_index].end_iteration(ctx, logger)
+            );
             if cur_ranking_rule_index == 0 {
                 break;
             } else {
@@ -193,6 +218,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 back!();
             }
 
+            report_durations!();
             return Ok(BucketSortOutput {
                 scores: valid_scores,
                 docids: valid_docids,
@@ -213,10 +239,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             continue;
         }
 
-        let Some(next_bucket) = ranking_rules[cur_ranking_rule_index].next_bucket(
-            ctx,
-            logger,
-            &ranking_rule_universes[cur_ranking_rule_index],
+        let Some(next_bucket) = timed!(
+            cur_ranking_rule_index,
+            ranking_rules[cur_ranking_rule_index].next_bucket(
+                ctx,
+                logger,
+                &ranking_rule_universes[cur_ranking_rule_index],
+            )
         )?
         else {
             back!();
@@ -266,14 +295,18 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             &next_bucket.query,
             &ranking_rule_universes[cur_ranking_rule_index],
         );
-        ranking_rules[cur_ranking_rule_index].start_iteration(
-            ctx,
-            logger,
-            &next_bucket.candidates,
-            &next_bucket.query,
+        timed!(
+            cur_ranking_rule_index,
+            ranking_rules[cur_ranking_rule_index].start_iteration(
+                ctx,
+                logger,
+                &next_bucket.candidates,
+                &next_bucket.query,
+            )
         )?;
     }
 
+    report_durations!();
     Ok(BucketSortOutput {
         docids: valid_docids,
         scores: valid_scores,
@@ -5,14 +5,16 @@ use heed::types::{Bytes, Unit};
 use heed::{RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde_json::Value;
 
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, InternalError, Result,
+    SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -79,7 +81,7 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     strategy: Strategy,
     ascending: bool,
     point: [f64; 2],
-    field_ids: Option<[u16; 2]>,
+    field_ids: Option<[u16; 3]>,
     rtree: Option<RTree<GeoPoint>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
@@ -129,6 +131,10 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         } else {
             None
         };
+        // The rtree returns the farthest point of the documents with several geo points first
+        // when sorting in descending order, they must be sorted by their closest point.
+        let rtree = rtree
+            .filter(|rtree| self.ascending || rtree.size() as u64 <= self.geo_candidates.len());
 
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
@@ -157,11 +163,23 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             }
         } else {
             // the iterative version
-            let [lat, lng] = self.field_ids.unwrap();
+            let [lat, lng, geo] = self.field_ids.unwrap();
 
             let mut documents = geo_candidates
                 .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
+                .map(|id| -> Result<_> {
+                    let points = geo_values(id, lat, lng, geo, ctx.index, ctx.txn)?;
+                    // the documents with several geo points are sorted by their closest one
+                    let closest = points
+                        .into_iter()
+                        .min_by(|a, b| {
+                            let a = distance_between_two_points(&self.point, a);
+                            let b = distance_between_two_points(&self.point, b);
+                            a.total_cmp(&b)
+                        })
+                        .expect("A geo faceted document doesn't contain any geo point");
+                    Ok((id, closest))
+                })
                 .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
             // computing the distance between two points is expensive thus we cache the result
             documents
@@ -177,31 +195,69 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
 ///
 /// If it is not able to find it in the facet number index it will extract it
 /// from the facet string index and parse it as f64 (as the geo extraction behaves).
-fn geo_value(
+/// The lat and long facet values of a document with several geo points are not paired,
+/// the points are then extracted from the `_geo` field of the document.
+fn geo_values(
     docid: u32,
     field_lat: u16,
     field_lng: u16,
+    field_geo: u16,
     index: &Index,
     rtxn: &RoTxn<'_>,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
-            Some(Err(e)) => Err(e.into()),
-            None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
-                Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
-                }
-                Some(Err(e)) => Err(e.into()),
-                None => panic!("A geo faceted document doesn't contain any lat or lng"),
-            },
+) -> Result<Vec<[f64; 2]>> {
+    let extract_geo = |geo_field: u16| -> Result<Vec<f64>> {
+        let values = facet_number_values(docid, geo_field, index, rtxn)?
+            .map(|result| result.map(|((_, _, geo), ())| geo))
+            .collect::<heed::Result<Vec<_>>>()?;
+        if !values.is_empty() {
+            return Ok(values);
         }
+
+        let values = facet_string_values(docid, geo_field, index, rtxn)?
+            .map(|result| {
+                result.map(|(_, geo)| geo.parse::<f64>().expect("cannot parse geo field as f64"))
+            })
+            .collect::<heed::Result<Vec<_>>>()?;
+        Ok(values)
     };
 
     let lat = extract_geo(field_lat)?;
     let lng = extract_geo(field_lng)?;
 
-    Ok([lat, lng])
+    match (lat.as_slice(), lng.as_slice()) {
+        ([lat], [lng]) => Ok(vec![[*lat, *lng]]),
+        ([], _) | (_, []) => panic!("A geo faceted document doesn't contain any lat or lng"),
+        _ => document_geo_points(docid, field_geo, index, rtxn),
+    }
+}
+
+/// Extracts all the geo points of a document from its `_geo` field.
+fn document_geo_points(
+    docid: u32,
+    field_geo: u16,
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+) -> Result<Vec<[f64; 2]>> {
+    let Some(geo) = index.document(rtxn, docid)?.get(field_geo) else {
+        return Ok(Vec::new());
+    };
+
+    let extract_geo = |value: &Value| match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+
+    let points = match serde_json::from_slice(geo).map_err(InternalError::SerdeJson)? {
+        Value::Array(points) => points,
+        point => vec![point],
+    };
+
+    Ok(points
+        .iter()
+        .filter_map(|point| extract_geo(&point["lat"]).zip(extract_geo(&point["lng"])))
+        .map(|(lat, lng)| [lat, lng])
+        .collect())
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
@@ -230,7 +286,8 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
         let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
         let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
         let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
-        self.field_ids = Some([lat, lng]);
+        let geo = fid_map.id(RESERVED_GEO_FIELD_NAME).expect("geo candidates but no fid for geo");
+        self.field_ids = Some([lat, lng, geo]);
         self.fill_buffer(ctx, &geo_candidates)?;
         Ok(())
     }
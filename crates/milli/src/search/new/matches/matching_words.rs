@@ -7,17 +7,23 @@ use charabia::Token;
 use super::super::interner::Interned;
 use super::super::query_term::LocatedQueryTerm;
 use super::super::{DedupInterner, Phrase};
+use super::MatchKind;
 use crate::SearchContext;
 
 pub struct LocatedMatchingPhrase {
     pub value: Interned<Phrase>,
     pub positions: RangeInclusive<WordId>,
+    pub original: String,
+    pub kind: MatchKind,
 }
 
 pub struct LocatedMatchingWords {
     pub value: Vec<Interned<String>>,
+    // the way each word of `value` derives from the original word.
+    pub kinds: Vec<MatchKind>,
     pub positions: RangeInclusive<WordId>,
     pub is_prefix: bool,
+    pub original: String,
     pub original_char_count: usize,
 }
 
@@ -41,19 +47,24 @@ impl MatchingWords {
         for located_term in located_terms {
             let term = ctx.term_interner.get(located_term.value);
             let (matching_words, matching_phrases) = term.all_computed_derivations();
+            let original = term.original_word(&ctx);
 
             for matching_phrase in matching_phrases {
                 phrases.push(LocatedMatchingPhrase {
                     value: matching_phrase,
                     positions: located_term.positions.clone(),
+                    original: original.clone(),
+                    kind: term.phrase_match_kind(matching_phrase),
                 });
             }
 
             words.push(LocatedMatchingWords {
+                kinds: matching_words.iter().map(|word| term.word_match_kind(*word)).collect(),
                 value: matching_words,
                 positions: located_term.positions.clone(),
                 is_prefix: term.is_prefix(),
-                original_char_count: term.original_word(&ctx).chars().count(),
+                original_char_count: original.chars().count(),
+                original,
             });
         }
 
@@ -103,6 +114,41 @@ impl MatchingWords {
 
         None
     }
+
+    /// Returns the query word located at the given positions that matched the given lemmas,
+    /// along with the way the lemmas derive from it.
+    pub(super) fn match_origin(
+        &self,
+        ids: &RangeInclusive<WordId>,
+        lemmas: &[&str],
+    ) -> Option<(&str, MatchKind)> {
+        // phrases are matched first, the same way `match_token` does.
+        let phrase = self.phrases.iter().filter(|lmp| &lmp.positions == ids).find(|lmp| {
+            let phrase = self.phrase_interner.get(lmp.value);
+            phrase.words.len() == lemmas.len()
+                && phrase.words.iter().zip(lemmas).all(|(word, lemma)| match word {
+                    Some(word) => self.word_interner.get(*word) == *lemma,
+                    None => true,
+                })
+        });
+        if let Some(located_phrase) = phrase {
+            return Some((&located_phrase.original, located_phrase.kind));
+        }
+
+        let [lemma] = lemmas else { return None };
+        for located_words in self.words.iter().filter(|lmw| &lmw.positions == ids) {
+            for (word, kind) in located_words.value.iter().zip(&located_words.kinds) {
+                let word = self.word_interner.get(*word);
+                if *lemma == word.as_str() {
+                    return Some((&located_words.original, *kind));
+                } else if located_words.is_prefix && lemma.starts_with(word.as_str()) {
+                    return Some((&located_words.original, MatchKind::Prefix));
+                }
+            }
+        }
+
+        None
+    }
 }
 
 /// Iterator over terms that match the given token,
@@ -14,17 +14,31 @@ use matching_words::{MatchType, PartialMatch};
 use r#match::{Match, MatchPosition};
 use serde::{Deserialize, Serialize};
 use simple_token_kind::SimpleTokenKind;
+use unicode_segmentation::UnicodeSegmentation;
 use utoipa::ToSchema;
 
 const DEFAULT_CROP_MARKER: &str = "…";
 const DEFAULT_HIGHLIGHT_PREFIX: &str = "<em>";
 const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
+/// The characters ending a sentence, a crop using [`CropStrategy::Sentence`] never cuts a sentence.
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?', '\n', '。', '！', '？'];
+
+/// How the bounds of a crop window are chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CropStrategy {
+    /// Keep exactly the crop length in words around the best matches.
+    #[default]
+    Words,
+    /// Extend the crop window to the start and end of the sentences it overlaps.
+    Sentence,
+}
 
 /// Structure used to build a Matcher allowing to customize formatting tags.
 pub struct MatcherBuilder<'m> {
     matching_words: MatchingWords,
     tokenizer: Tokenizer<'m>,
     crop_marker: Option<String>,
+    crop_strategy: CropStrategy,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
 }
@@ -35,6 +49,7 @@ impl<'m> MatcherBuilder<'m> {
             matching_words,
             tokenizer,
             crop_marker: None,
+            crop_strategy: CropStrategy::default(),
             highlight_prefix: None,
             highlight_suffix: None,
         }
@@ -45,6 +60,11 @@ impl<'m> MatcherBuilder<'m> {
         self
     }
 
+    pub fn crop_strategy(&mut self, strategy: CropStrategy) -> &Self {
+        self.crop_strategy = strategy;
+        self
+    }
+
     pub fn highlight_prefix(&mut self, prefix: String) -> &Self {
         self.highlight_prefix = Some(prefix);
         self
@@ -78,6 +98,7 @@ impl<'m> MatcherBuilder<'m> {
             matching_words: &self.matching_words,
             tokenizer: &self.tokenizer,
             crop_marker,
+            crop_strategy: self.crop_strategy,
             highlight_prefix,
             highlight_suffix,
             matches: None,
@@ -108,6 +129,51 @@ pub struct MatchBounds {
     pub length: usize,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub indices: Option<Vec<usize>>,
+    /// The start of the match counted in grapheme clusters instead of bytes.
+    #[serde(rename = "graphemeStart", skip_serializing_if = "Option::is_none", default)]
+    pub grapheme_start: Option<usize>,
+    /// The length of the match counted in grapheme clusters instead of bytes.
+    #[serde(rename = "graphemeLength", skip_serializing_if = "Option::is_none", default)]
+    pub grapheme_length: Option<usize>,
+}
+
+impl MatchBounds {
+    /// Computes the bounds of the match in grapheme clusters of the text it was found in.
+    pub fn with_grapheme_offsets(self, text: &str) -> Self {
+        let count = |s: Option<&str>| s.map_or(0, |s| s.graphemes(true).count());
+        let grapheme_start = count(text.get(..self.start));
+        let grapheme_length = count(text.get(self.start..self.start + self.length));
+        Self {
+            grapheme_start: Some(grapheme_start),
+            grapheme_length: Some(grapheme_length),
+            ..self
+        }
+    }
+}
+
+/// The way a word of a document derives from the query word it matched.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchKind {
+    /// The word is the query word itself.
+    Exact,
+    /// The word is at most two typos away from the query word.
+    Typo,
+    /// The word starts with the query word.
+    Prefix,
+    /// The word is a synonym of the query word.
+    Synonym,
+    /// The words are the query word split in two.
+    Split,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct MatchedTerm {
+    /// The query word, as written by the end user.
+    pub term: String,
+    /// The word of the document that matched the query word.
+    pub word: String,
+    pub kind: MatchKind,
 }
 
 /// Structure used to analyze a string, compute words that match,
@@ -118,12 +184,31 @@ pub struct Matcher<'t, 'tokenizer, 'b, 'lang> {
     tokenizer: &'b Tokenizer<'tokenizer>,
     locales: Option<&'lang [Language]>,
     crop_marker: &'b str,
+    crop_strategy: CropStrategy,
     highlight_prefix: &'b str,
     highlight_suffix: &'b str,
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
 }
 
-impl<'t, 'tokenizer> Matcher<'t, 'tokenizer, '_, '_> {
+impl<'t, 'tokenizer, 'b> Matcher<'t, 'tokenizer, 'b, '_> {
+    /// Overrides the crop marker of the builder for this text.
+    pub fn crop_marker(&mut self, marker: &'b str) -> &mut Self {
+        self.crop_marker = marker;
+        self
+    }
+
+    /// Overrides the highlight prefix of the builder for this text.
+    pub fn highlight_prefix(&mut self, prefix: &'b str) -> &mut Self {
+        self.highlight_prefix = prefix;
+        self
+    }
+
+    /// Overrides the highlight suffix of the builder for this text.
+    pub fn highlight_suffix(&mut self, suffix: &'b str) -> &mut Self {
+        self.highlight_suffix = suffix;
+        self
+    }
+
     /// Iterates over tokens and save any of them that matches the query.
     fn compute_matches(&mut self) -> &mut Self {
         /// some words are counted as matches only if they are close together and in the good order,
@@ -236,11 +321,46 @@ impl<'t, 'tokenizer> Matcher<'t, 'tokenizer, '_, '_> {
                     } else {
                         Some(array_indices.to_owned())
                     },
+                    grapheme_start: None,
+                    grapheme_length: None,
                 })
                 .collect(),
         }
     }
 
+    /// Returns the query words that matched the text, along with the matched words of the text.
+    pub fn matched_terms(&mut self) -> Vec<MatchedTerm> {
+        match &self.matches {
+            None => self.compute_matches().matched_terms(),
+            Some((tokens, matches)) => {
+                let mut terms = Vec::new();
+                for m in matches {
+                    let (Some(&first_id), Some(&last_id)) = (m.ids.first(), m.ids.last()) else {
+                        continue;
+                    };
+                    let tokens = &tokens[m.get_first_token_pos()..=m.get_last_token_pos()];
+                    let lemmas: Vec<_> =
+                        tokens.iter().filter(|t| !t.is_separator()).map(|t| t.lemma()).collect();
+                    let Some((term, kind)) =
+                        self.matching_words.match_origin(&(first_id..=last_id), &lemmas)
+                    else {
+                        continue;
+                    };
+                    let (start, end) = (tokens[0].byte_start, tokens[tokens.len() - 1].byte_end);
+                    let matched_term = MatchedTerm {
+                        term: term.to_owned(),
+                        word: self.text[start..end].to_owned(),
+                        kind,
+                    };
+                    if !terms.contains(&matched_term) {
+                        terms.push(matched_term);
+                    }
+                }
+                terms
+            }
+        }
+    }
+
     fn calc_byte_length(&self, tokens: &[Token<'t>], m: &Match) -> usize {
         (m.get_first_token_pos()..=m.get_last_token_pos())
             .flat_map(|i| match &tokens[i].char_map {
@@ -389,7 +509,46 @@ impl<'t, 'tokenizer> Matcher<'t, 'tokenizer, '_, '_> {
         let crop_byte_start = before_tokens.next().map_or(0, |t| t.byte_end);
         let crop_byte_end = after_tokens.next().map_or(self.text.len(), |t| t.byte_start);
 
-        [crop_byte_start, crop_byte_end]
+        match self.crop_strategy {
+            CropStrategy::Words => [crop_byte_start, crop_byte_end],
+            CropStrategy::Sentence => self.sentence_bounds(tokens, crop_byte_start, crop_byte_end),
+        }
+    }
+
+    /// Extends the crop window bounds to the start of its first sentence and the end of its last one.
+    fn sentence_bounds(
+        &self,
+        tokens: &[Token<'_>],
+        crop_byte_start: usize,
+        crop_byte_end: usize,
+    ) -> [usize; 2] {
+        let ends_sentence = |token: &Token<'_>| {
+            token.is_separator()
+                && self.text[token.byte_start..token.byte_end].contains(SENTENCE_TERMINATORS)
+        };
+
+        // the sentence starts at the first word following the previous sentence terminator.
+        let sentence_start = tokens
+            .iter()
+            .rposition(|token| token.byte_end <= crop_byte_start && ends_sentence(token))
+            .map_or(0, |position| position + 1);
+        let sentence_byte_start = tokens[sentence_start..]
+            .iter()
+            .find(|token| !token.is_separator())
+            .map_or(crop_byte_start, |token| token.byte_start.min(crop_byte_start));
+
+        // the sentence ends with the first sentence terminator of the end of the window or following it.
+        let sentence_byte_end = match tokens.iter().rev().find(|t| t.byte_end <= crop_byte_end) {
+            Some(last) if ends_sentence(last) => crop_byte_end,
+            _ => tokens
+                .iter()
+                .find(|token| token.byte_start >= crop_byte_end && ends_sentence(token))
+                .map_or(self.text.len(), |token| token.byte_end),
+        };
+        let sentence_byte_end = sentence_byte_start
+            + self.text[sentence_byte_start..sentence_byte_end].trim_end().len();
+
+        [sentence_byte_start, sentence_byte_end]
     }
 
     // Returns the formatted version of the original text.
@@ -522,6 +681,8 @@ mod tests {
                 TimeBudget::max(),
                 None,
                 None,
+                None,
+                false,
             )
             .unwrap();
 
@@ -890,6 +1051,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_crop_sentence() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split the world");
+        builder.crop_strategy(CropStrategy::Sentence);
+
+        let format_options = FormatOptions { highlight: false, crop: Some(10) };
+
+        // Text without any match.
+        let text = "A quick brown fox can not jump 32 feet, right? Brr, it is cold!";
+        let mut matcher = builder.build(text, None);
+        // the crop is extended to the end of the first sentence.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"A quick brown fox can not jump 32 feet, right?…"
+        );
+
+        // Text containing a match in the middle of the text.
+        let text = "Natalie risk her future. Split The World is a book written by Emily Henry. I never read it.";
+        let mut matcher = builder.build(text, None);
+        // the crop contains the whole sentence of the match.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"…Split The World is a book written by Emily Henry.…"
+        );
+
+        // set a smaller crop size
+        let format_options = FormatOptions { highlight: true, crop: Some(2) };
+        let mut matcher = builder.build(text, None);
+        // the sentence is kept whole even if it is longer than the crop size.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"…<em>Split</em> <em>The</em> <em>World</em> is a book written by Emily Henry.…"
+        );
+    }
+
+    #[test]
+    fn matched_terms() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "westfali worlds");
+
+        let text = "Westfália westfali the world";
+        let mut matcher = builder.build(text, None);
+        let terms: Vec<_> =
+            matcher.matched_terms().into_iter().map(|t| (t.term, t.word, t.kind)).collect();
+        assert_eq!(
+            terms,
+            vec![
+                ("westfali".to_string(), "Westfália".to_string(), MatchKind::Typo),
+                ("westfali".to_string(), "westfali".to_string(), MatchKind::Exact),
+                ("worlds".to_string(), "world".to_string(), MatchKind::Typo),
+            ]
+        );
+
+        // Text without any match.
+        let text = "A quick brown fox can not jump 32 feet, right? Brr, it is cold!";
+        let mut matcher = builder.build(text, None);
+        assert!(matcher.matched_terms().is_empty());
+    }
+
+    #[test]
+    fn grapheme_offsets() {
+        let text = "👩‍👩‍👧 Family café";
+        let bounds = MatchBounds {
+            start: 26,
+            length: 5,
+            indices: None,
+            grapheme_start: None,
+            grapheme_length: None,
+        };
+        let bounds = bounds.with_grapheme_offsets(text);
+        assert_eq!((bounds.grapheme_start, bounds.grapheme_length), (Some(9), Some(4)));
+    }
+
     #[test]
     fn smaller_crop_size() {
         //! testing: https://github.com/meilisearch/specifications/pull/120#discussion_r836536295
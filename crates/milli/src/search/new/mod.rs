@@ -12,6 +12,7 @@ mod query_term;
 mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
+mod sensitive_matching;
 mod small_bitmap;
 
 mod exact_attribute;
@@ -21,10 +22,11 @@ mod vector_sort;
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
+use std::time::Instant;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::{Language, TokenizerBuilder};
+use charabia::{Language, Normalize, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
@@ -41,14 +43,17 @@ use ranking_rules::{
 };
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
+use sensitive_matching::{resolve_sensitive_mismatches, sensitive_words};
 use sort::Sort;
 
+pub use self::distinct::distinct_single_docid;
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
+use crate::attribute_patterns::{match_pattern, PatternMatch};
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::index::PrefixSearch;
 use crate::localized_attributes_rules::LocalizedFieldIds;
@@ -56,8 +61,8 @@ use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, SearchTimings,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -71,6 +76,7 @@ pub struct SearchContext<'ctx> {
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
+    pub timings: SearchTimings,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -100,6 +106,7 @@ impl<'ctx> SearchContext<'ctx> {
             phrase_docids: <_>::default(),
             restricted_fids: None,
             prefix_search,
+            timings: SearchTimings::default(),
         })
     }
 
@@ -124,35 +131,54 @@ impl<'ctx> SearchContext<'ctx> {
                 // we cannot early exit as we want to returns error in case of unknown fields
                 continue;
             }
-            let searchable_weight =
-                searchable_fields_weights.iter().find(|(name, _, _)| name == field_name);
-            let (fid, weight) = match searchable_weight {
-                // The Field id exist and the field is searchable
-                Some((_name, fid, weight)) => (*fid, *weight),
+            // A pattern such as `metadata.*` or `*_title` selects every searchable field it matches
+            let searchable_weights: Vec<_> = if field_name.contains('*') {
+                searchable_fields_weights
+                    .iter()
+                    .filter(|(name, _, _)| match_pattern(field_name, name) == PatternMatch::Match)
+                    .map(|(_name, fid, weight)| (*fid, *weight))
+                    .collect()
+            } else {
+                searchable_fields_weights
+                    .iter()
+                    .find(|(name, _, _)| name == field_name)
+                    .map(|(_name, fid, weight)| (*fid, *weight))
+                    .into_iter()
+                    .collect()
+            };
+
+            if searchable_weights.is_empty() {
                 // The field is not searchable but the user didn't define any searchable attributes
-                None if user_defined_searchable.is_none() => continue,
+                if user_defined_searchable.is_none() {
+                    continue;
+                }
+
                 // The field is not searchable => User error
-                None => {
-                    let (valid_fields, hidden_fields) = self.index.remove_hidden_fields(
-                        self.txn,
-                        searchable_fields_weights.iter().map(|(name, _, _)| name),
-                    )?;
+                let (valid_fields, hidden_fields) = self.index.remove_hidden_fields(
+                    self.txn,
+                    searchable_fields_weights.iter().map(|(name, _, _)| name),
+                )?;
 
-                    let field = field_name.to_string();
-                    return Err(UserError::InvalidSearchableAttribute {
-                        field,
-                        valid_fields,
-                        hidden_fields,
-                    }
-                    .into());
+                let field = field_name.to_string();
+                return Err(UserError::InvalidSearchableAttribute {
+                    field,
+                    valid_fields,
+                    hidden_fields,
                 }
-            };
+                .into());
+            }
 
-            if exact_attributes_ids.contains(&fid) {
-                restricted_fids.exact.push((fid, weight));
-            } else {
-                restricted_fids.tolerant.push((fid, weight));
-            };
+            for (fid, weight) in searchable_weights {
+                let fids = if exact_attributes_ids.contains(&fid) {
+                    &mut restricted_fids.exact
+                } else {
+                    &mut restricted_fids.tolerant
+                };
+                // several patterns may select the same field
+                if !fids.contains(&(fid, weight)) {
+                    fids.push((fid, weight));
+                }
+            }
         }
 
         if wildcard {
@@ -332,6 +358,7 @@ fn get_ranking_rules_for_vector<'ctx>(
     embedder_name: &str,
     embedder: &Embedder,
     quantized: bool,
+    search_accuracy: Option<f32>,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     // query graph search
 
@@ -360,6 +387,7 @@ fn get_ranking_rules_for_vector<'ctx>(
                         embedder_name,
                         embedder,
                         quantized,
+                        search_accuracy,
                     )?;
                     ranking_rules.push(Box::new(vector_sort));
                     vector = true;
@@ -592,7 +620,9 @@ pub fn execute_vector_search(
     quantized: bool,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    search_accuracy: Option<f32>,
 ) -> Result<PartialSearchResult> {
+    let before_vector_search = Instant::now();
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
     // FIXME: input universe = universe & documents_with_vectors
@@ -606,6 +636,7 @@ pub fn execute_vector_search(
         embedder_name,
         embedder,
         quantized,
+        search_accuracy,
     )?;
 
     let mut placeholder_search_logger = logger::DefaultSearchLogger;
@@ -625,6 +656,7 @@ pub fn execute_vector_search(
         time_budget,
         ranking_score_threshold,
     )?;
+    ctx.timings.vector_search += before_vector_search.elapsed();
 
     Ok(PartialSearchResult {
         candidates: all_candidates,
@@ -636,6 +668,27 @@ pub fn execute_vector_search(
     })
 }
 
+/// Builds a set containing both the stop words of the index and the terms to ignore for a single search.
+///
+/// The terms to ignore are normalized the same way the stop words are when they are set in the settings.
+fn merge_stop_words_and_ignore_terms(
+    stop_words: Option<&fst::Set<&[u8]>>,
+    ignore_terms: &[String],
+) -> Result<fst::Set<Vec<u8>>> {
+    let mut words = normalize_ignore_terms(ignore_terms);
+
+    if let Some(stop_words) = stop_words {
+        words.extend(stop_words.stream().into_strs()?);
+    }
+
+    Ok(fst::Set::from_iter(words)?)
+}
+
+/// Normalizes the terms to ignore the way the stop words are when they are set in the settings.
+fn normalize_ignore_terms(ignore_terms: &[String]) -> BTreeSet<String> {
+    ignore_terms.iter().map(|w| w.as_str().normalize(&Default::default()).into_owned()).collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "search::main")]
 pub fn execute_search(
@@ -656,12 +709,16 @@ pub fn execute_search(
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<&Vec<Language>>,
+    ignore_terms: Option<&[String]>,
+    sensitive_matching: bool,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
+    let mut sensitive_query_words = None;
     let query_terms = if let Some(query) = query {
+        let before_tokenization = Instant::now();
         let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
         let entered = span.enter();
 
@@ -669,8 +726,20 @@ pub fn execute_search(
         // this ensures that the query builder is able to properly remove them.
         let mut tokbuilder = TokenizerBuilder::new();
         let stop_words = ctx.index.stop_words(ctx.txn)?;
-        if let Some(ref stop_words) = stop_words {
-            tokbuilder.stop_words(stop_words);
+        let stop_words_with_ignored_terms;
+        match ignore_terms {
+            // The ignored terms are handled exactly like stop words,
+            // they are removed from the query before building the query graph.
+            Some(ignore_terms) if !ignore_terms.is_empty() => {
+                stop_words_with_ignored_terms =
+                    merge_stop_words_and_ignore_terms(stop_words.as_ref(), ignore_terms)?;
+                tokbuilder.stop_words(&stop_words_with_ignored_terms);
+            }
+            _ => {
+                if let Some(ref stop_words) = stop_words {
+                    tokbuilder.stop_words(stop_words);
+                }
+            }
         }
 
         let separators = ctx.index.allowed_separators(ctx.txn)?;
@@ -719,7 +788,11 @@ pub fn execute_search(
                     ),
                 };
 
-                db_locales = localized_fields.all_locales();
+                db_locales = match localized_fields.all_locales() {
+                    // Otherwise fall back on the locales detected in the documents, if stored
+                    locales if locales.is_empty() => ctx.index.detected_locales(ctx.txn)?,
+                    locales => locales,
+                };
                 if !db_locales.is_empty() {
                     tokbuilder.allow_list(&db_locales);
                 }
@@ -734,16 +807,37 @@ pub fn execute_search(
         let tokens = tokenizer.tokenize(query);
         drop(entered);
 
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
+        let ExtractedTokens { mut query_terms, negative_words, negative_phrases } =
             located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+        // The last word of the query is kept even if it is a stop word, as it may be the prefix
+        // of a longer word, but an ignored term must not be searched at all.
+        if let Some(ignore_terms) = ignore_terms.filter(|ignore_terms| !ignore_terms.is_empty()) {
+            if let Some(last) = query_terms.last() {
+                let term = ctx.term_interner.get(last.value);
+                if term.original_phrase().is_none()
+                    && normalize_ignore_terms(ignore_terms).contains(&term.original_word(ctx))
+                {
+                    query_terms.pop();
+                }
+            }
+        }
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
+        ctx.timings.tokenization += before_tokenization.elapsed();
 
+        let before_candidates = Instant::now();
         let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
         let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
 
         universe -= ignored_documents;
         universe -= ignored_phrases;
 
+        ctx.timings.candidates += before_candidates.elapsed();
+
+        if sensitive_matching {
+            sensitive_query_words =
+                Some(sensitive_words(&tokenizer, query, ctx.is_prefix_search_allowed()));
+        }
+
         if query_terms.is_empty() {
             // Do a placeholder search instead
             None
@@ -754,51 +848,85 @@ pub fn execute_search(
         None
     };
 
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
+    let graph = if let Some(query_terms) = query_terms {
         let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
         located_query_terms = Some(new_located_query_terms);
 
-        let ranking_rules = get_ranking_rules_for_query_graph_search(
-            ctx,
-            sort_criteria,
-            geo_strategy,
-            terms_matching_strategy,
-        )?;
-
+        let before_candidates = Instant::now();
         universe &=
             resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
-
-        bucket_sort(
-            ctx,
-            ranking_rules,
-            &graph,
-            distinct.as_deref(),
-            &universe,
-            from,
-            length,
-            scoring_strategy,
-            query_graph_logger,
-            time_budget,
-            ranking_score_threshold,
-        )?
+        ctx.timings.candidates += before_candidates.elapsed();
+        Some(graph)
     } else {
-        let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
-        bucket_sort(
-            ctx,
-            ranking_rules,
-            &PlaceholderQuery,
-            distinct.as_deref(),
-            &universe,
-            from,
-            length,
-            scoring_strategy,
-            placeholder_search_logger,
-            time_budget,
-            ranking_score_threshold,
-        )?
+        None
     };
 
+    // The words are only matched once normalized, so the documents of the page in which a query
+    // word is written with another case or other diacritics are removed from the universe, and
+    // the page is sorted again until all its documents match the query words as they are written.
+    // The documents skipped by the offset are checked too, for the pages to stay consistent.
+    let (sort_from, sort_length) = match sensitive_query_words {
+        Some(_) => (0, from.saturating_add(length)),
+        None => (from, length),
+    };
+    let mut sensitive_checked = RoaringBitmap::new();
+    let mut bucket_sort_output = loop {
+        let bucket_sort_output = if let Some(graph) = &graph {
+            let ranking_rules = get_ranking_rules_for_query_graph_search(
+                ctx,
+                sort_criteria,
+                geo_strategy,
+                terms_matching_strategy,
+            )?;
+            bucket_sort(
+                ctx,
+                ranking_rules,
+                graph,
+                distinct.as_deref(),
+                &universe,
+                sort_from,
+                sort_length,
+                scoring_strategy,
+                query_graph_logger,
+                time_budget.clone(),
+                ranking_score_threshold,
+            )?
+        } else {
+            let ranking_rules =
+                get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
+            bucket_sort(
+                ctx,
+                ranking_rules,
+                &PlaceholderQuery,
+                distinct.as_deref(),
+                &universe,
+                sort_from,
+                sort_length,
+                scoring_strategy,
+                placeholder_search_logger,
+                time_budget.clone(),
+                ranking_score_threshold,
+            )?
+        };
+
+        let Some(words) = &sensitive_query_words else { break bucket_sort_output };
+        let page: RoaringBitmap = bucket_sort_output.docids.iter().copied().collect();
+        let page = page - &sensitive_checked;
+        let before_candidates = Instant::now();
+        let mismatches = resolve_sensitive_mismatches(ctx, words, &page)?;
+        ctx.timings.candidates += before_candidates.elapsed();
+        sensitive_checked |= page;
+        if mismatches.is_empty() {
+            break bucket_sort_output;
+        }
+        universe -= mismatches;
+    };
+    if sort_from != from {
+        let skipped = from.min(bucket_sort_output.docids.len());
+        bucket_sort_output.docids.drain(..skipped);
+        bucket_sort_output.scores.drain(..skipped);
+    }
+
     let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
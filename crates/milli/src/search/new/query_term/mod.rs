@@ -15,6 +15,7 @@ pub use parse_query::{
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
+use super::matches::MatchKind;
 use super::{limits, SearchContext, Word};
 use crate::Result;
 
@@ -485,6 +486,28 @@ impl QueryTerm {
         self.zero_typo.phrase
     }
 
+    /// Returns the way the given word, one of the computed derivations of the term, derives from the original word.
+    pub fn word_match_kind(&self, word: Interned<String>) -> MatchKind {
+        if self.zero_typo.exact == Some(word) {
+            MatchKind::Exact
+        } else if self.zero_typo.prefix_of.contains(&word) {
+            MatchKind::Prefix
+        } else {
+            MatchKind::Typo
+        }
+    }
+
+    /// Returns the way the given phrase, one of the computed derivations of the term, derives from the original word.
+    pub fn phrase_match_kind(&self, phrase: Interned<Phrase>) -> MatchKind {
+        if self.zero_typo.phrase == Some(phrase) {
+            MatchKind::Exact
+        } else if self.zero_typo.synonyms.contains(&phrase) {
+            MatchKind::Synonym
+        } else {
+            MatchKind::Split
+        }
+    }
+
     pub fn all_computed_derivations(&self) -> (Vec<Interned<String>>, Vec<Interned<Phrase>>) {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
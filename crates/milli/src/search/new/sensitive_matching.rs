@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use charabia::normalizer::NormalizerOption;
+use charabia::{Normalize, Token, TokenKind, Tokenizer};
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::SearchContext;
+use crate::attribute_patterns::PatternMatch;
+use crate::update::new::extract::perm_json_p::{
+    seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
+};
+use crate::update::new::extract::tokenizer_builder;
+use crate::{InternalError, Result};
+
+/// A word of the query, as it is matched against the index and as it was written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SensitiveWord {
+    /// The word with its case folded and diacritics removed, as stored in the index.
+    lemma: String,
+    /// The word with its case and diacritics.
+    exact: String,
+    prefix: bool,
+}
+
+impl SensitiveWord {
+    fn matches_lemma(&self, lemma: &str) -> bool {
+        if self.prefix {
+            lemma.starts_with(&self.lemma)
+        } else {
+            lemma == self.lemma
+        }
+    }
+
+    fn matches_exact(&self, exact: &str) -> bool {
+        if self.prefix {
+            exact.starts_with(&self.exact)
+        } else {
+            exact == self.exact
+        }
+    }
+}
+
+/// Extracts the words of the query, the last one being a prefix if the query doesn't end with a separator.
+pub fn sensitive_words(
+    tokenizer: &Tokenizer<'_>,
+    query: &str,
+    allow_prefix_search: bool,
+) -> Vec<SensitiveWord> {
+    let mut words: Vec<_> = word_tokens(tokenizer, query)
+        .map(|(lemma, exact)| SensitiveWord { lemma, exact, prefix: false })
+        .collect();
+
+    let ends_with_word = tokenizer.tokenize(query).last().is_some_and(|token| is_word(&token));
+    if let Some(last) = words.last_mut().filter(|_| allow_prefix_search && ends_with_word) {
+        last.prefix = true;
+    }
+
+    words
+}
+
+/// Returns the documents, among the given ones, matching a query word only once its case
+/// is folded or its diacritics are removed.
+///
+/// The index only stores the normalized words, so the searchable attributes of the documents are
+/// tokenized again, like when they were indexed, to compare the words as they were written.
+/// It must only be called with the documents of the page being returned.
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+pub fn resolve_sensitive_mismatches(
+    ctx: &SearchContext<'_>,
+    words: &[SensitiveWord],
+    documents: &RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    let words: HashSet<_> = words.iter().collect();
+    if words.is_empty() || documents.is_empty() {
+        return Ok(RoaringBitmap::new());
+    }
+
+    let stop_words = ctx.index.stop_words(ctx.txn)?;
+    let separators = ctx.index.allowed_separators(ctx.txn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|s| s.iter().map(String::as_str).collect());
+    let dictionary = ctx.index.dictionary(ctx.txn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|s| s.iter().map(String::as_str).collect());
+    let mut builder =
+        tokenizer_builder(stop_words.as_ref(), separators.as_deref(), dictionary.as_deref());
+    let tokenizer = builder.build();
+
+    let searchable_fids = match &ctx.restricted_fids {
+        Some(restricted_fids) => restricted_fids
+            .tolerant
+            .iter()
+            .chain(&restricted_fids.exact)
+            .map(|(fid, _)| *fid)
+            .collect(),
+        None => ctx.index.searchable_fields_ids(ctx.txn)?,
+    };
+    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+    let flatten_options = ctx.index.flatten_options(ctx.txn)?;
+
+    let mut mismatches = RoaringBitmap::new();
+    for result in ctx.index.iter_documents(ctx.txn, documents)? {
+        let (docid, document) = result?;
+
+        // the lemmas of the document words that match a query word, with the way they were written
+        let mut matched: Vec<(&SensitiveWord, String)> = Vec::new();
+        let mut tokenize_field = |field_name: &str, _depth, value: &Value| {
+            let is_searchable =
+                fields_ids_map.id(field_name).is_some_and(|fid| searchable_fids.contains(&fid));
+            let text = match value {
+                Value::String(text) if is_searchable => text.clone(),
+                Value::Number(number) if is_searchable => number.to_string(),
+                Value::Bool(boolean) if is_searchable => boolean.to_string(),
+                _ => return Ok(PatternMatch::Match),
+            };
+            for (lemma, exact) in word_tokens(&tokenizer, &text) {
+                for word in words.iter().filter(|word| word.matches_lemma(&lemma)) {
+                    matched.push((*word, exact.clone()));
+                }
+            }
+            Ok(PatternMatch::Match)
+        };
+
+        for (fid, value) in document.iter() {
+            let Some(field_name) = fields_ids_map.name(fid) else { continue };
+            match serde_json::from_slice(value).map_err(InternalError::SerdeJson)? {
+                Value::Object(object) => seek_leaf_values_in_object(
+                    &object,
+                    field_name,
+                    Depth::OnBaseKey,
+                    &flatten_options,
+                    &mut tokenize_field,
+                )?,
+                Value::Array(array) => seek_leaf_values_in_array(
+                    &array,
+                    field_name,
+                    Depth::OnBaseKey,
+                    &flatten_options,
+                    &mut tokenize_field,
+                )?,
+                value => {
+                    tokenize_field(field_name, Depth::OnBaseKey, &value)?;
+                }
+            }
+        }
+
+        let is_mismatch = words.iter().any(|word| {
+            let mut matched = matched.iter().filter(|(w, _)| w == word).peekable();
+            matched.peek().is_some() && !matched.any(|(_, exact)| word.matches_exact(exact))
+        });
+        if is_mismatch {
+            mismatches.insert(docid);
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Returns the lemma of every word of the text along with the word normalized without loss,
+/// keeping its case and diacritics.
+fn word_tokens<'t>(
+    tokenizer: &'t Tokenizer<'_>,
+    text: &'t str,
+) -> impl Iterator<Item = (String, String)> + 't {
+    let options = NormalizerOption { lossy: false, ..Default::default() };
+    tokenizer.tokenize(text).filter(|token| is_word(token)).map(move |token| {
+        let exact = text[token.byte_start..token.byte_end].normalize(&options);
+        (token.lemma().to_string(), exact.trim().to_string())
+    })
+}
+
+fn is_word(token: &Token<'_>) -> bool {
+    matches!(token.kind, TokenKind::Word | TokenKind::StopWord) && !token.lemma().is_empty()
+}
@@ -11,6 +11,7 @@ pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod sensitive_matching;
 pub mod sort;
 pub mod stop_words;
 pub mod typo;
@@ -0,0 +1,106 @@
+/*!
+This module tests the case- and diacritic-sensitive matching of the query words:
+
+1. a document only matching a query word once its case is folded or its diacritics
+are removed is not returned
+2. the last word of the query is matched as a prefix with its case and diacritics
+3. the `sensitiveMatching` setting of the index is the default of the search
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult};
+
+/// Returns the sorted ids of the matching documents, the ranking is not tested here.
+fn search(index: &TempIndex, query: &str, sensitive_matching: Option<bool>) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    if let Some(sensitive_matching) = sensitive_matching {
+        s.sensitive_matching(sensitive_matching);
+    }
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "tags".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the HashMap type", "tags": [] },
+            { "id": 1, "title": "a hashmap of strings", "tags": [] },
+            { "id": 2, "title": "a résumé template", "tags": [] },
+            { "id": 3, "title": "a resume button", "tags": ["Résumé"] },
+            { "id": 4, "title": "HASHMAP and hashmap", "tags": [] },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_sensitive_matching() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hashmap ", None)), @"[0, 1, 4]");
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "HashMap ", Some(true))), @"[0]");
+
+    // a document is returned as long as one of its words is written like the query word
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hashmap ", Some(true))), @"[1, 4]");
+
+    // the diacritics are kept, the values of the arrays are matched too
+    insta::assert_snapshot!(format!("{:?}", search(&index, "Résumé ", Some(true))), @"[3]");
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "résumé ", Some(true))), @"[2]");
+}
+
+#[test]
+fn test_sensitive_matching_prefix() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "Hash", Some(true))), @"[0]");
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "HASH", Some(true))), @"[4]");
+}
+
+#[test]
+fn test_sensitive_matching_setting() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_sensitive_matching(true);
+        })
+        .unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "HashMap ", None)), @"[0]");
+
+    // the search can still opt out
+    insta::assert_snapshot!(format!("{:?}", search(&index, "HashMap ", Some(false))), @"[0, 1, 4]");
+}
+
+#[test]
+fn test_sensitive_matching_pagination() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // only the documents of the page are checked, the mismatching ones are replaced by the next ones
+    let mut s = Search::new(&txn, &index);
+    s.query("HashMap ");
+    s.sensitive_matching(true);
+    s.limit(1);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    s.offset(1);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
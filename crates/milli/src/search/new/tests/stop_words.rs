@@ -7,6 +7,7 @@ This module tests the following properties about stop words:
 - If a query consists only of stop words, a placeholder query is used instead
 - A prefix word is never ignored, even if the prefix is a stop word
 - Phrases consisting only of stop words are ignored
+- Terms can be ignored for a single search, on top of the stop words
 */
 
 use std::collections::BTreeSet;
@@ -459,3 +460,54 @@ fn test_stop_words_in_phrase() {
     // As a result the scores are empty lists
     insta::assert_snapshot!(format!("{document_scores:#?}"));
 }
+
+#[test]
+fn test_ignore_terms() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+
+    // `shazam` is not a stop word, all the words are mandatory so nothing matches
+    let mut s = Search::new(&txn, &index);
+    s.query("captain shazam ");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    // `shazam` is ignored for this search only, the ignored terms are normalized
+    let mut s = Search::new(&txn, &index);
+    s.query("captain shazam ");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.ignore_terms(vec!["Shazam".to_owned()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    // the stop words of the index are still applied along with the ignored terms
+    let mut s = Search::new(&txn, &index);
+    s.query("the captain shazam ");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.ignore_terms(vec!["shazam".to_owned()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    // a query made only of ignored terms is a placeholder search
+    let mut s = Search::new(&txn, &index);
+    s.query("shazam marvel ");
+    s.ignore_terms(vec!["shazam".to_owned(), "marvel".to_owned()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5, 6]");
+
+    // the last word of the query is ignored too, even without a trailing space
+    let mut s = Search::new(&txn, &index);
+    s.query("captain shazam");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.ignore_terms(vec!["shazam".to_owned()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("shazam marvel");
+    s.ignore_terms(vec!["shazam".to_owned(), "marvel".to_owned()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5, 6]");
+}
@@ -16,6 +16,7 @@ pub struct VectorSort<Q: RankingRuleQueryTrait> {
     distribution_shift: Option<DistributionShift>,
     embedder_index: u8,
     quantized: bool,
+    search_accuracy: Option<f32>,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
@@ -27,6 +28,7 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         embedder_name: &str,
         embedder: &Embedder,
         quantized: bool,
+        search_accuracy: Option<f32>,
     ) -> Result<Self> {
         let embedder_index = ctx
             .index
@@ -43,6 +45,7 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             distribution_shift: embedder.distribution(),
             embedder_index,
             quantized,
+            search_accuracy,
         })
     }
 
@@ -53,8 +56,19 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
     ) -> Result<()> {
         let target = &self.target;
 
-        let reader = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
-        let results = reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?;
+        let reader = ArroyWrapper::new(
+            ctx.index.vector_arroy,
+            ctx.index.full_precision_vectors,
+            self.embedder_index,
+            self.quantized,
+        );
+        let results = reader.nns_by_vector(
+            ctx.txn,
+            target,
+            self.limit,
+            Some(vector_candidates),
+            self.search_accuracy,
+        )?;
         self.cached_sorted_docids = results.into_iter();
 
         Ok(())
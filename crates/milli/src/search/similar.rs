@@ -4,12 +4,16 @@ use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
 use crate::vector::{ArroyWrapper, Embedder};
-use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
+use crate::{
+    filtered_universe, DocumentId, Filter, Index, Result, Search, SearchResult,
+    TermsMatchingStrategy,
+};
 
 pub struct Similar<'a> {
     id: DocumentId,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    query: Option<&'a str>,
     offset: usize,
     limit: usize,
     rtxn: &'a heed::RoTxn<'a>,
@@ -35,6 +39,7 @@ impl<'a> Similar<'a> {
         Self {
             id,
             filter: None,
+            query: None,
             offset,
             limit,
             rtxn,
@@ -51,6 +56,14 @@ impl<'a> Similar<'a> {
         self
     }
 
+    /// Only return the documents matching all the terms of the query.
+    ///
+    /// Like in a regular search, words and phrases prefixed with `-` exclude the documents containing them.
+    pub fn query(&mut self, query: &'a str) -> &mut Self {
+        self.query = Some(query);
+        self
+    }
+
     pub fn ranking_score_threshold(&mut self, ranking_score_threshold: f64) -> &mut Self {
         self.ranking_score_threshold = Some(ranking_score_threshold);
         self
@@ -62,6 +75,16 @@ impl<'a> Similar<'a> {
         // we never want to receive the docid
         universe.remove(self.id);
 
+        let mut used_negative_operator = false;
+        if let Some(query) = self.query {
+            // we only need the candidates of the keyword search, not its ranking
+            let mut search = Search::new(self.rtxn, self.index);
+            search.query(query).terms_matching_strategy(TermsMatchingStrategy::All).limit(0);
+            let result = search.execute()?;
+            universe &= result.candidates;
+            used_negative_operator = result.used_negative_operator;
+        }
+
         let universe = universe;
 
         let embedder_index =
@@ -69,7 +92,12 @@ impl<'a> Similar<'a> {
                 || crate::UserError::InvalidSimilarEmbedder(self.embedder_name.to_owned()),
             )?;
 
-        let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_index, self.quantized);
+        let reader = ArroyWrapper::new(
+            self.index.vector_arroy,
+            self.index.full_precision_vectors,
+            embedder_index,
+            self.quantized,
+        );
         let results = reader.nns_by_item(
             self.rtxn,
             self.id,
@@ -126,7 +154,8 @@ impl<'a> Similar<'a> {
             documents_ids,
             document_scores,
             degraded: false,
-            used_negative_operator: false,
+            used_negative_operator,
+            timings: Default::default(),
         })
     }
 }
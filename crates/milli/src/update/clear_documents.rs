@@ -47,6 +47,11 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id: _,
+            // the cache is kept so that documents added back are not embedded again
+            embedding_cache: _,
+            full_precision_vectors,
+            // the revisions are kept so that the cleared documents can be restored
+            document_revisions: _,
             documents,
         } = self.index;
 
@@ -93,6 +98,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         field_id_docid_facet_strings.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
+        full_precision_vectors.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
 
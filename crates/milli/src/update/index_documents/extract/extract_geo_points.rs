@@ -13,7 +13,7 @@ use crate::{FieldId, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
-/// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// Returns the generated grenad reader containing the docid as key associated to the packed (latitude, longitude) pairs
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -46,15 +46,11 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
 
         if del_lat_lng != add_lat_lng {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Deletion, bytes)?;
+            if !del_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Deletion, pack_lat_lng(&del_lat_lng))?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Addition, bytes)?;
+            if !add_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Addition, pack_lat_lng(&add_lat_lng))?;
             }
             let bytes = obkv.into_inner()?;
             writer.insert(docid_bytes, bytes)?;
@@ -64,13 +60,27 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
+/// Packs the (latitude, longitude) pairs one after the other.
+fn pack_lat_lng(points: &[[f64; 2]]) -> Vec<u8> {
+    points
+        .iter()
+        .flat_map(|[lat, lng]| {
+            #[allow(clippy::drop_non_drop)]
+            let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            bytes
+        })
+        .collect()
+}
+
 /// Extract the finite floats lat and lng from two bytes slices.
+///
+/// When the document has several geo points the flattened lat and lng are arrays of the same length.
 fn extract_lat_lng(
     document: &obkv::KvReader<FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
+) -> Result<Vec<[f64; 2]>> {
     match settings.geo_fields_ids {
         Some((lat_fid, lng_fid)) => {
             let lat =
@@ -85,19 +95,34 @@ fn extract_lat_lng(
                 (None, Some(_)) => {
                     return Err(GeoError::MissingLongitude { document_id: document_id() }.into())
                 }
-                (None, None) => return Ok(None),
+                (None, None) => return Ok(Vec::new()),
             };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })?;
+            let lat = serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?;
+            let lng = serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?;
+            let (lats, lngs) = match (lat, lng) {
+                (Value::Array(lats), Value::Array(lngs)) => (lats, lngs),
+                (lat, lng) => (vec![lat], vec![lng]),
+            };
+            if lats.len() < lngs.len() {
+                return Err(GeoError::MissingLatitude { document_id: document_id() }.into());
+            }
+            if lngs.len() < lats.len() {
+                return Err(GeoError::MissingLongitude { document_id: document_id() }.into());
+            }
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })?;
-            Ok(Some([lat, lng]))
+            lats.into_iter()
+                .zip(lngs)
+                .map(|(lat, lng)| -> Result<[f64; 2]> {
+                    let lat = extract_finite_float_from_value(lat).map_err(|lat| {
+                        GeoError::BadLatitude { document_id: document_id(), value: lat }
+                    })?;
+                    let lng = extract_finite_float_from_value(lng).map_err(|lng| {
+                        GeoError::BadLongitude { document_id: document_id(), value: lng }
+                    })?;
+                    Ok([lat, lng])
+                })
+                .collect()
         }
-        None => Ok(None),
+        None => Ok(Vec::new()),
     }
 }
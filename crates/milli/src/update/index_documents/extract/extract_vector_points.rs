@@ -40,6 +40,7 @@ pub struct ExtractedVectorPoints {
     // embedder
     pub embedder_name: String,
     pub embedder: Arc<Embedder>,
+    pub prompt: Arc<Prompt>,
     pub add_to_user_provided: RoaringBitmap,
     pub remove_from_user_provided: RoaringBitmap,
 }
@@ -444,7 +445,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     for EmbedderVectorExtractor {
         embedder_name,
         embedder,
-        prompt: _,
+        prompt,
         prompts_writer,
         remove_vectors_writer,
         action,
@@ -467,6 +468,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             remove_vectors: writer_into_reader(remove_vectors_writer)?,
             prompts: writer_into_reader(prompts_writer)?,
             embedder,
+            prompt,
             embedder_name,
             add_to_user_provided,
             remove_from_user_provided,
@@ -680,6 +682,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     prompt_reader: grenad::Reader<R>,
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
+    template: &Prompt,
     embedder_name: &str,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
@@ -699,43 +702,48 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     let mut current_chunk = Vec::with_capacity(n_vectors_per_chunk);
     let mut current_chunk_ids = Vec::with_capacity(n_vectors_per_chunk);
     let mut chunks_ids = Vec::with_capacity(n_chunks);
+    // the embeddings of the last document, whose chunks may not all be embedded yet
+    let mut pending = None;
     let mut cursor = prompt_reader.into_cursor()?;
 
     while let Some((key, value)) = cursor.move_on_next()? {
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
         // SAFETY: precondition, the grenad value was saved from a string
         let prompt = unsafe { std::str::from_utf8_unchecked(value) };
-        if current_chunk.len() == current_chunk.capacity() {
-            chunks.push(std::mem::replace(
-                &mut current_chunk,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-            chunks_ids.push(std::mem::replace(
-                &mut current_chunk_ids,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-        };
-        current_chunk.push(prompt.to_owned());
-        current_chunk_ids.push(docid);
-
-        if chunks.len() == chunks.capacity() {
-            let chunked_embeds = embed_chunks(
-                &embedder,
-                std::mem::replace(&mut chunks, Vec::with_capacity(n_chunks)),
-                embedder_name,
-                possible_embedding_mistakes,
-                unused_vectors_distribution,
-                request_threads,
-            )?;
+        for chunk in template.chunks(prompt) {
+            if current_chunk.len() == current_chunk.capacity() {
+                chunks.push(std::mem::replace(
+                    &mut current_chunk,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+                chunks_ids.push(std::mem::replace(
+                    &mut current_chunk_ids,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+            };
+            current_chunk.push(chunk.to_owned());
+            current_chunk_ids.push(docid);
 
-            for (docid, embeddings) in chunks_ids
-                .iter()
-                .flat_map(|docids| docids.iter())
-                .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-            {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
+            if chunks.len() == chunks.capacity() {
+                let chunked_embeds = embed_chunks(
+                    &embedder,
+                    std::mem::replace(&mut chunks, Vec::with_capacity(n_chunks)),
+                    embedder_name,
+                    possible_embedding_mistakes,
+                    unused_vectors_distribution,
+                    request_threads,
+                )?;
+
+                write_embeddings(
+                    &mut state_writer,
+                    &mut pending,
+                    chunks_ids
+                        .iter()
+                        .flat_map(|docids| docids.iter())
+                        .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter())),
+                )?;
+                chunks_ids.clear();
             }
-            chunks_ids.clear();
         }
     }
 
@@ -749,13 +757,14 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             unused_vectors_distribution,
             request_threads,
         )?;
-        for (docid, embeddings) in chunks_ids
-            .iter()
-            .flat_map(|docids| docids.iter())
-            .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-        {
-            state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-        }
+        write_embeddings(
+            &mut state_writer,
+            &mut pending,
+            chunks_ids
+                .iter()
+                .flat_map(|docids| docids.iter())
+                .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter())),
+        )?;
     }
 
     if !current_chunk.is_empty() {
@@ -769,15 +778,48 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
         )?;
 
         if let Some(embeds) = embeds.first() {
-            for (docid, embeddings) in current_chunk_ids.iter().zip(embeds.iter()) {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-            }
+            write_embeddings(
+                &mut state_writer,
+                &mut pending,
+                current_chunk_ids.iter().zip(embeds.iter()),
+            )?;
         }
     }
 
+    if let Some((docid, embeddings)) = pending {
+        state_writer.insert(docid.to_be_bytes(), cast_slice(&embeddings))?;
+    }
+
     writer_into_reader(state_writer)
 }
 
+/// Writes the embeddings of the chunks of the documents, all the embeddings of a document in a single entry.
+///
+/// The chunks of a document are consecutive, so the embeddings of the last document are kept `pending`
+/// until the embeddings of another document are written.
+fn write_embeddings<'a>(
+    state_writer: &mut Writer<BufWriter<File>>,
+    pending: &mut Option<(DocumentId, Vec<f32>)>,
+    embeddings: impl Iterator<Item = (&'a DocumentId, &'a Embedding)>,
+) -> Result<()> {
+    for (&docid, embedding) in embeddings {
+        match pending {
+            Some((pending_docid, pending_embeddings)) if *pending_docid == docid => {
+                pending_embeddings.extend_from_slice(embedding)
+            }
+            _ => {
+                if let Some((pending_docid, pending_embeddings)) =
+                    pending.replace((docid, embedding.clone()))
+                {
+                    state_writer
+                        .insert(pending_docid.to_be_bytes(), cast_slice(&pending_embeddings))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn embed_chunks(
     embedder: &Embedder,
     text_chunks: Vec<Vec<String>>,
@@ -260,6 +260,7 @@ fn send_original_documents_data(
                         prompts,
                         embedder_name,
                         embedder,
+                        prompt,
                         add_to_user_provided,
                         remove_from_user_provided,
                     } in extracted_vectors
@@ -268,6 +269,7 @@ fn send_original_documents_data(
                             prompts,
                             indexer,
                             embedder.clone(),
+                            &prompt,
                             &embedder_name,
                             &possible_embedding_mistakes,
                             &unused_vectors_distribution,
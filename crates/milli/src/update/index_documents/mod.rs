@@ -53,7 +53,6 @@ pub struct DocumentAdditionResult {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[non_exhaustive]
 pub enum IndexDocumentsMethod {
     /// Replace the previous document with the new one,
     /// removing all the already known attributes.
@@ -62,6 +61,22 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Apply the new version of the document as a JSON Merge Patch (RFC 7396) on the previous one,
+    /// merging the nested objects and removing the attributes set to `null`.
+    MergePatchDocuments,
+
+    /// Apply a list of JSON Patch (RFC 6902) operations on the previous version of the document,
+    /// which must already exist in the index.
+    JsonPatchDocuments,
+
+    /// Only add the documents whose primary key is not already used,
+    /// silently ignoring the other ones.
+    SkipExistingDocuments,
+
+    /// Only add the documents whose primary key is not already used,
+    /// failing the whole payload if one of them already exists.
+    FailOnExistingDocuments,
 }
 
 impl Default for IndexDocumentsMethod {
@@ -496,8 +511,12 @@ where
                         key: None,
                     },
                 )?;
-                let reader =
-                    ArroyWrapper::new(self.index.vector_arroy, index, action.was_quantized);
+                let reader = ArroyWrapper::new(
+                    self.index.vector_arroy,
+                    self.index.full_precision_vectors,
+                    index,
+                    action.was_quantized,
+                );
                 let dim = reader.dimensions(self.wtxn)?;
                 dimension.insert(name.to_string(), dim);
             }
@@ -506,6 +525,7 @@ where
         for (embedder_name, dimension) in dimension {
             let wtxn = &mut *self.wtxn;
             let vector_arroy = self.index.vector_arroy;
+            let full_precision_vectors = self.index.full_precision_vectors;
             let cancel = &self.should_abort;
 
             let embedder_index = self.index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
@@ -520,7 +540,12 @@ where
             let is_quantizing = embedder_config.map_or(false, |action| action.is_being_quantized);
 
             pool.install(|| {
-                let mut writer = ArroyWrapper::new(vector_arroy, embedder_index, was_quantized);
+                let mut writer = ArroyWrapper::new(
+                    vector_arroy,
+                    full_precision_vectors,
+                    embedder_index,
+                    was_quantized,
+                );
                 writer.build_and_quantize(
                     wtxn,
                     // In the settings we don't have any progress to share
@@ -1321,11 +1346,11 @@ mod tests {
 
         search.filter(crate::Filter::from_str(r#"nested = array"#).unwrap().unwrap());
         let error = search.execute().map(|_| unreachable!()).unwrap_err(); // nested is not filterable
-        assert!(matches!(error, crate::Error::UserError(crate::UserError::InvalidFilter(_))));
+        assert!(matches!(error, crate::Error::UserError(crate::UserError::InvalidFilter(..))));
 
         search.filter(crate::Filter::from_str(r#"nested = "I lied""#).unwrap().unwrap());
         let error = search.execute().map(|_| unreachable!()).unwrap_err(); // nested is not filterable
-        assert!(matches!(error, crate::Error::UserError(crate::UserError::InvalidFilter(_))));
+        assert!(matches!(error, crate::Error::UserError(crate::UserError::InvalidFilter(..))));
     }
 
     #[test]
@@ -2777,6 +2802,7 @@ mod tests {
                         dimensions: Setting::Set(3),
                         document_template: Setting::NotSet,
                         document_template_max_bytes: Setting::NotSet,
+                        document_template_chunk_bytes: Setting::NotSet,
                         url: Setting::NotSet,
                         request: Setting::NotSet,
                         response: Setting::NotSet,
@@ -33,7 +33,7 @@ use crate::update::{AvailableIds, UpdateIndexingStep};
 use crate::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
 use crate::vector::settings::WriteBackToDocuments;
 use crate::vector::ArroyWrapper;
-use crate::{FieldDistribution, FieldId, FieldIdMapMissingEntry, Index, Result};
+use crate::{FieldDistribution, FieldId, FieldIdMapMissingEntry, FlattenOptions, Index, Result};
 
 pub struct TransformOutput {
     pub primary_key: String,
@@ -53,6 +53,7 @@ pub struct TransformOutput {
 pub struct Transform<'a, 'i> {
     pub index: &'i Index,
     fields_ids_map: FieldIdMapWithMetadata,
+    flatten_options: FlattenOptions,
 
     indexer_settings: &'a IndexerConfig,
     pub index_documents_method: IndexDocumentsMethod,
@@ -110,13 +111,25 @@ impl<'a, 'i> Transform<'a, 'i> {
         index_documents_method: IndexDocumentsMethod,
         _autogenerate_docids: bool,
     ) -> Result<Self> {
-        use IndexDocumentsMethod::{ReplaceDocuments, UpdateDocuments};
+        use IndexDocumentsMethod::{
+            FailOnExistingDocuments, JsonPatchDocuments, MergePatchDocuments, ReplaceDocuments,
+            SkipExistingDocuments, UpdateDocuments,
+        };
 
         // We must choose the appropriate merge function for when two or more documents
         // with the same user id must be merged or fully replaced in the same batch.
         let merge_function = match index_documents_method {
             ReplaceDocuments => Either::Left(ObkvsKeepLastAdditionMergeDeletions),
             UpdateDocuments => Either::Right(ObkvsMergeAdditionsAndDeletions),
+            MergePatchDocuments => {
+                return Err(InternalError::UnsupportedByLegacyIndexer("JSON merge patches").into())
+            }
+            JsonPatchDocuments => {
+                return Err(InternalError::UnsupportedByLegacyIndexer("JSON patches").into())
+            }
+            SkipExistingDocuments | FailOnExistingDocuments => {
+                return Err(InternalError::UnsupportedByLegacyIndexer("Conflict policies").into())
+            }
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -144,10 +157,12 @@ impl<'a, 'i> Transform<'a, 'i> {
         let fields_ids_map = index.fields_ids_map(wtxn)?;
         let builder = MetadataBuilder::from_index(index, wtxn)?;
         let fields_ids_map = FieldIdMapWithMetadata::new(fields_ids_map, builder);
+        let flatten_options = index.flatten_options(wtxn)?;
 
         Ok(Transform {
             index,
             fields_ids_map,
+            flatten_options,
             indexer_settings,
             available_documents_ids: AvailableIds::new(&documents_ids),
             original_sorter,
@@ -280,6 +295,16 @@ impl<'a, 'i> Transform<'a, 'i> {
                             DelAddOperation::DeletionAndAddition
                         }
                         IndexDocumentsMethod::ReplaceDocuments => DelAddOperation::Deletion,
+                        IndexDocumentsMethod::MergePatchDocuments => {
+                            unreachable!("JSON merge patches are only supported by the new indexer")
+                        }
+                        IndexDocumentsMethod::JsonPatchDocuments => {
+                            unreachable!("JSON patches are only supported by the new indexer")
+                        }
+                        IndexDocumentsMethod::SkipExistingDocuments
+                        | IndexDocumentsMethod::FailOnExistingDocuments => {
+                            unreachable!("Conflict policies are only supported by the new indexer")
+                        }
                     };
                     document_sorter_key_buffer.clear();
                     document_sorter_key_buffer.extend_from_slice(&docid.to_be_bytes());
@@ -294,9 +319,11 @@ impl<'a, 'i> Transform<'a, 'i> {
                     self.original_sorter
                         .insert(&document_sorter_key_buffer, &document_sorter_value_buffer)?;
                     let base_obkv = KvReader::from_slice(base_obkv);
-                    if let Some(flattened_obkv) =
-                        Self::flatten_from_fields_ids_map(base_obkv, &mut self.fields_ids_map)?
-                    {
+                    if let Some(flattened_obkv) = Self::flatten_from_fields_ids_map(
+                        base_obkv,
+                        &mut self.fields_ids_map,
+                        &self.flatten_options,
+                    )? {
                         // we recreate our buffer with the flattened documents
                         document_sorter_value_buffer.clear();
                         document_sorter_value_buffer.push(Operation::Addition as u8);
@@ -329,9 +356,11 @@ impl<'a, 'i> Transform<'a, 'i> {
                     .insert(&document_sorter_key_buffer, &document_sorter_value_buffer)?;
 
                 let flattened_obkv = KvReader::from_slice(&obkv_buffer);
-                if let Some(obkv) =
-                    Self::flatten_from_fields_ids_map(flattened_obkv, &mut self.fields_ids_map)?
-                {
+                if let Some(obkv) = Self::flatten_from_fields_ids_map(
+                    flattened_obkv,
+                    &mut self.fields_ids_map,
+                    &self.flatten_options,
+                )? {
                     document_sorter_value_buffer.clear();
                     document_sorter_value_buffer.push(Operation::Addition as u8);
                     into_del_add_obkv(
@@ -369,12 +398,13 @@ impl<'a, 'i> Transform<'a, 'i> {
     // created fields. Returns `None` if the document doesn't need to be flattened.
     #[tracing::instrument(
         level = "trace",
-        skip(obkv, fields_ids_map),
+        skip(obkv, fields_ids_map, flatten_options),
         target = "indexing::transform"
     )]
     fn flatten_from_fields_ids_map(
         obkv: &KvReader<FieldId>,
         fields_ids_map: &mut FieldIdMapWithMetadata,
+        flatten_options: &FlattenOptions,
     ) -> Result<Option<Vec<u8>>> {
         if obkv
             .iter()
@@ -409,7 +439,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
         }
 
-        let flattened = flatten_serde_json::flatten(&doc);
+        let flattened = flatten_serde_json::flatten_with_options(&doc, *flatten_options);
 
         // Once we have the flattened version we insert all the new generated fields_ids
         // (if any) in the fields ids map and serialize the value.
@@ -664,6 +694,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         old_vectors_fid: Option<FieldId>,
         original_obkv_buffer: Option<&mut Vec<u8>>,
         flattened_obkv_buffer: Option<&mut Vec<u8>>,
+        flatten_options: &FlattenOptions,
     ) -> Result<()> {
         // Always keep the primary key.
         let is_primary_key = |id: FieldId| -> bool { settings_diff.primary_key_id == Some(id) };
@@ -779,7 +810,8 @@ impl<'a, 'i> Transform<'a, 'i> {
         if let Some(flattened_obkv_buffer) = flattened_obkv_buffer {
             // take the non-flattened version if flatten_from_fields_ids_map returns None.
             let mut fields_ids_map = settings_diff.new.fields_ids_map.clone();
-            let flattened = Self::flatten_from_fields_ids_map(obkv, &mut fields_ids_map)?;
+            let flattened =
+                Self::flatten_from_fields_ids_map(obkv, &mut fields_ids_map, flatten_options)?;
             let flattened = flattened.as_deref().map_or(obkv, KvReader::from_slice);
 
             flattened_obkv_buffer.clear();
@@ -843,6 +875,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                 {
                     let reader = ArroyWrapper::new(
                         self.index.vector_arroy,
+                        self.index.full_precision_vectors,
                         *embedder_id,
                         action.was_quantized,
                     );
@@ -917,6 +950,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                     old_vectors_fid,
                     Some(&mut original_obkv_buffer).filter(|_| original_sorter.is_some()),
                     Some(&mut flattened_obkv_buffer).filter(|_| flattened_sorter.is_some()),
+                    &self.flatten_options,
                 )?;
 
                 if let Some(original_sorter) = original_sorter.as_mut() {
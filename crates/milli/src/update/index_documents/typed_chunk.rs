@@ -600,13 +600,15 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::from_slice(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.remove(&geopoint);
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.insert(geopoint);
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
@@ -670,7 +672,12 @@ pub(crate) fn write_typed_chunk_into_index(
                 .get(&embedder_name)
                 .map_or(false, |conf| conf.2);
             // FIXME: allow customizing distance
-            let writer = ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized);
+            let writer = ArroyWrapper::new(
+                index.vector_arroy,
+                index.full_precision_vectors,
+                embedder_index,
+                binary_quantized,
+            );
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
@@ -738,13 +745,15 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-pub fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Converts the packed latitudes and longitudes back to xyz GeoPoints.
+pub fn extract_geo_points(value: &[u8], docid: DocumentId) -> impl Iterator<Item = GeoPoint> + '_ {
+    value.chunks_exact(16).map(move |bytes| {
+        let (lat, tail) = helpers::try_split_array_at::<u8, 8>(bytes).unwrap();
+        let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+        let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+        let xyz_point = lat_lng_to_xyz(&point);
+        GeoPoint::new(xyz_point, (docid, point))
+    })
 }
 
 fn merge_word_docids_reader_into_fst<MF>(
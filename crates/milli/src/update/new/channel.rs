@@ -26,6 +26,7 @@ use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCod
 use crate::index::db_name;
 use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
 use crate::update::new::KvReaderFieldId;
+use crate::vector::persisted_cache::{self, CacheKey};
 use crate::vector::Embedding;
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
 
@@ -398,6 +399,7 @@ pub enum Database {
     FacetIdStringDocids,
     FieldIdDocidFacetStrings,
     FieldIdDocidFacetF64s,
+    EmbeddingCache,
 }
 
 impl Database {
@@ -419,6 +421,7 @@ impl Database {
             Database::FacetIdStringDocids => index.facet_id_string_docids.remap_types(),
             Database::FieldIdDocidFacetStrings => index.field_id_docid_facet_strings.remap_types(),
             Database::FieldIdDocidFacetF64s => index.field_id_docid_facet_f64s.remap_types(),
+            Database::EmbeddingCache => index.embedding_cache,
         }
     }
 
@@ -440,6 +443,7 @@ impl Database {
             Database::FacetIdStringDocids => db_name::FACET_ID_STRING_DOCIDS,
             Database::FieldIdDocidFacetStrings => db_name::FIELD_ID_DOCID_FACET_STRINGS,
             Database::FieldIdDocidFacetF64s => db_name::FIELD_ID_DOCID_FACET_F64S,
+            Database::EmbeddingCache => db_name::EMBEDDING_CACHE,
         }
     }
 }
@@ -938,13 +942,19 @@ impl EmbeddingSender<'_, '_> {
         self.0.set_vectors(docid, embedder_id, &embeddings[..])
     }
 
-    pub fn set_vector(
-        &self,
-        docid: DocumentId,
-        embedder_id: u8,
-        embedding: Embedding,
-    ) -> crate::Result<()> {
-        self.0.set_vectors(docid, embedder_id, &[embedding])
+    /// Stores the `embedding` in the persisted embedding cache of the index.
+    pub fn set_cached_embedding(&self, key: &CacheKey, embedding: &[f32]) -> crate::Result<()> {
+        let key_length = NonZeroU16::new(key.len() as u16).unwrap();
+        self.0.write_key_value_with(
+            Database::EmbeddingCache,
+            key_length,
+            persisted_cache::value_length(embedding),
+            |key_buffer, value_buffer| {
+                key_buffer.copy_from_slice(key);
+                persisted_cache::encode_value_into(embedding, value_buffer);
+                Ok(())
+            },
+        )
     }
 }
 
@@ -0,0 +1,78 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use charabia::{Language, StrDetection};
+use serde_json::Value;
+
+use crate::attribute_patterns::PatternMatch;
+use crate::update::new::document::Document;
+use crate::update::new::extract::perm_json_p::{
+    seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
+};
+use crate::{
+    FlattenOptions, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result, UserError,
+};
+
+/// Detects the languages of the searchable strings of the document.
+///
+/// The strings are detected together to be reliable, except that the strings of the fields
+/// matching a localized attributes rule are restricted to its locales and detected apart.
+pub fn detect_document_locales<'doc>(
+    document: impl Document<'doc>,
+    field_id_map: &mut GlobalFieldsIdsMap,
+    localized_attributes_rules: &[LocalizedAttributesRule],
+    flatten_options: &FlattenOptions,
+) -> Result<BTreeSet<Language>> {
+    // The texts to detect, by the position of the localized attributes rule of their fields.
+    let mut texts: BTreeMap<Option<usize>, String> = BTreeMap::new();
+    let mut detect_field = |field_name: &str, _depth, value: &Value| {
+        let Some((_, meta)) = field_id_map.id_with_metadata_or_insert(field_name) else {
+            return Err(UserError::AttributeLimitReached.into());
+        };
+
+        if let Value::String(text) = value {
+            if meta.is_searchable() {
+                let rule = localized_attributes_rules
+                    .iter()
+                    .position(|rule| rule.match_str(field_name) == PatternMatch::Match);
+                let buffer = texts.entry(rule).or_default();
+                buffer.push_str(text);
+                buffer.push('\n');
+            }
+        }
+
+        Ok(PatternMatch::Match)
+    };
+
+    for entry in document.iter_top_level_fields() {
+        let (field_name, value) = entry?;
+        match serde_json::to_value(value).map_err(InternalError::SerdeJson)? {
+            Value::Object(object) => seek_leaf_values_in_object(
+                &object,
+                field_name,
+                Depth::OnBaseKey,
+                flatten_options,
+                &mut detect_field,
+            )?,
+            Value::Array(array) => seek_leaf_values_in_array(
+                &array,
+                field_name,
+                Depth::OnBaseKey,
+                flatten_options,
+                &mut detect_field,
+            )?,
+            value => {
+                detect_field(field_name, Depth::OnBaseKey, &value)?;
+            }
+        }
+    }
+
+    let mut languages = BTreeSet::new();
+    for (rule, text) in &texts {
+        let locales = rule.map(|rule| localized_attributes_rules[rule].locales());
+        if let Some(language) = StrDetection::new(text, locales).language() {
+            languages.insert(language);
+        }
+    }
+
+    Ok(languages)
+}
@@ -24,7 +24,10 @@ use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, ThreadLocal};
 use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
-use crate::{DocumentId, FieldId, FilterableAttributesRule, Result, MAX_FACET_VALUE_LENGTH};
+use crate::{
+    DocumentId, FieldId, FilterableAttributesRule, FlattenOptions, LocalizedAttributesRule, Result,
+    MAX_FACET_VALUE_LENGTH,
+};
 
 pub struct FacetedExtractorData<'a, 'b> {
     sender: &'a FieldIdDocidFacetSender<'a, 'b>,
@@ -35,6 +38,9 @@ pub struct FacetedExtractorData<'a, 'b> {
     asc_desc_fields: &'a HashSet<String>,
     distinct_field: &'a Option<String>,
     is_geo_enabled: bool,
+    is_geojson_enabled: bool,
+    detected_locales: Option<&'a [LocalizedAttributesRule]>,
+    flatten_options: FlattenOptions,
 }
 
 impl<'a, 'b, 'extractor> Extractor<'extractor> for FacetedExtractorData<'a, 'b> {
@@ -62,6 +68,9 @@ impl<'a, 'b, 'extractor> Extractor<'extractor> for FacetedExtractorData<'a, 'b>
                 self.asc_desc_fields,
                 self.distinct_field,
                 self.is_geo_enabled,
+                self.is_geojson_enabled,
+                self.detected_locales,
+                &self.flatten_options,
                 change,
                 self.sender,
             )?
@@ -81,6 +90,9 @@ impl FacetedDocidsExtractor {
         asc_desc_fields: &HashSet<String>,
         distinct_field: &Option<String>,
         is_geo_enabled: bool,
+        is_geojson_enabled: bool,
+        detected_locales: Option<&[LocalizedAttributesRule]>,
+        flatten_options: &FlattenOptions,
         document_change: DocumentChange,
         sender: &FieldIdDocidFacetSender,
     ) -> Result<()> {
@@ -100,6 +112,9 @@ impl FacetedDocidsExtractor {
                 asc_desc_fields,
                 distinct_field,
                 is_geo_enabled,
+                is_geojson_enabled,
+                detected_locales,
+                flatten_options,
                 &mut |fid, meta, depth, value| {
                     Self::facet_fn_with_options(
                         &context.doc_alloc,
@@ -117,20 +132,23 @@ impl FacetedDocidsExtractor {
                 },
             ),
             DocumentChange::Update(inner) => {
-                if !inner.has_changed_for_fields(
-                    &mut |field_name| {
-                        match_faceted_field(
-                            field_name,
-                            filterable_attributes,
-                            sortable_fields,
-                            asc_desc_fields,
-                            distinct_field,
-                        )
-                    },
-                    rtxn,
-                    index,
-                    context.db_fields_ids_map,
-                )? {
+                // Any searchable field can change the detected locales.
+                if detected_locales.is_none()
+                    && !inner.has_changed_for_fields(
+                        &mut |field_name| {
+                            match_faceted_field(
+                                field_name,
+                                filterable_attributes,
+                                sortable_fields,
+                                asc_desc_fields,
+                                distinct_field,
+                            )
+                        },
+                        rtxn,
+                        index,
+                        context.db_fields_ids_map,
+                    )?
+                {
                     return Ok(());
                 }
 
@@ -143,6 +161,9 @@ impl FacetedDocidsExtractor {
                     asc_desc_fields,
                     distinct_field,
                     is_geo_enabled,
+                    is_geojson_enabled,
+                    detected_locales,
+                    flatten_options,
                     &mut |fid, meta, depth, value| {
                         Self::facet_fn_with_options(
                             &context.doc_alloc,
@@ -169,6 +190,9 @@ impl FacetedDocidsExtractor {
                     asc_desc_fields,
                     distinct_field,
                     is_geo_enabled,
+                    is_geojson_enabled,
+                    detected_locales,
+                    flatten_options,
                     &mut |fid, meta, depth, value| {
                         Self::facet_fn_with_options(
                             &context.doc_alloc,
@@ -195,6 +219,9 @@ impl FacetedDocidsExtractor {
                 asc_desc_fields,
                 distinct_field,
                 is_geo_enabled,
+                is_geojson_enabled,
+                detected_locales,
+                flatten_options,
                 &mut |fid, meta, depth, value| {
                     Self::facet_fn_with_options(
                         &context.doc_alloc,
@@ -467,6 +494,11 @@ impl FacetedDocidsExtractor {
         let asc_desc_fields = index.asc_desc_fields(&rtxn)?;
         let distinct_field = index.distinct_field(&rtxn)?.map(|s| s.to_string());
         let is_geo_enabled = index.is_geo_enabled(&rtxn)?;
+        let is_geojson_enabled = index.is_geojson_enabled(&rtxn)?;
+        let localized_attributes_rules =
+            index.localized_attributes_rules(&rtxn)?.unwrap_or_default();
+        let is_detected_locales_enabled = index.is_detected_locales_enabled(&rtxn)?;
+        let flatten_options = index.flatten_options(&rtxn)?;
         let datastore = ThreadLocal::new();
 
         {
@@ -483,6 +515,10 @@ impl FacetedDocidsExtractor {
                 asc_desc_fields: &asc_desc_fields,
                 distinct_field: &distinct_field,
                 is_geo_enabled,
+                is_geojson_enabled,
+                detected_locales: is_detected_locales_enabled
+                    .then_some(localized_attributes_rules.as_slice()),
+                flatten_options,
             };
             extract(
                 document_changes,
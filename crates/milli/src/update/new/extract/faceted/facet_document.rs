@@ -2,13 +2,19 @@ use std::collections::HashSet;
 
 use serde_json::Value;
 
+use super::detected_locales::detect_document_locales;
 use crate::attribute_patterns::PatternMatch;
+use crate::constants::{RESERVED_DETECTED_LOCALES_FIELD_NAME, RESERVED_GEOJSON_FIELD_NAME};
 use crate::fields_ids_map::metadata::Metadata;
+use crate::geojson::{
+    Geometry, GEOJSON_MAX_LAT, GEOJSON_MAX_LNG, GEOJSON_MIN_LAT, GEOJSON_MIN_LNG,
+};
 use crate::update::new::document::Document;
-use crate::update::new::extract::geo::extract_geo_coordinates;
+use crate::update::new::extract::geo::extract_geo_points;
 use crate::update::new::extract::perm_json_p;
 use crate::{
-    FieldId, FilterableAttributesRule, GlobalFieldsIdsMap, InternalError, Result, UserError,
+    FieldId, FilterableAttributesRule, FlattenOptions, GlobalFieldsIdsMap, InternalError,
+    LocalizedAttributesRule, Result, UserError,
 };
 
 use crate::filterable_attributes_rules::match_faceted_field;
@@ -23,6 +29,9 @@ pub fn extract_document_facets<'doc>(
     asc_desc_fields: &HashSet<String>,
     distinct_field: &Option<String>,
     is_geo_enabled: bool,
+    is_geojson_enabled: bool,
+    detected_locales: Option<&[LocalizedAttributesRule]>,
+    flatten_options: &FlattenOptions,
     facet_fn: &mut impl FnMut(FieldId, Metadata, perm_json_p::Depth, &Value) -> Result<()>,
 ) -> Result<()> {
     // return the match result for the given field name.
@@ -50,6 +59,10 @@ pub fn extract_document_facets<'doc>(
 
     for res in document.iter_top_level_fields() {
         let (field_name, value) = res?;
+        // The geometries are indexed by their bounding box, not by their coordinates.
+        if is_geojson_enabled && field_name == RESERVED_GEOJSON_FIELD_NAME {
+            continue;
+        }
         let selection = match_field(field_name);
 
         // extract the field if it matches a pattern and if it is faceted (facet searchable, filterable, sortable)
@@ -70,6 +83,7 @@ pub fn extract_document_facets<'doc>(
                         &object,
                         field_name,
                         perm_json_p::Depth::OnBaseKey,
+                        flatten_options,
                         &mut match_and_extract,
                     )?;
 
@@ -86,6 +100,7 @@ pub fn extract_document_facets<'doc>(
                         &array,
                         field_name,
                         perm_json_p::Depth::OnBaseKey,
+                        flatten_options,
                         &mut match_and_extract,
                     )?;
 
@@ -104,7 +119,7 @@ pub fn extract_document_facets<'doc>(
 
     if is_geo_enabled {
         if let Some(geo_value) = document.geo_field()? {
-            if let Some([lat, lng]) = extract_geo_coordinates(external_document_id, geo_value)? {
+            for [lat, lng] in extract_geo_points(external_document_id, geo_value)? {
                 let ((lat_fid, lat_meta), (lng_fid, lng_meta)) = field_id_map
                     .id_with_metadata_or_insert("_geo.lat")
                     .zip(field_id_map.id_with_metadata_or_insert("_geo.lng"))
@@ -116,5 +131,52 @@ pub fn extract_document_facets<'doc>(
         }
     }
 
+    if is_geojson_enabled {
+        if let Some(geojson) = document.top_level_field(RESERVED_GEOJSON_FIELD_NAME)? {
+            let value: Value =
+                serde_json::from_str(geojson.get()).map_err(InternalError::SerdeJson)?;
+            if !value.is_null() {
+                let geometry = Geometry::from_value(value).map_err(|error| {
+                    UserError::InvalidGeoJsonField {
+                        document_id: external_document_id.to_string(),
+                        error,
+                    }
+                })?;
+                if let Some(bbox) = geometry.bounding_box() {
+                    for (name, value) in [
+                        (GEOJSON_MIN_LAT, bbox.min.lat),
+                        (GEOJSON_MAX_LAT, bbox.max.lat),
+                        (GEOJSON_MIN_LNG, bbox.min.lng),
+                        (GEOJSON_MAX_LNG, bbox.max.lng),
+                    ] {
+                        let (fid, meta) = field_id_map
+                            .id_with_metadata_or_insert(name)
+                            .ok_or(UserError::AttributeLimitReached)?;
+                        facet_fn(fid, meta, perm_json_p::Depth::OnBaseKey, &value.into())?;
+                    }
+                }
+            }
+        }
+    }
+
+    // The detected locales are the values of a virtual field, only filterable when enabled.
+    if let Some(localized_attributes_rules) = detected_locales {
+        let languages = detect_document_locales(
+            document,
+            field_id_map,
+            localized_attributes_rules,
+            flatten_options,
+        )?;
+        if !languages.is_empty() {
+            let (fid, meta) = field_id_map
+                .id_with_metadata_or_insert(RESERVED_DETECTED_LOCALES_FIELD_NAME)
+                .ok_or(UserError::AttributeLimitReached)?;
+            for language in languages {
+                let value = Value::String(language.code().to_string());
+                facet_fn(fid, meta, perm_json_p::Depth::OnBaseKey, &value)?;
+            }
+        }
+    }
+
     Ok(())
 }
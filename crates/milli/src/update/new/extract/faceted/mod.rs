@@ -1,3 +1,4 @@
+mod detected_locales;
 mod extract_facets;
 mod facet_document;
 
@@ -174,10 +174,11 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let current = deletion.current(rtxn, index, db_fields_ids_map)?;
                     let current_geo = current
                         .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .map(|geo| extract_geo_points(external_id, geo))
+                        .transpose()?
+                        .unwrap_or_default();
 
-                    if let Some(lat_lng) = current_geo.flatten() {
+                    for lat_lng in current_geo {
                         let geopoint = ExtractedGeoPoint { docid, lat_lng };
                         match &mut data_ref.spilled_removed {
                             Some(file) => file.write_all(bytes_of(&geopoint))?,
@@ -192,20 +193,22 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
 
                     let current_geo = current
                         .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .map(|geo| extract_geo_points(external_id, geo))
+                        .transpose()?
+                        .unwrap_or_default();
 
                     let updated_geo = update
                         .merged(rtxn, index, db_fields_ids_map)?
                         .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .map(|geo| extract_geo_points(external_id, geo))
+                        .transpose()?
+                        .unwrap_or_default();
 
                     if current_geo != updated_geo {
                         // If the current and new geo points are different it means that
-                        // we need to replace the current by the new point and therefore
-                        // delete the current point from the RTree.
-                        if let Some(lat_lng) = current_geo.flatten() {
+                        // we need to replace the current by the new points and therefore
+                        // delete the current points from the RTree.
+                        for lat_lng in current_geo {
                             let geopoint = ExtractedGeoPoint { docid, lat_lng };
                             match &mut data_ref.spilled_removed {
                                 Some(file) => file.write_all(bytes_of(&geopoint))?,
@@ -213,7 +216,7 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                             }
                         }
 
-                        if let Some(lat_lng) = updated_geo.flatten() {
+                        for lat_lng in updated_geo {
                             let geopoint = ExtractedGeoPoint { docid, lat_lng };
                             match &mut data_ref.spilled_inserted {
                                 Some(file) => file.write_all(bytes_of(&geopoint))?,
@@ -229,10 +232,11 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let inserted_geo = insertion
                         .inserted()
                         .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .map(|geo| extract_geo_points(external_id, geo))
+                        .transpose()?
+                        .unwrap_or_default();
 
-                    if let Some(lat_lng) = inserted_geo.flatten() {
+                    for lat_lng in inserted_geo {
                         let geopoint = ExtractedGeoPoint { docid, lat_lng };
                         match &mut data_ref.spilled_inserted {
                             Some(file) => file.write_all(bytes_of(&geopoint))?,
@@ -247,14 +251,24 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
     }
 }
 
-/// Extracts and validates the latitude and latitude from a document geo field.
+/// Extracts and validates the latitudes and longitudes from a document geo field.
+///
+/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }` or an array of such objects.
+pub fn extract_geo_points(external_id: &str, raw_value: &RawValue) -> Result<Vec<[f64; 2]>> {
+    match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+        Value::Array(values) => values
+            .into_iter()
+            .filter_map(|value| extract_geo_coordinates(external_id, value).transpose())
+            .collect(),
+        value => Ok(extract_geo_coordinates(external_id, value)?.into_iter().collect()),
+    }
+}
+
+/// Extracts and validates the latitude and latitude from a single geo point.
 ///
 /// It can be of the form `{ "lat": 0.0, "lng": "1.0" }`.
-pub fn extract_geo_coordinates(
-    external_id: &str,
-    raw_value: &RawValue,
-) -> Result<Option<[f64; 2]>> {
-    let mut geo = match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+fn extract_geo_coordinates(external_id: &str, value: Value) -> Result<Option<[f64; 2]>> {
+    let mut geo = match value {
         Value::Null => return Ok(None),
         Value::Object(map) => map,
         value => {
@@ -16,9 +16,11 @@ pub use vectors::EmbeddingExtractor;
 
 /// TODO move in permissive json pointer
 pub mod perm_json_p {
+    use std::borrow::Cow;
+
     use serde_json::{Map, Value};
 
-    use crate::{attribute_patterns::PatternMatch, Result};
+    use crate::{attribute_patterns::PatternMatch, FlattenOptions, Result};
     const SPLIT_SYMBOL: char = '.';
 
     /// Returns `true` if the `selector` match the `key`.
@@ -49,10 +51,36 @@ pub mod perm_json_p {
         InsideArray,
     }
 
+    /// Seeks the values of the object found under the top-level field `base_key`.
     pub fn seek_leaf_values_in_object(
         value: &Map<String, Value>,
         base_key: &str,
         base_depth: Depth,
+        options: &FlattenOptions,
+        seeker: &mut impl FnMut(&str, Depth, &Value) -> Result<PatternMatch>,
+    ) -> Result<()> {
+        let level = if base_key.is_empty() { 0 } else { 1 };
+        seek_leaf_values_in_object_at(value, base_key, base_depth, level, options, seeker)
+    }
+
+    /// Seeks the values of the array found under the top-level field `base_key`.
+    pub fn seek_leaf_values_in_array(
+        values: &[Value],
+        base_key: &str,
+        base_depth: Depth,
+        options: &FlattenOptions,
+        seeker: &mut impl FnMut(&str, Depth, &Value) -> Result<PatternMatch>,
+    ) -> Result<()> {
+        seek_leaf_values_in_array_at(values, base_key, base_depth, 1, options, seeker)
+    }
+
+    /// `level` is the number of segments of the `base_key`.
+    fn seek_leaf_values_in_object_at(
+        value: &Map<String, Value>,
+        base_key: &str,
+        base_depth: Depth,
+        level: usize,
+        options: &FlattenOptions,
         seeker: &mut impl FnMut(&str, Depth, &Value) -> Result<PatternMatch>,
     ) -> Result<()> {
         if value.is_empty() {
@@ -60,21 +88,25 @@ pub mod perm_json_p {
         }
 
         for (key, value) in value.iter() {
-            let base_key = if base_key.is_empty() {
-                key.to_string()
+            let (base_key, depth, level) = if base_key.is_empty() {
+                (Cow::Owned(key.to_string()), Depth::OnBaseKey, 1)
+            } else if options.flattens_level(level) {
+                let base_key = format!("{}{}{}", base_key, SPLIT_SYMBOL, key);
+                (Cow::Owned(base_key), Depth::OnBaseKey, level + 1)
             } else {
-                format!("{}{}{}", base_key, SPLIT_SYMBOL, key)
+                // too deep, the values are merged into the base key like in an array
+                (Cow::Borrowed(base_key), Depth::InsideArray, level)
             };
 
-            let selection = seeker(&base_key, Depth::OnBaseKey, value)?;
+            let selection = seeker(&base_key, depth, value)?;
             if selection != PatternMatch::NoMatch {
                 match value {
-                    Value::Object(object) => {
-                        seek_leaf_values_in_object(object, &base_key, Depth::OnBaseKey, seeker)
-                    }
-                    Value::Array(array) => {
-                        seek_leaf_values_in_array(array, &base_key, Depth::OnBaseKey, seeker)
-                    }
+                    Value::Object(object) => seek_leaf_values_in_object_at(
+                        object, &base_key, depth, level, options, seeker,
+                    ),
+                    Value::Array(array) => seek_leaf_values_in_array_at(
+                        array, &base_key, depth, level, options, seeker,
+                    ),
                     _ => Ok(()),
                 }?;
             }
@@ -83,24 +115,52 @@ pub mod perm_json_p {
         Ok(())
     }
 
-    pub fn seek_leaf_values_in_array(
+    fn seek_leaf_values_in_array_at(
         values: &[Value],
         base_key: &str,
         base_depth: Depth,
+        level: usize,
+        options: &FlattenOptions,
         seeker: &mut impl FnMut(&str, Depth, &Value) -> Result<PatternMatch>,
     ) -> Result<()> {
         if values.is_empty() {
             seeker(base_key, base_depth, &Value::Array(vec![]))?;
         }
 
-        for value in values {
+        let array_positions = options.array_positions && options.flattens_level(level);
+        for (position, value) in values.iter().enumerate() {
             match value {
-                Value::Object(object) => {
-                    seek_leaf_values_in_object(object, base_key, Depth::InsideArray, seeker)
-                }
-                Value::Array(array) => {
-                    seek_leaf_values_in_array(array, base_key, Depth::InsideArray, seeker)
+                Value::Object(object) if array_positions => {
+                    let base_key = format!("{}{}{}", base_key, SPLIT_SYMBOL, position);
+                    let selection = seeker(&base_key, Depth::OnBaseKey, value)?;
+                    if selection != PatternMatch::NoMatch {
+                        seek_leaf_values_in_object_at(
+                            object,
+                            &base_key,
+                            Depth::OnBaseKey,
+                            level + 1,
+                            options,
+                            seeker,
+                        )?;
+                    }
+                    Ok(())
                 }
+                Value::Object(object) => seek_leaf_values_in_object_at(
+                    object,
+                    base_key,
+                    Depth::InsideArray,
+                    level,
+                    options,
+                    seeker,
+                ),
+                Value::Array(array) => seek_leaf_values_in_array_at(
+                    array,
+                    base_key,
+                    Depth::InsideArray,
+                    level,
+                    options,
+                    seeker,
+                ),
                 value => seeker(base_key, Depth::InsideArray, value).map(|_| ()),
             }?;
         }
@@ -274,6 +274,7 @@ impl WordDocidsExtractors {
             tokenizer: &tokenizer,
             localized_attributes_rules: &localized_attributes_rules,
             max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            flatten_options: indexing_context.index.flatten_options(&rtxn)?,
         };
         let extractor_data = WordDocidsExtractorData {
             tokenizer: document_tokenizer,
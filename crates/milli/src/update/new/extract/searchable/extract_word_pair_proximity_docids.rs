@@ -87,6 +87,7 @@ impl WordPairProximityDocidsExtractor {
             tokenizer: &tokenizer,
             localized_attributes_rules: &localized_attributes_rules,
             max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            flatten_options: indexing_context.index.flatten_options(&rtxn)?,
         };
         let extractor_data = WordPairProximityDocidsExtractorData {
             tokenizer: document_tokenizer,
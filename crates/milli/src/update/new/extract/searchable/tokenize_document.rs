@@ -9,8 +9,8 @@ use crate::update::new::extract::perm_json_p::{
     seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
 };
 use crate::{
-    FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result, UserError,
-    MAX_WORD_LENGTH,
+    FieldId, FlattenOptions, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result,
+    UserError, MAX_WORD_LENGTH,
 };
 
 // todo: should be crate::proximity::MAX_DISTANCE but it has been forgotten
@@ -20,6 +20,7 @@ pub struct DocumentTokenizer<'a> {
     pub tokenizer: &'a Tokenizer<'a>,
     pub localized_attributes_rules: &'a [LocalizedAttributesRule],
     pub max_positions_per_attributes: u32,
+    pub flatten_options: FlattenOptions,
 }
 
 impl<'a> DocumentTokenizer<'a> {
@@ -52,12 +53,14 @@ impl<'a> DocumentTokenizer<'a> {
                     &object,
                     field_name,
                     Depth::OnBaseKey,
+                    &self.flatten_options,
                     &mut tokenize_field,
                 )?,
                 Value::Array(array) => seek_leaf_values_in_array(
                     &array,
                     field_name,
                     Depth::OnBaseKey,
+                    &self.flatten_options,
                     &mut tokenize_field,
                 )?,
                 value => {
@@ -223,6 +226,7 @@ mod test {
             tokenizer: &tb.build(),
             localized_attributes_rules: &[],
             max_positions_per_attributes: 1000,
+            flatten_options: Default::default(),
         };
 
         let fields_ids_map = FieldIdMapWithMetadata::new(
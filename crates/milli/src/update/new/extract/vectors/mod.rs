@@ -6,6 +6,7 @@ use hashbrown::{DefaultHashBuilder, HashMap};
 
 use super::cache::DelAddRoaringBitmap;
 use crate::error::FaultSource;
+use crate::index::IndexEmbeddingConfig;
 use crate::prompt::Prompt;
 use crate::update::new::channel::EmbeddingSender;
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
@@ -15,11 +16,15 @@ use crate::update::new::DocumentChange;
 use crate::vector::error::{
     EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistributionBump,
 };
+use crate::vector::persisted_cache::{self, EmbedderFingerprint};
 use crate::vector::{Embedder, Embedding, EmbeddingConfigs};
-use crate::{DocumentId, FieldDistribution, InternalError, Result, ThreadPoolNoAbort, UserError};
+use crate::{
+    DocumentId, FieldDistribution, Index, InternalError, Result, ThreadPoolNoAbort, UserError,
+};
 
 pub struct EmbeddingExtractor<'a, 'b> {
     embedders: &'a EmbeddingConfigs,
+    fingerprints: HashMap<String, EmbedderFingerprint>,
     sender: EmbeddingSender<'a, 'b>,
     possible_embedding_mistakes: PossibleEmbeddingMistakes,
     threads: &'a ThreadPoolNoAbort,
@@ -28,12 +33,19 @@ pub struct EmbeddingExtractor<'a, 'b> {
 impl<'a, 'b> EmbeddingExtractor<'a, 'b> {
     pub fn new(
         embedders: &'a EmbeddingConfigs,
+        index_embeddings: &[IndexEmbeddingConfig],
         sender: EmbeddingSender<'a, 'b>,
         field_distribution: &'a FieldDistribution,
         threads: &'a ThreadPoolNoAbort,
     ) -> Self {
         let possible_embedding_mistakes = PossibleEmbeddingMistakes::new(field_distribution);
-        Self { embedders, sender, threads, possible_embedding_mistakes }
+        let fingerprints = index_embeddings
+            .iter()
+            .map(|config| {
+                (config.name.clone(), EmbedderFingerprint::new(&config.config.embedder_options))
+            })
+            .collect();
+        Self { embedders, fingerprints, sender, threads, possible_embedding_mistakes }
     }
 }
 
@@ -68,11 +80,20 @@ impl<'a, 'b, 'extractor> Extractor<'extractor> for EmbeddingExtractor<'a, 'b> {
                         key: None,
                     },
                 )?;
+            // user provided embeddings are never cached
+            let cache = match embedder.as_ref() {
+                Embedder::UserProvided(_) => None,
+                _ => self
+                    .fingerprints
+                    .get(embedder_name)
+                    .map(|&fingerprint| PersistedCache { index: context.index, fingerprint }),
+            };
             all_chunks.push(Chunks::new(
                 embedder,
                 embedder_id,
                 embedder_name,
                 prompt,
+                cache,
                 context.data,
                 &self.possible_embedding_mistakes,
                 self.threads,
@@ -259,6 +280,13 @@ impl<'a, 'b, 'extractor> Extractor<'extractor> for EmbeddingExtractor<'a, 'b> {
     }
 }
 
+/// The persisted embedding cache of the index, as seen by one embedder.
+#[derive(Clone, Copy)]
+struct PersistedCache<'a> {
+    index: &'a Index,
+    fingerprint: EmbedderFingerprint,
+}
+
 // **Warning**: the destructor of this struct is not normally run, make sure that all its fields:
 // 1. don't have side effects tied to they destructors
 // 2. if allocated, are allocated inside of the bumpalo
@@ -274,6 +302,7 @@ struct Chunks<'a, 'b, 'extractor> {
     embedder_id: u8,
     embedder_name: &'a str,
     prompt: &'a Prompt,
+    cache: Option<PersistedCache<'a>>,
     possible_embedding_mistakes: &'a PossibleEmbeddingMistakes,
     user_provided: &'a RefCell<EmbeddingExtractorData<'extractor>>,
     threads: &'a ThreadPoolNoAbort,
@@ -288,6 +317,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         embedder_id: u8,
         embedder_name: &'a str,
         prompt: &'a Prompt,
+        cache: Option<PersistedCache<'a>>,
         user_provided: &'a RefCell<EmbeddingExtractorData<'extractor>>,
         possible_embedding_mistakes: &'a PossibleEmbeddingMistakes,
         threads: &'a ThreadPoolNoAbort,
@@ -302,6 +332,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             ids,
             embedder,
             prompt,
+            cache,
             possible_embedding_mistakes,
             threads,
             sender,
@@ -324,9 +355,12 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.has_manual_generation.get_or_insert(external_docid);
         }
 
-        if self.texts.len() < self.texts.capacity() {
-            self.texts.push(rendered);
+        // all the chunks of a document are embedded in the same batch
+        for chunk in self.prompt.chunks(rendered) {
+            self.texts.push(chunk);
             self.ids.push(docid);
+        }
+        if self.texts.len() < self.texts.capacity() {
             return Ok(());
         }
 
@@ -336,6 +370,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.embedder,
             self.embedder_id,
             self.embedder_name,
+            self.cache,
             self.possible_embedding_mistakes,
             unused_vectors_distribution,
             self.threads,
@@ -354,6 +389,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.embedder,
             self.embedder_id,
             self.embedder_name,
+            self.cache,
             self.possible_embedding_mistakes,
             unused_vectors_distribution,
             self.threads,
@@ -372,6 +408,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         embedder: &Embedder,
         embedder_id: u8,
         embedder_name: &str,
+        cache: Option<PersistedCache<'_>>,
         possible_embedding_mistakes: &PossibleEmbeddingMistakes,
         unused_vectors_distribution: &UnusedVectorsDistributionBump,
         threads: &ThreadPoolNoAbort,
@@ -416,10 +453,49 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             return Err(crate::Error::UserError(crate::UserError::DocumentEmbeddingError(msg)));
         }
 
-        let res = match embedder.embed_index_ref(texts.as_slice(), threads) {
-            Ok(embeddings) => {
-                for (docid, embedding) in ids.into_iter().zip(embeddings) {
-                    sender.set_vector(*docid, embedder_id, embedding).unwrap();
+        // the embedding of each text, in the order of the texts
+        let mut embeddings: Vec<Option<Embedding>> = vec![None; texts.len()];
+        let mut missing = Vec::with_capacity(texts.len());
+        if let Some(cache) = cache {
+            let rtxn = cache.index.read_txn()?;
+            for (i, text) in texts.iter().enumerate() {
+                let key = cache.fingerprint.key(text);
+                match persisted_cache::get(cache.index, &rtxn, &key)? {
+                    Some(embedding) => {
+                        // write the embedding again to mark it as recently used
+                        sender.set_cached_embedding(&key, &embedding)?;
+                        embeddings[i] = Some(embedding);
+                    }
+                    None => missing.push(i),
+                }
+            }
+        } else {
+            missing.extend(0..texts.len());
+        }
+
+        let missing_texts: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+        let generated = if missing_texts.is_empty() {
+            Ok(Vec::new())
+        } else {
+            embedder.embed_index_ref(&missing_texts, threads)
+        };
+
+        let res = match generated {
+            Ok(generated) => {
+                for (i, embedding) in missing.into_iter().zip(generated) {
+                    if let Some(cache) = cache {
+                        sender
+                            .set_cached_embedding(&cache.fingerprint.key(texts[i]), &embedding)?;
+                    }
+                    embeddings[i] = Some(embedding);
+                }
+
+                // the chunks of a document are consecutive, they are sent together
+                let mut embeddings = embeddings.into_iter().map(Option::unwrap);
+                for document_ids in ids.chunk_by(|left, right| left == right) {
+                    let document_embeddings =
+                        embeddings.by_ref().take(document_ids.len()).collect();
+                    sender.set_vectors(document_ids[0], embedder_id, document_embeddings).unwrap();
                 }
                 Ok(())
             }
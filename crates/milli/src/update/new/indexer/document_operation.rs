@@ -4,16 +4,19 @@ use bumpalo::collections::CollectIn;
 use bumpalo::Bump;
 use bumparaw_collections::RawMap;
 use hashbrown::hash_map::Entry;
-use heed::RoTxn;
+use heed::{RoTxn, RwTxn};
 use memmap2::Mmap;
 use rayon::slice::ParallelSlice;
 use rustc_hash::FxBuildHasher;
+use serde::Deserialize;
 use serde_json::value::RawValue;
-use serde_json::Deserializer;
+use serde_json::{Deserializer, Value};
 
 use super::super::document_change::DocumentChange;
 use super::document_changes::{DocumentChangeContext, DocumentChanges};
 use super::guess_primary_key::retrieve_or_guess_primary_key;
+use super::{DocumentSchema, IngestFunction};
+use crate::attachments::AttachmentExtractor;
 use crate::documents::PrimaryKey;
 use crate::progress::{AtomicPayloadStep, Progress};
 use crate::update::new::document::Versions;
@@ -21,7 +24,10 @@ use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::{Deletion, Insertion, Update};
 use crate::update::{AvailableIds, IndexDocumentsMethod};
-use crate::{DocumentId, Error, FieldsIdsMap, Index, InternalError, Result, UserError};
+use crate::{
+    all_obkv_to_json, DocumentId, Error, FieldsIdsMap, Index, InternalError, Object, Result,
+    UserError,
+};
 
 #[derive(Default)]
 pub struct DocumentOperation<'pl> {
@@ -53,6 +59,47 @@ impl<'pl> DocumentOperation<'pl> {
         Ok(())
     }
 
+    /// Append JSON merge patches (RFC 7396) of documents.
+    ///
+    /// The payload is expected to be in the NDJSON format
+    pub fn merge_patch_documents(&mut self, payload: &'pl Mmap) -> Result<()> {
+        #[cfg(unix)]
+        payload.advise(memmap2::Advice::Sequential)?;
+        self.operations.push(Payload::MergePatch(&payload[..]));
+        Ok(())
+    }
+
+    /// Append an addition of the documents that do not exist yet, ignoring the other ones.
+    ///
+    /// The payload is expected to be in the NDJSON format
+    pub fn skip_existing_documents(&mut self, payload: &'pl Mmap) -> Result<()> {
+        #[cfg(unix)]
+        payload.advise(memmap2::Advice::Sequential)?;
+        self.operations.push(Payload::SkipExisting(&payload[..]));
+        Ok(())
+    }
+
+    /// Append an addition of documents that fails if one of them already exists.
+    ///
+    /// The payload is expected to be in the NDJSON format
+    pub fn fail_on_existing_documents(&mut self, payload: &'pl Mmap) -> Result<()> {
+        #[cfg(unix)]
+        payload.advise(memmap2::Advice::Sequential)?;
+        self.operations.push(Payload::FailOnExisting(&payload[..]));
+        Ok(())
+    }
+
+    /// Append JSON patches (RFC 6902) of existing documents.
+    ///
+    /// The payload is expected to be in the NDJSON format, each line being an object
+    /// with the `documentId` to patch and the list of `operations` to apply on it.
+    pub fn json_patch_documents(&mut self, payload: &'pl Mmap) -> Result<()> {
+        #[cfg(unix)]
+        payload.advise(memmap2::Advice::Sequential)?;
+        self.operations.push(Payload::JsonPatch(&payload[..]));
+        Ok(())
+    }
+
     /// Append a deletion of documents IDs.
     ///
     /// The list is a set of external documents IDs.
@@ -122,6 +169,54 @@ impl<'pl> DocumentOperation<'pl> {
                     IndexDocumentsMethod::UpdateDocuments,
                     payload,
                 ),
+                Payload::MergePatch(payload) => extract_addition_payload_changes(
+                    indexer,
+                    index,
+                    rtxn,
+                    primary_key_from_op,
+                    &mut primary_key,
+                    new_fields_ids_map,
+                    &mut available_docids,
+                    &mut bytes,
+                    &docids_version_offsets,
+                    IndexDocumentsMethod::MergePatchDocuments,
+                    payload,
+                ),
+                Payload::SkipExisting(payload) => extract_addition_payload_changes(
+                    indexer,
+                    index,
+                    rtxn,
+                    primary_key_from_op,
+                    &mut primary_key,
+                    new_fields_ids_map,
+                    &mut available_docids,
+                    &mut bytes,
+                    &docids_version_offsets,
+                    IndexDocumentsMethod::SkipExistingDocuments,
+                    payload,
+                ),
+                Payload::FailOnExisting(payload) => extract_addition_payload_changes(
+                    indexer,
+                    index,
+                    rtxn,
+                    primary_key_from_op,
+                    &mut primary_key,
+                    new_fields_ids_map,
+                    &mut available_docids,
+                    &mut bytes,
+                    &docids_version_offsets,
+                    IndexDocumentsMethod::FailOnExistingDocuments,
+                    payload,
+                ),
+                Payload::JsonPatch(payload) => extract_json_patch_payload_changes(
+                    index,
+                    rtxn,
+                    &mut primary_key,
+                    new_fields_ids_map,
+                    &mut bytes,
+                    &docids_version_offsets,
+                    payload,
+                ),
                 Payload::Deletion(to_delete) => extract_deletion_payload_changes(
                     index,
                     rtxn,
@@ -157,7 +252,32 @@ impl<'pl> DocumentOperation<'pl> {
             .sort_unstable_by_key(|(_, po)| first_update_pointer(&po.operations).unwrap_or(0));
 
         let docids_version_offsets = docids_version_offsets.into_bump_slice();
-        Ok((DocumentOperationChanges { docids_version_offsets }, operations_stats, primary_key))
+        let ingest_function = match (index.ingest_function(rtxn)?, primary_key) {
+            (Some(code), Some(primary_key)) => Some(IngestFunction::new(code, primary_key)?),
+            _ => None,
+        };
+        let document_schema = match index.document_schema(rtxn)? {
+            Some(schema) => Some(DocumentSchema::new(&schema)?),
+            None => None,
+        };
+
+        let skip_unchanged_documents = index.skip_unchanged_documents(rtxn)?;
+        let attachments = match index.attachments(rtxn)? {
+            Some(settings) => Some(AttachmentExtractor::new(settings)?),
+            None => None,
+        };
+
+        Ok((
+            DocumentOperationChanges {
+                docids_version_offsets,
+                ingest_function,
+                document_schema,
+                skip_unchanged_documents,
+                attachments,
+            },
+            operations_stats,
+            primary_key,
+        ))
     }
 }
 
@@ -175,7 +295,10 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
     method: IndexDocumentsMethod,
     payload: &'pl [u8],
 ) -> Result<hashbrown::HashMap<&'pl str, PayloadOperations<'pl>>> {
-    use IndexDocumentsMethod::{ReplaceDocuments, UpdateDocuments};
+    use IndexDocumentsMethod::{
+        FailOnExistingDocuments, JsonPatchDocuments, MergePatchDocuments, ReplaceDocuments,
+        SkipExistingDocuments, UpdateDocuments,
+    };
 
     let mut new_docids_version_offsets = hashbrown::HashMap::<&str, PayloadOperations<'pl>>::new();
 
@@ -221,6 +344,27 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
 
         let external_id = external_id.to_de();
         let current_offset = iter.byte_offset();
+
+        if matches!(method, SkipExistingDocuments | FailOnExistingDocuments) {
+            // The most recent operations on the document tell whether it still exists.
+            let operations = new_docids_version_offsets
+                .get(external_id)
+                .or_else(|| main_docids_version_offsets.get(external_id));
+            let exists = match operations {
+                Some(po) => !matches!(po.operations.last(), Some(InnerDocOp::Deletion)),
+                None => index.external_documents_ids().get(rtxn, external_id)?.is_some(),
+            };
+
+            if exists {
+                if method == FailOnExistingDocuments {
+                    let document_id = external_id.to_string();
+                    return Err(UserError::DocumentAlreadyExists { document_id }.into());
+                }
+                previous_offset = current_offset;
+                continue;
+            }
+        }
+
         let document_offset = DocumentOffset { content: &payload[previous_offset..current_offset] };
 
         match main_docids_version_offsets.get(external_id) {
@@ -228,12 +372,22 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                 match index.external_documents_ids().get(rtxn, external_id) {
                     Ok(Some(docid)) => match new_docids_version_offsets.entry(external_id) {
                         Entry::Occupied(mut entry) => match method {
-                            ReplaceDocuments => entry.get_mut().push_replacement(document_offset),
+                            ReplaceDocuments | SkipExistingDocuments | FailOnExistingDocuments => {
+                                entry.get_mut().push_replacement(document_offset)
+                            }
                             UpdateDocuments => entry.get_mut().push_update(document_offset),
+                            MergePatchDocuments => {
+                                entry.get_mut().push_merge_patch(document_offset)
+                            }
+                            JsonPatchDocuments => {
+                                unreachable!("JSON patches have their own payload")
+                            }
                         },
                         Entry::Vacant(entry) => {
                             match method {
-                                ReplaceDocuments => {
+                                ReplaceDocuments
+                                | SkipExistingDocuments
+                                | FailOnExistingDocuments => {
                                     entry.insert(PayloadOperations::new_replacement(
                                         docid,
                                         false, // is new
@@ -247,13 +401,31 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                                         document_offset,
                                     ));
                                 }
+                                MergePatchDocuments => {
+                                    entry.insert(PayloadOperations::new_merge_patch(
+                                        docid,
+                                        false, // is new
+                                        document_offset,
+                                    ));
+                                }
+                                JsonPatchDocuments => {
+                                    unreachable!("JSON patches have their own payload")
+                                }
                             }
                         }
                     },
                     Ok(None) => match new_docids_version_offsets.entry(external_id) {
                         Entry::Occupied(mut entry) => match method {
-                            ReplaceDocuments => entry.get_mut().push_replacement(document_offset),
+                            ReplaceDocuments | SkipExistingDocuments | FailOnExistingDocuments => {
+                                entry.get_mut().push_replacement(document_offset)
+                            }
                             UpdateDocuments => entry.get_mut().push_update(document_offset),
+                            MergePatchDocuments => {
+                                entry.get_mut().push_merge_patch(document_offset)
+                            }
+                            JsonPatchDocuments => {
+                                unreachable!("JSON patches have their own payload")
+                            }
                         },
                         Entry::Vacant(entry) => {
                             let docid = match available_docids.next() {
@@ -262,7 +434,9 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                             };
 
                             match method {
-                                ReplaceDocuments => {
+                                ReplaceDocuments
+                                | SkipExistingDocuments
+                                | FailOnExistingDocuments => {
                                     entry.insert(PayloadOperations::new_replacement(
                                         docid,
                                         true, // is new
@@ -276,6 +450,16 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                                         document_offset,
                                     ));
                                 }
+                                MergePatchDocuments => {
+                                    entry.insert(PayloadOperations::new_merge_patch(
+                                        docid,
+                                        true, // is new
+                                        document_offset,
+                                    ));
+                                }
+                                JsonPatchDocuments => {
+                                    unreachable!("JSON patches have their own payload")
+                                }
                             }
                         }
                     },
@@ -284,11 +468,15 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
             }
             Some(payload_operations) => match new_docids_version_offsets.entry(external_id) {
                 Entry::Occupied(mut entry) => match method {
-                    ReplaceDocuments => entry.get_mut().push_replacement(document_offset),
+                    ReplaceDocuments | SkipExistingDocuments | FailOnExistingDocuments => {
+                        entry.get_mut().push_replacement(document_offset)
+                    }
                     UpdateDocuments => entry.get_mut().push_update(document_offset),
+                    MergePatchDocuments => entry.get_mut().push_merge_patch(document_offset),
+                    JsonPatchDocuments => unreachable!("JSON patches have their own payload"),
                 },
                 Entry::Vacant(entry) => match method {
-                    ReplaceDocuments => {
+                    ReplaceDocuments | SkipExistingDocuments | FailOnExistingDocuments => {
                         entry.insert(PayloadOperations::new_replacement(
                             payload_operations.docid,
                             payload_operations.is_new,
@@ -302,6 +490,14 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                             document_offset,
                         ));
                     }
+                    MergePatchDocuments => {
+                        entry.insert(PayloadOperations::new_merge_patch(
+                            payload_operations.docid,
+                            payload_operations.is_new,
+                            document_offset,
+                        ));
+                    }
+                    JsonPatchDocuments => unreachable!("JSON patches have their own payload"),
                 },
             },
         }
@@ -330,6 +526,132 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
     Ok(new_docids_version_offsets)
 }
 
+/// A line of a JSON patch payload.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPatchLine<'pl> {
+    document_id: &'pl str,
+    #[serde(borrow)]
+    operations: &'pl RawValue,
+}
+
+fn extract_json_patch_payload_changes<'r, 'pl: 'r>(
+    index: &Index,
+    rtxn: &'r RoTxn<'r>,
+    primary_key: &mut Option<PrimaryKey<'r>>,
+    new_fields_ids_map: &mut FieldsIdsMap,
+    bytes: &mut u64,
+    main_docids_version_offsets: &hashbrown::HashMap<&'pl str, PayloadOperations<'pl>>,
+    payload: &'pl [u8],
+) -> Result<hashbrown::HashMap<&'pl str, PayloadOperations<'pl>>> {
+    let mut new_docids_version_offsets = hashbrown::HashMap::<&str, PayloadOperations<'pl>>::new();
+
+    // The documents to patch must already exist, and so must the primary key.
+    if primary_key.is_none() {
+        match retrieve_or_guess_primary_key(rtxn, index, new_fields_ids_map, None, None)? {
+            Ok((pk, _)) => *primary_key = Some(pk),
+            Err(user_error) => return Err(Error::UserError(user_error)),
+        }
+    }
+    let primary_key_name = primary_key.as_ref().map(|pk| pk.name()).unwrap_or_default();
+    let primary_key_field = primary_key_name.split('.').next().unwrap_or_default();
+
+    let mut iter = Deserializer::from_slice(payload).into_iter::<JsonPatchLine>();
+    while let Some(line) = iter.next().transpose().map_err(InternalError::SerdeJson)? {
+        let JsonPatchLine { document_id, operations } = line;
+        let invalid_patch = |error: &str| UserError::InvalidDocumentJsonPatch {
+            document_id: document_id.to_string(),
+            error: error.to_string(),
+        };
+
+        let patch: json_patch::Patch =
+            serde_json::from_str(operations.get()).map_err(|e| invalid_patch(&e.to_string()))?;
+        let raw_operations: Vec<Object> =
+            serde_json::from_str(operations.get()).map_err(InternalError::SerdeJson)?;
+        for operation in &raw_operations {
+            let op = operation.get("op").and_then(Value::as_str).unwrap_or_default();
+            let path = operation.get("path").and_then(Value::as_str).unwrap_or_default();
+            if op == "test" {
+                continue;
+            }
+
+            let mut modified_fields = vec![path];
+            if op == "move" {
+                modified_fields.extend(operation.get("from").and_then(Value::as_str));
+            }
+            for pointer in modified_fields {
+                match first_pointer_token(pointer) {
+                    None => {
+                        return Err(invalid_patch("the whole document cannot be modified").into())
+                    }
+                    Some(field) if field == primary_key_field => {
+                        return Err(invalid_patch("the primary key cannot be modified").into())
+                    }
+                    Some(_) => (),
+                }
+            }
+
+            // The top-level fields that appear must be known by the global fields ids map.
+            if op != "remove" {
+                if let Some(field) = first_pointer_token(path) {
+                    new_fields_ids_map.insert(&field).ok_or(UserError::AttributeLimitReached)?;
+                }
+            }
+        }
+
+        let document_offset = DocumentOffset { content: operations.get().as_bytes() };
+        match main_docids_version_offsets.get(document_id) {
+            Some(payload_operations) => {
+                if matches!(payload_operations.operations.last(), Some(InnerDocOp::Deletion)) {
+                    return Err(invalid_patch("the document does not exist").into());
+                }
+                match new_docids_version_offsets.entry(document_id) {
+                    Entry::Occupied(mut entry) => entry.get_mut().push_json_patch(document_offset),
+                    Entry::Vacant(entry) => {
+                        entry.insert(PayloadOperations::new_json_patch(
+                            payload_operations.docid,
+                            payload_operations.is_new,
+                            document_offset,
+                        ));
+                    }
+                }
+            }
+            None => match new_docids_version_offsets.entry(document_id) {
+                Entry::Occupied(mut entry) => entry.get_mut().push_json_patch(document_offset),
+                Entry::Vacant(entry) => {
+                    let Some(docid) = index.external_documents_ids().get(rtxn, document_id)? else {
+                        return Err(invalid_patch("the document does not exist").into());
+                    };
+
+                    // The patch is directly applied on the stored version of the document to
+                    // report the failing operations on this task rather than on the whole batch.
+                    let document = index.document(rtxn, docid)?;
+                    let mut document =
+                        Value::Object(all_obkv_to_json(document, new_fields_ids_map)?);
+                    json_patch::patch(&mut document, &patch.0)
+                        .map_err(|e| invalid_patch(&e.to_string()))?;
+
+                    entry.insert(PayloadOperations::new_json_patch(
+                        docid,
+                        false, // is new
+                        document_offset,
+                    ));
+                }
+            },
+        }
+
+        *bytes = iter.byte_offset() as u64;
+    }
+
+    Ok(new_docids_version_offsets)
+}
+
+/// Returns the unescaped first reference token of a JSON pointer, `None` for the whole document.
+fn first_pointer_token(pointer: &str) -> Option<String> {
+    let token = pointer.strip_prefix('/')?.split('/').next()?;
+    Some(token.replace("~1", "/").replace("~0", "~"))
+}
+
 fn extract_deletion_payload_changes<'s, 'pl: 's>(
     index: &Index,
     rtxn: &RoTxn,
@@ -424,7 +746,28 @@ impl<'pl> DocumentChanges<'pl> for DocumentOperationChanges<'pl> {
         'pl: 'doc,
     {
         let (external_doc, payload_operations) = item;
-        payload_operations.merge(external_doc, &context.doc_alloc)
+        let ingest_function = self.ingest_function.as_ref();
+        let document_schema = self.document_schema.as_ref();
+        let skip_unchanged = self.skip_unchanged_documents && !payload_operations.is_new;
+        let attachments = self.attachments.as_ref();
+        if payload_operations.needs_patching()
+            || ((ingest_function.is_some()
+                || document_schema.is_some()
+                || skip_unchanged
+                || attachments.is_some())
+                && !payload_operations.is_deletion())
+        {
+            payload_operations.apply_patches(
+                external_doc,
+                context,
+                ingest_function,
+                document_schema,
+                skip_unchanged,
+                attachments,
+            )
+        } else {
+            payload_operations.merge(external_doc, &context.doc_alloc)
+        }
     }
 
     fn len(&self) -> usize {
@@ -434,11 +777,52 @@ impl<'pl> DocumentChanges<'pl> for DocumentOperationChanges<'pl> {
 
 pub struct DocumentOperationChanges<'pl> {
     docids_version_offsets: &'pl [(&'pl str, PayloadOperations<'pl>)],
+    /// The function of the index transforming the documents before they are indexed.
+    ingest_function: Option<IngestFunction<'pl>>,
+    /// The JSON Schema of the index the documents must match to be indexed.
+    document_schema: Option<DocumentSchema>,
+    /// Whether the documents that didn't change are compared to their stored version and skipped.
+    skip_unchanged_documents: bool,
+    /// The extraction of the text of the attachments of the documents.
+    attachments: Option<AttachmentExtractor>,
+}
+
+impl DocumentOperationChanges<'_> {
+    /// Records the current version of the documents modified or deleted by the operations as
+    /// their most recent revision, when the index keeps the revisions of its documents.
+    ///
+    /// Must be called before the documents are indexed, with the write transaction indexing them.
+    pub fn record_document_revisions(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        index: &Index,
+        fields_ids_map: &FieldsIdsMap,
+    ) -> Result<()> {
+        if index.max_document_revisions(wtxn)?.map_or(true, |max| max == 0) {
+            return Ok(());
+        }
+
+        for (external_id, payload_operations) in self.docids_version_offsets {
+            if !payload_operations.is_new {
+                index.record_document_revision(
+                    wtxn,
+                    external_id,
+                    payload_operations.docid,
+                    fields_ids_map,
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 pub enum Payload<'pl> {
     Replace(&'pl [u8]),
     Update(&'pl [u8]),
+    MergePatch(&'pl [u8]),
+    JsonPatch(&'pl [u8]),
+    SkipExisting(&'pl [u8]),
+    FailOnExisting(&'pl [u8]),
     Deletion(&'pl [&'pl str]),
 }
 
@@ -466,6 +850,14 @@ impl<'pl> PayloadOperations<'pl> {
         Self { docid, is_new, operations: vec![InnerDocOp::Update(offset)] }
     }
 
+    fn new_merge_patch(docid: DocumentId, is_new: bool, offset: DocumentOffset<'pl>) -> Self {
+        Self { docid, is_new, operations: vec![InnerDocOp::MergePatch(offset)] }
+    }
+
+    fn new_json_patch(docid: DocumentId, is_new: bool, offset: DocumentOffset<'pl>) -> Self {
+        Self { docid, is_new, operations: vec![InnerDocOp::JsonPatch(offset)] }
+    }
+
     fn new_deletion(docid: DocumentId, is_new: bool) -> Self {
         Self { docid, is_new, operations: vec![InnerDocOp::Deletion] }
     }
@@ -481,6 +873,14 @@ impl<'pl> PayloadOperations<'pl> {
         self.operations.push(InnerDocOp::Update(offset))
     }
 
+    fn push_merge_patch(&mut self, offset: DocumentOffset<'pl>) {
+        self.operations.push(InnerDocOp::MergePatch(offset))
+    }
+
+    fn push_json_patch(&mut self, offset: DocumentOffset<'pl>) {
+        self.operations.push(InnerDocOp::JsonPatch(offset))
+    }
+
     fn push_deletion(&mut self) {
         self.operations.clear();
         self.operations.push(InnerDocOp::Deletion);
@@ -494,6 +894,155 @@ impl<'pl> PayloadOperations<'pl> {
         self.operations.append(&mut operations);
     }
 
+    /// Returns the position of the last operation that resets the document, if any.
+    fn last_tombstone(&self) -> Option<usize> {
+        self.operations
+            .iter()
+            .rposition(|op| matches!(op, InnerDocOp::Deletion | InnerDocOp::Replace(_)))
+    }
+
+    /// Whether the document must be patched after the last operation that resets it.
+    fn needs_patching(&self) -> bool {
+        let operations = match self.last_tombstone() {
+            Some(i) => &self.operations[i..],
+            None => &self.operations[..],
+        };
+        operations
+            .iter()
+            .any(|op| matches!(op, InnerDocOp::MergePatch(_) | InnerDocOp::JsonPatch(_)))
+    }
+
+    /// Returns `true` if the last operation on this document is a deletion.
+    fn is_deletion(&self) -> bool {
+        matches!(self.operations.last(), Some(InnerDocOp::Deletion))
+    }
+
+    /// Returns the most recent version of a document by applying its operations one after the other,
+    /// starting from the version of the document in the database.
+    ///
+    /// The merge patches and JSON patches can remove nested attributes, so the whole document
+    /// is computed here and handled as a replacement, like the documents edited by a function.
+    ///
+    /// When `skip_unchanged` is set, the document is compared to its version in the database even
+    /// if it was replaced, and nothing is indexed when they are equal.
+    fn apply_patches<'doc, T: MostlySend + 'doc>(
+        &self,
+        external_doc: &'doc str,
+        context: &'doc DocumentChangeContext<T>,
+        ingest_function: Option<&IngestFunction>,
+        document_schema: Option<&DocumentSchema>,
+        skip_unchanged: bool,
+        attachments: Option<&AttachmentExtractor>,
+    ) -> Result<Option<DocumentChange<'doc>>>
+    where
+        'pl: 'doc,
+    {
+        let DocumentChangeContext { index, db_fields_ids_map, rtxn, doc_alloc, .. } = context;
+
+        let last_tombstone = self.last_tombstone();
+        let previous_document = if self.is_new || (last_tombstone.is_some() && !skip_unchanged) {
+            None
+        } else {
+            let document = index.document(rtxn, self.docid)?;
+            Some(all_obkv_to_json(document, db_fields_ids_map)?)
+        };
+        let (mut document, operations) = match last_tombstone {
+            Some(i) => match self.operations[i] {
+                InnerDocOp::Deletion => (Object::new(), &self.operations[i + 1..]),
+                _ => (Object::new(), &self.operations[i..]),
+            },
+            None => (previous_document.clone().unwrap_or_default(), &self.operations[..]),
+        };
+
+        for operation in operations {
+            match operation {
+                InnerDocOp::Replace(DocumentOffset { content }) => {
+                    document = serde_json::from_slice(content).map_err(InternalError::SerdeJson)?;
+                }
+                InnerDocOp::Update(DocumentOffset { content }) => {
+                    let version: Object =
+                        serde_json::from_slice(content).map_err(InternalError::SerdeJson)?;
+                    document.extend(version);
+                }
+                InnerDocOp::MergePatch(DocumentOffset { content }) => {
+                    let patch: Object =
+                        serde_json::from_slice(content).map_err(InternalError::SerdeJson)?;
+                    merge_patch(&mut document, patch);
+                }
+                InnerDocOp::JsonPatch(DocumentOffset { content }) => {
+                    let patch: json_patch::Patch =
+                        serde_json::from_slice(content).map_err(InternalError::SerdeJson)?;
+                    let mut patched = Value::Object(std::mem::take(&mut document));
+                    json_patch::patch(&mut patched, &patch.0).map_err(|e| {
+                        UserError::InvalidDocumentJsonPatch {
+                            document_id: external_doc.to_string(),
+                            error: e.to_string(),
+                        }
+                    })?;
+                    document = match patched {
+                        Value::Object(document) => document,
+                        _ => return Err(UserError::DocumentEditionDocumentMustBeObject.into()),
+                    };
+                }
+                InnerDocOp::Deletion => unreachable!("Deletion in document operations"),
+            }
+        }
+
+        if let Some(attachments) = attachments {
+            attachments.extract(external_doc, &mut document)?;
+        }
+
+        if let Some(ingest_function) = ingest_function {
+            document = match ingest_function.apply(document)? {
+                Some(document) => document,
+                // The function removed the document, we must not index it.
+                None if self.is_new => return Ok(None),
+                None => {
+                    return Ok(Some(DocumentChange::Deletion(Deletion::create(
+                        self.docid,
+                        external_doc,
+                    ))))
+                }
+            };
+        }
+
+        if let Some(document_schema) = document_schema {
+            let checked = Value::Object(document);
+            document_schema.check(external_doc, &checked)?;
+            let Value::Object(checked) = checked else { unreachable!() };
+            document = checked;
+        }
+
+        if previous_document.as_ref() == Some(&document) {
+            return Ok(None);
+        }
+
+        let mut buffer = bumpalo::collections::Vec::new_in(doc_alloc);
+        serde_json::to_writer(&mut buffer, &document).map_err(InternalError::SerdeJson)?;
+        let document =
+            serde_json::from_slice(buffer.into_bump_slice()).map_err(InternalError::SerdeJson)?;
+        if let Some(ingest_function) = ingest_function {
+            ingest_function.check_document(external_doc, document, context)?;
+        }
+        let document = RawMap::from_raw_value_and_hasher(document, FxBuildHasher, doc_alloc)
+            .map_err(InternalError::SerdeJson)?;
+
+        if self.is_new {
+            Ok(Some(DocumentChange::Insertion(Insertion::create(
+                self.docid,
+                external_doc,
+                Versions::single(document),
+            ))))
+        } else {
+            Ok(Some(DocumentChange::Update(Update::create(
+                self.docid,
+                external_doc,
+                Versions::single(document),
+                true, // It is like document replacement
+            ))))
+        }
+    }
+
     /// Returns only the most recent version of a document based on the updates from the payloads.
     ///
     /// This function is only meant to be used when doing a replacement and not an update.
@@ -542,7 +1091,11 @@ impl<'pl> PayloadOperations<'pl> {
                     Some(i) => match self.operations[i] {
                         InnerDocOp::Deletion => &self.operations[i + 1..],
                         InnerDocOp::Replace(_) => &self.operations[i..],
-                        InnerDocOp::Update(_) => unreachable!("Found a non-tombstone operation"),
+                        InnerDocOp::Update(_)
+                        | InnerDocOp::MergePatch(_)
+                        | InnerDocOp::JsonPatch(_) => {
+                            unreachable!("Found a non-tombstone operation")
+                        }
                     },
                     None => &self.operations[..],
                 };
@@ -551,6 +1104,9 @@ impl<'pl> PayloadOperations<'pl> {
                 let versions = operations.iter().map(|operation| {
                     let DocumentOffset { content } = match operation {
                         InnerDocOp::Replace(offset) | InnerDocOp::Update(offset) => offset,
+                        InnerDocOp::MergePatch(_) | InnerDocOp::JsonPatch(_) => {
+                            unreachable!("Patch in document updates")
+                        }
                         InnerDocOp::Deletion => unreachable!("Deletion in document operations"),
                     };
 
@@ -579,6 +1135,9 @@ impl<'pl> PayloadOperations<'pl> {
                     ))))
                 }
             }
+            Some(InnerDocOp::MergePatch(_) | InnerDocOp::JsonPatch(_)) => {
+                unreachable!("Patches are applied separately")
+            }
             Some(InnerDocOp::Deletion) => {
                 return if self.is_new {
                     Ok(None)
@@ -596,9 +1155,36 @@ impl<'pl> PayloadOperations<'pl> {
 pub enum InnerDocOp<'pl> {
     Replace(DocumentOffset<'pl>),
     Update(DocumentOffset<'pl>),
+    MergePatch(DocumentOffset<'pl>),
+    /// The JSON patch operations (RFC 6902) to apply on the document.
+    JsonPatch(DocumentOffset<'pl>),
     Deletion,
 }
 
+/// Applies a JSON Merge Patch (RFC 7396) on a document.
+///
+/// The attributes set to `null` in the patch are removed and the nested objects are merged recursively.
+fn merge_patch(document: &mut Object, patch: Object) {
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                document.shift_remove(&key);
+            }
+            Value::Object(patch) => match document.get_mut(&key) {
+                Some(Value::Object(target)) => merge_patch(target, patch),
+                _ => {
+                    let mut target = Object::new();
+                    merge_patch(&mut target, patch);
+                    document.insert(key, Value::Object(target));
+                }
+            },
+            value => {
+                document.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Represents an offset where a document lives
 /// in an mmapped grenad reader file.
 #[derive(Clone)]
@@ -614,7 +1200,9 @@ pub struct DocumentOffset<'pl> {
 pub fn first_update_pointer(docops: &[InnerDocOp]) -> Option<usize> {
     docops.iter().find_map(|ido: &_| match ido {
         InnerDocOp::Replace(replace) => Some(replace.content.as_ptr() as usize),
-        InnerDocOp::Update(update) => Some(update.content.as_ptr() as usize),
+        InnerDocOp::Update(update)
+        | InnerDocOp::MergePatch(update)
+        | InnerDocOp::JsonPatch(update) => Some(update.content.as_ptr() as usize),
         InnerDocOp::Deletion => None,
     })
 }
@@ -0,0 +1,34 @@
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::{Result, UserError};
+
+/// The JSON Schema of an index, checking the documents before they are indexed.
+pub struct DocumentSchema {
+    validator: Validator,
+}
+
+impl DocumentSchema {
+    /// Compiles the schema, the references to remote schemas are not resolved.
+    pub fn new(schema: &Value) -> Result<Self> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| UserError::InvalidDocumentSchema(e.to_string()))?;
+        Ok(DocumentSchema { validator })
+    }
+
+    /// Returns an error describing the first violation of the schema by the document, if any.
+    pub fn check(&self, external_doc: &str, document: &Value) -> Result<()> {
+        match self.validator.validate(document) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                let path = e.instance_path.to_string();
+                let error = if path.is_empty() { e.to_string() } else { format!("`{path}`: {e}") };
+                Err(UserError::DocumentDoesNotMatchSchema {
+                    document_id: external_doc.to_string(),
+                    error,
+                }
+                .into())
+            }
+        }
+    }
+}
@@ -229,6 +229,7 @@ where
         let embedding_sender = extractor_sender.embeddings();
         let extractor = EmbeddingExtractor::new(
             embedders,
+            &index_embeddings,
             embedding_sender,
             field_distribution,
             request_threads(),
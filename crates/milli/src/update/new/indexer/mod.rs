@@ -6,10 +6,12 @@ use big_s::S;
 use document_changes::{DocumentChanges, IndexingContext};
 pub use document_deletion::DocumentDeletion;
 pub use document_operation::{DocumentOperation, PayloadStats};
+pub use document_schema::DocumentSchema;
 use hashbrown::HashMap;
 use heed::RwTxn;
 pub use partial_dump::PartialDump;
-pub use update_by_function::UpdateByFunction;
+pub use update_by_function::{validate_function, IngestFunction, UpdateByFunction};
+pub use validate_documents::{validate_documents, DocumentDiagnostics, DocumentsValidation};
 pub use write::ChannelCongestion;
 use write::{build_vectors, update_index, write_to_db};
 
@@ -20,18 +22,20 @@ use crate::documents::PrimaryKey;
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::progress::Progress;
 use crate::update::GrenadParameters;
-use crate::vector::{ArroyWrapper, EmbeddingConfigs};
+use crate::vector::{usage, ArroyWrapper, EmbeddingConfigs};
 use crate::{FieldsIdsMap, GlobalFieldsIdsMap, Index, InternalError, Result, ThreadPoolNoAbort};
 
 pub(crate) mod de;
 pub mod document_changes;
 mod document_deletion;
 mod document_operation;
+mod document_schema;
 mod extract;
 mod guess_primary_key;
 mod partial_dump;
 mod post_processing;
 mod update_by_function;
+mod validate_documents;
 mod write;
 
 static LOG_MEMORY_METRICS_ONCE: Once = Once::new();
@@ -61,6 +65,7 @@ where
 {
     let mut bbbuffers = Vec::new();
     let finished_extraction = AtomicBool::new(false);
+    let usage_before = usage::indexing_usage(&embedders);
 
     let arroy_memory = grenad_parameters.max_memory;
 
@@ -165,6 +170,7 @@ where
         let global_fields_ids_map = GlobalFieldsIdsMap::new(&new_fields_ids_map);
 
         let vector_arroy = index.vector_arroy;
+        let full_precision_vectors = index.full_precision_vectors;
         let arroy_writers: Result<HashMap<_, _>> = embedders
             .inner_as_ref()
             .iter()
@@ -177,7 +183,12 @@ where
                 )?;
 
                 let dimensions = embedder.dimensions();
-                let writer = ArroyWrapper::new(vector_arroy, embedder_index, *was_quantized);
+                let writer = ArroyWrapper::new(
+                    vector_arroy,
+                    full_precision_vectors,
+                    embedder_index,
+                    *was_quantized,
+                );
 
                 Ok((
                     embedder_index,
@@ -226,6 +237,8 @@ where
     drop(fields_ids_map_store);
 
     let new_fields_ids_map = new_fields_ids_map.into_inner().unwrap();
+    let embedder_usage = usage::indexing_usage_since(&embedders, &usage_before);
+    index.add_embedder_usage(wtxn, &embedder_usage)?;
     update_index(
         index,
         wtxn,
@@ -20,6 +20,7 @@ use crate::update::new::words_prefix_docids::{
 };
 use crate::update::new::FacetFieldIdsDelta;
 use crate::update::{FacetsUpdateBulk, GrenadParameters};
+use crate::vector::persisted_cache;
 use crate::{GlobalFieldsIdsMap, Index, Result};
 
 pub(super) fn post_process<MSP>(
@@ -39,6 +40,7 @@ where
     if let Some(prefix_delta) = compute_word_fst(index, wtxn)? {
         compute_prefix_database(index, wtxn, prefix_delta, indexing_context.grenad_parameters)?;
     };
+    persisted_cache::evict(index, wtxn)?;
     Ok(())
 }
 
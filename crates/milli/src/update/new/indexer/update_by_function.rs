@@ -4,6 +4,7 @@ use rayon::slice::ParallelSlice as _;
 use rhai::{Dynamic, Engine, OptimizationLevel, Scope, AST};
 use roaring::RoaringBitmap;
 use rustc_hash::FxBuildHasher;
+use serde_json::value::RawValue;
 
 use super::document_changes::DocumentChangeContext;
 use super::DocumentChanges;
@@ -43,19 +44,7 @@ impl UpdateByFunction {
     ) -> Result<UpdateByFunctionChanges<'index>> {
         let Self { documents, context, code } = self;
 
-        // Setup the security and limits of the Engine
-        let mut engine = Engine::new();
-        engine.set_optimization_level(OptimizationLevel::Full);
-        engine.set_max_call_levels(1000);
-        // It is an arbitrary value. We need to let users define this in the settings.
-        engine.set_max_operations(1_000_000);
-        engine.set_max_variables(1000);
-        engine.set_max_functions(30);
-        engine.set_max_expr_depths(100, 1000);
-        engine.set_max_string_size(1024 * 1024 * 1024); // 1 GiB
-        engine.set_max_array_size(10_000);
-        engine.set_max_map_size(10_000);
-
+        let engine = new_engine();
         let ast = engine.compile(code).map_err(UserError::DocumentEditionCompilationError)?;
         let context = match context {
             Some(context) => {
@@ -189,6 +178,85 @@ impl<'index> DocumentChanges<'index> for UpdateByFunctionChanges<'index> {
     }
 }
 
+/// The function of an index transforming the documents before they are indexed.
+pub struct IngestFunction<'pl> {
+    primary_key: PrimaryKey<'pl>,
+    engine: Engine,
+    ast: AST,
+}
+
+impl<'pl> IngestFunction<'pl> {
+    pub fn new(code: &str, primary_key: PrimaryKey<'pl>) -> Result<Self> {
+        let engine = new_engine();
+        let ast = engine.compile(code).map_err(UserError::DocumentEditionCompilationError)?;
+        Ok(IngestFunction { primary_key, engine, ast })
+    }
+
+    /// Runs the function on the document, returns `None` if the function removed the document.
+    pub fn apply(&self, document: Object) -> Result<Option<Object>> {
+        let rhai_document: Dynamic =
+            serde_json::from_value(document.into()).map_err(InternalError::SerdeJson)?;
+
+        let mut scope = Scope::new();
+        scope.push_dynamic("doc", rhai_document);
+        let _ = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast)
+            .map_err(UserError::DocumentEditionRuntimeError)?;
+
+        match scope.remove::<Dynamic>("doc") {
+            Some(doc) if doc.is_unit() => Ok(None),
+            None => unreachable!("missing doc variable from the Rhai scope"),
+            Some(new_document) => match new_document.try_cast() {
+                Some(new_rhai_document) => Ok(Some(rhaimap_to_object(new_rhai_document))),
+                None => Err(Error::UserError(UserError::DocumentEditionDocumentMustBeObject)),
+            },
+        }
+    }
+
+    /// Registers the fields of the transformed document and makes sure that
+    /// the function didn't modify its primary key.
+    pub fn check_document<'doc, T: MostlySend + 'doc>(
+        &self,
+        external_doc: &str,
+        document: &'doc RawValue,
+        context: &'doc DocumentChangeContext<T>,
+    ) -> Result<()> {
+        let mut global_fields_ids_map = context.new_fields_ids_map.borrow_mut_or_yield();
+        let document_id = self
+            .primary_key
+            .extract_fields_and_docid(document, &mut *global_fields_ids_map, &context.doc_alloc)?
+            .to_de();
+
+        if document_id != external_doc {
+            Err(Error::UserError(UserError::DocumentEditionCannotModifyPrimaryKey))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns an engine with the security and limits of the functions editing the documents.
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::Full);
+    engine.set_max_call_levels(1000);
+    // It is an arbitrary value. We need to let users define this in the settings.
+    engine.set_max_operations(1_000_000);
+    engine.set_max_variables(1000);
+    engine.set_max_functions(30);
+    engine.set_max_expr_depths(100, 1000);
+    engine.set_max_string_size(1024 * 1024 * 1024); // 1 GiB
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine
+}
+
+/// Makes sure that the code of a function editing the documents compiles.
+pub fn validate_function(code: &str) -> std::result::Result<(), rhai::ParseError> {
+    new_engine().compile(code).map(drop)
+}
+
 fn obkv_to_rhaimap(obkv: &KvReaderFieldId, fields_ids_map: &FieldsIdsMap) -> Result<rhai::Map> {
     let all_keys = obkv.iter().map(|(k, _v)| k).collect::<Vec<_>>();
     let map: Result<rhai::Map> = all_keys
@@ -0,0 +1,327 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use bumpalo::Bump;
+use bumparaw_collections::RawMap;
+use heed::RoTxn;
+use rustc_hash::FxBuildHasher;
+use serde_json::value::RawValue;
+use serde_json::{Deserializer, Value};
+
+use super::guess_primary_key::retrieve_or_guess_primary_key;
+use super::DocumentSchema;
+use crate::constants::{
+    RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
+use crate::filterable_attributes_rules::matching_features;
+use crate::geojson::Geometry;
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::{BytesRefCodec, StrRefCodec};
+use crate::prompt::Prompt;
+use crate::search::facet::get_first_facet_value;
+use crate::update::new::document::{DocumentFromVersions, Versions};
+use crate::update::new::extract::extract_geo_points;
+use crate::vector::EmbedderOptions;
+use crate::{
+    FieldId, FieldsIdsMap, FilterableAttributesRule, FlattenOptions, GlobalFieldsIdsMap, Index,
+    InternalError, Object, Result, UserError,
+};
+
+/// The outcome of the validation of a payload of documents, see [`validate_documents`].
+#[derive(Debug)]
+pub struct DocumentsValidation {
+    /// The primary key of the index, or the one that would be inferred from the payload.
+    pub primary_key: String,
+    /// The number of documents in the payload.
+    pub documents_count: usize,
+    /// The diagnostics of the documents having at least one error or warning.
+    pub diagnostics: Vec<DocumentDiagnostics>,
+}
+
+/// The problems found in a single document of the payload.
+#[derive(Debug)]
+pub struct DocumentDiagnostics {
+    /// The position of the document in the payload, starting at zero.
+    pub position: usize,
+    /// The external id of the document, when it could be extracted.
+    pub document_id: Option<String>,
+    /// The errors that would make the indexing of the payload fail.
+    pub errors: Vec<crate::Error>,
+    /// The values that would be indexed but are unlikely to behave as expected.
+    pub warnings: Vec<String>,
+}
+
+/// Checks a payload of documents against the current settings of the index, without indexing them.
+///
+/// The payload is a sequence of JSON objects, like the update files of the document additions.
+/// The primary key is retrieved or inferred exactly like an addition would do, and an error is
+/// returned when it can't be. Each document is then checked for:
+/// - a missing or invalid document id,
+/// - an invalid `_geo` or `_geojson` field when these fields are filterable or sortable,
+/// - a violation of the `documentSchema` of the index,
+/// - a failure to render the document template of the embedders,
+/// - a filterable or sortable value whose type differs from the values already in the index.
+pub fn validate_documents(
+    rtxn: &RoTxn<'_>,
+    index: &Index,
+    primary_key_from_op: Option<&str>,
+    payload: &[u8],
+) -> Result<DocumentsValidation> {
+    let indexer = Bump::new();
+    let mut new_fields_ids_map = index.fields_ids_map(rtxn)?;
+
+    let mut iter = Deserializer::from_slice(payload).into_iter::<&RawValue>();
+    let first_document = match iter.next().transpose().map_err(InternalError::SerdeJson)? {
+        Some(doc) => Some(
+            RawMap::from_raw_value_and_hasher(doc, FxBuildHasher, &indexer)
+                .map_err(UserError::SerdeJson)?,
+        ),
+        None => None,
+    };
+    let primary_key = match retrieve_or_guess_primary_key(
+        rtxn,
+        index,
+        &mut new_fields_ids_map,
+        primary_key_from_op,
+        first_document,
+    )? {
+        Ok((primary_key, _has_changed)) => primary_key,
+        Err(user_error) => return Err(user_error.into()),
+    };
+
+    let checker = DocumentChecker::new(rtxn, index)?;
+    let fields_ids_map = RwLock::new(index.fields_ids_map_with_metadata(rtxn)?);
+    let global_fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map));
+
+    let mut doc_alloc = Bump::new();
+    let mut documents_count = 0;
+    let mut diagnostics = Vec::new();
+    let iter = Deserializer::from_slice(payload).into_iter::<&RawValue>();
+    for (position, doc) in iter.enumerate() {
+        let doc = doc.map_err(InternalError::SerdeJson)?;
+        documents_count += 1;
+        doc_alloc.reset();
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        let document_id =
+            match primary_key.extract_fields_and_docid(doc, &mut new_fields_ids_map, &doc_alloc) {
+                Ok(document_id) => Some(document_id.to_bump(&doc_alloc).to_string()),
+                Err(error) => {
+                    errors.push(error);
+                    None
+                }
+            };
+        let external_id = document_id.as_deref().unwrap_or_default();
+
+        checker.check(
+            external_id,
+            doc,
+            &global_fields_ids_map,
+            &doc_alloc,
+            &mut errors,
+            &mut warnings,
+        )?;
+
+        if !errors.is_empty() || !warnings.is_empty() {
+            diagnostics.push(DocumentDiagnostics { position, document_id, errors, warnings });
+        }
+    }
+
+    Ok(DocumentsValidation {
+        primary_key: primary_key.name().to_string(),
+        documents_count,
+        diagnostics,
+    })
+}
+
+/// The kind of facet values a field holds in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FacetKind {
+    Number,
+    String,
+}
+
+impl FacetKind {
+    fn of(value: &Value) -> Option<FacetKind> {
+        match value {
+            Value::Number(_) => Some(FacetKind::Number),
+            Value::String(_) | Value::Bool(_) => Some(FacetKind::String),
+            _ => None,
+        }
+    }
+
+    fn plural(self) -> &'static str {
+        match self {
+            FacetKind::Number => "numbers",
+            FacetKind::String => "strings",
+        }
+    }
+}
+
+/// The settings of the index a document is checked against.
+struct DocumentChecker<'t> {
+    rtxn: &'t RoTxn<'t>,
+    index: &'t Index,
+    db_fields_ids_map: FieldsIdsMap,
+    filterable_attributes: Vec<FilterableAttributesRule>,
+    sortable_fields: HashSet<String>,
+    flatten_options: FlattenOptions,
+    is_geo_enabled: bool,
+    is_geojson_enabled: bool,
+    document_schema: Option<DocumentSchema>,
+    prompts: Vec<Prompt>,
+    /// The only kind of facet values each field holds in the index, if any.
+    facet_kinds: RefCell<HashMap<FieldId, Option<FacetKind>>>,
+}
+
+impl<'t> DocumentChecker<'t> {
+    fn new(rtxn: &'t RoTxn<'t>, index: &'t Index) -> Result<Self> {
+        let document_schema = match index.document_schema(rtxn)? {
+            Some(schema) => Some(DocumentSchema::new(&schema)?),
+            None => None,
+        };
+
+        let mut prompts = Vec::new();
+        for config in index.embedding_configs(rtxn)? {
+            if matches!(config.config.embedder_options, EmbedderOptions::UserProvided(_)) {
+                continue;
+            }
+            let prompt = Prompt::try_from(config.config.prompt)
+                .map_err(|error| UserError::InvalidPromptForEmbeddings(config.name, error))?;
+            prompts.push(prompt);
+        }
+
+        Ok(DocumentChecker {
+            rtxn,
+            index,
+            db_fields_ids_map: index.fields_ids_map(rtxn)?,
+            filterable_attributes: index.filterable_attributes_rules(rtxn)?,
+            sortable_fields: index.sortable_fields(rtxn)?,
+            flatten_options: index.flatten_options(rtxn)?,
+            is_geo_enabled: index.is_geo_enabled(rtxn)?,
+            is_geojson_enabled: index.is_geojson_enabled(rtxn)?,
+            document_schema,
+            prompts,
+            facet_kinds: RefCell::new(HashMap::new()),
+        })
+    }
+
+    fn check<'doc>(
+        &self,
+        external_id: &str,
+        doc: &'doc RawValue,
+        global_fields_ids_map: &RefCell<GlobalFieldsIdsMap>,
+        doc_alloc: &'doc Bump,
+        errors: &mut Vec<crate::Error>,
+        warnings: &mut Vec<String>,
+    ) -> Result<()> {
+        let (map, object) = match RawMap::from_raw_value_and_hasher(doc, FxBuildHasher, doc_alloc)
+            .and_then(|map| Ok((map, serde_json::from_str::<Object>(doc.get())?)))
+        {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                errors.push(UserError::SerdeJson(error).into());
+                return Ok(());
+            }
+        };
+        let versions = Versions::single(map);
+
+        if self.is_geo_enabled {
+            if let Some(geo) = versions.geo_field() {
+                if let Err(error) = extract_geo_points(external_id, geo) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        if self.is_geojson_enabled {
+            if let Some(geojson) = object.get(RESERVED_GEOJSON_FIELD_NAME) {
+                if !geojson.is_null() {
+                    if let Err(error) = Geometry::from_value(geojson.clone()) {
+                        let document_id = external_id.to_string();
+                        errors.push(UserError::InvalidGeoJsonField { document_id, error }.into());
+                    }
+                }
+            }
+        }
+
+        let document = DocumentFromVersions::new(&versions);
+        for prompt in &self.prompts {
+            if let Err(error) =
+                prompt.render_document(external_id, &document, global_fields_ids_map, doc_alloc)
+            {
+                errors.push(error.into());
+            }
+        }
+
+        let flattened = flatten_serde_json::flatten_with_options(&object, self.flatten_options);
+        for (field, value) in &flattened {
+            // the reserved fields are checked above or are not faceted
+            let top_level = field.split('.').next().unwrap_or_default();
+            if [RESERVED_GEO_FIELD_NAME, RESERVED_GEOJSON_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME]
+                .contains(&top_level)
+            {
+                continue;
+            }
+
+            let is_filterable = matching_features(field, &self.filterable_attributes)
+                .is_some_and(|(_, features)| features.is_filterable());
+            let is_sortable = self.sortable_fields.contains(field);
+            if !is_filterable && !is_sortable {
+                continue;
+            }
+
+            let Some(expected) = self.facet_kind_in_index(field)? else { continue };
+            let values = match value {
+                Value::Array(values) => values.as_slice(),
+                value => std::slice::from_ref(value),
+            };
+            if let Some(found) =
+                values.iter().filter_map(FacetKind::of).find(|kind| *kind != expected)
+            {
+                let attribute = if is_sortable { "sortable" } else { "filterable" };
+                warnings.push(format!(
+                    "The {attribute} attribute `{field}` holds {} while the documents of the index only hold {} for it.",
+                    found.plural(),
+                    expected.plural(),
+                ));
+            }
+        }
+
+        if let Some(schema) = &self.document_schema {
+            if let Err(error) = schema.check(external_id, &Value::Object(object)) {
+                errors.push(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the kind of facet values of the field when the index only holds one kind of them.
+    fn facet_kind_in_index(&self, field: &str) -> Result<Option<FacetKind>> {
+        let Some(field_id) = self.db_fields_ids_map.id(field) else { return Ok(None) };
+        if let Some(kind) = self.facet_kinds.borrow().get(&field_id) {
+            return Ok(*kind);
+        }
+
+        let numbers =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let strings =
+            self.index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let has_numbers =
+            get_first_facet_value::<OrderedF64Codec, _>(self.rtxn, numbers, field_id)?.is_some();
+        let has_strings =
+            get_first_facet_value::<StrRefCodec, _>(self.rtxn, strings, field_id)?.is_some();
+        let kind = match (has_numbers, has_strings) {
+            (true, false) => Some(FacetKind::Number),
+            (false, true) => Some(FacetKind::String),
+            _ => None,
+        };
+
+        self.facet_kinds.borrow_mut().insert(field_id, kind);
+        Ok(kind)
+    }
+}
@@ -39,15 +39,14 @@ where
             let extracted_geo_point = result?;
             let removed = rtree.remove(&GeoPoint::from(extracted_geo_point));
             debug_assert!(removed.is_some());
-            let removed = faceted.remove(extracted_geo_point.docid);
-            debug_assert!(removed);
+            // a document with several geo points is removed once per point
+            faceted.remove(extracted_geo_point.docid);
         }
 
         for result in frozen.iter_and_clear_inserted()? {
             let extracted_geo_point = result?;
             rtree.insert(GeoPoint::from(extracted_geo_point));
-            let inserted = faceted.insert(extracted_geo_point.docid);
-            debug_assert!(inserted);
+            faceted.insert(extracted_geo_point.docid);
         }
     }
 
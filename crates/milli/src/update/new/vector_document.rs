@@ -119,8 +119,12 @@ impl<'t> VectorDocumentFromDb<'t> {
         embedder_id: u8,
         config: &IndexEmbeddingConfig,
     ) -> Result<VectorEntry<'t>> {
-        let reader =
-            ArroyWrapper::new(self.index.vector_arroy, embedder_id, config.config.quantized());
+        let reader = ArroyWrapper::new(
+            self.index.vector_arroy,
+            self.index.full_precision_vectors,
+            embedder_id,
+            config.config.quantized(),
+        );
         let vectors = reader.item_vectors(self.rtxn, self.docid)?;
 
         Ok(VectorEntry {
@@ -13,7 +13,9 @@ use time::OffsetDateTime;
 
 use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
+use super::new::indexer::{validate_function, DocumentSchema};
 use super::IndexerConfig;
+use crate::attachments::{AttachmentExtractor, AttachmentsSettings};
 use crate::attribute_patterns::PatternMatch;
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::criterion::Criterion;
@@ -29,11 +31,12 @@ use crate::prompt::default_max_bytes;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::vector::reranker::{RerankerOptions, RerankerSettings};
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
+use crate::vector::{usage, Embedder, EmbeddingConfig, EmbeddingConfigs};
 use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -183,6 +186,15 @@ pub struct Settings<'a, 't, 'i> {
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    sensitive_matching: Setting<bool>,
+    reranker: Setting<RerankerSettings>,
+    flatten_max_depth: Setting<usize>,
+    flatten_array_positions: Setting<bool>,
+    ingest_function: Setting<String>,
+    document_schema: Setting<serde_json::Value>,
+    skip_unchanged_documents: Setting<bool>,
+    attachments: Setting<AttachmentsSettings>,
+    max_document_revisions: Setting<u64>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -220,6 +232,15 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            sensitive_matching: Setting::NotSet,
+            reranker: Setting::NotSet,
+            flatten_max_depth: Setting::NotSet,
+            flatten_array_positions: Setting::NotSet,
+            ingest_function: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            skip_unchanged_documents: Setting::NotSet,
+            attachments: Setting::NotSet,
+            max_document_revisions: Setting::NotSet,
             indexer_config,
         }
     }
@@ -442,6 +463,78 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.facet_search = Setting::Reset;
     }
 
+    pub fn set_sensitive_matching(&mut self, value: bool) {
+        self.sensitive_matching = Setting::Set(value);
+    }
+
+    pub fn reset_sensitive_matching(&mut self) {
+        self.sensitive_matching = Setting::Reset;
+    }
+
+    pub fn set_reranker(&mut self, value: RerankerSettings) {
+        self.reranker = Setting::Set(value);
+    }
+
+    pub fn reset_reranker(&mut self) {
+        self.reranker = Setting::Reset;
+    }
+
+    pub fn set_flatten_max_depth(&mut self, value: usize) {
+        self.flatten_max_depth = Setting::Set(value);
+    }
+
+    pub fn reset_flatten_max_depth(&mut self) {
+        self.flatten_max_depth = Setting::Reset;
+    }
+
+    pub fn set_flatten_array_positions(&mut self, value: bool) {
+        self.flatten_array_positions = Setting::Set(value);
+    }
+
+    pub fn reset_flatten_array_positions(&mut self) {
+        self.flatten_array_positions = Setting::Reset;
+    }
+
+    pub fn set_ingest_function(&mut self, value: String) {
+        self.ingest_function = Setting::Set(value);
+    }
+
+    pub fn reset_ingest_function(&mut self) {
+        self.ingest_function = Setting::Reset;
+    }
+
+    pub fn set_document_schema(&mut self, value: serde_json::Value) {
+        self.document_schema = Setting::Set(value);
+    }
+
+    pub fn reset_document_schema(&mut self) {
+        self.document_schema = Setting::Reset;
+    }
+
+    pub fn set_skip_unchanged_documents(&mut self, value: bool) {
+        self.skip_unchanged_documents = Setting::Set(value);
+    }
+
+    pub fn reset_skip_unchanged_documents(&mut self) {
+        self.skip_unchanged_documents = Setting::Reset;
+    }
+
+    pub fn set_attachments(&mut self, value: AttachmentsSettings) {
+        self.attachments = Setting::Set(value);
+    }
+
+    pub fn reset_attachments(&mut self) {
+        self.attachments = Setting::Reset;
+    }
+
+    pub fn set_max_document_revisions(&mut self, value: u64) {
+        self.max_document_revisions = Setting::Set(value);
+    }
+
+    pub fn reset_max_document_revisions(&mut self) {
+        self.max_document_revisions = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -471,6 +564,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             false,
         )?;
 
+        let embedders = settings_diff.new.embedding_configs.clone();
+        let usage_before = usage::indexing_usage(&embedders);
+
         // We clear the databases and remap the documents fields based on the new `FieldsIdsMap`.
         let output = transform.prepare_for_documents_reindexing(self.wtxn, settings_diff)?;
 
@@ -487,6 +583,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
 
         indexing_builder.execute_raw(output)?;
 
+        let embedder_usage = usage::indexing_usage_since(&embedders, &usage_before);
+        self.index.add_embedder_usage(self.wtxn, &embedder_usage)?;
+
         Ok(())
     }
 
@@ -1193,6 +1292,170 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_sensitive_matching(&mut self) -> Result<bool> {
+        let changed = match self.sensitive_matching {
+            Setting::Set(new) => {
+                let old = self.index.sensitive_matching(self.wtxn)?;
+                if old == new {
+                    false
+                } else {
+                    self.index.put_sensitive_matching(self.wtxn, new)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_sensitive_matching(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_reranker(&mut self) -> Result<bool> {
+        let changed = match &self.reranker {
+            Setting::Set(new) => {
+                let new = RerankerOptions::try_from(new.clone())?;
+                let old = self.index.reranker(self.wtxn)?;
+                if old.as_ref() == Some(&new) {
+                    false
+                } else {
+                    self.index.put_reranker(self.wtxn, &new)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_reranker(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_flattening(&mut self) -> Result<()> {
+        let old = self.index.flatten_options(self.wtxn)?;
+        let mut new = old;
+        match self.flatten_max_depth {
+            Setting::Set(max_depth) => new.max_depth = Some(max_depth),
+            Setting::Reset => new.max_depth = None,
+            Setting::NotSet => (),
+        }
+        match self.flatten_array_positions {
+            Setting::Set(array_positions) => new.array_positions = array_positions,
+            Setting::Reset => new.array_positions = false,
+            Setting::NotSet => (),
+        }
+
+        if old == new {
+            return Ok(());
+        }
+        // The documents would need to be flattened again with their old and new fields.
+        if self.index.number_of_documents(self.wtxn)? != 0 {
+            return Err(UserError::FlatteningCannotBeChanged.into());
+        }
+
+        match new.max_depth {
+            Some(max_depth) => self.index.put_flatten_max_depth(self.wtxn, max_depth as u64)?,
+            None => {
+                self.index.delete_flatten_max_depth(self.wtxn)?;
+            }
+        }
+        if new.array_positions {
+            self.index.put_flatten_array_positions(self.wtxn, true)?;
+        } else {
+            self.index.delete_flatten_array_positions(self.wtxn)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_ingest_function(&mut self) -> Result<()> {
+        match &self.ingest_function {
+            Setting::Set(new) => {
+                let old = self.index.ingest_function(self.wtxn)?;
+                if old != Some(new.as_str()) {
+                    validate_function(new).map_err(UserError::InvalidIngestFunction)?;
+                    self.index.put_ingest_function(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_ingest_function(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_document_schema(&mut self) -> Result<()> {
+        match &self.document_schema {
+            Setting::Set(new) => {
+                let old = self.index.document_schema(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    DocumentSchema::new(new)?;
+                    self.index.put_document_schema(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_document_schema(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_skip_unchanged_documents(&mut self) -> Result<()> {
+        match self.skip_unchanged_documents {
+            Setting::Set(new) => {
+                let old = self.index.skip_unchanged_documents(self.wtxn)?;
+                if old != new {
+                    self.index.put_skip_unchanged_documents(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_skip_unchanged_documents(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_attachments(&mut self) -> Result<()> {
+        match &self.attachments {
+            Setting::Set(new) => {
+                let old = self.index.attachments(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    AttachmentExtractor::new(new.clone())?;
+                    self.index.put_attachments(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_attachments(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_max_document_revisions(&mut self) -> Result<()> {
+        match self.max_document_revisions {
+            Setting::Set(new) => {
+                let old = self.index.max_document_revisions(self.wtxn)?;
+                if old != Some(new) {
+                    self.index.put_max_document_revisions(self.wtxn, new)?;
+                    self.index.truncate_document_revisions(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_max_document_revisions(self.wtxn)?;
+                self.index.clear_document_revisions(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_localized_attributes_rules(&mut self) -> Result<()> {
         match &self.localized_attributes_rules {
             Setting::Set(new) => {
@@ -1224,6 +1487,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_distinct_field()?;
         self.update_criteria()?;
         self.update_primary_key()?;
+        self.update_flattening()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
         self.update_exact_words()?;
@@ -1231,6 +1495,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_sensitive_matching()?;
+        self.update_reranker()?;
+        self.update_ingest_function()?;
+        self.update_document_schema()?;
+        self.update_skip_unchanged_documents()?;
+        self.update_attachments()?;
+        self.update_max_document_revisions()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1671,6 +1942,25 @@ fn validate_prompt(
     }
 }
 
+fn validate_chunk_bytes(name: &str, chunk_bytes: &Setting<usize>) -> Result<()> {
+    if let Some(0) = chunk_bytes.set() {
+        return Err(crate::error::UserError::InvalidSettingsDocumentTemplateChunkBytes {
+            embedder_name: name.to_owned(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+pub fn validate_reranker_settings(
+    settings: Setting<RerankerSettings>,
+) -> Result<Setting<RerankerSettings>> {
+    if let Setting::Set(settings) = &settings {
+        RerankerOptions::try_from(settings.clone())?;
+    }
+    Ok(settings)
+}
+
 pub fn validate_embedding_settings(
     settings: Setting<EmbeddingSettings>,
     name: &str,
@@ -1685,6 +1975,7 @@ pub fn validate_embedding_settings(
         dimensions,
         document_template,
         document_template_max_bytes,
+        document_template_chunk_bytes,
         url,
         request,
         response,
@@ -1696,6 +1987,7 @@ pub fn validate_embedding_settings(
     } = settings;
 
     let document_template = validate_prompt(name, document_template, document_template_max_bytes)?;
+    validate_chunk_bytes(name, &document_template_chunk_bytes)?;
 
     if let Some(0) = dimensions.set() {
         return Err(crate::error::UserError::InvalidSettingsDimensions {
@@ -1732,6 +2024,7 @@ pub fn validate_embedding_settings(
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url,
             request,
             response,
@@ -1756,6 +2049,7 @@ pub fn validate_embedding_settings(
         &response,
         &document_template,
         &document_template_max_bytes,
+        &document_template_chunk_bytes,
         &headers,
         &search_embedder,
         &indexing_embedder,
@@ -1794,10 +2088,52 @@ pub fn validate_embedding_settings(
                 }
             }
         }
+        EmbedderSource::Cohere => {
+            if let Setting::Set(dimensions) = dimensions {
+                let model = model
+                    .as_ref()
+                    .set()
+                    .map_or(crate::vector::cohere::DEFAULT_MODEL, |model| model.as_str());
+                if let Some(supported_dimensions) =
+                    crate::vector::cohere::supported_dimensions(model)
+                {
+                    if !supported_dimensions.contains(&dimensions) {
+                        return Err(crate::error::UserError::InvalidCohereModelDimensions {
+                            embedder_name: name.to_owned(),
+                            model: model.to_owned(),
+                            dimensions,
+                            supported_dimensions,
+                        }
+                        .into());
+                    }
+                }
+            }
+        }
         EmbedderSource::Ollama
         | EmbedderSource::HuggingFace
         | EmbedderSource::UserProvided
-        | EmbedderSource::Rest => {}
+        | EmbedderSource::Rest
+        | EmbedderSource::AzureOpenAi
+        | EmbedderSource::Onnx => {}
+        EmbedderSource::Bedrock => {
+            if let Setting::Set(model) = &model {
+                if crate::vector::bedrock::ModelFamily::from_model(model).is_none() {
+                    return Err(crate::error::UserError::InvalidBedrockModel {
+                        embedder_name: name.to_owned(),
+                        model: model.clone(),
+                    }
+                    .into());
+                }
+            }
+            if let Setting::Set(api_key) = &api_key {
+                if crate::vector::bedrock::Credentials::parse(api_key).is_none() {
+                    return Err(crate::error::UserError::InvalidBedrockApiKey {
+                        embedder_name: name.to_owned(),
+                    }
+                    .into());
+                }
+            }
+        }
         EmbedderSource::Composite => {
             if let Setting::Set(embedder) = &search_embedder {
                 if let Some(source) = embedder.source.set() {
@@ -1834,6 +2170,7 @@ pub fn validate_embedding_settings(
                         &embedder.response,
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
+                        &embedder.document_template_chunk_bytes,
                         &embedder.headers,
                         &search_embedder,
                         &indexing_embedder,
@@ -1854,6 +2191,7 @@ pub fn validate_embedding_settings(
                     embedder.document_template,
                     embedder.document_template_max_bytes,
                 )?;
+                validate_chunk_bytes(name, &embedder.document_template_chunk_bytes)?;
 
                 if let Some(source) = embedder.source.set() {
                     let search_embedder = match embedder.search_embedder.clone() {
@@ -1889,6 +2227,7 @@ pub fn validate_embedding_settings(
                         &embedder.response,
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
+                        &embedder.document_template_chunk_bytes,
                         &embedder.headers,
                         &search_embedder,
                         &indexing_embedder,
@@ -1916,6 +2255,7 @@ pub fn validate_embedding_settings(
         dimensions,
         document_template,
         document_template_max_bytes,
+        document_template_chunk_bytes,
         url,
         request,
         response,
@@ -896,6 +896,15 @@ fn test_correct_settings_init() {
                 localized_attributes_rules,
                 prefix_search,
                 facet_search,
+                sensitive_matching,
+                reranker,
+                flatten_max_depth,
+                flatten_array_positions,
+                ingest_function,
+                document_schema,
+                skip_unchanged_documents,
+                attachments,
+                max_document_revisions,
             } = settings;
             assert!(matches!(searchable_fields, Setting::NotSet));
             assert!(matches!(displayed_fields, Setting::NotSet));
@@ -923,6 +932,15 @@ fn test_correct_settings_init() {
             assert!(matches!(localized_attributes_rules, Setting::NotSet));
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
+            assert!(matches!(sensitive_matching, Setting::NotSet));
+            assert!(matches!(reranker, Setting::NotSet));
+            assert!(matches!(flatten_max_depth, Setting::NotSet));
+            assert!(matches!(flatten_array_positions, Setting::NotSet));
+            assert!(matches!(ingest_function, Setting::NotSet));
+            assert!(matches!(document_schema, Setting::NotSet));
+            assert!(matches!(skip_unchanged_documents, Setting::NotSet));
+            assert!(matches!(attachments, Setting::NotSet));
+            assert!(matches!(max_document_revisions, Setting::NotSet));
         })
         .unwrap();
 }
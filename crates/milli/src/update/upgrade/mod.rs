@@ -3,6 +3,7 @@ mod v1_13;
 mod v1_14;
 
 use heed::RwTxn;
+use serde::Serialize;
 use v1_12::{V1_12_3_To_V1_13_0, V1_12_To_V1_12_3};
 use v1_13::{V1_13_0_To_V1_13_1, V1_13_1_To_Latest_V1_13};
 use v1_14::Latest_V1_13_To_Latest_V1_14;
@@ -20,24 +21,41 @@ trait UpgradeIndex {
         progress: Progress,
     ) -> Result<bool>;
     fn target_version(&self) -> (u32, u32, u32);
+    /// The internal structures of the index rewritten by this step.
+    fn changes(&self) -> &'static [&'static str];
+    fn cost(&self) -> UpgradeCost;
 }
 
-/// Return true if the cached stats of the index must be regenerated
-pub fn upgrade(
-    wtxn: &mut RwTxn,
-    index: &Index,
-    db_version: (u32, u32, u32),
-    progress: Progress,
-) -> Result<bool> {
-    let from = index.get_version(wtxn)?.unwrap_or(db_version);
-    let upgrade_functions: &[&dyn UpgradeIndex] = &[
-        &V1_12_To_V1_12_3 {},
-        &V1_12_3_To_V1_13_0 {},
-        &V1_13_0_To_V1_13_1 {},
-        &V1_13_1_To_Latest_V1_13 {},
-        &Latest_V1_13_To_Latest_V1_14 {},
-    ];
+/// What an upgrade step must go through, used to estimate how long it takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpgradeCost {
+    /// Only a handful of entries are rewritten, whatever the size of the index.
+    Constant,
+    /// Every document of the index is read.
+    Documents,
+    /// Every embedding of the index is rewritten.
+    Embeddings,
+}
+
+/// A step of the upgrade path of an index, as returned by [`upgrade_plan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeStep {
+    pub from: (u32, u32, u32),
+    pub to: (u32, u32, u32),
+    pub changes: &'static [&'static str],
+    pub cost: UpgradeCost,
+}
+
+const UPGRADE_FUNCTIONS: &[&dyn UpgradeIndex] = &[
+    &V1_12_To_V1_12_3 {},
+    &V1_12_3_To_V1_13_0 {},
+    &V1_13_0_To_V1_13_1 {},
+    &V1_13_1_To_Latest_V1_13 {},
+    &Latest_V1_13_To_Latest_V1_14 {},
+];
 
+fn upgrade_path(from: (u32, u32, u32)) -> Result<&'static [&'static dyn UpgradeIndex]> {
     let start = match from {
         (1, 12, 0..=2) => 0,
         (1, 12, 3..) => 1,
@@ -50,8 +68,38 @@ pub fn upgrade(
         }
     };
 
+    Ok(&UPGRADE_FUNCTIONS[start..])
+}
+
+/// Returns the steps an index at version `from` would go through when upgraded, without touching it.
+pub fn upgrade_plan(from: (u32, u32, u32)) -> Result<Vec<UpgradeStep>> {
+    let mut current_version = from;
+    let mut steps = Vec::new();
+    for upgrade in upgrade_path(from)? {
+        let target = upgrade.target_version();
+        steps.push(UpgradeStep {
+            from: current_version,
+            to: target,
+            changes: upgrade.changes(),
+            cost: upgrade.cost(),
+        });
+        current_version = target;
+    }
+
+    Ok(steps)
+}
+
+/// Return true if the cached stats of the index must be regenerated
+pub fn upgrade(
+    wtxn: &mut RwTxn,
+    index: &Index,
+    db_version: (u32, u32, u32),
+    progress: Progress,
+) -> Result<bool> {
+    let from = index.get_version(wtxn)?.unwrap_or(db_version);
+
     enum UpgradeVersion {}
-    let upgrade_path = &upgrade_functions[start..];
+    let upgrade_path = upgrade_path(from)?;
 
     let mut current_version = from;
     let mut regenerate_stats = false;
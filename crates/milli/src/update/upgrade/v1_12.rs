@@ -1,6 +1,6 @@
 use heed::RwTxn;
 
-use super::UpgradeIndex;
+use super::{UpgradeCost, UpgradeIndex};
 use crate::progress::Progress;
 use crate::{make_enum_progress, Index, Result};
 
@@ -28,6 +28,14 @@ impl UpgradeIndex for V1_12_To_V1_12_3 {
     fn target_version(&self) -> (u32, u32, u32) {
         (1, 12, 3)
     }
+
+    fn changes(&self) -> &'static [&'static str] {
+        &["fieldDistribution", "indexStats"]
+    }
+
+    fn cost(&self) -> UpgradeCost {
+        UpgradeCost::Documents
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -48,4 +56,12 @@ impl UpgradeIndex for V1_12_3_To_V1_13_0 {
     fn target_version(&self) -> (u32, u32, u32) {
         (1, 13, 0)
     }
+
+    fn changes(&self) -> &'static [&'static str] {
+        &["indexStats"]
+    }
+
+    fn cost(&self) -> UpgradeCost {
+        UpgradeCost::Constant
+    }
 }
@@ -1,6 +1,6 @@
 use heed::RwTxn;
 
-use super::UpgradeIndex;
+use super::{UpgradeCost, UpgradeIndex};
 use crate::constants::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
 use crate::database_stats::DatabaseStats;
 use crate::progress::Progress;
@@ -34,6 +34,14 @@ impl UpgradeIndex for V1_13_0_To_V1_13_1 {
     fn target_version(&self) -> (u32, u32, u32) {
         (1, 13, 1)
     }
+
+    fn changes(&self) -> &'static [&'static str] {
+        &["documentsStats", "indexStats"]
+    }
+
+    fn cost(&self) -> UpgradeCost {
+        UpgradeCost::Documents
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -57,4 +65,12 @@ impl UpgradeIndex for V1_13_1_To_Latest_V1_13 {
             VERSION_PATCH.parse().unwrap(),
         )
     }
+
+    fn changes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn cost(&self) -> UpgradeCost {
+        UpgradeCost::Constant
+    }
 }
@@ -1,7 +1,7 @@
 use arroy::distances::Cosine;
 use heed::RwTxn;
 
-use super::UpgradeIndex;
+use super::{UpgradeCost, UpgradeIndex};
 use crate::progress::Progress;
 use crate::{make_enum_progress, Index, Result};
 
@@ -38,4 +38,12 @@ impl UpgradeIndex for Latest_V1_13_To_Latest_V1_14 {
     fn target_version(&self) -> (u32, u32, u32) {
         (1, 14, 0)
     }
+
+    fn changes(&self) -> &'static [&'static str] {
+        &["vectorStore"]
+    }
+
+    fn cost(&self) -> UpgradeCost {
+        UpgradeCost::Embeddings
+    }
 }
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+use rayon::slice::ParallelSlice as _;
+
+use super::error::{EmbedError, EmbedErrorKind, NewEmbedderError};
+use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
+use super::usage::EmbedderUsage;
+use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
+use crate::error::FaultSource;
+use crate::vector::Embedding;
+use crate::ThreadPoolNoAbort;
+
+/// The `api-version` used when the endpoint doesn't specify one.
+pub const DEFAULT_API_VERSION: &str = "2024-10-21";
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// The endpoint of the Azure OpenAI resource, such as `https://{resource-name}.openai.azure.com`.
+    ///
+    /// An `api-version` query parameter can be passed to override the default API version.
+    pub url: String,
+    /// The name of the deployment of the embedding model in the resource.
+    pub deployment: String,
+    /// Sent in the `api-key` header.
+    pub api_key: Option<String>,
+    /// Additional headers, such as an `Authorization` header to authenticate with a Microsoft Entra ID token.
+    pub headers: BTreeMap<String, String>,
+    pub dimensions: Option<usize>,
+    pub distribution: Option<DistributionShift>,
+}
+
+impl EmbedderOptions {
+    /// The URL of the embeddings route of the deployment.
+    pub fn embeddings_url(&self) -> String {
+        let (endpoint, query) = self.url.split_once('?').unwrap_or((&self.url, ""));
+        let api_version = url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "api-version")
+            .map(|(_, api_version)| api_version.into_owned())
+            .unwrap_or_else(|| DEFAULT_API_VERSION.to_owned());
+        let endpoint = endpoint.trim_end_matches('/');
+        format!(
+            "{endpoint}/openai/deployments/{}/embeddings?api-version={api_version}",
+            self.deployment
+        )
+    }
+
+    fn into_rest_embedder_config(self) -> RestEmbedderOptions {
+        let url = self.embeddings_url();
+
+        let mut request = serde_json::json!({
+            "input": [super::rest::REQUEST_PLACEHOLDER, super::rest::REPEAT_PLACEHOLDER]
+        });
+        if let Some(dimensions) = self.dimensions {
+            request["dimensions"] = dimensions.into();
+        }
+
+        let mut headers = self.headers;
+        // An `Authorization` header means that the user authenticates with Microsoft Entra ID
+        let api_key = match self.api_key {
+            Some(api_key) => Some(api_key),
+            None if headers.keys().any(|header| header.eq_ignore_ascii_case("authorization")) => {
+                None
+            }
+            None => infer_api_key(),
+        };
+        if let Some(api_key) = api_key {
+            headers.insert("api-key".to_owned(), api_key);
+        }
+
+        RestEmbedderOptions {
+            // Azure doesn't use the `Authorization: Bearer` scheme for its API keys
+            api_key: None,
+            dimensions: self.dimensions,
+            distribution: self.distribution,
+            url,
+            request,
+            response: serde_json::json!({
+                "data": [{
+                    "embedding":
+                    super::rest::RESPONSE_PLACEHOLDER
+                },
+                super::rest::REPEAT_PLACEHOLDER
+                ]
+            }),
+            headers,
+        }
+    }
+}
+
+fn infer_api_key() -> Option<String> {
+    std::env::var("MEILI_AZURE_OPENAI_API_KEY")
+        .or_else(|_| std::env::var("AZURE_OPENAI_API_KEY"))
+        .ok()
+        .filter(|api_key| !api_key.is_empty())
+}
+
+#[derive(Debug)]
+pub struct Embedder {
+    rest_embedder: RestEmbedder,
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions, cache_cap: usize) -> Result<Self, NewEmbedderError> {
+        let rest_embedder = RestEmbedder::new(
+            options.into_rest_embedder_config(),
+            cache_cap,
+            super::rest::ConfigurationSource::AzureOpenAi,
+        )?;
+
+        Ok(Self { rest_embedder })
+    }
+
+    pub fn embed<S: AsRef<str> + serde::Serialize>(
+        &self,
+        texts: &[S],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        self.rest_embedder.embed_ref(texts, deadline)
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<Embedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks.into_par_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<f32>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed(chunk, None))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed(chunk, None))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        self.rest_embedder.chunk_count_hint()
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        self.rest_embedder.prompt_count_in_chunk_hint()
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.rest_embedder.dimensions()
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.rest_embedder.distribution()
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        self.rest_embedder.cache()
+    }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        self.rest_embedder.indexing_usage()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn options(url: &str) -> EmbedderOptions {
+        EmbedderOptions {
+            url: url.to_owned(),
+            deployment: "my-deployment".to_owned(),
+            api_key: None,
+            headers: Default::default(),
+            dimensions: None,
+            distribution: None,
+        }
+    }
+
+    #[test]
+    fn embeddings_url() {
+        insta::assert_snapshot!(options("https://my-resource.openai.azure.com").embeddings_url(), @"https://my-resource.openai.azure.com/openai/deployments/my-deployment/embeddings?api-version=2024-10-21");
+        insta::assert_snapshot!(options("https://my-resource.openai.azure.com/").embeddings_url(), @"https://my-resource.openai.azure.com/openai/deployments/my-deployment/embeddings?api-version=2024-10-21");
+        insta::assert_snapshot!(options("https://my-resource.openai.azure.com?api-version=2023-05-15").embeddings_url(), @"https://my-resource.openai.azure.com/openai/deployments/my-deployment/embeddings?api-version=2023-05-15");
+    }
+}
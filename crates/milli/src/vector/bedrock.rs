@@ -0,0 +1,523 @@
+use std::fmt;
+use std::time::Instant;
+
+use hmac::{Hmac, Mac};
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+use rayon::slice::ParallelSlice as _;
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use super::error::{EmbedError, EmbedErrorKind, NewEmbedderError};
+use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
+use super::usage::EmbedderUsage;
+use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
+use crate::error::FaultSource;
+use crate::vector::Embedding;
+use crate::ThreadPoolNoAbort;
+
+/// The region used when neither the `url` nor the environment specify one.
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// The name of the service in the scope of the SigV4 signatures.
+const SERVICE: &str = "bedrock";
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// The endpoint of the Bedrock runtime, such as `https://bedrock-runtime.us-east-1.amazonaws.com`.
+    pub url: Option<String>,
+    /// The credentials, in the `<accessKeyId>:<secretAccessKey>[:<sessionToken>]` format.
+    pub api_key: Option<String>,
+    /// The identifier of the model in Bedrock, such as `amazon.titan-embed-text-v2:0`.
+    pub embedding_model: String,
+    pub dimensions: Option<usize>,
+    pub distribution: Option<DistributionShift>,
+}
+
+/// The families of models whose API is known to Meilisearch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFamily {
+    /// `amazon.titan-embed-text-*`, embedding a single text per request.
+    Titan,
+    /// `cohere.embed-*`, embedding several texts per request.
+    Cohere,
+}
+
+impl ModelFamily {
+    pub fn supported_models() -> &'static [&'static str] {
+        &["amazon.titan-embed-text-*", "cohere.embed-*"]
+    }
+
+    pub fn from_model(model: &str) -> Option<Self> {
+        if model.starts_with("amazon.titan-embed-text") {
+            Some(ModelFamily::Titan)
+        } else if model.starts_with("cohere.embed") {
+            Some(ModelFamily::Cohere)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether the documents or the search queries are embedded.
+///
+/// Only Cohere models embed them differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputType {
+    SearchDocument,
+    SearchQuery,
+}
+
+impl InputType {
+    fn name(&self) -> &'static str {
+        match self {
+            InputType::SearchDocument => "search_document",
+            InputType::SearchQuery => "search_query",
+        }
+    }
+}
+
+impl EmbedderOptions {
+    fn endpoint(&self) -> String {
+        match &self.url {
+            Some(url) => url.trim_end_matches('/').to_owned(),
+            None => format!("https://bedrock-runtime.{}.amazonaws.com", infer_region()),
+        }
+    }
+
+    /// The URL of the route invoking the model.
+    pub fn invoke_url(&self) -> String {
+        // the model identifiers of Bedrock can contain `:`, that must be encoded in the path
+        let model = self.embedding_model.replace(':', "%3A");
+        format!("{}/model/{model}/invoke", self.endpoint())
+    }
+
+    fn into_rest_embedder_config(
+        &self,
+        family: ModelFamily,
+        input_type: InputType,
+        dimensions: Option<usize>,
+    ) -> RestEmbedderOptions {
+        let (request, response) = match family {
+            ModelFamily::Titan => {
+                let mut request = serde_json::json!({
+                    "inputText": super::rest::REQUEST_PLACEHOLDER,
+                });
+                // only the second version of the Titan models supports choosing the dimensions
+                if !self.embedding_model.starts_with("amazon.titan-embed-text-v1") {
+                    if let Some(dimensions) = self.dimensions {
+                        request["dimensions"] = dimensions.into();
+                    }
+                }
+                (request, serde_json::json!({ "embedding": super::rest::RESPONSE_PLACEHOLDER }))
+            }
+            ModelFamily::Cohere => (
+                serde_json::json!({
+                    "texts": [super::rest::REQUEST_PLACEHOLDER, super::rest::REPEAT_PLACEHOLDER],
+                    "input_type": input_type.name(),
+                }),
+                serde_json::json!({
+                    "embeddings": [super::rest::RESPONSE_PLACEHOLDER, super::rest::REPEAT_PLACEHOLDER]
+                }),
+            ),
+        };
+
+        RestEmbedderOptions {
+            // authentication is done by signing the requests
+            api_key: None,
+            distribution: self.distribution,
+            dimensions,
+            url: self.invoke_url(),
+            request,
+            response,
+            headers: Default::default(),
+        }
+    }
+}
+
+fn infer_region() -> String {
+    std::env::var("AWS_REGION")
+        .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+        .ok()
+        .filter(|region| !region.is_empty())
+        .unwrap_or_else(|| DEFAULT_REGION.to_owned())
+}
+
+/// The AWS credentials used to sign the requests.
+#[derive(Clone)]
+pub struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Parses credentials in the `<accessKeyId>:<secretAccessKey>[:<sessionToken>]` format.
+    pub fn parse(api_key: &str) -> Option<Self> {
+        let mut parts = api_key.splitn(3, ':');
+        let access_key_id = parts.next().filter(|part| !part.is_empty())?;
+        let secret_access_key = parts.next().filter(|part| !part.is_empty())?;
+        let session_token = parts.next().filter(|part| !part.is_empty());
+        Some(Self {
+            access_key_id: access_key_id.to_owned(),
+            secret_access_key: secret_access_key.to_owned(),
+            session_token: session_token.map(ToOwned::to_owned),
+        })
+    }
+
+    fn from_env() -> Option<Self> {
+        let access_key_id = std::env::var("AWS_ACCESS_KEY_ID").ok()?;
+        let secret_access_key = std::env::var("AWS_SECRET_ACCESS_KEY").ok()?;
+        let session_token = std::env::var("AWS_SESSION_TOKEN").ok().filter(|s| !s.is_empty());
+        Some(Self { access_key_id, secret_access_key, session_token })
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Signs the requests sent to Bedrock with the [AWS Signature Version 4](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_aws-signing.html).
+///
+/// As the signature depends on the body and on the time of the request, each request must be signed when it is sent.
+#[derive(Debug, Clone)]
+pub struct SigV4Signer {
+    credentials: Credentials,
+    region: String,
+}
+
+impl SigV4Signer {
+    pub fn new(credentials: Credentials, region: String) -> Self {
+        Self { credentials, region }
+    }
+
+    /// The headers to add to a `POST` request of `body` with a JSON content type to `url`.
+    pub fn sign(&self, url: &str, body: &[u8]) -> Vec<(&'static str, String)> {
+        self.sign_at(url, body, OffsetDateTime::now_utc())
+    }
+
+    fn sign_at(&self, url: &str, body: &[u8], now: OffsetDateTime) -> Vec<(&'static str, String)> {
+        let amz_date = now
+            .format(time::macros::format_description!("[year][month][day]T[hour][minute][second]Z"))
+            .unwrap();
+        let date = &amz_date[..8];
+
+        let (host, canonical_uri, canonical_query) = match url::Url::parse(url) {
+            Ok(url) => {
+                let host = match (url.host_str(), url.port()) {
+                    (Some(host), Some(port)) => format!("{host}:{port}"),
+                    (Some(host), None) => host.to_owned(),
+                    (None, _) => String::new(),
+                };
+                let canonical_uri: Vec<_> = url.path().split('/').map(uri_encode).collect();
+                let mut query: Vec<_> = url
+                    .query_pairs()
+                    .map(|(key, value)| format!("{}={}", uri_encode(&key), uri_encode(&value)))
+                    .collect();
+                query.sort_unstable();
+                (host, canonical_uri.join("/"), query.join("&"))
+            }
+            // the URL was validated when the settings were updated
+            Err(_) => (String::new(), "/".to_owned(), String::new()),
+        };
+
+        let payload_hash = format!("{:x}", Sha256::digest(body));
+
+        let mut canonical_headers =
+            format!("content-type:application/json\nhost:{host}\nx-amz-date:{amz_date}\n");
+        let mut signed_headers = "content-type;host;x-amz-date".to_owned();
+        if let Some(session_token) = &self.credentials.session_token {
+            canonical_headers.push_str(&format!("x-amz-security-token:{session_token}\n"));
+            signed_headers.push_str(";x-amz-security-token");
+        }
+
+        let canonical_request = format!(
+            "POST\n{canonical_uri}\n{canonical_query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}"
+        );
+
+        let scope = format!("{date}/{}/{SERVICE}/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{:x}",
+            Sha256::digest(canonical_request.as_bytes())
+        );
+
+        let signing_key = [date, self.region.as_str(), SERVICE, "aws4_request"].iter().fold(
+            format!("AWS4{}", self.credentials.secret_access_key).into_bytes(),
+            |key, data| hmac_sha256(&key, data.as_bytes()),
+        );
+        let signature: String = hmac_sha256(&signing_key, string_to_sign.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        let mut headers = vec![
+            (
+                "Authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                    self.credentials.access_key_id,
+                ),
+            ),
+            ("X-Amz-Date", amz_date.clone()),
+        ];
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.push(("X-Amz-Security-Token", session_token.clone()));
+        }
+        headers
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // new_from_slice function never fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Encodes everything but the unreserved characters, as required by the canonical request.
+fn uri_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// The region of a `bedrock-runtime.{region}.amazonaws.com` endpoint.
+fn region_from_url(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let labels: Vec<_> = url.host_str()?.split('.').collect();
+    match labels.as_slice() {
+        [service, region, "amazonaws", "com"] if service.starts_with("bedrock-runtime") => {
+            Some(region.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Embedder {
+    /// Embeds the documents.
+    index_embedder: RestEmbedder,
+    /// Embeds the queries, that are embedded differently than the documents by Cohere models.
+    search_embedder: RestEmbedder,
+    options: EmbedderOptions,
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions, cache_cap: usize) -> Result<Self, NewEmbedderError> {
+        let family = ModelFamily::from_model(&options.embedding_model).ok_or_else(|| {
+            NewEmbedderError::bedrock_unsupported_model(options.embedding_model.clone())
+        })?;
+
+        let credentials = match &options.api_key {
+            Some(api_key) => Credentials::parse(api_key),
+            None => Credentials::from_env(),
+        }
+        .ok_or_else(NewEmbedderError::bedrock_missing_credentials)?;
+
+        let endpoint = options.endpoint();
+        let region = region_from_url(&endpoint).unwrap_or_else(infer_region);
+        let signer = SigV4Signer::new(credentials, region);
+
+        // cache is only used at search
+        let index_embedder = RestEmbedder::new_signed(
+            options.into_rest_embedder_config(
+                family,
+                InputType::SearchDocument,
+                options.dimensions,
+            ),
+            0,
+            super::rest::ConfigurationSource::Bedrock,
+            Some(signer.clone()),
+        )?;
+
+        // reuse the dimensions of the documents to avoid inferring them a second time
+        let search_embedder = RestEmbedder::new_signed(
+            options.into_rest_embedder_config(
+                family,
+                InputType::SearchQuery,
+                Some(index_embedder.dimensions()),
+            ),
+            cache_cap,
+            super::rest::ConfigurationSource::Bedrock,
+            Some(signer),
+        )?;
+
+        Ok(Self { index_embedder, search_embedder, options })
+    }
+
+    pub fn embed<S: AsRef<str> + serde::Serialize>(
+        &self,
+        texts: &[S],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        self.index_embedder.embed_ref(texts, deadline)
+    }
+
+    pub fn embed_search(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Embedding, EmbedError> {
+        self.search_embedder
+            .embed_ref(&[text], deadline)?
+            .pop()
+            .ok_or_else(EmbedError::missing_embedding)
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<Embedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks.into_par_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<f32>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed(chunk, None))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed(chunk, None))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        self.index_embedder.chunk_count_hint()
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        self.index_embedder.prompt_count_in_chunk_hint()
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.index_embedder.dimensions()
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        self.search_embedder.cache()
+    }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        let mut usage = self.index_embedder.indexing_usage();
+        usage += self.search_embedder.indexing_usage();
+        usage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_credentials() {
+        let credentials = Credentials::parse("AKID:SECRET").unwrap();
+        assert_eq!(credentials.access_key_id, "AKID");
+        assert_eq!(credentials.secret_access_key, "SECRET");
+        assert_eq!(credentials.session_token, None);
+
+        let credentials = Credentials::parse("AKID:SECRET:TOKEN:WITH:COLONS").unwrap();
+        assert_eq!(credentials.session_token.as_deref(), Some("TOKEN:WITH:COLONS"));
+
+        assert!(Credentials::parse("AKID").is_none());
+        assert!(Credentials::parse(":SECRET").is_none());
+    }
+
+    #[test]
+    fn region() {
+        assert_eq!(
+            region_from_url("https://bedrock-runtime.eu-west-3.amazonaws.com").as_deref(),
+            Some("eu-west-3")
+        );
+        assert_eq!(
+            region_from_url("https://bedrock-runtime-fips.us-east-1.amazonaws.com").as_deref(),
+            Some("us-east-1")
+        );
+        assert_eq!(region_from_url("http://localhost:8080"), None);
+    }
+
+    #[test]
+    fn sign() {
+        let signer = SigV4Signer::new(
+            Credentials::parse("AKIDEXAMPLE:wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY").unwrap(),
+            "us-east-1".to_owned(),
+        );
+        let options = EmbedderOptions {
+            url: Some("https://bedrock-runtime.us-east-1.amazonaws.com".to_owned()),
+            api_key: None,
+            embedding_model: "amazon.titan-embed-text-v2:0".to_owned(),
+            dimensions: None,
+            distribution: None,
+        };
+        let url = options.invoke_url();
+        insta::assert_snapshot!(url, @"https://bedrock-runtime.us-east-1.amazonaws.com/model/amazon.titan-embed-text-v2%3A0/invoke");
+
+        let headers = signer.sign_at(
+            &url,
+            br#"{"inputText":"hello"}"#,
+            time::macros::datetime!(2025-01-02 03:04:05 UTC),
+        );
+        insta::assert_debug_snapshot!(headers, @r###"
+        [
+            (
+                "Authorization",
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20250102/us-east-1/bedrock/aws4_request, SignedHeaders=content-type;host;x-amz-date, Signature=870a897d5eba1c84d3d67e0d8ec784cc499dc3ab5909ed6550187fd3dad0fdbb",
+            ),
+            (
+                "X-Amz-Date",
+                "20250102T030405Z",
+            ),
+        ]
+        "###);
+    }
+}
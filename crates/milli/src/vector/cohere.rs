@@ -0,0 +1,268 @@
+use std::time::Instant;
+
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+use rayon::slice::ParallelSlice as _;
+
+use super::error::{EmbedError, EmbedErrorKind, NewEmbedderError};
+use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
+use super::usage::EmbedderUsage;
+use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
+use crate::error::FaultSource;
+use crate::vector::Embedding;
+use crate::ThreadPoolNoAbort;
+
+pub const COHERE_EMBEDDINGS_URL: &str = "https://api.cohere.com/v2/embed";
+
+/// The default model of the `cohere` source.
+pub const DEFAULT_MODEL: &str = "embed-english-v3.0";
+
+/// Cohere rejects requests embedding more than 96 texts at once.
+const MAX_TEXTS_PER_REQUEST: usize = 96;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    pub url: Option<String>,
+    pub api_key: Option<String>,
+    pub embedding_model: String,
+    pub dimensions: Option<usize>,
+    pub distribution: Option<DistributionShift>,
+}
+
+/// Cohere embeds the documents and the search queries differently, so that queries are closer
+/// to the documents answering them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputType {
+    SearchDocument,
+    SearchQuery,
+}
+
+impl InputType {
+    fn name(&self) -> &'static str {
+        match self {
+            InputType::SearchDocument => "search_document",
+            InputType::SearchQuery => "search_query",
+        }
+    }
+}
+
+impl EmbedderOptions {
+    pub fn with_default_model(api_key: Option<String>) -> Self {
+        Self {
+            url: None,
+            api_key,
+            embedding_model: DEFAULT_MODEL.to_owned(),
+            dimensions: None,
+            distribution: None,
+        }
+    }
+
+    /// The dimensions of the embeddings, if they are known before embedding a sample text.
+    pub fn dimensions(&self) -> Option<usize> {
+        self.dimensions.or_else(|| default_dimensions(&self.embedding_model))
+    }
+
+    fn request(&self, input_type: InputType) -> serde_json::Value {
+        let mut request = serde_json::json!({
+            "model": self.embedding_model,
+            "texts": [super::rest::REQUEST_PLACEHOLDER, super::rest::REPEAT_PLACEHOLDER],
+            "input_type": input_type.name(),
+            "embedding_types": ["float"],
+        });
+
+        if supports_overriding_dimensions(&self.embedding_model) {
+            if let Some(dimensions) = self.dimensions {
+                request["output_dimension"] = dimensions.into();
+            }
+        }
+
+        request
+    }
+
+    fn into_rest_embedder_config(
+        &self,
+        input_type: InputType,
+        api_key: Option<String>,
+        dimensions: Option<usize>,
+    ) -> RestEmbedderOptions {
+        RestEmbedderOptions {
+            api_key,
+            distribution: self.distribution,
+            dimensions,
+            url: self.url.as_deref().unwrap_or(COHERE_EMBEDDINGS_URL).to_owned(),
+            request: self.request(input_type),
+            response: serde_json::json!({
+                "embeddings": {
+                    "float": [super::rest::RESPONSE_PLACEHOLDER, super::rest::REPEAT_PLACEHOLDER]
+                }
+            }),
+            headers: Default::default(),
+        }
+    }
+}
+
+/// The dimensions supported by a known Cohere model, the last one being its native dimensions.
+///
+/// Returns `None` for the models that are unknown to Meilisearch.
+pub fn supported_dimensions(model: &str) -> Option<&'static [usize]> {
+    match model {
+        "embed-v4.0" => Some(&[256, 512, 1024, 1536]),
+        "embed-english-v3.0" | "embed-multilingual-v3.0" => Some(&[1024]),
+        "embed-english-light-v3.0" | "embed-multilingual-light-v3.0" => Some(&[384]),
+        _ => None,
+    }
+}
+
+fn default_dimensions(model: &str) -> Option<usize> {
+    supported_dimensions(model).and_then(|dimensions| dimensions.last().copied())
+}
+
+fn supports_overriding_dimensions(model: &str) -> bool {
+    supported_dimensions(model).is_some_and(|dimensions| dimensions.len() > 1)
+}
+
+fn infer_api_key() -> String {
+    std::env::var("MEILI_COHERE_API_KEY")
+        .or_else(|_| std::env::var("COHERE_API_KEY"))
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+pub struct Embedder {
+    /// Embeds the documents, with the `search_document` input type.
+    index_embedder: RestEmbedder,
+    /// Embeds the queries, with the `search_query` input type.
+    search_embedder: RestEmbedder,
+    options: EmbedderOptions,
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions, cache_cap: usize) -> Result<Self, NewEmbedderError> {
+        let api_key = options.api_key.clone().unwrap_or_else(infer_api_key);
+        let api_key = (!api_key.is_empty()).then_some(api_key);
+
+        // cache is only used at search
+        let index_embedder = RestEmbedder::new(
+            options.into_rest_embedder_config(
+                InputType::SearchDocument,
+                api_key.clone(),
+                options.dimensions(),
+            ),
+            0,
+            super::rest::ConfigurationSource::Cohere,
+        )?;
+
+        // reuse the dimensions of the documents to avoid inferring them a second time
+        let search_embedder = RestEmbedder::new(
+            options.into_rest_embedder_config(
+                InputType::SearchQuery,
+                api_key,
+                Some(index_embedder.dimensions()),
+            ),
+            cache_cap,
+            super::rest::ConfigurationSource::Cohere,
+        )?;
+
+        Ok(Self { index_embedder, search_embedder, options })
+    }
+
+    pub fn embed<S: AsRef<str> + serde::Serialize>(
+        &self,
+        texts: &[S],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        self.index_embedder.embed_ref(texts, deadline)
+    }
+
+    pub fn embed_search(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Embedding, EmbedError> {
+        self.search_embedder
+            .embed_ref(&[text], deadline)?
+            .pop()
+            .ok_or_else(EmbedError::missing_embedding)
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<Embedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks.into_par_iter().map(move |chunk| self.embed(&chunk, None)).collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<f32>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed(chunk, None))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed(chunk, None))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        self.index_embedder.chunk_count_hint()
+    }
+
+    /// Sending as many texts as Cohere accepts in a request keeps the number of requests,
+    /// and so the chances to be rate limited, as low as possible.
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        MAX_TEXTS_PER_REQUEST
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.index_embedder.dimensions()
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        self.search_embedder.cache()
+    }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        let mut usage = self.index_embedder.indexing_usage();
+        usage += self.search_embedder.indexing_usage();
+        usage
+    }
+}
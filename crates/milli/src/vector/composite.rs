@@ -3,9 +3,10 @@ use std::time::Instant;
 use arroy::Distance;
 
 use super::error::CompositeEmbedderContainsHuggingFace;
+use super::usage::EmbedderUsage;
 use super::{
-    hf, manual, ollama, openai, rest, DistributionShift, EmbedError, Embedding, EmbeddingCache,
-    NewEmbedderError,
+    azure_openai, bedrock, cohere, hf, manual, ollama, onnx, openai, rest, DistributionShift,
+    EmbedError, Embedding, EmbeddingCache, NewEmbedderError,
 };
 use crate::ThreadPoolNoAbort;
 
@@ -21,6 +22,14 @@ pub enum SubEmbedder {
     Ollama(ollama::Embedder),
     /// An embedder based on making embedding queries against a generic JSON/REST embedding server.
     Rest(rest::Embedder),
+    /// An embedder based on making embedding queries against the Cohere API.
+    Cohere(cohere::Embedder),
+    /// An embedder based on making embedding queries against an Azure OpenAI deployment.
+    AzureOpenAi(azure_openai::Embedder),
+    /// An embedder based on making signed embedding queries against AWS Bedrock.
+    Bedrock(bedrock::Embedder),
+    /// An embedder based on running local ONNX models with the ONNX Runtime.
+    Onnx(onnx::Embedder),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -30,16 +39,24 @@ pub enum SubEmbedderOptions {
     Ollama(ollama::EmbedderOptions),
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
+    Cohere(cohere::EmbedderOptions),
+    AzureOpenAi(azure_openai::EmbedderOptions),
+    Bedrock(bedrock::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl SubEmbedderOptions {
     pub fn distribution(&self) -> Option<DistributionShift> {
         match self {
             SubEmbedderOptions::HuggingFace(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::Onnx(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::OpenAi(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::Ollama(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::UserProvided(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::Rest(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::Cohere(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::Bedrock(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::AzureOpenAi(embedder_options) => embedder_options.distribution,
         }
     }
 }
@@ -143,6 +160,18 @@ impl SubEmbedder {
                 cache_cap,
                 rest::ConfigurationSource::User,
             )?),
+            SubEmbedderOptions::Cohere(options) => {
+                Self::Cohere(cohere::Embedder::new(options, cache_cap)?)
+            }
+            SubEmbedderOptions::AzureOpenAi(options) => {
+                Self::AzureOpenAi(azure_openai::Embedder::new(options, cache_cap)?)
+            }
+            SubEmbedderOptions::Bedrock(options) => {
+                Self::Bedrock(bedrock::Embedder::new(options, cache_cap)?)
+            }
+            SubEmbedderOptions::Onnx(options) => {
+                Self::Onnx(onnx::Embedder::new(options, cache_cap)?)
+            }
         })
     }
 
@@ -153,10 +182,14 @@ impl SubEmbedder {
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.embed(texts),
+            SubEmbedder::Onnx(embedder) => embedder.embed(texts),
             SubEmbedder::OpenAi(embedder) => embedder.embed(&texts, deadline),
             SubEmbedder::Ollama(embedder) => embedder.embed(&texts, deadline),
             SubEmbedder::UserProvided(embedder) => embedder.embed(&texts),
             SubEmbedder::Rest(embedder) => embedder.embed(texts, deadline),
+            SubEmbedder::Cohere(embedder) => embedder.embed(&texts, deadline),
+            SubEmbedder::Bedrock(embedder) => embedder.embed(&texts, deadline),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.embed(&texts, deadline),
         }
     }
 
@@ -167,6 +200,7 @@ impl SubEmbedder {
     ) -> std::result::Result<Embedding, EmbedError> {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.embed_one(text),
+            SubEmbedder::Onnx(embedder) => embedder.embed_one(text),
             SubEmbedder::OpenAi(embedder) => {
                 embedder.embed(&[text], deadline)?.pop().ok_or_else(EmbedError::missing_embedding)
             }
@@ -178,6 +212,11 @@ impl SubEmbedder {
                 .embed_ref(&[text], deadline)?
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
+            SubEmbedder::Cohere(embedder) => embedder.embed_search(text, deadline),
+            SubEmbedder::Bedrock(embedder) => embedder.embed_search(text, deadline),
+            SubEmbedder::AzureOpenAi(embedder) => {
+                embedder.embed(&[text], deadline)?.pop().ok_or_else(EmbedError::missing_embedding)
+            }
         }
     }
 
@@ -191,10 +230,14 @@ impl SubEmbedder {
     ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.embed_index(text_chunks),
+            SubEmbedder::Onnx(embedder) => embedder.embed_index(text_chunks),
             SubEmbedder::OpenAi(embedder) => embedder.embed_index(text_chunks, threads),
             SubEmbedder::Ollama(embedder) => embedder.embed_index(text_chunks, threads),
             SubEmbedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
             SubEmbedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
+            SubEmbedder::Cohere(embedder) => embedder.embed_index(text_chunks, threads),
+            SubEmbedder::Bedrock(embedder) => embedder.embed_index(text_chunks, threads),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.embed_index(text_chunks, threads),
         }
     }
 
@@ -206,10 +249,14 @@ impl SubEmbedder {
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.embed_index_ref(texts),
+            SubEmbedder::Onnx(embedder) => embedder.embed_index_ref(texts),
             SubEmbedder::OpenAi(embedder) => embedder.embed_index_ref(texts, threads),
             SubEmbedder::Ollama(embedder) => embedder.embed_index_ref(texts, threads),
             SubEmbedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
             SubEmbedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
+            SubEmbedder::Cohere(embedder) => embedder.embed_index_ref(texts, threads),
+            SubEmbedder::Bedrock(embedder) => embedder.embed_index_ref(texts, threads),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.embed_index_ref(texts, threads),
         }
     }
 
@@ -217,10 +264,14 @@ impl SubEmbedder {
     pub fn chunk_count_hint(&self) -> usize {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.chunk_count_hint(),
             SubEmbedder::OpenAi(embedder) => embedder.chunk_count_hint(),
             SubEmbedder::Ollama(embedder) => embedder.chunk_count_hint(),
             SubEmbedder::UserProvided(_) => 100,
             SubEmbedder::Rest(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::Cohere(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::Bedrock(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -228,10 +279,14 @@ impl SubEmbedder {
     pub fn prompt_count_in_chunk_hint(&self) -> usize {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
             SubEmbedder::OpenAi(embedder) => embedder.prompt_count_in_chunk_hint(),
             SubEmbedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
             SubEmbedder::UserProvided(_) => 1,
             SubEmbedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::Cohere(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::Bedrock(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -240,7 +295,11 @@ impl SubEmbedder {
             SubEmbedder::HuggingFace(_)
             | SubEmbedder::OpenAi(_)
             | SubEmbedder::Ollama(_)
-            | SubEmbedder::Rest(_) => true,
+            | SubEmbedder::Rest(_)
+            | SubEmbedder::Cohere(_)
+            | SubEmbedder::AzureOpenAi(_)
+            | SubEmbedder::Bedrock(_)
+            | SubEmbedder::Onnx(_) => true,
             SubEmbedder::UserProvided(_) => false,
         }
     }
@@ -249,10 +308,14 @@ impl SubEmbedder {
     pub fn dimensions(&self) -> usize {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.dimensions(),
+            SubEmbedder::Onnx(embedder) => embedder.dimensions(),
             SubEmbedder::OpenAi(embedder) => embedder.dimensions(),
             SubEmbedder::Ollama(embedder) => embedder.dimensions(),
             SubEmbedder::UserProvided(embedder) => embedder.dimensions(),
             SubEmbedder::Rest(embedder) => embedder.dimensions(),
+            SubEmbedder::Cohere(embedder) => embedder.dimensions(),
+            SubEmbedder::Bedrock(embedder) => embedder.dimensions(),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.dimensions(),
         }
     }
 
@@ -260,20 +323,42 @@ impl SubEmbedder {
     pub fn distribution(&self) -> Option<DistributionShift> {
         match self {
             SubEmbedder::HuggingFace(embedder) => embedder.distribution(),
+            SubEmbedder::Onnx(embedder) => embedder.distribution(),
             SubEmbedder::OpenAi(embedder) => embedder.distribution(),
             SubEmbedder::Ollama(embedder) => embedder.distribution(),
             SubEmbedder::UserProvided(embedder) => embedder.distribution(),
             SubEmbedder::Rest(embedder) => embedder.distribution(),
+            SubEmbedder::Cohere(embedder) => embedder.distribution(),
+            SubEmbedder::Bedrock(embedder) => embedder.distribution(),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.distribution(),
         }
     }
 
     pub(super) fn cache(&self) -> Option<&EmbeddingCache> {
         match self {
             SubEmbedder::HuggingFace(embedder) => Some(embedder.cache()),
+            SubEmbedder::Onnx(embedder) => Some(embedder.cache()),
             SubEmbedder::OpenAi(embedder) => Some(embedder.cache()),
             SubEmbedder::UserProvided(_) => None,
             SubEmbedder::Ollama(embedder) => Some(embedder.cache()),
             SubEmbedder::Rest(embedder) => Some(embedder.cache()),
+            SubEmbedder::Cohere(embedder) => Some(embedder.cache()),
+            SubEmbedder::Bedrock(embedder) => Some(embedder.cache()),
+            SubEmbedder::AzureOpenAi(embedder) => Some(embedder.cache()),
+        }
+    }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        match self {
+            SubEmbedder::HuggingFace(_) | SubEmbedder::Onnx(_) | SubEmbedder::UserProvided(_) => {
+                EmbedderUsage::default()
+            }
+            SubEmbedder::OpenAi(embedder) => embedder.indexing_usage(),
+            SubEmbedder::Ollama(embedder) => embedder.indexing_usage(),
+            SubEmbedder::Rest(embedder) => embedder.indexing_usage(),
+            SubEmbedder::Cohere(embedder) => embedder.indexing_usage(),
+            SubEmbedder::Bedrock(embedder) => embedder.indexing_usage(),
+            SubEmbedder::AzureOpenAi(embedder) => embedder.indexing_usage(),
         }
     }
 }
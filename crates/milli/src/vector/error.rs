@@ -57,6 +57,8 @@ pub enum EmbedErrorKind {
     TensorValue(candle_core::Error),
     #[error("could not run model:\n  - {0}")]
     ModelForward(candle_core::Error),
+    #[error("could not run ONNX model:\n  - {0}")]
+    OnnxInference(Box<dyn std::error::Error + Send + Sync>),
     #[error("attempt to embed the following text in a configuration where embeddings must be user provided:\n  - `{0}`")]
     ManualEmbed(String),
     #[error("model not found. Meilisearch will not automatically download models from the Ollama library, please pull the model manually{}", option_info(.0.as_deref(), "server replied with "))]
@@ -68,13 +70,21 @@ pub enum EmbedErrorKind {
     #[error("could not authenticate against {embedding} server{server_reply}{hint}", embedding=match *.1 {
         ConfigurationSource::User => "embedding",
         ConfigurationSource::OpenAi => "OpenAI",
-        ConfigurationSource::Ollama => "Ollama"
+        ConfigurationSource::Ollama => "Ollama",
+        ConfigurationSource::Cohere => "Cohere",
+        ConfigurationSource::AzureOpenAi => "Azure OpenAI",
+        ConfigurationSource::Bedrock => "AWS Bedrock",
+        ConfigurationSource::Reranker => "reranking"
     },
     server_reply=option_info(.0.as_deref(), "server replied with "),
     hint=match *.1 {
         ConfigurationSource::User => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration",
         ConfigurationSource::OpenAi => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration, and the `MEILI_OPENAI_API_KEY` and `OPENAI_API_KEY` environment variables",
-        ConfigurationSource::Ollama => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration"
+        ConfigurationSource::Ollama => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration",
+        ConfigurationSource::Cohere => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration, and the `MEILI_COHERE_API_KEY` and `COHERE_API_KEY` environment variables",
+        ConfigurationSource::AzureOpenAi => "\n  - Hint: Check the `apiKey` and `headers` parameters in the embedder configuration, and the `MEILI_AZURE_OPENAI_API_KEY` and `AZURE_OPENAI_API_KEY` environment variables",
+        ConfigurationSource::Bedrock => "\n  - Hint: Check the `apiKey` parameter in the embedder configuration, and the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` environment variables",
+        ConfigurationSource::Reranker => "\n  - Hint: Check the `apiKey` parameter in the `reranker` index setting"
     })]
     RestUnauthorized(Option<String>, ConfigurationSource),
     #[error("sent too many requests to embedding server{}", option_info(.0.as_deref(), "server replied with "))]
@@ -95,6 +105,10 @@ pub enum EmbedErrorKind {
     RestNetwork(ureq::Transport),
     #[error("error extracting embeddings from the response:\n  - {0}")]
     RestExtractionError(String),
+    #[error(
+        "reranking server returned a result for document `{0}`, but only {1} documents were sent"
+    )]
+    RerankUnknownIndex(usize, usize),
     #[error("was expecting embeddings of dimension `{0}`, got embeddings of dimensions `{1}`")]
     UnexpectedDimension(usize, usize),
     #[error("no embedding was produced")]
@@ -127,6 +141,10 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::ModelForward(inner), fault: FaultSource::Runtime }
     }
 
+    pub(crate) fn onnx_inference(inner: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self { kind: EmbedErrorKind::OnnxInference(inner), fault: FaultSource::Runtime }
+    }
+
     pub(crate) fn embed_on_manual_embedder(texts: String) -> EmbedError {
         Self { kind: EmbedErrorKind::ManualEmbed(texts), fault: FaultSource::User }
     }
@@ -210,6 +228,13 @@ impl EmbedError {
     pub(crate) fn rest_extraction_error(error: String) -> EmbedError {
         Self { kind: EmbedErrorKind::RestExtractionError(error), fault: FaultSource::Runtime }
     }
+
+    pub(crate) fn rerank_unknown_index(index: usize, document_count: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankUnknownIndex(index, document_count),
+            fault: FaultSource::Runtime,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -337,6 +362,25 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::OllamaUnsupportedUrl(url), fault: FaultSource::User }
     }
 
+    pub(crate) fn bedrock_unsupported_model(model: String) -> NewEmbedderError {
+        Self {
+            kind: NewEmbedderErrorKind::BedrockUnsupportedModel(model),
+            fault: FaultSource::User,
+        }
+    }
+
+    pub(crate) fn bedrock_missing_credentials() -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::BedrockMissingCredentials, fault: FaultSource::User }
+    }
+
+    pub(crate) fn onnx_load_model(inner: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxLoadModel(inner), fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn onnx_unavailable() -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxUnavailable, fault: FaultSource::User }
+    }
+
     pub(crate) fn composite_dimensions_mismatch(
         search_dimensions: usize,
         index_dimensions: usize,
@@ -491,6 +535,14 @@ pub enum NewEmbedderErrorKind {
     CouldNotParseTemplate(String),
     #[error("unsupported Ollama URL.\n  - For `ollama` sources, the URL must end with `/api/embed` or `/api/embeddings`\n  - Got `{0}`")]
     OllamaUnsupportedUrl(String),
+    #[error("unsupported Bedrock model `{0}`.\n  - Supported models: {}", crate::vector::bedrock::ModelFamily::supported_models().iter().map(|model| format!("`{model}`")).collect::<Vec<_>>().join(", "))]
+    BedrockUnsupportedModel(String),
+    #[error("missing AWS credentials.\n  - Hint: Set the `apiKey` parameter in the embedder configuration to `<accessKeyId>:<secretAccessKey>` or `<accessKeyId>:<secretAccessKey>:<sessionToken>`, or set the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables")]
+    BedrockMissingCredentials,
+    #[error("could not load the ONNX model:\n  - {0}")]
+    OnnxLoadModel(Box<dyn std::error::Error + Send + Sync>),
+    #[error("this Meilisearch was compiled without support for the `onnx` source.\n  - Hint: compile Meilisearch with the `onnx` feature")]
+    OnnxUnavailable,
     #[error("error while generating test embeddings.\n  - the dimensions of embeddings produced at search time and at indexing time don't match.\n  - Search time dimensions: {search_dimensions}\n  - Indexing time dimensions: {index_dimensions}\n  - Note: Dimensions of embeddings produced by both embedders are required to match.")]
     CompositeDimensionsMismatch { search_dimensions: usize, index_dimensions: usize },
     #[error("error while generating test embeddings.\n  - could not generate test embedding with embedder at {failing_embedder} time.\n  - Embedding failed with {inner}")]
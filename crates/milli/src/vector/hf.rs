@@ -2,7 +2,7 @@ use candle_core::Tensor;
 use candle_nn::VarBuilder;
 use candle_transformers::models::bert::{BertModel, Config, DTYPE};
 // FIXME: currently we'll be using the hub to retrieve model, in the future we might want to embed it into Meilisearch itself
-use hf_hub::api::sync::Api;
+use hf_hub::api::sync::{Api, ApiRepo};
 use hf_hub::{Repo, RepoType};
 use tokenizers::{PaddingParams, Tokenizer};
 
@@ -149,6 +149,44 @@ impl From<PoolingConfig> for Pooling {
     }
 }
 
+/// Retrieves the pooling method of the model from its sentence-transformers configuration, if any.
+pub(super) fn fetch_pooling(
+    api: &ApiRepo,
+    model: &str,
+    override_pooling: OverridePooling,
+) -> Result<Pooling, NewEmbedderError> {
+    let pooling = match api.get("1_Pooling/config.json") {
+        Ok(pooling) => Some(pooling),
+        Err(hf_hub::api::sync::ApiError::RequestError(error))
+            if matches!(*error, ureq::Error::Status(404, _,)) =>
+        {
+            // ignore the error if the file simply doesn't exist
+            None
+        }
+        Err(error) => return Err(NewEmbedderError::api_get(error)),
+    };
+    let mut pooling: Pooling = match pooling {
+        Some(pooling_filename) => {
+            let pooling = std::fs::read_to_string(&pooling_filename).map_err(|inner| {
+                NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner)
+            })?;
+
+            let pooling: PoolingConfig = serde_json::from_str(&pooling).map_err(|inner| {
+                NewEmbedderError::deserialize_pooling_config(
+                    model.to_owned(),
+                    pooling_filename,
+                    inner,
+                )
+            })?;
+            pooling.into()
+        }
+        None => Pooling::default(),
+    };
+
+    pooling.override_with(override_pooling);
+    Ok(pooling)
+}
+
 impl Embedder {
     pub fn new(
         options: EmbedderOptions,
@@ -179,36 +217,7 @@ impl Embedder {
                     })
                     .map_err(NewEmbedderError::api_get)?
             };
-            let pooling = match api.get("1_Pooling/config.json") {
-                Ok(pooling) => Some(pooling),
-                Err(hf_hub::api::sync::ApiError::RequestError(error))
-                    if matches!(*error, ureq::Error::Status(404, _,)) =>
-                {
-                    // ignore the error if the file simply doesn't exist
-                    None
-                }
-                Err(error) => return Err(NewEmbedderError::api_get(error)),
-            };
-            let mut pooling: Pooling = match pooling {
-                Some(pooling_filename) => {
-                    let pooling = std::fs::read_to_string(&pooling_filename).map_err(|inner| {
-                        NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner)
-                    })?;
-
-                    let pooling: PoolingConfig =
-                        serde_json::from_str(&pooling).map_err(|inner| {
-                            NewEmbedderError::deserialize_pooling_config(
-                                options.model.clone(),
-                                pooling_filename,
-                                inner,
-                            )
-                        })?;
-                    pooling.into()
-                }
-                None => Pooling::default(),
-            };
-
-            pooling.override_with(options.pooling);
+            let pooling = fetch_pooling(&api, &options.model, options.pooling)?;
 
             (config, tokenizer, weights, source, pooling)
         };
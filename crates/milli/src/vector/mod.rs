@@ -4,8 +4,10 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use arroy::distances::{BinaryQuantizedCosine, Cosine};
-use arroy::ItemId;
+use arroy::{Distance as _, ItemId};
+use bytemuck::allocation::pod_collect_to_vec;
 use deserr::{DeserializeError, Deserr};
+use heed::types::Bytes;
 use heed::{RoTxn, RwTxn, Unspecified};
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
@@ -24,31 +26,47 @@ pub mod json_template;
 pub mod manual;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod persisted_cache;
 pub mod settings;
 
+pub mod azure_openai;
+pub mod bedrock;
+pub mod cohere;
 pub mod ollama;
+pub mod onnx;
+pub mod reranker;
 pub mod rest;
+pub mod usage;
 
 pub use self::error::Error;
+use self::usage::EmbedderUsage;
 
 pub type Embedding = Vec<f32>;
 
 pub const REQUEST_PARALLELISM: usize = 40;
 pub const MAX_COMPOSITE_DISTANCE: f32 = 0.01;
 
+/// When searching a binary quantized embedder, the number of candidates retrieved for each requested result.
+///
+/// The candidates are then rescored using their full-precision vectors.
+const RESCORING_OVERSAMPLING: usize = 4;
+
 pub struct ArroyWrapper {
     quantized: bool,
     embedder_index: u8,
     database: arroy::Database<Unspecified>,
+    /// The full-precision vectors of the binary quantized embedders, used for rescoring.
+    full_precision: heed::Database<Bytes, Bytes>,
 }
 
 impl ArroyWrapper {
     pub fn new(
         database: arroy::Database<Unspecified>,
+        full_precision: heed::Database<Bytes, Bytes>,
         embedder_index: u8,
         quantized: bool,
     ) -> Self {
-        Self { database, embedder_index, quantized }
+        Self { database, full_precision, embedder_index, quantized }
     }
 
     pub fn embedder_index(&self) -> u8 {
@@ -93,6 +111,9 @@ impl ArroyWrapper {
         arroy_memory: Option<usize>,
         cancel: &(impl Fn() -> bool + Sync + Send),
     ) -> Result<(), arroy::Error> {
+        if quantizing && !self.quantized {
+            self.store_full_precision_vectors(wtxn, dimension)?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -142,6 +163,9 @@ impl ArroyWrapper {
         embeddings: &Embeddings<f32>,
     ) -> Result<(), arroy::Error> {
         let dimension = embeddings.dimension();
+        if self.quantized {
+            self.put_full_precision_vectors(wtxn, item_id, embeddings.as_inner())?;
+        }
         for (index, vector) in
             arroy_db_range_for_embedder(self.embedder_index).zip(embeddings.iter())
         {
@@ -164,6 +188,9 @@ impl ArroyWrapper {
         vector: &[f32],
     ) -> Result<(), arroy::Error> {
         if self.quantized {
+            let mut vectors = self.full_precision_vectors(wtxn, item_id)?.unwrap_or_default();
+            vectors.extend_from_slice(vector);
+            self.put_full_precision_vectors(wtxn, item_id, &vectors)?;
             self._add_item(wtxn, self.quantized_db(), item_id, vector)
         } else {
             self._add_item(wtxn, self.angular_db(), item_id, vector)
@@ -196,6 +223,9 @@ impl ArroyWrapper {
         dimension: usize,
         item_id: arroy::ItemId,
     ) -> Result<(), arroy::Error> {
+        if self.quantized {
+            self.full_precision.delete(wtxn, &self.full_precision_key(item_id))?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -221,6 +251,19 @@ impl ArroyWrapper {
         vector: &[f32],
     ) -> Result<bool, arroy::Error> {
         if self.quantized {
+            if let Some(mut vectors) = self.full_precision_vectors(wtxn, item_id)? {
+                if let Some(position) =
+                    vectors.chunks_exact(vector.len()).position(|candidate| candidate == vector)
+                {
+                    let start = position * vector.len();
+                    vectors.drain(start..start + vector.len());
+                    if vectors.is_empty() {
+                        self.full_precision.delete(wtxn, &self.full_precision_key(item_id))?;
+                    } else {
+                        self.put_full_precision_vectors(wtxn, item_id, &vectors)?;
+                    }
+                }
+            }
             self._del_item(wtxn, self.quantized_db(), item_id, vector)
         } else {
             self._del_item(wtxn, self.angular_db(), item_id, vector)
@@ -272,6 +315,16 @@ impl ArroyWrapper {
     }
 
     pub fn clear(&self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
+        if self.quantized {
+            let keys: Vec<_> = self
+                .full_precision
+                .prefix_iter(wtxn, &[self.embedder_index])?
+                .map(|entry| entry.map(|(key, _)| key.to_vec()))
+                .collect::<Result<_, _>>()?;
+            for key in keys {
+                self.full_precision.delete(wtxn, &key)?;
+            }
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -358,17 +411,32 @@ impl ArroyWrapper {
         Ok(results)
     }
 
+    /// Returns the `limit` nearest neighbors of `vector`.
+    ///
+    /// The `search_accuracy` scales the number of candidates inspected in the trees,
+    /// `None` inspects as many candidates as arroy does by default.
     pub fn nns_by_vector(
         &self,
         rtxn: &RoTxn,
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
+        search_accuracy: Option<f32>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
         if self.quantized {
-            self._nns_by_vector(rtxn, self.quantized_db(), vector, limit, filter)
+            let mut results = self._nns_by_vector(
+                rtxn,
+                self.quantized_db(),
+                vector,
+                limit.saturating_mul(RESCORING_OVERSAMPLING),
+                filter,
+                search_accuracy,
+            )?;
+            self.rescore(rtxn, vector, &mut results)?;
+            results.truncate(limit);
+            Ok(results)
         } else {
-            self._nns_by_vector(rtxn, self.angular_db(), vector, limit, filter)
+            self._nns_by_vector(rtxn, self.angular_db(), vector, limit, filter, search_accuracy)
         }
     }
 
@@ -379,6 +447,7 @@ impl ArroyWrapper {
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
+        search_accuracy: Option<f32>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
         let mut results = Vec::new();
 
@@ -388,6 +457,14 @@ impl ArroyWrapper {
             if let Some(filter) = filter {
                 searcher.candidates(filter);
             }
+            if let Some(search_accuracy) = search_accuracy {
+                // arroy inspects `n_trees * limit` candidates by default
+                let search_k = (reader.n_trees() * limit) as f64 * search_accuracy as f64;
+                let search_k = (search_k.ceil() as usize).max(limit);
+                if let Some(search_k) = NonZeroUsize::new(search_k) {
+                    searcher.search_k(search_k);
+                }
+            }
 
             results.append(&mut searcher.by_vector(rtxn, vector)?);
         }
@@ -420,6 +497,108 @@ impl ArroyWrapper {
         Ok(vectors)
     }
 
+    fn full_precision_key(&self, item_id: ItemId) -> [u8; 5] {
+        let mut key = [0; 5];
+        key[0] = self.embedder_index;
+        key[1..].copy_from_slice(&item_id.to_be_bytes());
+        key
+    }
+
+    /// The full-precision vectors of an item of a binary quantized embedder, stored consecutively.
+    fn full_precision_vectors(
+        &self,
+        rtxn: &RoTxn,
+        item_id: ItemId,
+    ) -> Result<Option<Vec<f32>>, arroy::Error> {
+        Ok(self
+            .full_precision
+            .get(rtxn, &self.full_precision_key(item_id))?
+            .map(pod_collect_to_vec))
+    }
+
+    fn put_full_precision_vectors(
+        &self,
+        wtxn: &mut RwTxn,
+        item_id: ItemId,
+        vectors: &[f32],
+    ) -> Result<(), arroy::Error> {
+        Ok(self.full_precision.put(
+            wtxn,
+            &self.full_precision_key(item_id),
+            bytemuck::cast_slice(vectors),
+        )?)
+    }
+
+    /// Copies the vectors of the embedder before it gets binary quantized, so that they can be used for rescoring.
+    fn store_full_precision_vectors(
+        &self,
+        wtxn: &mut RwTxn,
+        dimension: usize,
+    ) -> Result<(), arroy::Error> {
+        let mut items = RoaringBitmap::new();
+        for reader in self.readers(wtxn, self.angular_db()) {
+            items |= reader?.item_ids();
+        }
+
+        for item_id in items {
+            let mut vectors = Vec::new();
+            for index in arroy_db_range_for_embedder(self.embedder_index) {
+                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+                let Some(vector) = writer.item_vector(wtxn, item_id)? else {
+                    // uses invariant: vectors are packed in the first writers.
+                    break;
+                };
+                vectors.extend(vector);
+            }
+            if !vectors.is_empty() {
+                self.put_full_precision_vectors(wtxn, item_id, &vectors)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the distances of the `results` of a binary quantized search by the distances to their full-precision vectors.
+    ///
+    /// Items without full-precision vectors keep their distance.
+    fn rescore(
+        &self,
+        rtxn: &RoTxn,
+        target: &[f32],
+        results: &mut Vec<(ItemId, f32)>,
+    ) -> Result<(), arroy::Error> {
+        let dimensions = target.len();
+        let target = arroy::internals::UnalignedVector::from_slice(target);
+        let target = arroy::internals::Leaf { header: Cosine::new_header(&target), vector: target };
+
+        // items with multiple vectors can appear multiple times, the closest occurrence comes first
+        let mut seen = RoaringBitmap::new();
+        results.retain(|(item_id, _)| seen.insert(*item_id));
+
+        for (item_id, distance) in results.iter_mut() {
+            let Some(vectors) = self.full_precision_vectors(rtxn, *item_id)? else {
+                continue;
+            };
+            let full_precision_distance = vectors
+                .chunks_exact(dimensions)
+                .map(|vector| {
+                    let vector = arroy::internals::UnalignedVector::from_slice(vector);
+                    let vector =
+                        arroy::internals::Leaf { header: Cosine::new_header(&vector), vector };
+                    Cosine::normalized_distance(
+                        Cosine::built_distance(&target, &vector),
+                        dimensions,
+                    )
+                })
+                .min_by_key(|distance| OrderedFloat(*distance));
+            if let Some(full_precision_distance) = full_precision_distance {
+                *distance = full_precision_distance;
+            }
+        }
+
+        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        Ok(())
+    }
+
     fn angular_db(&self) -> arroy::Database<Cosine> {
         self.database.remap_data_type()
     }
@@ -553,6 +732,14 @@ pub enum Embedder {
     Ollama(ollama::Embedder),
     /// An embedder based on making embedding queries against a generic JSON/REST embedding server.
     Rest(rest::Embedder),
+    /// An embedder based on making embedding queries against the Cohere API.
+    Cohere(cohere::Embedder),
+    /// An embedder based on making embedding queries against an Azure OpenAI deployment.
+    AzureOpenAi(azure_openai::Embedder),
+    /// An embedder based on making signed embedding queries against AWS Bedrock.
+    Bedrock(bedrock::Embedder),
+    /// An embedder based on running local ONNX models with the ONNX Runtime.
+    Onnx(onnx::Embedder),
     /// An embedder composed of an embedder at search time and an embedder at indexing time.
     Composite(composite::Embedder),
 }
@@ -664,6 +851,10 @@ pub enum EmbedderOptions {
     Ollama(ollama::EmbedderOptions),
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
+    Cohere(cohere::EmbedderOptions),
+    AzureOpenAi(azure_openai::EmbedderOptions),
+    Bedrock(bedrock::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
     Composite(composite::EmbedderOptions),
 }
 
@@ -697,6 +888,16 @@ impl Embedder {
                 cache_cap,
                 rest::ConfigurationSource::User,
             )?),
+            EmbedderOptions::Cohere(options) => {
+                Self::Cohere(cohere::Embedder::new(options, cache_cap)?)
+            }
+            EmbedderOptions::AzureOpenAi(options) => {
+                Self::AzureOpenAi(azure_openai::Embedder::new(options, cache_cap)?)
+            }
+            EmbedderOptions::Bedrock(options) => {
+                Self::Bedrock(bedrock::Embedder::new(options, cache_cap)?)
+            }
+            EmbedderOptions::Onnx(options) => Self::Onnx(onnx::Embedder::new(options, cache_cap)?),
             EmbedderOptions::Composite(options) => {
                 Self::Composite(composite::Embedder::new(options, cache_cap)?)
             }
@@ -719,6 +920,7 @@ impl Embedder {
         }
         let embedding = match self {
             Embedder::HuggingFace(embedder) => embedder.embed_one(text),
+            Embedder::Onnx(embedder) => embedder.embed_one(text),
             Embedder::OpenAi(embedder) => {
                 embedder.embed(&[text], deadline)?.pop().ok_or_else(EmbedError::missing_embedding)
             }
@@ -730,6 +932,11 @@ impl Embedder {
                 .embed_ref(&[text], deadline)?
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
+            Embedder::Cohere(embedder) => embedder.embed_search(text, deadline),
+            Embedder::Bedrock(embedder) => embedder.embed_search(text, deadline),
+            Embedder::AzureOpenAi(embedder) => {
+                embedder.embed(&[text], deadline)?.pop().ok_or_else(EmbedError::missing_embedding)
+            }
             Embedder::Composite(embedder) => embedder.search.embed_one(text, deadline),
         }?;
 
@@ -750,10 +957,14 @@ impl Embedder {
     ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
         match self {
             Embedder::HuggingFace(embedder) => embedder.embed_index(text_chunks),
+            Embedder::Onnx(embedder) => embedder.embed_index(text_chunks),
             Embedder::OpenAi(embedder) => embedder.embed_index(text_chunks, threads),
             Embedder::Ollama(embedder) => embedder.embed_index(text_chunks, threads),
             Embedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
             Embedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
+            Embedder::Cohere(embedder) => embedder.embed_index(text_chunks, threads),
+            Embedder::Bedrock(embedder) => embedder.embed_index(text_chunks, threads),
+            Embedder::AzureOpenAi(embedder) => embedder.embed_index(text_chunks, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index(text_chunks, threads),
         }
     }
@@ -766,10 +977,14 @@ impl Embedder {
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
         match self {
             Embedder::HuggingFace(embedder) => embedder.embed_index_ref(texts),
+            Embedder::Onnx(embedder) => embedder.embed_index_ref(texts),
             Embedder::OpenAi(embedder) => embedder.embed_index_ref(texts, threads),
             Embedder::Ollama(embedder) => embedder.embed_index_ref(texts, threads),
             Embedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
             Embedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
+            Embedder::Cohere(embedder) => embedder.embed_index_ref(texts, threads),
+            Embedder::Bedrock(embedder) => embedder.embed_index_ref(texts, threads),
+            Embedder::AzureOpenAi(embedder) => embedder.embed_index_ref(texts, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index_ref(texts, threads),
         }
     }
@@ -778,10 +993,14 @@ impl Embedder {
     pub fn chunk_count_hint(&self) -> usize {
         match self {
             Embedder::HuggingFace(embedder) => embedder.chunk_count_hint(),
+            Embedder::Onnx(embedder) => embedder.chunk_count_hint(),
             Embedder::OpenAi(embedder) => embedder.chunk_count_hint(),
             Embedder::Ollama(embedder) => embedder.chunk_count_hint(),
             Embedder::UserProvided(_) => 100,
             Embedder::Rest(embedder) => embedder.chunk_count_hint(),
+            Embedder::Cohere(embedder) => embedder.chunk_count_hint(),
+            Embedder::Bedrock(embedder) => embedder.chunk_count_hint(),
+            Embedder::AzureOpenAi(embedder) => embedder.chunk_count_hint(),
             Embedder::Composite(embedder) => embedder.index.chunk_count_hint(),
         }
     }
@@ -790,10 +1009,14 @@ impl Embedder {
     pub fn prompt_count_in_chunk_hint(&self) -> usize {
         match self {
             Embedder::HuggingFace(embedder) => embedder.prompt_count_in_chunk_hint(),
+            Embedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::OpenAi(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::UserProvided(_) => 1,
             Embedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
+            Embedder::Cohere(embedder) => embedder.prompt_count_in_chunk_hint(),
+            Embedder::Bedrock(embedder) => embedder.prompt_count_in_chunk_hint(),
+            Embedder::AzureOpenAi(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Composite(embedder) => embedder.index.prompt_count_in_chunk_hint(),
         }
     }
@@ -802,10 +1025,14 @@ impl Embedder {
     pub fn dimensions(&self) -> usize {
         match self {
             Embedder::HuggingFace(embedder) => embedder.dimensions(),
+            Embedder::Onnx(embedder) => embedder.dimensions(),
             Embedder::OpenAi(embedder) => embedder.dimensions(),
             Embedder::Ollama(embedder) => embedder.dimensions(),
             Embedder::UserProvided(embedder) => embedder.dimensions(),
             Embedder::Rest(embedder) => embedder.dimensions(),
+            Embedder::Cohere(embedder) => embedder.dimensions(),
+            Embedder::Bedrock(embedder) => embedder.dimensions(),
+            Embedder::AzureOpenAi(embedder) => embedder.dimensions(),
             Embedder::Composite(embedder) => embedder.dimensions(),
         }
     }
@@ -814,10 +1041,14 @@ impl Embedder {
     pub fn distribution(&self) -> Option<DistributionShift> {
         match self {
             Embedder::HuggingFace(embedder) => embedder.distribution(),
+            Embedder::Onnx(embedder) => embedder.distribution(),
             Embedder::OpenAi(embedder) => embedder.distribution(),
             Embedder::Ollama(embedder) => embedder.distribution(),
             Embedder::UserProvided(embedder) => embedder.distribution(),
             Embedder::Rest(embedder) => embedder.distribution(),
+            Embedder::Cohere(embedder) => embedder.distribution(),
+            Embedder::Bedrock(embedder) => embedder.distribution(),
+            Embedder::AzureOpenAi(embedder) => embedder.distribution(),
             Embedder::Composite(embedder) => embedder.distribution(),
         }
     }
@@ -827,7 +1058,11 @@ impl Embedder {
             Embedder::HuggingFace(_)
             | Embedder::OpenAi(_)
             | Embedder::Ollama(_)
-            | Embedder::Rest(_) => true,
+            | Embedder::Rest(_)
+            | Embedder::Cohere(_)
+            | Embedder::AzureOpenAi(_)
+            | Embedder::Bedrock(_)
+            | Embedder::Onnx(_) => true,
             Embedder::UserProvided(_) => false,
             Embedder::Composite(embedder) => embedder.index.uses_document_template(),
         }
@@ -836,13 +1071,39 @@ impl Embedder {
     fn cache(&self) -> Option<&EmbeddingCache> {
         match self {
             Embedder::HuggingFace(embedder) => Some(embedder.cache()),
+            Embedder::Onnx(embedder) => Some(embedder.cache()),
             Embedder::OpenAi(embedder) => Some(embedder.cache()),
             Embedder::UserProvided(_) => None,
             Embedder::Ollama(embedder) => Some(embedder.cache()),
             Embedder::Rest(embedder) => Some(embedder.cache()),
+            Embedder::Cohere(embedder) => Some(embedder.cache()),
+            Embedder::Bedrock(embedder) => Some(embedder.cache()),
+            Embedder::AzureOpenAi(embedder) => Some(embedder.cache()),
             Embedder::Composite(embedder) => embedder.search.cache(),
         }
     }
+
+    /// The requests sent to the embedding provider outside of searches, since the embedder was created.
+    ///
+    /// Local embedders send no requests and always return an empty usage.
+    pub fn indexing_usage(&self) -> EmbedderUsage {
+        match self {
+            Embedder::HuggingFace(_) | Embedder::Onnx(_) | Embedder::UserProvided(_) => {
+                EmbedderUsage::default()
+            }
+            Embedder::OpenAi(embedder) => embedder.indexing_usage(),
+            Embedder::Ollama(embedder) => embedder.indexing_usage(),
+            Embedder::Rest(embedder) => embedder.indexing_usage(),
+            Embedder::Cohere(embedder) => embedder.indexing_usage(),
+            Embedder::Bedrock(embedder) => embedder.indexing_usage(),
+            Embedder::AzureOpenAi(embedder) => embedder.indexing_usage(),
+            Embedder::Composite(embedder) => {
+                let mut usage = embedder.index.indexing_usage();
+                usage += embedder.search.indexing_usage();
+                usage
+            }
+        }
+    }
 }
 
 /// Describes the mean and sigma of distribution of embedding similarity in the embedding space.
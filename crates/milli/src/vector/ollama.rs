@@ -5,6 +5,7 @@ use rayon::slice::ParallelSlice as _;
 
 use super::error::{EmbedError, EmbedErrorKind, NewEmbedderError, NewEmbedderErrorKind};
 use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
+use super::usage::EmbedderUsage;
 use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
 use crate::error::FaultSource;
 use crate::vector::Embedding;
@@ -187,6 +188,10 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         self.rest_embedder.cache()
     }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        self.rest_embedder.indexing_usage()
+    }
 }
 
 fn get_ollama_path() -> String {
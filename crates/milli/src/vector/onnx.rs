@@ -0,0 +1,350 @@
+// FIXME: currently we'll be using the hub to retrieve model, in the future we might want to embed it into Meilisearch itself
+use hf_hub::api::sync::Api;
+use hf_hub::{Repo, RepoType};
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::error::{EmbedError, NewEmbedderError};
+use super::hf::{OverridePooling, Pooling};
+use super::{DistributionShift, Embedding, EmbeddingCache};
+
+/// The default model of the `onnx` source.
+pub const DEFAULT_MODEL: &str = "sentence-transformers/all-MiniLM-L6-v2";
+
+/// The files looked up in the repository of the model, in order.
+///
+/// Quantized exports are preferred as they use less memory.
+const MODEL_FILENAMES: &[&str] = &["onnx/model_quantized.onnx", "onnx/model.onnx", "model.onnx"];
+
+/// Longer sequences are truncated, like in the Hugging Face embedder.
+const MAX_SEQUENCE_LENGTH: usize = 512;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    pub model: String,
+    pub revision: Option<String>,
+    pub distribution: Option<DistributionShift>,
+    #[serde(default)]
+    pub pooling: OverridePooling,
+}
+
+impl EmbedderOptions {
+    pub fn new() -> Self {
+        Self {
+            model: DEFAULT_MODEL.to_string(),
+            revision: None,
+            distribution: None,
+            pooling: OverridePooling::UseModel,
+        }
+    }
+}
+
+impl Default for EmbedderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Perform embedding of documents and queries with the ONNX Runtime
+pub struct Embedder {
+    model: Model,
+    tokenizer: Tokenizer,
+    options: EmbedderOptions,
+    dimensions: usize,
+    pooling: Pooling,
+    cache: EmbeddingCache,
+}
+
+impl std::fmt::Debug for Embedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedder")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .field("pooling", &self.pooling)
+            .finish()
+    }
+}
+
+impl Embedder {
+    pub fn new(
+        options: EmbedderOptions,
+        cache_cap: usize,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        let repo = match options.revision.clone() {
+            Some(revision) => Repo::with_revision(options.model.clone(), RepoType::Model, revision),
+            None => Repo::model(options.model.clone()),
+        };
+        let (tokenizer_filename, model_filename, pooling) = {
+            let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
+            let api = api.repo(repo);
+            let tokenizer = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
+            let mut model = api.get(MODEL_FILENAMES[0]);
+            for filename in &MODEL_FILENAMES[1..] {
+                if model.is_ok() {
+                    break;
+                }
+                model = api.get(filename);
+            }
+            let model = model.map_err(NewEmbedderError::api_get)?;
+            let pooling = super::hf::fetch_pooling(&api, &options.model, options.pooling)?;
+            (tokenizer, model, pooling)
+        };
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+
+        tracing::debug!(model = options.model, file = ?model_filename, pooling = ?pooling, "model config");
+
+        let model = Model::load(&model_filename)?;
+
+        if let Some(pp) = tokenizer.get_padding_mut() {
+            pp.strategy = tokenizers::PaddingStrategy::BatchLongest
+        } else {
+            let pp = PaddingParams {
+                strategy: tokenizers::PaddingStrategy::BatchLongest,
+                ..Default::default()
+            };
+            tokenizer.with_padding(Some(pp));
+        }
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_SEQUENCE_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+
+        let mut this = Self {
+            model,
+            tokenizer,
+            options,
+            dimensions: 0,
+            pooling,
+            cache: EmbeddingCache::new(cache_cap),
+        };
+
+        let embeddings = this
+            .embed(vec!["test".into()])
+            .map_err(NewEmbedderError::could_not_determine_dimension)?;
+        this.dimensions = embeddings.first().unwrap().len();
+
+        Ok(this)
+    }
+
+    pub fn embed(&self, texts: Vec<String>) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        self.embed_batch(texts)
+    }
+
+    pub fn embed_one(&self, text: &str) -> std::result::Result<Embedding, EmbedError> {
+        let mut embeddings = self.embed_batch(vec![text])?;
+        embeddings.pop().ok_or_else(EmbedError::missing_embedding)
+    }
+
+    /// Embeds all the texts with a single run of the model, on sequences padded to the longest text.
+    fn embed_batch<'s, S>(&self, texts: Vec<S>) -> std::result::Result<Vec<Embedding>, EmbedError>
+    where
+        S: Into<tokenizers::EncodeInput<'s>> + Send,
+    {
+        let encodings = self.tokenizer.encode_batch(texts, true).map_err(EmbedError::tokenize)?;
+        let batch_size = encodings.len();
+        let Some(sequence_length) = encodings.first().map(|encoding| encoding.len()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut input_ids = Vec::with_capacity(batch_size * sequence_length);
+        let mut attention_mask = Vec::with_capacity(batch_size * sequence_length);
+        let mut token_type_ids = Vec::with_capacity(batch_size * sequence_length);
+        for encoding in &encodings {
+            input_ids.extend(encoding.get_ids().iter().map(|&id| id as i64));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|&mask| mask as i64));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|&id| id as i64));
+        }
+
+        let hidden_states = self.model.run(
+            [batch_size, sequence_length],
+            input_ids,
+            attention_mask.clone(),
+            token_type_ids,
+        )?;
+        let hidden_size = hidden_states.len() / (batch_size * sequence_length);
+
+        Ok(hidden_states
+            .chunks_exact(sequence_length * hidden_size)
+            .zip(attention_mask.chunks_exact(sequence_length))
+            .map(|(tokens, mask)| pool(self.pooling, tokens, mask, hidden_size))
+            .collect())
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        text_chunks.into_iter().map(|prompts| self.embed(prompts)).collect()
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        1
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8)
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(crate) fn embed_index_ref(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+        texts
+            .chunks(self.prompt_count_in_chunk_hint())
+            .map(|chunk| self.embed_batch(chunk.to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|embeddings| embeddings.into_iter().flatten().collect())
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        &self.cache
+    }
+}
+
+/// Pools the hidden states of the tokens of a sequence, ignoring the padding tokens.
+fn pool(pooling: Pooling, tokens: &[f32], mask: &[i64], hidden_size: usize) -> Embedding {
+    let tokens: Vec<&[f32]> = tokens
+        .chunks_exact(hidden_size)
+        .zip(mask)
+        .filter(|(_, &mask)| mask != 0)
+        .map(|(token, _)| token)
+        .collect();
+
+    let sum = || {
+        tokens.iter().fold(vec![0.0; hidden_size], |mut sum, token| {
+            sum.iter_mut().zip(token.iter()).for_each(|(sum, value)| *sum += value);
+            sum
+        })
+    };
+
+    match pooling {
+        Pooling::Cls => tokens.first().map_or_else(|| vec![0.0; hidden_size], |t| t.to_vec()),
+        Pooling::LastToken => tokens.last().map_or_else(|| vec![0.0; hidden_size], |t| t.to_vec()),
+        Pooling::Mean => {
+            let count = tokens.len().max(1) as f32;
+            sum().into_iter().map(|value| value / count).collect()
+        }
+        Pooling::MeanSqrtLen => {
+            let count = (tokens.len().max(1) as f32).sqrt();
+            sum().into_iter().map(|value| value / count).collect()
+        }
+        Pooling::Max => tokens.iter().fold(vec![f32::MIN; hidden_size], |mut max, token| {
+            max.iter_mut().zip(token.iter()).for_each(|(max, &value)| *max = max.max(value));
+            max
+        }),
+    }
+}
+
+/// A model loaded in the ONNX Runtime.
+#[cfg(feature = "onnx")]
+struct Model {
+    session: ort::session::Session,
+}
+
+#[cfg(feature = "onnx")]
+impl Model {
+    fn load(filename: &std::path::Path) -> Result<Self, NewEmbedderError> {
+        use ort::execution_providers::{CPUExecutionProvider, CUDAExecutionProvider};
+        use ort::session::builder::GraphOptimizationLevel;
+        use ort::session::Session;
+
+        // execution providers that are not available are skipped, defaulting to the CPU
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| {
+                builder.with_execution_providers([
+                    CUDAExecutionProvider::default().build(),
+                    CPUExecutionProvider::default().build(),
+                ])
+            })
+            .and_then(|builder| builder.commit_from_file(filename))
+            .map_err(|error| NewEmbedderError::onnx_load_model(Box::new(error)))?;
+
+        Ok(Self { session })
+    }
+
+    /// Returns the hidden states of the tokens, of shape `[batch_size, sequence_length, hidden_size]`.
+    fn run(
+        &self,
+        shape: [usize; 2],
+        input_ids: Vec<i64>,
+        attention_mask: Vec<i64>,
+        token_type_ids: Vec<i64>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        use std::borrow::Cow;
+
+        use ort::session::SessionInputValue;
+        use ort::value::Tensor;
+
+        let inference = |error: ort::Error| EmbedError::onnx_inference(Box::new(error));
+
+        let mut inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = vec![
+            ("input_ids".into(), Tensor::from_array((shape, input_ids)).map_err(inference)?.into()),
+            (
+                "attention_mask".into(),
+                Tensor::from_array((shape, attention_mask)).map_err(inference)?.into(),
+            ),
+        ];
+        // only BERT-like models expect the token types
+        if self.session.inputs.iter().any(|input| input.name == "token_type_ids") {
+            inputs.push((
+                "token_type_ids".into(),
+                Tensor::from_array((shape, token_type_ids)).map_err(inference)?.into(),
+            ));
+        }
+
+        let outputs = self.session.run(inputs).map_err(inference)?;
+        let (_shape, hidden_states) =
+            outputs[0].try_extract_raw_tensor::<f32>().map_err(inference)?;
+        Ok(hidden_states.to_vec())
+    }
+}
+
+/// Without the `onnx` feature, no model can be loaded.
+#[cfg(not(feature = "onnx"))]
+enum Model {}
+
+#[cfg(not(feature = "onnx"))]
+impl Model {
+    fn load(_filename: &std::path::Path) -> Result<Self, NewEmbedderError> {
+        Err(NewEmbedderError::onnx_unavailable())
+    }
+
+    fn run(
+        &self,
+        _shape: [usize; 2],
+        _input_ids: Vec<i64>,
+        _attention_mask: Vec<i64>,
+        _token_type_ids: Vec<i64>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        match *self {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pooling_ignores_padding() {
+        // two tokens and a padding token, with a hidden size of 2
+        let tokens = [1.0, 2.0, 3.0, 6.0, 100.0, 100.0];
+        let mask = [1, 1, 0];
+
+        assert_eq!(pool(Pooling::Cls, &tokens, &mask, 2), vec![1.0, 2.0]);
+        assert_eq!(pool(Pooling::LastToken, &tokens, &mask, 2), vec![3.0, 6.0]);
+        assert_eq!(pool(Pooling::Mean, &tokens, &mask, 2), vec![2.0, 4.0]);
+        assert_eq!(pool(Pooling::Max, &tokens, &mask, 2), vec![3.0, 6.0]);
+        let sqrt_2 = 2f32.sqrt();
+        assert_eq!(pool(Pooling::MeanSqrtLen, &tokens, &mask, 2), vec![4.0 / sqrt_2, 8.0 / sqrt_2]);
+    }
+}
@@ -7,6 +7,7 @@ use rayon::slice::ParallelSlice as _;
 
 use super::error::{EmbedError, NewEmbedderError};
 use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
+use super::usage::EmbedderUsage;
 use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
 use crate::error::FaultSource;
 use crate::vector::error::EmbedErrorKind;
@@ -323,6 +324,10 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         self.rest_embedder.cache()
     }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        self.rest_embedder.indexing_usage()
+    }
 }
 
 impl fmt::Debug for Embedder {
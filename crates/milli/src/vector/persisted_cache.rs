@@ -0,0 +1,182 @@
+//! A cache of the embeddings generated while indexing documents, persisted in the environment of the index.
+//!
+//! Entries are keyed by a hash of the options of the embedder and of the rendered document template,
+//! so that documents whose rendering was already embedded, for instance documents that were deleted and
+//! added back, are not sent to the embedder again.
+//!
+//! The cache is bounded to [`MAX_ENTRIES`] entries, and the least recently used entries are evicted
+//! at the end of the indexing operations that overflow it.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use heed::{RoTxn, RwTxn};
+use sha2::{Digest as _, Sha256};
+
+use super::{EmbedderOptions, Embedding};
+use crate::{Index, Result};
+
+/// Maximal number of embeddings kept in the cache of an index.
+pub const MAX_ENTRIES: u64 = 100_000;
+
+/// Number of entries kept when evicting, so that an eviction is not triggered by each indexing operation.
+const ENTRIES_AFTER_EVICTION: u64 = MAX_ENTRIES / 10 * 9;
+
+const TIMESTAMP_LEN: usize = std::mem::size_of::<u64>();
+
+/// The key of an embedding in the cache.
+pub type CacheKey = [u8; 32];
+
+/// A hash of the options of an embedder.
+///
+/// Changing any option of the embedder changes the fingerprint, so that embeddings generated with other
+/// options are never returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbedderFingerprint([u8; 32]);
+
+impl EmbedderFingerprint {
+    pub fn new(options: &EmbedderOptions) -> Self {
+        // serializing the options cannot fail as they only contain maps with string keys
+        let options = serde_json::to_vec(options).unwrap();
+        Self(Sha256::digest(options).into())
+    }
+
+    /// The key in the cache of the embedding of the `rendered` text.
+    pub fn key(&self, rendered: &str) -> CacheKey {
+        let mut hasher = Sha256::new();
+        hasher.update(self.0);
+        hasher.update(rendered.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// Returns the embedding cached under `key`, if any.
+pub fn get(index: &Index, rtxn: &RoTxn<'_>, key: &CacheKey) -> Result<Option<Embedding>> {
+    let Some(value) = index.embedding_cache.get(rtxn, key)? else {
+        return Ok(None);
+    };
+    Ok(Some(decode_embedding(value)))
+}
+
+/// The length of the value storing `embedding` in the cache.
+pub fn value_length(embedding: &[f32]) -> usize {
+    TIMESTAMP_LEN + std::mem::size_of_val(embedding)
+}
+
+/// Writes `embedding` with the current time in `buffer`, that must be of [`value_length`] bytes.
+pub fn encode_value_into(embedding: &[f32], buffer: &mut [u8]) {
+    let (timestamp, embedding_bytes) = buffer.split_at_mut(TIMESTAMP_LEN);
+    timestamp.copy_from_slice(&now().to_be_bytes());
+    embedding_bytes.copy_from_slice(bytemuck::cast_slice(embedding));
+}
+
+fn decode_embedding(value: &[u8]) -> Embedding {
+    // the value isn't necessarily aligned for f32s
+    value[TIMESTAMP_LEN..]
+        .chunks_exact(std::mem::size_of::<f32>())
+        .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+        .collect()
+}
+
+fn decode_timestamp(value: &[u8]) -> u64 {
+    u64::from_be_bytes(value[..TIMESTAMP_LEN].try_into().unwrap())
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+/// Evicts the least recently used embeddings if the cache holds more than [`MAX_ENTRIES`] entries.
+///
+/// Returns the number of evicted embeddings.
+pub fn evict(index: &Index, wtxn: &mut RwTxn<'_>) -> Result<u64> {
+    evict_with_bounds(index, wtxn, MAX_ENTRIES, ENTRIES_AFTER_EVICTION)
+}
+
+fn evict_with_bounds(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    max_entries: u64,
+    entries_after_eviction: u64,
+) -> Result<u64> {
+    let len = index.embedding_cache.len(wtxn)?;
+    if len <= max_entries {
+        return Ok(0);
+    }
+
+    let mut entries = Vec::with_capacity(len as usize);
+    for result in index.embedding_cache.iter(wtxn)? {
+        let (key, value) = result?;
+        let key: CacheKey = key.try_into().unwrap();
+        entries.push((decode_timestamp(value), key));
+    }
+
+    let to_evict = (len - entries_after_eviction) as usize;
+    if to_evict < entries.len() {
+        entries.select_nth_unstable(to_evict);
+    }
+    for (_, key) in &entries[..to_evict] {
+        index.embedding_cache.delete(wtxn, key)?;
+    }
+
+    tracing::debug!(evicted = to_evict, "evicted embeddings from the cache");
+    Ok(to_evict as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::index::tests::TempIndex;
+    use crate::vector::manual;
+
+    fn put(index: &Index, wtxn: &mut RwTxn<'_>, key: CacheKey, embedding: &[f32], timestamp: u64) {
+        let mut value = vec![0; value_length(embedding)];
+        encode_value_into(embedding, &mut value);
+        value[..TIMESTAMP_LEN].copy_from_slice(&timestamp.to_be_bytes());
+        index.embedding_cache.put(wtxn, &key, &value).unwrap();
+    }
+
+    #[test]
+    fn fingerprint_depends_on_options() {
+        let options = |dimensions| {
+            EmbedderOptions::UserProvided(manual::EmbedderOptions {
+                dimensions,
+                distribution: None,
+            })
+        };
+        let fingerprint = EmbedderFingerprint::new(&options(3));
+        assert_eq!(fingerprint, EmbedderFingerprint::new(&options(3)));
+        assert_ne!(fingerprint, EmbedderFingerprint::new(&options(4)));
+        assert_ne!(fingerprint.key("kefir"), fingerprint.key("intel"));
+        assert_ne!(fingerprint.key("kefir"), EmbedderFingerprint::new(&options(4)).key("kefir"));
+    }
+
+    #[test]
+    fn get_cached_embedding() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+        put(&index, &mut wtxn, [0; 32], &[0.5, -1.0, 2.0], 0);
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(get(&index, &rtxn, &[0; 32]).unwrap(), Some(vec![0.5, -1.0, 2.0]));
+        assert_eq!(get(&index, &rtxn, &[1; 32]).unwrap(), None);
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+        for i in 0..10u8 {
+            put(&index, &mut wtxn, [i; 32], &[i as f32], 100 - i as u64);
+        }
+
+        assert_eq!(evict_with_bounds(&index, &mut wtxn, 10, 6).unwrap(), 0);
+        put(&index, &mut wtxn, [10; 32], &[10.0], 50);
+        assert_eq!(evict_with_bounds(&index, &mut wtxn, 10, 6).unwrap(), 5);
+
+        let mut remaining: Vec<u8> =
+            index.embedding_cache.iter(&wtxn).unwrap().map(|entry| entry.unwrap().0[0]).collect();
+        remaining.sort_unstable();
+        assert_eq!(remaining, vec![0, 1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use bumpalo::Bump;
+use deserr::Deserr;
+use heed::RoTxn;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::error::EmbedError;
+use super::rest::{check_response, ConfigurationSource, Retry};
+use crate::prompt::{default_max_bytes, Prompt, PromptData};
+use crate::update::new::document::DocumentFromDb;
+use crate::update::Setting;
+use crate::{DocumentId, GlobalFieldsIdsMap, Index, Result, UserError};
+
+/// The reranker must answer faster than an embedder, as it runs during every search request.
+const RERANK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankerSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// URL of the reranking endpoint.
+    ///
+    /// The endpoint receives `{"model", "query", "documents", "top_n"}` and must reply with
+    /// `{"results": [{"index", "relevance_score"}]}`, like the Cohere and Jina rerank APIs.
+    ///
+    /// # Availability
+    ///
+    /// - Mandatory when the reranker is set
+    pub url: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// Authentication token sent as a bearer token to the reranking endpoint.
+    pub api_key: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// Name of the cross-encoder model, sent along with the documents to rerank.
+    pub model: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// A liquid template used to render the documents sent to the reranker.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to the default document template of the embedders
+    pub document_template: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Rendered documents are truncated to this many bytes.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to 400
+    pub document_template_max_bytes: Setting<usize>,
+}
+
+/// The reranker configuration, as stored in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RerankerOptions {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub document_template: String,
+    pub document_template_max_bytes: Option<NonZeroUsize>,
+}
+
+impl TryFrom<RerankerSettings> for RerankerOptions {
+    type Error = UserError;
+
+    fn try_from(settings: RerankerSettings) -> std::result::Result<Self, Self::Error> {
+        let RerankerSettings {
+            url,
+            api_key,
+            model,
+            document_template,
+            document_template_max_bytes,
+        } = settings;
+
+        let url = url.set().ok_or(UserError::MissingRerankerUrl)?;
+        url::Url::parse(&url).map_err(|inner_error| UserError::InvalidRerankerUrl {
+            inner_error,
+            url: url.clone(),
+        })?;
+
+        let document_template_max_bytes = match document_template_max_bytes.set() {
+            Some(max_bytes) => Some(
+                NonZeroUsize::new(max_bytes)
+                    .ok_or(UserError::InvalidRerankerDocumentTemplateMaxBytes)?,
+            ),
+            None => None,
+        };
+        let document_template =
+            document_template.set().unwrap_or_else(|| PromptData::default().template);
+        Prompt::new(document_template.clone(), document_template_max_bytes)
+            .map_err(UserError::InvalidRerankerDocumentTemplate)?;
+
+        Ok(Self {
+            url,
+            api_key: api_key.set(),
+            model: model.set(),
+            document_template,
+            document_template_max_bytes,
+        })
+    }
+}
+
+impl From<RerankerOptions> for RerankerSettings {
+    fn from(options: RerankerOptions) -> Self {
+        let RerankerOptions { url, api_key, model, document_template, document_template_max_bytes } =
+            options;
+        Self {
+            url: Setting::Set(url),
+            api_key: Setting::some_or_not_set(api_key),
+            model: Setting::some_or_not_set(model),
+            document_template: Setting::Set(document_template),
+            document_template_max_bytes: Setting::Set(
+                document_template_max_bytes.unwrap_or(default_max_bytes()).get(),
+            ),
+        }
+    }
+}
+
+/// Reorders the top hits of a search with a cross-encoder model served behind a REST endpoint.
+pub struct Reranker {
+    client: ureq::Agent,
+    bearer: Option<String>,
+    url: String,
+    model: Option<String>,
+    prompt: Prompt,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f64,
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self> {
+        let RerankerOptions { url, api_key, model, document_template, document_template_max_bytes } =
+            options;
+        let prompt = Prompt::new(
+            document_template,
+            Some(document_template_max_bytes.unwrap_or(default_max_bytes())),
+        )
+        .map_err(UserError::InvalidRerankerDocumentTemplate)?;
+        let client = ureq::AgentBuilder::new().timeout(RERANK_TIMEOUT).build();
+
+        Ok(Self {
+            client,
+            bearer: api_key.map(|api_key| format!("Bearer {api_key}")),
+            url,
+            model,
+            prompt,
+        })
+    }
+
+    /// Scores the documents against the query.
+    ///
+    /// Returns the positions of the documents in `docids` along with their relevance score,
+    /// sorted by decreasing relevance. The documents omitted by the reranker are not returned.
+    pub fn rerank(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        query: &str,
+        docids: &[DocumentId],
+    ) -> Result<Vec<(usize, f64)>> {
+        if docids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let db_fields_ids_map = index.fields_ids_map(rtxn)?;
+        let fields_ids_map = RwLock::new(index.fields_ids_map_with_metadata(rtxn)?);
+        let fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map));
+        let doc_alloc = Bump::new();
+
+        let mut documents = Vec::with_capacity(docids.len());
+        for (&docid, external_docid) in
+            docids.iter().zip(index.external_id_of(rtxn, docids.iter().copied())?)
+        {
+            let external_docid = external_docid?;
+            let document = DocumentFromDb::new(docid, rtxn, index, &db_fields_ids_map)?
+                .ok_or(UserError::UnknownInternalDocumentId { document_id: docid })?;
+            let rendered = self
+                .prompt
+                .render_document(&external_docid, document, &fields_ids_map, &doc_alloc)
+                .map_err(UserError::MissingDocumentField)?;
+            documents.push(rendered);
+        }
+
+        let mut request = self.client.post(&self.url);
+        if let Some(bearer) = &self.bearer {
+            request = request.set("Authorization", bearer);
+        }
+        let body = serde_json::json!({
+            "model": self.model,
+            "query": query,
+            "documents": documents,
+            "top_n": documents.len(),
+        });
+
+        let response = check_response(request.send_json(&body), ConfigurationSource::Reranker)
+            .map_err(Retry::into_error)
+            .map_err(super::Error::from)?;
+        let response: RerankResponse = response
+            .into_json()
+            .map_err(EmbedError::rest_response_deserialization)
+            .map_err(super::Error::from)?;
+
+        let mut seen = vec![false; documents.len()];
+        let mut results = Vec::with_capacity(response.results.len());
+        for RerankResult { index, relevance_score } in response.results {
+            let Some(seen) = seen.get_mut(index) else {
+                return Err(super::Error::from(EmbedError::rerank_unknown_index(
+                    index,
+                    documents.len(),
+                ))
+                .into());
+            };
+            if !std::mem::replace(seen, true) {
+                results.push((index, relevance_score));
+            }
+        }
+        results.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+        Ok(results)
+    }
+}
@@ -7,8 +7,10 @@ use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
 use rayon::slice::ParallelSlice as _;
 use serde::{Deserialize, Serialize};
 
+use super::bedrock::SigV4Signer;
 use super::error::EmbedErrorKind;
 use super::json_template::ValueTemplate;
+use super::usage::{self, EmbedderUsage, UsageCounter};
 use super::{
     DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError, REQUEST_PARALLELISM,
 };
@@ -25,7 +27,11 @@ pub struct Retry {
 pub enum ConfigurationSource {
     OpenAi,
     Ollama,
+    Cohere,
+    AzureOpenAi,
+    Bedrock,
     User,
+    Reranker,
 }
 
 pub enum RetryStrategy {
@@ -33,6 +39,7 @@ pub enum RetryStrategy {
     Retry,
     RetryTokenized,
     RetryAfterRateLimit,
+    RetryAfter(std::time::Duration),
 }
 
 impl Retry {
@@ -52,6 +59,11 @@ impl Retry {
         Self { error, strategy: RetryStrategy::RetryAfterRateLimit }
     }
 
+    /// Rate limited by a server that told us how long to wait before retrying.
+    pub fn rate_limited_for(error: EmbedError, duration: std::time::Duration) -> Self {
+        Self { error, strategy: RetryStrategy::RetryAfter(duration) }
+    }
+
     pub fn into_duration(self, attempt: u32) -> Result<std::time::Duration, EmbedError> {
         match self.strategy {
             RetryStrategy::GiveUp => Err(self.error),
@@ -60,6 +72,7 @@ impl Retry {
             RetryStrategy::RetryAfterRateLimit => {
                 Ok(std::time::Duration::from_millis(100 + 10u64.pow(attempt)))
             }
+            RetryStrategy::RetryAfter(duration) => Ok(duration),
         }
     }
 
@@ -90,6 +103,9 @@ struct EmbedderData {
     request: Request,
     response: Response,
     configuration_source: ConfigurationSource,
+    /// Signs each request, for the servers that don't authenticate with static headers.
+    signer: Option<SigV4Signer>,
+    usage: UsageCounter,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -128,6 +144,15 @@ impl Embedder {
         options: EmbedderOptions,
         cache_cap: usize,
         configuration_source: ConfigurationSource,
+    ) -> Result<Self, NewEmbedderError> {
+        Self::new_signed(options, cache_cap, configuration_source, None)
+    }
+
+    pub(super) fn new_signed(
+        options: EmbedderOptions,
+        cache_cap: usize,
+        configuration_source: ConfigurationSource,
+        signer: Option<SigV4Signer>,
     ) -> Result<Self, NewEmbedderError> {
         let bearer = options.api_key.as_deref().map(|api_key| format!("Bearer {api_key}"));
 
@@ -148,6 +173,8 @@ impl Embedder {
             response,
             configuration_source,
             headers: options.headers,
+            signer,
+            usage: UsageCounter::default(),
         };
 
         let dimensions = if let Some(dimensions) = options.dimensions {
@@ -269,6 +296,10 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
+
+    pub(super) fn indexing_usage(&self) -> EmbedderUsage {
+        self.data.usage.get()
+    }
 }
 
 fn infer_dimensions(data: &EmbedderData) -> Result<usize, NewEmbedderError> {
@@ -302,13 +333,16 @@ where
     let body = data.request.inject_texts(inputs);
 
     for attempt in 0..10 {
-        let response = request.clone().send_json(&body);
+        let response = send(data, &request, &body);
         let result = check_response(response, data.configuration_source).and_then(|response| {
             response_to_embedding(response, data, expected_count, expected_dimension)
         });
 
         let retry_duration = match result {
-            Ok(response) => return Ok(response),
+            Ok((embeddings, tokens)) => {
+                record_usage(data, expected_count, tokens);
+                return Ok(embeddings);
+            }
             Err(retry) => {
                 tracing::warn!("Failed: {}", retry.error);
                 if let Some(deadline) = deadline {
@@ -336,28 +370,86 @@ where
         std::thread::sleep(retry_duration);
     }
 
-    let response = request.send_json(&body);
+    let response = send(data, &request, &body);
     let result = check_response(response, data.configuration_source);
-    result.map_err(Retry::into_error).and_then(|response| {
+    let (embeddings, tokens) = result.map_err(Retry::into_error).and_then(|response| {
         response_to_embedding(response, data, expected_count, expected_dimension)
             .map_err(Retry::into_error)
-    })
+    })?;
+    record_usage(data, expected_count, tokens);
+    Ok(embeddings)
 }
 
-fn check_response(
+fn record_usage(data: &EmbedderData, texts: usize, tokens: u64) {
+    usage::record(&data.usage, EmbedderUsage { requests: 1, texts: texts as u64, tokens });
+}
+
+/// The tokens billed for a request, in the places where the known embedding providers report them.
+fn reported_tokens(response: &serde_json::Value) -> u64 {
+    const TOKEN_POINTERS: &[&str] = &[
+        // OpenAI, Azure OpenAI, Mistral and the OpenAI-compatible APIs
+        "/usage/total_tokens",
+        // Cohere
+        "/meta/billed_units/input_tokens",
+        // Ollama
+        "/prompt_eval_count",
+        // Amazon Titan on Bedrock
+        "/inputTextTokenCount",
+    ];
+    TOKEN_POINTERS
+        .iter()
+        .find_map(|pointer| response.pointer(pointer).and_then(serde_json::Value::as_u64))
+        .unwrap_or_default()
+}
+
+fn send(
+    data: &EmbedderData,
+    request: &ureq::Request,
+    body: &serde_json::Value,
+) -> Result<ureq::Response, ureq::Error> {
+    match &data.signer {
+        Some(signer) => {
+            // the signature is computed on the exact bytes that are sent
+            let body = body.to_string();
+            let mut request = request.clone();
+            for (header, value) in signer.sign(&data.url, body.as_bytes()) {
+                request = request.set(header, &value);
+            }
+            request.send_string(&body)
+        }
+        None => request.clone().send_json(body),
+    }
+}
+
+pub(super) fn check_response(
     response: Result<ureq::Response, ureq::Error>,
     configuration_source: ConfigurationSource,
 ) -> Result<ureq::Response, Retry> {
     match response {
         Ok(response) => Ok(response),
         Err(ureq::Error::Status(code, response)) => {
+            // the `Retry-After` header is expressed in seconds
+            let retry_after = response
+                .header("Retry-After")
+                .and_then(|seconds| seconds.trim().parse().ok())
+                .map(std::time::Duration::from_secs);
             let error_response: Option<String> = response.into_string().ok();
             Err(match code {
                 401 => Retry::give_up(EmbedError::rest_unauthorized(
                     error_response,
                     configuration_source,
                 )),
-                429 => Retry::rate_limited(EmbedError::rest_too_many_requests(error_response)),
+                // AWS replies with a 403 when the signature or the credentials are invalid
+                403 if configuration_source == ConfigurationSource::Bedrock => Retry::give_up(
+                    EmbedError::rest_unauthorized(error_response, configuration_source),
+                ),
+                429 => {
+                    let error = EmbedError::rest_too_many_requests(error_response);
+                    match retry_after {
+                        Some(duration) => Retry::rate_limited_for(error, duration),
+                        None => Retry::rate_limited(error),
+                    }
+                }
                 400 => Retry::give_up(EmbedError::rest_bad_request(
                     error_response,
                     configuration_source,
@@ -382,12 +474,13 @@ fn response_to_embedding(
     data: &EmbedderData,
     expected_count: usize,
     expected_dimensions: Option<usize>,
-) -> Result<Vec<Embedding>, Retry> {
+) -> Result<(Vec<Embedding>, u64), Retry> {
     let response: serde_json::Value = response
         .into_json()
         .map_err(EmbedError::rest_response_deserialization)
         .map_err(Retry::retry_later)?;
 
+    let tokens = reported_tokens(&response);
     let embeddings = data.response.extract_embeddings(response).map_err(Retry::give_up)?;
 
     if embeddings.len() != expected_count {
@@ -408,7 +501,7 @@ fn response_to_embedding(
         }
     }
 
-    Ok(embeddings)
+    Ok((embeddings, tokens))
 }
 
 pub(super) const REQUEST_PLACEHOLDER: &str = "{{text}}";
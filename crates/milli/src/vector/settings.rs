@@ -8,7 +8,7 @@ use utoipa::ToSchema;
 
 use super::composite::SubEmbedderOptions;
 use super::hf::OverridePooling;
-use super::{ollama, openai, DistributionShift, EmbedderOptions};
+use super::{azure_openai, bedrock, cohere, ollama, openai, DistributionShift, EmbedderOptions};
 use crate::prompt::{default_max_bytes, PromptData};
 use crate::update::Setting;
 use crate::vector::EmbeddingConfig;
@@ -40,11 +40,11 @@ pub struct EmbeddingSettings {
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama`, `azureOpenAi` and `bedrock`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -54,6 +54,10 @@ pub struct EmbeddingSettings {
     ///
     /// - For source `openAi`, defaults to `text-embedding-3-small`
     /// - For source `huggingFace`, defaults to `BAAI/bge-base-en-v1.5`
+    /// - For source `onnx`, defaults to `sentence-transformers/all-MiniLM-L6-v2`
+    /// - For source `cohere`, defaults to `embed-english-v3.0`
+    /// - For source `azureOpenAi`, this is the name of the deployment of the model
+    /// - For source `bedrock`, this is the identifier of the model, such as `amazon.titan-embed-text-v2:0`
     pub model: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -64,7 +68,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -82,7 +86,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -103,7 +107,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -112,6 +116,9 @@ pub struct EmbeddingSettings {
     /// # Defaults
     ///
     /// - For source `openAi`, the key is read from `OPENAI_API_KEY`, then `MEILI_OPENAI_API_KEY`.
+    /// - For source `cohere`, the key is read from `MEILI_COHERE_API_KEY`, then `COHERE_API_KEY`.
+    /// - For source `azureOpenAi`, the key is read from `MEILI_AZURE_OPENAI_API_KEY`, then `AZURE_OPENAI_API_KEY`, unless an `Authorization` header is set.
+    /// - For source `bedrock`, the key has the `<accessKeyId>:<secretAccessKey>[:<sessionToken>]` format, and is read from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` otherwise.
     /// - For other sources, no bearer token is sent if this parameter is not set.
     ///
     /// # Note
@@ -129,17 +136,18 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `rest`, `userProvided`
     ///
     /// # 🔄 Reindexing
     ///
-    /// - 🏗️ When the source is `openAi`, changing the value of this parameter always regenerates embeddings
+    /// - 🏗️ When the source is `openAi`, `cohere`, `azureOpenAi` or `bedrock`, changing the value of this parameter always regenerates embeddings
     /// - 🌱 For other sources, changing the value of this parameter never regenerates embeddings
     ///
     /// # Defaults
     ///
     /// - For source `openAi`, the dimensions is the maximum allowed by the model.
-    /// - For sources `ollama` and `rest`, the dimensions are inferred by embedding a sample text.
+    /// - For source `cohere`, the dimensions is the native dimensions of the model, or is inferred by embedding a sample text for models unknown to Meilisearch.
+    /// - For sources `ollama`, `azureOpenAi`, `bedrock` and `rest`, the dimensions are inferred by embedding a sample text.
     pub dimensions: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -177,7 +185,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest
     ///
     /// # 🔄 Reindexing
     ///
@@ -190,7 +198,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -203,21 +211,47 @@ pub struct EmbeddingSettings {
     pub document_template_max_bytes: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Rendered texts are split in chunks of at most this size, each chunk producing an embedding for the document.
+    ///
+    /// Searches rank a document by its chunk closest to the query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are regenerated for all documents
+    ///
+    /// # Default
+    ///
+    /// - Rendered texts are not split
+    pub document_template_chunk_bytes: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// URL to reach the remote embedder.
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `rest`
+    /// - This parameter is mandatory for sources `azureOpenAi` and `rest`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock` and `rest`
+    ///
+    /// # Note
+    ///
+    /// - For source `azureOpenAi`, this is the endpoint of the resource, such as `https://{resource-name}.openai.azure.com`.
+    ///   An `api-version` query parameter can be passed to override the default API version (`2024-10-21`).
+    /// - For source `bedrock`, this is the endpoint of the Bedrock runtime, defaulting to `https://bedrock-runtime.{region}.amazonaws.com`,
+    ///   where the region is read from `AWS_REGION`, then `AWS_DEFAULT_REGION`, and defaults to `us-east-1`.
     ///
     /// # 🔄 Reindexing
     ///
-    /// - 🌱 When modified for source `openAi`, embeddings are never regenerated
-    /// - 🏗️ When modified for sources `ollama` and `rest`, embeddings are always regenerated
+    /// - 🌱 When modified for sources `openAi` and `cohere`, embeddings are never regenerated
+    /// - 🏗️ When modified for sources `ollama`, `azureOpenAi`, `bedrock` and `rest`, embeddings are always regenerated
     pub url: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -260,7 +294,11 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `rest`
+    /// - This parameter is available for sources `azureOpenAi` and `rest`
+    ///
+    /// # Note
+    ///
+    /// - For source `azureOpenAi`, pass an `Authorization: Bearer <token>` header to authenticate with Microsoft Entra ID instead of an API key.
     ///
     /// # 🔄 Reindexing
     ///
@@ -318,11 +356,11 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama`, `azureOpenAi` and `bedrock`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -332,6 +370,10 @@ pub struct SubEmbeddingSettings {
     ///
     /// - For source `openAi`, defaults to `text-embedding-3-small`
     /// - For source `huggingFace`, defaults to `BAAI/bge-base-en-v1.5`
+    /// - For source `onnx`, defaults to `sentence-transformers/all-MiniLM-L6-v2`
+    /// - For source `cohere`, defaults to `embed-english-v3.0`
+    /// - For source `azureOpenAi`, this is the name of the deployment of the model
+    /// - For source `bedrock`, this is the identifier of the model, such as `amazon.titan-embed-text-v2:0`
     pub model: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -342,7 +384,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -360,7 +402,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -381,7 +423,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -390,6 +432,9 @@ pub struct SubEmbeddingSettings {
     /// # Defaults
     ///
     /// - For source `openAi`, the key is read from `OPENAI_API_KEY`, then `MEILI_OPENAI_API_KEY`.
+    /// - For source `cohere`, the key is read from `MEILI_COHERE_API_KEY`, then `COHERE_API_KEY`.
+    /// - For source `azureOpenAi`, the key is read from `MEILI_AZURE_OPENAI_API_KEY`, then `AZURE_OPENAI_API_KEY`, unless an `Authorization` header is set.
+    /// - For source `bedrock`, the key has the `<accessKeyId>:<secretAccessKey>[:<sessionToken>]` format, and is read from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` otherwise.
     /// - For other sources, no bearer token is sent if this parameter is not set.
     ///
     /// # Note
@@ -407,17 +452,18 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `rest`, `userProvided`
     ///
     /// # 🔄 Reindexing
     ///
-    /// - 🏗️ When the source is `openAi`, changing the value of this parameter always regenerates embeddings
+    /// - 🏗️ When the source is `openAi`, `cohere`, `azureOpenAi` or `bedrock`, changing the value of this parameter always regenerates embeddings
     /// - 🌱 For other sources, changing the value of this parameter never regenerates embeddings
     ///
     /// # Defaults
     ///
     /// - For source `openAi`, the dimensions is the maximum allowed by the model.
-    /// - For sources `ollama` and `rest`, the dimensions are inferred by embedding a sample text.
+    /// - For source `cohere`, the dimensions is the native dimensions of the model, or is inferred by embedding a sample text for models unknown to Meilisearch.
+    /// - For sources `ollama`, `azureOpenAi`, `bedrock` and `rest`, the dimensions are inferred by embedding a sample text.
     pub dimensions: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -429,7 +475,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest
     ///
     /// # 🔄 Reindexing
     ///
@@ -442,7 +488,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -455,21 +501,47 @@ pub struct SubEmbeddingSettings {
     pub document_template_max_bytes: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Rendered texts are split in chunks of at most this size, each chunk producing an embedding for the document.
+    ///
+    /// Searches rank a document by its chunk closest to the query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `cohere`, `azureOpenAi`, `bedrock`, `onnx` and `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are regenerated for all documents
+    ///
+    /// # Default
+    ///
+    /// - Rendered texts are not split
+    pub document_template_chunk_bytes: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// URL to reach the remote embedder.
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `rest`
+    /// - This parameter is mandatory for sources `azureOpenAi` and `rest`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `ollama`, `cohere`, `azureOpenAi`, `bedrock` and `rest`
+    ///
+    /// # Note
+    ///
+    /// - For source `azureOpenAi`, this is the endpoint of the resource, such as `https://{resource-name}.openai.azure.com`.
+    ///   An `api-version` query parameter can be passed to override the default API version (`2024-10-21`).
+    /// - For source `bedrock`, this is the endpoint of the Bedrock runtime, defaulting to `https://bedrock-runtime.{region}.amazonaws.com`,
+    ///   where the region is read from `AWS_REGION`, then `AWS_DEFAULT_REGION`, and defaults to `us-east-1`.
     ///
     /// # 🔄 Reindexing
     ///
-    /// - 🌱 When modified for source `openAi`, embeddings are never regenerated
-    /// - 🏗️ When modified for sources `ollama` and `rest`, embeddings are always regenerated
+    /// - 🌱 When modified for sources `openAi` and `cohere`, embeddings are never regenerated
+    /// - 🏗️ When modified for sources `ollama`, `azureOpenAi`, `bedrock` and `rest`, embeddings are always regenerated
     pub url: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -512,7 +584,11 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `rest`
+    /// - This parameter is available for sources `azureOpenAi` and `rest`
+    ///
+    /// # Note
+    ///
+    /// - For source `azureOpenAi`, pass an `Authorization: Bearer <token>` header to authenticate with Microsoft Entra ID instead of an API key.
     ///
     /// # 🔄 Reindexing
     ///
@@ -629,6 +705,7 @@ impl SettingsDiff {
                     mut distribution,
                     mut headers,
                     mut document_template_max_bytes,
+                    mut document_template_chunk_bytes,
                     binary_quantized: mut binary_quantize,
                 } = old;
 
@@ -648,6 +725,7 @@ impl SettingsDiff {
                     distribution: new_distribution,
                     headers: new_headers,
                     document_template_max_bytes: new_document_template_max_bytes,
+                    document_template_chunk_bytes: new_document_template_chunk_bytes,
                     binary_quantized: new_binary_quantize,
                 } = new;
 
@@ -671,6 +749,7 @@ impl SettingsDiff {
                     &mut dimensions,
                     &mut document_template,
                     &mut document_template_max_bytes,
+                    &mut document_template_chunk_bytes,
                     &mut url,
                     &mut request,
                     &mut response,
@@ -683,6 +762,7 @@ impl SettingsDiff {
                     new_dimensions,
                     new_document_template,
                     new_document_template_max_bytes,
+                    new_document_template_chunk_bytes,
                     new_url,
                     new_request,
                     new_response,
@@ -717,6 +797,7 @@ impl SettingsDiff {
                     distribution,
                     headers,
                     document_template_max_bytes,
+                    document_template_chunk_bytes,
                     binary_quantized: binary_quantize,
                 };
 
@@ -756,6 +837,7 @@ impl SettingsDiff {
                     mut dimensions,
                     mut document_template,
                     mut document_template_max_bytes,
+                    mut document_template_chunk_bytes,
                     mut url,
                     mut request,
                     mut response,
@@ -781,6 +863,7 @@ impl SettingsDiff {
                     dimensions: new_dimensions,
                     document_template: new_document_template,
                     document_template_max_bytes: new_document_template_max_bytes,
+                    document_template_chunk_bytes: new_document_template_chunk_bytes,
                     url: new_url,
                     request: new_request,
                     response: new_response,
@@ -801,6 +884,7 @@ impl SettingsDiff {
                     &mut dimensions,
                     &mut document_template,
                     &mut document_template_max_bytes,
+                    &mut document_template_chunk_bytes,
                     &mut url,
                     &mut request,
                     &mut response,
@@ -813,6 +897,7 @@ impl SettingsDiff {
                     new_dimensions,
                     new_document_template,
                     new_document_template_max_bytes,
+                    new_document_template_chunk_bytes,
                     new_url,
                     new_request,
                     new_response,
@@ -838,6 +923,7 @@ impl SettingsDiff {
                     response,
                     headers,
                     document_template_max_bytes,
+                    document_template_chunk_bytes,
                     distribution,
                     binary_quantized,
                     search_embedder,
@@ -862,6 +948,7 @@ impl SettingsDiff {
         dimensions: &mut Setting<usize>,
         document_template: &mut Setting<String>,
         document_template_max_bytes: &mut Setting<usize>,
+        document_template_chunk_bytes: &mut Setting<usize>,
         url: &mut Setting<String>,
         request: &mut Setting<serde_json::Value>,
         response: &mut Setting<serde_json::Value>,
@@ -874,6 +961,7 @@ impl SettingsDiff {
         new_dimensions: Setting<usize>,
         new_document_template: Setting<String>,
         new_document_template_max_bytes: Setting<usize>,
+        new_document_template_chunk_bytes: Setting<usize>,
         new_url: Setting<String>,
         new_request: Setting<serde_json::Value>,
         new_response: Setting<serde_json::Value>,
@@ -894,6 +982,7 @@ impl SettingsDiff {
                 response,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 headers,
                 // send dummy values, the source cannot recursively be composite
                 &mut Setting::NotSet,
@@ -911,8 +1000,14 @@ impl SettingsDiff {
         }
         if dimensions.apply(new_dimensions) {
             match *source {
-                // regenerate on dimensions change in OpenAI since truncation is supported
-                Setting::Set(EmbedderSource::OpenAi) | Setting::Reset => {
+                // regenerate on dimensions change in OpenAI, Cohere, Azure OpenAI and Bedrock since truncation is supported
+                Setting::Set(
+                    EmbedderSource::OpenAi
+                    | EmbedderSource::Cohere
+                    | EmbedderSource::AzureOpenAi
+                    | EmbedderSource::Bedrock,
+                )
+                | Setting::Reset => {
                     ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
                 }
                 // for all other embedders, the parameter is a hint that should not be able to change the result
@@ -922,8 +1017,8 @@ impl SettingsDiff {
         }
         if url.apply(new_url) {
             match *source {
-                // do not regenerate on an url change in OpenAI
-                Setting::Set(EmbedderSource::OpenAi) | Setting::Reset => {}
+                // do not regenerate on an url change in OpenAI and Cohere
+                Setting::Set(EmbedderSource::OpenAi | EmbedderSource::Cohere) | Setting::Reset => {}
                 _ => {
                     ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
                 }
@@ -953,6 +1048,11 @@ impl SettingsDiff {
             }
         }
 
+        // the rendered texts don't change with the chunk size, so their embeddings must all be regenerated
+        if document_template_chunk_bytes.apply(new_document_template_chunk_bytes) {
+            ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
+        }
+
         api_key.apply(new_api_key);
         headers.apply(new_headers);
     }
@@ -980,12 +1080,13 @@ fn apply_default_for_source(
     response: &mut Setting<serde_json::Value>,
     document_template: &mut Setting<String>,
     document_template_max_bytes: &mut Setting<usize>,
+    document_template_chunk_bytes: &mut Setting<usize>,
     headers: &mut Setting<BTreeMap<String, String>>,
     search_embedder: &mut Setting<SubEmbeddingSettings>,
     indexing_embedder: &mut Setting<SubEmbeddingSettings>,
 ) {
     match source {
-        Setting::Set(EmbedderSource::HuggingFace) => {
+        Setting::Set(EmbedderSource::HuggingFace | EmbedderSource::Onnx) => {
             *model = Setting::Reset;
             *revision = Setting::Reset;
             *pooling = Setting::Reset;
@@ -1021,6 +1122,42 @@ fn apply_default_for_source(
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
+        Setting::Set(EmbedderSource::Cohere) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *pooling = Setting::NotSet;
+            *dimensions = Setting::NotSet;
+            *url = Setting::Reset;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
+        Setting::Set(EmbedderSource::AzureOpenAi) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *pooling = Setting::NotSet;
+            *dimensions = Setting::Reset;
+            *url = Setting::Reset;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::Reset;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
+        Setting::Set(EmbedderSource::Bedrock) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *pooling = Setting::NotSet;
+            *dimensions = Setting::Reset;
+            *url = Setting::Reset;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
         Setting::Set(EmbedderSource::Rest) => {
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
@@ -1043,6 +1180,7 @@ fn apply_default_for_source(
             *response = Setting::NotSet;
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
+            *document_template_chunk_bytes = Setting::NotSet;
             *headers = Setting::NotSet;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
@@ -1057,6 +1195,7 @@ fn apply_default_for_source(
             *response = Setting::NotSet;
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
+            *document_template_chunk_bytes = Setting::NotSet;
             *headers = Setting::NotSet;
             *search_embedder = Setting::Reset;
             *indexing_embedder = Setting::Reset;
@@ -1118,6 +1257,7 @@ pub enum MetaEmbeddingSetting {
     Dimensions,
     DocumentTemplate,
     DocumentTemplateMaxBytes,
+    DocumentTemplateChunkBytes,
     Url,
     Request,
     Response,
@@ -1140,6 +1280,7 @@ impl MetaEmbeddingSetting {
             Dimensions => "dimensions",
             DocumentTemplate => "documentTemplate",
             DocumentTemplateMaxBytes => "documentTemplateMaxBytes",
+            DocumentTemplateChunkBytes => "documentTemplateChunkBytes",
             Url => "url",
             Request => "request",
             Response => "response",
@@ -1168,6 +1309,7 @@ impl EmbeddingSettings {
         response: &Setting<serde_json::Value>,
         document_template: &Setting<String>,
         document_template_max_bytes: &Setting<usize>,
+        document_template_chunk_bytes: &Setting<usize>,
         headers: &Setting<BTreeMap<String, String>>,
         search_embedder: &Setting<SubEmbeddingSettings>,
         indexing_embedder: &Setting<SubEmbeddingSettings>,
@@ -1226,6 +1368,13 @@ impl EmbeddingSettings {
             context,
             document_template_max_bytes,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::DocumentTemplateChunkBytes,
+            context,
+            document_template_chunk_bytes,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1322,7 +1471,11 @@ impl EmbeddingSettings {
         match (source, field, context) {
             (_, Distribution | BinaryQuantized, NotNested) => FieldStatus::Allowed,
             (_, Distribution | BinaryQuantized, _) => FieldStatus::Disallowed,
-            (_, DocumentTemplate | DocumentTemplateMaxBytes, Search) => FieldStatus::Disallowed,
+            (
+                _,
+                DocumentTemplate | DocumentTemplateMaxBytes | DocumentTemplateChunkBytes,
+                Search,
+            ) => FieldStatus::Disallowed,
             (
                 OpenAi,
                 Source
@@ -1330,6 +1483,7 @@ impl EmbeddingSettings {
                 | ApiKey
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
                 | Dimensions
                 | Url,
                 _,
@@ -1341,12 +1495,18 @@ impl EmbeddingSettings {
                 _,
             ) => FieldStatus::Disallowed,
             (
-                HuggingFace,
-                Source | Model | Revision | Pooling | DocumentTemplate | DocumentTemplateMaxBytes,
+                HuggingFace | Onnx,
+                Source
+                | Model
+                | Revision
+                | Pooling
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes,
                 _,
             ) => FieldStatus::Allowed,
             (
-                HuggingFace,
+                HuggingFace | Onnx,
                 ApiKey | Dimensions | Url | Request | Response | Headers | SearchEmbedder
                 | IndexingEmbedder,
                 _,
@@ -1354,7 +1514,13 @@ impl EmbeddingSettings {
             (Ollama, Model, _) => FieldStatus::Mandatory,
             (
                 Ollama,
-                Source | DocumentTemplate | DocumentTemplateMaxBytes | Url | ApiKey | Dimensions,
+                Source
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
+                | Url
+                | ApiKey
+                | Dimensions,
                 _,
             ) => FieldStatus::Allowed,
             (
@@ -1363,6 +1529,59 @@ impl EmbeddingSettings {
                 | IndexingEmbedder,
                 _,
             ) => FieldStatus::Disallowed,
+            (
+                Cohere,
+                Source
+                | Model
+                | ApiKey
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
+                | Dimensions
+                | Url,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                Cohere,
+                Revision | Pooling | Request | Response | Headers | SearchEmbedder
+                | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
+            (AzureOpenAi, Model | Url, _) => FieldStatus::Mandatory,
+            (
+                AzureOpenAi,
+                Source
+                | ApiKey
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
+                | Dimensions
+                | Headers,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                AzureOpenAi,
+                Revision | Pooling | Request | Response | SearchEmbedder | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
+            (Bedrock, Model, _) => FieldStatus::Mandatory,
+            (
+                Bedrock,
+                Source
+                | ApiKey
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
+                | Dimensions
+                | Url,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                Bedrock,
+                Revision | Pooling | Request | Response | Headers | SearchEmbedder
+                | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
             (UserProvided, Dimensions, _) => FieldStatus::Mandatory,
             (UserProvided, Source, _) => FieldStatus::Allowed,
             (
@@ -1373,6 +1592,7 @@ impl EmbeddingSettings {
                 | ApiKey
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
                 | Url
                 | Request
                 | Response
@@ -1389,6 +1609,7 @@ impl EmbeddingSettings {
                 | Dimensions
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
                 | Headers,
                 _,
             ) => FieldStatus::Allowed,
@@ -1406,6 +1627,7 @@ impl EmbeddingSettings {
                 | Dimensions
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunkBytes
                 | Url
                 | Request
                 | Response
@@ -1455,7 +1677,11 @@ impl EmbeddingSettings {
                 EmbedderSource::OpenAi
                 | EmbedderSource::HuggingFace
                 | EmbedderSource::Ollama
-                | EmbedderSource::Rest,
+                | EmbedderSource::Rest
+                | EmbedderSource::Cohere
+                | EmbedderSource::AzureOpenAi
+                | EmbedderSource::Bedrock
+                | EmbedderSource::Onnx,
             ) => Ok(()),
         }
     }
@@ -1484,6 +1710,10 @@ pub enum EmbedderSource {
     UserProvided,
     Rest,
     Composite,
+    Cohere,
+    AzureOpenAi,
+    Bedrock,
+    Onnx,
 }
 
 impl std::fmt::Display for EmbedderSource {
@@ -1495,6 +1725,10 @@ impl std::fmt::Display for EmbedderSource {
             EmbedderSource::Ollama => "ollama",
             EmbedderSource::Rest => "rest",
             EmbedderSource::Composite => "composite",
+            EmbedderSource::Cohere => "cohere",
+            EmbedderSource::AzureOpenAi => "azureOpenAi",
+            EmbedderSource::Bedrock => "bedrock",
+            EmbedderSource::Onnx => "onnx",
         };
         f.write_str(s)
     }
@@ -1510,6 +1744,7 @@ impl EmbeddingSettings {
     }: super::hf::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1521,6 +1756,35 @@ impl EmbeddingSettings {
             dimensions: Setting::NotSet,
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+        }
+    }
+
+    fn from_onnx(
+        super::onnx::EmbedderOptions { model, revision, distribution, pooling }: super::onnx::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Onnx),
+            model: Setting::Set(model),
+            revision: Setting::some_or_not_set(revision),
+            pooling: Setting::Set(pooling),
+            api_key: Setting::NotSet,
+            dimensions: Setting::NotSet,
+            document_template,
+            document_template_max_bytes,
+            document_template_chunk_bytes,
             url: Setting::NotSet,
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1542,6 +1806,7 @@ impl EmbeddingSettings {
         }: super::openai::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1553,6 +1818,7 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url: Setting::some_or_not_set(url),
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1574,6 +1840,7 @@ impl EmbeddingSettings {
         }: super::ollama::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1585,6 +1852,110 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
+            url: Setting::some_or_not_set(url),
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+        }
+    }
+
+    fn from_cohere(
+        super::cohere::EmbedderOptions {
+            url,
+            api_key,
+            embedding_model,
+            dimensions,
+            distribution,
+        }: super::cohere::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Cohere),
+            model: Setting::Set(embedding_model),
+            revision: Setting::NotSet,
+            pooling: Setting::NotSet,
+            api_key: Setting::some_or_not_set(api_key),
+            dimensions: Setting::some_or_not_set(dimensions),
+            document_template,
+            document_template_max_bytes,
+            document_template_chunk_bytes,
+            url: Setting::some_or_not_set(url),
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+        }
+    }
+
+    fn from_azure_openai(
+        super::azure_openai::EmbedderOptions {
+            url,
+            deployment,
+            api_key,
+            headers,
+            dimensions,
+            distribution,
+        }: super::azure_openai::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::AzureOpenAi),
+            model: Setting::Set(deployment),
+            revision: Setting::NotSet,
+            pooling: Setting::NotSet,
+            api_key: Setting::some_or_not_set(api_key),
+            dimensions: Setting::some_or_not_set(dimensions),
+            document_template,
+            document_template_max_bytes,
+            document_template_chunk_bytes,
+            url: Setting::Set(url),
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::Set(headers),
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+        }
+    }
+
+    fn from_bedrock(
+        super::bedrock::EmbedderOptions {
+            url,
+            api_key,
+            embedding_model,
+            dimensions,
+            distribution,
+        }: super::bedrock::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Bedrock),
+            model: Setting::Set(embedding_model),
+            revision: Setting::NotSet,
+            pooling: Setting::NotSet,
+            api_key: Setting::some_or_not_set(api_key),
+            dimensions: Setting::some_or_not_set(dimensions),
+            document_template,
+            document_template_max_bytes,
+            document_template_chunk_bytes,
             url: Setting::some_or_not_set(url),
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1609,6 +1980,7 @@ impl EmbeddingSettings {
             dimensions: Setting::Set(dimensions),
             document_template: Setting::NotSet,
             document_template_max_bytes: Setting::NotSet,
+            document_template_chunk_bytes: Setting::NotSet,
             url: Setting::NotSet,
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1632,6 +2004,7 @@ impl EmbeddingSettings {
         }: super::rest::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1643,6 +2016,7 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url: Setting::Set(url),
             request: Setting::Set(request),
             response: Setting::Set(response),
@@ -1660,23 +2034,28 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
         let EmbeddingConfig { embedder_options, prompt, quantized } = value;
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
+        let document_template_chunk_bytes =
+            Setting::some_or_not_set(prompt.chunk_bytes.map(NonZeroUsize::get));
         match embedder_options {
             super::EmbedderOptions::HuggingFace(options) => Self::from_hugging_face(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 quantized,
             ),
             super::EmbedderOptions::OpenAi(options) => Self::from_openai(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 quantized,
             ),
             super::EmbedderOptions::Ollama(options) => Self::from_ollama(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 quantized,
             ),
             super::EmbedderOptions::UserProvided(options) => {
@@ -1686,6 +2065,35 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunk_bytes,
+                quantized,
+            ),
+            super::EmbedderOptions::Cohere(options) => Self::from_cohere(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                document_template_chunk_bytes,
+                quantized,
+            ),
+            super::EmbedderOptions::AzureOpenAi(options) => Self::from_azure_openai(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                document_template_chunk_bytes,
+                quantized,
+            ),
+            super::EmbedderOptions::Bedrock(options) => Self::from_bedrock(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                document_template_chunk_bytes,
+                quantized,
+            ),
+            super::EmbedderOptions::Onnx(options) => Self::from_onnx(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                document_template_chunk_bytes,
                 quantized,
             ),
             super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
@@ -1701,6 +2109,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 binary_quantized: Setting::some_or_not_set(quantized),
                 document_template: Setting::NotSet,
                 document_template_max_bytes: Setting::NotSet,
+                document_template_chunk_bytes: Setting::NotSet,
                 url: Setting::NotSet,
                 request: Setting::NotSet,
                 response: Setting::NotSet,
@@ -1710,11 +2119,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                     search,
                     Setting::NotSet,
                     Setting::NotSet,
+                    Setting::NotSet,
                 )),
                 indexing_embedder: Setting::Set(SubEmbeddingSettings::from_options(
                     index,
                     Setting::Set(prompt.template),
                     document_template_max_bytes,
+                    document_template_chunk_bytes,
                 )),
             },
         }
@@ -1726,6 +2137,7 @@ impl SubEmbeddingSettings {
         options: SubEmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunk_bytes: Setting<usize>,
     ) -> Self {
         let settings = match options {
             SubEmbedderOptions::HuggingFace(embedder_options) => {
@@ -1733,6 +2145,7 @@ impl SubEmbeddingSettings {
                     embedder_options,
                     document_template,
                     document_template_max_bytes,
+                    document_template_chunk_bytes,
                     None,
                 )
             }
@@ -1740,12 +2153,14 @@ impl SubEmbeddingSettings {
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 None,
             ),
             SubEmbedderOptions::Ollama(embedder_options) => EmbeddingSettings::from_ollama(
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunk_bytes,
                 None,
             ),
             SubEmbedderOptions::UserProvided(embedder_options) => {
@@ -1755,6 +2170,37 @@ impl SubEmbeddingSettings {
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunk_bytes,
+                None,
+            ),
+            SubEmbedderOptions::Cohere(embedder_options) => EmbeddingSettings::from_cohere(
+                embedder_options,
+                document_template,
+                document_template_max_bytes,
+                document_template_chunk_bytes,
+                None,
+            ),
+            SubEmbedderOptions::AzureOpenAi(embedder_options) => {
+                EmbeddingSettings::from_azure_openai(
+                    embedder_options,
+                    document_template,
+                    document_template_max_bytes,
+                    document_template_chunk_bytes,
+                    None,
+                )
+            }
+            SubEmbedderOptions::Bedrock(embedder_options) => EmbeddingSettings::from_bedrock(
+                embedder_options,
+                document_template,
+                document_template_max_bytes,
+                document_template_chunk_bytes,
+                None,
+            ),
+            SubEmbedderOptions::Onnx(embedder_options) => EmbeddingSettings::from_onnx(
+                embedder_options,
+                document_template,
+                document_template_max_bytes,
+                document_template_chunk_bytes,
                 None,
             ),
         };
@@ -1773,6 +2219,7 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url,
             request,
             response,
@@ -1791,6 +2238,7 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url,
             request,
             response,
@@ -1815,6 +2263,7 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunk_bytes,
             url,
             request,
             response,
@@ -1826,18 +2275,25 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
         } = value;
 
         this.quantized = binary_quantized.set();
-        if let Some((template, document_template_max_bytes)) =
+        if let Some((template, document_template_max_bytes, document_template_chunk_bytes)) =
             match (document_template, &mut indexing_embedder) {
-                (Setting::Set(template), _) => Some((template, document_template_max_bytes)),
+                (Setting::Set(template), _) => {
+                    Some((template, document_template_max_bytes, document_template_chunk_bytes))
+                }
                 // retrieve the prompt from the indexing embedder in case of a composite embedder
                 (
                     _,
                     Setting::Set(SubEmbeddingSettings {
                         document_template: Setting::Set(document_template),
                         document_template_max_bytes,
+                        document_template_chunk_bytes,
                         ..
                     }),
-                ) => Some((std::mem::take(document_template), *document_template_max_bytes)),
+                ) => Some((
+                    std::mem::take(document_template),
+                    *document_template_max_bytes,
+                    *document_template_chunk_bytes,
+                )),
                 _ => None,
             }
         {
@@ -1846,7 +2302,9 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 .and_then(NonZeroUsize::new)
                 .unwrap_or(default_max_bytes());
 
-            this.prompt = PromptData { template, max_bytes: Some(max_bytes) }
+            let chunk_bytes = document_template_chunk_bytes.set().and_then(NonZeroUsize::new);
+
+            this.prompt = PromptData { template, max_bytes: Some(max_bytes), chunk_bytes }
         }
 
         if let Some(source) = source.set() {
@@ -1857,9 +2315,32 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 EmbedderSource::Ollama => {
                     SubEmbedderOptions::ollama(model, url, api_key, dimensions, distribution).into()
                 }
+                EmbedderSource::Cohere => {
+                    SubEmbedderOptions::cohere(model, url, api_key, dimensions, distribution).into()
+                }
+                EmbedderSource::Bedrock => SubEmbedderOptions::bedrock(
+                    model.set().unwrap(),
+                    url,
+                    api_key,
+                    dimensions,
+                    distribution,
+                )
+                .into(),
+                EmbedderSource::AzureOpenAi => SubEmbedderOptions::azure_openai(
+                    model.set().unwrap(),
+                    url.set().unwrap(),
+                    api_key,
+                    headers,
+                    dimensions,
+                    distribution,
+                )
+                .into(),
                 EmbedderSource::HuggingFace => {
                     SubEmbedderOptions::hugging_face(model, revision, pooling, distribution).into()
                 }
+                EmbedderSource::Onnx => {
+                    SubEmbedderOptions::onnx(model, revision, pooling, distribution).into()
+                }
                 EmbedderSource::UserProvided => {
                     SubEmbedderOptions::user_provided(dimensions.set().unwrap(), distribution)
                         .into()
@@ -1909,6 +2390,7 @@ impl SubEmbedderOptions {
             // retrieved by the EmbeddingConfig
             document_template: _,
             document_template_max_bytes: _,
+            document_template_chunk_bytes: _,
             url,
             request,
             response,
@@ -1925,7 +2407,20 @@ impl SubEmbedderOptions {
             EmbedderSource::HuggingFace => {
                 Self::hugging_face(model, revision, pooling, distribution)
             }
+            EmbedderSource::Onnx => Self::onnx(model, revision, pooling, distribution),
             EmbedderSource::Ollama => Self::ollama(model, url, api_key, dimensions, distribution),
+            EmbedderSource::Cohere => Self::cohere(model, url, api_key, dimensions, distribution),
+            EmbedderSource::Bedrock => {
+                Self::bedrock(model.set().unwrap(), url, api_key, dimensions, distribution)
+            }
+            EmbedderSource::AzureOpenAi => Self::azure_openai(
+                model.set().unwrap(),
+                url.set().unwrap(),
+                api_key,
+                headers,
+                dimensions,
+                distribution,
+            ),
             EmbedderSource::UserProvided => {
                 Self::user_provided(dimensions.set().unwrap(), distribution)
             }
@@ -1992,6 +2487,25 @@ impl SubEmbedderOptions {
         options.distribution = distribution.set();
         SubEmbedderOptions::HuggingFace(options)
     }
+    fn onnx(
+        model: Setting<String>,
+        revision: Setting<String>,
+        pooling: Setting<OverridePooling>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        let mut options = super::onnx::EmbedderOptions::default();
+        if let Some(model) = model.set() {
+            options.model = model;
+        }
+        if let Some(revision) = revision.set() {
+            options.revision = Some(revision);
+        }
+        if let Some(pooling) = pooling.set() {
+            options.pooling = pooling;
+        }
+        options.distribution = distribution.set();
+        SubEmbedderOptions::Onnx(options)
+    }
     fn user_provided(dimensions: usize, distribution: Setting<DistributionShift>) -> Self {
         Self::UserProvided(super::manual::EmbedderOptions {
             dimensions,
@@ -2037,6 +2551,55 @@ impl SubEmbedderOptions {
         options.distribution = distribution.set();
         SubEmbedderOptions::Ollama(options)
     }
+    fn cohere(
+        model: Setting<String>,
+        url: Setting<String>,
+        api_key: Setting<String>,
+        dimensions: Setting<usize>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        let mut options: cohere::EmbedderOptions =
+            super::cohere::EmbedderOptions::with_default_model(api_key.set());
+        if let Some(model) = model.set() {
+            options.embedding_model = model;
+        }
+        options.url = url.set();
+        options.dimensions = dimensions.set();
+        options.distribution = distribution.set();
+        SubEmbedderOptions::Cohere(options)
+    }
+    fn azure_openai(
+        deployment: String,
+        url: String,
+        api_key: Setting<String>,
+        headers: Setting<BTreeMap<String, String>>,
+        dimensions: Setting<usize>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        Self::AzureOpenAi(azure_openai::EmbedderOptions {
+            url,
+            deployment,
+            api_key: api_key.set(),
+            headers: headers.set().unwrap_or_default(),
+            dimensions: dimensions.set(),
+            distribution: distribution.set(),
+        })
+    }
+    fn bedrock(
+        model: String,
+        url: Setting<String>,
+        api_key: Setting<String>,
+        dimensions: Setting<usize>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        Self::Bedrock(bedrock::EmbedderOptions {
+            url: url.set(),
+            api_key: api_key.set(),
+            embedding_model: model,
+            dimensions: dimensions.set(),
+            distribution: distribution.set(),
+        })
+    }
 }
 
 impl From<SubEmbedderOptions> for EmbedderOptions {
@@ -2051,6 +2614,12 @@ impl From<SubEmbedderOptions> for EmbedderOptions {
                 Self::UserProvided(embedder_options)
             }
             SubEmbedderOptions::Rest(embedder_options) => Self::Rest(embedder_options),
+            SubEmbedderOptions::Cohere(embedder_options) => Self::Cohere(embedder_options),
+            SubEmbedderOptions::AzureOpenAi(embedder_options) => {
+                Self::AzureOpenAi(embedder_options)
+            }
+            SubEmbedderOptions::Bedrock(embedder_options) => Self::Bedrock(embedder_options),
+            SubEmbedderOptions::Onnx(embedder_options) => Self::Onnx(embedder_options),
         }
     }
 }
@@ -0,0 +1,124 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::EmbeddingConfigs;
+
+/// The requests sent to a remote embedder, to attribute the cost of the embedding provider.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbedderUsage {
+    /// Number of successful requests sent to the embedder.
+    pub requests: u64,
+    /// Number of texts embedded by these requests.
+    pub texts: u64,
+    /// Number of tokens billed for these requests, as reported by the embedder in its responses.
+    ///
+    /// Stays at `0` for the embedders that don't report the tokens they used.
+    pub tokens: u64,
+}
+
+impl EmbedderUsage {
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self {
+            requests: self.requests.saturating_sub(other.requests),
+            texts: self.texts.saturating_sub(other.texts),
+            tokens: self.tokens.saturating_sub(other.tokens),
+        }
+    }
+}
+
+impl AddAssign for EmbedderUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.requests += other.requests;
+        self.texts += other.texts;
+        self.tokens += other.tokens;
+    }
+}
+
+/// Counts the requests sent by an embedder for indexing purposes.
+#[derive(Debug, Default)]
+pub struct UsageCounter {
+    requests: AtomicU64,
+    texts: AtomicU64,
+    tokens: AtomicU64,
+}
+
+impl UsageCounter {
+    pub fn get(&self) -> EmbedderUsage {
+        EmbedderUsage {
+            requests: self.requests.load(Ordering::Relaxed),
+            texts: self.texts.load(Ordering::Relaxed),
+            tokens: self.tokens.load(Ordering::Relaxed),
+        }
+    }
+
+    fn add(&self, usage: EmbedderUsage) {
+        self.requests.fetch_add(usage.requests, Ordering::Relaxed);
+        self.texts.fetch_add(usage.texts, Ordering::Relaxed);
+        self.tokens.fetch_add(usage.tokens, Ordering::Relaxed);
+    }
+}
+
+thread_local! {
+    static SEARCH_USAGE: Cell<Option<EmbedderUsage>> = const { Cell::new(None) };
+}
+
+/// Runs `f` and returns the usage of the requests it sent to the embedders from the current thread.
+///
+/// The embedders are shared between the indexes and the searches run concurrently,
+/// so the usage of a search is measured on the thread running it instead of on the embedders.
+/// The requests measured this way are not counted in the indexing usage of the embedders.
+pub fn measure_search_usage<T>(f: impl FnOnce() -> T) -> (T, EmbedderUsage) {
+    let previous = SEARCH_USAGE.replace(Some(EmbedderUsage::default()));
+    let ret = f();
+    let usage = SEARCH_USAGE.replace(previous).unwrap_or_default();
+    (ret, usage)
+}
+
+/// Records a request either in the search being measured on this thread, or in the indexing `counter`.
+pub(super) fn record(counter: &UsageCounter, usage: EmbedderUsage) {
+    let measured = SEARCH_USAGE.with(|search_usage| match search_usage.get() {
+        Some(mut current) => {
+            current += usage;
+            search_usage.set(Some(current));
+            true
+        }
+        None => false,
+    });
+    if !measured {
+        counter.add(usage);
+    }
+}
+
+/// The indexing usage of the embedders, by embedder name.
+pub fn indexing_usage(embedders: &EmbeddingConfigs) -> BTreeMap<String, EmbedderUsage> {
+    embedders
+        .inner_as_ref()
+        .iter()
+        .map(|(name, (embedder, _, _))| (name.clone(), embedder.indexing_usage()))
+        .collect()
+}
+
+/// The usage of the embedders since the `before` snapshot of [`indexing_usage`], omitting the unused embedders.
+pub fn indexing_usage_since(
+    embedders: &EmbeddingConfigs,
+    before: &BTreeMap<String, EmbedderUsage>,
+) -> BTreeMap<String, EmbedderUsage> {
+    indexing_usage(embedders)
+        .into_iter()
+        .map(|(name, after)| {
+            let before = before.get(&name).copied().unwrap_or_default();
+            (name, after.saturating_sub(before))
+        })
+        .filter(|(_, usage)| !usage.is_empty())
+        .collect()
+}